@@ -0,0 +1,282 @@
+use chrono::{Duration, SecondsFormat, Utc};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use k8s_openapi::api::core::v1::{Event, Pod, Service};
+use kube::Client;
+use kube_health_reporter::metrics::base::{build_usage_map_from_http, PodUsageMap};
+use kube_health_reporter::metrics::{self, PodMetricsItem};
+use kube_health_reporter::Config;
+use mockito::{Matcher, Server, ServerGuard};
+
+const NAMESPACE: &str = "bench";
+const POD_COUNTS: &[usize] = &[1_000, 10_000, 50_000];
+const NODE_COUNT: usize = 500;
+/// Pods of the cluster-wide list behind the node utilization benchmark
+const CLUSTER_POD_COUNT: usize = 10_000;
+const WORKLOADS: usize = 200;
+
+fn timestamp(minutes_ago: i64) -> String {
+    (Utc::now() - Duration::minutes(minutes_ago)).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Pod `i` of a synthetic namespace: mostly healthy, every 10th restarting after an OOM
+/// kill, every 25th Pending, every 40th unready and every 100th Failed, spread over
+/// `WORKLOADS` ReplicaSets and `NODE_COUNT` nodes
+fn synthetic_pod_json(i: usize) -> serde_json::Value {
+    let workload = format!("app-{}", i % WORKLOADS);
+    let phase = if i.is_multiple_of(100) {
+        "Failed"
+    } else if i.is_multiple_of(25) {
+        "Pending"
+    } else {
+        "Running"
+    };
+    let ready = !i.is_multiple_of(40);
+    let restart_count = if i.is_multiple_of(10) { 5 } else { 0 };
+    let last_state = if i.is_multiple_of(10) {
+        serde_json::json!({"terminated": {"reason": "OOMKilled", "exitCode": 137, "finishedAt": timestamp(2)}})
+    } else {
+        serde_json::json!({})
+    };
+    serde_json::json!({
+        "metadata": {
+            "name": format!("{}-{}", workload, i),
+            "namespace": NAMESPACE,
+            "labels": {"app": workload},
+            "creationTimestamp": timestamp(60),
+            "ownerReferences": [{
+                "apiVersion": "apps/v1",
+                "kind": "ReplicaSet",
+                "name": format!("{}-7d9f8", workload),
+                "uid": format!("uid-{}", i % WORKLOADS),
+                "controller": true
+            }]
+        },
+        "spec": {
+            "nodeName": format!("node-{}", i % NODE_COUNT),
+            "containers": [{
+                "name": "main",
+                "image": "example/app:1.0",
+                "resources": {
+                    "requests": {"cpu": "100m", "memory": "128Mi"},
+                    "limits": {"cpu": "500m", "memory": "256Mi"}
+                },
+                "envFrom": [{"configMapRef": {"name": format!("{}-config", workload)}}]
+            }]
+        },
+        "status": {
+            "phase": phase,
+            "startTime": timestamp(60),
+            "reason": if phase == "Failed" { "Evicted" } else { "" },
+            "conditions": [
+                {"type": "Ready", "status": if ready { "True" } else { "False" }, "lastTransitionTime": timestamp(30)},
+                {"type": "PodScheduled", "status": if phase == "Pending" { "False" } else { "True" }, "lastTransitionTime": timestamp(30)}
+            ],
+            "containerStatuses": [{
+                "name": "main",
+                "image": "example/app:1.0",
+                "imageID": "",
+                "ready": ready,
+                "restartCount": restart_count,
+                "lastState": last_state
+            }]
+        }
+    })
+}
+
+fn synthetic_pods(count: usize) -> Vec<Pod> {
+    (0..count).map(|i| serde_json::from_value(synthetic_pod_json(i)).unwrap()).collect()
+}
+
+/// Usage samples putting every 7th pod above the heavy usage threshold
+fn synthetic_usage(count: usize) -> PodUsageMap {
+    let items: Vec<PodMetricsItem> = (0..count)
+        .map(|i| {
+            let cpu = if i % 7 == 0 { "120m" } else { "50m" };
+            serde_json::from_value(serde_json::json!({
+                "metadata": {"name": format!("app-{}-{}", i % WORKLOADS, i), "namespace": NAMESPACE},
+                "timestamp": timestamp(0),
+                "window": "30s",
+                "containers": [{"name": "main", "usage": {"cpu": cpu, "memory": "64Mi"}}]
+            }))
+            .unwrap()
+        })
+        .collect();
+    build_usage_map_from_http(items)
+}
+
+/// A Warning event per 20 pods, half of them about missing ConfigMaps
+fn synthetic_events(count: usize) -> Vec<Event> {
+    (0..count / 20)
+        .map(|i| {
+            let pod = format!("app-{}-{}", (i * 20) % WORKLOADS, i * 20);
+            let (reason, message) = if i % 2 == 0 {
+                ("FailedMount", format!("configmap \"app-{}-config\" not found", (i * 20) % WORKLOADS))
+            } else {
+                ("BackOff", "Back-off restarting failed container".to_string())
+            };
+            serde_json::from_value(serde_json::json!({
+                "metadata": {"name": format!("{}.{}", pod, i), "namespace": NAMESPACE},
+                "involvedObject": {"kind": "Pod", "name": pod, "namespace": NAMESPACE},
+                "type": "Warning",
+                "reason": reason,
+                "message": message,
+                "count": 3,
+                "firstTimestamp": timestamp(20),
+                "lastTimestamp": timestamp(1)
+            }))
+            .unwrap()
+        })
+        .collect()
+}
+
+fn synthetic_services() -> Vec<Service> {
+    (0..WORKLOADS)
+        .map(|i| {
+            serde_json::from_value(serde_json::json!({
+                "metadata": {"name": format!("app-{}", i), "namespace": NAMESPACE},
+                "spec": {"selector": {"app": format!("app-{}", i)}, "ports": [{"port": 80}]}
+            }))
+            .unwrap()
+        })
+        .collect()
+}
+
+fn bench_config() -> Config {
+    Config {
+        namespaces: vec![NAMESPACE.to_string()],
+        threshold_percent: 85.0,
+        fail_if_no_metrics: false,
+        ..Default::default()
+    }
+}
+
+fn pod_analyzers_benchmark(c: &mut Criterion) {
+    let config = bench_config();
+    let services = synthetic_services();
+    let mut group = c.benchmark_group("pod_analyzers");
+    group.sample_size(10);
+
+    for &count in POD_COUNTS {
+        let pods = synthetic_pods(count);
+        let usage = synthetic_usage(count);
+        let events = synthetic_events(count);
+        group.throughput(Throughput::Elements(count as u64));
+
+        group.bench_with_input(BenchmarkId::new("heavy_usage", count), &pods, |b, pods| {
+            b.iter(|| metrics::pods::analyze_heavy_usage_with_usage(NAMESPACE, &config, black_box(pods), &usage))
+        });
+        group.bench_with_input(BenchmarkId::new("restarts", count), &pods, |b, pods| {
+            b.iter(|| metrics::pods::analyze_restarts_with_pods(NAMESPACE, &config, black_box(pods)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("pending_failed_unready", count), &pods, |b, pods| {
+            b.iter(|| {
+                (
+                    metrics::pods::analyze_pending_pods_with_pods(NAMESPACE, &config, black_box(pods)),
+                    metrics::pods::analyze_failed_pods_with_pods(NAMESPACE, &config, pods),
+                    metrics::pods::analyze_unready_pods_with_pods(NAMESPACE, &config, pods),
+                )
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("oom_killed", count), &pods, |b, pods| {
+            b.iter(|| metrics::pods::analyze_oom_killed_with_pods(NAMESPACE, &config, black_box(pods)))
+        });
+        group.bench_with_input(BenchmarkId::new("resource_hygiene", count), &pods, |b, pods| {
+            b.iter(|| metrics::pods::analyze_resource_hygiene_with_pods(NAMESPACE, &config, black_box(pods)))
+        });
+        group.bench_with_input(BenchmarkId::new("pod_security", count), &pods, |b, pods| {
+            b.iter(|| metrics::analyze_pod_security_with_pods(NAMESPACE, &config, black_box(pods)))
+        });
+        group.bench_with_input(BenchmarkId::new("missing_config_refs", count), &pods, |b, pods| {
+            b.iter(|| metrics::pods::analyze_missing_config_refs_with_pods(NAMESPACE, black_box(pods), &events))
+        });
+        group.bench_with_input(BenchmarkId::new("pod_churn", count), &pods, |b, pods| {
+            b.iter(|| metrics::churn::analyze_pod_churn(NAMESPACE, &config, black_box(pods), &events, Utc::now()))
+        });
+        group.bench_with_input(BenchmarkId::new("blast_radius", count), &pods, |b, pods| {
+            b.iter(|| metrics::services::analyze_blast_radius(&services, &[], black_box(pods)))
+        });
+        group.bench_with_input(BenchmarkId::new("workload_usage", count), &pods, |b, pods| {
+            b.iter(|| metrics::recommendations::workload_usage_with_usage(NAMESPACE, black_box(pods), &usage))
+        });
+    }
+    group.finish();
+}
+
+/// Node `i`: every 10th under MemoryPressure, every 5th above 90% CPU
+fn node_list_json() -> String {
+    let items: Vec<serde_json::Value> = (0..NODE_COUNT)
+        .map(|i| {
+            serde_json::json!({
+                "metadata": {"name": format!("node-{}", i), "labels": {"kubernetes.io/os": "linux"}},
+                "status": {
+                    "capacity": {"cpu": "8", "memory": "32Gi", "pods": "110"},
+                    "allocatable": {"cpu": "8", "memory": "32Gi", "pods": "110"},
+                    "conditions": [
+                        {"type": "Ready", "status": "True", "lastTransitionTime": timestamp(600)},
+                        {"type": "MemoryPressure", "status": if i % 10 == 0 { "True" } else { "False" }, "lastTransitionTime": timestamp(30)}
+                    ]
+                }
+            })
+        })
+        .collect();
+    serde_json::json!({"apiVersion": "v1", "kind": "NodeList", "metadata": {}, "items": items}).to_string()
+}
+
+fn node_metrics_json() -> String {
+    let items: Vec<serde_json::Value> = (0..NODE_COUNT)
+        .map(|i| {
+            serde_json::json!({
+                "metadata": {"name": format!("node-{}", i)},
+                "usage": {"cpu": if i % 5 == 0 { "7500m" } else { "2" }, "memory": "12Gi"}
+            })
+        })
+        .collect();
+    serde_json::json!({"items": items}).to_string()
+}
+
+fn cluster_pod_list_json() -> String {
+    let items: Vec<serde_json::Value> = (0..CLUSTER_POD_COUNT).map(synthetic_pod_json).collect();
+    serde_json::json!({"apiVersion": "v1", "kind": "PodList", "metadata": {}, "items": items}).to_string()
+}
+
+fn mock_cluster_server() -> ServerGuard {
+    let mut server = Server::new();
+    let routes = [
+        ("^/api/v1/nodes", node_list_json()),
+        ("^/apis/metrics.k8s.io/v1beta1/nodes", node_metrics_json()),
+        ("^/api/v1/pods", cluster_pod_list_json()),
+    ];
+    for (path, body) in routes {
+        server
+            .mock("GET", Matcher::Regex(path.to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create();
+    }
+    server
+}
+
+fn node_analyzers_benchmark(c: &mut Criterion) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let server = mock_cluster_server();
+    let client = {
+        let _guard = rt.enter();
+        Client::try_from(kube::Config::new(server.url().parse().unwrap())).unwrap()
+    };
+    let namespaces = vec![NAMESPACE.to_string()];
+    let mut group = c.benchmark_group("node_analyzers");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(NODE_COUNT as u64));
+
+    group.bench_function(BenchmarkId::new("problematic_nodes", NODE_COUNT), |b| {
+        b.iter(|| rt.block_on(metrics::analyze_problematic_nodes(&client)).unwrap())
+    });
+    group.bench_function(BenchmarkId::new("node_utilization", NODE_COUNT), |b| {
+        b.iter(|| rt.block_on(metrics::analyze_node_utilization(&client, 85.0, &namespaces, true)).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, pod_analyzers_benchmark, node_analyzers_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,98 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use kube::Client;
+use kube_health_reporter::{Config, MetricsCollector};
+use mockito::{Matcher, Server, ServerGuard};
+
+const NAMESPACE: &str = "bench";
+const POD_COUNT: usize = 500;
+
+fn pod_list_json(count: usize) -> String {
+    let items: Vec<serde_json::Value> = (0..count)
+        .map(|i| {
+            serde_json::json!({
+                "metadata": {"name": format!("pod-{}", i), "namespace": NAMESPACE},
+                "spec": {"containers": [{
+                    "name": "main",
+                    "resources": {"requests": {"cpu": "100m", "memory": "128Mi"}}
+                }]},
+                "status": {
+                    "phase": "Running",
+                    "startTime": "2024-01-01T00:00:00Z",
+                    "conditions": [{"type": "Ready", "status": "True"}],
+                    "containerStatuses": [{
+                        "name": "main",
+                        "image": "example/app:1.0",
+                        "imageID": "",
+                        "ready": true,
+                        "restartCount": 0
+                    }]
+                }
+            })
+        })
+        .collect();
+    serde_json::json!({"apiVersion": "v1", "kind": "PodList", "metadata": {}, "items": items}).to_string()
+}
+
+fn pod_metrics_json(count: usize) -> String {
+    let items: Vec<serde_json::Value> = (0..count)
+        .map(|i| {
+            serde_json::json!({
+                "metadata": {"name": format!("pod-{}", i), "namespace": NAMESPACE},
+                "containers": [{"name": "main", "usage": {"cpu": "50m", "memory": "64Mi"}}]
+            })
+        })
+        .collect();
+    serde_json::json!({"items": items}).to_string()
+}
+
+fn empty_list_json(kind: &str) -> String {
+    serde_json::json!({"apiVersion": "batch/v1", "kind": kind, "metadata": {}, "items": []}).to_string()
+}
+
+fn mock_api_server() -> ServerGuard {
+    let mut server = Server::new();
+    let routes = [
+        (format!("^/api/v1/namespaces/{}/pods", NAMESPACE), pod_list_json(POD_COUNT)),
+        (format!("^/apis/metrics.k8s.io/v1beta1/namespaces/{}/pods", NAMESPACE), pod_metrics_json(POD_COUNT)),
+        (format!("^/apis/batch/v1/namespaces/{}/jobs", NAMESPACE), empty_list_json("JobList")),
+        (format!("^/apis/batch/v1/namespaces/{}/cronjobs", NAMESPACE), empty_list_json("CronJobList")),
+    ];
+    for (path, body) in routes {
+        server
+            .mock("GET", Matcher::Regex(path))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create();
+    }
+    server
+}
+
+fn collection_benchmark(c: &mut Criterion) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let server = mock_api_server();
+    let client = {
+        let _guard = rt.enter();
+        let kube_config = kube::Config::new(server.url().parse().unwrap());
+        Client::try_from(kube_config).unwrap()
+    };
+    let config = Config {
+        namespaces: vec![NAMESPACE.to_string()],
+        threshold_percent: 85.0,
+        slack_webhook_url: "https://hooks.slack.com/bench".to_string(),
+        restart_grace_minutes: 5,
+        pending_grace_minutes: 5,
+        cluster_name: None,
+        datacenter_name: None,
+        fail_if_no_metrics: false,
+        ..Default::default()
+    };
+    let collector = MetricsCollector::new(&client, &config);
+
+    c.bench_function("collect_namespace_metrics", |b| {
+        b.iter(|| rt.block_on(collector.collect_namespace_metrics(NAMESPACE)).unwrap())
+    });
+}
+
+criterion_group!(benches, collection_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,471 @@
+{{- /*
+Reporter CronJob. Called with a dict: root (the chart context), tenant (name from
+.Values.tenants, empty for the deployment-wide job) and schedule.
+*/ -}}
+{{- define "kube-health-reporter.cronjob" -}}
+{{- $tenant := .tenant -}}
+{{- $schedule := .schedule -}}
+{{- with .root -}}
+apiVersion: batch/v1
+kind: CronJob
+metadata:
+  name: {{ include "kube-health-reporter.fullname" . }}{{ with $tenant }}-{{ . }}{{ end }}
+  labels:
+    {{- include "kube-health-reporter.labels" . | nindent 4 }}
+    {{- with $tenant }}
+    kube-health-reporter.io/tenant: {{ . }}
+    {{- end }}
+spec:
+  schedule: {{ $schedule | quote }}
+  concurrencyPolicy: {{ .Values.cron.concurrencyPolicy }}
+  successfulJobsHistoryLimit: {{ .Values.cron.successfulJobsHistoryLimit }}
+  failedJobsHistoryLimit: {{ .Values.cron.failedJobsHistoryLimit }}
+  jobTemplate:
+    spec:
+      template:
+        metadata:
+          labels:
+            {{- include "kube-health-reporter.selectorLabels" . | nindent 12 }}
+        spec:
+          serviceAccountName: {{ include "kube-health-reporter.serviceAccountName" . }}
+          restartPolicy: Never
+          containers:
+            - name: reporter
+              image: "{{ .Values.image.repository }}:{{ .Values.image.tag }}"
+              imagePullPolicy: {{ .Values.image.pullPolicy }}
+              env:
+                - name: NAMESPACES
+                  value: {{ join "," .Values.namespaces | quote }}
+                {{- with $tenant }}
+                - name: TENANT
+                  value: {{ . | quote }}
+                - name: TENANTS_FILE
+                  value: /etc/kube-health-reporter-tenants/tenants.json
+                {{- end }}
+                - name: NAMESPACE_TIMEOUT_SECONDS
+                  value: {{ .Values.collection.namespaceTimeoutSeconds | quote }}
+                - name: RUN_TIMEOUT_SECONDS
+                  value: {{ .Values.collection.runTimeoutSeconds | quote }}
+                - name: MEMORY_WARN_PERCENT
+                  value: {{ .Values.collection.memoryWarnPercent | quote }}
+                - name: THRESHOLD_PERCENT
+                  value: {{ .Values.thresholdPercent | quote }}
+                - name: RESTART_GRACE_MINUTES
+                  value: {{ .Values.restartGraceMinutes | quote }}
+                - name: PENDING_GRACE_MINUTES
+                  value: {{ .Values.pendingGraceMinutes | quote }}
+                - name: TERMINATING_GRACE_MINUTES
+                  value: {{ .Values.terminatingGraceMinutes | quote }}
+                - name: LIMIT_REQUEST_RATIO_THRESHOLD
+                  value: {{ .Values.limitRequestRatioThreshold | quote }}
+                - name: POD_CHURN_WINDOW_MINUTES
+                  value: {{ .Values.podChurnWindowMinutes | quote }}
+                - name: POD_CHURN_THRESHOLD
+                  value: {{ .Values.podChurnThreshold | quote }}
+                - name: EVENT_FLOOD_WINDOW_MINUTES
+                  value: {{ .Values.eventFloodWindowMinutes | quote }}
+                - name: EVENT_FLOOD_THRESHOLD
+                  value: {{ .Values.eventFloodThreshold | quote }}
+                - name: JOB_BACKOFF_WARNING_PERCENT
+                  value: {{ .Values.jobBackoffWarningPercent | quote }}
+                - name: CRONJOB_ACTIVE_JOBS_THRESHOLD
+                  value: {{ .Values.cronjobActiveJobsThreshold | quote }}
+                - name: FINISHED_OBJECTS_THRESHOLD
+                  value: {{ .Values.finishedObjectsThreshold | quote }}
+                - name: READINESS_FLAP_WINDOW_MINUTES
+                  value: {{ .Values.readinessFlapWindowMinutes | quote }}
+                - name: READINESS_FLAP_THRESHOLD
+                  value: {{ .Values.readinessFlapThreshold | quote }}
+                - name: OBJECT_COUNT_CHECK
+                  value: {{ ternary "true" "false" .Values.objectCounts.enabled | quote }}
+                - name: OBJECT_COUNT_THRESHOLD
+                  value: {{ .Values.objectCounts.threshold | int64 | quote }}
+                - name: OBJECT_COUNT_GROWTH_PERCENT
+                  value: {{ .Values.objectCounts.growthPercent | quote }}
+                - name: NODE_FLAP_WINDOW_MINUTES
+                  value: {{ .Values.nodeFlapWindowMinutes | quote }}
+                - name: NODE_FLAP_THRESHOLD
+                  value: {{ .Values.nodeFlapThreshold | quote }}
+                - name: ENDPOINT_CHURN_WINDOW_MINUTES
+                  value: {{ .Values.endpointChurnWindowMinutes | quote }}
+                - name: ENDPOINT_CHURN_THRESHOLD
+                  value: {{ .Values.endpointChurnThreshold | quote }}
+                - name: DUAL_STACK_CHECK
+                  value: {{ .Values.dualStackCheck | quote }}
+                - name: HEALTH_SUPPRESSIONS
+                  value: {{ .Values.suppressions.enabled | quote }}
+                {{- with .Values.changeFreeze.source }}
+                - name: CHANGE_FREEZE_SOURCE
+                  value: {{ . | quote }}
+                {{- end }}
+                - name: SINK_TIMEOUT_SECONDS
+                  value: {{ .Values.sinks.timeoutSeconds | quote }}
+                - name: SINK_FAILURE_THRESHOLD
+                  value: {{ .Values.sinks.failureThreshold | quote }}
+                - name: SINK_COOLDOWN_MINUTES
+                  value: {{ .Values.sinks.cooldownMinutes | quote }}
+                {{- with .Values.sinks.httpsProxy }}
+                - name: HTTPS_PROXY
+                  value: {{ . | quote }}
+                {{- end }}
+                {{- with .Values.sinks.noProxy }}
+                - name: NO_PROXY
+                  value: {{ . | quote }}
+                {{- end }}
+                {{- if .Values.redactionPatterns }}
+                - name: REDACTION_PATTERNS
+                  value: {{ join "\n" .Values.redactionPatterns | toJson }}
+                {{- end }}
+                {{- if .Values.sinks.signing.secretName }}
+                - name: WEBHOOK_SIGNING_SECRET
+                  valueFrom:
+                    secretKeyRef:
+                      name: {{ .Values.sinks.signing.secretName }}
+                      key: {{ .Values.sinks.signing.secretKey }}
+                - name: WEBHOOK_SIGNING_ALGORITHM
+                  value: {{ .Values.sinks.signing.algorithm | quote }}
+                {{- end }}
+                {{- if .Values.sinks.caBundle.configMap }}
+                - name: WEBHOOK_CA_BUNDLE
+                  value: /etc/kube-health-reporter-ca/{{ .Values.sinks.caBundle.key }}
+                {{- end }}
+                - name: POD_SECURITY_LEVEL
+                  value: {{ .Values.podSecurity.level | quote }}
+                {{- with .Values.podSecurity.namespaceLevels }}
+                {{- $levels := list }}
+                {{- range $ns, $level := . }}
+                {{- $levels = append $levels (printf "%s=%s" $ns $level) }}
+                {{- end }}
+                - name: POD_SECURITY_NAMESPACE_LEVELS
+                  value: {{ join "," $levels | quote }}
+                {{- end }}
+                - name: LIMIT_RANGE_EDGE_PERCENT
+                  value: {{ .Values.limitRangeEdgePercent | quote }}
+                {{- with .Values.customResourceChecks }}
+                {{- $checks := list }}
+                {{- range . }}
+                {{- $conditions := list }}
+                {{- range $type, $status := .conditions }}
+                {{- $conditions = append $conditions (printf "%s=%s" $type $status) }}
+                {{- end }}
+                {{- $resource := printf "%s/%s" .apiGroup .kind }}
+                {{- if .version }}
+                {{- $resource = printf "%s/%s/%s" .apiGroup .version .kind }}
+                {{- end }}
+                {{- $checks = append $checks (printf "%s: %s" $resource (join ", " $conditions)) }}
+                {{- end }}
+                - name: CUSTOM_RESOURCE_CHECKS
+                  value: {{ join "; " $checks | quote }}
+                {{- end }}
+                - name: NAMESPACE_QUOTA_CHECK
+                  value: {{ ternary "true" "false" .Values.namespaceQuotaCheck | quote }}
+                {{- if .Values.ownership }}
+                - name: OWNERSHIP_FILE
+                  value: /etc/kube-health-reporter/ownership.json
+                {{- end }}
+                {{- if .Values.issueLabels }}
+                - name: ISSUE_LABELS
+                  value: {{ join "," .Values.issueLabels | quote }}
+                {{- end }}
+                - name: CRASH_LOG_LINES
+                  value: {{ .Values.crashLogLines | quote }}
+                - name: OMIT_EMPTY_SECTIONS
+                  value: {{ ternary "true" "false" .Values.omitEmptySections | quote }}
+                {{- if .Values.sectionOrder }}
+                - name: SECTION_ORDER
+                  value: {{ join "," .Values.sectionOrder | quote }}
+                {{- end }}
+                - name: PRIORITY_TOP_N
+                  value: {{ .Values.priorityTopN | quote }}
+                {{- if .Values.severityRules }}
+                - name: SEVERITY_RULES
+                  value: {{ join "," .Values.severityRules | quote }}
+                {{- end }}
+                - name: NETWORK_AGENT_DAEMONSETS
+                  value: {{ join "," .Values.networkAgentDaemonSets | quote }}
+                - name: OPERATOR_CHECK
+                  value: {{ ternary "true" "false" .Values.operatorCheck | quote }}
+                - name: FAIL_IF_NO_METRICS
+                  value: {{ ternary "true" "false" .Values.failIfNoMetrics | quote }}
+                {{- if eq .Values.rbac.scope "namespace" }}
+                - name: RBAC_SCOPE
+                  value: "namespace"
+                {{- end }}
+                - name: METRICS_STALENESS_MINUTES
+                  value: {{ .Values.metricsStalenessMinutes | quote }}
+                - name: NODE_POD_COUNT_ALL_NAMESPACES
+                  value: {{ ternary "true" "false" .Values.nodePodCountAllNamespaces | quote }}
+                - name: NODE_DISK_FORECAST
+                  value: {{ ternary "true" "false" .Values.nodeDisk.forecast | quote }}
+                - name: NODE_DISK_MARGIN_PERCENT
+                  value: {{ .Values.nodeDisk.marginPercent | quote }}
+                {{- if .Values.clusterName }}
+                - name: CLUSTER_NAME
+                  value: {{ .Values.clusterName | quote }}
+                {{- end }}
+                {{- if .Values.datacenterName }}
+                - name: DATACENTER_NAME
+                  value: {{ .Values.datacenterName | quote }}
+                {{- end }}
+                {{- if and .Values.federation.clusters (not $tenant) }}
+                - name: CLUSTERS
+                  value: {{ join "," .Values.federation.clusters | quote }}
+                - name: KUBECONFIG
+                  value: /etc/kube-health-reporter-kubeconfig/config
+                {{- if .Values.federation.webhookSecretName }}
+                - name: FEDERATION_WEBHOOK_URL
+                  valueFrom:
+                    secretKeyRef:
+                      name: {{ .Values.federation.webhookSecretName }}
+                      key: {{ .Values.federation.webhookSecretKey }}
+                {{- end }}
+                {{- end }}
+                {{- if .Values.recommendations.enabled }}
+                - name: RECOMMENDATIONS_HISTORY_PATH
+                  value: /var/lib/kube-health-reporter/usage-history{{ with $tenant }}-{{ . }}{{ end }}.json
+                {{- end }}
+                {{- if .Values.state.enabled }}
+                - name: STATE_FILE
+                  value: /var/lib/kube-health-reporter/state{{ with $tenant }}-{{ . }}{{ end }}.json
+                - name: NOTIFY_RESOLVED
+                  value: {{ ternary "true" "false" .Values.state.notifyResolved | quote }}
+                {{- if .Values.state.auditLog }}
+                - name: AUDIT_LOG_FILE
+                  value: /var/lib/kube-health-reporter/audit{{ with $tenant }}-{{ . }}{{ end }}.jsonl
+                {{- end }}
+                {{- end }}
+                {{- if .Values.allowApiWrites }}
+                - name: ALLOW_API_WRITES
+                  value: "true"
+                {{- end }}
+                - name: SECRETS_CACHE_SECONDS
+                  value: {{ .Values.secretManager.cacheSeconds | quote }}
+                {{- with .Values.secretManager.vault }}
+                {{- if .addr }}
+                - name: VAULT_ADDR
+                  value: {{ .addr | quote }}
+                - name: VAULT_K8S_ROLE
+                  value: {{ .role | quote }}
+                - name: VAULT_K8S_MOUNT
+                  value: {{ .mount | quote }}
+                {{- if .namespace }}
+                - name: VAULT_NAMESPACE
+                  value: {{ .namespace | quote }}
+                {{- end }}
+                {{- end }}
+                {{- end }}
+                {{- with .Values.secretManager.aws.region }}
+                - name: AWS_REGION
+                  value: {{ . | quote }}
+                {{- end }}
+                {{- with .Values.email.smtpHost }}
+                - name: SMTP_HOST
+                  value: {{ . | quote }}
+                - name: SMTP_TLS
+                  value: {{ $.Values.email.tls | quote }}
+                {{- with $.Values.email.smtpPort }}
+                - name: SMTP_PORT
+                  value: {{ . | quote }}
+                {{- end }}
+                {{- with $.Values.email.username }}
+                - name: SMTP_USERNAME
+                  value: {{ . | quote }}
+                {{- end }}
+                {{- if $.Values.email.passwordSecretName }}
+                - name: SMTP_PASSWORD
+                  valueFrom:
+                    secretKeyRef:
+                      name: {{ $.Values.email.passwordSecretName }}
+                      key: {{ $.Values.email.passwordSecretKey }}
+                {{- end }}
+                - name: EMAIL_FROM
+                  value: {{ required "email.from is required with email.smtpHost" $.Values.email.from | quote }}
+                - name: EMAIL_TO
+                  value: {{ join "," $.Values.email.to | quote }}
+                {{- if $.Values.sinks.caBundle.configMap }}
+                - name: SMTP_CA_BUNDLE
+                  value: /etc/kube-health-reporter-ca/{{ $.Values.sinks.caBundle.key }}
+                {{- end }}
+                {{- end }}
+                {{- if .Values.telegram.botTokenSecretName }}
+                - name: TELEGRAM_BOT_TOKEN
+                  valueFrom:
+                    secretKeyRef:
+                      name: {{ .Values.telegram.botTokenSecretName }}
+                      key: {{ .Values.telegram.botTokenSecretKey }}
+                - name: TELEGRAM_CHAT_ID
+                  value: {{ required "telegram.chatId is required with telegram.botTokenSecretName" .Values.telegram.chatId | quote }}
+                {{- end }}
+                {{- if .Values.pushgateway.url }}
+                - name: PUSHGATEWAY_URL
+                  value: {{ .Values.pushgateway.url | quote }}
+                - name: PUSHGATEWAY_JOB
+                  value: {{ .Values.pushgateway.job | quote }}
+                {{- with .Values.pushgateway.instance }}
+                - name: PUSHGATEWAY_INSTANCE
+                  value: {{ . | quote }}
+                {{- end }}
+                {{- end }}
+                {{- with .Values.hooks.preCollection }}
+                - name: HOOK_PRE_COLLECTION
+                  value: {{ . | quote }}
+                {{- end }}
+                {{- with .Values.hooks.postReport }}
+                - name: HOOK_POST_REPORT
+                  value: {{ . | quote }}
+                {{- end }}
+                {{- with .Values.hooks.preSend }}
+                - name: HOOK_PRE_SEND
+                  value: {{ . | quote }}
+                {{- end }}
+                {{- if or .Values.hooks.preCollection .Values.hooks.postReport .Values.hooks.preSend }}
+                - name: HOOK_TIMEOUT_SECONDS
+                  value: {{ .Values.hooks.timeoutSeconds | quote }}
+                {{- end }}
+                {{- if .Values.pagerduty.routingKeySecretName }}
+                - name: PAGERDUTY_ROUTING_KEY
+                  valueFrom:
+                    secretKeyRef:
+                      name: {{ .Values.pagerduty.routingKeySecretName }}
+                      key: {{ .Values.pagerduty.routingKeySecretKey }}
+                - name: PAGERDUTY_SECTIONS
+                  value: {{ required "pagerduty.sections is required with pagerduty.routingKeySecretName" (join "," .Values.pagerduty.sections) | quote }}
+                {{- end }}
+                {{- if .Values.genericWebhook.urlSecretName }}
+                - name: GENERIC_WEBHOOK_URL
+                  valueFrom:
+                    secretKeyRef:
+                      name: {{ .Values.genericWebhook.urlSecretName }}
+                      key: {{ .Values.genericWebhook.urlSecretKey }}
+                {{- end }}
+                {{- if .Values.genericWebhook.headersSecretName }}
+                - name: GENERIC_WEBHOOK_HEADERS
+                  valueFrom:
+                    secretKeyRef:
+                      name: {{ .Values.genericWebhook.headersSecretName }}
+                      key: {{ .Values.genericWebhook.headersSecretKey }}
+                {{- end }}
+                {{- if .Values.openMetrics.textfileHostPath }}
+                - name: METRICS_TEXTFILE
+                  value: /var/lib/kube-health-reporter-textfile/kube_health_reporter{{ with $tenant }}_{{ . }}{{ end }}.prom
+                {{- end }}
+                {{- if .Values.htmlReport.existingClaim }}
+                - name: HTML_REPORT_FILE
+                  value: /var/lib/kube-health-reporter-html/index{{ with $tenant }}_{{ . }}{{ end }}.html
+                {{- end }}
+                {{- if .Values.slack.botTokenSecretName }}
+                - name: SLACK_BOT_TOKEN
+                  valueFrom:
+                    secretKeyRef:
+                      name: {{ .Values.slack.botTokenSecretName }}
+                      key: {{ .Values.slack.botTokenSecretKey }}
+                - name: SLACK_CHANNEL
+                  value: {{ required "slack.channel is required with slack.botTokenSecretName" .Values.slack.channel | quote }}
+                {{- else if .Values.slack.mountAsFile }}
+                - name: SLACK_WEBHOOK_URL_FILE
+                  value: /etc/kube-health-reporter-slack/{{ .Values.slack.webhookSecretKey }}
+                {{- else if .Values.slack.webhookSecretName }}
+                - name: SLACK_WEBHOOK_URL
+                  valueFrom:
+                    secretKeyRef:
+                      name: {{ .Values.slack.webhookSecretName }}
+                      key: {{ .Values.slack.webhookSecretKey }}
+                {{- end }}
+              resources:
+                {{- toYaml .Values.resources | nindent 16 }}
+              {{- if or .Values.recommendations.enabled .Values.state.enabled .Values.ownership .Values.sinks.caBundle.configMap .Values.slack.mountAsFile $tenant .Values.federation.clusters .Values.openMetrics.textfileHostPath .Values.htmlReport.existingClaim }}
+              volumeMounts:
+                {{- if or .Values.recommendations.enabled .Values.state.enabled }}
+                - name: state
+                  mountPath: /var/lib/kube-health-reporter
+                {{- end }}
+                {{- if .Values.ownership }}
+                - name: ownership
+                  mountPath: /etc/kube-health-reporter
+                  readOnly: true
+                {{- end }}
+                {{- if .Values.sinks.caBundle.configMap }}
+                - name: webhook-ca
+                  mountPath: /etc/kube-health-reporter-ca
+                  readOnly: true
+                {{- end }}
+                {{- if .Values.slack.mountAsFile }}
+                - name: slack-webhook
+                  mountPath: /etc/kube-health-reporter-slack
+                  readOnly: true
+                {{- end }}
+                {{- if .Values.openMetrics.textfileHostPath }}
+                - name: textfile
+                  mountPath: /var/lib/kube-health-reporter-textfile
+                {{- end }}
+                {{- if .Values.htmlReport.existingClaim }}
+                - name: html-report
+                  mountPath: /var/lib/kube-health-reporter-html
+                {{- end }}
+                {{- if $tenant }}
+                - name: tenants
+                  mountPath: /etc/kube-health-reporter-tenants
+                  readOnly: true
+                {{- else if .Values.federation.clusters }}
+                - name: kubeconfig
+                  mountPath: /etc/kube-health-reporter-kubeconfig
+                  readOnly: true
+                {{- end }}
+              {{- end }}
+          {{- if or .Values.recommendations.enabled .Values.state.enabled .Values.ownership .Values.sinks.caBundle.configMap .Values.slack.mountAsFile $tenant .Values.federation.clusters .Values.openMetrics.textfileHostPath .Values.htmlReport.existingClaim }}
+          volumes:
+            {{- if or .Values.recommendations.enabled .Values.state.enabled }}
+            - name: state
+              persistentVolumeClaim:
+                claimName: {{ required "state.existingClaim or recommendations.existingClaim is required" (.Values.state.existingClaim | default .Values.recommendations.existingClaim) }}
+            {{- end }}
+            {{- if .Values.ownership }}
+            - name: ownership
+              configMap:
+                name: {{ include "kube-health-reporter.fullname" . }}-ownership
+            {{- end }}
+            {{- if .Values.sinks.caBundle.configMap }}
+            - name: webhook-ca
+              configMap:
+                name: {{ .Values.sinks.caBundle.configMap }}
+            {{- end }}
+            {{- if .Values.slack.mountAsFile }}
+            - name: slack-webhook
+              secret:
+                secretName: {{ .Values.slack.webhookSecretName }}
+            {{- end }}
+            {{- if .Values.openMetrics.textfileHostPath }}
+            - name: textfile
+              hostPath:
+                path: {{ .Values.openMetrics.textfileHostPath }}
+                type: DirectoryOrCreate
+            {{- end }}
+            {{- if .Values.htmlReport.existingClaim }}
+            - name: html-report
+              persistentVolumeClaim:
+                claimName: {{ .Values.htmlReport.existingClaim }}
+            {{- end }}
+            {{- if $tenant }}
+            - name: tenants
+              configMap:
+                name: {{ include "kube-health-reporter.fullname" . }}-tenants
+            {{- else if .Values.federation.clusters }}
+            - name: kubeconfig
+              secret:
+                secretName: {{ required "federation.kubeconfigSecretName is required with federation.clusters" .Values.federation.kubeconfigSecretName }}
+            {{- end }}
+          {{- end }}
+          {{- with .Values.nodeSelector }}
+          nodeSelector:
+            {{- toYaml . | nindent 12 }}
+          {{- end }}
+          {{- with .Values.tolerations }}
+          tolerations:
+            {{- toYaml . | nindent 12 }}
+          {{- end }}
+          {{- with .Values.affinity }}
+          affinity:
+            {{- toYaml . | nindent 12 }}
+          {{- end }}
+{{- end }}
+{{- end -}}
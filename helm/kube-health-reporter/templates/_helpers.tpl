@@ -32,4 +32,49 @@ app.kubernetes.io/instance: {{ .Release.Name }}
 {{- end -}}
 {{- end -}}
 
-
+{{/*
+Rules for the reported namespaces: a ClusterRole bound per namespace with
+rbac.scope=cluster, a Role in each namespace with rbac.scope=namespace
+*/}}
+{{- define "kube-health-reporter.namespacedRules" -}}
+- apiGroups: [""]
+  resources: ["pods"]
+  verbs: ["get", "list", "watch"]
+- apiGroups: ["metrics.k8s.io"]
+  resources: ["pods"]
+  verbs: ["get", "list"]
+- apiGroups: ["batch"]
+  resources: ["jobs", "cronjobs"]
+  verbs: ["get", "list", "watch"]
+# Events, claims, services and LimitRanges for the churn, volume scheduling, endpoint
+# and LimitRange checks
+- apiGroups: [""]
+  resources: ["events", "persistentvolumeclaims", "services", "limitranges"]
+  verbs: ["list"]
+# Ingresses routing to the Services, for the blast radius of pod findings
+- apiGroups: ["networking.k8s.io"]
+  resources: ["ingresses"]
+  verbs: ["list"]
+# The namespace itself, for the stuck Terminating check
+- apiGroups: [""]
+  resources: ["namespaces"]
+  verbs: ["get"]
+{{- if .Values.namespaceQuotaCheck }}
+# ResourceQuotas for the quota check
+- apiGroups: [""]
+  resources: ["resourcequotas"]
+  verbs: ["list"]
+{{- end }}
+{{- range .Values.customResourceChecks }}
+# {{ .kind }} resources for the custom resource condition checks
+- apiGroups: [{{ .apiGroup | quote }}]
+  resources: [{{ .resource | quote }}]
+  verbs: ["list"]
+{{- end }}
+{{- if .Values.crashLogLines }}
+# Previous container logs for crash snippets
+- apiGroups: [""]
+  resources: ["pods/log"]
+  verbs: ["get"]
+{{- end }}
+{{- end -}}
@@ -0,0 +1,145 @@
+//! Tally of the API server's responses seen while collecting, reported as an indirect
+//! control-plane health signal (error and throttling rates, slowest calls).
+
+use anyhow::Result;
+use http::{Request, Response};
+use kube::Client;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tower::{Layer, Service};
+
+use crate::audit::{ApiAudit, ApiAuditLayer};
+use crate::recording::client_builder;
+use crate::types::{ApiCallInfo, ApiHealthInfo, ApiRecording};
+
+/// Slowest calls kept for the report
+const MAX_SLOWEST_CALLS: usize = 3;
+
+/// Response tally shared by every request of one client
+#[derive(Clone, Default)]
+pub struct ApiStats(Arc<Mutex<ApiHealthInfo>>);
+
+impl ApiStats {
+    fn record(&self, call: ApiCallInfo) {
+        let mut health = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        health.requests += 1;
+        match call.status {
+            Some(429) => health.throttled += 1,
+            // Not found/forbidden answers are expected for optional checks
+            Some(status) if status < 500 => {}
+            _ => health.errors += 1,
+        }
+        let at = health.slowest.partition_point(|c| c.duration_ms >= call.duration_ms);
+        if at < MAX_SLOWEST_CALLS {
+            health.slowest.insert(at, call);
+            health.slowest.truncate(MAX_SLOWEST_CALLS);
+        }
+    }
+
+    /// The tally so far
+    pub fn snapshot(&self) -> ApiHealthInfo {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// The tally so far, starting a new one for the client's next run
+    pub fn take(&self) -> ApiHealthInfo {
+        std::mem::take(&mut *self.0.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+/// Client for `config` whose API server responses are tallied in the returned stats and
+/// whose requests are audited; unless `allow_writes`, anything but get/list/watch is
+/// refused (and not counted in the stats, as it never reaches the API server). With a
+/// `recording`, responses are recorded to or replayed from its directory.
+pub fn instrumented_client(
+    config: kube::Config,
+    allow_writes: bool,
+    recording: Option<&ApiRecording>,
+) -> Result<(Client, ApiStats, ApiAudit)> {
+    let stats = ApiStats::default();
+    let audit = ApiAudit::default();
+    let client = client_builder(config, recording)?
+        .with_layer(&ApiStatsLayer(stats.clone()))
+        .with_layer(&ApiAuditLayer { audit: audit.clone(), allow_writes })
+        .build();
+    Ok((client, stats, audit))
+}
+
+struct ApiStatsLayer(ApiStats);
+
+impl<S> Layer<S> for ApiStatsLayer {
+    type Service = ApiStatsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ApiStatsService { inner, stats: self.0.clone() }
+    }
+}
+
+/// Service recording the status and latency of every request into [`ApiStats`]
+#[derive(Clone)]
+pub struct ApiStatsService<S> {
+    inner: S,
+    stats: ApiStats,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for ApiStatsService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let method = request.method().to_string();
+        let path = request.uri().path().to_string();
+        let stats = self.stats.clone();
+        let started = Instant::now();
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let result = response.await;
+            stats.record(ApiCallInfo {
+                method,
+                path,
+                status: result.as_ref().ok().map(|r| r.status().as_u16()),
+                duration_ms: started.elapsed().as_millis() as u64,
+            });
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(path: &str, status: Option<u16>, duration_ms: u64) -> ApiCallInfo {
+        ApiCallInfo { method: "GET".to_string(), path: path.to_string(), status, duration_ms }
+    }
+
+    #[test]
+    fn test_api_stats_record() {
+        let stats = ApiStats::default();
+        stats.record(call("/api/v1/nodes", Some(200), 120));
+        stats.record(call("/api/v1/namespaces/default/pods", Some(429), 15));
+        stats.record(call("/api/v1/namespaces/default/secrets/db", Some(404), 10));
+        stats.record(call("/apis/batch/v1/namespaces/default/jobs", Some(503), 900));
+        stats.record(call("/api/v1/nodes/node-a/proxy/stats/summary", None, 3000));
+
+        let health = stats.snapshot();
+        assert_eq!(health.requests, 5);
+        assert_eq!(health.throttled, 1);
+        assert_eq!(health.errors, 2);
+        assert_eq!(health.error_pct(), 40.0);
+        let slowest: Vec<u64> = health.slowest.iter().map(|c| c.duration_ms).collect();
+        assert_eq!(slowest, vec![3000, 900, 120]);
+    }
+}
@@ -0,0 +1,194 @@
+//! Read-only guard and audit of the API verbs used in a run. The reporter only reads
+//! (get/list/watch); any other request is refused before it reaches the API server
+//! unless `ALLOW_API_WRITES` is set, so a security review can rely on the code path
+//! rather than on RBAC alone.
+
+use anyhow::{Context as _, Result};
+use http::{Method, Request, Response};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::io::Write;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use tower::{BoxError, Layer, Service};
+
+use crate::types::ApiAuditEntry;
+
+/// Kubernetes verbs that only read
+pub const READ_VERBS: &[&str] = &["get", "list", "watch"];
+
+/// Kubernetes verb, resource and namespace of a request, e.g. `list`, `pods`,
+/// `Some("default")`. Resources of API groups are `group/resource`, subresources are
+/// appended (`nodes/proxy`); non-resource URLs (`/metrics`, discovery) keep their path.
+pub fn classify_request(method: &Method, path: &str, query: Option<&str>) -> (String, String, Option<String>) {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').filter(|s| !s.is_empty()).collect();
+    let (group, rest) = match segments.as_slice() {
+        ["api", _version, rest @ ..] if !rest.is_empty() => ("", rest),
+        ["apis", group, _version, rest @ ..] if !rest.is_empty() => (*group, rest),
+        _ => return (method.as_str().to_lowercase(), path.to_string(), None),
+    };
+    let (namespace, rest) = match rest {
+        ["namespaces", namespace, rest @ ..] if !rest.is_empty() => (Some(namespace.to_string()), rest),
+        rest => (None, rest),
+    };
+    let mut resource = if group.is_empty() { rest[0].to_string() } else { format!("{}/{}", group, rest[0]) };
+    if rest.len() > 2 {
+        resource = format!("{}/{}", resource, rest[2..].join("/"));
+    }
+    let named = rest.len() > 1;
+    let watch = query.is_some_and(|q| q.split('&').any(|p| p == "watch=true" || p == "watch=1"));
+    let verb = match *method {
+        Method::GET if watch => "watch",
+        Method::GET if named => "get",
+        Method::GET => "list",
+        Method::POST => "create",
+        Method::PUT => "update",
+        Method::PATCH => "patch",
+        Method::DELETE if named => "delete",
+        Method::DELETE => "deletecollection",
+        ref other => return (other.as_str().to_lowercase(), resource, namespace),
+    };
+    (verb.to_string(), resource, namespace)
+}
+
+/// Verb, resource and namespace an audit entry is kept under
+type AuditKey = (String, String, Option<String>);
+
+/// Requests of one client by verb, resource and namespace
+#[derive(Clone, Default)]
+pub struct ApiAudit(Arc<Mutex<BTreeMap<AuditKey, ApiAuditEntry>>>);
+
+impl ApiAudit {
+    fn record(&self, verb: String, resource: String, namespace: Option<String>, denied: bool) {
+        let mut entries = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = entries.entry((verb.clone(), resource.clone(), namespace.clone())).or_insert_with(|| ApiAuditEntry {
+            verb,
+            resource,
+            namespace,
+            requests: 0,
+            denied: 0,
+        });
+        entry.requests += 1;
+        if denied {
+            entry.denied += 1;
+        }
+    }
+
+    /// The requests so far, ordered by verb, resource and namespace
+    pub fn snapshot(&self) -> Vec<ApiAuditEntry> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).values().cloned().collect()
+    }
+
+    /// The requests so far, starting afresh for the client's next run
+    pub fn take(&self) -> Vec<ApiAuditEntry> {
+        std::mem::take(&mut *self.0.lock().unwrap_or_else(|e| e.into_inner())).into_values().collect()
+    }
+}
+
+/// Append one JSON line for the run to `path`: run ID, cluster, time and the requests
+pub fn append_audit_log(path: &Path, run_id: &str, cluster: Option<&str>, entries: &[ApiAuditEntry]) -> Result<()> {
+    let line = json!({
+        "run_id": run_id,
+        "cluster": cluster,
+        "at": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        "read_only": entries.iter().all(|e| READ_VERBS.contains(&e.verb.as_str())),
+        "requests": entries,
+    });
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open audit log {}", path.display()))?;
+    writeln!(file, "{}", line).with_context(|| format!("Failed to write audit log {}", path.display()))?;
+    Ok(())
+}
+
+/// Records every request in [`ApiAudit`] and, unless `allow_writes`, refuses the ones
+/// whose verb is not in [`READ_VERBS`]
+pub struct ApiAuditLayer {
+    pub audit: ApiAudit,
+    pub allow_writes: bool,
+}
+
+impl<S> Layer<S> for ApiAuditLayer {
+    type Service = ApiAuditService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ApiAuditService { inner, audit: self.audit.clone(), allow_writes: self.allow_writes }
+    }
+}
+
+#[derive(Clone)]
+pub struct ApiAuditService<S> {
+    inner: S,
+    audit: ApiAudit,
+    allow_writes: bool,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for ApiAuditService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Error: Into<BoxError>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let (verb, resource, namespace) = classify_request(request.method(), request.uri().path(), request.uri().query());
+        let denied = !self.allow_writes && !READ_VERBS.contains(&verb.as_str());
+        self.audit.record(verb.clone(), resource.clone(), namespace, denied);
+        if denied {
+            let path = request.uri().path().to_string();
+            return Box::pin(async move {
+                Err(format!("Read-only mode: refusing to {} {} ({}); set ALLOW_API_WRITES to allow", verb, resource, path).into())
+            });
+        }
+        let response = self.inner.call(request);
+        Box::pin(async move { response.await.map_err(Into::into) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_request() {
+        let classify = |method: Method, path: &str, query: Option<&str>| {
+            let (verb, resource, namespace) = classify_request(&method, path, query);
+            (verb, resource, namespace.unwrap_or_default())
+        };
+        let expect = |verb: &str, resource: &str, namespace: &str| (verb.to_string(), resource.to_string(), namespace.to_string());
+        assert_eq!(classify(Method::GET, "/api/v1/namespaces/default/pods", None), expect("list", "pods", "default"));
+        assert_eq!(classify(Method::GET, "/api/v1/namespaces/default/pods/api-0/log", Some("previous=true")), expect("get", "pods/log", "default"));
+        assert_eq!(classify(Method::GET, "/api/v1/nodes", Some("watch=true&resourceVersion=1")), expect("watch", "nodes", ""));
+        assert_eq!(classify(Method::GET, "/api/v1/nodes/node-a/proxy/stats/summary", None), expect("get", "nodes/proxy/stats/summary", ""));
+        assert_eq!(classify(Method::GET, "/api/v1/namespaces/kube-system", None), expect("get", "namespaces", ""));
+        assert_eq!(classify(Method::GET, "/apis/batch/v1/namespaces/jobs-ns/cronjobs", None), expect("list", "batch/cronjobs", "jobs-ns"));
+        assert_eq!(classify(Method::GET, "/metrics", None), expect("get", "/metrics", ""));
+        assert_eq!(classify(Method::POST, "/api/v1/namespaces/default/events", None), expect("create", "events", "default"));
+        assert_eq!(classify(Method::DELETE, "/apis/apps/v1/namespaces/default/deployments", None), expect("deletecollection", "apps/deployments", "default"));
+    }
+
+    #[test]
+    fn test_api_audit_record() {
+        let audit = ApiAudit::default();
+        audit.record("list".to_string(), "pods".to_string(), Some("default".to_string()), false);
+        audit.record("list".to_string(), "pods".to_string(), Some("default".to_string()), false);
+        audit.record("create".to_string(), "events".to_string(), Some("default".to_string()), true);
+
+        let entries = audit.snapshot();
+        let summary: Vec<(&str, &str, usize, usize)> =
+            entries.iter().map(|e| (e.verb.as_str(), e.resource.as_str(), e.requests, e.denied)).collect();
+        assert_eq!(summary, vec![("create", "events", 1, 1), ("list", "pods", 2, 0)]);
+    }
+}
@@ -0,0 +1,177 @@
+use serde::Serialize;
+
+use crate::types::SlackPayload;
+
+/// Block Kit text object
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Text {
+    PlainText { text: String },
+    Mrkdwn { text: String },
+}
+
+impl Text {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Text::PlainText { text } | Text::Mrkdwn { text } => text,
+        }
+    }
+}
+
+/// The Block Kit blocks used by the reports. `block_id` tags report sections with
+/// their `REPORT_SECTIONS` key so they can be reordered.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Block {
+    Header {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        block_id: Option<String>,
+        text: Text,
+    },
+    Section {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        block_id: Option<String>,
+        text: Text,
+    },
+    Context {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        block_id: Option<String>,
+        elements: Vec<Text>,
+    },
+    Divider {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        block_id: Option<String>,
+    },
+}
+
+impl Block {
+    /// Plain text header (Slack does not render mrkdwn in headers)
+    pub fn header(text: impl Into<String>) -> Self {
+        Block::Header { block_id: None, text: Text::PlainText { text: text.into() } }
+    }
+
+    pub fn section(mrkdwn: impl Into<String>) -> Self {
+        Block::Section { block_id: None, text: Text::Mrkdwn { text: mrkdwn.into() } }
+    }
+
+    /// Small grey text line
+    pub fn context(mrkdwn: impl Into<String>) -> Self {
+        Block::Context { block_id: None, elements: vec![Text::Mrkdwn { text: mrkdwn.into() }] }
+    }
+
+    pub fn divider() -> Self {
+        Block::Divider { block_id: None }
+    }
+
+    pub fn with_block_id(mut self, id: impl Into<String>) -> Self {
+        let (Block::Header { block_id, .. }
+        | Block::Section { block_id, .. }
+        | Block::Context { block_id, .. }
+        | Block::Divider { block_id }) = &mut self;
+        *block_id = Some(id.into());
+        self
+    }
+
+    pub fn block_id(&self) -> Option<&str> {
+        let (Block::Header { block_id, .. }
+        | Block::Section { block_id, .. }
+        | Block::Context { block_id, .. }
+        | Block::Divider { block_id }) = self;
+        block_id.as_deref()
+    }
+
+    /// Text of the block, context elements joined by spaces; for renderers other than Slack
+    pub fn text(&self) -> Option<String> {
+        match self {
+            Block::Header { text, .. } | Block::Section { text, .. } => Some(text.as_str().to_string()),
+            Block::Context { elements, .. } => Some(elements.iter().map(Text::as_str).collect::<Vec<_>>().join(" ")),
+            Block::Divider { .. } => None,
+        }
+    }
+}
+
+impl From<Block> for serde_json::Value {
+    fn from(block: Block) -> Self {
+        serde_json::to_value(block).expect("blocks always serialize")
+    }
+}
+
+/// Builder for a Slack message
+#[derive(Debug, Clone, Default)]
+pub struct SlackMessage {
+    text: Option<String>,
+    blocks: Vec<Block>,
+}
+
+impl SlackMessage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fallback text for notifications and clients without Block Kit
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    pub fn block(mut self, block: Block) -> Self {
+        self.blocks.push(block);
+        self
+    }
+
+    pub fn push(&mut self, block: Block) {
+        self.blocks.push(block);
+    }
+
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    pub fn build(self) -> SlackPayload {
+        SlackPayload {
+            text: self.text,
+            blocks: self.blocks.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_blocks_serialize_to_block_kit() {
+        assert_eq!(
+            serde_json::Value::from(Block::header("Report")),
+            json!({"type": "header", "text": {"type": "plain_text", "text": "Report"}})
+        );
+        assert_eq!(
+            serde_json::Value::from(Block::section("*Pending pods*\n• `a/b`").with_block_id("pending")),
+            json!({"type": "section", "block_id": "pending", "text": {"type": "mrkdwn", "text": "*Pending pods*\n• `a/b`"}})
+        );
+        assert_eq!(
+            serde_json::Value::from(Block::context("Run ID: `x`")),
+            json!({"type": "context", "elements": [{"type": "mrkdwn", "text": "Run ID: `x`"}]})
+        );
+        assert_eq!(serde_json::Value::from(Block::divider()), json!({"type": "divider"}));
+    }
+
+    #[test]
+    fn test_slack_message_builder() {
+        let mut message = SlackMessage::new()
+            .text("2 issues")
+            .block(Block::header("Report"))
+            .block(Block::section("body").with_block_id("usage"));
+        message.push(Block::divider());
+
+        assert_eq!(message.blocks()[1].block_id(), Some("usage"));
+        assert_eq!(message.blocks()[1].text().as_deref(), Some("body"));
+        assert_eq!(message.blocks()[2].text(), None);
+
+        let payload = message.build();
+        assert_eq!(payload.text.as_deref(), Some("2 issues"));
+        assert_eq!(payload.blocks.len(), 3);
+        assert_eq!(payload.blocks[1]["block_id"], "usage");
+    }
+}
@@ -1,22 +1,113 @@
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
+use k8s_openapi::api::core::v1::Namespace;
 use kube::Client;
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use tokio::time::Instant;
+use tracing::warn;
 
 use crate::types::*;
 use crate::metrics;
+use crate::kubernetes::MetricsAvailability;
 
 /// Collector structure that groups related metrics collection
 pub struct MetricsCollector<'a> {
     client: &'a Client,
     config: &'a Config,
+    availability: MetricsAvailability,
+    custom_resources: Vec<metrics::custom::ResolvedCustomResourceCheck>,
 }
 
 impl<'a> MetricsCollector<'a> {
     pub fn new(client: &'a Client, config: &'a Config) -> Self {
-        Self { client, config }
+        Self { client, config, availability: MetricsAvailability::default(), custom_resources: Vec::new() }
     }
 
-    /// Collect all pod-related metrics for a namespace
-    pub async fn collect_pod_metrics(&self, namespace: &str) -> Result<PodMetrics> {
+    /// Skip usage checks for namespaces and nodes the metrics API is known not to serve
+    pub fn with_metrics_availability(mut self, availability: MetricsAvailability) -> Self {
+        self.availability = availability;
+        self
+    }
+
+    /// Check the status conditions of these custom resources in every namespace
+    pub fn with_custom_resource_checks(mut self, checks: Vec<metrics::custom::ResolvedCustomResourceCheck>) -> Self {
+        self.custom_resources = checks;
+        self
+    }
+
+    /// Collect pod, job and volume metrics for a namespace, running the
+    /// independent analyzers concurrently
+    pub async fn collect_namespace_metrics(&self, namespace: &str) -> Result<NamespaceMetrics> {
+        self.collect_namespace_metrics_until(namespace, None).await
+    }
+
+    /// Like [`Self::collect_namespace_metrics`], but analyzer groups still running at
+    /// `deadline` are abandoned: their metrics stay empty and they are listed in
+    /// `timed_out`, while the groups that finished are kept
+    pub async fn collect_namespace_metrics_until(
+        &self,
+        namespace: &str,
+        deadline: Option<Instant>,
+    ) -> Result<NamespaceMetrics> {
+        // A namespace being deleted only reports whether its deletion is stuck, not the
+        // failing and unready pods that come with it. Otherwise the object only tells the
+        // pod checks whether the namespace itself is stuck deleting.
+        let namespace_object = match until(deadline, metrics::finalizers::get_namespace(self.client, namespace)).await {
+            Some(Ok(namespace_object)) => Some(namespace_object),
+            Some(Err(e)) => {
+                warn!("Namespace {} unreadable, skipping its Terminating check: {}", namespace, e);
+                None
+            }
+            None => None,
+        };
+        if let Some(namespace_object) = &namespace_object {
+            if metrics::finalizers::is_terminating(namespace_object) {
+                let stuck = metrics::finalizers::analyze_stuck_namespace(namespace_object, self.config, chrono::Utc::now());
+                return Ok(NamespaceMetrics {
+                    pods: PodMetrics { stuck_terminating: stuck.into_iter().collect(), ..Default::default() },
+                    jobs: JobMetrics::default(),
+                    volumes: VolumeMetrics::default(),
+                    timed_out: Vec::new(),
+                    terminating: true,
+                });
+            }
+        }
+
+        let (pods, jobs, volumes) = tokio::join!(
+            until(deadline, self.collect_pod_metrics(namespace, namespace_object.as_ref())),
+            until(deadline, self.collect_job_metrics(namespace)),
+            until(deadline, self.collect_volume_metrics(namespace)),
+        );
+
+        let mut timed_out = Vec::new();
+        let pods = match pods {
+            Some(pods) => pods?,
+            None => {
+                timed_out.push("pod");
+                PodMetrics::default()
+            }
+        };
+        let jobs = match jobs {
+            Some(jobs) => jobs?,
+            None => {
+                timed_out.push("job");
+                JobMetrics::default()
+            }
+        };
+        let volumes = match volumes {
+            Some(volumes) => volumes?,
+            None => {
+                timed_out.push("volume");
+                VolumeMetrics::default()
+            }
+        };
+
+        Ok(NamespaceMetrics { pods, jobs, volumes, timed_out, terminating: false })
+    }
+
+    /// Collect all pod-related metrics for a namespace; `namespace_object` (when readable)
+    /// adds the namespace's own stuck deletion to the Terminating check
+    pub async fn collect_pod_metrics(&self, namespace: &str, namespace_object: Option<&Namespace>) -> Result<PodMetrics> {
         // List pods once
         let pods = {
             use kube::{Api, api::ListParams};
@@ -25,13 +116,177 @@ impl<'a> MetricsCollector<'a> {
             pod_api.list(&ListParams::default()).await?.items
         };
 
+        // Fetch pod usage once for the usage-based analyzers
+        let usage = if self.availability.pods_available(namespace) {
+            metrics::list_pod_metrics_http(self.client, namespace).await
+        } else {
+            Err(anyhow!("metrics API did not respond to the availability probe"))
+        };
+        let (usage_by_pod, usage_skipped) = match usage {
+            Ok(items) => (metrics::base::build_usage_map_from_http(items), false),
+            // With FAIL_IF_NO_METRICS a metrics API failing after the up-front check (and
+            // the retries) still fails the run; otherwise the usage checks are skipped
+            Err(e) if self.config.fail_if_no_metrics => {
+                return Err(e).with_context(|| format!("Pod metrics unavailable in namespace {}", namespace));
+            }
+            Err(e) => {
+                warn!("Pod metrics unavailable in namespace {}, skipping usage checks: {}", namespace, e);
+                (Default::default(), true)
+            }
+        };
+
+        // Events feed the churn, readiness flapping, volume scheduling and config
+        // reference checks; without them those fall back to what the pod and PVC
+        // objects show (churn and readiness flapping cannot be detected at all)
+        let events = match metrics::churn::list_namespace_events(self.client, namespace).await {
+            Ok(events) => events,
+            Err(e) => {
+                warn!("Events unavailable in namespace {}, continuing without them: {}", namespace, e);
+                Vec::new()
+            }
+        };
+
+        let pvcs = match metrics::volumes::list_namespace_pvcs(self.client, namespace).await {
+            Ok(pvcs) => pvcs,
+            Err(e) => {
+                warn!("PersistentVolumeClaims unavailable in namespace {}, volume scheduling checks use events only: {}", namespace, e);
+                Vec::new()
+            }
+        };
+
+        let services = match metrics::services::list_namespace_services(self.client, namespace).await {
+            Ok(services) => services,
+            Err(e) => {
+                warn!("Services unavailable in namespace {}, skipping endpoint churn checks: {}", namespace, e);
+                Vec::new()
+            }
+        };
+
+        // Ingresses only add to the blast radius of findings; Services alone still give it
+        let ingresses = match metrics::services::list_namespace_ingresses(self.client, namespace).await {
+            Ok(ingresses) => ingresses,
+            Err(e) => {
+                warn!("Ingresses unavailable in namespace {}, blast radius counts Services only: {}", namespace, e);
+                Vec::new()
+            }
+        };
+
+        let limit_ranges = match metrics::policy::list_namespace_limit_ranges(self.client, namespace).await {
+            Ok(limit_ranges) => Some(limit_ranges),
+            Err(e) => {
+                warn!("LimitRanges unavailable in namespace {}, skipping LimitRange and quota checks: {}", namespace, e);
+                None
+            }
+        };
+
+        // Without both lists the quota check cannot tell "none defined" from "not visible"
+        let namespace_quotas = match &limit_ranges {
+            Some(limit_ranges) if self.config.namespace_quota_check => {
+                match metrics::policy::list_namespace_resource_quotas(self.client, namespace).await {
+                    Ok(quotas) => metrics::policy::analyze_namespace_quotas(namespace, &quotas, limit_ranges),
+                    Err(e) => {
+                        warn!("ResourceQuotas unavailable in namespace {}, skipping quota checks: {}", namespace, e);
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        let mut custom_resource_conditions = Vec::new();
+        for resolved in &self.custom_resources {
+            match metrics::custom::list_custom_resources(self.client, namespace, resolved).await {
+                Ok(objects) => custom_resource_conditions.extend(
+                    metrics::custom::analyze_custom_resource_conditions(namespace, &resolved.check, &objects),
+                ),
+                Err(e) => warn!("{} resources unavailable in namespace {}, skipping their condition checks: {}", resolved.check.kind, namespace, e),
+            }
+        }
+
         // Run analyzers against the pre-listed pods
-        let heavy_usage = metrics::pods::analyze_heavy_usage_with_pods(self.client, namespace, self.config, &pods).await?;
-        let restarts = metrics::pods::analyze_restarts_with_pods(namespace, self.config, &pods)?;
-        let pending = metrics::pods::analyze_pending_pods_with_pods(namespace, self.config, &pods);
-        let failed = metrics::pods::analyze_failed_pods_with_pods(namespace, self.config, &pods);
-        let unready = metrics::pods::analyze_unready_pods_with_pods(namespace, self.config, &pods);
-        let oom_killed = metrics::pods::analyze_oom_killed_with_pods(namespace, self.config, &pods);
+        let heavy_usage = metrics::pods::analyze_heavy_usage_with_usage(namespace, self.config, &pods, &usage_by_pod);
+        let stale_metrics = metrics::pods::analyze_stale_metrics_with_usage(namespace, self.config, &usage_by_pod);
+        let workload_usage = metrics::recommendations::workload_usage_with_usage(namespace, &pods, &usage_by_pod);
+        let mut restarts = metrics::pods::analyze_restarts_with_pods(namespace, self.config, &pods)?;
+        let mut pending = metrics::pods::analyze_pending_pods_with_pods(namespace, self.config, &pods);
+        let mut failed = metrics::pods::analyze_failed_pods_with_pods(namespace, self.config, &pods);
+        let mut unready = metrics::pods::analyze_unready_pods_with_pods(namespace, self.config, &pods);
+        let readiness_flapping = metrics::pods::analyze_readiness_flapping_with_pods(
+            namespace,
+            self.config,
+            &pods,
+            &events,
+            chrono::Utc::now(),
+        );
+        let endpoint_churn = metrics::services::analyze_endpoint_churn_with_pods(
+            namespace,
+            self.config,
+            &services,
+            &pods,
+            &readiness_flapping,
+            chrono::Utc::now(),
+        );
+        let dual_stack = if self.config.dual_stack_check {
+            metrics::services::analyze_dual_stack_with_pods(namespace, &services, &pods)
+        } else {
+            Vec::new()
+        };
+        let mut oom_killed = metrics::pods::analyze_oom_killed_with_pods(namespace, self.config, &pods);
+        let resource_hygiene = metrics::pods::analyze_resource_hygiene_with_pods(namespace, self.config, &pods);
+        let pod_security = metrics::security::analyze_pod_security_with_pods(namespace, self.config, &pods);
+        let limit_range_conflicts = metrics::policy::analyze_limit_range_conflicts_with_pods(
+            namespace,
+            self.config,
+            &pods,
+            limit_ranges.as_deref().unwrap_or_default(),
+        );
+        let missing_config_refs = metrics::pods::analyze_missing_config_refs_with_pods(namespace, &pods, &events);
+        let volume_scheduling = metrics::volumes::analyze_volume_scheduling_with_pods(namespace, self.config, &pods, &events, &pvcs);
+        let churn = metrics::churn::analyze_pod_churn(namespace, self.config, &pods, &events, chrono::Utc::now());
+        let now = chrono::Utc::now();
+        let mut stuck_terminating: Vec<StuckTerminatingInfo> = namespace_object
+            .and_then(|ns| metrics::finalizers::analyze_stuck_namespace(ns, self.config, now))
+            .into_iter()
+            .collect();
+        stuck_terminating.extend(metrics::finalizers::analyze_stuck_terminating(namespace, self.config, &pvcs, now));
+        stuck_terminating.extend(metrics::finalizers::analyze_stuck_terminating(namespace, self.config, &services, now));
+        let event_flood = metrics::churn::analyze_event_flood(namespace, self.config, &events, chrono::Utc::now());
+
+        let blast_radius = metrics::services::analyze_blast_radius(&services, &ingresses, &pods);
+        for r in restarts.iter_mut() {
+            r.blast_radius = blast_radius.get(&r.pod).cloned();
+        }
+        for p in pending.iter_mut() {
+            p.blast_radius = blast_radius.get(&p.pod).cloned();
+        }
+        for f in failed.iter_mut() {
+            f.blast_radius = blast_radius.get(&f.pod).cloned();
+        }
+        for u in unready.iter_mut() {
+            u.blast_radius = blast_radius.get(&u.pod).cloned();
+        }
+        for o in oom_killed.iter_mut() {
+            o.blast_radius = blast_radius.get(&o.pod).cloned();
+        }
+
+        if self.config.crash_log_lines > 0 {
+            let targets = metrics::logs::crash_log_targets(&pods, &restarts, &oom_killed);
+            let snippets = metrics::logs::collect_crash_logs(self.client, namespace, &targets, self.config.crash_log_lines).await;
+            for r in restarts.iter_mut() {
+                r.log_tail = snippets.get(&(r.pod.clone(), r.container.clone())).cloned();
+            }
+            for o in oom_killed.iter_mut() {
+                o.log_tail = snippets.get(&(o.pod.clone(), o.container.clone())).cloned();
+            }
+        }
+
+        let listed_objects = BTreeMap::from([
+            ("pods".to_string(), pods.len()),
+            ("events".to_string(), events.len()),
+            ("persistentvolumeclaims".to_string(), pvcs.len()),
+            ("services".to_string(), services.len()),
+            ("ingresses".to_string(), ingresses.len()),
+        ]);
 
         Ok(PodMetrics {
             heavy_usage,
@@ -39,22 +294,63 @@ impl<'a> MetricsCollector<'a> {
             pending,
             failed,
             unready,
+            readiness_flapping,
+            endpoint_churn,
+            dual_stack,
             oom_killed,
+            volume_scheduling,
+            missing_config_refs,
+            churn,
+            event_flood,
+            stuck_terminating,
+            custom_resource_conditions,
+            resource_hygiene,
+            pod_security,
+            namespace_quotas,
+            limit_range_conflicts,
+            stale_metrics,
+            workload_usage,
+            usage_skipped,
+            listed_objects,
         })
     }
 
     /// Collect all job-related metrics for a namespace
     pub async fn collect_job_metrics(&self, namespace: &str) -> Result<JobMetrics> {
-        let failed_jobs = metrics::analyze_failed_jobs(self.client, namespace, self.config).await?;
-        let missed_cronjobs = metrics::analyze_missed_cronjobs(
-            self.client, 
-            namespace, 
-            self.config.pending_grace_minutes
-        ).await?;
+        let (jobs, cronjobs, succeeded_pods) = tokio::try_join!(
+            metrics::jobs::list_namespace_jobs(self.client, namespace),
+            metrics::jobs::list_namespace_cronjobs(self.client, namespace),
+            metrics::jobs::count_succeeded_pods(self.client, namespace),
+        )?;
+
+        let failed_jobs = metrics::jobs::analyze_failed_jobs_with_jobs(namespace, self.config, &jobs);
+        let backoff_risk = metrics::analyze_backoff_risk_with_jobs(namespace, self.config, &jobs);
+        let missed_cronjobs = metrics::jobs::analyze_missed_cronjobs_with_cronjobs(
+            namespace,
+            self.config.pending_grace_minutes,
+            &cronjobs,
+        );
+        let finished_objects = metrics::jobs::analyze_finished_objects_with_jobs(
+            namespace,
+            self.config,
+            &jobs,
+            succeeded_pods,
+        );
+        let cronjob_concurrency = metrics::analyze_cronjob_concurrency_with_jobs(
+            namespace,
+            self.config,
+            &cronjobs,
+            &jobs,
+            chrono::Utc::now(),
+        );
 
         Ok(JobMetrics {
             failed_jobs,
+            backoff_risk,
             missed_cronjobs,
+            cronjob_concurrency,
+            finished_objects,
+            listed_objects: BTreeMap::from([("jobs".to_string(), jobs.len()), ("cronjobs".to_string(), cronjobs.len())]),
         })
     }
 
@@ -73,43 +369,249 @@ impl<'a> MetricsCollector<'a> {
 
     /// Collect all cluster-wide metrics
     pub async fn collect_cluster_metrics(&self) -> Result<ClusterMetrics> {
+        // Namespaced Roles cannot read nodes or anything else cluster-wide
+        if self.config.rbac_scope == RbacScope::Namespace {
+            return Ok(ClusterMetrics { cluster_checks_skipped: true, ..Default::default() });
+        }
+
         let problematic_nodes = metrics::analyze_problematic_nodes(self.client).await?;
-        let high_utilization_nodes = metrics::analyze_node_utilization(
-            self.client, 
-            self.config.threshold_percent,
-            &self.config.namespaces,
-        ).await?;
+        let high_utilization_nodes = if self.availability.nodes_available() {
+            metrics::analyze_node_utilization(
+                self.client,
+                self.config.threshold_percent,
+                &self.config.namespaces,
+                self.config.node_pod_count_all_namespaces,
+            ).await
+        } else {
+            Err(anyhow!("metrics API did not respond to the availability probe"))
+        };
+        let (high_utilization_nodes, usage_skipped) = match high_utilization_nodes {
+            Ok(nodes) => (nodes, false),
+            Err(e) if self.config.fail_if_no_metrics => return Err(e).context("Node metrics unavailable"),
+            Err(e) => {
+                warn!("Node metrics unavailable, skipping node utilization checks: {}", e);
+                (Vec::new(), true)
+            }
+        };
+
+        // A broken networking agent check must not hide the other node findings
+        let network_agent_issues = metrics::analyze_network_agents(
+            self.client,
+            &self.config.network_agent_daemonsets,
+        ).await.unwrap_or_else(|e| {
+            warn!("Skipping networking agent checks: {}", e);
+            Vec::new()
+        });
+
+        let operator_issues = if self.config.operator_check {
+            metrics::operators::analyze_operators(self.client).await.unwrap_or_else(|e| {
+                warn!("Skipping operator checks: {}", e);
+                Vec::new()
+            })
+        } else {
+            Vec::new()
+        };
+
+        // Node events may be expired or unreadable; the rest of the node checks still apply
+        let flapping_nodes = metrics::analyze_node_flapping(
+            self.client,
+            self.config.node_flap_window_minutes,
+            self.config.node_flap_threshold,
+        ).await.unwrap_or_else(|e| {
+            warn!("Skipping node flap checks: {}", e);
+            Vec::new()
+        });
+
+        let node_disk_forecast = if self.config.node_disk_forecast {
+            metrics::analyze_node_disk_forecast(self.client, self.config.node_disk_margin_percent)
+                .await
+                .unwrap_or_else(|e| {
+                    warn!("Skipping node disk forecast: {}", e);
+                    Vec::new()
+                })
+        } else {
+            Vec::new()
+        };
+
+        let object_counts = if self.config.object_count_check {
+            metrics::objects::fetch_object_counts(self.client).await.unwrap_or_else(|e| {
+                warn!("API server object counts unavailable: {}", e);
+                Default::default()
+            })
+        } else {
+            Default::default()
+        };
+
+        // Zones only annotate pod findings, so a failure just leaves them out
+        let node_zones = metrics::list_node_zones(self.client).await.unwrap_or_else(|e| {
+            warn!("Node zones unavailable: {}", e);
+            Default::default()
+        });
 
         Ok(ClusterMetrics {
             problematic_nodes,
+            flapping_nodes,
+            node_zones,
             high_utilization_nodes,
+            network_agent_issues,
+            operator_issues,
+            node_disk_forecast,
+            object_counts,
+            object_count_warnings: Vec::new(),
+            usage_skipped,
+            cluster_checks_skipped: false,
         })
     }
 }
 
+/// `future`'s output, or `None` when `deadline` passes first
+pub async fn until<F: Future>(deadline: Option<Instant>, future: F) -> Option<F::Output> {
+    match deadline {
+        Some(deadline) => tokio::time::timeout_at(deadline, future).await.ok(),
+        None => Some(future.await),
+    }
+}
+
+/// All metrics collected for a single namespace
+pub struct NamespaceMetrics {
+    pub pods: PodMetrics,
+    pub jobs: JobMetrics,
+    pub volumes: VolumeMetrics,
+    /// Analyzer groups (`pod`, `job`, `volume`) abandoned at the namespace's deadline
+    pub timed_out: Vec<&'static str>,
+    /// The namespace is being deleted, so only its stuck-deletion check ran
+    pub terminating: bool,
+}
+
 /// Grouped pod metrics
+#[derive(Default)]
 pub struct PodMetrics {
     pub heavy_usage: Vec<HeavyUsagePod>,
     pub restarts: Vec<RestartEventInfo>,
     pub pending: Vec<PendingPodInfo>,
     pub failed: Vec<FailedPodInfo>,
     pub unready: Vec<UnreadyPodInfo>,
+    /// Pods whose readiness keeps flipping, even if Ready right now
+    pub readiness_flapping: Vec<ReadinessFlapInfo>,
+    /// Services whose endpoints change membership rapidly
+    pub endpoint_churn: Vec<ServiceEndpointChurnInfo>,
+    /// Dual-stack Services and pods lacking an address of one family
+    pub dual_stack: Vec<DualStackIssueInfo>,
     pub oom_killed: Vec<OomKilledInfo>,
+    /// Pending pods blocked on their PersistentVolumeClaims
+    pub volume_scheduling: Vec<VolumeSchedulingInfo>,
+    /// Pods referencing ConfigMaps/Secrets that do not exist
+    pub missing_config_refs: Vec<MissingConfigRefInfo>,
+    pub churn: Vec<PodChurnInfo>,
+    /// The namespace, when it records an abnormal number of Warning events
+    pub event_flood: Option<EventFloodInfo>,
+    /// The namespace, claims and Services held in Terminating by finalizers
+    pub stuck_terminating: Vec<StuckTerminatingInfo>,
+    /// Configured custom resources whose status conditions differ from the expectation
+    pub custom_resource_conditions: Vec<CustomResourceConditionInfo>,
+    pub resource_hygiene: Vec<ResourceHygieneInfo>,
+    /// Workloads failing the namespace's Pod Security Standards profile
+    pub pod_security: Vec<PodSecurityViolationInfo>,
+    /// The namespace lacks a ResourceQuota or LimitRange (`NAMESPACE_QUOTA_CHECK`)
+    pub namespace_quotas: Option<NamespaceQuotaInfo>,
+    /// Containers outside or at the edge of the namespace's LimitRange constraints
+    pub limit_range_conflicts: Vec<LimitRangeConflictInfo>,
+    pub stale_metrics: Vec<StaleMetricsInfo>,
+    /// Raw per-pod usage feeding the right-sizing history
+    pub workload_usage: Vec<WorkloadUsage>,
+    /// Usage checks were skipped because metrics.k8s.io was unavailable
+    pub usage_skipped: bool,
+    /// Objects listed for the analyzers, by resource
+    pub listed_objects: BTreeMap<String, usize>,
 }
 
 /// Grouped job metrics
+#[derive(Default)]
 pub struct JobMetrics {
     pub failed_jobs: Vec<FailedJobInfo>,
+    /// Unfinished jobs close to exhausting their backoffLimit
+    pub backoff_risk: Vec<JobBackoffRiskInfo>,
     pub missed_cronjobs: Vec<MissedCronJobInfo>,
+    /// CronJobs whose runs overlap or pile up
+    pub cronjob_concurrency: Vec<CronJobConcurrencyInfo>,
+    /// Finished Jobs/pods piling up in the namespace, if above the threshold
+    pub finished_objects: Option<FinishedObjectsInfo>,
+    /// Objects listed for the analyzers, by resource
+    pub listed_objects: BTreeMap<String, usize>,
 }
 
 /// Grouped volume metrics
+#[derive(Default)]
 pub struct VolumeMetrics {
     pub volume_issues: Vec<VolumeIssueInfo>,
 }
 
 /// Grouped cluster-wide metrics
+#[derive(Clone, Default)]
 pub struct ClusterMetrics {
     pub problematic_nodes: Vec<ProblematicNodeInfo>,
+    /// Nodes that went NotReady repeatedly within the flap window, even if Ready now
+    pub flapping_nodes: Vec<NodeFlapInfo>,
     pub high_utilization_nodes: Vec<NodeUtilizationInfo>,
+    /// Nodes without a healthy kube-proxy/CNI agent
+    pub network_agent_issues: Vec<NetworkAgentIssueInfo>,
+    /// OLM operators failing to install/upgrade or short of available replicas
+    pub operator_issues: Vec<OperatorIssueInfo>,
+    /// Node filesystems nearing the kubelet image GC/eviction thresholds
+    pub node_disk_forecast: Vec<NodeDiskForecastInfo>,
+    /// Stored objects per resource; empty when the check is off or `/metrics` is unreadable
+    pub object_counts: BTreeMap<String, u64>,
+    /// Resources with large or fast-growing object counts, judged against the previous
+    /// run's counts once the state is loaded (advisory, not counted as issues)
+    pub object_count_warnings: Vec<ObjectCountInfo>,
+    /// Zone per node name, used to annotate pod findings
+    pub node_zones: HashMap<String, String>,
+    /// Node utilization checks were skipped because metrics.k8s.io was unavailable
+    pub usage_skipped: bool,
+    /// Every cluster-scoped check was skipped because the RBAC is namespaced only
+    pub cluster_checks_skipped: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_until() {
+        assert_eq!(until(None, async { 1 }).await, Some(1));
+        assert_eq!(until(Some(Instant::now() + std::time::Duration::from_secs(60)), async { 2 }).await, Some(2));
+        assert_eq!(until(Some(Instant::now()), std::future::pending::<()>()).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_collect_pod_metrics_without_metrics_api() {
+        use bytes::Bytes;
+        use http::{Request, Response};
+        use http_body_util::{BodyExt, Full};
+        use kube::client::{Body, ClientBuilder, DynBody};
+        use tower::util::BoxService;
+        use tower::BoxError;
+
+        // Every list is empty and metrics.k8s.io is not served
+        let cluster = tower::service_fn(|request: Request<Body>| async move {
+            let (status, body) = if request.uri().path().starts_with("/apis/metrics.k8s.io") {
+                (404, serde_json::json!({"kind": "Status", "apiVersion": "v1", "status": "Failure", "reason": "NotFound", "code": 404}))
+            } else {
+                (200, serde_json::json!({"kind": "List", "apiVersion": "v1", "metadata": {}, "items": []}))
+            };
+            let body: Box<DynBody> = Box::new(Full::new(Bytes::from(body.to_string())).map_err(|never| match never {}));
+            Ok::<_, BoxError>(Response::builder().status(status).body(body).unwrap())
+        });
+        let client = ClientBuilder::new(BoxService::new(cluster), "default").build();
+
+        let config = Config { fail_if_no_metrics: false, ..Config::default() };
+        let pods = MetricsCollector::new(&client, &config).collect_pod_metrics("default", None).await.unwrap();
+        assert!(pods.usage_skipped);
+
+        let config = Config { fail_if_no_metrics: true, ..Config::default() };
+        let Err(error) = MetricsCollector::new(&client, &config).collect_pod_metrics("default", None).await else {
+            panic!("expected FAIL_IF_NO_METRICS to fail the collection");
+        };
+        assert!(format!("{:#}", error).contains("Pod metrics unavailable in namespace default"));
+    }
 }
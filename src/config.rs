@@ -1,10 +1,31 @@
 use anyhow::{anyhow, Context, Result};
-use std::collections::HashMap;
-use crate::types::Config;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use crate::pagerduty::PAGEABLE_SECTIONS;
+use crate::tenancy::{apply_tenant, TenantsFile};
+use crate::types::{
+    ApiRecording, ChangeFreezeSource, Config, CustomResourceCheck, EmailSettings, HookCommands, OnCallProvider, OnCallSettings, OutputFormat, PodSecurityLevel, PushgatewaySettings,
+    RbacScope, RunMode, Severity, SeverityRule, SigningAlgorithm, SlackBotSettings, SmtpTls, TelegramSettings, MEASURED_SECTIONS, REPORT_SECTIONS,
+};
 
 /// Trait for abstracting environment variable access
 pub trait EnvironmentProvider {
     fn get_var(&self, key: &str) -> Option<String>;
+
+    /// Secret from `<key>_FILE` (e.g. a mounted Secret) or else from `key` itself;
+    /// surrounding whitespace of the file content is dropped. Setting both is an error.
+    fn get_secret(&self, key: &str) -> Result<Option<String>> {
+        let file_key = format!("{}_FILE", key);
+        match (self.get_var(&file_key), self.get_var(key)) {
+            (Some(_), Some(_)) => Err(anyhow!("Only one of {} and {} may be set", key, file_key)),
+            (Some(path), None) => {
+                let value = std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {} {}", file_key, path))?;
+                Ok(Some(value.trim().to_string()))
+            }
+            (None, value) => Ok(value),
+        }
+    }
 }
 
 /// Production implementation using std::env
@@ -54,6 +75,259 @@ impl EnvironmentProvider for MockEnvironment {
     }
 }
 
+const DEFAULT_NETWORK_AGENT_DAEMONSETS: &str = "kube-system/kube-proxy,kube-system/calico-node,\
+kube-system/cilium,kube-system/aws-node,kube-system/kube-flannel-ds,kube-flannel/kube-flannel-ds";
+
+/// Every entry must be a key from `REPORT_SECTIONS`
+pub(crate) fn validate_section_order(order: &[String]) -> Result<()> {
+    if let Some(unknown) = order.iter().find(|s| !REPORT_SECTIONS.contains(&s.as_str())) {
+        return Err(anyhow!(
+            "Invalid SECTION_ORDER: unknown section '{}' (expected one of: {})",
+            unknown,
+            REPORT_SECTIONS.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// Parse one `section[>threshold]=severity` entry of `SEVERITY_RULES`, e.g. `usage>95%=critical`
+/// or `pending>30m=critical`; thresholds in hours (`2h`) are converted to minutes
+fn parse_severity_rule(entry: &str) -> Result<SeverityRule> {
+    let invalid = || anyhow!("Invalid SEVERITY_RULES entry '{}' (expected section[>threshold]=severity)", entry);
+    let (condition, severity) = entry.split_once('=').ok_or_else(invalid)?;
+    let severity = match severity.trim() {
+        "info" => Severity::Info,
+        "warning" => Severity::Warning,
+        "critical" => Severity::Critical,
+        other => return Err(anyhow!("Invalid SEVERITY_RULES severity '{}' (expected info, warning or critical)", other)),
+    };
+    let (section, threshold) = match condition.split_once('>') {
+        Some((section, threshold)) => (section.trim(), Some(threshold.trim())),
+        None => (condition.trim(), None),
+    };
+    if !REPORT_SECTIONS.contains(&section) {
+        return Err(anyhow!("Invalid SEVERITY_RULES: unknown section '{}' (expected one of: {})", section, REPORT_SECTIONS.join(", ")));
+    }
+    let threshold = match threshold {
+        None => None,
+        Some(_) if !MEASURED_SECTIONS.contains(&section) => {
+            return Err(anyhow!(
+                "Invalid SEVERITY_RULES: section '{}' has no threshold (only {} do)",
+                section,
+                MEASURED_SECTIONS.join(", ")
+            ));
+        }
+        Some(value) => {
+            let (number, scale) = match value.strip_suffix('h') {
+                Some(hours) => (hours, 60.0),
+                None => (value.trim_end_matches(['%', 'm']), 1.0),
+            };
+            Some(number.trim().parse::<f64>().map_err(|_| invalid())? * scale)
+        }
+    };
+    Ok(SeverityRule { section: section.to_string(), threshold, severity })
+}
+
+fn parse_pod_security_level(value: &str, var: &str) -> Result<PodSecurityLevel> {
+    match value.trim() {
+        "" | "privileged" => Ok(PodSecurityLevel::Privileged),
+        "baseline" => Ok(PodSecurityLevel::Baseline),
+        "restricted" => Ok(PodSecurityLevel::Restricted),
+        other => Err(anyhow!("Invalid {}: {} (expected privileged, baseline or restricted)", var, other)),
+    }
+}
+
+/// Parse one `group[/version]/Kind: Type=Status[, Type=Status]` entry of `CUSTOM_RESOURCE_CHECKS`
+fn parse_custom_resource_check(entry: &str) -> Result<CustomResourceCheck> {
+    let invalid = || anyhow!("Invalid CUSTOM_RESOURCE_CHECKS entry '{}' (expected group/Kind: Condition=Status)", entry);
+    let (resource, conditions) = entry.split_once(':').ok_or_else(invalid)?;
+    let (group, version, kind) = match resource.trim().split('/').collect::<Vec<_>>().as_slice() {
+        [group, kind] => (group.to_string(), None, kind.to_string()),
+        [group, version, kind] => (group.to_string(), Some(version.to_string()), kind.to_string()),
+        _ => return Err(invalid()),
+    };
+    let conditions = conditions
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|condition| {
+            let (type_, status) = condition.split_once('=').ok_or_else(invalid)?;
+            Ok((type_.trim().to_string(), status.trim().to_string()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if group.is_empty() || kind.is_empty() || conditions.is_empty() {
+        return Err(invalid());
+    }
+    Ok(CustomResourceCheck { group, version, kind, conditions })
+}
+
+/// Extra HTTP headers, one `Name: value` per line; blank lines are skipped
+fn parse_headers(text: &str) -> Result<Vec<(String, String)>> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let invalid = || anyhow!("Invalid GENERIC_WEBHOOK_HEADERS line {}: expected 'Name: value'", index + 1);
+            let (name, value) = line.split_once(':').ok_or_else(invalid)?;
+            let (name, value) = (name.trim(), value.trim());
+            reqwest::header::HeaderName::from_bytes(name.as_bytes()).map_err(|_| invalid())?;
+            reqwest::header::HeaderValue::from_str(value).map_err(|_| invalid())?;
+            Ok((name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// `configmap:<namespace>/<name>`, `namespace:<name>` or an `http(s)://` URL
+fn parse_change_freeze_source(value: &str) -> Result<ChangeFreezeSource> {
+    let invalid = || anyhow!(
+        "Invalid CHANGE_FREEZE_SOURCE (expected configmap:<namespace>/<name>, namespace:<name> or an http(s) URL)"
+    );
+    let value = value.trim();
+    if value.starts_with("http://") || value.starts_with("https://") {
+        return Ok(ChangeFreezeSource::Http(value.to_string()));
+    }
+    match value.split_once(':').ok_or_else(invalid)? {
+        ("configmap", object) => match object.trim().split_once('/') {
+            Some((namespace, name)) if !namespace.is_empty() && !name.is_empty() => {
+                Ok(ChangeFreezeSource::ConfigMap { namespace: namespace.to_string(), name: name.to_string() })
+            }
+            _ => Err(invalid()),
+        },
+        ("namespace", name) if !name.trim().is_empty() => Ok(ChangeFreezeSource::Namespace(name.trim().to_string())),
+        _ => Err(invalid()),
+    }
+}
+
+/// On-call schedule lookup; `None` unless `ONCALL_PROVIDER` is set
+fn load_oncall_settings<E: EnvironmentProvider>(env: &E) -> Result<Option<OnCallSettings>> {
+    let provider = match env.get_var("ONCALL_PROVIDER").as_deref().map(str::trim) {
+        None | Some("") => return Ok(None),
+        Some("pagerduty") => OnCallProvider::PagerDuty,
+        Some("opsgenie") => OnCallProvider::Opsgenie,
+        Some(other) => return Err(anyhow!("Invalid ONCALL_PROVIDER: {} (expected pagerduty or opsgenie)", other)),
+    };
+    let schedule_id = env.get_var("ONCALL_SCHEDULE_ID")
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| anyhow!("ONCALL_SCHEDULE_ID must be set when ONCALL_PROVIDER is"))?;
+    let api_token = env.get_secret("ONCALL_API_TOKEN")?
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| anyhow!("ONCALL_API_TOKEN or ONCALL_API_TOKEN_FILE must be set when ONCALL_PROVIDER is"))?;
+    // `email=member ID` pairs, e.g. "jane@example.com=U0123ABC"
+    let slack_users = env.get_var("ONCALL_SLACK_USERS")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (email, member) = pair.split_once('=')
+                .ok_or_else(|| anyhow!("Invalid ONCALL_SLACK_USERS entry '{}' (expected email=member ID)", pair))?;
+            Ok((email.trim().to_lowercase(), member.trim().to_string()))
+        })
+        .collect::<Result<BTreeMap<_, _>>>()?;
+    Ok(Some(OnCallSettings {
+        provider,
+        schedule_id,
+        api_token,
+        api_url: env.get_var("ONCALL_API_URL").map(|u| u.trim().to_string()).filter(|u| !u.is_empty()),
+        slack_users,
+    }))
+}
+
+/// Mention syntax for a Slack ID: user groups (`S…`) as `<!subteam^S…>`, anything else as
+/// a member (`<@U…>`); IDs already in mention syntax are kept
+fn format_slack_mention(id: &str) -> String {
+    if id.starts_with('<') {
+        id.to_string()
+    } else if id.starts_with('S') {
+        format!("<!subteam^{}>", id)
+    } else {
+        format!("<@{}>", id)
+    }
+}
+
+/// Slack Web API delivery settings; `None` unless `SLACK_BOT_TOKEN` is set
+fn load_slack_bot_settings<E: EnvironmentProvider>(env: &E) -> Result<Option<SlackBotSettings>> {
+    let Some(token) = env.get_secret("SLACK_BOT_TOKEN")?.filter(|t| !t.is_empty()) else {
+        return Ok(None);
+    };
+    let channel = env.get_var("SLACK_CHANNEL")
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .ok_or_else(|| anyhow!("SLACK_CHANNEL must be set when SLACK_BOT_TOKEN is"))?;
+    Ok(Some(SlackBotSettings { token, channel }))
+}
+
+/// Telegram delivery settings; `None` unless `TELEGRAM_BOT_TOKEN` is set
+fn load_telegram_settings<E: EnvironmentProvider>(env: &E) -> Result<Option<TelegramSettings>> {
+    let Some(bot_token) = env.get_secret("TELEGRAM_BOT_TOKEN")?.filter(|t| !t.is_empty()) else {
+        return Ok(None);
+    };
+    let chat_id = env.get_var("TELEGRAM_CHAT_ID")
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .ok_or_else(|| anyhow!("TELEGRAM_CHAT_ID must be set when TELEGRAM_BOT_TOKEN is"))?;
+    Ok(Some(TelegramSettings { bot_token, chat_id }))
+}
+
+/// Pushgateway settings; `None` unless `PUSHGATEWAY_URL` is set
+fn load_pushgateway_settings<E: EnvironmentProvider>(env: &E) -> Result<Option<PushgatewaySettings>> {
+    let Some(url) = env.get_secret("PUSHGATEWAY_URL")?.filter(|u| !u.is_empty()) else {
+        return Ok(None);
+    };
+    let label = |name: &str| -> Result<Option<String>> {
+        let value = env.get_var(name).map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+        // The Pushgateway reads grouping labels from the URL path
+        if value.as_deref().is_some_and(|v| v.contains('/')) {
+            return Err(anyhow!("{} must not contain '/'", name));
+        }
+        Ok(value)
+    };
+    let job = label("PUSHGATEWAY_JOB")?.unwrap_or_else(|| "kube-health-reporter".to_string());
+    let instance = label("PUSHGATEWAY_INSTANCE")?;
+    Ok(Some(PushgatewaySettings { url, job, instance }))
+}
+
+/// Email delivery settings; `None` unless `SMTP_HOST` is set
+fn load_email_settings<E: EnvironmentProvider>(env: &E) -> Result<Option<EmailSettings>> {
+    let Some(smtp_host) = env.get_var("SMTP_HOST").map(|h| h.trim().to_string()).filter(|h| !h.is_empty()) else {
+        return Ok(None);
+    };
+    let smtp_tls = match env.get_var("SMTP_TLS").as_deref().map(str::trim) {
+        None | Some("") | Some("starttls") => SmtpTls::StartTls,
+        Some("tls") => SmtpTls::Tls,
+        Some("none") => SmtpTls::None,
+        Some(other) => return Err(anyhow!("Invalid SMTP_TLS: {} (expected starttls, tls or none)", other)),
+    };
+    let smtp_port = match env.get_var("SMTP_PORT") {
+        Some(port) => port.trim().parse().context("Invalid SMTP_PORT")?,
+        None => smtp_tls.default_port(),
+    };
+    let from = env.get_var("EMAIL_FROM")
+        .map(|f| f.trim().to_string())
+        .filter(|f| !f.is_empty())
+        .ok_or_else(|| anyhow!("EMAIL_FROM must be set when SMTP_HOST is"))?;
+    let to: Vec<String> = env.get_var("EMAIL_TO")
+        .unwrap_or_default()
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    if to.is_empty() {
+        return Err(anyhow!("EMAIL_TO must be set when SMTP_HOST is (comma-separated)"));
+    }
+    Ok(Some(EmailSettings {
+        smtp_host,
+        smtp_port,
+        smtp_tls,
+        smtp_username: env.get_var("SMTP_USERNAME").filter(|u| !u.is_empty()),
+        smtp_password: env.get_secret("SMTP_PASSWORD")?.filter(|p| !p.is_empty()),
+        ca_bundle: env.get_var("SMTP_CA_BUNDLE").filter(|p| !p.trim().is_empty()),
+        from,
+        to,
+    }))
+}
+
 pub fn load_config() -> Result<Config> {
     load_config_with_env(&SystemEnvironment)
 }
@@ -65,7 +339,9 @@ pub fn load_config_with_env<E: EnvironmentProvider>(env: &E) -> Result<Config> {
         .map(|s| s.trim().to_string())
         .filter(|s| !s.is_empty())
         .collect();
-    if namespaces.is_empty() {
+    // A tenant brings its own namespaces
+    let tenant = env.get_var("TENANT").filter(|t| !t.trim().is_empty());
+    if namespaces.is_empty() && tenant.is_none() {
         return Err(anyhow!("NAMESPACES env var must be set (comma-separated)"));
     }
 
@@ -74,8 +350,79 @@ pub fn load_config_with_env<E: EnvironmentProvider>(env: &E) -> Result<Config> {
         .parse()
         .context("Invalid THRESHOLD_PERCENT")?;
 
-    let slack_webhook_url = env.get_var("SLACK_WEBHOOK_URL")
-        .ok_or_else(|| anyhow!("SLACK_WEBHOOK_URL must be provided via Secret env"))?;
+    let email = load_email_settings(env)?;
+    let slack_bot = load_slack_bot_settings(env)?;
+    let telegram = load_telegram_settings(env)?;
+    let pushgateway = load_pushgateway_settings(env)?;
+
+    let hook_command = |name: &str| env.get_var(name).map(|c| c.trim().to_string()).filter(|c| !c.is_empty());
+    let hook_commands = HookCommands {
+        pre_collection: hook_command("HOOK_PRE_COLLECTION"),
+        post_report: hook_command("HOOK_POST_REPORT"),
+        pre_send: hook_command("HOOK_PRE_SEND"),
+    };
+    let hook_timeout_seconds: u64 = env.get_var("HOOK_TIMEOUT_SECONDS")
+        .unwrap_or_else(|| "30".to_string())
+        .parse()
+        .context("Invalid HOOK_TIMEOUT_SECONDS")?;
+
+    let generic_webhook_url = env.get_secret("GENERIC_WEBHOOK_URL")?
+        .filter(|url| !url.is_empty());
+    // One `Name: value` per line, e.g. "Authorization: Bearer ..."; usually mounted from a Secret
+    let generic_webhook_headers = parse_headers(&env.get_secret("GENERIC_WEBHOOK_HEADERS")?.unwrap_or_default())?;
+
+    let metrics_textfile = env.get_var("METRICS_TEXTFILE")
+        .filter(|p| !p.trim().is_empty());
+
+    // `-` writes to stdout
+    let csv_export = env.get_var("CSV_EXPORT")
+        .filter(|p| !p.trim().is_empty());
+
+    let html_report_file = env.get_var("HTML_REPORT_FILE")
+        .filter(|p| !p.trim().is_empty());
+
+    let metrics_listen_addr = match env.get_var("METRICS_LISTEN_ADDR").as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(addr) => Some(addr.parse().with_context(|| format!("Invalid METRICS_LISTEN_ADDR: {}", addr))?),
+    };
+
+    // The exporter needs a process that outlives the run
+    let run_mode = match env.get_var("RUN_MODE").as_deref().map(str::trim) {
+        None | Some("") if metrics_listen_addr.is_some() => RunMode::Daemon,
+        None | Some("") | Some("once") => RunMode::Once,
+        Some("daemon") => RunMode::Daemon,
+        Some(other) => return Err(anyhow!("Invalid RUN_MODE: {} (expected once or daemon)", other)),
+    };
+    if run_mode == RunMode::Once && metrics_listen_addr.is_some() {
+        return Err(anyhow!("METRICS_LISTEN_ADDR requires RUN_MODE=daemon"));
+    }
+    let interval_seconds: u64 = env.get_var("INTERVAL_SECONDS")
+        .unwrap_or_else(|| "300".to_string())
+        .parse()
+        .ok()
+        .filter(|s| *s > 0)
+        .ok_or_else(|| anyhow!("Invalid INTERVAL_SECONDS (expected a positive number of seconds)"))?;
+
+    let output_format = match env.get_var("OUTPUT_FORMAT").as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some("json") => Some(OutputFormat::Json),
+        Some("yaml") => Some(OutputFormat::Yaml),
+        Some(other) => return Err(anyhow!("Invalid OUTPUT_FORMAT: {} (expected json or yaml)", other)),
+    };
+
+    if output_format.is_some() && csv_export.as_deref() == Some("-") {
+        return Err(anyhow!("OUTPUT_FORMAT and CSV_EXPORT=- cannot both write to stdout"));
+    }
+
+    // Not needed for clusters that only reach a mail relay, feed another pipeline, are scraped or exported
+    let slack_webhook_url = match env.get_secret("SLACK_WEBHOOK_URL")?.filter(|url| !url.is_empty()) {
+        Some(_) if slack_bot.is_some() => return Err(anyhow!("Only one of SLACK_WEBHOOK_URL and SLACK_BOT_TOKEN may be set")),
+        Some(url) => url,
+        None if slack_bot.is_some() || email.is_some() || telegram.is_some() || pushgateway.is_some() || generic_webhook_url.is_some() || metrics_textfile.is_some() || csv_export.is_some() || html_report_file.is_some() || metrics_listen_addr.is_some() || output_format.is_some() => String::new(),
+        None => return Err(anyhow!(
+            "SLACK_WEBHOOK_URL or SLACK_WEBHOOK_URL_FILE must be provided via Secret (or SLACK_BOT_TOKEN for the Slack Web API, SMTP_HOST for email, TELEGRAM_BOT_TOKEN for Telegram, PUSHGATEWAY_URL for the Pushgateway, GENERIC_WEBHOOK_URL for JSON, METRICS_TEXTFILE for OpenMetrics, CSV_EXPORT for CSV, HTML_REPORT_FILE for HTML, METRICS_LISTEN_ADDR for Prometheus, OUTPUT_FORMAT for stdout)"
+        )),
+    };
 
     let restart_grace_minutes: i64 = env.get_var("RESTART_GRACE_MINUTES")
         .unwrap_or_else(|| "5".to_string())
@@ -87,6 +434,11 @@ pub fn load_config_with_env<E: EnvironmentProvider>(env: &E) -> Result<Config> {
         .parse()
         .unwrap_or(5);
 
+    let terminating_grace_minutes: i64 = env.get_var("TERMINATING_GRACE_MINUTES")
+        .unwrap_or_else(|| "60".to_string())
+        .parse()
+        .context("Invalid TERMINATING_GRACE_MINUTES")?;
+
     let cluster_name = env.get_var("CLUSTER_NAME");
     let datacenter_name = env.get_var("DATACENTER_NAME");
 
@@ -94,16 +446,454 @@ pub fn load_config_with_env<E: EnvironmentProvider>(env: &E) -> Result<Config> {
         .map(|v| matches!(v.as_str(), "1" | "true" | "TRUE" | "True"))
         .unwrap_or(true); // default to true per requirement
 
-    Ok(Config {
+    let rbac_scope = match env.get_var("RBAC_SCOPE").as_deref().map(str::trim) {
+        None | Some("") | Some("auto") => RbacScope::Auto,
+        Some("cluster") => RbacScope::Cluster,
+        Some("namespace") => RbacScope::Namespace,
+        Some(other) => return Err(anyhow!("Invalid RBAC_SCOPE: {} (expected auto, cluster or namespace)", other)),
+    };
+
+    let namespace_timeout_seconds: u64 = env.get_var("NAMESPACE_TIMEOUT_SECONDS")
+        .unwrap_or_else(|| "120".to_string())
+        .parse()
+        .context("Invalid NAMESPACE_TIMEOUT_SECONDS")?;
+
+    let run_timeout_seconds: u64 = env.get_var("RUN_TIMEOUT_SECONDS")
+        .unwrap_or_else(|| "0".to_string())
+        .parse()
+        .context("Invalid RUN_TIMEOUT_SECONDS")?;
+
+    let memory_warn_percent: f64 = env.get_var("MEMORY_WARN_PERCENT")
+        .unwrap_or_else(|| "80".to_string())
+        .parse()
+        .context("Invalid MEMORY_WARN_PERCENT")?;
+
+    let metrics_staleness_minutes: i64 = env.get_var("METRICS_STALENESS_MINUTES")
+        .unwrap_or_else(|| "5".to_string())
+        .parse()
+        .unwrap_or(5);
+
+    let node_pod_count_all_namespaces = env.get_var("NODE_POD_COUNT_ALL_NAMESPACES")
+        .map(|v| matches!(v.as_str(), "1" | "true" | "TRUE" | "True"))
+        .unwrap_or(false);
+
+    let node_disk_forecast = env.get_var("NODE_DISK_FORECAST")
+        .map(|v| matches!(v.as_str(), "1" | "true" | "TRUE" | "True"))
+        .unwrap_or(false);
+
+    let node_disk_margin_percent: f64 = env.get_var("NODE_DISK_MARGIN_PERCENT")
+        .unwrap_or_else(|| "10".to_string())
+        .parse()
+        .context("Invalid NODE_DISK_MARGIN_PERCENT")?;
+
+    let state_file = env.get_var("STATE_FILE")
+        .filter(|p| !p.trim().is_empty());
+
+    let notify_resolved = env.get_var("NOTIFY_RESOLVED")
+        .map(|v| matches!(v.as_str(), "1" | "true" | "TRUE" | "True"))
+        .unwrap_or(false);
+
+    let allow_api_writes = env.get_var("ALLOW_API_WRITES")
+        .map(|v| matches!(v.as_str(), "1" | "true" | "TRUE" | "True"))
+        .unwrap_or(false);
+
+    let audit_log_file = env.get_var("AUDIT_LOG_FILE")
+        .filter(|p| !p.trim().is_empty());
+
+    let record_dir = env.get_var("API_RECORD_DIR").filter(|p| !p.trim().is_empty());
+    let replay_dir = env.get_var("API_REPLAY_DIR").filter(|p| !p.trim().is_empty());
+    let api_recording = match (record_dir, replay_dir) {
+        (Some(_), Some(_)) => return Err(anyhow!("Only one of API_RECORD_DIR and API_REPLAY_DIR may be set")),
+        (Some(dir), None) => Some(ApiRecording::Record(dir)),
+        (None, Some(dir)) => Some(ApiRecording::Replay(dir)),
+        (None, None) => None,
+    };
+
+    let sink_timeout_seconds: u64 = env.get_var("SINK_TIMEOUT_SECONDS")
+        .unwrap_or_else(|| "10".to_string())
+        .parse()
+        .context("Invalid SINK_TIMEOUT_SECONDS")?;
+
+    let sink_failure_threshold: u32 = env.get_var("SINK_FAILURE_THRESHOLD")
+        .unwrap_or_else(|| "3".to_string())
+        .parse()
+        .context("Invalid SINK_FAILURE_THRESHOLD")?;
+
+    let sink_cooldown_minutes: i64 = env.get_var("SINK_COOLDOWN_MINUTES")
+        .unwrap_or_else(|| "60".to_string())
+        .parse()
+        .context("Invalid SINK_COOLDOWN_MINUTES")?;
+
+    let webhook_ca_bundle = env.get_var("WEBHOOK_CA_BUNDLE")
+        .filter(|p| !p.trim().is_empty());
+
+    let webhook_signing_secret = env.get_secret("WEBHOOK_SIGNING_SECRET")?
+        .filter(|s| !s.is_empty());
+
+    // May be a URL with a token, so it can come from a Secret
+    let change_freeze_source = env.get_secret("CHANGE_FREEZE_SOURCE")?
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| parse_change_freeze_source(&s))
+        .transpose()?;
+
+    let webhook_signing_algorithm = match env.get_var("WEBHOOK_SIGNING_ALGORITHM").as_deref().map(str::trim) {
+        None | Some("") | Some("sha256") => SigningAlgorithm::Sha256,
+        Some("sha512") => SigningAlgorithm::Sha512,
+        Some(other) => return Err(anyhow!("Invalid WEBHOOK_SIGNING_ALGORITHM: {} (expected sha256 or sha512)", other)),
+    };
+
+    // One regex per line, as patterns may well contain commas
+    let redaction_patterns = env.get_var("REDACTION_PATTERNS")
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|p| regex::Regex::new(p).with_context(|| format!("Invalid REDACTION_PATTERNS entry '{}'", p)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let pagerduty_routing_key = env.get_secret("PAGERDUTY_ROUTING_KEY")?
+        .filter(|key| !key.is_empty());
+    // Nothing pages unless explicitly marked as paging-worthy
+    let pagerduty_sections: Vec<String> = env.get_var("PAGERDUTY_SECTIONS")
+        .unwrap_or_default()
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    if let Some(unknown) = pagerduty_sections.iter().find(|s| !PAGEABLE_SECTIONS.contains(&s.as_str())) {
+        return Err(anyhow!(
+            "Invalid PAGERDUTY_SECTIONS: '{}' cannot page (expected one of: {})",
+            unknown,
+            PAGEABLE_SECTIONS.join(", ")
+        ));
+    }
+    if pagerduty_routing_key.is_some() && pagerduty_sections.is_empty() {
+        return Err(anyhow!("PAGERDUTY_SECTIONS must be set when PAGERDUTY_ROUTING_KEY is (e.g. oom,failed)"));
+    }
+
+    let oncall = load_oncall_settings(env)?;
+    let slack_mention = env.get_var("SLACK_MENTION")
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty());
+
+    // `section=IDs` pairs, e.g. "nodes=S0123ABC,oom=U0456DEF U0789GHI"
+    let slack_section_mentions = env.get_var("SLACK_SECTION_MENTIONS")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (section, ids) = pair.split_once('=')
+                .ok_or_else(|| anyhow!("Invalid SLACK_SECTION_MENTIONS entry '{}' (expected section=IDs)", pair))?;
+            let section = section.trim();
+            if !REPORT_SECTIONS.contains(&section) {
+                return Err(anyhow!(
+                    "Invalid SLACK_SECTION_MENTIONS: unknown section '{}' (expected one of: {})",
+                    section,
+                    REPORT_SECTIONS.join(", ")
+                ));
+            }
+            let mentions: Vec<String> = ids.split_whitespace().map(format_slack_mention).collect();
+            if mentions.is_empty() {
+                return Err(anyhow!("Invalid SLACK_SECTION_MENTIONS entry '{}' (expected section=IDs)", pair));
+            }
+            Ok((section.to_string(), mentions.join(" ")))
+        })
+        .collect::<Result<BTreeMap<_, _>>>()?;
+
+    let clusters: Vec<String> = env.get_var("CLUSTERS")
+        .unwrap_or_default()
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    if !clusters.is_empty() && run_mode == RunMode::Daemon {
+        return Err(anyhow!("RUN_MODE=daemon is not supported with CLUSTERS"));
+    }
+
+    let federation_webhook_url = env.get_secret("FEDERATION_WEBHOOK_URL")?
+        .filter(|url| !url.is_empty());
+
+    // privileged (the default) leaves pods unchecked
+    let pod_security_level = parse_pod_security_level(
+        &env.get_var("POD_SECURITY_LEVEL").unwrap_or_default(),
+        "POD_SECURITY_LEVEL",
+    )?;
+
+    // namespace=level pairs, e.g. "payments=restricted,legacy=privileged"
+    let pod_security_namespace_levels = env.get_var("POD_SECURITY_NAMESPACE_LEVELS")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|entry| {
+            let (namespace, level) = entry.split_once('=')
+                .ok_or_else(|| anyhow!("Invalid POD_SECURITY_NAMESPACE_LEVELS entry '{}' (expected namespace=level)", entry))?;
+            Ok((namespace.trim().to_string(), parse_pod_security_level(level, "POD_SECURITY_NAMESPACE_LEVELS")?))
+        })
+        .collect::<Result<BTreeMap<_, _>>>()?;
+
+    let limit_range_edge_percent: f64 = env.get_var("LIMIT_RANGE_EDGE_PERCENT")
+        .unwrap_or_else(|| "90".to_string())
+        .parse()
+        .context("Invalid LIMIT_RANGE_EDGE_PERCENT")?;
+
+    let custom_resource_checks = env.get_var("CUSTOM_RESOURCE_CHECKS")
+        .unwrap_or_default()
+        .split(';')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(parse_custom_resource_check)
+        .collect::<Result<Vec<_>>>()?;
+
+    let namespace_quota_check = env.get_var("NAMESPACE_QUOTA_CHECK")
+        .map(|v| matches!(v.as_str(), "1" | "true" | "TRUE" | "True"))
+        .unwrap_or(false);
+
+    let suppressions_enabled = env.get_var("HEALTH_SUPPRESSIONS")
+        .map(|v| matches!(v.as_str(), "1" | "true" | "TRUE" | "True"))
+        .unwrap_or(true);
+
+    let omit_empty_sections = env.get_var("OMIT_EMPTY_SECTIONS")
+        .map(|v| matches!(v.as_str(), "1" | "true" | "TRUE" | "True"))
+        .unwrap_or(false);
+
+    let recommendations_history_path = env.get_var("RECOMMENDATIONS_HISTORY_PATH")
+        .filter(|p| !p.trim().is_empty());
+
+    let limit_request_ratio_threshold: f64 = env.get_var("LIMIT_REQUEST_RATIO_THRESHOLD")
+        .unwrap_or_else(|| "10".to_string())
+        .parse()
+        .context("Invalid LIMIT_REQUEST_RATIO_THRESHOLD")?;
+
+    let pod_churn_window_minutes: i64 = env.get_var("POD_CHURN_WINDOW_MINUTES")
+        .unwrap_or_else(|| "60".to_string())
+        .parse()
+        .context("Invalid POD_CHURN_WINDOW_MINUTES")?;
+
+    let pod_churn_threshold: i64 = env.get_var("POD_CHURN_THRESHOLD")
+        .unwrap_or_else(|| "10".to_string())
+        .parse()
+        .context("Invalid POD_CHURN_THRESHOLD")?;
+
+    let event_flood_window_minutes: i64 = env.get_var("EVENT_FLOOD_WINDOW_MINUTES")
+        .unwrap_or_else(|| "60".to_string())
+        .parse()
+        .context("Invalid EVENT_FLOOD_WINDOW_MINUTES")?;
+
+    let event_flood_threshold: i64 = env.get_var("EVENT_FLOOD_THRESHOLD")
+        .unwrap_or_else(|| "500".to_string())
+        .parse()
+        .context("Invalid EVENT_FLOOD_THRESHOLD")?;
+
+    let job_backoff_warning_percent: f64 = env.get_var("JOB_BACKOFF_WARNING_PERCENT")
+        .unwrap_or_else(|| "60".to_string())
+        .parse()
+        .context("Invalid JOB_BACKOFF_WARNING_PERCENT")?;
+
+    let cronjob_active_jobs_threshold: usize = env.get_var("CRONJOB_ACTIVE_JOBS_THRESHOLD")
+        .unwrap_or_else(|| "3".to_string())
+        .parse()
+        .context("Invalid CRONJOB_ACTIVE_JOBS_THRESHOLD")?;
+
+    let finished_objects_threshold: usize = env.get_var("FINISHED_OBJECTS_THRESHOLD")
+        .unwrap_or_else(|| "100".to_string())
+        .parse()
+        .context("Invalid FINISHED_OBJECTS_THRESHOLD")?;
+
+    let readiness_flap_window_minutes: i64 = env.get_var("READINESS_FLAP_WINDOW_MINUTES")
+        .unwrap_or_else(|| "60".to_string())
+        .parse()
+        .unwrap_or(60);
+
+    let readiness_flap_threshold: i64 = env.get_var("READINESS_FLAP_THRESHOLD")
+        .unwrap_or_else(|| "5".to_string())
+        .parse()
+        .unwrap_or(5);
+
+    let object_count_check = env.get_var("OBJECT_COUNT_CHECK")
+        .map(|v| matches!(v.as_str(), "1" | "true" | "TRUE" | "True"))
+        .unwrap_or(false);
+
+    let object_count_threshold: u64 = env.get_var("OBJECT_COUNT_THRESHOLD")
+        .unwrap_or_else(|| "100000".to_string())
+        .parse()
+        .context("Invalid OBJECT_COUNT_THRESHOLD")?;
+
+    let object_count_growth_percent: f64 = env.get_var("OBJECT_COUNT_GROWTH_PERCENT")
+        .unwrap_or_else(|| "50".to_string())
+        .parse()
+        .context("Invalid OBJECT_COUNT_GROWTH_PERCENT")?;
+
+    let node_flap_window_minutes: i64 = env.get_var("NODE_FLAP_WINDOW_MINUTES")
+        .unwrap_or_else(|| "60".to_string())
+        .parse()
+        .unwrap_or(60);
+
+    let node_flap_threshold: i64 = env.get_var("NODE_FLAP_THRESHOLD")
+        .unwrap_or_else(|| "3".to_string())
+        .parse()
+        .unwrap_or(3);
+
+    let endpoint_churn_window_minutes: i64 = env.get_var("ENDPOINT_CHURN_WINDOW_MINUTES")
+        .unwrap_or_else(|| "60".to_string())
+        .parse()
+        .unwrap_or(60);
+
+    let endpoint_churn_threshold: i64 = env.get_var("ENDPOINT_CHURN_THRESHOLD")
+        .unwrap_or_else(|| "10".to_string())
+        .parse()
+        .unwrap_or(10);
+
+    let dual_stack_check = env.get_var("DUAL_STACK_CHECK")
+        .map(|v| matches!(v.as_str(), "1" | "true" | "TRUE" | "True"))
+        .unwrap_or(false);
+
+    // Unset: check the common kube-proxy/CNI DaemonSets; set to "" to disable the check
+    let network_agent_daemonsets: Vec<String> = env.get_var("NETWORK_AGENT_DAEMONSETS")
+        .unwrap_or_else(|| DEFAULT_NETWORK_AGENT_DAEMONSETS.to_string())
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    let operator_check = env.get_var("OPERATOR_CHECK")
+        .map(|v| matches!(v.as_str(), "1" | "true" | "TRUE" | "True"))
+        .unwrap_or(false);
+
+    let crash_log_lines: usize = env.get_var("CRASH_LOG_LINES")
+        .unwrap_or_else(|| "0".to_string())
+        .parse()
+        .context("Invalid CRASH_LOG_LINES")?;
+
+    let ownership_file = env.get_var("OWNERSHIP_FILE").filter(|p| !p.is_empty());
+
+    let issue_labels: Vec<String> = env.get_var("ISSUE_LABELS")
+        .unwrap_or_default()
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    let section_order: Vec<String> = env.get_var("SECTION_ORDER")
+        .unwrap_or_default()
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    validate_section_order(&section_order)?;
+
+    let severity_rules = env.get_var("SEVERITY_RULES")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(parse_severity_rule)
+        .collect::<Result<Vec<_>>>()?;
+
+    let priority_top_n: usize = env.get_var("PRIORITY_TOP_N")
+        .unwrap_or_else(|| "5".to_string())
+        .parse()
+        .unwrap_or(5);
+
+    let mut config = Config {
         namespaces,
         threshold_percent,
         slack_webhook_url,
         restart_grace_minutes,
         pending_grace_minutes,
+        terminating_grace_minutes,
         cluster_name,
         datacenter_name,
         fail_if_no_metrics,
-    })
+        rbac_scope,
+        namespace_timeout_seconds,
+        run_timeout_seconds,
+        memory_warn_percent,
+        metrics_staleness_minutes,
+        node_pod_count_all_namespaces,
+        node_disk_forecast,
+        node_disk_margin_percent,
+        recommendations_history_path,
+        limit_request_ratio_threshold,
+        pod_churn_window_minutes,
+        pod_churn_threshold,
+        event_flood_window_minutes,
+        event_flood_threshold,
+        job_backoff_warning_percent,
+        cronjob_active_jobs_threshold,
+        finished_objects_threshold,
+        readiness_flap_window_minutes,
+        readiness_flap_threshold,
+        object_count_check,
+        object_count_threshold,
+        object_count_growth_percent,
+        node_flap_window_minutes,
+        node_flap_threshold,
+        endpoint_churn_window_minutes,
+        endpoint_churn_threshold,
+        dual_stack_check,
+        network_agent_daemonsets,
+        operator_check,
+        omit_empty_sections,
+        section_order,
+        priority_top_n,
+        severity_rules,
+        crash_log_lines,
+        issue_labels,
+        ownership_file,
+        suppressions_enabled,
+        state_file,
+        notify_resolved,
+        allow_api_writes,
+        audit_log_file,
+        api_recording,
+        sink_timeout_seconds,
+        sink_failure_threshold,
+        sink_cooldown_minutes,
+        webhook_ca_bundle,
+        webhook_signing_secret,
+        webhook_signing_algorithm,
+        email,
+        pagerduty_routing_key,
+        pagerduty_sections,
+        slack_bot,
+        telegram,
+        pushgateway,
+        hook_commands,
+        hook_timeout_seconds,
+        oncall,
+        slack_mention,
+        slack_section_mentions,
+        generic_webhook_url,
+        generic_webhook_headers,
+        metrics_textfile,
+        csv_export,
+        output_format,
+        html_report_file,
+        metrics_listen_addr,
+        run_mode,
+        interval_seconds,
+        change_freeze_source,
+        redaction_patterns,
+        tenant: None,
+        clusters,
+        federation_webhook_url,
+        pod_security_level,
+        pod_security_namespace_levels,
+        namespace_quota_check,
+        limit_range_edge_percent,
+        custom_resource_checks,
+    };
+    if let Some(name) = tenant {
+        let path = env.get_var("TENANTS_FILE")
+            .ok_or_else(|| anyhow!("TENANTS_FILE must be set when TENANT is"))?;
+        let tenants = TenantsFile::load(Path::new(&path))?;
+        let spec = tenants.tenants.get(&name)
+            .ok_or_else(|| anyhow!("Unknown TENANT {} (not in {})", name, path))?;
+        apply_tenant(&mut config, &name, spec)?;
+    }
+    Ok(config)
 }
 
 #[cfg(test)]
@@ -118,9 +908,64 @@ mod tests {
             .with_var("SLACK_WEBHOOK_URL", "https://hooks.slack.com/test")
             .with_var("RESTART_GRACE_MINUTES", "10")
             .with_var("PENDING_GRACE_MINUTES", "15")
+            .with_var("TERMINATING_GRACE_MINUTES", "120")
             .with_var("CLUSTER_NAME", "test-cluster")
             .with_var("DATACENTER_NAME", "us-west-1")
-            .with_var("FAIL_IF_NO_METRICS", "false");
+            .with_var("FAIL_IF_NO_METRICS", "false")
+            .with_var("RBAC_SCOPE", "namespace")
+            .with_var("NAMESPACE_TIMEOUT_SECONDS", "30")
+            .with_var("RUN_TIMEOUT_SECONDS", "600")
+            .with_var("MEMORY_WARN_PERCENT", "90")
+            .with_var("METRICS_STALENESS_MINUTES", "15")
+            .with_var("NODE_POD_COUNT_ALL_NAMESPACES", "true")
+            .with_var("NODE_DISK_FORECAST", "true")
+            .with_var("NODE_DISK_MARGIN_PERCENT", "5")
+            .with_var("RECOMMENDATIONS_HISTORY_PATH", "/data/usage-history.json")
+            .with_var("LIMIT_REQUEST_RATIO_THRESHOLD", "4")
+            .with_var("POD_CHURN_WINDOW_MINUTES", "30")
+            .with_var("POD_CHURN_THRESHOLD", "20")
+            .with_var("EVENT_FLOOD_WINDOW_MINUTES", "15")
+            .with_var("EVENT_FLOOD_THRESHOLD", "200")
+            .with_var("JOB_BACKOFF_WARNING_PERCENT", "50")
+            .with_var("CRONJOB_ACTIVE_JOBS_THRESHOLD", "5")
+            .with_var("FINISHED_OBJECTS_THRESHOLD", "250")
+            .with_var("READINESS_FLAP_WINDOW_MINUTES", "30")
+            .with_var("READINESS_FLAP_THRESHOLD", "8")
+            .with_var("OBJECT_COUNT_CHECK", "true")
+            .with_var("OPERATOR_CHECK", "true")
+            .with_var("OBJECT_COUNT_THRESHOLD", "500000")
+            .with_var("OBJECT_COUNT_GROWTH_PERCENT", "25")
+            .with_var("NODE_FLAP_WINDOW_MINUTES", "120")
+            .with_var("NODE_FLAP_THRESHOLD", "2")
+            .with_var("ENDPOINT_CHURN_WINDOW_MINUTES", "15")
+            .with_var("ENDPOINT_CHURN_THRESHOLD", "20")
+            .with_var("DUAL_STACK_CHECK", "true")
+            .with_var("NETWORK_AGENT_DAEMONSETS", "kube-system/kube-proxy, kube-system/cilium")
+            .with_var("OMIT_EMPTY_SECTIONS", "true")
+            .with_var("SECTION_ORDER", "nodes, oom,restarts")
+            .with_var("CRASH_LOG_LINES", "30")
+            .with_var("PRIORITY_TOP_N", "3")
+            .with_var("CHANGE_FREEZE_SOURCE", "configmap:ops/change-freeze")
+            .with_var("ISSUE_LABELS", "team, app")
+            .with_var("OWNERSHIP_FILE", "/etc/kube-health-reporter/ownership.json")
+            .with_var("HEALTH_SUPPRESSIONS", "false")
+            .with_var("STATE_FILE", "/data/state.json")
+            .with_var("NOTIFY_RESOLVED", "true")
+            .with_var("AUDIT_LOG_FILE", "/data/audit.jsonl")
+            .with_var("SINK_TIMEOUT_SECONDS", "5")
+            .with_var("SINK_FAILURE_THRESHOLD", "2")
+            .with_var("SINK_COOLDOWN_MINUTES", "30")
+            .with_var("WEBHOOK_CA_BUNDLE", "/etc/ssl/proxy/ca.crt")
+            .with_var("WEBHOOK_SIGNING_SECRET", "s3cret")
+            .with_var("WEBHOOK_SIGNING_ALGORITHM", "sha512")
+            .with_var("CLUSTERS", "prod-eu, prod-us")
+            .with_var("FEDERATION_WEBHOOK_URL", "https://hooks.slack.com/central")
+            .with_var("POD_SECURITY_LEVEL", "baseline")
+            .with_var("NAMESPACE_QUOTA_CHECK", "true")
+            .with_var("LIMIT_RANGE_EDGE_PERCENT", "95")
+            .with_var("CUSTOM_RESOURCE_CHECKS", "kafka.strimzi.io/Kafka: Ready=True; cert-manager.io/v1/Certificate: Ready=True, Issuing=False")
+            .with_var("POD_SECURITY_NAMESPACE_LEVELS", "payments=restricted, legacy=privileged")
+            .with_var("REDACTION_PATTERNS", "AKIA[0-9A-Z]{16}\n\n  [\\w.]+@example\\.com  \n");
         
         let config = load_config_with_env(&env).unwrap();
         
@@ -129,9 +974,88 @@ mod tests {
         assert_eq!(config.slack_webhook_url, "https://hooks.slack.com/test");
         assert_eq!(config.restart_grace_minutes, 10);
         assert_eq!(config.pending_grace_minutes, 15);
+        assert_eq!(config.terminating_grace_minutes, 120);
         assert_eq!(config.cluster_name, Some("test-cluster".to_string()));
         assert_eq!(config.datacenter_name, Some("us-west-1".to_string()));
         assert_eq!(config.fail_if_no_metrics, false);
+        assert_eq!(config.rbac_scope, RbacScope::Namespace);
+        assert_eq!(
+            config.change_freeze_source,
+            Some(ChangeFreezeSource::ConfigMap { namespace: "ops".to_string(), name: "change-freeze".to_string() })
+        );
+        assert_eq!(config.namespace_timeout_seconds, 30);
+        assert_eq!(config.run_timeout_seconds, 600);
+        assert_eq!(config.memory_warn_percent, 90.0);
+        assert_eq!(config.metrics_staleness_minutes, 15);
+        assert!(config.node_pod_count_all_namespaces);
+        assert!(config.node_disk_forecast);
+        assert_eq!(config.node_disk_margin_percent, 5.0);
+        assert_eq!(config.recommendations_history_path.as_deref(), Some("/data/usage-history.json"));
+        assert_eq!(config.limit_request_ratio_threshold, 4.0);
+        assert_eq!(config.pod_churn_window_minutes, 30);
+        assert_eq!(config.pod_churn_threshold, 20);
+        assert_eq!(config.event_flood_window_minutes, 15);
+        assert_eq!(config.event_flood_threshold, 200);
+        assert_eq!(config.job_backoff_warning_percent, 50.0);
+        assert_eq!(config.cronjob_active_jobs_threshold, 5);
+        assert_eq!(config.finished_objects_threshold, 250);
+        assert_eq!(config.readiness_flap_window_minutes, 30);
+        assert_eq!(config.readiness_flap_threshold, 8);
+        assert!(config.object_count_check);
+        assert_eq!(config.object_count_threshold, 500_000);
+        assert_eq!(config.object_count_growth_percent, 25.0);
+        assert_eq!(config.node_flap_window_minutes, 120);
+        assert_eq!(config.node_flap_threshold, 2);
+        assert_eq!(config.endpoint_churn_window_minutes, 15);
+        assert_eq!(config.endpoint_churn_threshold, 20);
+        assert!(config.dual_stack_check);
+        assert_eq!(config.network_agent_daemonsets, vec!["kube-system/kube-proxy", "kube-system/cilium"]);
+        assert!(config.operator_check);
+        assert!(config.omit_empty_sections);
+        assert_eq!(config.section_order, vec!["nodes", "oom", "restarts"]);
+        assert_eq!(config.crash_log_lines, 30);
+        assert_eq!(config.priority_top_n, 3);
+        assert_eq!(config.issue_labels, vec!["team", "app"]);
+        assert_eq!(config.ownership_file.as_deref(), Some("/etc/kube-health-reporter/ownership.json"));
+        assert!(!config.suppressions_enabled);
+        assert_eq!(config.state_file.as_deref(), Some("/data/state.json"));
+        assert!(config.notify_resolved);
+        assert_eq!(config.audit_log_file.as_deref(), Some("/data/audit.jsonl"));
+        assert_eq!(config.sink_timeout_seconds, 5);
+        assert_eq!(config.sink_failure_threshold, 2);
+        assert_eq!(config.sink_cooldown_minutes, 30);
+        assert_eq!(config.webhook_ca_bundle.as_deref(), Some("/etc/ssl/proxy/ca.crt"));
+        assert_eq!(config.webhook_signing_secret.as_deref(), Some("s3cret"));
+        assert_eq!(config.webhook_signing_algorithm, SigningAlgorithm::Sha512);
+        assert_eq!(config.clusters, vec!["prod-eu", "prod-us"]);
+        assert_eq!(config.federation_webhook_url.as_deref(), Some("https://hooks.slack.com/central"));
+        assert_eq!(config.pod_security_level, PodSecurityLevel::Baseline);
+        assert!(config.namespace_quota_check);
+        assert_eq!(config.limit_range_edge_percent, 95.0);
+        assert_eq!(
+            config.custom_resource_checks,
+            vec![
+                CustomResourceCheck {
+                    group: "kafka.strimzi.io".to_string(),
+                    version: None,
+                    kind: "Kafka".to_string(),
+                    conditions: vec![("Ready".to_string(), "True".to_string())],
+                },
+                CustomResourceCheck {
+                    group: "cert-manager.io".to_string(),
+                    version: Some("v1".to_string()),
+                    kind: "Certificate".to_string(),
+                    conditions: vec![
+                        ("Ready".to_string(), "True".to_string()),
+                        ("Issuing".to_string(), "False".to_string()),
+                    ],
+                },
+            ]
+        );
+        assert_eq!(config.pod_security_namespace_levels.get("payments"), Some(&PodSecurityLevel::Restricted));
+        assert_eq!(config.pod_security_namespace_levels.get("legacy"), Some(&PodSecurityLevel::Privileged));
+        let patterns: Vec<&str> = config.redaction_patterns.iter().map(|r| r.as_str()).collect();
+        assert_eq!(patterns, vec!["AKIA[0-9A-Z]{16}", r"[\w.]+@example\.com"]);
     }
 
     #[test]
@@ -146,9 +1070,87 @@ mod tests {
         assert_eq!(config.threshold_percent, 85.0); // default
         assert_eq!(config.restart_grace_minutes, 5); // default
         assert_eq!(config.pending_grace_minutes, 5); // default
+        assert_eq!(config.terminating_grace_minutes, 60); // default
         assert_eq!(config.cluster_name, None); // default
         assert_eq!(config.datacenter_name, None); // default
         assert_eq!(config.fail_if_no_metrics, true); // default
+        assert_eq!(config.rbac_scope, RbacScope::Auto); // default
+        assert_eq!(config.namespace_timeout_seconds, 120); // default
+        assert_eq!(config.run_timeout_seconds, 0); // default
+        assert_eq!(config.memory_warn_percent, 80.0); // default
+        assert_eq!(config.metrics_staleness_minutes, 5); // default
+        assert!(!config.node_pod_count_all_namespaces); // default
+        assert!(!config.node_disk_forecast); // default
+        assert_eq!(config.node_disk_margin_percent, 10.0); // default
+        assert_eq!(config.recommendations_history_path, None); // default
+        assert_eq!(config.limit_request_ratio_threshold, 10.0); // default
+        assert_eq!(config.pod_churn_window_minutes, 60); // default
+        assert_eq!(config.pod_churn_threshold, 10); // default
+        assert_eq!(config.event_flood_window_minutes, 60); // default
+        assert_eq!(config.event_flood_threshold, 500); // default
+        assert_eq!(config.job_backoff_warning_percent, 60.0); // default
+        assert_eq!(config.cronjob_active_jobs_threshold, 3); // default
+        assert_eq!(config.finished_objects_threshold, 100); // default
+        assert_eq!(config.readiness_flap_window_minutes, 60); // default
+        assert_eq!(config.readiness_flap_threshold, 5); // default
+        assert!(!config.object_count_check); // default
+        assert_eq!(config.object_count_threshold, 100_000); // default
+        assert_eq!(config.object_count_growth_percent, 50.0); // default
+        assert_eq!(config.node_flap_window_minutes, 60); // default
+        assert_eq!(config.node_flap_threshold, 3); // default
+        assert_eq!(config.endpoint_churn_window_minutes, 60); // default
+        assert_eq!(config.endpoint_churn_threshold, 10); // default
+        assert!(!config.dual_stack_check); // default
+        assert!(config.network_agent_daemonsets.contains(&"kube-system/kube-proxy".to_string())); // default
+        assert!(!config.operator_check); // default
+        assert!(!config.omit_empty_sections); // default
+        assert!(config.section_order.is_empty()); // default
+        assert_eq!(config.crash_log_lines, 0); // default
+        assert_eq!(config.priority_top_n, 5); // default
+        assert!(config.severity_rules.is_empty()); // default
+        assert!(config.issue_labels.is_empty()); // default
+        assert_eq!(config.ownership_file, None); // default
+        assert!(config.suppressions_enabled); // default
+        assert_eq!(config.state_file, None); // default
+        assert!(!config.notify_resolved); // default
+        assert!(!config.allow_api_writes); // default
+        assert_eq!(config.audit_log_file, None); // default
+        assert_eq!(config.api_recording, None); // default
+        assert_eq!(config.sink_timeout_seconds, 10); // default
+        assert_eq!(config.sink_failure_threshold, 3); // default
+        assert_eq!(config.sink_cooldown_minutes, 60); // default
+        assert_eq!(config.webhook_ca_bundle, None); // default
+        assert_eq!(config.webhook_signing_secret, None); // default
+        assert_eq!(config.email, None); // default
+        assert_eq!(config.slack_bot, None); // default
+        assert_eq!(config.telegram, None); // default
+        assert_eq!(config.pushgateway, None); // default
+        assert_eq!(config.hook_commands, HookCommands::default()); // default
+        assert_eq!(config.hook_timeout_seconds, 30); // default
+        assert_eq!(config.oncall, None); // default
+        assert_eq!(config.slack_mention, None); // default
+        assert!(config.slack_section_mentions.is_empty()); // default
+        assert_eq!(config.pagerduty_routing_key, None); // default
+        assert!(config.pagerduty_sections.is_empty()); // default
+        assert_eq!(config.generic_webhook_url, None); // default
+        assert!(config.generic_webhook_headers.is_empty()); // default
+        assert_eq!(config.metrics_textfile, None); // default
+        assert_eq!(config.csv_export, None); // default
+        assert_eq!(config.output_format, None); // default
+        assert_eq!(config.html_report_file, None); // default
+        assert_eq!(config.metrics_listen_addr, None); // default
+        assert_eq!(config.run_mode, RunMode::Once); // default
+        assert_eq!(config.interval_seconds, 300); // default
+        assert_eq!(config.change_freeze_source, None); // default
+        assert_eq!(config.webhook_signing_algorithm, SigningAlgorithm::Sha256); // default
+        assert!(config.redaction_patterns.is_empty()); // default
+        assert!(config.clusters.is_empty()); // default
+        assert_eq!(config.federation_webhook_url, None); // default
+        assert_eq!(config.pod_security_level, PodSecurityLevel::Privileged); // default
+        assert!(config.pod_security_namespace_levels.is_empty()); // default
+        assert!(!config.namespace_quota_check); // default
+        assert_eq!(config.limit_range_edge_percent, 90.0); // default
+        assert!(config.custom_resource_checks.is_empty()); // default
     }
 
     #[test]
@@ -170,6 +1172,299 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("SLACK_WEBHOOK_URL"));
     }
 
+    #[test]
+    fn test_config_loading_pagerduty() {
+        let env = MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("SLACK_WEBHOOK_URL", "https://hooks.slack.com/test")
+            .with_var("PAGERDUTY_ROUTING_KEY", "R0UT1NG");
+        let error = load_config_with_env(&env).unwrap_err().to_string();
+        assert!(error.contains("PAGERDUTY_SECTIONS must be set"));
+
+        let env = env.with_var("PAGERDUTY_SECTIONS", "oom, failed_jobs");
+        let config = load_config_with_env(&env).unwrap();
+        assert_eq!(config.pagerduty_routing_key.as_deref(), Some("R0UT1NG"));
+        assert_eq!(config.pagerduty_sections, vec!["oom", "failed_jobs"]);
+
+        // Cluster-wide findings have no per-object dedup key
+        let invalid = env.with_var("PAGERDUTY_SECTIONS", "oom,nodes");
+        let error = load_config_with_env(&invalid).unwrap_err().to_string();
+        assert!(error.contains("Invalid PAGERDUTY_SECTIONS: 'nodes' cannot page"));
+    }
+
+    #[test]
+    fn test_config_loading_slack_bot() {
+        let env = MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("SLACK_BOT_TOKEN", "xoxb-123")
+            .with_var("SLACK_CHANNEL", "C0123ABC");
+        let config = load_config_with_env(&env).unwrap();
+        assert_eq!(config.slack_webhook_url, "");
+        assert_eq!(
+            config.slack_bot,
+            Some(SlackBotSettings { token: "xoxb-123".to_string(), channel: "C0123ABC".to_string() })
+        );
+
+        let env = MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("SLACK_BOT_TOKEN", "xoxb-123")
+            .with_var("SLACK_CHANNEL", "C0123ABC")
+            .with_var("SLACK_WEBHOOK_URL", "https://hooks.slack.com/test");
+        let error = load_config_with_env(&env).unwrap_err().to_string();
+        assert!(error.contains("Only one of SLACK_WEBHOOK_URL and SLACK_BOT_TOKEN"));
+    }
+
+    #[test]
+    fn test_config_loading_telegram() {
+        let env = MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("TELEGRAM_BOT_TOKEN", "123456:ABC-DEF")
+            .with_var("TELEGRAM_CHAT_ID", " -1001234567890 ");
+        let config = load_config_with_env(&env).unwrap();
+        assert_eq!(config.slack_webhook_url, "");
+        assert_eq!(
+            config.telegram,
+            Some(TelegramSettings { bot_token: "123456:ABC-DEF".to_string(), chat_id: "-1001234567890".to_string() })
+        );
+
+        let env = MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("TELEGRAM_BOT_TOKEN", "123456:ABC-DEF");
+        let error = load_config_with_env(&env).unwrap_err().to_string();
+        assert!(error.contains("TELEGRAM_CHAT_ID must be set"));
+    }
+
+    #[test]
+    fn test_config_loading_pushgateway() {
+        let env = || MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("PUSHGATEWAY_URL", "http://pushgateway.monitoring:9091");
+        // Slack is optional once the Pushgateway is configured
+        let config = load_config_with_env(&env()).unwrap();
+        assert_eq!(config.slack_webhook_url, "");
+        assert_eq!(
+            config.pushgateway,
+            Some(PushgatewaySettings {
+                url: "http://pushgateway.monitoring:9091".to_string(),
+                job: "kube-health-reporter".to_string(),
+                instance: None,
+            })
+        );
+
+        let config = load_config_with_env(&env().with_var("PUSHGATEWAY_JOB", "khr").with_var("PUSHGATEWAY_INSTANCE", " prod-eu ")).unwrap();
+        let pushgateway = config.pushgateway.unwrap();
+        assert_eq!((pushgateway.job.as_str(), pushgateway.instance.as_deref()), ("khr", Some("prod-eu")));
+
+        let error = load_config_with_env(&env().with_var("PUSHGATEWAY_INSTANCE", "eu/prod")).unwrap_err().to_string();
+        assert!(error.contains("PUSHGATEWAY_INSTANCE must not contain '/'"));
+    }
+
+    #[test]
+    fn test_config_loading_hooks() {
+        let env = MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("SLACK_WEBHOOK_URL", "https://hooks.slack.com/test")
+            .with_var("HOOK_PRE_COLLECTION", "/hooks/check-maintenance.sh")
+            .with_var("HOOK_PRE_SEND", " /hooks/veto-weekends.sh ")
+            .with_var("HOOK_POST_REPORT", "")
+            .with_var("HOOK_TIMEOUT_SECONDS", "5");
+        let config = load_config_with_env(&env).unwrap();
+        assert_eq!(
+            config.hook_commands,
+            HookCommands {
+                pre_collection: Some("/hooks/check-maintenance.sh".to_string()),
+                post_report: None,
+                pre_send: Some("/hooks/veto-weekends.sh".to_string()),
+            }
+        );
+        assert_eq!(config.hook_timeout_seconds, 5);
+    }
+
+    #[test]
+    fn test_config_loading_oncall() {
+        let env = MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("SLACK_WEBHOOK_URL", "https://hooks.slack.com/test")
+            .with_var("ONCALL_PROVIDER", "opsgenie")
+            .with_var("ONCALL_SCHEDULE_ID", "sre-primary");
+        let error = load_config_with_env(&env).unwrap_err().to_string();
+        assert!(error.contains("ONCALL_API_TOKEN or ONCALL_API_TOKEN_FILE must be set"));
+
+        let env = env
+            .with_var("ONCALL_API_TOKEN", "g3n13")
+            .with_var("ONCALL_SLACK_USERS", "Jane@example.com=U0123ABC, joe@example.com=U0456DEF")
+            .with_var("SLACK_MENTION", "<!subteam^S0123>");
+        let config = load_config_with_env(&env).unwrap();
+        let oncall = config.oncall.unwrap();
+        assert_eq!(oncall.provider, OnCallProvider::Opsgenie);
+        assert_eq!(oncall.schedule_id, "sre-primary");
+        assert_eq!(oncall.api_token, "g3n13");
+        assert_eq!(oncall.api_url, None);
+        assert_eq!(oncall.slack_users.get("jane@example.com").map(String::as_str), Some("U0123ABC"));
+        assert_eq!(config.slack_mention.as_deref(), Some("<!subteam^S0123>"));
+
+        let invalid = env.with_var("ONCALL_SLACK_USERS", "jane@example.com");
+        let error = load_config_with_env(&invalid).unwrap_err().to_string();
+        assert!(error.contains("Invalid ONCALL_SLACK_USERS entry 'jane@example.com'"));
+    }
+
+    #[test]
+    fn test_config_loading_severity_rules() {
+        let env = || MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("SLACK_WEBHOOK_URL", "https://hooks.slack.com/test");
+
+        let config = load_config_with_env(&env().with_var("SEVERITY_RULES", "usage>95%=critical, pending>2h=critical,restarts=info")).unwrap();
+        assert_eq!(
+            config.severity_rules,
+            vec![
+                SeverityRule { section: "usage".to_string(), threshold: Some(95.0), severity: Severity::Critical },
+                SeverityRule { section: "pending".to_string(), threshold: Some(120.0), severity: Severity::Critical },
+                SeverityRule { section: "restarts".to_string(), threshold: None, severity: Severity::Info },
+            ]
+        );
+
+        let error = load_config_with_env(&env().with_var("SEVERITY_RULES", "oom>3=critical")).unwrap_err().to_string();
+        assert!(error.contains("section 'oom' has no threshold"));
+        let error = load_config_with_env(&env().with_var("SEVERITY_RULES", "pending>30m=urgent")).unwrap_err().to_string();
+        assert!(error.contains("Invalid SEVERITY_RULES severity 'urgent'"));
+        let error = load_config_with_env(&env().with_var("SEVERITY_RULES", "pending>soon=critical")).unwrap_err().to_string();
+        assert!(error.contains("Invalid SEVERITY_RULES entry 'pending>soon=critical'"));
+    }
+
+    #[test]
+    fn test_config_loading_slack_section_mentions() {
+        let env = || MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("SLACK_WEBHOOK_URL", "https://hooks.slack.com/test");
+
+        let config = load_config_with_env(&env().with_var("SLACK_SECTION_MENTIONS", "nodes=S0123ABC, oom=U0456DEF <!here>")).unwrap();
+        assert_eq!(config.slack_section_mentions.get("nodes").map(String::as_str), Some("<!subteam^S0123ABC>"));
+        assert_eq!(config.slack_section_mentions.get("oom").map(String::as_str), Some("<@U0456DEF> <!here>"));
+
+        let error = load_config_with_env(&env().with_var("SLACK_SECTION_MENTIONS", "oomkilled=U0456DEF")).unwrap_err().to_string();
+        assert!(error.contains("unknown section 'oomkilled'"));
+        let error = load_config_with_env(&env().with_var("SLACK_SECTION_MENTIONS", "oom=")).unwrap_err().to_string();
+        assert!(error.contains("Invalid SLACK_SECTION_MENTIONS entry 'oom='"));
+    }
+
+    #[test]
+    fn test_config_loading_generic_webhook() {
+        let env = MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("GENERIC_WEBHOOK_URL", "https://incidents.internal/ingest")
+            .with_var("GENERIC_WEBHOOK_HEADERS", "Authorization: Bearer s3cr3t\n\nX-Source: kube-health-reporter\n");
+
+        // Slack is optional once the JSON webhook is configured
+        let config = load_config_with_env(&env).unwrap();
+        assert_eq!(config.slack_webhook_url, "");
+        assert_eq!(config.generic_webhook_url.as_deref(), Some("https://incidents.internal/ingest"));
+        assert_eq!(
+            config.generic_webhook_headers,
+            vec![
+                ("Authorization".to_string(), "Bearer s3cr3t".to_string()),
+                ("X-Source".to_string(), "kube-health-reporter".to_string()),
+            ]
+        );
+
+        // Likewise for the OpenMetrics textfile
+        let textfile_only = MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("METRICS_TEXTFILE", "/textfile/kube_health_reporter.prom");
+        let config = load_config_with_env(&textfile_only).unwrap();
+        assert_eq!(config.slack_webhook_url, "");
+        assert_eq!(config.metrics_textfile.as_deref(), Some("/textfile/kube_health_reporter.prom"));
+
+        let csv_only = MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("CSV_EXPORT", "-");
+        let config = load_config_with_env(&csv_only).unwrap();
+        assert_eq!(config.slack_webhook_url, "");
+        assert_eq!(config.csv_export.as_deref(), Some("-"));
+
+        let html_only = MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("HTML_REPORT_FILE", "/srv/health/index.html");
+        let config = load_config_with_env(&html_only).unwrap();
+        assert_eq!(config.slack_webhook_url, "");
+        assert_eq!(config.html_report_file.as_deref(), Some("/srv/health/index.html"));
+
+        let stdout_only = |format: &str| MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("OUTPUT_FORMAT", format);
+        let config = load_config_with_env(&stdout_only("json")).unwrap();
+        assert_eq!(config.slack_webhook_url, "");
+        assert_eq!(config.output_format, Some(OutputFormat::Json));
+        let config = load_config_with_env(&stdout_only("yaml")).unwrap();
+        assert_eq!(config.output_format, Some(OutputFormat::Yaml));
+        let error = load_config_with_env(&stdout_only("toml")).unwrap_err().to_string();
+        assert!(error.contains("Invalid OUTPUT_FORMAT: toml (expected json or yaml)"));
+        let error = load_config_with_env(&stdout_only("json").with_var("CSV_EXPORT", "-")).unwrap_err().to_string();
+        assert!(error.contains("cannot both write to stdout"));
+
+        let invalid = env.with_var("GENERIC_WEBHOOK_HEADERS", "Authorization Bearer s3cr3t");
+        let error = load_config_with_env(&invalid).unwrap_err().to_string();
+        assert!(error.contains("Invalid GENERIC_WEBHOOK_HEADERS line 1"));
+        // The value is a secret and must not end up in logs
+        assert!(!error.contains("s3cr3t"));
+    }
+
+    #[test]
+    fn test_config_loading_email() {
+        let env = MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("SMTP_HOST", "relay.internal")
+            .with_var("SMTP_USERNAME", "reporter")
+            .with_var("SMTP_PASSWORD", "vault:secret/data/smtp#password")
+            .with_var("EMAIL_FROM", "kube-health@example.com")
+            .with_var("EMAIL_TO", "sre@example.com, platform@example.com");
+
+        // Slack is optional once email is configured
+        let config = load_config_with_env(&env).unwrap();
+        assert_eq!(config.slack_webhook_url, "");
+        let email = config.email.unwrap();
+        assert_eq!(email.smtp_host, "relay.internal");
+        assert_eq!(email.smtp_port, 587); // default for STARTTLS
+        assert_eq!(email.smtp_tls, SmtpTls::StartTls);
+        assert_eq!(email.smtp_username.as_deref(), Some("reporter"));
+        assert_eq!(email.smtp_password.as_deref(), Some("vault:secret/data/smtp#password"));
+        assert_eq!(email.to, vec!["sre@example.com", "platform@example.com"]);
+
+        let env = env.with_var("SMTP_TLS", "none").with_var("SMTP_PORT", "2525");
+        let email = load_config_with_env(&env).unwrap().email.unwrap();
+        assert_eq!((email.smtp_tls, email.smtp_port), (SmtpTls::None, 2525));
+
+        let invalid = env.with_var("SMTP_TLS", "ssl");
+        assert!(load_config_with_env(&invalid).unwrap_err().to_string().contains("Invalid SMTP_TLS"));
+
+        let no_recipients = MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("SMTP_HOST", "relay.internal")
+            .with_var("EMAIL_FROM", "kube-health@example.com");
+        assert!(load_config_with_env(&no_recipients).unwrap_err().to_string().contains("EMAIL_TO"));
+    }
+
+    #[test]
+    fn test_config_secret_from_file() {
+        let path = std::env::temp_dir().join(format!("khr-webhook-{}", std::process::id()));
+        std::fs::write(&path, "https://hooks.slack.com/from-file\n").unwrap();
+        let env = MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("SLACK_WEBHOOK_URL_FILE", path.display().to_string());
+
+        let config = load_config_with_env(&env).unwrap();
+        assert_eq!(config.slack_webhook_url, "https://hooks.slack.com/from-file");
+
+        let both = env.with_var("SLACK_WEBHOOK_URL", "https://hooks.slack.com/test");
+        assert!(load_config_with_env(&both).unwrap_err().to_string().contains("Only one of"));
+        std::fs::remove_file(&path).unwrap();
+
+        let missing = MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("SLACK_WEBHOOK_URL_FILE", "/nonexistent/webhook");
+        assert!(load_config_with_env(&missing).is_err());
+    }
+
     #[test]
     fn test_config_loading_invalid_threshold() {
         let env = MockEnvironment::new()
@@ -182,6 +1477,144 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("THRESHOLD_PERCENT"));
     }
 
+    #[test]
+    fn test_config_loading_invalid_section_order() {
+        let env = MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("SLACK_WEBHOOK_URL", "https://hooks.slack.com/test")
+            .with_var("SECTION_ORDER", "nodes,bogus");
+
+        let result = load_config_with_env(&env);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("unknown section 'bogus'"));
+    }
+
+    #[test]
+    fn test_config_loading_tenant() {
+        let path = std::env::temp_dir().join(format!("khr-tenants-{}.json", std::process::id()));
+        std::fs::write(&path, r#"{"tenants": {"payments": {"namespaces": ["payments"], "threshold_percent": 70}}}"#).unwrap();
+        let env = MockEnvironment::new()
+            .with_var("SLACK_WEBHOOK_URL", "https://hooks.slack.com/test")
+            .with_var("TENANTS_FILE", path.display().to_string())
+            .with_var("TENANT", "payments");
+
+        // NAMESPACES is not needed with a tenant
+        let config = load_config_with_env(&env).unwrap();
+        assert_eq!(config.namespaces, vec!["payments"]);
+        assert_eq!(config.threshold_percent, 70.0);
+        assert_eq!(config.tenant.as_deref(), Some("payments"));
+
+        let unknown = env.with_var("TENANT", "billing");
+        assert!(load_config_with_env(&unknown).unwrap_err().to_string().contains("Unknown TENANT billing"));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_config_loading_invalid_redaction_pattern() {
+        let env = MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("SLACK_WEBHOOK_URL", "https://hooks.slack.com/test")
+            .with_var("REDACTION_PATTERNS", "token-\\d+\n(unclosed");
+
+        let result = load_config_with_env(&env);
+        assert!(result.unwrap_err().to_string().contains("Invalid REDACTION_PATTERNS entry '(unclosed'"));
+    }
+
+    #[test]
+    fn test_config_loading_invalid_pod_security_level() {
+        let env = MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("SLACK_WEBHOOK_URL", "https://hooks.slack.com/test")
+            .with_var("POD_SECURITY_NAMESPACE_LEVELS", "payments=strict");
+        let result = load_config_with_env(&env);
+        assert!(result.unwrap_err().to_string().contains("Invalid POD_SECURITY_NAMESPACE_LEVELS: strict"));
+
+        let env = env.with_var("POD_SECURITY_NAMESPACE_LEVELS", "payments");
+        let result = load_config_with_env(&env);
+        assert!(result.unwrap_err().to_string().contains("expected namespace=level"));
+    }
+
+    #[test]
+    fn test_config_loading_invalid_custom_resource_checks() {
+        for value in ["Kafka: Ready=True", "kafka.strimzi.io/Kafka", "kafka.strimzi.io/Kafka: Ready"] {
+            let env = MockEnvironment::new()
+                .with_var("NAMESPACES", "default")
+                .with_var("SLACK_WEBHOOK_URL", "https://hooks.slack.com/test")
+                .with_var("CUSTOM_RESOURCE_CHECKS", value);
+            let result = load_config_with_env(&env);
+            assert!(result.unwrap_err().to_string().contains("Invalid CUSTOM_RESOURCE_CHECKS entry"), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_config_loading_change_freeze_source() {
+        let env = |value: &str| MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("SLACK_WEBHOOK_URL", "https://hooks.slack.com/test")
+            .with_var("CHANGE_FREEZE_SOURCE", value);
+        let config = load_config_with_env(&env("namespace:ops")).unwrap();
+        assert_eq!(config.change_freeze_source, Some(ChangeFreezeSource::Namespace("ops".to_string())));
+        let config = load_config_with_env(&env("https://freeze.internal/status?token=abc")).unwrap();
+        assert_eq!(
+            config.change_freeze_source,
+            Some(ChangeFreezeSource::Http("https://freeze.internal/status?token=abc".to_string()))
+        );
+
+        for value in ["ops/change-freeze", "configmap:change-freeze", "secret:ops/freeze"] {
+            let result = load_config_with_env(&env(value));
+            assert!(result.unwrap_err().to_string().contains("Invalid CHANGE_FREEZE_SOURCE"), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_config_loading_api_recording() {
+        let env = || MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("SLACK_WEBHOOK_URL", "https://hooks.slack.com/test");
+        let config = load_config_with_env(&env().with_var("API_RECORD_DIR", "/tmp/recording")).unwrap();
+        assert_eq!(config.api_recording, Some(ApiRecording::Record("/tmp/recording".to_string())));
+        let config = load_config_with_env(&env().with_var("API_REPLAY_DIR", "/tmp/recording")).unwrap();
+        assert_eq!(config.api_recording, Some(ApiRecording::Replay("/tmp/recording".to_string())));
+
+        let both = env().with_var("API_RECORD_DIR", "/tmp/a").with_var("API_REPLAY_DIR", "/tmp/b");
+        assert!(load_config_with_env(&both).unwrap_err().to_string().contains("Only one of API_RECORD_DIR"));
+    }
+
+    #[test]
+    fn test_config_loading_metrics_exporter() {
+        let env = || MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("METRICS_LISTEN_ADDR", "0.0.0.0:9090");
+        // Slack is optional once the exporter is enabled, which runs as a daemon
+        let config = load_config_with_env(&env()).unwrap();
+        assert_eq!(config.slack_webhook_url, "");
+        assert_eq!(config.metrics_listen_addr, Some("0.0.0.0:9090".parse().unwrap()));
+        assert_eq!(config.run_mode, RunMode::Daemon);
+
+        let error = load_config_with_env(&env().with_var("METRICS_LISTEN_ADDR", ":9090")).unwrap_err().to_string();
+        assert!(error.contains("Invalid METRICS_LISTEN_ADDR: :9090"));
+        let error = load_config_with_env(&env().with_var("RUN_MODE", "once")).unwrap_err().to_string();
+        assert!(error.contains("METRICS_LISTEN_ADDR requires RUN_MODE=daemon"));
+    }
+
+    #[test]
+    fn test_config_loading_daemon() {
+        let env = || MockEnvironment::new()
+            .with_var("NAMESPACES", "default")
+            .with_var("SLACK_WEBHOOK_URL", "https://hooks.slack.com/test")
+            .with_var("RUN_MODE", "daemon");
+        let config = load_config_with_env(&env().with_var("INTERVAL_SECONDS", "60")).unwrap();
+        assert_eq!(config.run_mode, RunMode::Daemon);
+        assert_eq!(config.interval_seconds, 60);
+
+        let error = load_config_with_env(&env().with_var("INTERVAL_SECONDS", "0")).unwrap_err().to_string();
+        assert!(error.contains("Invalid INTERVAL_SECONDS"));
+        let error = load_config_with_env(&env().with_var("RUN_MODE", "forever")).unwrap_err().to_string();
+        assert!(error.contains("Invalid RUN_MODE: forever (expected once or daemon)"));
+        let error = load_config_with_env(&env().with_var("CLUSTERS", "prod-eu")).unwrap_err().to_string();
+        assert!(error.contains("RUN_MODE=daemon is not supported with CLUSTERS"));
+    }
+
     #[test]
     fn test_namespace_parsing() {
         // Test various namespace formats
@@ -235,6 +1668,27 @@ mod tests {
         assert!(config.fail_if_no_metrics);
     }
 
+    #[test]
+    fn test_config_loading_invalid_numbers() {
+        let keys = [
+            "SINK_FAILURE_THRESHOLD",
+            "SINK_COOLDOWN_MINUTES",
+            "CRASH_LOG_LINES",
+            "EVENT_FLOOD_WINDOW_MINUTES",
+            "EVENT_FLOOD_THRESHOLD",
+            "POD_CHURN_WINDOW_MINUTES",
+            "POD_CHURN_THRESHOLD",
+        ];
+        for key in keys {
+            let env = MockEnvironment::new()
+                .with_var("NAMESPACES", "default")
+                .with_var("SLACK_WEBHOOK_URL", "https://hooks.slack.com/test")
+                .with_var(key, "soon");
+            let error = load_config_with_env(&env).unwrap_err();
+            assert_eq!(error.to_string(), format!("Invalid {}", key));
+        }
+    }
+
     #[test]
     fn test_numeric_parsing_with_invalid_values() {
         // Test invalid grace minutes (should use defaults)
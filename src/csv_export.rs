@@ -0,0 +1,211 @@
+//! Findings as CSV, one row per issue, for teams reviewing them in a spreadsheet.
+
+use anyhow::{Context, Result};
+use std::io::Write as _;
+
+use crate::report::priority::{severity, NODE_SEVERITY};
+use crate::report::HealthReport;
+use crate::webhook::report_document;
+
+pub const CSV_HEADER: &[&str] = &["namespace", "kind", "name", "category", "severity", "since", "details"];
+
+/// Section, the field naming the object, and its kind; an empty kind is taken from the
+/// `Kind/name` form of the name or from the finding's own `kind` field
+const SECTION_OBJECTS: &[(&str, &str, &str)] = &[
+    ("usage", "pod", "Pod"),
+    ("restarts", "pod", "Pod"),
+    ("pending", "pod", "Pod"),
+    ("failed", "pod", "Pod"),
+    ("unready", "pod", "Pod"),
+    ("oom", "pod", "Pod"),
+    ("nodes", "name", "Node"),
+    ("node_flapping", "name", "Node"),
+    ("node_utilization", "name", "Node"),
+    ("volumes", "pod", "Pod"),
+    ("failed_jobs", "job", "Job"),
+    ("missed_cronjobs", "cronjob", "CronJob"),
+    ("readiness_flapping", "pod", "Pod"),
+    ("endpoint_churn", "service", "Service"),
+    ("dual_stack", "object", ""),
+    ("missing_config", "pod", "Pod"),
+    ("volume_scheduling", "pod", "Pod"),
+    ("network_agents", "node", "Node"),
+    ("operators", "operator", "Operator"),
+    ("node_disk", "name", "Node"),
+    ("object_counts", "resource", "Resource"),
+    ("job_backoff", "job", "Job"),
+    ("cronjob_concurrency", "cronjob", "CronJob"),
+    ("pod_churn", "workload", ""),
+    ("event_flood", "namespace", "Namespace"),
+    ("stuck_terminating", "object", ""),
+    ("custom_resources", "resource", ""),
+    ("stale_metrics", "pod", "Pod"),
+    ("finished_objects", "namespace", "Namespace"),
+    ("resource_hygiene", "workload", ""),
+    ("pod_security", "workload", ""),
+    ("namespace_quotas", "namespace", "Namespace"),
+    ("limit_range_conflicts", "workload", ""),
+];
+
+/// Fields holding when a finding started, most specific first
+const SINCE_FIELDS: &[&str] = &[
+    "since", "last_oom_time", "last_restart_time", "last_failure_time", "last_schedule_time", "last_transition",
+    "oldest_active_since",
+];
+
+/// Fields that are columns of their own or too large for a cell
+const SKIPPED_FIELDS: &[&str] = &["namespace", "labels", "blast_radius", "log_tail"];
+
+/// Every finding of the report as a row of `CSV_HEADER`, in section order. `severity`
+/// is `critical` for findings escalated by a change freeze; `since` is the first time
+/// the issue was seen when state is kept, else when the finding says it started;
+/// `details` lists the remaining fields as `key=value` pairs. Strings carry the
+/// redaction of the report document.
+pub fn render_csv(report: &HealthReport) -> String {
+    let document = report_document(report);
+    let mut out = String::new();
+    push_row(&mut out, CSV_HEADER.iter().copied());
+    for (section, name_field, kind) in SECTION_OBJECTS {
+        let Some(findings) = document["sections"][section].as_array() else {
+            continue;
+        };
+        for finding in findings {
+            let Some(fields) = finding.as_object() else {
+                continue;
+            };
+            let namespace = fields.get("namespace").and_then(|v| v.as_str()).unwrap_or_default();
+            let object = fields.get(*name_field).and_then(|v| v.as_str()).unwrap_or_default();
+            let (kind, name) = match (kind.is_empty(), object.split_once('/')) {
+                (true, Some((kind, name))) => (kind, name),
+                (true, None) => (fields.get("kind").and_then(|v| v.as_str()).unwrap_or_default(), object),
+                (false, _) => (*kind, object),
+            };
+            let fingerprint = format!("{}/{}/{}", section, namespace, object);
+            let severity = if report.is_escalated(&fingerprint) { "critical" } else { severity_label(section) };
+
+            let since_field = SINCE_FIELDS.iter().copied().find(|f| fields.get(*f).is_some_and(|v| v.is_string()));
+            let since = match report.issue_history.get(&fingerprint) {
+                Some(history) => history.first_seen.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                None => since_field.and_then(|f| fields[f].as_str()).unwrap_or_default().to_string(),
+            };
+            let details = fields
+                .iter()
+                .filter(|(key, value)| {
+                    !value.is_null()
+                        && !SKIPPED_FIELDS.contains(&key.as_str())
+                        && key.as_str() != *name_field
+                        && Some(key.as_str()) != since_field
+                })
+                .map(|(key, value)| format!("{}={}", key, cell_value(value)))
+                .collect::<Vec<_>>()
+                .join("; ");
+
+            push_row(&mut out, [namespace, kind, name, *section, severity, since.as_str(), details.as_str()]);
+        }
+    }
+    out
+}
+
+/// Write `contents` to `target`, or to stdout when it is `-`
+pub fn write_csv(target: &str, contents: &str) -> Result<()> {
+    if target == "-" {
+        let mut stdout = std::io::stdout().lock();
+        stdout.write_all(contents.as_bytes()).context("Failed to write CSV to stdout")?;
+        return stdout.flush().context("Failed to write CSV to stdout");
+    }
+    std::fs::write(target, contents).with_context(|| format!("Failed to write {}", target))
+}
+
+/// `critical` to `low` by the weight `priority` gives the section; `info` for advisories
+fn severity_label(section: &str) -> &'static str {
+    let weight = if section == "nodes" { Some(NODE_SEVERITY) } else { severity(section) };
+    match weight {
+        Some(w) if w >= 5.0 => "critical",
+        Some(w) if w >= 3.0 => "high",
+        Some(w) if w >= 2.0 => "medium",
+        Some(_) => "low",
+        None => "info",
+    }
+}
+
+fn cell_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(items) if items.iter().all(|i| !i.is_array() && !i.is_object()) => {
+            items.iter().map(cell_value).collect::<Vec<_>>().join(",")
+        }
+        other => other.to_string(),
+    }
+}
+
+/// One RFC 4180 record: fields with commas, quotes or line breaks are quoted, quotes doubled
+fn push_row<'a>(out: &mut String, fields: impl IntoIterator<Item = &'a str>) {
+    let fields: Vec<String> = fields
+        .into_iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect();
+    out.push_str(&fields.join(","));
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Config, IssueHistory, OomKilledInfo, ProblematicNodeInfo, ResourceHygieneInfo, ResourceHygieneIssue};
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_render_csv() {
+        let mut report = HealthReport::new(Config::default());
+        report.pod_metrics.oom_killed.push(OomKilledInfo {
+            namespace: "payments".to_string(),
+            pod: "api-0".to_string(),
+            labels: Default::default(),
+            node_name: Some("node-a".to_string()),
+            zone: None,
+            blast_radius: None,
+            container: "main".to_string(),
+            last_oom_time: None,
+            restart_count: 3,
+            image: Some("example/api:1.0".to_string()),
+            log_tail: Some("killed\n".to_string()),
+        });
+        let first_seen = chrono::Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        report.issue_history.insert(
+            "oom/payments/api-0".to_string(),
+            IssueHistory { first_seen, last_seen: first_seen, occurrences: 2 },
+        );
+        report.cluster_metrics.problematic_nodes.push(ProblematicNodeInfo {
+            name: "node-a".to_string(),
+            os: "linux".to_string(),
+            conditions: vec!["MemoryPressure".to_string(), "DiskPressure".to_string()],
+            since: first_seen,
+        });
+        report.pod_metrics.resource_hygiene.push(ResourceHygieneInfo {
+            namespace: "payments".to_string(),
+            workload: "Deployment/api".to_string(),
+            container: "main".to_string(),
+            issue: ResourceHygieneIssue::MemoryLimitUnset,
+        });
+
+        let csv = render_csv(&report);
+        let rows: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(rows[0], "namespace,kind,name,category,severity,since,details");
+        assert_eq!(
+            rows[1],
+            "payments,Pod,api-0,oom,critical,2026-01-01T00:00:00Z,container=main; image=example/api:1.0; node_name=node-a; restart_count=3"
+        );
+        assert_eq!(rows[2], ",Node,node-a,nodes,critical,2026-01-01T00:00:00Z,\"conditions=MemoryPressure,DiskPressure; os=linux\"");
+        assert_eq!(
+            rows[3],
+            "payments,Deployment,api,resource_hygiene,info,,\"container=main; issue={\"\"kind\"\":\"\"memory_limit_unset\"\"}\""
+        );
+        assert_eq!(rows[4], "");
+    }
+}
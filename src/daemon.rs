@@ -0,0 +1,104 @@
+//! Daemon mode (`RUN_MODE=daemon`): collect, report and sleep `INTERVAL_SECONDS` in a
+//! loop instead of relying on a CronJob, with one API client for every run. A failed
+//! run is retried sooner rather than ending the process. SIGHUP drops the cached
+//! secrets, so the next run resolves rotated webhook URLs and tokens.
+
+use anyhow::{Context, Result};
+use std::time::Duration;
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::time::{sleep_until, Instant};
+use tracing::{info, info_span, warn, Instrument};
+
+use crate::exporter::MetricsRegistry;
+use crate::hooks::{default_hooks, HookRegistry};
+use crate::pipeline::{default_notifiers, notify_unless_vetoed, resolve_oncall_mention, run_hooked, RunClient};
+use crate::recording::kube_config;
+use crate::secrets::SecretResolver;
+use crate::types::Config;
+
+/// Wait before retrying the first failed run; doubled for each further failure
+const FIRST_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Report every `cfg.interval_seconds` (counted from the start of a run) until the
+/// process is stopped, updating `registry` with each report. Only a client that cannot
+/// be built ends the loop; a run failing, e.g. on an unreachable API server, is logged
+/// and retried with backoff.
+pub async fn run_daemon(cfg: Config, resolver: &SecretResolver, registry: Option<&MetricsRegistry>) -> Result<()> {
+    let client = RunClient::new(kube_config(cfg.api_recording.as_ref()).await?, &cfg)?;
+    let hooks = default_hooks(&cfg);
+    let mut hangup = signal(SignalKind::hangup()).context("Failed to listen for SIGHUP")?;
+    let interval = Duration::from_secs(cfg.interval_seconds);
+    info!("Reporting every {}s", cfg.interval_seconds);
+    let mut failures = 0;
+    loop {
+        let started = Instant::now();
+        let wait = match report_once(&client, &hooks, &cfg, resolver, registry).await {
+            Ok(()) => {
+                failures = 0;
+                interval
+            }
+            Err(e) => {
+                failures += 1;
+                if let Some(registry) = registry {
+                    registry.record_failure();
+                }
+                let delay = retry_delay(failures, interval);
+                warn!("Run failed ({} in a row), retrying in {:?}: {:#}", failures, delay, e);
+                delay
+            }
+        };
+        loop {
+            tokio::select! {
+                _ = sleep_until(started + wait) => break,
+                _ = hangup.recv() => {
+                    info!("Received SIGHUP, dropping cached secrets");
+                    resolver.invalidate();
+                }
+            }
+        }
+    }
+}
+
+/// One run; fails only when no report could be collected, as a failed notification
+/// would fail again if retried sooner
+async fn report_once(
+    client: &RunClient,
+    hooks: &HookRegistry,
+    cfg: &Config,
+    resolver: &SecretResolver,
+    registry: Option<&MetricsRegistry>,
+) -> Result<()> {
+    let mut report = run_hooked(hooks, client, cfg.clone()).await?;
+    if let Some(registry) = registry {
+        registry.update(&report);
+    }
+    resolve_oncall_mention(resolver, &mut report).await;
+    let notified = async { notify_unless_vetoed(hooks, &default_notifiers(resolver, &report).await?, &report).await }
+        .instrument(info_span!("run", run_id = %report.run_id))
+        .await;
+    if let Err(e) = notified {
+        warn!("Failed to send the report: {:#}", e);
+    }
+    Ok(())
+}
+
+/// Wait after the `failures`th failed run in a row: 30s, doubling, never beyond `interval`
+pub fn retry_delay(failures: u32, interval: Duration) -> Duration {
+    FIRST_RETRY_DELAY.saturating_mul(2u32.saturating_pow(failures.saturating_sub(1))).min(interval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay() {
+        let interval = Duration::from_secs(300);
+        assert_eq!(retry_delay(1, interval), Duration::from_secs(30));
+        assert_eq!(retry_delay(2, interval), Duration::from_secs(60));
+        assert_eq!(retry_delay(4, interval), Duration::from_secs(240));
+        assert_eq!(retry_delay(5, interval), interval);
+        assert_eq!(retry_delay(64, interval), interval);
+        assert_eq!(retry_delay(1, Duration::from_secs(10)), Duration::from_secs(10));
+    }
+}
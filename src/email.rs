@@ -0,0 +1,344 @@
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::Utc;
+use regex::Regex;
+use std::io::BufReader as StdBufReader;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+use tokio_rustls::rustls::{ClientConfig, OwnedTrustAnchor, RootCertStore, ServerName};
+use tokio_rustls::TlsConnector;
+use tracing::debug;
+
+use crate::redaction::redact_payload;
+use crate::report::{HealthReport, ReportSummary};
+use crate::slack::build_report_payload;
+use crate::types::{EmailSettings, SmtpTls};
+
+/// A rendered report email
+#[derive(Debug, Clone)]
+pub struct EmailMessage {
+    pub subject: String,
+    pub html: String,
+}
+
+/// Slack shortcodes used by the report blocks, as emoji
+pub(crate) const EMOJI: &[(&str, &str)] = &[
+    (":warning:", "\u{26a0}\u{fe0f}"),
+    (":x:", "\u{274c}"),
+    (":white_check_mark:", "\u{2705}"),
+    (":rotating_light:", "\u{1f6a8}"),
+    (":red_circle:", "\u{1f534}"),
+    (":large_yellow_circle:", "\u{1f7e1}"),
+    (":large_green_circle:", "\u{1f7e2}"),
+    (":large_blue_circle:", "\u{1f535}"),
+    (":dart:", "\u{1f3af}"),
+    (":memo:", "\u{1f4dd}"),
+];
+
+/// The report as an email: the blocks of the Slack message (sections, order and
+/// redaction included) rendered as HTML, so both channels show the same findings
+pub fn render_report_email(report: &HealthReport, summary: &ReportSummary) -> EmailMessage {
+    let mut payload = build_report_payload(report, summary);
+    redact_payload(&mut payload, &report.config.redaction_patterns);
+
+    let mut title = String::new();
+    let mut body = String::new();
+    for block in &payload.blocks {
+        match block["type"].as_str() {
+            Some("header") => {
+                let text = block["text"]["text"].as_str().unwrap_or_default();
+                if title.is_empty() {
+                    title = text.to_string();
+                }
+                body.push_str(&format!("<h2>{}</h2>\n", escape_html(text)));
+            }
+            Some("section") => {
+                let text = block["text"]["text"].as_str().unwrap_or_default();
+                body.push_str(&format!("<p>{}</p>\n", mrkdwn_to_html(text)));
+            }
+            Some("context") => {
+                let texts: Vec<&str> = block["elements"].as_array().into_iter().flatten().filter_map(|e| e["text"].as_str()).collect();
+                body.push_str(&format!(
+                    "<p style=\"color:#616061;font-size:12px\">{}</p>\n",
+                    mrkdwn_to_html(&texts.join(" "))
+                ));
+            }
+            Some("divider") => body.push_str("<hr>\n"),
+            _ => {}
+        }
+    }
+
+    let issues = summary.total_issues();
+    let subject = match issues {
+        0 => format!("{}: no issues", title),
+        1 => format!("{}: 1 issue", title),
+        n => format!("{}: {} issues", title, n),
+    };
+    let html = format!(
+        "<!DOCTYPE html>\n<html>\n<body style=\"font-family:-apple-system,Segoe UI,Helvetica,Arial,sans-serif;font-size:14px\">\n{}</body>\n</html>\n",
+        body
+    );
+    EmailMessage { subject, html }
+}
+
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Slack mrkdwn as used by the report (`*bold*`, `` `code` ``, ```` ``` ```` blocks, emoji
+/// shortcodes, line breaks) to HTML
+fn mrkdwn_to_html(text: &str) -> String {
+    static CODE: OnceLock<Regex> = OnceLock::new();
+    static BOLD: OnceLock<Regex> = OnceLock::new();
+    let code = CODE.get_or_init(|| Regex::new(r"`([^`\n]+)`").unwrap());
+    let bold = BOLD.get_or_init(|| Regex::new(r"\*([^*\n]+)\*").unwrap());
+
+    let mut html = String::new();
+    // Odd parts are the insides of code blocks (e.g. crash log tails)
+    for (i, part) in escape_html(text).split("```").enumerate() {
+        if i % 2 == 1 {
+            html.push_str(&format!("<pre>{}</pre>", part.trim_matches('\n')));
+            continue;
+        }
+        let mut part = part.to_string();
+        for (shortcode, emoji) in EMOJI {
+            part = part.replace(shortcode, emoji);
+        }
+        let part = code.replace_all(&part, "<code>$1</code>");
+        let part = bold.replace_all(&part, "<b>$1</b>");
+        html.push_str(&part.replace('\n', "<br>\n"));
+    }
+    html
+}
+
+/// Send the message through the SMTP relay; `password` has its secret reference resolved.
+/// Fails when the relay has not accepted the message within `deadline`.
+pub async fn send_email(
+    settings: &EmailSettings,
+    password: Option<&str>,
+    message: &EmailMessage,
+    deadline: Duration,
+) -> Result<()> {
+    let relay = format!("{}:{}", settings.smtp_host, settings.smtp_port);
+    timeout(deadline, send_email_inner(settings, password, message))
+        .await
+        .map_err(|_| anyhow!("Timed out after {}s sending email through {}", deadline.as_secs(), relay))?
+        .with_context(|| format!("Failed to send email through {}", relay))
+}
+
+async fn send_email_inner(settings: &EmailSettings, password: Option<&str>, message: &EmailMessage) -> Result<()> {
+    let tcp = TcpStream::connect((settings.smtp_host.as_str(), settings.smtp_port)).await?;
+    let data = mime_message(settings, message);
+    match settings.smtp_tls {
+        SmtpTls::Tls => {
+            let mut smtp = SmtpConnection::new(tls_connect(settings, tcp).await?);
+            smtp.reply(220).await?;
+            smtp.ehlo().await?;
+            smtp.transaction(settings, password, &data).await
+        }
+        SmtpTls::StartTls => {
+            let mut smtp = SmtpConnection::new(tcp);
+            smtp.reply(220).await?;
+            let extensions = smtp.ehlo().await?;
+            if !extensions.iter().any(|e| e.eq_ignore_ascii_case("STARTTLS")) {
+                bail!("relay does not offer STARTTLS (set SMTP_TLS=none for plain SMTP)");
+            }
+            smtp.command("STARTTLS", 220).await?;
+            let mut smtp = SmtpConnection::new(tls_connect(settings, smtp.into_inner()).await?);
+            smtp.ehlo().await?;
+            smtp.transaction(settings, password, &data).await
+        }
+        SmtpTls::None => {
+            let mut smtp = SmtpConnection::new(tcp);
+            smtp.reply(220).await?;
+            smtp.ehlo().await?;
+            smtp.transaction(settings, password, &data).await
+        }
+    }
+}
+
+/// Built-in web PKI roots plus the CAs of `smtp_ca_bundle`, for relays with an internal CA
+async fn tls_connect(
+    settings: &EmailSettings,
+    tcp: TcpStream,
+) -> Result<tokio_rustls::client::TlsStream<TcpStream>> {
+    let mut roots = RootCertStore::empty();
+    roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
+        OwnedTrustAnchor::from_subject_spki_name_constraints(ta.subject, ta.spki, ta.name_constraints)
+    }));
+    if let Some(path) = settings.ca_bundle.as_deref() {
+        let pem = std::fs::File::open(path).with_context(|| format!("Failed to read SMTP_CA_BUNDLE {}", path))?;
+        let certificates = rustls_pemfile::certs(&mut StdBufReader::new(pem))
+            .with_context(|| format!("Invalid SMTP_CA_BUNDLE {}", path))?;
+        let (added, _) = roots.add_parsable_certificates(&certificates);
+        if added == 0 {
+            bail!("SMTP_CA_BUNDLE {} contains no certificates", path);
+        }
+    }
+    let config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    let server_name = ServerName::try_from(settings.smtp_host.as_str())
+        .map_err(|_| anyhow!("Invalid SMTP_HOST {} for TLS", settings.smtp_host))?;
+    Ok(TlsConnector::from(Arc::new(config)).connect(server_name, tcp).await?)
+}
+
+/// Headers and base64 HTML body; base64 keeps lines short and needs no dot-stuffing
+fn mime_message(settings: &EmailSettings, message: &EmailMessage) -> String {
+    let subject = if message.subject.is_ascii() {
+        message.subject.clone()
+    } else {
+        format!("=?UTF-8?B?{}?=", base64(message.subject.as_bytes()))
+    };
+    let body = base64(message.html.as_bytes());
+    let body_lines: Vec<&str> = body.as_bytes().chunks(76).map(|c| std::str::from_utf8(c).unwrap()).collect();
+    format!(
+        "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nMIME-Version: 1.0\r\n\
+         Content-Type: text/html; charset=utf-8\r\nContent-Transfer-Encoding: base64\r\n\r\n{}\r\n",
+        settings.from,
+        settings.to.join(", "),
+        subject,
+        Utc::now().to_rfc2822(),
+        body_lines.join("\r\n"),
+    )
+}
+
+/// Standard base64 with padding
+pub(crate) fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, b)| n | ((*b as u32) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// One SMTP session over a plain or TLS stream
+struct SmtpConnection<S> {
+    stream: BufReader<S>,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> SmtpConnection<S> {
+    fn new(stream: S) -> Self {
+        Self { stream: BufReader::new(stream) }
+    }
+
+    fn into_inner(self) -> S {
+        self.stream.into_inner()
+    }
+
+    /// Read a (possibly multi-line) reply and check its code; returns the text lines
+    async fn reply(&mut self, expected: u16) -> Result<Vec<String>> {
+        let mut lines = Vec::new();
+        loop {
+            let mut line = String::new();
+            if self.stream.read_line(&mut line).await? == 0 {
+                bail!("relay closed the connection");
+            }
+            let line = line.trim_end();
+            let code: u16 = line.get(..3).and_then(|c| c.parse().ok())
+                .ok_or_else(|| anyhow!("unexpected reply '{}'", line))?;
+            lines.push(line.get(4..).unwrap_or_default().to_string());
+            if line.as_bytes().get(3) != Some(&b'-') {
+                if code != expected {
+                    bail!("relay replied {} {}", code, lines.join(" "));
+                }
+                return Ok(lines);
+            }
+        }
+    }
+
+    async fn command(&mut self, command: &str, expected: u16) -> Result<Vec<String>> {
+        let verb = command.split(' ').next().unwrap_or(command);
+        debug!("SMTP {}", verb);
+        self.stream.get_mut().write_all(format!("{}\r\n", command).as_bytes()).await?;
+        self.stream.get_mut().flush().await?;
+        self.reply(expected).await.with_context(|| format!("{} failed", verb))
+    }
+
+    /// EHLO; returns the extension keywords the relay announced
+    async fn ehlo(&mut self) -> Result<Vec<String>> {
+        let lines = self.command("EHLO kube-health-reporter", 250).await?;
+        Ok(lines.iter().skip(1).filter_map(|l| l.split(' ').next()).map(str::to_string).collect())
+    }
+
+    async fn transaction(&mut self, settings: &EmailSettings, password: Option<&str>, data: &str) -> Result<()> {
+        if let Some(username) = settings.smtp_username.as_deref() {
+            let credentials = format!("\0{}\0{}", username, password.unwrap_or_default());
+            self.command(&format!("AUTH PLAIN {}", base64(credentials.as_bytes())), 235).await?;
+        }
+        self.command(&format!("MAIL FROM:<{}>", settings.from), 250).await?;
+        for recipient in &settings.to {
+            self.command(&format!("RCPT TO:<{}>", recipient), 250).await?;
+        }
+        self.command("DATA", 354).await?;
+        self.stream.get_mut().write_all(data.as_bytes()).await?;
+        self.command(".", 250).await?;
+        self.command("QUIT", 221).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Config, OomKilledInfo};
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"\0user\0secret"), "AHVzZXIAc2VjcmV0");
+    }
+
+    #[test]
+    fn test_render_report_email() {
+        let config = Config {
+            namespaces: vec!["default".to_string()],
+            cluster_name: Some("prod".to_string()),
+            ..Default::default()
+        };
+        let mut report = HealthReport::new(config);
+        report.pod_metrics.oom_killed.push(OomKilledInfo {
+            namespace: "default".to_string(),
+            pod: "api-0".to_string(),
+            labels: Default::default(),
+            node_name: None,
+            zone: None,
+            blast_radius: None,
+            container: "app".to_string(),
+            last_oom_time: None,
+            restart_count: 4,
+            image: None,
+            log_tail: Some("panic: out of memory".to_string()),
+        });
+        let summary = report.summary();
+
+        let email = render_report_email(&report, &summary);
+        assert_eq!(email.subject, "Kubernetes Health Report - prod: 1 issue");
+        assert!(email.html.contains("<h2>Kubernetes Health Report - prod</h2>"));
+        assert!(email.html.contains("<code>default/api-0</code>"));
+        assert!(!email.html.contains('`'));
+    }
+
+    #[test]
+    fn test_mrkdwn_to_html() {
+        assert_eq!(
+            mrkdwn_to_html(":warning: *Pending pods*\n• `a/b` <none> & more"),
+            "\u{26a0}\u{fe0f} <b>Pending pods</b><br>\n• <code>a/b</code> &lt;none&gt; &amp; more"
+        );
+        assert_eq!(mrkdwn_to_html("log:\n```\nline *1*\nline 2\n```"), "log:<br>\n<pre>line *1*\nline 2</pre>");
+    }
+}
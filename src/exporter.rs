@@ -0,0 +1,285 @@
+//! Prometheus exporter: in daemon mode, the latest report's results are served as
+//! gauges on `/metrics`, so alerts can be routed through Alertmanager as well as the
+//! configured notifiers.
+
+use anyhow::{anyhow, Context, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::timeout;
+use tracing::{info, warn};
+
+use crate::daemon::run_daemon;
+use crate::openmetrics::escape_label;
+use crate::report::severity::section_severities;
+use crate::report::HealthReport;
+use crate::secrets::SecretResolver;
+use crate::types::{Config, NodeUtilizationInfo, Scoped};
+
+/// Longest request head read before the connection is answered 400
+const MAX_REQUEST_HEAD: usize = 8192;
+
+/// Time a client has to send its request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Name, help text and value of a per-node gauge
+type NodeGauge = (&'static str, &'static str, fn(&NodeUtilizationInfo) -> Option<f64>);
+
+/// Gauges of the last successful run, and how the last runs went
+#[derive(Default)]
+pub struct MetricsRegistry(RwLock<RegistryState>);
+
+#[derive(Default)]
+struct RegistryState {
+    gauges: String,
+    last_run_failed: bool,
+    last_success: Option<chrono::DateTime<chrono::Utc>>,
+    failures: u64,
+}
+
+impl MetricsRegistry {
+    pub fn update(&self, report: &HealthReport) {
+        let gauges = render_gauges(report);
+        let mut state = self.0.write().unwrap_or_else(|e| e.into_inner());
+        state.gauges = gauges;
+        state.last_run_failed = false;
+        state.last_success = Some(chrono::Utc::now());
+    }
+
+    /// Keep serving the previous gauges; `khr_last_run_success` tells they are stale
+    pub fn record_failure(&self) {
+        let mut state = self.0.write().unwrap_or_else(|e| e.into_inner());
+        state.last_run_failed = true;
+        state.failures += 1;
+    }
+
+    /// The exposition served on `/metrics`, in the Prometheus text format
+    pub fn gather(&self) -> String {
+        let state = self.0.read().unwrap_or_else(|e| e.into_inner());
+        let mut out = state.gauges.clone();
+        out.push_str("# HELP khr_last_run_success Whether the last collection succeeded.\n");
+        out.push_str("# TYPE khr_last_run_success gauge\n");
+        let succeeded = state.last_success.is_some() && !state.last_run_failed;
+        let _ = writeln!(out, "khr_last_run_success {}", u8::from(succeeded));
+        if let Some(at) = state.last_success {
+            out.push_str("# HELP khr_last_success_timestamp_seconds When the served gauges were collected.\n");
+            out.push_str("# TYPE khr_last_success_timestamp_seconds gauge\n");
+            let _ = writeln!(out, "khr_last_success_timestamp_seconds {}", at.timestamp());
+        }
+        out.push_str("# HELP khr_run_failures_total Collections that failed since the exporter started.\n");
+        out.push_str("# TYPE khr_run_failures_total counter\n");
+        let _ = writeln!(out, "khr_run_failures_total {}", state.failures);
+        out
+    }
+}
+
+/// The report as Prometheus gauges:
+/// - `khr_issues`: the report's issue count
+/// - `khr_findings{section,severity}`: findings per section and severity
+/// - `khr_{heavy_usage,restarting,pending,failed,unready,oom_killed}_pods{namespace}`:
+///   pods with findings per monitored namespace, 0 included so alerts can resolve
+/// - `khr_node_cpu_pct{node}`, `khr_node_memory_pct{node}`: usage of the nodes above
+///   the utilization threshold
+pub fn render_gauges(report: &HealthReport) -> String {
+    let mut out = String::new();
+    let summary = report.summary();
+    gauge_header(&mut out, "khr_issues", "Issues in the last report.");
+    let _ = writeln!(out, "khr_issues {}", summary.total_issues());
+
+    gauge_header(&mut out, "khr_findings", "Findings in the last report by section and severity.");
+    for (section, counts) in section_severities(report) {
+        for (severity, count) in [("critical", counts.critical), ("warning", counts.warning), ("info", counts.info)] {
+            let _ = writeln!(out, "khr_findings{{section=\"{}\",severity=\"{}\"}} {}", section, severity, count);
+        }
+    }
+
+    let pods = &report.pod_metrics;
+    let namespaces: BTreeSet<&str> = report.config.namespaces.iter().map(String::as_str).collect();
+    let per_namespace = [
+        ("khr_heavy_usage_pods", "Pods above the usage threshold.", by_namespace(&namespaces, &pods.heavy_usage)),
+        ("khr_restarting_pods", "Pods restarting more than allowed.", by_namespace(&namespaces, &pods.restarts)),
+        ("khr_pending_pods", "Pods pending longer than the grace period.", by_namespace(&namespaces, &pods.pending)),
+        ("khr_failed_pods", "Failed pods.", by_namespace(&namespaces, &pods.failed)),
+        ("khr_unready_pods", "Pods unready longer than the grace period.", by_namespace(&namespaces, &pods.unready)),
+        ("khr_oom_killed_pods", "Pods with OOMKilled containers.", by_namespace(&namespaces, &pods.oom_killed)),
+    ];
+    for (name, help, counts) in per_namespace {
+        gauge_header(&mut out, name, help);
+        for (namespace, count) in counts {
+            let _ = writeln!(out, "{}{{namespace=\"{}\"}} {}", name, escape_label(namespace), count);
+        }
+    }
+
+    let nodes = &report.cluster_metrics.high_utilization_nodes;
+    let node_gauges: [NodeGauge; 2] = [
+        ("khr_node_cpu_pct", "CPU usage of nodes above the utilization threshold, in percent.", |n| n.cpu_pct),
+        ("khr_node_memory_pct", "Memory usage of nodes above the utilization threshold, in percent.", |n| n.memory_pct),
+    ];
+    for (name, help, value) in node_gauges {
+        gauge_header(&mut out, name, help);
+        for node in nodes {
+            if let Some(pct) = value(node) {
+                let _ = writeln!(out, "{}{{node=\"{}\"}} {:.1}", name, escape_label(&node.name), pct);
+            }
+        }
+    }
+    out
+}
+
+fn gauge_header(out: &mut String, name: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}\n# TYPE {} gauge", name, help, name);
+}
+
+/// Findings per namespace: every one of `namespaces`, plus any other a finding is in
+fn by_namespace<'a, T: Scoped>(namespaces: &BTreeSet<&'a str>, items: &'a [T]) -> BTreeMap<&'a str, usize> {
+    let mut counts: BTreeMap<&str, usize> = namespaces.iter().map(|ns| (*ns, 0)).collect();
+    for item in items {
+        *counts.entry(item.namespace()).or_default() += 1;
+    }
+    counts
+}
+
+/// Serve `/metrics` on `cfg.metrics_listen_addr` while the daemon refreshes it with
+/// every report. A failed run keeps the previous gauges.
+pub async fn run_exporter(cfg: Config, resolver: &SecretResolver) -> Result<()> {
+    let addr = cfg.metrics_listen_addr.ok_or_else(|| anyhow!("METRICS_LISTEN_ADDR is not set"))?;
+    let listener = TcpListener::bind(addr).await.with_context(|| format!("Failed to listen on {}", addr))?;
+    info!("Serving Prometheus metrics on http://{}/metrics", addr);
+    let registry = Arc::new(MetricsRegistry::default());
+    let mut server = tokio::spawn(serve_metrics(listener, registry.clone()));
+    tokio::select! {
+        ran = run_daemon(cfg, resolver, Some(&registry)) => ran,
+        served = &mut server => served.context("Metrics server task failed")?,
+    }
+}
+
+/// Answer HTTP requests on `listener` until it fails
+pub async fn serve_metrics(listener: TcpListener, registry: Arc<MetricsRegistry>) -> Result<()> {
+    loop {
+        let (stream, peer) = listener.accept().await.context("Failed to accept a metrics connection")?;
+        let registry = registry.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, &registry).await {
+                warn!("Metrics request from {} failed: {:#}", peer, e);
+            }
+        });
+    }
+}
+
+/// One request per connection: `GET /metrics`, or `GET /healthz` for probes
+async fn handle_connection(mut stream: TcpStream, registry: &MetricsRegistry) -> Result<()> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        if head.len() > MAX_REQUEST_HEAD {
+            return respond(&mut stream, "400 Bad Request", "request too large\n").await;
+        }
+        let read = timeout(REQUEST_TIMEOUT, stream.read(&mut buf))
+            .await
+            .map_err(|_| anyhow!("no request within {:?}", REQUEST_TIMEOUT))??;
+        if read == 0 {
+            return Ok(());
+        }
+        head.extend_from_slice(&buf[..read]);
+    }
+
+    let head = String::from_utf8_lossy(&head);
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let (method, target) = (request_line.next().unwrap_or_default(), request_line.next().unwrap_or_default());
+    let path = target.split('?').next().unwrap_or_default();
+    match (method, path) {
+        ("GET", "/metrics") => respond(&mut stream, "200 OK", &registry.gather()).await,
+        ("GET", "/healthz") => respond(&mut stream, "200 OK", "ok\n").await,
+        ("GET", _) => respond(&mut stream, "404 Not Found", "not found\n").await,
+        _ => respond(&mut stream, "405 Method Not Allowed", "method not allowed\n").await,
+    }
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, body: &str) -> Result<()> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PendingPodInfo;
+
+    fn report() -> HealthReport {
+        let config = Config { namespaces: vec!["default".to_string(), "payments".to_string()], ..Default::default() };
+        let mut report = HealthReport::new(config);
+        report.pod_metrics.pending.push(PendingPodInfo {
+            namespace: "payments".to_string(),
+            pod: "api-0".to_string(),
+            labels: Default::default(),
+            node_name: None,
+            zone: None,
+            blast_radius: None,
+            since: chrono::Utc::now(),
+            duration_minutes: 12,
+            image_pull_failures: vec![],
+        });
+        report.cluster_metrics.high_utilization_nodes.push(NodeUtilizationInfo {
+            name: "node-a".to_string(),
+            os: "linux".to_string(),
+            cpu_pct: Some(93.46),
+            memory_pct: None,
+            cpu_millicores: Some(3730),
+            memory_bytes: None,
+            pods_count: 40,
+            pods_capacity: 110,
+        });
+        report
+    }
+
+    #[test]
+    fn test_render_gauges() {
+        let text = render_gauges(&report());
+        assert!(text.contains("# TYPE khr_pending_pods gauge\n"));
+        assert!(text.contains("khr_pending_pods{namespace=\"default\"} 0\n"));
+        assert!(text.contains("khr_pending_pods{namespace=\"payments\"} 1\n"));
+        assert!(text.contains("khr_heavy_usage_pods{namespace=\"payments\"} 0\n"));
+        assert!(text.contains("khr_findings{section=\"pending\",severity=\"warning\"} 1\n"));
+        assert!(text.contains("khr_node_cpu_pct{node=\"node-a\"} 93.5\n"));
+        assert!(!text.contains("khr_node_memory_pct{"));
+        assert!(text.contains("khr_issues 2\n"));
+    }
+
+    #[tokio::test]
+    async fn test_serve_metrics() {
+        let registry = Arc::new(MetricsRegistry::default());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve_metrics(listener, registry.clone()));
+        let get = |path: &'static str| async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream.write_all(format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).as_bytes()).await.unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        };
+
+        // Nothing collected yet
+        let response = get("/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with("khr_last_run_success 0\n# HELP khr_run_failures_total Collections that failed since the exporter started.\n# TYPE khr_run_failures_total counter\nkhr_run_failures_total 0\n"));
+
+        registry.update(&report());
+        registry.record_failure();
+        let response = get("/metrics?x=1").await;
+        assert!(response.contains("khr_pending_pods{namespace=\"payments\"} 1\n"));
+        assert!(response.contains("khr_last_run_success 0\n"));
+        assert!(response.contains("khr_run_failures_total 1\n"));
+        assert!(get("/other").await.starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+}
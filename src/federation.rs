@@ -0,0 +1,247 @@
+use anyhow::{anyhow, Context, Result};
+use kube::config::{KubeConfigOptions, Kubeconfig};
+use std::path::Path;
+use tracing::{error, info, info_span, Instrument};
+
+use crate::blocks::{Block, SlackMessage};
+use crate::delivery::{check_outcomes, deliver_all, webhook_client, Delivery, PayloadSigner};
+use crate::hooks::default_hooks;
+use crate::pipeline::{default_notifiers, notify_unless_vetoed, run_hooked, RunClient};
+use crate::report::{HealthReport, ReportSummary};
+use crate::secrets::SecretResolver;
+use crate::types::{Config, SlackPayload};
+
+/// Findings listed per cluster in the federation summary
+const MAX_WORST_ISSUES: usize = 5;
+
+/// Sections whose findings are listed first as a cluster's worst issues
+const CRITICAL_SECTIONS: &[&str] = &["oom", "failed", "volumes", "failed_jobs", "restarts", "pending"];
+
+/// Health of one cluster in the federation summary
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClusterHealth {
+    pub cluster: String,
+    /// 0–100, see `health_score`
+    pub score: u32,
+    pub total_issues: usize,
+    pub worst_issues: Vec<String>,
+    /// The cluster could not be reported on
+    pub error: Option<String>,
+}
+
+/// 100 minus 5 points per node-level, OOM, failed pod or volume issue and 1 per other
+/// issue, floored at 0
+pub fn health_score(summary: &ReportSummary) -> u32 {
+    let critical = summary.network_agent_issue_count
+        + summary.problematic_node_count
+        + summary.oom_killed_count
+        + summary.failed_pod_count
+        + summary.volume_issue_count;
+    let penalty = 5 * critical + (summary.total_issues() - critical);
+    100u32.saturating_sub(penalty.min(100) as u32)
+}
+
+pub fn summarize(cluster: &str, report: &HealthReport) -> ClusterHealth {
+    let summary = report.summary();
+    let nodes = &report.cluster_metrics;
+    let fingerprints = report.fingerprints();
+    let worst_issues = nodes
+        .network_agent_issues
+        .iter()
+        .map(|n| format!("network/{}/{}", n.node, n.daemonset))
+        .chain(nodes.problematic_nodes.iter().map(|n| format!("nodes/{}", n.name)))
+        .chain(CRITICAL_SECTIONS.iter().flat_map(|section| {
+            let prefix = format!("{}/", section);
+            fingerprints.iter().filter(move |f| f.starts_with(&prefix)).cloned()
+        }))
+        .take(MAX_WORST_ISSUES)
+        .collect();
+    ClusterHealth {
+        cluster: cluster.to_string(),
+        score: health_score(&summary),
+        total_issues: summary.total_issues(),
+        worst_issues,
+        error: None,
+    }
+}
+
+/// One line per cluster, worst score first, with its worst issues
+pub fn build_federation_payload(clusters: &[ClusterHealth]) -> SlackPayload {
+    let mut sorted: Vec<&ClusterHealth> = clusters.iter().collect();
+    sorted.sort_by_key(|c| (c.error.is_none(), c.score, c.cluster.clone()));
+
+    let mut message = SlackMessage::new()
+        .text(format!("Federation health: {} clusters", clusters.len()))
+        .block(Block::header("Kubernetes Health Report - Federation"));
+    for cluster in sorted {
+        let text = match &cluster.error {
+            Some(e) => format!(":x: *{}*: report failed ({})", cluster.cluster, e),
+            None => {
+                let icon = match cluster.score {
+                    90.. => ":large_green_circle:",
+                    60..=89 => ":large_yellow_circle:",
+                    _ => ":red_circle:",
+                };
+                let mut text = format!("{} *{}*: score {} ({} issues)", icon, cluster.cluster, cluster.score, cluster.total_issues);
+                for issue in &cluster.worst_issues {
+                    text.push_str(&format!("\n• `{}`", issue));
+                }
+                text
+            }
+        };
+        message.push(Block::section(text).with_block_id(format!("cluster:{}", cluster.cluster)));
+    }
+    message.build()
+}
+
+/// Configuration of the run against kubeconfig context `cluster`: reports are titled
+/// with the context and each cluster keeps its own state files
+pub fn config_for_cluster(cfg: &Config, cluster: &str) -> Config {
+    let suffixed = |path: &str| {
+        let path = Path::new(path);
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("state");
+        let name = match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) => format!("{}-{}.{}", stem, cluster, ext),
+            None => format!("{}-{}", stem, cluster),
+        };
+        path.with_file_name(name).display().to_string()
+    };
+    let mut cluster_cfg = cfg.clone();
+    cluster_cfg.cluster_name = Some(cluster.to_string());
+    cluster_cfg.state_file = cfg.state_file.as_deref().map(suffixed);
+    cluster_cfg.recommendations_history_path = cfg.recommendations_history_path.as_deref().map(suffixed);
+    cluster_cfg
+}
+
+/// Report on every cluster in `cfg.clusters` (kubeconfig contexts) with the usual
+/// per-cluster delivery, then send the summary to `federation_webhook_url`. A failing
+/// cluster shows up in the summary and fails the run at the end.
+pub async fn run_federation(cfg: Config, resolver: &SecretResolver) -> Result<()> {
+    let kubeconfig = Kubeconfig::read().context("Failed to read kubeconfig for CLUSTERS")?;
+    let mut clusters = Vec::new();
+    let mut failed = Vec::new();
+    for cluster in &cfg.clusters {
+        let result = report_cluster(&kubeconfig, &config_for_cluster(&cfg, cluster), resolver)
+            .instrument(info_span!("cluster", cluster = %cluster))
+            .await;
+        match result {
+            Ok(report) => clusters.push(summarize(cluster, &report)),
+            Err(e) => {
+                error!("Cluster {} failed: {:#}", cluster, e);
+                failed.push(cluster.as_str());
+                clusters.push(ClusterHealth {
+                    cluster: cluster.clone(),
+                    score: 0,
+                    total_issues: 0,
+                    worst_issues: Vec::new(),
+                    error: Some(format!("{:#}", e)),
+                });
+            }
+        }
+    }
+
+    if let Some(url) = cfg.federation_webhook_url.as_deref() {
+        let delivery = Delivery {
+            sink: "federation".to_string(),
+            webhook_url: resolver.resolve(url).await?,
+            payload: build_federation_payload(&clusters),
+            required: true,
+            timeout: std::time::Duration::from_secs(cfg.sink_timeout_seconds),
+        };
+        let signer = PayloadSigner::from_config(&cfg);
+        check_outcomes(&deliver_all(&webhook_client(&cfg)?, signer.as_ref(), vec![delivery]).await)?;
+    }
+    info!("Reported on {} of {} clusters", cfg.clusters.len() - failed.len(), cfg.clusters.len());
+    if !failed.is_empty() {
+        return Err(anyhow!("Reports failed for clusters {}", failed.join(", ")));
+    }
+    Ok(())
+}
+
+async fn report_cluster(kubeconfig: &Kubeconfig, cfg: &Config, resolver: &SecretResolver) -> Result<HealthReport> {
+    let context = cfg.cluster_name.clone().unwrap_or_default();
+    let options = KubeConfigOptions { context: Some(context.clone()), ..Default::default() };
+    let client_config = kube::Config::from_custom_kubeconfig(kubeconfig.clone(), &options)
+        .await
+        .with_context(|| format!("Invalid kubeconfig context {}", context))?;
+    let hooks = default_hooks(cfg);
+    let report = run_hooked(&hooks, &RunClient::new(client_config, cfg)?, cfg.clone()).await?;
+    notify_unless_vetoed(&hooks, &default_notifiers(resolver, &report).await?, &report).await?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{FailedPodInfo, ProblematicNodeInfo};
+    use chrono::Utc;
+
+    fn report_with_failures(failed_pods: usize) -> HealthReport {
+        let mut report = HealthReport::new(Config::default());
+        report.pod_metrics.failed = (0..failed_pods)
+            .map(|i| FailedPodInfo {
+                labels: Default::default(),
+                node_name: None,
+                zone: None,
+                blast_radius: None,
+                namespace: "default".to_string(),
+                pod: format!("api-{}", i),
+                since: Utc::now(),
+                duration_minutes: 10,
+                reason: None,
+                message: None,
+            })
+            .collect();
+        report
+    }
+
+    #[test]
+    fn test_summarize_and_score() {
+        let mut report = report_with_failures(2);
+        report.cluster_metrics.problematic_nodes.push(ProblematicNodeInfo {
+            name: "node-1".to_string(),
+            os: "linux".to_string(),
+            conditions: vec!["MemoryPressure".to_string()],
+            since: Utc::now(),
+        });
+        let health = summarize("prod-eu", &report);
+        assert_eq!(health.score, 85);
+        assert_eq!(health.total_issues, 3);
+        assert_eq!(health.worst_issues, vec!["nodes/node-1", "failed/default/api-0", "failed/default/api-1"]);
+
+        assert_eq!(summarize("dev", &report_with_failures(0)).score, 100);
+        assert_eq!(summarize("bad", &report_with_failures(30)).score, 0);
+    }
+
+    #[test]
+    fn test_build_federation_payload() {
+        let healthy = summarize("dev", &report_with_failures(0));
+        let degraded = summarize("prod-eu", &report_with_failures(9));
+        let broken = ClusterHealth {
+            cluster: "prod-us".to_string(),
+            score: 0,
+            total_issues: 0,
+            worst_issues: Vec::new(),
+            error: Some("connection refused".to_string()),
+        };
+        let payload = build_federation_payload(&[healthy, degraded, broken]);
+
+        let lines: Vec<&str> = payload.blocks[1..].iter().map(|b| b["text"]["text"].as_str().unwrap()).collect();
+        assert_eq!(lines[0], ":x: *prod-us*: report failed (connection refused)");
+        assert!(lines[1].starts_with(":red_circle: *prod-eu*: score 55 (9 issues)\n• `failed/default/api-0`"));
+        assert_eq!(lines[1].matches('•').count(), MAX_WORST_ISSUES);
+        assert_eq!(lines[2], ":large_green_circle: *dev*: score 100 (0 issues)");
+    }
+
+    #[test]
+    fn test_config_for_cluster() {
+        let cfg = Config {
+            state_file: Some("/var/lib/kube-health-reporter/state.json".to_string()),
+            ..Default::default()
+        };
+        let cluster_cfg = config_for_cluster(&cfg, "prod-eu");
+        assert_eq!(cluster_cfg.cluster_name.as_deref(), Some("prod-eu"));
+        assert_eq!(cluster_cfg.state_file.as_deref(), Some("/var/lib/kube-health-reporter/state-prod-eu.json"));
+        assert_eq!(cluster_cfg.recommendations_history_path, None);
+    }
+}
@@ -0,0 +1,134 @@
+//! Change-freeze awareness: while a freeze is in effect, findings that are new this run
+//! are escalated to critical, as breakage nobody should be deploying into needs a
+//! faster response.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use k8s_openapi::api::core::v1::{ConfigMap, Namespace};
+use kube::{Api, Client};
+use std::collections::BTreeSet;
+use std::time::Duration;
+use tokio::time::timeout;
+
+use crate::report::HealthReport;
+use crate::types::{ChangeFreezeInfo, ChangeFreezeSource};
+
+/// Key of the flag in a ConfigMap source
+pub const FREEZE_CONFIGMAP_KEY: &str = "change-freeze";
+/// Annotation holding the flag on a namespace source
+pub const FREEZE_ANNOTATION: &str = "kube-health-reporter.io/change-freeze";
+
+/// Read the flag from `source`; `None` when no freeze is in effect at `now`. A missing
+/// ConfigMap, key or annotation means no freeze; an unreachable source is an error.
+pub async fn check_change_freeze(
+    client: &Client,
+    http: &reqwest::Client,
+    source: &ChangeFreezeSource,
+    deadline: Duration,
+    now: DateTime<Utc>,
+) -> Result<Option<ChangeFreezeInfo>> {
+    let flag = match source {
+        ChangeFreezeSource::ConfigMap { namespace, name } => {
+            let api: Api<ConfigMap> = Api::namespaced(client.clone(), namespace);
+            let config_map = api.get_opt(name).await.with_context(|| format!("Failed to read ConfigMap {}/{}", namespace, name))?;
+            config_map.and_then(|cm| cm.data).and_then(|mut data| data.remove(FREEZE_CONFIGMAP_KEY))
+        }
+        ChangeFreezeSource::Namespace(name) => {
+            let api: Api<Namespace> = Api::all(client.clone());
+            let namespace = api.get_opt(name).await.with_context(|| format!("Failed to read namespace {}", name))?;
+            namespace.and_then(|ns| ns.metadata.annotations).and_then(|mut a| a.remove(FREEZE_ANNOTATION))
+        }
+        ChangeFreezeSource::Http(url) => {
+            let response = timeout(deadline, http.get(url).send())
+                .await
+                .map_err(|_| anyhow!("Change-freeze endpoint timed out after {:?}", deadline))?
+                .context("Failed to reach the change-freeze endpoint")?;
+            let status = response.status();
+            if !status.is_success() {
+                return Err(anyhow!("Change-freeze endpoint answered {}", status));
+            }
+            Some(response.text().await.context("Failed to read the change-freeze endpoint")?)
+        }
+    };
+    Ok(flag.and_then(|flag| freeze_until(&flag, now)).map(|until| ChangeFreezeInfo {
+        source: source.to_string(),
+        until,
+        escalated: BTreeSet::new(),
+    }))
+}
+
+/// Whether a flag value puts `now` in a freeze: `true` for an open-ended freeze
+/// (`Some(None)`), or an RFC 3339 `<start>/<end>` window containing `now`
+/// (`Some(Some(end))`). Anything else, `false` included, is no freeze.
+fn freeze_until(flag: &str, now: DateTime<Utc>) -> Option<Option<DateTime<Utc>>> {
+    let flag = flag.trim();
+    if matches!(flag, "1" | "true" | "TRUE" | "True") {
+        return Some(None);
+    }
+    let (start, end) = flag.split_once('/')?;
+    let start = DateTime::parse_from_rfc3339(start.trim()).ok()?.with_timezone(&Utc);
+    let end = DateTime::parse_from_rfc3339(end.trim()).ok()?.with_timezone(&Utc);
+    (start <= now && now < end).then_some(Some(end))
+}
+
+/// Fingerprints of the findings first seen this run; without a state file every finding
+/// counts as new
+pub fn new_findings(report: &HealthReport) -> BTreeSet<String> {
+    report
+        .fingerprints()
+        .into_iter()
+        .filter(|f| report.issue_history.get(f).is_none_or(|h| h.occurrences <= 1))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Config, IssueHistory, OomKilledInfo};
+    use chrono::TimeZone;
+
+    fn oom(pod: &str) -> OomKilledInfo {
+        OomKilledInfo {
+            namespace: "payments".to_string(),
+            pod: pod.to_string(),
+            labels: Default::default(),
+            node_name: None,
+            zone: None,
+            blast_radius: None,
+            container: "app".to_string(),
+            last_oom_time: None,
+            restart_count: 1,
+            image: None,
+            log_tail: None,
+        }
+    }
+
+    #[test]
+    fn test_freeze_until() {
+        let now = Utc.with_ymd_and_hms(2026, 12, 20, 12, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2027, 1, 4, 0, 0, 0).unwrap();
+        assert_eq!(freeze_until("true\n", now), Some(None));
+        assert_eq!(freeze_until("2026-12-18T00:00:00Z/2027-01-04T00:00:00Z", now), Some(Some(end)));
+        assert_eq!(freeze_until("2027-01-04T00:00:00Z/2027-01-10T00:00:00Z", now), None);
+        assert_eq!(freeze_until("false", now), None);
+        assert_eq!(freeze_until("next week", now), None);
+    }
+
+    #[test]
+    fn test_new_findings() {
+        let now = Utc::now();
+        let mut report = HealthReport::new(Config::default());
+        report.pod_metrics.oom_killed.extend([oom("api-0"), oom("api-1"), oom("api-2")]);
+        report.issue_history.insert(
+            "oom/payments/api-0".to_string(),
+            IssueHistory { first_seen: now, last_seen: now, occurrences: 1 },
+        );
+        report.issue_history.insert(
+            "oom/payments/api-1".to_string(),
+            IssueHistory { first_seen: now, last_seen: now, occurrences: 4 },
+        );
+
+        let new: Vec<String> = new_findings(&report).into_iter().collect();
+        assert_eq!(new, vec!["oom/payments/api-0", "oom/payments/api-2"]);
+    }
+}
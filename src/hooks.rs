@@ -0,0 +1,293 @@
+//! Lifecycle hooks around a run: before collection, after the report is assembled (to
+//! enrich it) and before it is sent (to veto the notifications). Hooks implement
+//! [`Hook`]; `HOOK_*` shell commands are registered as a [`CommandHook`].
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::future::Future;
+use std::pin::Pin;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::time::timeout;
+use tracing::{info, warn};
+
+use crate::freeze::new_findings;
+use crate::report::HealthReport;
+use crate::types::{ChangeFreezeInfo, Config, HookCommands};
+use crate::webhook::report_document;
+
+/// Future returned by the `Hook` callbacks; boxed so hooks can be registered as trait objects
+pub type HookFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// Callbacks at the hook points of a run; each does nothing unless overridden
+pub trait Hook: Send + Sync {
+    /// Short name used in logs, e.g. `command`
+    fn name(&self) -> &str;
+
+    /// Before the cluster is queried; an error aborts the run
+    fn before_collection<'a>(&'a self, _config: &'a Config) -> HookFuture<'a, ()> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Once the report is assembled, to add context to it
+    fn after_report<'a>(&'a self, _report: &'a mut HealthReport) -> HookFuture<'a, ()> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Before the notifiers run; `Some(reason)` vetoes every notification of the run
+    fn before_send<'a>(&'a self, _report: &'a HealthReport) -> HookFuture<'a, Option<String>> {
+        Box::pin(async { Ok(None) })
+    }
+}
+
+/// Hooks of a run, called in registration order
+#[derive(Default)]
+pub struct HookRegistry {
+    hooks: Vec<Box<dyn Hook>>,
+}
+
+impl HookRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, hook: impl Hook + 'static) {
+        self.hooks.push(Box::new(hook));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    pub async fn before_collection(&self, config: &Config) -> Result<()> {
+        for hook in &self.hooks {
+            hook.before_collection(config).await.with_context(|| format!("Hook {} failed before collection", hook.name()))?;
+        }
+        Ok(())
+    }
+
+    /// A failing hook leaves the report as it was and is noted in its warnings
+    pub async fn after_report(&self, report: &mut HealthReport) {
+        for hook in &self.hooks {
+            if let Err(e) = hook.after_report(report).await {
+                warn!("Hook {} failed after the report: {:#}", hook.name(), e);
+                report.collection_warnings.push(format!("Hook {} failed to enrich the report", hook.name()));
+            }
+        }
+    }
+
+    /// The first veto, as `<hook>: <reason>`. A failing hook does not veto, so a broken
+    /// hook never silences reports.
+    pub async fn before_send(&self, report: &HealthReport) -> Option<String> {
+        for hook in &self.hooks {
+            match hook.before_send(report).await {
+                Ok(Some(reason)) => return Some(format!("{}: {}", hook.name(), reason)),
+                Ok(None) => {}
+                Err(e) => warn!("Hook {} failed before sending, sending anyway: {:#}", hook.name(), e),
+            }
+        }
+        None
+    }
+}
+
+/// Hooks configured for `cfg`: the `HOOK_*` commands, if any
+pub fn default_hooks(cfg: &Config) -> HookRegistry {
+    let mut hooks = HookRegistry::new();
+    if cfg.hook_commands != HookCommands::default() {
+        hooks.register(CommandHook::new(cfg.hook_commands.clone(), Duration::from_secs(cfg.hook_timeout_seconds)));
+    }
+    hooks
+}
+
+/// Runs the configured commands with `sh -c`. Each gets `KHR_HOOK` (the hook point),
+/// `KHR_CLUSTER` and, once known, `KHR_RUN_ID` in its environment; after collection
+/// the report document is written to its stdin.
+///
+/// - `pre_collection`: a non-zero exit aborts the run
+/// - `post_report`: may print a JSON object to attach to the report, see
+///   [`apply_enrichment`]; a non-zero exit is an error
+/// - `pre_send`: a non-zero exit vetoes the notifications, the first line printed
+///   being the reason
+pub struct CommandHook {
+    commands: HookCommands,
+    timeout: Duration,
+}
+
+impl CommandHook {
+    pub fn new(commands: HookCommands, timeout: Duration) -> Self {
+        Self { commands, timeout }
+    }
+
+    async fn exec(&self, point: &str, command: &str, config: &Config, run_id: Option<&str>, input: Option<Vec<u8>>) -> Result<(bool, String)> {
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .env("KHR_HOOK", point)
+            .env("KHR_CLUSTER", config.cluster_name.as_deref().unwrap_or_default())
+            .env("KHR_RUN_ID", run_id.unwrap_or_default())
+            .stdin(if input.is_some() { Stdio::piped() } else { Stdio::null() })
+            .stdout(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to start {} hook", point))?;
+        // Written alongside reading stdout, so a command printing before it reads cannot block
+        if let (Some(mut stdin), Some(input)) = (child.stdin.take(), input) {
+            tokio::spawn(async move {
+                // A command not reading its stdin closes the pipe early
+                let _ = stdin.write_all(&input).await;
+            });
+        }
+        let output = timeout(self.timeout, child.wait_with_output())
+            .await
+            .map_err(|_| anyhow!("{} hook timed out after {:?}", point, self.timeout))??;
+        Ok((output.status.success(), String::from_utf8_lossy(&output.stdout).into_owned()))
+    }
+}
+
+impl Hook for CommandHook {
+    fn name(&self) -> &str {
+        "command"
+    }
+
+    fn before_collection<'a>(&'a self, config: &'a Config) -> HookFuture<'a, ()> {
+        Box::pin(async move {
+            let Some(command) = self.commands.pre_collection.as_deref() else {
+                return Ok(());
+            };
+            let (succeeded, stdout) = self.exec("pre_collection", command, config, None, None).await?;
+            if !succeeded {
+                return Err(anyhow!("pre_collection hook exited with an error: {}", first_line(&stdout)));
+            }
+            Ok(())
+        })
+    }
+
+    fn after_report<'a>(&'a self, report: &'a mut HealthReport) -> HookFuture<'a, ()> {
+        Box::pin(async move {
+            let Some(command) = self.commands.post_report.as_deref() else {
+                return Ok(());
+            };
+            let input = serde_json::to_vec(&report_document(report))?;
+            let (succeeded, stdout) = self.exec("post_report", command, &report.config, Some(&report.run_id), Some(input)).await?;
+            if !succeeded {
+                return Err(anyhow!("post_report hook exited with an error: {}", first_line(&stdout)));
+            }
+            apply_enrichment(report, &stdout)
+        })
+    }
+
+    fn before_send<'a>(&'a self, report: &'a HealthReport) -> HookFuture<'a, Option<String>> {
+        Box::pin(async move {
+            let Some(command) = self.commands.pre_send.as_deref() else {
+                return Ok(None);
+            };
+            let input = serde_json::to_vec(&report_document(report))?;
+            let (succeeded, stdout) = self.exec("pre_send", command, &report.config, Some(&report.run_id), Some(input)).await?;
+            if succeeded {
+                return Ok(None);
+            }
+            let reason = first_line(&stdout);
+            Ok(Some(if reason.is_empty() { "pre_send hook exited with an error".to_string() } else { reason.to_string() }))
+        })
+    }
+}
+
+/// What a `post_report` command may print
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Enrichment {
+    /// Shown with the run's context in every message
+    #[serde(default)]
+    notes: Vec<String>,
+    /// A change freeze known outside the cluster; its new findings are escalated
+    change_freeze: Option<FreezeEnrichment>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FreezeEnrichment {
+    until: Option<DateTime<Utc>>,
+}
+
+/// Attach the JSON object printed by a `post_report` command, e.g.
+/// `{"notes": ["Release 4.2 rolling out"], "change_freeze": {"until": "2026-01-05T00:00:00Z"}}`.
+/// Blank output attaches nothing; a freeze already read from `CHANGE_FREEZE_*` wins.
+pub fn apply_enrichment(report: &mut HealthReport, output: &str) -> Result<()> {
+    if output.trim().is_empty() {
+        return Ok(());
+    }
+    let enrichment: Enrichment = serde_json::from_str(output).context("post_report hook printed invalid JSON")?;
+    report.notes.extend(enrichment.notes.into_iter().map(|n| n.trim().to_string()).filter(|n| !n.is_empty()));
+    if let (Some(freeze), None) = (enrichment.change_freeze, &report.change_freeze) {
+        let escalated = new_findings(report);
+        info!("Change freeze attached by a hook, escalating {} new findings", escalated.len());
+        report.change_freeze = Some(ChangeFreezeInfo { source: "post_report hook".to_string(), until: freeze.until, escalated });
+    }
+    Ok(())
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::OomKilledInfo;
+
+    #[test]
+    fn test_apply_enrichment() {
+        let mut report = HealthReport::new(Config::default());
+        report.pod_metrics.oom_killed.push(OomKilledInfo {
+            namespace: "payments".to_string(),
+            pod: "api-0".to_string(),
+            labels: Default::default(),
+            node_name: None,
+            zone: None,
+            blast_radius: None,
+            container: "app".to_string(),
+            last_oom_time: None,
+            restart_count: 3,
+            image: None,
+            log_tail: None,
+        });
+        apply_enrichment(&mut report, "\n").unwrap();
+        assert!(report.notes.is_empty() && report.change_freeze.is_none());
+
+        apply_enrichment(&mut report, r#"{"notes": ["Release 4.2 rolling out", " "], "change_freeze": {"until": null}}"#).unwrap();
+        assert_eq!(report.notes, vec!["Release 4.2 rolling out".to_string()]);
+        let freeze = report.change_freeze.as_ref().unwrap();
+        assert_eq!(freeze.until, None);
+        assert!(report.is_escalated("oom/payments/api-0"));
+
+        let error = apply_enrichment(&mut report, r#"{"note": "typo"}"#).unwrap_err();
+        assert!(error.to_string().contains("invalid JSON"));
+    }
+
+    #[tokio::test]
+    async fn test_command_hook() {
+        let commands = HookCommands {
+            pre_collection: Some("exit 1".to_string()),
+            post_report: Some(r#"grep -q '"run_id":"run-1"' && echo "{\"notes\": [\"$KHR_HOOK\"]}""#.to_string()),
+            pre_send: Some("cat > /dev/null; echo 'Weekend, reports resume Monday'; exit 3".to_string()),
+        };
+        let mut hooks = HookRegistry::new();
+        hooks.register(CommandHook::new(commands, Duration::from_secs(10)));
+        let mut report = HealthReport::new(Config::default()).with_run_id("run-1".to_string());
+
+        let error = hooks.before_collection(&report.config).await.unwrap_err();
+        assert_eq!(error.to_string(), "Hook command failed before collection");
+        hooks.after_report(&mut report).await;
+        assert_eq!(report.notes, vec!["post_report".to_string()]);
+        assert_eq!(hooks.before_send(&report).await.as_deref(), Some("command: Weekend, reports resume Monday"));
+
+        // Hooks that hang are killed, and do not veto
+        let mut slow = HookRegistry::new();
+        let commands = HookCommands { pre_send: Some("sleep 5".to_string()), ..Default::default() };
+        slow.register(CommandHook::new(commands, Duration::from_millis(100)));
+        assert_eq!(slow.before_send(&report).await, None);
+    }
+}
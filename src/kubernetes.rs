@@ -1,15 +1,282 @@
 use anyhow::{anyhow, Result};
-use kube::Client;
+use k8s_openapi::api::core::v1::{ConfigMap, Namespace, Node};
+use kube::{api::ListParams, Api, Client};
+use std::time::Duration;
+use tracing::{debug, info, warn};
 
-use crate::types::{Config, HeavyUsagePod, RestartEventInfo, PendingPodInfo};
-use crate::metrics::{analyze_heavy_usage, analyze_restarts, analyze_pending_pods, list_pod_metrics_http};
+use crate::types::{Config, HeavyUsagePod, RbacScope, RestartEventInfo, PendingPodInfo};
+use crate::metrics::{analyze_heavy_usage, analyze_restarts, analyze_pending_pods};
+use crate::metrics::base::get_json_with_retry;
+use crate::metrics::custom::resolve_custom_resource_checks;
+use crate::collector::{JobMetrics, MetricsCollector, PodMetrics, VolumeMetrics};
 
-pub async fn ensure_metrics_available(client: &Client, namespaces: &[String]) -> Result<()> {
-    let ns = namespaces.get(0).ok_or_else(|| anyhow!("No namespaces provided"))?;
-    let _ = list_pod_metrics_http(client, ns).await?;
-    Ok(())
+/// Result of probing metrics.k8s.io for every configured namespace and for nodes
+#[derive(Debug, Clone, Default)]
+pub struct MetricsAvailability {
+    /// Namespaces whose pod metrics endpoint did not respond
+    pub unavailable_namespaces: Vec<String>,
+    /// The node metrics endpoint did not respond
+    pub nodes_unavailable: bool,
 }
 
+impl MetricsAvailability {
+    pub fn pods_available(&self, namespace: &str) -> bool {
+        !self.unavailable_namespaces.iter().any(|ns| ns == namespace)
+    }
+
+    pub fn nodes_available(&self) -> bool {
+        !self.nodes_unavailable
+    }
+
+    pub fn is_fully_available(&self) -> bool {
+        self.unavailable_namespaces.is_empty() && !self.nodes_unavailable
+    }
+}
+
+/// Probe the metrics API for each namespace and the nodes endpoint without failing
+pub async fn probe_metrics_availability(client: &Client, namespaces: &[String]) -> MetricsAvailability {
+    let mut availability = probe_namespace_metrics_availability(client, namespaces).await;
+    if let Err(e) = get_json_with_retry::<serde_json::Value>(client, "/apis/metrics.k8s.io/v1beta1/nodes?limit=1").await {
+        warn!("Node metrics unavailable: {}", e);
+        availability.nodes_unavailable = true;
+    }
+    availability
+}
+
+/// Probe the metrics API for each namespace only, for namespace-scoped runs that may
+/// not read node metrics
+pub async fn probe_namespace_metrics_availability(client: &Client, namespaces: &[String]) -> MetricsAvailability {
+    let mut availability = MetricsAvailability::default();
+    for ns in namespaces {
+        let path = format!("/apis/metrics.k8s.io/v1beta1/namespaces/{}/pods?limit=1", ns);
+        if let Err(e) = get_json_with_retry::<serde_json::Value>(client, &path).await {
+            warn!("Pod metrics unavailable for namespace {}: {}", ns, e);
+            availability.unavailable_namespaces.push(ns.clone());
+        }
+    }
+    availability
+}
+
+/// Probe the metrics API and fail if any namespace or, with `include_nodes`, the nodes
+/// endpoint is unavailable
+pub async fn ensure_metrics_available(client: &Client, namespaces: &[String], include_nodes: bool) -> Result<MetricsAvailability> {
+    if namespaces.is_empty() {
+        return Err(anyhow!("No namespaces provided"));
+    }
+    let availability = if include_nodes {
+        probe_metrics_availability(client, namespaces).await
+    } else {
+        probe_namespace_metrics_availability(client, namespaces).await
+    };
+    if !availability.unavailable_namespaces.is_empty() {
+        return Err(anyhow!(
+            "Metrics API unavailable for namespaces: {}",
+            availability.unavailable_namespaces.join(", ")
+        ));
+    }
+    if availability.nodes_unavailable {
+        return Err(anyhow!("Metrics API unavailable for nodes"));
+    }
+    Ok(availability)
+}
+
+/// Preflight check resolving `RBAC_SCOPE=auto`: `Namespace` when the API server forbids
+/// listing nodes (the reporter was installed with namespaced Roles only), `Cluster`
+/// otherwise; other failures are left for the analyzers to report
+pub async fn resolve_rbac_scope(client: &Client, scope: RbacScope) -> RbacScope {
+    if scope != RbacScope::Auto {
+        return scope;
+    }
+    let node_api: Api<Node> = Api::all(client.clone());
+    match node_api.list(&ListParams::default().limit(1)).await {
+        Err(kube::Error::Api(ae)) if ae.code == 403 => {
+            info!("Listing nodes is forbidden, running namespace-scoped analyzers only");
+            RbacScope::Namespace
+        }
+        _ => RbacScope::Cluster,
+    }
+}
+
+/// Configured namespaces the API server says do not exist (typos, deleted namespaces).
+/// Namespaces whose lookup fails otherwise, e.g. forbidden, are assumed to exist.
+pub async fn find_missing_namespaces(client: &Client, namespaces: &[String]) -> Vec<String> {
+    let namespace_api: Api<Namespace> = Api::all(client.clone());
+    let mut missing = Vec::new();
+    for namespace in namespaces {
+        match namespace_api.get(namespace).await {
+            Err(kube::Error::Api(ae)) if ae.code == 404 => missing.push(namespace.clone()),
+            Err(e) => debug!("Could not verify namespace {} exists: {}", namespace, e),
+            Ok(_) => {}
+        }
+    }
+    missing
+}
+
+/// Node labels that carry the cluster name on managed offerings (eksctl-built EKS, AKS)
+const CLUSTER_NAME_NODE_LABELS: &[&str] = &["alpha.eksctl.io/cluster-name", "kubernetes.azure.com/cluster"];
+
+const GKE_CLUSTER_NAME_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/attributes/cluster-name";
+
+/// Best-effort cluster identifier for when `CLUSTER_NAME` is unset. Tries, in order:
+/// node labels set by EKS/AKS, the GKE metadata server, the API server host from the
+/// `kube-public/cluster-info` ConfigMap, and finally the kube-system namespace UID.
+pub async fn detect_cluster_name(client: &Client) -> Option<String> {
+    let node_api: Api<Node> = Api::all(client.clone());
+    match node_api.list(&ListParams::default().limit(1)).await {
+        Ok(nodes) => {
+            if let Some(node) = nodes.items.first() {
+                if let Some(name) = cluster_name_from_node_labels(node) {
+                    return Some(name);
+                }
+                let on_gce = node
+                    .spec
+                    .as_ref()
+                    .and_then(|s| s.provider_id.as_deref())
+                    .is_some_and(|id| id.starts_with("gce://"));
+                if on_gce {
+                    match gke_cluster_name().await {
+                        Ok(name) => return Some(name),
+                        Err(e) => debug!("GKE metadata server lookup failed: {}", e),
+                    }
+                }
+            }
+        }
+        Err(e) => debug!("Listing nodes for cluster name detection failed: {}", e),
+    }
+
+    let cm_api: Api<ConfigMap> = Api::namespaced(client.clone(), "kube-public");
+    match cm_api.get_opt("cluster-info").await {
+        Ok(Some(cm)) => {
+            let host = cm
+                .data
+                .as_ref()
+                .and_then(|d| d.get("kubeconfig"))
+                .and_then(|kubeconfig| cluster_info_server_host(kubeconfig));
+            if host.is_some() {
+                return host;
+            }
+        }
+        Ok(None) => {}
+        Err(e) => debug!("Reading kube-public/cluster-info failed: {}", e),
+    }
+
+    let ns_api: Api<Namespace> = Api::all(client.clone());
+    match ns_api.get("kube-system").await {
+        Ok(ns) => ns.metadata.uid.as_deref().map(cluster_id_from_uid),
+        Err(e) => {
+            warn!("Could not detect a cluster name: {}", e);
+            None
+        }
+    }
+}
+
+fn cluster_name_from_node_labels(node: &Node) -> Option<String> {
+    let labels = node.metadata.labels.as_ref()?;
+    CLUSTER_NAME_NODE_LABELS
+        .iter()
+        .find_map(|key| labels.get(*key))
+        .filter(|name| !name.is_empty())
+        .cloned()
+}
+
+async fn gke_cluster_name() -> Result<String> {
+    // The metadata server is link-local; an egress proxy from the environment cannot reach it
+    let name = reqwest::Client::builder()
+        .no_proxy()
+        .build()?
+        .get(GKE_CLUSTER_NAME_URL)
+        .header("Metadata-Flavor", "Google")
+        .timeout(Duration::from_secs(2))
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(anyhow!("empty cluster-name attribute"));
+    }
+    Ok(name.to_string())
+}
+
+/// Host of the `server:` URL in the cluster-info kubeconfig (e.g. `api.prod.example.com`)
+fn cluster_info_server_host(kubeconfig: &str) -> Option<String> {
+    let server = kubeconfig
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("server:"))?
+        .trim()
+        .trim_matches(|c| c == '"' || c == '\'');
+    let authority = server.split_once("://").map_or(server, |(_, rest)| rest);
+    let host = authority.split(['/', ':']).next()?;
+    (!host.is_empty()).then(|| host.to_string())
+}
+
+/// Short stable identifier derived from the kube-system namespace UID
+fn cluster_id_from_uid(uid: &str) -> String {
+    format!("cluster-{}", uid.chars().take(8).collect::<String>())
+}
+
+/// Every check's findings for one namespace
+#[derive(Default)]
+pub struct NamespaceHealth {
+    pub namespace: String,
+    pub pods: PodMetrics,
+    pub jobs: JobMetrics,
+    pub volumes: VolumeMetrics,
+}
+
+impl NamespaceHealth {
+    /// Findings counted as issues in the report summary; hygiene, Pod Security Standards,
+    /// quotas, LimitRange conflicts, stale metrics and finished-object backlog are advisory
+    /// and left out
+    pub fn issue_count(&self) -> usize {
+        let (pods, jobs) = (&self.pods, &self.jobs);
+        pods.heavy_usage.len()
+            + pods.restarts.len()
+            + pods.pending.len()
+            + pods.failed.len()
+            + pods.unready.len()
+            + pods.readiness_flapping.len()
+            + pods.endpoint_churn.len()
+            + pods.oom_killed.len()
+            + pods.volume_scheduling.len()
+            + pods.missing_config_refs.len()
+            + pods.churn.len()
+            + usize::from(pods.event_flood.is_some())
+            + pods.stuck_terminating.len()
+            + pods.custom_resource_conditions.len()
+            + jobs.failed_jobs.len()
+            + jobs.backoff_risk.len()
+            + jobs.missed_cronjobs.len()
+            + jobs.cronjob_concurrency.len()
+            + self.volumes.volume_issues.len()
+    }
+
+    pub fn has_issues(&self) -> bool {
+        self.issue_count() > 0
+    }
+}
+
+/// Run every namespaced check for `namespace`, probing metrics.k8s.io first so usage
+/// checks are skipped rather than failing when it is unavailable
+pub async fn analyze_namespace_health(client: &Client, namespace: &str, cfg: &Config) -> Result<NamespaceHealth> {
+    let availability = probe_metrics_availability(client, &[namespace.to_string()]).await;
+    let custom_resource_checks = resolve_custom_resource_checks(client, &cfg.custom_resource_checks).await;
+    let metrics = MetricsCollector::new(client, cfg)
+        .with_metrics_availability(availability)
+        .with_custom_resource_checks(custom_resource_checks)
+        .collect_namespace_metrics(namespace)
+        .await?;
+    Ok(NamespaceHealth {
+        namespace: namespace.to_string(),
+        pods: metrics.pods,
+        jobs: metrics.jobs,
+        volumes: metrics.volumes,
+    })
+}
+
+#[deprecated(note = "covers only heavy usage, restarts and pending pods; use analyze_namespace_health")]
 pub async fn analyze_namespace(
     client: &Client,
     namespace: &str,
@@ -22,3 +289,80 @@ pub async fn analyze_namespace(
     Ok((heavy, restarts, pendings))
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_availability() {
+        let availability = MetricsAvailability::default();
+        assert!(availability.is_fully_available());
+        assert!(availability.pods_available("default"));
+        assert!(availability.nodes_available());
+
+        let availability = MetricsAvailability {
+            unavailable_namespaces: vec!["broken".to_string()],
+            nodes_unavailable: true,
+        };
+        assert!(!availability.is_fully_available());
+        assert!(!availability.pods_available("broken"));
+        assert!(availability.pods_available("default"));
+        assert!(!availability.nodes_available());
+    }
+
+    #[test]
+    fn test_cluster_name_from_node_labels() {
+        use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+
+        let node = Node {
+            metadata: ObjectMeta {
+                labels: Some([("alpha.eksctl.io/cluster-name".to_string(), "prod-eu".to_string())].into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(cluster_name_from_node_labels(&node), Some("prod-eu".to_string()));
+        assert_eq!(cluster_name_from_node_labels(&Node::default()), None);
+    }
+
+    #[test]
+    fn test_cluster_info_server_host() {
+        let kubeconfig = "apiVersion: v1\nclusters:\n- cluster:\n    certificate-authority-data: abc\n    server: https://api.prod.example.com:6443\n  name: \"\"\n";
+        assert_eq!(cluster_info_server_host(kubeconfig), Some("api.prod.example.com".to_string()));
+        assert_eq!(cluster_info_server_host("clusters: []"), None);
+    }
+
+    #[test]
+    fn test_namespace_health_issue_count() {
+        use crate::types::{FailedJobInfo, ResourceHygieneInfo, ResourceHygieneIssue};
+
+        let mut health = NamespaceHealth {
+            namespace: "batch".to_string(),
+            ..Default::default()
+        };
+        assert!(!health.has_issues());
+
+        health.pods.resource_hygiene.push(ResourceHygieneInfo {
+            namespace: "batch".to_string(),
+            workload: "Deployment/api".to_string(),
+            container: "main".to_string(),
+            issue: ResourceHygieneIssue::MemoryLimitUnset,
+        });
+        // Advisory findings are not issues
+        assert!(!health.has_issues());
+
+        health.jobs.failed_jobs.push(FailedJobInfo {
+            namespace: "batch".to_string(),
+            job: "nightly".to_string(),
+            failed_pods: 2,
+            last_failure_time: None,
+            reason: None,
+        });
+        assert_eq!(health.issue_count(), 1);
+    }
+
+    #[test]
+    fn test_cluster_id_from_uid() {
+        assert_eq!(cluster_id_from_uid("3f2a9c1e-0b7d-4e55-9a11-2c6d8e4f0a12"), "cluster-3f2a9c1e");
+    }
+}
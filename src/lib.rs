@@ -3,17 +3,60 @@ pub mod types;
 pub mod config;
 pub mod parsing;
 pub mod slack;
+pub mod slack_api;
+pub mod blocks;
 pub mod kubernetes;
 pub mod metrics;
 pub mod collector;
 pub mod report;
+pub mod ownership;
+pub mod suppression;
+pub mod state;
+pub mod delivery;
+pub mod secrets;
+pub mod redaction;
+pub mod tenancy;
+pub mod federation;
+pub mod pipeline;
+pub mod notifier;
+pub mod hooks;
+pub mod email;
+pub mod telegram;
+pub mod pagerduty;
+pub mod webhook;
+pub mod openmetrics;
+pub mod pushgateway;
+pub mod csv_export;
+pub mod output;
+pub mod exporter;
+pub mod daemon;
+pub mod freeze;
+pub mod oncall;
+pub mod api_health;
+pub mod audit;
+pub mod recording;
+pub mod self_usage;
+pub mod schema;
+pub mod scaffold;
 
 // Re-export commonly used items
 pub use types::*;
 pub use config::{load_config, load_config_with_env, EnvironmentProvider, SystemEnvironment, MockEnvironment};
-pub use parsing::{parse_cpu_to_millicores, parse_memory_to_bytes, compute_utilization_percentages, any_exceeds};
-pub use slack::{build_slack_payload, send_to_slack};
-pub use kubernetes::{ensure_metrics_available, analyze_namespace};
+pub use parsing::{parse_quantity, Quantity, parse_cpu_to_millicores, parse_memory_to_bytes, humanize_bytes, humanize_millicores, format_cpu, format_memory, compute_utilization_percentages, any_exceeds};
+pub use blocks::{Block, SlackMessage, Text};
+pub use slack::{build_slack_payload, build_stale_metrics_section, build_recommendations_section, build_resource_hygiene_section, build_pod_security_section, build_pod_churn_section, build_event_flood_section, build_stuck_terminating_section, build_custom_resources_section,
+    build_volume_scheduling_section, build_job_backoff_section,
+    build_cronjob_concurrency_section, build_finished_objects_section, build_namespace_quotas_section, build_limit_range_conflicts_section,
+    build_missing_config_refs_section, build_readiness_flapping_section,
+    build_endpoint_churn_section, build_dual_stack_section, build_network_agents_section, build_operators_section, build_node_disk_section, build_object_counts_section, build_node_flapping_section, build_summary_context, apply_section_order, build_run_footer, build_owners_section, build_team_context,
+    build_previously_snoozed_section, build_chronic_issues_section,
+    build_resolved_section, build_api_health_context, build_report_payload, send_to_slack, split_payload};
+pub use kubernetes::{detect_cluster_name, ensure_metrics_available, probe_metrics_availability, probe_namespace_metrics_availability, resolve_rbac_scope, find_missing_namespaces, analyze_namespace_health, NamespaceHealth, MetricsAvailability};
+#[allow(deprecated)]
+pub use kubernetes::analyze_namespace;
 pub use metrics::*;
 pub use collector::MetricsCollector;
-pub use report::{HealthReport, ReportSummary};
+pub use report::{new_run_id, HealthReport, ReportSummary};
+pub use pipeline::{default_notifiers, default_sinks, deliver, render, report_payload, should_send, notify_unless_vetoed, resolve_oncall_mention, run, run_hooked, run_instrumented, run_with_client, RunClient, Sink};
+pub use hooks::{default_hooks, CommandHook, Hook, HookFuture, HookRegistry};
+pub use notifier::{CsvNotifier, EmailNotifier, GenericWebhookNotifier, Notifier, NotifierRegistry, NotifyFuture, PagerDutyNotifier, SlackBotNotifier, SlackNotifier, TelegramNotifier, TextfileNotifier};
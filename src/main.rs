@@ -1,92 +1,142 @@
 use anyhow::Result;
-use kube::Client;
-use tracing::info;
+use std::path::Path;
+use tracing::{info, info_span, warn, Instrument};
 
-mod types;
-mod config;
-mod parsing;
-mod slack;
-mod kubernetes;
-mod metrics;
-mod collector;
-mod report;
-
-use config::load_config;
-use slack::{build_slack_payload, send_to_slack};
-use kubernetes::ensure_metrics_available;
-use collector::MetricsCollector;
-use report::HealthReport;
+use kube_health_reporter::config::{load_config, SystemEnvironment};
+use kube_health_reporter::daemon::run_daemon;
+use kube_health_reporter::delivery::webhook_client;
+use kube_health_reporter::exporter::run_exporter;
+use kube_health_reporter::federation::run_federation;
+use kube_health_reporter::hooks::default_hooks;
+use kube_health_reporter::pipeline::{default_notifiers, notify_unless_vetoed, resolve_oncall_mention, run_hooked, RunClient};
+use kube_health_reporter::recording::kube_config;
+use kube_health_reporter::report::rollup::{build_rollup, render_rollup_html, render_rollup_markdown};
+use kube_health_reporter::scaffold::scaffold_analyzer;
+use kube_health_reporter::schema::{load_settings_file, settings_schema, validate_settings};
+use kube_health_reporter::secrets::SecretResolver;
+use kube_health_reporter::state::{parse_snooze_duration, Snooze, StateStore};
+use kube_health_reporter::types::RunMode;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     init_tracing();
-    let cfg = load_config()?;
-    info!("namespaces = {:?}", cfg.namespaces);
-
-    let client = Client::try_default().await?;
-
-    // Check metrics API availability early (fail fast if requested)
-    if cfg.fail_if_no_metrics { 
-        ensure_metrics_available(&client, &cfg.namespaces).await?; 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        return run_command(&args);
     }
 
-    let collector = MetricsCollector::new(&client, &cfg);
-    let mut report = HealthReport::new(cfg.clone());
-
-    // Collect metrics for each namespace
-    for ns in &cfg.namespaces {
-        info!("Collecting metrics for namespace: {}", ns);
-        
-        // Collect pod metrics
-        let pod_metrics = collector.collect_pod_metrics(ns).await?;
-        report.add_pod_metrics(pod_metrics);
-
-        // Collect job metrics
-        let job_metrics = collector.collect_job_metrics(ns).await?;
-        report.add_job_metrics(job_metrics);
-
-        // Collect volume metrics
-        let volume_metrics = collector.collect_volume_metrics(ns).await?;
-        report.add_volume_metrics(volume_metrics);
+    let cfg = load_config()?;
+    // Secret references are resolved up front so a bad one fails before collection; the
+    // reference itself is kept and resolved again for every run, picking up rotations
+    let resolver = SecretResolver::from_env(&SystemEnvironment, webhook_client(&cfg)?)?;
+    resolver.resolve(&cfg.slack_webhook_url).await?;
+    if !cfg.clusters.is_empty() {
+        return run_federation(cfg, &resolver).await;
     }
+    if cfg.run_mode == RunMode::Daemon {
+        return match cfg.metrics_listen_addr {
+            Some(_) => run_exporter(cfg, &resolver).await,
+            None => run_daemon(cfg, &resolver, None).await,
+        };
+    }
+    let hooks = default_hooks(&cfg);
+    let client = RunClient::new(kube_config(cfg.api_recording.as_ref()).await?, &cfg)?;
+    let mut report = run_hooked(&hooks, &client, cfg).await?;
+    resolve_oncall_mention(&resolver, &mut report).await;
+    let notifiers = default_notifiers(&resolver, &report).await?;
+    notify_unless_vetoed(&hooks, &notifiers, &report)
+        .instrument(info_span!("run", run_id = %report.run_id))
+        .await
+}
 
-    // Collect cluster-wide metrics
-    info!("Collecting cluster-wide metrics");
-    let cluster_metrics = collector.collect_cluster_metrics().await?;
-    report.set_cluster_metrics(cluster_metrics);
-
-    // Log summary
-    let summary = report.summary();
-    info!("Health report summary: {} total issues found", summary.total_issues());
-
-    // Send to Slack only if there are issues
-    if summary.has_issues() {
-        info!("Issues detected, sending notification to Slack");
-        let payload = build_slack_payload(
-            &report.config, 
-            &report.pod_metrics.heavy_usage, 
-            &report.pod_metrics.restarts, 
-            &report.pod_metrics.pending,
-            &report.pod_metrics.failed,
-            &report.pod_metrics.unready,
-            &report.pod_metrics.oom_killed,
-            &report.cluster_metrics.problematic_nodes,
-            &report.cluster_metrics.high_utilization_nodes,
-            &report.volume_metrics.volume_issues,
-            &report.job_metrics.failed_jobs,
-            &report.job_metrics.missed_cronjobs
-        );
-        send_to_slack(&report.config.slack_webhook_url, &payload).await?;
-    } else {
-        info!("No issues detected, skipping Slack notification");
+/// `snooze <section/namespace/name> <duration> [reason...]` and `unsnooze <fingerprint>`
+/// edit the snoozes in `STATE_FILE`; the next runs leave the finding out until it expires.
+/// `rollup [days] [--html]` prints a Markdown (or HTML) summary of the last days
+/// (default 7) of runs.
+/// `config schema` prints the JSON Schema of the settings and `config validate <file>`
+/// checks a settings file against it. `generate analyzer <name>` scaffolds a new check
+/// in the source tree (run from the repository root).
+fn run_command(args: &[String]) -> Result<()> {
+    if args[0] == "config" {
+        return run_config_command(&args[1..]);
     }
+    if let [cmd, kind, name] = args {
+        if cmd == "generate" && kind == "analyzer" {
+            for path in scaffold_analyzer(Path::new("."), name)? {
+                println!("{}", path.display());
+            }
+            return Ok(());
+        }
+    }
+    let path = std::env::var("STATE_FILE").map_err(|_| anyhow::anyhow!("STATE_FILE must be set for {}", args[0]))?;
+    let path = Path::new(&path);
+    let mut state = StateStore::load(path)?;
+    match args {
+        [cmd, fingerprint, duration, reason @ ..] if cmd == "snooze" => {
+            let until = chrono::Utc::now()
+                .checked_add_signed(parse_snooze_duration(duration)?)
+                .ok_or_else(|| anyhow::anyhow!("Snooze duration {} is too long", duration))?;
+            let reason = (!reason.is_empty()).then(|| reason.join(" "));
+            state.snoozes.insert(fingerprint.clone(), Snooze { until, reason, suppression: None });
+            info!("Snoozed {} until {}", fingerprint, until);
+        }
+        [cmd, fingerprint] if cmd == "unsnooze" => {
+            if state.snoozes.remove(fingerprint).is_none() {
+                warn!("{} is not snoozed", fingerprint);
+            }
+        }
+        [cmd, rest @ ..] if cmd == "rollup" => {
+            let html = rest.iter().any(|arg| arg == "--html");
+            let days = match rest.iter().filter(|arg| *arg != "--html").collect::<Vec<_>>()[..] {
+                [d] => d.parse().ok().filter(|d| *d > 0).ok_or_else(|| anyhow::anyhow!("Invalid rollup days: {}", d))?,
+                [] => 7,
+                _ => anyhow::bail!("Usage: kube-health-reporter rollup [days] [--html]"),
+            };
+            let rollup = build_rollup(&state.runs, chrono::Utc::now(), days);
+            let cluster = std::env::var("CLUSTER_NAME").ok();
+            if html {
+                print!("{}", render_rollup_html(&rollup, cluster.as_deref()));
+            } else {
+                print!("{}", render_rollup_markdown(&rollup, cluster.as_deref()));
+            }
+            return Ok(());
+        }
+        _ => anyhow::bail!(
+            "Usage: kube-health-reporter [snooze <section/namespace/name> <duration> [reason...] | \
+             unsnooze <section/namespace/name> | rollup [days] [--html] | config schema | config validate <file> | \
+             generate analyzer <name>]"
+        ),
+    }
+    state.save(path)
+}
 
-    Ok(())
+/// Needs no cluster or state, so GitOps pipelines can lint settings before rollout
+fn run_config_command(args: &[String]) -> Result<()> {
+    match args {
+        [cmd] if cmd == "schema" => {
+            println!("{}", serde_json::to_string_pretty(&settings_schema())?);
+            Ok(())
+        }
+        [cmd, file] if cmd == "validate" => {
+            let problems = validate_settings(&load_settings_file(Path::new(file))?);
+            if problems.is_empty() {
+                println!("{}: valid", file);
+                return Ok(());
+            }
+            for problem in &problems {
+                eprintln!("{}: {}", file, problem);
+            }
+            anyhow::bail!("{} problems found in {}", problems.len(), file)
+        }
+        _ => anyhow::bail!("Usage: kube-health-reporter config [schema | validate <file>]"),
+    }
 }
 
+/// Logs go to stderr so that `CSV_EXPORT=-` and `OUTPUT_FORMAT` leave stdout to the report
 fn init_tracing() {
     let _ = tracing_subscriber::fmt()
         .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .with_writer(std::io::stderr)
         .with_target(false)
         .try_init();
 }
\ No newline at end of file
@@ -1,9 +1,13 @@
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc};
-use k8s_openapi::api::core::v1::Pod;
+use k8s_openapi::api::core::v1::{Event, Pod};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ObjectMeta, Time};
 use kube::Client;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use std::collections::HashMap;
+use std::time::Duration;
+use tracing::debug;
 
 use crate::types::PodUsageTotals;
 use crate::parsing::{parse_cpu_to_millicores, parse_memory_to_bytes};
@@ -14,9 +18,14 @@ pub struct ContainerMetrics {
     pub usage: HashMap<String, String> 
 }
 
+/// metrics.k8s.io/v1beta1 PodMetrics
 #[derive(Debug, Deserialize)]
 pub struct PodMetricsItem { 
-    pub metadata: serde_json::Value, 
+    pub metadata: ObjectMeta,
+    /// When the usage sample was taken
+    pub timestamp: Option<Time>,
+    /// Sampling window the usage was averaged over (e.g. "30s")
+    pub window: Option<String>,
     pub containers: Vec<ContainerMetrics> 
 }
 
@@ -25,29 +34,96 @@ pub struct PodMetricsList {
     pub items: Vec<PodMetricsItem> 
 }
 
+/// metrics.k8s.io/v1beta1 NodeMetrics
+#[derive(Debug, Deserialize)]
+pub struct NodeMetricsItem {
+    pub metadata: ObjectMeta,
+    pub timestamp: Option<Time>,
+    pub window: Option<String>,
+    pub usage: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeMetricsList {
+    pub items: Vec<NodeMetricsItem>,
+}
+
 pub async fn list_pod_metrics_http(client: &Client, namespace: &str) -> Result<Vec<PodMetricsItem>> {
-    use http::Request as HttpRequest;
     let path = format!("/apis/metrics.k8s.io/v1beta1/namespaces/{}/pods", namespace);
+    let list: PodMetricsList = get_json_with_retry(client, &path).await?;
+    Ok(list.items)
+}
+
+pub async fn list_node_metrics_http(client: &Client) -> Result<Vec<NodeMetricsItem>> {
+    let list: NodeMetricsList = get_json_with_retry(client, "/apis/metrics.k8s.io/v1beta1/nodes").await?;
+    Ok(list.items)
+}
+
+const METRICS_RETRY_ATTEMPTS: u32 = 3;
+const METRICS_RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
+
+/// Like [`get_json`], but retries transient failures with exponential backoff.
+///
+/// metrics-server tends to answer 503 for a while after it restarts, so the
+/// metrics.k8s.io calls go through this instead of failing on the first error.
+pub(crate) async fn get_json_with_retry<T: DeserializeOwned>(client: &Client, path: &str) -> Result<T> {
+    let mut delay = METRICS_RETRY_BASE_DELAY;
+    let mut attempt = 1;
+    loop {
+        match get_json(client, path).await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < METRICS_RETRY_ATTEMPTS && is_retryable(&e) => {
+                debug!("GET {} failed (attempt {}/{}), retrying in {:?}: {}", path, attempt, METRICS_RETRY_ATTEMPTS, delay, e);
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn is_retryable(err: &anyhow::Error) -> bool {
+    match err.downcast_ref::<kube::Error>() {
+        Some(kube::Error::Api(resp)) => matches!(resp.code, 429 | 500 | 502 | 503 | 504),
+        Some(kube::Error::HyperError(_)) | Some(kube::Error::Service(_)) => true,
+        _ => false,
+    }
+}
+
+/// Issue a raw GET against the API server and decode the JSON body.
+///
+/// The Accept header is pinned to JSON on purpose: the apiserver would happily
+/// answer `application/vnd.kubernetes.protobuf` for built-in list calls, but
+/// neither kube nor k8s-openapi ship protobuf codecs, so negotiating it would
+/// only produce bodies we cannot decode.
+pub(crate) async fn get_json<T: DeserializeOwned>(client: &Client, path: &str) -> Result<T> {
+    use http::Request as HttpRequest;
     let req = HttpRequest::builder()
         .method("GET")
         .uri(path)
+        .header(http::header::ACCEPT, "application/json")
         .body(Vec::new())
         .map_err(|e| anyhow!("build request: {}", e))?;
-    let list: PodMetricsList = client.request(req).await?;
-    Ok(list.items)
+    Ok(client.request(req).await?)
 }
 
-pub fn build_usage_map_from_http(items: Vec<PodMetricsItem>) -> HashMap<String, PodUsageTotals> {
+/// Usage totals keyed by `(namespace, pod)` so identically named pods never collide
+pub type PodUsageMap = HashMap<(String, String), PodUsageTotals>;
+
+pub fn build_usage_map_from_http(items: Vec<PodMetricsItem>) -> PodUsageMap {
     let mut map = HashMap::new();
     for item in items {
-        let name = item
-            .metadata
-            .get("name")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string();
-        if name.is_empty() { continue; }
-        let mut totals = PodUsageTotals::default();
+        let name = match item.metadata.name {
+            Some(n) if !n.is_empty() => n,
+            _ => continue,
+        };
+        let namespace = item.metadata.namespace.unwrap_or_default();
+        let mut totals = PodUsageTotals {
+            timestamp: item.timestamp.map(|t| t.0),
+            window: item.window,
+            ..Default::default()
+        };
         for c in item.containers {
             if let Some(cpu_q) = c.usage.get("cpu") {
                 if let Some(mc) = parse_cpu_to_millicores(cpu_q) { totals.cpu_millicores += mc; }
@@ -56,11 +132,58 @@ pub fn build_usage_map_from_http(items: Vec<PodMetricsItem>) -> HashMap<String,
                 if let Some(bytes) = parse_memory_to_bytes(mem_q) { totals.memory_bytes += bytes; }
             }
         }
-        map.insert(name, totals);
+        map.insert((namespace, name), totals);
     }
     map
 }
 
+/// Resolve the workload controlling a pod as `(kind, name)`.
+///
+/// ReplicaSets created by a Deployment are reported as the Deployment by
+/// stripping the pod-template-hash suffix. Bare pods have no workload.
+pub fn pod_workload(pod: &Pod) -> Option<(String, String)> {
+    let owner = pod
+        .metadata
+        .owner_references
+        .as_ref()?
+        .iter()
+        .find(|o| o.controller == Some(true))?;
+    Some(controller_workload(&owner.kind, &owner.name))
+}
+
+/// Map a pod controller to its workload, reporting ReplicaSets as their Deployment
+pub fn controller_workload(kind: &str, name: &str) -> (String, String) {
+    if kind == "ReplicaSet" {
+        if let Some((deployment, _hash)) = name.rsplit_once('-') {
+            return ("Deployment".to_string(), deployment.to_string());
+        }
+    }
+    (kind.to_string(), name.to_string())
+}
+
+/// Estimate how many occurrences of an aggregated event fall after `cutoff`,
+/// assuming they are spread evenly between the first and last timestamp
+pub fn event_occurrences_since(event: &Event, cutoff: DateTime<Utc>) -> i64 {
+    let last = event
+        .last_timestamp
+        .as_ref()
+        .map(|t| t.0)
+        .or_else(|| event.event_time.as_ref().map(|t| t.0))
+        .or_else(|| event.metadata.creation_timestamp.as_ref().map(|t| t.0));
+    let last = match last {
+        Some(t) if t > cutoff => t,
+        _ => return 0,
+    };
+    let count = event.count.unwrap_or(1).max(1) as i64;
+    let first = event.first_timestamp.as_ref().map(|t| t.0).unwrap_or(last);
+    if first >= cutoff || last <= first {
+        return count;
+    }
+    let span = (last - first).num_seconds() as f64;
+    let in_window = (last - cutoff).num_seconds() as f64;
+    ((count as f64 * in_window / span).ceil() as i64).clamp(1, count)
+}
+
 pub fn pod_status_time(pod: &Pod) -> Option<DateTime<Utc>> {
     // Prefer status.startTime, fallback to metadata.creationTimestamp
     if let Some(st) = pod.status.as_ref().and_then(|s| s.start_time.as_ref()) {
@@ -71,3 +194,108 @@ pub fn pod_status_time(pod: &Pod) -> Option<DateTime<Utc>> {
         .as_ref()
         .map(|t| t.0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kube::error::ErrorResponse;
+
+    fn api_error(code: u16) -> anyhow::Error {
+        kube::Error::Api(ErrorResponse {
+            status: "Failure".to_string(),
+            message: "test".to_string(),
+            reason: "test".to_string(),
+            code,
+        })
+        .into()
+    }
+
+    fn metrics_item(namespace: &str, name: &str, cpu: &str) -> PodMetricsItem {
+        PodMetricsItem {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                namespace: Some(namespace.to_string()),
+                ..Default::default()
+            },
+            timestamp: Some(Time(Utc::now())),
+            window: Some("30s".to_string()),
+            containers: vec![ContainerMetrics {
+                name: "main".to_string(),
+                usage: HashMap::from([
+                    ("cpu".to_string(), cpu.to_string()),
+                    ("memory".to_string(), "64Mi".to_string()),
+                ]),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_build_usage_map_keys_by_namespace() {
+        let map = build_usage_map_from_http(vec![
+            metrics_item("team-a", "api", "100m"),
+            metrics_item("team-b", "api", "300m"),
+        ]);
+
+        assert_eq!(map.len(), 2);
+        let a = &map[&("team-a".to_string(), "api".to_string())];
+        let b = &map[&("team-b".to_string(), "api".to_string())];
+        assert_eq!(a.cpu_millicores, 100);
+        assert_eq!(b.cpu_millicores, 300);
+        assert_eq!(a.memory_bytes, 64 * 1024 * 1024);
+        assert!(a.timestamp.is_some());
+        assert_eq!(a.window.as_deref(), Some("30s"));
+    }
+
+    #[test]
+    fn test_pod_workload() {
+        use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
+
+        let owned_by = |kind: &str, name: &str| Pod {
+            metadata: ObjectMeta {
+                owner_references: Some(vec![OwnerReference {
+                    kind: kind.to_string(),
+                    name: name.to_string(),
+                    controller: Some(true),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(pod_workload(&owned_by("ReplicaSet", "api-7d9f8b6c5")), Some(("Deployment".to_string(), "api".to_string())));
+        assert_eq!(pod_workload(&owned_by("StatefulSet", "db")), Some(("StatefulSet".to_string(), "db".to_string())));
+        assert_eq!(pod_workload(&Pod::default()), None);
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(is_retryable(&api_error(503)));
+        assert!(is_retryable(&api_error(429)));
+        assert!(is_retryable(&api_error(500)));
+        assert!(!is_retryable(&api_error(404)));
+        assert!(!is_retryable(&api_error(403)));
+        assert!(!is_retryable(&anyhow!("not a kube error")));
+    }
+
+    fn aggregated_event(count: i32, first: DateTime<Utc>, last: DateTime<Utc>) -> Event {
+        Event {
+            count: Some(count),
+            first_timestamp: Some(Time(first)),
+            last_timestamp: Some(Time(last)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_occurrences_since_prorates_long_running_events() {
+        let now = Utc::now();
+        let cutoff = now - chrono::Duration::minutes(60);
+        // 100 creations spread over 10 hours, one hour of which is inside the window
+        let event = aggregated_event(100, now - chrono::Duration::hours(10), now);
+        assert_eq!(event_occurrences_since(&event, cutoff), 10);
+
+        let expired = aggregated_event(100, now - chrono::Duration::hours(10), now - chrono::Duration::hours(2));
+        assert_eq!(event_occurrences_since(&expired, cutoff), 0);
+    }
+}
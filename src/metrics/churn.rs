@@ -0,0 +1,256 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use k8s_openapi::api::core::v1::{Event, Pod};
+use kube::{api::ListParams, Api, Client};
+use std::collections::BTreeMap;
+
+use crate::types::{Config, EventFloodInfo, PodChurnInfo};
+use super::base::{controller_workload, event_occurrences_since};
+
+/// List events in a namespace; controllers record pod creations/deletions here
+pub async fn list_namespace_events(client: &Client, namespace: &str) -> Result<Vec<Event>> {
+    let event_api: Api<Event> = Api::namespaced(client.clone(), namespace);
+    Ok(event_api.list(&ListParams::default()).await?.items)
+}
+
+/// Flag workloads that replaced an abnormal number of pods within the churn window.
+///
+/// Creations are taken from controller `SuccessfulCreate` events, which also cover
+/// pods that were already deleted again. They are counted per controller (each
+/// ReplicaSet of a Deployment on its own): the pods a controller runs now were created
+/// once by a rollout or scale-up, only the creations beyond them are replacements.
+/// Workloads without such events (e.g. once they expired) are not checked.
+pub fn analyze_pod_churn(
+    namespace: &str,
+    cfg: &Config,
+    pods: &[Pod],
+    events: &[Event],
+    now: DateTime<Utc>,
+) -> Vec<PodChurnInfo> {
+    let cutoff = now - Duration::minutes(cfg.pod_churn_window_minutes);
+    let mut created_by_controller: BTreeMap<(String, String), i64> = BTreeMap::new();
+    let mut deleted_by_controller: BTreeMap<(String, String), i64> = BTreeMap::new();
+
+    for event in events {
+        let target = match event.reason.as_deref() {
+            Some("SuccessfulCreate") => &mut created_by_controller,
+            Some("SuccessfulDelete") => &mut deleted_by_controller,
+            _ => continue,
+        };
+        let (kind, name) = match (
+            event.involved_object.kind.as_deref(),
+            event.involved_object.name.as_deref(),
+        ) {
+            (Some(kind), Some(name)) => (kind, name),
+            _ => continue,
+        };
+        let occurrences = event_occurrences_since(event, cutoff);
+        if occurrences > 0 {
+            *target.entry((kind.to_string(), name.to_string())).or_default() += occurrences;
+        }
+    }
+
+    // Pods each controller runs now; creating them replaced nothing
+    let mut running: BTreeMap<(&str, &str), i64> = BTreeMap::new();
+    for pod in pods {
+        let owner = pod.metadata.owner_references.iter().flatten().find(|o| o.controller == Some(true));
+        if let Some(owner) = owner {
+            *running.entry((owner.kind.as_str(), owner.name.as_str())).or_default() += 1;
+        }
+    }
+
+    let mut replaced: BTreeMap<(String, String), i64> = BTreeMap::new();
+    let mut created: BTreeMap<(String, String), i64> = BTreeMap::new();
+    for ((kind, name), &count) in &created_by_controller {
+        let workload = controller_workload(kind, name);
+        let current = running.get(&(kind.as_str(), name.as_str())).copied().unwrap_or(0);
+        *replaced.entry(workload.clone()).or_default() += (count - current).max(0);
+        *created.entry(workload).or_default() += count;
+    }
+    let mut deleted: BTreeMap<(String, String), i64> = BTreeMap::new();
+    for ((kind, name), &count) in &deleted_by_controller {
+        *deleted.entry(controller_workload(kind, name)).or_default() += count;
+    }
+
+    replaced
+        .into_iter()
+        .filter_map(|(workload, replaced)| {
+            if replaced < cfg.pod_churn_threshold {
+                return None;
+            }
+            Some(PodChurnInfo {
+                namespace: namespace.to_string(),
+                kind: workload.0.clone(),
+                workload: workload.1.clone(),
+                created: created[&workload],
+                deleted: deleted.get(&workload).copied().unwrap_or(0),
+                window_minutes: cfg.pod_churn_window_minutes,
+            })
+        })
+        .collect()
+}
+
+/// Reasons listed for a flooding namespace
+const MAX_FLOOD_REASONS: usize = 3;
+
+/// Flag a namespace recording at least `event_flood_threshold` Warning events within the
+/// window, with the most frequent reasons. Noisy controllers show up here even when the
+/// objects they manage look healthy.
+pub fn analyze_event_flood(namespace: &str, cfg: &Config, events: &[Event], now: DateTime<Utc>) -> Option<EventFloodInfo> {
+    let cutoff = now - Duration::minutes(cfg.event_flood_window_minutes);
+    let mut by_reason: BTreeMap<String, i64> = BTreeMap::new();
+    for event in events.iter().filter(|e| e.type_.as_deref() == Some("Warning")) {
+        let occurrences = event_occurrences_since(event, cutoff);
+        if occurrences > 0 {
+            *by_reason.entry(event.reason.clone().unwrap_or_default()).or_default() += occurrences;
+        }
+    }
+
+    let warnings: i64 = by_reason.values().sum();
+    if warnings < cfg.event_flood_threshold {
+        return None;
+    }
+    let mut top_reasons: Vec<(String, i64)> = by_reason.into_iter().collect();
+    top_reasons.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    top_reasons.truncate(MAX_FLOOD_REASONS);
+    Some(EventFloodInfo {
+        namespace: namespace.to_string(),
+        warnings,
+        top_reasons,
+        window_minutes: cfg.event_flood_window_minutes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::ObjectReference;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ObjectMeta, OwnerReference, Time};
+
+    fn create_test_config() -> Config {
+        Config {
+            pod_churn_window_minutes: 60,
+            pod_churn_threshold: 5,
+            ..Default::default()
+        }
+    }
+
+    fn controller_event(reason: &str, kind: &str, name: &str, count: i32, first: DateTime<Utc>, last: DateTime<Utc>) -> Event {
+        Event {
+            reason: Some(reason.to_string()),
+            involved_object: ObjectReference {
+                kind: Some(kind.to_string()),
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
+            count: Some(count),
+            first_timestamp: Some(Time(first)),
+            last_timestamp: Some(Time(last)),
+            ..Default::default()
+        }
+    }
+
+    fn owned_pods(owner_kind: &str, owner: &str, count: usize) -> Vec<Pod> {
+        (0..count)
+            .map(|i| Pod {
+                metadata: ObjectMeta {
+                    name: Some(format!("{}-{}", owner, i)),
+                    owner_references: Some(vec![OwnerReference {
+                        kind: owner_kind.to_string(),
+                        name: owner.to_string(),
+                        controller: Some(true),
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_churn_from_controller_events() {
+        let now = Utc::now();
+        let events = vec![
+            controller_event("SuccessfulCreate", "ReplicaSet", "api-7d9f8b6c5", 8, now - Duration::minutes(30), now),
+            controller_event("SuccessfulDelete", "ReplicaSet", "api-7d9f8b6c5", 7, now - Duration::minutes(30), now),
+            controller_event("SuccessfulCreate", "ReplicaSet", "web-5c6d7e8f9", 2, now - Duration::minutes(30), now),
+            controller_event("Scheduled", "Pod", "api-7d9f8b6c5-abcde", 1, now, now),
+        ];
+
+        // One pod left running, the other 7 creations replaced deleted pods
+        let mut pods = owned_pods("ReplicaSet", "api-7d9f8b6c5", 1);
+        pods.extend(owned_pods("ReplicaSet", "web-5c6d7e8f9", 2));
+
+        let churn = analyze_pod_churn("default", &create_test_config(), &pods, &events, now);
+        assert_eq!(churn.len(), 1);
+        assert_eq!(churn[0].kind, "Deployment");
+        assert_eq!(churn[0].workload, "api");
+        assert_eq!(churn[0].created, 8);
+        assert_eq!(churn[0].deleted, 7);
+    }
+
+    #[test]
+    fn test_churn_counts_creations_within_the_window_only() {
+        let now = Utc::now();
+        // A rollout hours ago; the pods it created are no churn now
+        let events = vec![
+            controller_event("SuccessfulCreate", "ReplicaSet", "worker-6b7c8d9e0", 6, now - Duration::hours(4), now - Duration::hours(3)),
+        ];
+        assert!(analyze_pod_churn("default", &create_test_config(), &[], &events, now).is_empty());
+    }
+
+    #[test]
+    fn test_rollout_is_no_churn() {
+        let now = Utc::now();
+        let config = Config { pod_churn_window_minutes: 60, ..Default::default() };
+        // A 12-replica Deployment rolled out: the new ReplicaSet created 12 pods and
+        // runs them, the old one deleted its 12
+        let events = vec![
+            controller_event("SuccessfulCreate", "ReplicaSet", "api-8e0a9c7d6", 12, now - Duration::minutes(20), now - Duration::minutes(10)),
+            controller_event("SuccessfulDelete", "ReplicaSet", "api-7d9f8b6c5", 12, now - Duration::minutes(20), now - Duration::minutes(10)),
+        ];
+        let pods = owned_pods("ReplicaSet", "api-8e0a9c7d6", 12);
+        assert!(analyze_pod_churn("default", &config, &pods, &events, now).is_empty());
+
+        // The same ReplicaSet replacing its pods again afterwards is churn
+        let mut events = events;
+        events.push(controller_event("SuccessfulCreate", "ReplicaSet", "api-8e0a9c7d6", 10, now - Duration::minutes(9), now));
+        let churn = analyze_pod_churn("default", &config, &pods, &events, now);
+        assert_eq!(churn.len(), 1);
+        assert_eq!((churn[0].created, churn[0].deleted), (22, 12));
+    }
+
+    #[test]
+    fn test_analyze_event_flood() {
+        let now = Utc::now();
+        let config = Config { event_flood_window_minutes: 60, event_flood_threshold: 100, ..Default::default() };
+        let warning = |reason: &str, count: i32, last: DateTime<Utc>| Event {
+            type_: Some("Warning".to_string()),
+            ..controller_event(reason, "Pod", "api", count, last, last)
+        };
+        let mut events = vec![
+            warning("BackOff", 60, now - Duration::minutes(5)),
+            warning("FailedMount", 25, now - Duration::minutes(10)),
+            warning("Unhealthy", 10, now - Duration::minutes(20)),
+            warning("FailedScheduling", 5, now - Duration::minutes(30)),
+            // Outside the window, and a Normal event
+            warning("BackOff", 500, now - Duration::hours(3)),
+            controller_event("SuccessfulCreate", "ReplicaSet", "api-1", 400, now, now),
+        ];
+
+        let flood = analyze_event_flood("default", &config, &events, now).unwrap();
+        assert_eq!(flood.namespace, "default");
+        assert_eq!(flood.warnings, 100);
+        assert_eq!(
+            flood.top_reasons,
+            vec![("BackOff".to_string(), 60), ("FailedMount".to_string(), 25), ("Unhealthy".to_string(), 10)]
+        );
+        assert_eq!(flood.window_minutes, 60);
+
+        events.pop();
+        events.remove(0);
+        assert!(analyze_event_flood("default", &config, &events, now).is_none());
+    }
+
+}
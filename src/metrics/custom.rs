@@ -0,0 +1,133 @@
+use anyhow::Result;
+use kube::api::{ApiResource, DynamicObject, GroupVersionKind, ListParams};
+use kube::discovery::{self, Scope};
+use kube::{Api, Client};
+use tracing::warn;
+
+use crate::types::{CustomResourceCheck, CustomResourceConditionInfo};
+
+/// A configured check with the API resource it resolved to
+#[derive(Debug, Clone)]
+pub struct ResolvedCustomResourceCheck {
+    pub check: CustomResourceCheck,
+    pub resource: ApiResource,
+}
+
+/// Resolve the checked kinds through API discovery. Kinds the cluster does not serve
+/// (CRD not installed) and cluster-scoped kinds are skipped with a warning.
+pub async fn resolve_custom_resource_checks(client: &Client, checks: &[CustomResourceCheck]) -> Vec<ResolvedCustomResourceCheck> {
+    let mut resolved = Vec::new();
+    for check in checks {
+        let found = match &check.version {
+            Some(version) => {
+                let gvk = GroupVersionKind::gvk(&check.group, version, &check.kind);
+                discovery::pinned_kind(client, &gvk).await.map(Some)
+            }
+            None => discovery::group(client, &check.group).await.map(|group| group.recommended_kind(&check.kind)),
+        };
+        match found {
+            Ok(Some((resource, capabilities))) if capabilities.scope == Scope::Namespaced => {
+                resolved.push(ResolvedCustomResourceCheck { check: check.clone(), resource });
+            }
+            Ok(Some(_)) => warn!("{}/{} is cluster-scoped, only namespaced custom resources are checked", check.group, check.kind),
+            Ok(None) => warn!("{}/{} is not served by the cluster, skipping its condition checks", check.group, check.kind),
+            Err(e) => warn!("Discovery of {}/{} failed, skipping its condition checks: {}", check.group, check.kind, e),
+        }
+    }
+    resolved
+}
+
+/// List the custom resources of a resolved check in a namespace
+pub async fn list_custom_resources(
+    client: &Client,
+    namespace: &str,
+    resolved: &ResolvedCustomResourceCheck,
+) -> Result<Vec<DynamicObject>> {
+    let api: Api<DynamicObject> = Api::namespaced_with(client.clone(), namespace, &resolved.resource);
+    Ok(api.list(&ListParams::default()).await?.items)
+}
+
+/// Compare `status.conditions` of each object with the check's expectations. A condition
+/// that is not reported at all is a violation too: the operator has not reconciled the
+/// object yet, or it never got far enough to set it.
+pub fn analyze_custom_resource_conditions(
+    namespace: &str,
+    check: &CustomResourceCheck,
+    objects: &[DynamicObject],
+) -> Vec<CustomResourceConditionInfo> {
+    let mut violations = Vec::new();
+
+    for object in objects {
+        let Some(name) = object.metadata.name.as_ref() else {
+            continue;
+        };
+        let conditions = object.data.pointer("/status/conditions").and_then(|c| c.as_array());
+
+        for (condition, expected) in &check.conditions {
+            let found = conditions.into_iter().flatten().find(|c| c["type"] == condition.as_str());
+            let field = |key: &str| found.and_then(|c| c[key].as_str()).map(str::to_string);
+            let actual = field("status");
+            if actual.as_deref() == Some(expected.as_str()) {
+                continue;
+            }
+            violations.push(CustomResourceConditionInfo {
+                namespace: namespace.to_string(),
+                resource: format!("{}/{}", check.kind, name),
+                condition: condition.clone(),
+                expected: expected.clone(),
+                actual,
+                reason: field("reason"),
+                message: field("message"),
+            });
+        }
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn kafka(name: &str, conditions: serde_json::Value) -> DynamicObject {
+        let resource = ApiResource::from_gvk(&GroupVersionKind::gvk("kafka.strimzi.io", "v1beta2", "Kafka"));
+        DynamicObject::new(name, &resource).within("streaming").data(json!({ "status": { "conditions": conditions } }))
+    }
+
+    #[test]
+    fn test_analyze_custom_resource_conditions() {
+        let check = CustomResourceCheck {
+            group: "kafka.strimzi.io".to_string(),
+            version: None,
+            kind: "Kafka".to_string(),
+            conditions: vec![("Ready".to_string(), "True".to_string())],
+        };
+        let objects = vec![
+            kafka("healthy", json!([{ "type": "Ready", "status": "True" }])),
+            kafka(
+                "broken",
+                json!([
+                    { "type": "Warning", "status": "True" },
+                    { "type": "Ready", "status": "False", "reason": "ZooKeeperNotReady", "message": "Waiting for ZooKeeper" },
+                ]),
+            ),
+            kafka("new", json!(null)),
+        ];
+
+        let violations = analyze_custom_resource_conditions("streaming", &check, &objects);
+        assert_eq!(violations.len(), 2);
+
+        assert_eq!(violations[0].namespace, "streaming");
+        assert_eq!(violations[0].resource, "Kafka/broken");
+        assert_eq!(violations[0].condition, "Ready");
+        assert_eq!(violations[0].expected, "True");
+        assert_eq!(violations[0].actual.as_deref(), Some("False"));
+        assert_eq!(violations[0].reason.as_deref(), Some("ZooKeeperNotReady"));
+        assert_eq!(violations[0].message.as_deref(), Some("Waiting for ZooKeeper"));
+
+        // No conditions reported yet
+        assert_eq!(violations[1].resource, "Kafka/new");
+        assert_eq!(violations[1].actual, None);
+        assert_eq!(violations[1].reason, None);
+    }
+}
@@ -0,0 +1,162 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use k8s_openapi::api::core::v1::Namespace;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use k8s_openapi::{Metadata, Resource};
+use kube::{Api, Client};
+
+use crate::types::{Config, StuckTerminatingInfo};
+
+/// Namespace conditions naming what keeps a namespace from being deleted
+const NAMESPACE_BLOCKING_CONDITIONS: &[&str] = &["NamespaceFinalizersRemaining", "NamespaceContentRemaining"];
+
+/// Fetch a namespace object; a namespaced RoleBinding grants `get` on its own namespace
+pub async fn get_namespace(client: &Client, namespace: &str) -> Result<Namespace> {
+    let namespace_api: Api<Namespace> = Api::all(client.clone());
+    Ok(namespace_api.get(namespace).await?)
+}
+
+/// Whether the namespace is being deleted; its pods are then expected to fail or go unready
+pub fn is_terminating(namespace: &Namespace) -> bool {
+    namespace.metadata.deletion_timestamp.is_some()
+        || namespace.status.as_ref().and_then(|s| s.phase.as_deref()) == Some("Terminating")
+}
+
+/// Objects marked for deletion longer than `terminating_grace_minutes` ago that are
+/// still held by finalizers
+pub fn analyze_stuck_terminating<K>(namespace: &str, cfg: &Config, objects: &[K], now: DateTime<Utc>) -> Vec<StuckTerminatingInfo>
+where
+    K: Resource + Metadata<Ty = ObjectMeta>,
+{
+    objects
+        .iter()
+        .filter_map(|object| stuck_terminating(namespace, K::KIND, object.metadata(), Vec::new(), cfg, now))
+        .collect()
+}
+
+/// The namespace itself when stuck deleting, with the namespace controller's account
+/// of what is left (remaining content or finalizers)
+pub fn analyze_stuck_namespace(namespace: &Namespace, cfg: &Config, now: DateTime<Utc>) -> Option<StuckTerminatingInfo> {
+    let name = namespace.metadata.name.as_deref()?;
+    let mut finalizers: Vec<String> = namespace
+        .spec
+        .as_ref()
+        .and_then(|s| s.finalizers.clone())
+        .unwrap_or_default();
+    finalizers.extend(namespace.metadata.finalizers.iter().flatten().cloned());
+    let details = namespace
+        .status
+        .as_ref()
+        .and_then(|s| s.conditions.as_ref())
+        .into_iter()
+        .flatten()
+        .filter(|c| c.status == "True" && NAMESPACE_BLOCKING_CONDITIONS.contains(&c.type_.as_str()))
+        .filter_map(|c| c.message.clone())
+        .collect();
+    let mut info = stuck_terminating(name, Namespace::KIND, &namespace.metadata, details, cfg, now)?;
+    // Namespace finalizers live in the spec; the generic check only sees metadata ones
+    info.finalizers = finalizers;
+    Some(info)
+}
+
+fn stuck_terminating(
+    namespace: &str,
+    kind: &str,
+    metadata: &ObjectMeta,
+    details: Vec<String>,
+    cfg: &Config,
+    now: DateTime<Utc>,
+) -> Option<StuckTerminatingInfo> {
+    let since = metadata.deletion_timestamp.as_ref()?.0;
+    if now - since < Duration::minutes(cfg.terminating_grace_minutes) {
+        return None;
+    }
+    Some(StuckTerminatingInfo {
+        namespace: namespace.to_string(),
+        object: format!("{}/{}", kind, metadata.name.as_deref().unwrap_or_default()),
+        since,
+        finalizers: metadata.finalizers.clone().unwrap_or_default(),
+        details,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::{NamespaceCondition, NamespaceSpec, NamespaceStatus, PersistentVolumeClaim, Service};
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+
+    fn deleting(name: &str, since: Option<DateTime<Utc>>, finalizers: &[&str]) -> ObjectMeta {
+        ObjectMeta {
+            name: Some(name.to_string()),
+            deletion_timestamp: since.map(Time),
+            finalizers: Some(finalizers.iter().map(|f| f.to_string()).collect()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_analyze_stuck_terminating() {
+        let now = Utc::now();
+        let config = Config { terminating_grace_minutes: 60, ..Default::default() };
+        let pvcs = vec![
+            PersistentVolumeClaim {
+                metadata: deleting("data-0", Some(now - Duration::hours(30)), &["kubernetes.io/pvc-protection"]),
+                ..Default::default()
+            },
+            // Within grace, and not being deleted
+            PersistentVolumeClaim { metadata: deleting("data-1", Some(now - Duration::minutes(5)), &[]), ..Default::default() },
+            PersistentVolumeClaim { metadata: deleting("data-2", None, &["kubernetes.io/pvc-protection"]), ..Default::default() },
+        ];
+
+        let stuck = analyze_stuck_terminating("default", &config, &pvcs, now);
+        assert_eq!(stuck.len(), 1);
+        assert_eq!(stuck[0].object, "PersistentVolumeClaim/data-0");
+        assert_eq!(stuck[0].finalizers, vec!["kubernetes.io/pvc-protection"]);
+
+        let services = vec![Service {
+            metadata: deleting("lb", Some(now - Duration::hours(2)), &["service.kubernetes.io/load-balancer-cleanup"]),
+            ..Default::default()
+        }];
+        assert_eq!(analyze_stuck_terminating("default", &config, &services, now)[0].object, "Service/lb");
+    }
+
+    #[test]
+    fn test_analyze_stuck_namespace() {
+        let now = Utc::now();
+        let config = Config { terminating_grace_minutes: 60, ..Default::default() };
+        let namespace = Namespace {
+            metadata: deleting("old-team", Some(now - Duration::days(3)), &[]),
+            spec: Some(NamespaceSpec { finalizers: Some(vec!["kubernetes".to_string()]) }),
+            status: Some(NamespaceStatus {
+                phase: Some("Terminating".to_string()),
+                conditions: Some(vec![
+                    NamespaceCondition {
+                        type_: "NamespaceFinalizersRemaining".to_string(),
+                        status: "True".to_string(),
+                        message: Some("Some content in the namespace has finalizers remaining: example.com/cleanup in 2 resource instances".to_string()),
+                        ..Default::default()
+                    },
+                    NamespaceCondition {
+                        type_: "NamespaceDeletionDiscoveryFailure".to_string(),
+                        status: "False".to_string(),
+                        message: Some("All resources successfully discovered".to_string()),
+                        ..Default::default()
+                    },
+                ]),
+            }),
+        };
+
+        assert!(is_terminating(&namespace));
+        let info = analyze_stuck_namespace(&namespace, &config, now).unwrap();
+        assert_eq!(info.namespace, "old-team");
+        assert_eq!(info.object, "Namespace/old-team");
+        assert_eq!(info.finalizers, vec!["kubernetes"]);
+        assert_eq!(info.details.len(), 1);
+        assert!(info.details[0].contains("example.com/cleanup"));
+
+        let active = Namespace { metadata: deleting("team", None, &[]), ..Default::default() };
+        assert!(!is_terminating(&active));
+        assert!(analyze_stuck_namespace(&active, &config, now).is_none());
+    }
+}
@@ -1,9 +1,22 @@
 use anyhow::Result;
 use chrono::{DateTime, Duration, Utc};
 use k8s_openapi::api::batch::v1::{Job, CronJob};
+use k8s_openapi::api::core::v1::Pod;
 use kube::{api::ListParams, Api, Client};
 
-use crate::types::{Config, FailedJobInfo, MissedCronJobInfo};
+use crate::types::{
+    Config, CronJobConcurrencyInfo, FailedJobInfo, FinishedObjectsInfo, JobBackoffRiskInfo, MissedCronJobInfo
+};
+use crate::parsing::parse_cron_schedule;
+
+/// Kubernetes default for `spec.backoffLimit`
+const DEFAULT_BACKOFF_LIMIT: i32 = 6;
+
+/// List jobs in a namespace
+pub async fn list_namespace_jobs(client: &Client, namespace: &str) -> Result<Vec<Job>> {
+    let job_api: Api<Job> = Api::namespaced(client.clone(), namespace);
+    Ok(job_api.list(&ListParams::default()).await?.items)
+}
 
 /// Analyze failed jobs
 pub async fn analyze_failed_jobs(
@@ -11,22 +24,30 @@ pub async fn analyze_failed_jobs(
     namespace: &str,
     cfg: &Config,
 ) -> Result<Vec<FailedJobInfo>> {
-    let job_api: Api<Job> = Api::namespaced(client.clone(), namespace);
-    let jobs = job_api.list(&ListParams::default()).await?;
+    let jobs = list_namespace_jobs(client, namespace).await?;
+    Ok(analyze_failed_jobs_with_jobs(namespace, cfg, &jobs))
+}
+
+/// Analyze failed jobs using pre-listed jobs
+pub fn analyze_failed_jobs_with_jobs(
+    namespace: &str,
+    cfg: &Config,
+    jobs: &[Job],
+) -> Vec<FailedJobInfo> {
     let mut failed_jobs = Vec::new();
 
-    for job in jobs.items {
+    for job in jobs {
         let job_name = match job.metadata.name.as_ref() {
             Some(n) => n.clone(),
             None => continue,
         };
 
-        if is_job_failed_over_grace(&job, cfg.pending_grace_minutes) {
+        if is_job_failed_over_grace(job, cfg.pending_grace_minutes) {
             let failed_pods = job.status.as_ref()
                 .and_then(|s| s.failed)
                 .unwrap_or(0);
             
-            let (last_failure_time, reason) = extract_job_failure_info(&job);
+            let (last_failure_time, reason) = extract_job_failure_info(job);
 
             failed_jobs.push(FailedJobInfo {
                 namespace: namespace.to_string(),
@@ -38,7 +59,93 @@ pub async fn analyze_failed_jobs(
         }
     }
 
-    Ok(failed_jobs)
+    failed_jobs
+}
+
+/// Report unfinished jobs whose failures are approaching `spec.backoffLimit`
+pub fn analyze_backoff_risk_with_jobs(
+    namespace: &str,
+    cfg: &Config,
+    jobs: &[Job],
+) -> Vec<JobBackoffRiskInfo> {
+    let mut at_risk = Vec::new();
+
+    for job in jobs {
+        let job_name = match job.metadata.name.as_ref() {
+            Some(n) => n.clone(),
+            None => continue,
+        };
+        if is_job_finished(job) {
+            continue;
+        }
+
+        let failed = job.status.as_ref().and_then(|s| s.failed).unwrap_or(0);
+        let backoff_limit = job.spec.as_ref()
+            .and_then(|s| s.backoff_limit)
+            .unwrap_or(DEFAULT_BACKOFF_LIMIT);
+        if failed == 0 || backoff_limit == 0 {
+            continue;
+        }
+
+        let used_percent = failed as f64 * 100.0 / backoff_limit as f64;
+        if used_percent >= cfg.job_backoff_warning_percent {
+            at_risk.push(JobBackoffRiskInfo {
+                namespace: namespace.to_string(),
+                job: job_name,
+                failed,
+                backoff_limit,
+                active: job.status.as_ref().and_then(|s| s.active).unwrap_or(0),
+            });
+        }
+    }
+    at_risk
+}
+
+/// Count Succeeded pods in a namespace; only metadata is fetched
+pub async fn count_succeeded_pods(client: &Client, namespace: &str) -> Result<usize> {
+    let pod_api: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    let lp = ListParams::default().fields("status.phase=Succeeded");
+    Ok(pod_api.list_metadata(&lp).await?.items.len())
+}
+
+/// Report a namespace accumulating finished Jobs without `ttlSecondsAfterFinished`
+/// or Succeeded pods beyond the configured threshold
+pub fn analyze_finished_objects_with_jobs(
+    namespace: &str,
+    cfg: &Config,
+    jobs: &[Job],
+    succeeded_pods: usize,
+) -> Option<FinishedObjectsInfo> {
+    let mut completed_jobs = 0;
+    let mut failed_jobs = 0;
+    for job in jobs {
+        let has_ttl = job.spec.as_ref().and_then(|s| s.ttl_seconds_after_finished).is_some();
+        if has_ttl {
+            continue;
+        }
+        match finished_condition(job) {
+            Some("Complete") => completed_jobs += 1,
+            Some("Failed") => failed_jobs += 1,
+            _ => {}
+        }
+    }
+
+    let threshold = cfg.finished_objects_threshold;
+    if completed_jobs + failed_jobs < threshold && succeeded_pods < threshold {
+        return None;
+    }
+    Some(FinishedObjectsInfo {
+        namespace: namespace.to_string(),
+        completed_jobs,
+        failed_jobs,
+        succeeded_pods,
+    })
+}
+
+/// List CronJobs in a namespace
+pub async fn list_namespace_cronjobs(client: &Client, namespace: &str) -> Result<Vec<CronJob>> {
+    let cronjob_api: Api<CronJob> = Api::namespaced(client.clone(), namespace);
+    Ok(cronjob_api.list(&ListParams::default()).await?.items)
 }
 
 /// Analyze missed CronJobs
@@ -47,17 +154,25 @@ pub async fn analyze_missed_cronjobs(
     namespace: &str,
     grace_minutes: i64,
 ) -> Result<Vec<MissedCronJobInfo>> {
-    let cronjob_api: Api<CronJob> = Api::namespaced(client.clone(), namespace);
-    let cronjobs = cronjob_api.list(&ListParams::default()).await?;
+    let cronjobs = list_namespace_cronjobs(client, namespace).await?;
+    Ok(analyze_missed_cronjobs_with_cronjobs(namespace, grace_minutes, &cronjobs))
+}
+
+/// Analyze missed CronJobs using pre-listed CronJobs
+pub fn analyze_missed_cronjobs_with_cronjobs(
+    namespace: &str,
+    grace_minutes: i64,
+    cronjobs: &[CronJob],
+) -> Vec<MissedCronJobInfo> {
     let mut missed_cronjobs = Vec::new();
 
-    for cronjob in cronjobs.items {
+    for cronjob in cronjobs {
         let cronjob_name = match cronjob.metadata.name.as_ref() {
             Some(n) => n.clone(),
             None => continue,
         };
 
-        if let Some((last_schedule_time, missed_runs)) = extract_missed_runs(&cronjob, grace_minutes) {
+        if let Some((last_schedule_time, missed_runs)) = extract_missed_runs(cronjob, grace_minutes) {
             missed_cronjobs.push(MissedCronJobInfo {
                 namespace: namespace.to_string(),
                 cronjob: cronjob_name,
@@ -67,7 +182,71 @@ pub async fn analyze_missed_cronjobs(
         }
     }
 
-    Ok(missed_cronjobs)
+    missed_cronjobs
+}
+
+/// Detect CronJobs whose runs overlap: a Forbid/Replace run still active when the
+/// next run was due, or active jobs piling up under any policy
+pub fn analyze_cronjob_concurrency_with_jobs(
+    namespace: &str,
+    cfg: &Config,
+    cronjobs: &[CronJob],
+    jobs: &[Job],
+    now: DateTime<Utc>,
+) -> Vec<CronJobConcurrencyInfo> {
+    let mut overlapping = Vec::new();
+
+    for cronjob in cronjobs {
+        let cronjob_name = match cronjob.metadata.name.as_ref() {
+            Some(n) => n.clone(),
+            None => continue,
+        };
+        let active: Vec<&str> = cronjob.status
+            .as_ref()
+            .and_then(|s| s.active.as_ref())
+            .map(|refs| refs.iter().filter_map(|r| r.name.as_deref()).collect())
+            .unwrap_or_default();
+        if active.is_empty() {
+            continue;
+        }
+
+        let spec = cronjob.spec.as_ref();
+        let concurrency_policy = spec
+            .and_then(|s| s.concurrency_policy.clone())
+            .unwrap_or_else(|| "Allow".to_string());
+
+        // Start of the oldest active run, from the Job objects referenced by the CronJob
+        let oldest_active_since = jobs
+            .iter()
+            .filter(|j| j.metadata.name.as_deref().is_some_and(|n| active.contains(&n)))
+            .filter_map(|j| {
+                j.status.as_ref().and_then(|s| s.start_time.as_ref())
+                    .or(j.metadata.creation_timestamp.as_ref())
+                    .map(|t| t.0)
+            })
+            .min();
+
+        let next_run_due = match (concurrency_policy.as_str(), oldest_active_since) {
+            ("Forbid" | "Replace", Some(since)) => spec
+                .and_then(|s| parse_cron_schedule(&s.schedule))
+                .and_then(|schedule| schedule.next_after(since))
+                .filter(|next| *next <= now),
+            _ => None,
+        };
+        let piled_up = active.len() >= cfg.cronjob_active_jobs_threshold;
+
+        if next_run_due.is_some() || piled_up {
+            overlapping.push(CronJobConcurrencyInfo {
+                namespace: namespace.to_string(),
+                cronjob: cronjob_name,
+                concurrency_policy,
+                active_jobs: active.len(),
+                oldest_active_since,
+                next_run_due,
+            });
+        }
+    }
+    overlapping
 }
 
 // Helper functions
@@ -94,6 +273,20 @@ fn is_job_failed_over_grace(job: &Job, grace_minutes: i64) -> bool {
     (Utc::now() - creation_time) > Duration::minutes(grace_minutes)
 }
 
+fn is_job_finished(job: &Job) -> bool {
+    finished_condition(job).is_some()
+}
+
+/// `Complete` or `Failed` once the job has finished
+fn finished_condition(job: &Job) -> Option<&str> {
+    job.status
+        .as_ref()
+        .and_then(|s| s.conditions.as_ref())?
+        .iter()
+        .find(|c| (c.type_ == "Complete" || c.type_ == "Failed") && c.status == "True")
+        .map(|c| c.type_.as_str())
+}
+
 fn extract_job_failure_info(job: &Job) -> (Option<DateTime<Utc>>, Option<String>) {
     let last_failure_time = job.status
         .as_ref()
@@ -139,7 +332,8 @@ fn extract_missed_runs(cronjob: &CronJob, grace_minutes: i64) -> Option<(DateTim
 #[cfg(test)]
 mod tests {
     use super::*;
-    use k8s_openapi::api::batch::v1::{JobStatus, JobCondition, CronJobStatus};
+    use k8s_openapi::api::batch::v1::{JobSpec, JobStatus, JobCondition, CronJobSpec, CronJobStatus};
+    use k8s_openapi::api::core::v1::ObjectReference;
     use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ObjectMeta, Time};
 
     fn create_test_config() -> Config {
@@ -152,6 +346,7 @@ mod tests {
             cluster_name: None,
             datacenter_name: None,
             fail_if_no_metrics: false,
+            ..Default::default()
         }
     }
 
@@ -199,6 +394,52 @@ mod tests {
         assert!(!is_job_failed_over_grace(&job, config.pending_grace_minutes));
     }
 
+    fn running_job(name: &str, failed: i32, backoff_limit: Option<i32>) -> Job {
+        Job {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
+            spec: Some(JobSpec {
+                backoff_limit,
+                ..Default::default()
+            }),
+            status: Some(JobStatus {
+                active: Some(1),
+                failed: Some(failed),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn test_analyze_backoff_risk() {
+        let config = Config {
+            job_backoff_warning_percent: 60.0,
+            ..create_test_config()
+        };
+        let mut finished = running_job("finished", 6, None);
+        finished.status.as_mut().unwrap().conditions = Some(vec![JobCondition {
+            type_: "Failed".to_string(),
+            status: "True".to_string(),
+            ..Default::default()
+        }]);
+        let jobs = vec![
+            running_job("near-limit", 4, None),
+            running_job("healthy", 1, Some(6)),
+            running_job("no-retries", 0, Some(0)),
+            running_job("custom-limit", 2, Some(3)),
+            finished,
+        ];
+
+        let at_risk = analyze_backoff_risk_with_jobs("default", &config, &jobs);
+        let names: Vec<&str> = at_risk.iter().map(|j| j.job.as_str()).collect();
+        assert_eq!(names, vec!["near-limit", "custom-limit"]);
+        assert_eq!(at_risk[0].failed, 4);
+        assert_eq!(at_risk[0].backoff_limit, DEFAULT_BACKOFF_LIMIT);
+        assert_eq!(at_risk[0].active, 1);
+    }
+
     #[test]
     fn test_extract_job_failure_info() {
         let failure_time = Utc::now() - Duration::minutes(5);
@@ -267,4 +508,119 @@ mod tests {
         let missed_info = extract_missed_runs(&cronjob, grace_minutes);
         assert!(missed_info.is_none());
     }
+
+    fn finished_job(name: &str, condition: &str, ttl: Option<i32>) -> Job {
+        Job {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
+            spec: Some(JobSpec {
+                ttl_seconds_after_finished: ttl,
+                ..Default::default()
+            }),
+            status: Some(JobStatus {
+                conditions: Some(vec![JobCondition {
+                    type_: condition.to_string(),
+                    status: "True".to_string(),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn test_analyze_finished_objects() {
+        let config = Config {
+            finished_objects_threshold: 3,
+            ..create_test_config()
+        };
+        let mut jobs = vec![
+            finished_job("done-1", "Complete", None),
+            finished_job("done-2", "Complete", None),
+            finished_job("cleaned-up", "Complete", Some(3600)),
+            running_job("running", 0, None),
+        ];
+        assert!(analyze_finished_objects_with_jobs("default", &config, &jobs, 0).is_none());
+
+        jobs.push(finished_job("broken", "Failed", None));
+        let info = analyze_finished_objects_with_jobs("default", &config, &jobs, 1).unwrap();
+        assert_eq!(info.completed_jobs, 2);
+        assert_eq!(info.failed_jobs, 1);
+        assert_eq!(info.succeeded_pods, 1);
+
+        // Succeeded pods alone can cross the threshold
+        assert!(analyze_finished_objects_with_jobs("default", &config, &[], 3).is_some());
+    }
+
+    fn cronjob_with_active(name: &str, schedule: &str, policy: &str, active: &[&str]) -> CronJob {
+        CronJob {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
+            spec: Some(CronJobSpec {
+                schedule: schedule.to_string(),
+                concurrency_policy: Some(policy.to_string()),
+                ..Default::default()
+            }),
+            status: Some(CronJobStatus {
+                active: Some(active.iter().map(|n| ObjectReference {
+                    name: Some(n.to_string()),
+                    ..Default::default()
+                }).collect()),
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn job_started(name: &str, start: DateTime<Utc>) -> Job {
+        Job {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
+            status: Some(JobStatus {
+                start_time: Some(Time(start)),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_analyze_cronjob_concurrency() {
+        let config = Config {
+            cronjob_active_jobs_threshold: 3,
+            ..create_test_config()
+        };
+        let now = DateTime::parse_from_rfc3339("2024-05-01T12:10:00Z").unwrap().with_timezone(&Utc);
+        let cronjobs = vec![
+            // Hourly run started at 11:00 is still active after the 12:00 slot
+            cronjob_with_active("overrun", "0 * * * *", "Forbid", &["overrun-1"]),
+            // Daily run started at 00:00 is still within its slot
+            cronjob_with_active("long-daily", "0 0 * * *", "Forbid", &["long-daily-1"]),
+            // Allow policy: overlap is expected, but three active jobs pile up
+            cronjob_with_active("pileup", "*/5 * * * *", "Allow", &["pileup-1", "pileup-2", "pileup-3"]),
+            cronjob_with_active("idle", "*/5 * * * *", "Forbid", &[]),
+        ];
+        let jobs = vec![
+            job_started("overrun-1", now - Duration::minutes(70)),
+            job_started("long-daily-1", now - Duration::hours(12)),
+            job_started("pileup-1", now - Duration::minutes(15)),
+        ];
+
+        let overlapping = analyze_cronjob_concurrency_with_jobs("default", &config, &cronjobs, &jobs, now);
+        assert_eq!(overlapping.len(), 2);
+        assert_eq!(overlapping[0].cronjob, "overrun");
+        assert_eq!(overlapping[0].active_jobs, 1);
+        assert_eq!(
+            overlapping[0].next_run_due,
+            Some(DateTime::parse_from_rfc3339("2024-05-01T12:00:00Z").unwrap().with_timezone(&Utc))
+        );
+        assert_eq!(overlapping[1].cronjob, "pileup");
+        assert_eq!(overlapping[1].active_jobs, 3);
+        assert!(overlapping[1].next_run_due.is_none());
+    }
 }
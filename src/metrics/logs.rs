@@ -0,0 +1,251 @@
+use k8s_openapi::api::core::v1::Pod;
+use kube::{api::LogParams, Api, Client};
+use std::collections::BTreeMap;
+use tracing::{debug, warn};
+
+use crate::types::{OomKilledInfo, RestartEventInfo};
+
+/// Upper bound on `logs --previous` calls per namespace and run
+pub const MAX_LOG_FETCHES_PER_NAMESPACE: usize = 20;
+
+/// Longest snippet kept per container, in characters
+const MAX_LOG_CHARS: usize = 1500;
+
+/// Keys whose values are redacted from log snippets
+const SECRET_KEYS: &[&str] = &["password", "passwd", "secret", "token", "apikey", "api_key", "api-key", "authorization"];
+
+/// Containers worth a log snippet: those waiting in CrashLoopBackOff and those OOMKilled,
+/// as `(pod, container)` pairs limited to the ones that are reported
+pub fn crash_log_targets(
+    pods: &[Pod],
+    restarts: &[RestartEventInfo],
+    oom_killed: &[OomKilledInfo],
+) -> Vec<(String, String)> {
+    let crashlooping = |pod_name: &str, container: &str| {
+        pods.iter()
+            .filter(|p| p.metadata.name.as_deref() == Some(pod_name))
+            .filter_map(|p| p.status.as_ref()?.container_statuses.as_ref())
+            .flatten()
+            .any(|cs| {
+                cs.name == container
+                    && cs.state.as_ref().and_then(|s| s.waiting.as_ref()).and_then(|w| w.reason.as_deref())
+                        == Some("CrashLoopBackOff")
+            })
+    };
+
+    let mut targets: Vec<(String, String)> = restarts
+        .iter()
+        .filter(|r| r.cause() == crate::types::RestartCause::OomKilled || crashlooping(&r.pod, &r.container))
+        .map(|r| (r.pod.clone(), r.container.clone()))
+        .chain(oom_killed.iter().map(|o| (o.pod.clone(), o.container.clone())))
+        .collect();
+    targets.sort();
+    targets.dedup();
+    targets
+}
+
+/// Fetch the sanitized tail of the previous container instance's log for each target.
+/// Stops early when pod logs are forbidden (no `pods/log` RBAC) and never fails the run.
+pub async fn collect_crash_logs(
+    client: &Client,
+    namespace: &str,
+    targets: &[(String, String)],
+    lines: usize,
+) -> BTreeMap<(String, String), String> {
+    let pod_api: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    let mut snippets = BTreeMap::new();
+
+    for (pod, container) in targets.iter().take(MAX_LOG_FETCHES_PER_NAMESPACE) {
+        let params = LogParams {
+            container: Some(container.clone()),
+            previous: true,
+            tail_lines: Some(lines as i64),
+            ..Default::default()
+        };
+        match pod_api.logs(pod, &params).await {
+            Ok(raw) => {
+                let snippet = sanitize_log_tail(&raw, lines);
+                if !snippet.is_empty() {
+                    snippets.insert((pod.clone(), container.clone()), snippet);
+                }
+            }
+            Err(kube::Error::Api(ae)) if ae.code == 403 => {
+                warn!("Pod logs forbidden in namespace {}, skipping crash log snippets: {}", namespace, ae.message);
+                break;
+            }
+            Err(e) => debug!("No previous logs for {}/{} [{}]: {}", namespace, pod, container, e),
+        }
+    }
+    snippets
+}
+
+/// Keep the last `max_lines` lines, strip ANSI escapes and control characters, redact
+/// `key=value` style secrets and cap the length, keeping the end of the log
+pub fn sanitize_log_tail(raw: &str, max_lines: usize) -> String {
+    let lines: Vec<&str> = raw.lines().collect();
+    let start = lines.len().saturating_sub(max_lines);
+    let cleaned: Vec<String> = lines[start..]
+        .iter()
+        .map(|line| redact_secrets(&strip_control(line)))
+        .collect();
+    // Backtick fences would end the Slack code block early
+    let text = cleaned.join("\n").replace("```", "'''");
+    let text = text.trim_matches('\n');
+
+    let char_count = text.chars().count();
+    if char_count <= MAX_LOG_CHARS {
+        return text.to_string();
+    }
+    let tail: String = text.chars().skip(char_count - MAX_LOG_CHARS).collect();
+    format!("…{}", tail)
+}
+
+fn strip_control(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // CSI sequence: ESC [ params final-byte
+            if chars.peek() == Some(&'[') {
+                chars.next();
+                for n in chars.by_ref() {
+                    if ('@'..='~').contains(&n) {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        if c == '\t' || !c.is_control() {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn redact_secrets(line: &str) -> String {
+    let lower = line.to_ascii_lowercase();
+    let mut out = String::with_capacity(line.len());
+    let mut pos = 0;
+
+    while pos < line.len() {
+        let next = SECRET_KEYS
+            .iter()
+            .filter_map(|key| lower[pos..].find(key).map(|i| (pos + i, key.len())))
+            .min();
+        let (key_start, key_len) = match next {
+            Some(found) => found,
+            None => break,
+        };
+        let mut value_start = key_start + key_len;
+        let rest = &line[value_start..];
+        let separator = rest.trim_start_matches([' ', '"', '\'']);
+        if !(separator.starts_with('=') || separator.starts_with(':')) {
+            out.push_str(&line[pos..value_start]);
+            pos = value_start;
+            continue;
+        }
+        value_start += rest.len() - separator.len() + 1;
+        let value = &line[value_start..];
+        let trimmed = value.trim_start_matches([' ', '"', '\'']);
+        // "Authorization: Bearer xyz" keeps the scheme and hides the credential
+        let skip_scheme = ["bearer ", "basic "]
+            .iter()
+            .find(|s| trimmed.to_ascii_lowercase().starts_with(*s))
+            .map_or(0, |s| s.len());
+        let secret_start = value_start + (value.len() - trimmed.len()) + skip_scheme;
+        let secret_len = line[secret_start..]
+            .find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | ',' | '&' | ';'))
+            .unwrap_or(line.len() - secret_start);
+        out.push_str(&line[pos..secret_start]);
+        if secret_len > 0 {
+            out.push_str("[REDACTED]");
+        }
+        pos = secret_start + secret_len;
+    }
+    out.push_str(&line[pos..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::{ContainerState, ContainerStateWaiting, ContainerStatus, PodStatus};
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+
+    #[test]
+    fn test_sanitize_log_tail() {
+        let raw = "line 1\nline 2\n\u{1b}[31mERROR\u{1b}[0m connecting with password=hunter2 user=app\n\
+                   Authorization: Bearer abc.def.ghi\npanic: ```boom```\n";
+        let snippet = sanitize_log_tail(raw, 3);
+        assert_eq!(
+            snippet,
+            "ERROR connecting with password=[REDACTED] user=app\nAuthorization: Bearer [REDACTED]\npanic: '''boom'''"
+        );
+
+        let long = "x".repeat(MAX_LOG_CHARS + 10);
+        let truncated = sanitize_log_tail(&long, 10);
+        assert!(truncated.starts_with('…'));
+        assert_eq!(truncated.chars().count(), MAX_LOG_CHARS + 1);
+    }
+
+    #[test]
+    fn test_crash_log_targets() {
+        let crashlooping = Pod {
+            metadata: ObjectMeta {
+                name: Some("api-0".to_string()),
+                ..Default::default()
+            },
+            status: Some(PodStatus {
+                container_statuses: Some(vec![ContainerStatus {
+                    name: "main".to_string(),
+                    state: Some(ContainerState {
+                        waiting: Some(ContainerStateWaiting {
+                            reason: Some("CrashLoopBackOff".to_string()),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let restart = |pod: &str, exit_code: i32, reason: &str| RestartEventInfo {
+            labels: Default::default(),
+            node_name: None,
+            zone: None,
+            blast_radius: None,
+            namespace: "default".to_string(),
+            pod: pod.to_string(),
+            container: "main".to_string(),
+            last_restart_time: None,
+            reason: Some(reason.to_string()),
+            message: None,
+            exit_code: Some(exit_code),
+            image: None,
+            log_tail: None,
+        };
+        let restarts = vec![restart("api-0", 1, "Error"), restart("web-0", 1, "Error"), restart("db-0", 137, "OOMKilled")];
+        let oom_killed = vec![OomKilledInfo {
+            labels: Default::default(),
+            node_name: None,
+            zone: None,
+            blast_radius: None,
+            namespace: "default".to_string(),
+            pod: "db-0".to_string(),
+            container: "main".to_string(),
+            last_oom_time: None,
+            restart_count: 3,
+            image: None,
+            log_tail: None,
+        }];
+
+        let targets = crash_log_targets(&[crashlooping], &restarts, &oom_killed);
+        assert_eq!(
+            targets,
+            vec![("api-0".to_string(), "main".to_string()), ("db-0".to_string(), "main".to_string())]
+        );
+    }
+}
@@ -4,13 +4,27 @@ pub mod nodes;
 pub mod jobs;
 pub mod volumes;
 pub mod base;
+pub mod recommendations;
+pub mod churn;
+pub mod services;
+pub mod logs;
+pub mod security;
+pub mod policy;
+pub mod objects;
+pub mod finalizers;
+pub mod custom;
+pub mod operators;
 
 // Re-export commonly used items
 pub use pods::{
     analyze_failed_pods, analyze_unready_pods, analyze_oom_killed,
     analyze_heavy_usage, analyze_restarts, analyze_pending_pods
 };
-pub use nodes::{analyze_problematic_nodes, analyze_node_utilization};
-pub use jobs::{analyze_failed_jobs, analyze_missed_cronjobs};
+pub use nodes::{analyze_problematic_nodes, analyze_node_utilization, analyze_network_agents, analyze_node_disk_forecast, analyze_node_flapping, list_node_zones};
+pub use jobs::{
+    analyze_failed_jobs, analyze_missed_cronjobs, analyze_backoff_risk_with_jobs,
+    analyze_cronjob_concurrency_with_jobs
+};
 pub use volumes::analyze_volume_issues;
-pub use base::list_pod_metrics_http;
+pub use security::analyze_pod_security_with_pods;
+pub use base::{list_pod_metrics_http, list_node_metrics_http, PodMetricsItem, NodeMetricsItem};
@@ -1,11 +1,16 @@
 use anyhow::Result;
-use chrono::{DateTime, Utc};
-use k8s_openapi::api::core::v1::Node;
+use chrono::{DateTime, Duration, Utc};
+use k8s_openapi::api::apps::v1::DaemonSet;
+use k8s_openapi::api::core::v1::{Event, Node};
 use kube::{api::ListParams, Api, Client};
-use k8s_openapi::api::core::v1::Pod;
+use std::collections::HashMap;
+use tracing::debug;
+use k8s_openapi::api::core::v1::{Pod, PodSpec};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
 
-use crate::types::{ProblematicNodeInfo, NodeUtilizationInfo};
+use crate::types::{ProblematicNodeInfo, NodeUtilizationInfo, NetworkAgentIssue, NetworkAgentIssueInfo, NodeDiskForecastInfo, NodeFlapInfo};
 use crate::parsing::{parse_cpu_to_millicores, parse_memory_to_bytes};
+use super::base::{event_occurrences_since, get_json, list_node_metrics_http, NodeMetricsItem};
 
 /// Analyze problematic nodes
 pub async fn analyze_problematic_nodes(client: &Client) -> Result<Vec<ProblematicNodeInfo>> {
@@ -24,6 +29,7 @@ pub async fn analyze_problematic_nodes(client: &Client) -> Result<Vec<Problemati
             let since = node_condition_since(&node).unwrap_or_else(Utc::now);
             problematic_nodes.push(ProblematicNodeInfo {
                 name: node_name,
+                os: node_os(&node),
                 conditions: problematic_conditions,
                 since,
             });
@@ -33,15 +39,241 @@ pub async fn analyze_problematic_nodes(client: &Client) -> Result<Vec<Problemati
     Ok(problematic_nodes)
 }
 
+/// Report nodes that went NotReady at least `threshold` times within the window, whether
+/// or not they are Ready now; recovered nodes are otherwise invisible in the report
+pub async fn analyze_node_flapping(
+    client: &Client,
+    window_minutes: i64,
+    threshold: i64,
+) -> Result<Vec<NodeFlapInfo>> {
+    let node_api: Api<Node> = Api::all(client.clone());
+    let nodes = node_api.list(&ListParams::default()).await?;
+    // Node events are recorded in the default namespace, but ask everywhere to be safe
+    let event_api: Api<Event> = Api::all(client.clone());
+    let events = event_api
+        .list(&ListParams::default().fields("involvedObject.kind=Node,reason=NodeNotReady"))
+        .await?;
+    Ok(node_flaps(&nodes.items, &events.items, window_minutes, threshold, Utc::now()))
+}
+
+fn node_flaps(nodes: &[Node], events: &[Event], window_minutes: i64, threshold: i64, now: DateTime<Utc>) -> Vec<NodeFlapInfo> {
+    let cutoff = now - Duration::minutes(window_minutes);
+    let mut flapping = Vec::new();
+
+    for node in nodes {
+        let Some(node_name) = node.metadata.name.as_ref() else {
+            continue;
+        };
+        // The kubelet and the node controller may both record the same transition, so
+        // count per reporting component and take the largest
+        let mut per_source: HashMap<String, i64> = HashMap::new();
+        for event in events.iter().filter(|e| {
+            e.reason.as_deref() == Some("NodeNotReady") && e.involved_object.name.as_deref() == Some(node_name.as_str())
+        }) {
+            let source = event
+                .source
+                .as_ref()
+                .and_then(|s| s.component.clone())
+                .or_else(|| event.reporting_component.clone())
+                .unwrap_or_default();
+            *per_source.entry(source).or_default() += event_occurrences_since(event, cutoff);
+        }
+        let not_ready_transitions = per_source.into_values().max().unwrap_or(0);
+        if not_ready_transitions < threshold {
+            continue;
+        }
+
+        let currently_ready = node
+            .status
+            .as_ref()
+            .and_then(|s| s.conditions.as_ref())
+            .and_then(|conditions| conditions.iter().find(|c| c.type_ == "Ready"))
+            .is_some_and(|c| c.status == "True");
+        flapping.push(NodeFlapInfo {
+            name: node_name.clone(),
+            not_ready_transitions,
+            currently_ready,
+            window_minutes,
+        });
+    }
+    flapping
+}
+
+/// Label the kubelet sets to the node's operating system
+const OS_LABEL: &str = "kubernetes.io/os";
+
+/// Operating system of a node: its `kubernetes.io/os` label, else what the kubelet reports
+/// in `nodeInfo`; `linux` when neither is set
+pub fn node_os(node: &Node) -> String {
+    metadata_os(&node.metadata)
+        .or_else(|| {
+            node.status
+                .as_ref()
+                .and_then(|s| s.node_info.as_ref())
+                .map(|i| i.operating_system.clone())
+                .filter(|os| !os.is_empty())
+        })
+        .unwrap_or_else(|| "linux".to_string())
+}
+
+fn metadata_os(metadata: &ObjectMeta) -> Option<String> {
+    metadata.labels.as_ref()?.get(OS_LABEL).cloned()
+}
+
+/// Current and legacy node zone labels, in order of preference
+const ZONE_LABELS: &[&str] = &["topology.kubernetes.io/zone", "failure-domain.beta.kubernetes.io/zone"];
+
+/// Zone of every node that carries a zone label, keyed by node name
+pub async fn list_node_zones(client: &Client) -> Result<HashMap<String, String>> {
+    let node_api: Api<Node> = Api::all(client.clone());
+    let nodes = node_api.list_metadata(&ListParams::default()).await?;
+    Ok(nodes
+        .items
+        .iter()
+        .filter_map(|n| Some((n.metadata.name.clone()?, node_zone(&n.metadata)?)))
+        .collect())
+}
+
+fn node_zone(metadata: &ObjectMeta) -> Option<String> {
+    let labels = metadata.labels.as_ref()?;
+    ZONE_LABELS.iter().find_map(|key| labels.get(*key)).cloned()
+}
+
+/// Check that every networking DaemonSet (kube-proxy, CNI agents) has a Running and
+/// Ready pod on each node it targets. DaemonSets given as `namespace/name` that do not
+/// exist in the cluster are skipped, so the list can name several CNI flavours.
+pub async fn analyze_network_agents(
+    client: &Client,
+    daemonsets: &[String],
+) -> Result<Vec<NetworkAgentIssueInfo>> {
+    if daemonsets.is_empty() {
+        return Ok(Vec::new());
+    }
+    let node_api: Api<Node> = Api::all(client.clone());
+    let nodes = node_api.list(&ListParams::default()).await?.items;
+    let mut issues = Vec::new();
+
+    for qualified in daemonsets {
+        let (namespace, name) = match qualified.split_once('/') {
+            Some(parts) => parts,
+            None => continue,
+        };
+        let ds_api: Api<DaemonSet> = Api::namespaced(client.clone(), namespace);
+        let daemonset = match ds_api.get_opt(name).await? {
+            Some(ds) => ds,
+            None => {
+                debug!("Networking DaemonSet {} not present, skipping", qualified);
+                continue;
+            }
+        };
+
+        let selector = daemonset
+            .spec
+            .as_ref()
+            .and_then(|s| s.selector.match_labels.as_ref())
+            .map(|labels| labels.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>().join(","))
+            .unwrap_or_default();
+        let pod_api: Api<Pod> = Api::namespaced(client.clone(), namespace);
+        let pods = pod_api.list(&ListParams::default().labels(&selector)).await?.items;
+
+        issues.extend(network_agent_issues(qualified, &daemonset, &nodes, &pods));
+    }
+    Ok(issues)
+}
+
+/// Join DaemonSet pods to nodes by `spec.nodeName` and report nodes without a healthy agent
+fn network_agent_issues(
+    qualified: &str,
+    daemonset: &DaemonSet,
+    nodes: &[Node],
+    pods: &[Pod],
+) -> Vec<NetworkAgentIssueInfo> {
+    let template = daemonset.spec.as_ref().and_then(|s| s.template.spec.as_ref());
+    let node_selector = template.and_then(|s| s.node_selector.as_ref());
+    let required_os = template.and_then(required_os);
+    let mut issues = Vec::new();
+
+    for node in nodes {
+        let node_name = match node.metadata.name.as_ref() {
+            Some(n) => n,
+            None => continue,
+        };
+        let targeted = node_selector.is_none_or(|selector| {
+            let labels = node.metadata.labels.as_ref();
+            selector.iter().all(|(k, v)| labels.and_then(|l| l.get(k)) == Some(v))
+        });
+        // Agents pinned to one OS through node affinity (e.g. aws-node) skip the others
+        let os_matches = required_os.as_ref().is_none_or(|oses| oses.contains(&node_os(node)));
+        if !targeted || !os_matches {
+            continue;
+        }
+
+        let agent = pods
+            .iter()
+            .find(|p| p.spec.as_ref().and_then(|s| s.node_name.as_ref()) == Some(node_name));
+        let issue = match agent {
+            None => Some(NetworkAgentIssue::Missing),
+            Some(pod) => {
+                let status = pod.status.as_ref();
+                let phase = status.and_then(|s| s.phase.clone()).unwrap_or_else(|| "Unknown".to_string());
+                let ready = status
+                    .and_then(|s| s.conditions.as_ref())
+                    .is_some_and(|c| c.iter().any(|c| c.type_ == "Ready" && c.status == "True"));
+                if phase == "Running" && ready {
+                    None
+                } else {
+                    Some(NetworkAgentIssue::Unhealthy {
+                        pod: pod.metadata.name.clone().unwrap_or_default(),
+                        phase,
+                    })
+                }
+            }
+        };
+        if let Some(issue) = issue {
+            issues.push(NetworkAgentIssueInfo {
+                node: node_name.clone(),
+                daemonset: qualified.to_string(),
+                issue,
+            });
+        }
+    }
+    issues
+}
+
+/// Operating systems a pod template is pinned to through required node affinity on
+/// `kubernetes.io/os`; `None` when any term leaves the OS open
+fn required_os(spec: &PodSpec) -> Option<Vec<String>> {
+    let required = spec
+        .affinity
+        .as_ref()?
+        .node_affinity
+        .as_ref()?
+        .required_during_scheduling_ignored_during_execution
+        .as_ref()?;
+    // Terms are ORed, so every one of them has to name the OS
+    let mut oses = Vec::new();
+    for term in &required.node_selector_terms {
+        let expression = term
+            .match_expressions
+            .iter()
+            .flatten()
+            .find(|e| e.key == OS_LABEL && e.operator == "In")?;
+        oses.extend(expression.values.iter().flatten().cloned());
+    }
+    (!oses.is_empty()).then_some(oses)
+}
+
 /// Analyze node utilization
 pub async fn analyze_node_utilization(
     client: &Client,
     threshold_percent: f64,
     target_namespaces: &[String],
+    count_all_namespaces: bool,
 ) -> Result<Vec<NodeUtilizationInfo>> {
     let node_api: Api<Node> = Api::all(client.clone());
     let nodes = node_api.list(&ListParams::default()).await?;
     let mut high_utilization_nodes = Vec::new();
+    let count_namespaces = (!count_all_namespaces).then_some(target_namespaces);
 
     // Get node metrics
     let node_metrics = list_node_metrics_http(client).await?;
@@ -53,15 +285,28 @@ pub async fn analyze_node_utilization(
             None => continue,
         };
 
-        let (pods_count, pods_capacity) = (
-            count_scheduled_pods_on_node(client, &node_name, target_namespaces).await.unwrap_or(0),
-            extract_node_pod_capacity(&node)
-        );
-        let (cpu_pct, memory_pct) = if let Some(metrics) = metrics_by_node.get(&node_name) {
-            calculate_node_utilization_percentages(&node, metrics)
-        } else {
-            (None, None)
+        let pods_count = count_scheduled_pods_on_node(client, &node_name, count_namespaces).await.unwrap_or(0);
+        let pods_capacity = extract_node_pod_capacity(&node);
+        let summary_metrics;
+        let metrics = match metrics_by_node.get(&node_name) {
+            Some(metrics) => Some(metrics),
+            // metrics-server has no sample for this node; ask the kubelet directly
+            None => match fetch_node_summary_metrics(client, &node_name).await {
+                Ok(metrics) => {
+                    summary_metrics = metrics;
+                    Some(&summary_metrics)
+                }
+                Err(e) => {
+                    debug!("No kubelet summary for node {}: {}", node_name, e);
+                    None
+                }
+            },
         };
+        let (cpu_pct, memory_pct) = metrics
+            .map(|m| calculate_node_utilization_percentages(&node, m))
+            .unwrap_or((None, None));
+        let cpu_millicores = metrics.and_then(|m| m.usage.get("cpu")).and_then(|c| parse_cpu_to_millicores(c));
+        let memory_bytes = metrics.and_then(|m| m.usage.get("memory")).and_then(|m| parse_memory_to_bytes(m));
 
         // Check if node exceeds thresholds
         let exceeds_threshold = cpu_pct.map(|c| c > threshold_percent).unwrap_or(false) ||
@@ -71,8 +316,11 @@ pub async fn analyze_node_utilization(
         if exceeds_threshold {
             high_utilization_nodes.push(NodeUtilizationInfo {
                 name: node_name,
+                os: node_os(&node),
                 cpu_pct,
                 memory_pct,
+                cpu_millicores,
+                memory_bytes,
                 pods_count,
                 pods_capacity,
             });
@@ -82,40 +330,210 @@ pub async fn analyze_node_utilization(
     Ok(high_utilization_nodes)
 }
 
-// Node metrics structures
+/// Kubelet defaults, used when `/configz` is unreachable or leaves a threshold unset
+const DEFAULT_IMAGE_GC_HIGH_PERCENT: f64 = 85.0;
+const DEFAULT_IMAGEFS_AVAILABLE: &str = "15%";
+const DEFAULT_NODEFS_AVAILABLE: &str = "10%";
+
+/// Report node filesystems whose usage is within `margin_percent` of the kubelet's image
+/// GC or eviction threshold, i.e. before DiskPressure fires. Usage comes from the kubelet
+/// stats summary and thresholds from its `/configz`; nodes whose kubelet is unreachable
+/// are skipped. Windows kubelets have no imagefs eviction signal, so only a configured one
+/// counts there.
+pub async fn analyze_node_disk_forecast(client: &Client, margin_percent: f64) -> Result<Vec<NodeDiskForecastInfo>> {
+    let node_api: Api<Node> = Api::all(client.clone());
+    let nodes = node_api.list_metadata(&ListParams::default()).await?;
+    let mut forecasts = Vec::new();
+
+    for node in nodes.items {
+        let os = metadata_os(&node.metadata).unwrap_or_else(|| "linux".to_string());
+        let Some(node_name) = node.metadata.name else {
+            continue;
+        };
+        let path = format!("/api/v1/nodes/{}/proxy/stats/summary", node_name);
+        let summary: StatsSummary = match get_json(client, &path).await {
+            Ok(summary) => summary,
+            Err(e) => {
+                debug!("No kubelet summary for node {}: {}", node_name, e);
+                continue;
+            }
+        };
+        let path = format!("/api/v1/nodes/{}/proxy/configz", node_name);
+        let thresholds = match get_json::<KubeletConfigz>(client, &path).await {
+            Ok(configz) => configz.kubeletconfig,
+            Err(e) => {
+                debug!("No kubelet configz for node {}, assuming default thresholds: {}", node_name, e);
+                KubeletThresholds::default()
+            }
+        };
+        forecasts.extend(disk_forecasts(&node_name, &os, &summary, &thresholds, margin_percent));
+    }
+
+    Ok(forecasts)
+}
+
+fn disk_forecasts(
+    node_name: &str,
+    os: &str,
+    summary: &StatsSummary,
+    thresholds: &KubeletThresholds,
+    margin_percent: f64,
+) -> Vec<NodeDiskForecastInfo> {
+    let nodefs = summary.node.fs.as_ref();
+    let imagefs = summary.node.runtime.as_ref().and_then(|r| r.image_fs.as_ref());
+    let image_gc_pct = thresholds.image_gc_high_threshold_percent.unwrap_or(DEFAULT_IMAGE_GC_HIGH_PERCENT);
+    // Without a dedicated image filesystem the kubelet reports the root one twice and
+    // evicts on the nodefs signal
+    let shared = nodefs.is_some() && nodefs == imagefs;
+
+    let mut candidates = Vec::new();
+    if let Some(fs) = nodefs {
+        let gc = if shared { Some(image_gc_pct) } else { None };
+        candidates.push(("nodefs", fs, gc, "nodefs.available", Some(DEFAULT_NODEFS_AVAILABLE)));
+    }
+    if let Some(fs) = imagefs.filter(|_| !shared) {
+        let default = if os == "windows" { None } else { Some(DEFAULT_IMAGEFS_AVAILABLE) };
+        candidates.push(("imagefs", fs, Some(image_gc_pct), "imagefs.available", default));
+    }
+
+    candidates
+        .into_iter()
+        .filter_map(|(filesystem, fs, image_gc_pct, signal, default)| {
+            let (Some(capacity), Some(available)) = (fs.capacity_bytes, fs.available_bytes) else {
+                return None;
+            };
+            if capacity == 0 {
+                return None;
+            }
+            let used_bytes = capacity.saturating_sub(available);
+            let used_pct = used_bytes as f64 / capacity as f64 * 100.0;
+            let eviction_pct = thresholds.eviction_usage_percent(signal, default, capacity);
+            let nearest = image_gc_pct.into_iter().chain(eviction_pct).reduce(f64::min)?;
+            (used_pct >= nearest - margin_percent).then(|| NodeDiskForecastInfo {
+                name: node_name.to_string(),
+                filesystem: filesystem.to_string(),
+                used_pct,
+                used_bytes,
+                capacity_bytes: capacity,
+                image_gc_pct,
+                eviction_pct,
+            })
+        })
+        .collect()
+}
+
 #[derive(Debug, serde::Deserialize)]
-struct NodeMetricsItem {
-    metadata: serde_json::Value,
-    usage: std::collections::HashMap<String, String>,
+struct KubeletConfigz {
+    kubeletconfig: KubeletThresholds,
+}
+
+/// Disk-related fields of the kubelet configuration
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KubeletThresholds {
+    #[serde(rename = "imageGCHighThresholdPercent")]
+    image_gc_high_threshold_percent: Option<f64>,
+    #[serde(default)]
+    eviction_hard: HashMap<String, String>,
+    #[serde(default)]
+    eviction_soft: HashMap<String, String>,
+}
+
+impl KubeletThresholds {
+    /// Lowest usage percent at which a hard or soft eviction threshold on `signal` trips
+    fn eviction_usage_percent(&self, signal: &str, default: Option<&str>, capacity_bytes: u64) -> Option<f64> {
+        let configured: Vec<&str> = [&self.eviction_hard, &self.eviction_soft]
+            .iter()
+            .filter_map(|thresholds| thresholds.get(signal).map(String::as_str))
+            .collect();
+        let values = if configured.is_empty() { default.into_iter().collect() } else { configured };
+        values
+            .into_iter()
+            .filter_map(|available| available_to_usage_percent(available, capacity_bytes))
+            .reduce(f64::min)
+    }
+}
+
+/// Convert an `*.available` eviction threshold ("15%" or a quantity like "1Gi") to the
+/// usage percent at which it trips
+fn available_to_usage_percent(available: &str, capacity_bytes: u64) -> Option<f64> {
+    let available_pct = match available.strip_suffix('%') {
+        Some(pct) => pct.parse::<f64>().ok()?,
+        None => parse_memory_to_bytes(available)? as f64 / capacity_bytes as f64 * 100.0,
+    };
+    Some(100.0 - available_pct)
+}
+
+// Kubelet stats summary structures (only the node-level fields we need)
+#[derive(Debug, serde::Deserialize)]
+struct StatsSummary {
+    node: NodeStats,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct NodeStats {
+    cpu: Option<CpuStats>,
+    memory: Option<MemoryStats>,
+    /// Root filesystem holding the kubelet directory, logs and emptyDirs
+    fs: Option<FsStats>,
+    runtime: Option<RuntimeStats>,
 }
 
 #[derive(Debug, serde::Deserialize)]
-struct NodeMetricsList {
-    items: Vec<NodeMetricsItem>,
+#[serde(rename_all = "camelCase")]
+struct RuntimeStats {
+    image_fs: Option<FsStats>,
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FsStats {
+    available_bytes: Option<u64>,
+    capacity_bytes: Option<u64>,
 }
 
-async fn list_node_metrics_http(client: &Client) -> Result<Vec<NodeMetricsItem>> {
-    use http::Request as HttpRequest;
-    let path = "/apis/metrics.k8s.io/v1beta1/nodes";
-    let req = HttpRequest::builder()
-        .method("GET")
-        .uri(path)
-        .body(Vec::new())
-        .map_err(|e| anyhow::anyhow!("build request: {}", e))?;
-    let list: NodeMetricsList = client.request(req).await?;
-    Ok(list.items)
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CpuStats {
+    usage_nano_cores: Option<u64>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MemoryStats {
+    working_set_bytes: Option<u64>,
+}
+
+/// Fetch node usage from the kubelet summary API via the API server proxy
+async fn fetch_node_summary_metrics(client: &Client, node_name: &str) -> Result<NodeMetricsItem> {
+    let path = format!("/api/v1/nodes/{}/proxy/stats/summary", node_name);
+    let summary: StatsSummary = get_json(client, &path).await?;
+    Ok(node_metrics_from_summary(node_name, &summary))
+}
+
+fn node_metrics_from_summary(node_name: &str, summary: &StatsSummary) -> NodeMetricsItem {
+    let mut usage = std::collections::HashMap::new();
+    if let Some(nanos) = summary.node.cpu.as_ref().and_then(|c| c.usage_nano_cores) {
+        usage.insert("cpu".to_string(), format!("{}n", nanos));
+    }
+    if let Some(bytes) = summary.node.memory.as_ref().and_then(|m| m.working_set_bytes) {
+        usage.insert("memory".to_string(), bytes.to_string());
+    }
+    NodeMetricsItem {
+        metadata: ObjectMeta {
+            name: Some(node_name.to_string()),
+            ..Default::default()
+        },
+        timestamp: None,
+        window: None,
+        usage,
+    }
 }
 
 fn build_node_metrics_map(items: Vec<NodeMetricsItem>) -> std::collections::HashMap<String, NodeMetricsItem> {
     let mut map = std::collections::HashMap::new();
     for item in items {
-        let name = item
-            .metadata
-            .get("name")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string();
-        if !name.is_empty() {
+        if let Some(name) = item.metadata.name.clone().filter(|n| !n.is_empty()) {
             map.insert(name, item);
         }
     }
@@ -172,14 +590,21 @@ fn extract_node_pod_capacity(node: &Node) -> i32 {
         .unwrap_or(0)
 }
 
-async fn count_scheduled_pods_on_node(client: &Client, node_name: &str, target_namespaces: &[String]) -> Result<i32> {
-    // Count pods scheduled on the node restricted to target namespaces
-    let lp = ListParams::default().fields(&format!("spec.nodeName={}", node_name));
+/// Terminated pods no longer occupy a pod slot on the node
+const ACTIVE_POD_PHASES: &str = "status.phase!=Succeeded,status.phase!=Failed";
+
+/// Pods scheduled on the node, in `target_namespaces` or (`None`) in all namespaces.
+/// Only the count matters, so fetch metadata instead of full pod specs; the node
+/// selector keeps each list to at most the node's pod capacity.
+async fn count_scheduled_pods_on_node(client: &Client, node_name: &str, target_namespaces: Option<&[String]>) -> Result<i32> {
+    let lp = ListParams::default().fields(&format!("spec.nodeName={},{}", node_name, ACTIVE_POD_PHASES));
+    let apis: Vec<Api<Pod>> = match target_namespaces {
+        Some(namespaces) => namespaces.iter().map(|ns| Api::namespaced(client.clone(), ns)).collect(),
+        None => vec![Api::all(client.clone())],
+    };
     let mut total = 0usize;
-    for ns in target_namespaces {
-        let pod_api: Api<Pod> = Api::namespaced(client.clone(), ns);
-        let pods = pod_api.list(&lp).await?;
-        total += pods.items.len();
+    for pod_api in apis {
+        total += pod_api.list_metadata(&lp).await?.items.len();
     }
     Ok(total as i32)
 }
@@ -227,10 +652,131 @@ fn calculate_node_utilization_percentages(
 mod tests {
     use super::*;
     use k8s_openapi::api::core::v1::{NodeStatus, NodeCondition};
-    use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ObjectMeta, Time};
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
     use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
     use std::collections::BTreeMap;
 
+    #[test]
+    fn test_node_zone() {
+        let meta = |labels: &[(&str, &str)]| ObjectMeta {
+            labels: Some(labels.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()),
+            ..Default::default()
+        };
+        assert_eq!(node_zone(&meta(&[("topology.kubernetes.io/zone", "eu-west-1a")])).as_deref(), Some("eu-west-1a"));
+        assert_eq!(node_zone(&meta(&[("failure-domain.beta.kubernetes.io/zone", "us-central1-b")])).as_deref(), Some("us-central1-b"));
+        assert_eq!(node_zone(&meta(&[("kubernetes.io/os", "linux")])), None);
+    }
+
+    #[test]
+    fn test_network_agent_issues() {
+        use k8s_openapi::api::apps::v1::DaemonSetSpec;
+        use k8s_openapi::api::core::v1::{PodCondition, PodSpec, PodStatus, PodTemplateSpec};
+
+        let node = |name: &str, os: &str| Node {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                labels: Some([("kubernetes.io/os".to_string(), os.to_string())].into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let agent = |name: &str, node: &str, phase: &str, ready: &str| Pod {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
+            spec: Some(PodSpec {
+                node_name: Some(node.to_string()),
+                ..Default::default()
+            }),
+            status: Some(PodStatus {
+                phase: Some(phase.to_string()),
+                conditions: Some(vec![PodCondition {
+                    type_: "Ready".to_string(),
+                    status: ready.to_string(),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+        };
+        let daemonset = DaemonSet {
+            spec: Some(DaemonSetSpec {
+                template: PodTemplateSpec {
+                    spec: Some(PodSpec {
+                        node_selector: Some([("kubernetes.io/os".to_string(), "linux".to_string())].into()),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let nodes = vec![node("n1", "linux"), node("n2", "linux"), node("n3", "linux"), node("win1", "windows")];
+        let pods = vec![
+            agent("kube-proxy-a", "n1", "Running", "True"),
+            agent("kube-proxy-b", "n2", "Running", "False"),
+        ];
+
+        let issues = network_agent_issues("kube-system/kube-proxy", &daemonset, &nodes, &pods);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].node, "n2");
+        assert_eq!(
+            issues[0].issue,
+            NetworkAgentIssue::Unhealthy { pod: "kube-proxy-b".to_string(), phase: "Running".to_string() }
+        );
+        assert_eq!(issues[1].node, "n3");
+        assert_eq!(issues[1].issue, NetworkAgentIssue::Missing);
+
+        // Pinned to Linux through node affinity instead of a nodeSelector, like aws-node
+        use k8s_openapi::api::core::v1::{Affinity, NodeAffinity, NodeSelector, NodeSelectorRequirement, NodeSelectorTerm};
+        let mut affinity_daemonset = daemonset.clone();
+        let template = affinity_daemonset.spec.as_mut().unwrap().template.spec.as_mut().unwrap();
+        template.node_selector = None;
+        template.affinity = Some(Affinity {
+            node_affinity: Some(NodeAffinity {
+                required_during_scheduling_ignored_during_execution: Some(NodeSelector {
+                    node_selector_terms: vec![NodeSelectorTerm {
+                        match_expressions: Some(vec![NodeSelectorRequirement {
+                            key: "kubernetes.io/os".to_string(),
+                            operator: "In".to_string(),
+                            values: Some(vec!["linux".to_string()]),
+                        }]),
+                        ..Default::default()
+                    }],
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let issues = network_agent_issues("kube-system/aws-node", &affinity_daemonset, &nodes, &pods);
+        assert_eq!(issues.iter().map(|i| i.node.as_str()).collect::<Vec<_>>(), vec!["n2", "n3"]);
+    }
+
+    #[test]
+    fn test_node_os() {
+        let mut node = Node {
+            metadata: ObjectMeta {
+                labels: Some([("kubernetes.io/os".to_string(), "windows".to_string())].into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(node_os(&node), "windows");
+
+        node.metadata.labels = None;
+        assert_eq!(node_os(&node), "linux");
+        node.status = Some(NodeStatus {
+            node_info: Some(k8s_openapi::api::core::v1::NodeSystemInfo {
+                operating_system: "windows".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        assert_eq!(node_os(&node), "windows");
+    }
+
     #[test]
     fn test_extract_problematic_conditions() {
         let mut node = Node {
@@ -335,7 +881,12 @@ mod tests {
         usage.insert("memory".to_string(), "4Gi".to_string()); // 4GB
         
         let metrics = NodeMetricsItem {
-            metadata: serde_json::json!({"name": "test-node"}),
+            metadata: ObjectMeta {
+                name: Some("test-node".to_string()),
+                ..Default::default()
+            },
+            timestamp: None,
+            window: None,
             usage,
         };
 
@@ -348,6 +899,169 @@ mod tests {
         assert!((memory_pct.unwrap() - 50.0).abs() < 0.1);
     }
 
+    #[tokio::test]
+    async fn test_pod_counts_skip_terminated_pods() {
+        use bytes::Bytes;
+        use http::{Request, Response};
+        use http_body_util::{BodyExt, Full};
+        use kube::client::{Body, ClientBuilder, DynBody};
+        use std::sync::{Arc, Mutex};
+        use tower::util::BoxService;
+        use tower::BoxError;
+
+        // Both ways of counting must leave out the same pods and list metadata only, so
+        // record their paths, selectors and Accept headers
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        let cluster = tower::service_fn(move |request: Request<Body>| {
+            let url = reqwest::Url::parse(&format!("http://cluster{}", request.uri())).unwrap();
+            let selector = url.query_pairs().find(|(key, _)| key == "fieldSelector").map(|(_, value)| value.into_owned());
+            let accept = request.headers().get("accept").and_then(|v| v.to_str().ok()).unwrap_or_default().to_string();
+            recorded.lock().unwrap().push((url.path().to_string(), selector.unwrap_or_default(), accept));
+            let list = serde_json::json!({"kind": "PartialObjectMetadataList", "apiVersion": "meta.k8s.io/v1", "metadata": {}, "items": []});
+            let body: Box<DynBody> = Box::new(Full::new(Bytes::from(list.to_string())).map_err(|never| match never {}));
+            async move { Ok::<_, BoxError>(Response::new(body)) }
+        });
+        let client = ClientBuilder::new(BoxService::new(cluster), "default").build();
+
+        count_scheduled_pods_on_node(&client, "node-a", Some(&["default".to_string()])).await.unwrap();
+        count_scheduled_pods_on_node(&client, "node-a", None).await.unwrap();
+        let requests = requests.lock().unwrap();
+        assert_eq!(
+            requests.iter().map(|(path, _, _)| path.as_str()).collect::<Vec<_>>(),
+            vec!["/api/v1/namespaces/default/pods", "/api/v1/pods"]
+        );
+        for (_, selector, accept) in requests.iter() {
+            assert_eq!(selector, &format!("spec.nodeName=node-a,{}", ACTIVE_POD_PHASES));
+            assert!(accept.contains("as=PartialObjectMetadataList"), "{}", accept);
+        }
+    }
+
+    #[test]
+    fn test_node_metrics_from_summary() {
+        let summary: StatsSummary = serde_json::from_value(serde_json::json!({
+            "node": {
+                "nodeName": "test-node",
+                "cpu": {"usageNanoCores": 1500000000u64},
+                "memory": {"workingSetBytes": 2147483648u64}
+            },
+            "pods": []
+        })).unwrap();
+
+        let metrics = node_metrics_from_summary("test-node", &summary);
+        assert_eq!(metrics.usage.get("cpu").and_then(|c| parse_cpu_to_millicores(c)), Some(1500));
+        assert_eq!(metrics.usage.get("memory").and_then(|m| parse_memory_to_bytes(m)), Some(2147483648));
+
+        // Missing sections leave the usage map empty
+        let summary: StatsSummary = serde_json::from_value(serde_json::json!({"node": {}})).unwrap();
+        assert!(node_metrics_from_summary("test-node", &summary).usage.is_empty());
+    }
+
+    #[test]
+    fn test_node_flaps() {
+        use k8s_openapi::api::core::v1::{EventSource, ObjectReference};
+
+        let now = Utc::now();
+        let node = |name: &str, ready: &str| Node {
+            metadata: ObjectMeta { name: Some(name.to_string()), ..Default::default() },
+            status: Some(NodeStatus {
+                conditions: Some(vec![NodeCondition {
+                    type_: "Ready".to_string(),
+                    status: ready.to_string(),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let not_ready = |node: &str, component: &str, count: i32, last: DateTime<Utc>| Event {
+            reason: Some("NodeNotReady".to_string()),
+            involved_object: ObjectReference {
+                kind: Some("Node".to_string()),
+                name: Some(node.to_string()),
+                ..Default::default()
+            },
+            source: Some(EventSource { component: Some(component.to_string()), ..Default::default() }),
+            count: Some(count),
+            first_timestamp: Some(Time(last)),
+            last_timestamp: Some(Time(last)),
+            ..Default::default()
+        };
+        let nodes = vec![node("flappy", "True"), node("broken", "False"), node("steady", "True")];
+        let events = vec![
+            // Same transitions seen by both components count once
+            not_ready("flappy", "node-controller", 3, now - Duration::minutes(10)),
+            not_ready("flappy", "kubelet", 2, now - Duration::minutes(10)),
+            not_ready("broken", "node-controller", 1, now - Duration::minutes(5)),
+            // Outside the window
+            not_ready("steady", "node-controller", 5, now - Duration::hours(3)),
+        ];
+
+        let flapping = node_flaps(&nodes, &events, 60, 3, now);
+        assert_eq!(flapping.len(), 1);
+        assert_eq!(flapping[0].name, "flappy");
+        assert_eq!(flapping[0].not_ready_transitions, 3);
+        assert!(flapping[0].currently_ready);
+        assert_eq!(flapping[0].window_minutes, 60);
+
+        assert_eq!(node_flaps(&nodes, &events, 60, 1, now).len(), 2);
+    }
+
+    #[test]
+    fn test_disk_forecasts() {
+        let gib = 1024u64 * 1024 * 1024;
+        let summary: StatsSummary = serde_json::from_value(serde_json::json!({
+            "node": {
+                "fs": {"availableBytes": 30 * gib, "capacityBytes": 100 * gib},
+                "runtime": {"imageFs": {"availableBytes": 20 * gib, "capacityBytes": 100 * gib}}
+            }
+        })).unwrap();
+
+        // Defaults: image GC at 85%, imagefs eviction at 85%, nodefs eviction at 90%
+        let forecasts = disk_forecasts("node-a", "linux", &summary, &KubeletThresholds::default(), 10.0);
+        assert_eq!(forecasts.len(), 1);
+        assert_eq!(forecasts[0].filesystem, "imagefs");
+        assert_eq!(forecasts[0].used_pct, 80.0);
+        assert_eq!(forecasts[0].used_bytes, 80 * gib);
+        assert_eq!(forecasts[0].image_gc_pct, Some(85.0));
+        assert_eq!(forecasts[0].eviction_pct, Some(85.0));
+
+        // Windows kubelets only evict on nodefs by default; image GC still applies
+        let forecasts = disk_forecasts("win-a", "windows", &summary, &KubeletThresholds::default(), 10.0);
+        assert_eq!(forecasts.len(), 1);
+        assert_eq!(forecasts[0].filesystem, "imagefs");
+        assert_eq!(forecasts[0].eviction_pct, None);
+
+        // Configured thresholds win; a soft threshold below the hard one trips first
+        let thresholds: KubeletThresholds = serde_json::from_value(serde_json::json!({
+            "imageGCHighThresholdPercent": 95,
+            "evictionHard": {"nodefs.available": "5%", "imagefs.available": "2Gi"},
+            "evictionSoft": {"nodefs.available": "25%"}
+        })).unwrap();
+        let forecasts = disk_forecasts("node-a", "linux", &summary, &thresholds, 10.0);
+        assert_eq!(forecasts.len(), 1);
+        assert_eq!(forecasts[0].filesystem, "nodefs");
+        assert_eq!(forecasts[0].eviction_pct, Some(75.0));
+        assert_eq!(forecasts[0].image_gc_pct, None);
+
+        // A shared filesystem is reported once, as nodefs with the image GC threshold
+        let summary: StatsSummary = serde_json::from_value(serde_json::json!({
+            "node": {
+                "fs": {"availableBytes": 12 * gib, "capacityBytes": 100 * gib},
+                "runtime": {"imageFs": {"availableBytes": 12 * gib, "capacityBytes": 100 * gib}}
+            }
+        })).unwrap();
+        let forecasts = disk_forecasts("node-b", "linux", &summary, &KubeletThresholds::default(), 0.0);
+        assert_eq!(forecasts.len(), 1);
+        assert_eq!(forecasts[0].filesystem, "nodefs");
+        assert_eq!(forecasts[0].image_gc_pct, Some(85.0));
+        assert_eq!(forecasts[0].eviction_pct, Some(90.0));
+
+        // No filesystem stats, nothing to forecast
+        let summary: StatsSummary = serde_json::from_value(serde_json::json!({"node": {}})).unwrap();
+        assert!(disk_forecasts("node-c", "linux", &summary, &KubeletThresholds::default(), 10.0).is_empty());
+    }
+
     #[test]
     fn test_node_condition_since() {
         let transition_time = Utc::now() - chrono::Duration::minutes(30);
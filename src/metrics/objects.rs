@@ -0,0 +1,115 @@
+use anyhow::{anyhow, Result};
+use kube::Client;
+use std::collections::BTreeMap;
+
+use crate::types::{Config, ObjectCountInfo};
+
+/// Stored object counts per resource from the API server's `/metrics`: the
+/// `apiserver_storage_objects` gauge, or `etcd_object_counts` on clusters older than 1.23
+pub async fn fetch_object_counts(client: &Client) -> Result<BTreeMap<String, u64>> {
+    let req = http::Request::builder()
+        .method("GET")
+        .uri("/metrics")
+        .header(http::header::ACCEPT, "text/plain")
+        .body(Vec::new())
+        .map_err(|e| anyhow!("build request: {}", e))?;
+    Ok(parse_object_counts(&client.request_text(req).await?))
+}
+
+fn parse_object_counts(metrics: &str) -> BTreeMap<String, u64> {
+    let counts = |metric: &str| -> BTreeMap<String, u64> {
+        let prefix = format!("{}{{", metric);
+        metrics
+            .lines()
+            .filter_map(|line| {
+                let (labels, value) = line.strip_prefix(prefix.as_str())?.split_once('}')?;
+                let resource = labels
+                    .split(',')
+                    .find_map(|label| label.strip_prefix("resource=\""))?
+                    .trim_end_matches('"');
+                // -1 means the count is not known yet
+                let value: f64 = value.trim().parse().ok().filter(|v: &f64| *v >= 0.0)?;
+                Some((resource.to_string(), value as u64))
+            })
+            .collect()
+    };
+    let current = counts("apiserver_storage_objects");
+    if current.is_empty() {
+        counts("etcd_object_counts")
+    } else {
+        current
+    }
+}
+
+/// Resources holding at least `object_count_threshold` objects, or that grew by more
+/// than `object_count_growth_percent` since the previous run. Growth is only judged
+/// from a tenth of the threshold up, so small resources doubling are not reported.
+pub fn analyze_object_counts(
+    counts: &BTreeMap<String, u64>,
+    previous: &BTreeMap<String, u64>,
+    cfg: &Config,
+) -> Vec<ObjectCountInfo> {
+    counts
+        .iter()
+        .filter_map(|(resource, &count)| {
+            let previous = previous.get(resource).copied();
+            let grew = previous.is_some_and(|p| {
+                p > 0
+                    && count >= cfg.object_count_threshold / 10
+                    && (count as f64 - p as f64) / p as f64 * 100.0 > cfg.object_count_growth_percent
+            });
+            (count >= cfg.object_count_threshold || grew).then(|| ObjectCountInfo {
+                resource: resource.clone(),
+                count,
+                previous,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_object_counts() {
+        let metrics = r#"# HELP apiserver_storage_objects [STABLE] Number of stored objects at the time of last check split by kind.
+# TYPE apiserver_storage_objects gauge
+apiserver_storage_objects{resource="events"} 1.234567e+06
+apiserver_storage_objects{resource="pods"} 512
+apiserver_storage_objects{resource="leases.coordination.k8s.io"} -1
+etcd_object_counts{resource="pods"} 1
+apiserver_request_total{code="200",resource="pods"} 99
+"#;
+        let counts = parse_object_counts(metrics);
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts["events"], 1_234_567);
+        assert_eq!(counts["pods"], 512);
+
+        // Older clusters only have the etcd gauge
+        let counts = parse_object_counts(r#"etcd_object_counts{resource="secrets"} 40"#);
+        assert_eq!(counts["secrets"], 40);
+        assert!(parse_object_counts("").is_empty());
+    }
+
+    #[test]
+    fn test_analyze_object_counts() {
+        let config = Config { object_count_threshold: 100_000, object_count_growth_percent: 50.0, ..Default::default() };
+        let counts = BTreeMap::from([
+            ("events".to_string(), 1_500_000),
+            ("configmaps".to_string(), 30_000),
+            ("secrets".to_string(), 400),
+            ("pods".to_string(), 12_000),
+        ]);
+        let previous = BTreeMap::from([
+            ("configmaps".to_string(), 15_000),
+            ("secrets".to_string(), 100),
+            ("pods".to_string(), 11_000),
+        ]);
+
+        let warnings = analyze_object_counts(&counts, &previous, &config);
+        let resources: Vec<(&str, Option<u64>)> = warnings.iter().map(|w| (w.resource.as_str(), w.previous)).collect();
+        // Secrets quadrupled but are far below the growth floor; pods grew under 50%
+        assert_eq!(resources, vec![("configmaps", Some(15_000)), ("events", None)]);
+    }
+}
@@ -0,0 +1,164 @@
+use anyhow::{Context, Result};
+use k8s_openapi::api::apps::v1::Deployment;
+use kube::api::{ApiResource, DynamicObject, GroupVersionKind, ListParams};
+use kube::{Api, Client};
+use tracing::debug;
+
+use crate::types::{OperatorIssue, OperatorIssueInfo};
+
+const OLM_GROUP: &str = "operators.coreos.com";
+/// Prefix of the `operators.coreos.com/<package>.<namespace>` label OLM puts on operator objects
+const OPERATOR_LABEL_PREFIX: &str = "operators.coreos.com/";
+
+/// Subscription conditions that mean OLM cannot install or upgrade the operator when True
+const FAILING_SUBSCRIPTION_CONDITIONS: &[&str] = &[
+    "ResolutionFailed",
+    "InstallPlanFailed",
+    "InstallPlanMissing",
+    "CatalogSourcesUnhealthy",
+];
+
+/// Check OLM Subscriptions for failed resolution or InstallPlans and operator Deployments
+/// for missing replicas, across all namespaces. Clusters without OLM have no findings.
+pub async fn analyze_operators(client: &Client) -> Result<Vec<OperatorIssueInfo>> {
+    let gvk = GroupVersionKind::gvk(OLM_GROUP, "v1alpha1", "Subscription");
+    let resource = ApiResource::from_gvk_with_plural(&gvk, "subscriptions");
+    let subscription_api: Api<DynamicObject> = Api::all_with(client.clone(), &resource);
+    let subscriptions = match subscription_api.list(&ListParams::default()).await {
+        Ok(list) => list.items,
+        Err(kube::Error::Api(ae)) if ae.code == 404 => {
+            debug!("OLM Subscription CRD not installed, skipping operator checks");
+            return Ok(Vec::new());
+        }
+        Err(e) => return Err(e).context("Failed to list OLM Subscriptions"),
+    };
+
+    let deployment_api: Api<Deployment> = Api::all(client.clone());
+    let deployments = deployment_api.list(&ListParams::default()).await
+        .context("Failed to list operator Deployments")?
+        .items;
+
+    Ok(operator_issues(&subscriptions, &deployments))
+}
+
+/// Failing Subscription conditions, then operator Deployments short of available replicas
+fn operator_issues(subscriptions: &[DynamicObject], deployments: &[Deployment]) -> Vec<OperatorIssueInfo> {
+    let mut issues = Vec::new();
+
+    for subscription in subscriptions {
+        let (Some(namespace), Some(name)) = (&subscription.metadata.namespace, &subscription.metadata.name) else {
+            continue;
+        };
+        let conditions = subscription.data.pointer("/status/conditions").and_then(|c| c.as_array());
+        for condition in conditions.into_iter().flatten() {
+            let Some(type_) = condition["type"].as_str() else {
+                continue;
+            };
+            if FAILING_SUBSCRIPTION_CONDITIONS.contains(&type_) && condition["status"] == "True" {
+                issues.push(OperatorIssueInfo {
+                    namespace: namespace.clone(),
+                    operator: name.clone(),
+                    issue: OperatorIssue::Subscription {
+                        condition: type_.to_string(),
+                        message: condition["message"].as_str().map(str::to_string),
+                    },
+                });
+            }
+        }
+    }
+
+    for deployment in deployments {
+        let (Some(namespace), Some(name)) = (&deployment.metadata.namespace, &deployment.metadata.name) else {
+            continue;
+        };
+        let Some(package) = deployment
+            .metadata
+            .labels
+            .iter()
+            .flatten()
+            .find_map(|(key, _)| key.strip_prefix(OPERATOR_LABEL_PREFIX))
+        else {
+            continue;
+        };
+        let desired = deployment.spec.as_ref().and_then(|s| s.replicas).unwrap_or(1);
+        let available = deployment.status.as_ref().and_then(|s| s.available_replicas).unwrap_or(0);
+        if available >= desired {
+            continue;
+        }
+        // The label is `<package>.<namespace>`
+        let operator = package.strip_suffix(&format!(".{}", namespace)).unwrap_or(package);
+        issues.push(OperatorIssueInfo {
+            namespace: namespace.clone(),
+            operator: operator.to_string(),
+            issue: OperatorIssue::DeploymentUnavailable { deployment: name.clone(), available, desired },
+        });
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::apps::v1::{DeploymentSpec, DeploymentStatus};
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+    use serde_json::json;
+
+    fn subscription(name: &str, conditions: serde_json::Value) -> DynamicObject {
+        let gvk = GroupVersionKind::gvk(OLM_GROUP, "v1alpha1", "Subscription");
+        let resource = ApiResource::from_gvk_with_plural(&gvk, "subscriptions");
+        DynamicObject::new(name, &resource)
+            .within("openshift-operators")
+            .data(json!({ "status": { "conditions": conditions } }))
+    }
+
+    fn deployment(name: &str, label: Option<&str>, desired: i32, available: Option<i32>) -> Deployment {
+        Deployment {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                namespace: Some("openshift-operators".to_string()),
+                labels: label.map(|l| [(l.to_string(), String::new())].into()),
+                ..Default::default()
+            },
+            spec: Some(DeploymentSpec { replicas: Some(desired), ..Default::default() }),
+            status: Some(DeploymentStatus { available_replicas: available, ..Default::default() }),
+        }
+    }
+
+    #[test]
+    fn test_operator_issues() {
+        let subscriptions = vec![
+            subscription("etcd", json!([{ "type": "CatalogSourcesUnhealthy", "status": "False" }])),
+            subscription(
+                "strimzi-kafka-operator",
+                json!([{ "type": "InstallPlanFailed", "status": "True", "message": "api-server resource not found installing CustomResourceDefinition" }]),
+            ),
+        ];
+        let deployments = vec![
+            deployment("etcd-operator", Some("operators.coreos.com/etcd.openshift-operators"), 1, Some(1)),
+            deployment("strimzi-cluster-operator", Some("operators.coreos.com/strimzi-kafka-operator.openshift-operators"), 1, None),
+            // Not managed by OLM
+            deployment("web", Some("app"), 2, Some(0)),
+        ];
+
+        let issues = operator_issues(&subscriptions, &deployments);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].namespace, "openshift-operators");
+        assert_eq!(issues[0].operator, "strimzi-kafka-operator");
+        assert_eq!(
+            issues[0].issue,
+            OperatorIssue::Subscription {
+                condition: "InstallPlanFailed".to_string(),
+                message: Some("api-server resource not found installing CustomResourceDefinition".to_string()),
+            }
+        );
+        assert_eq!(issues[1].operator, "strimzi-kafka-operator");
+        assert_eq!(
+            issues[1].issue,
+            OperatorIssue::DeploymentUnavailable {
+                deployment: "strimzi-cluster-operator".to_string(),
+                available: 0,
+                desired: 1,
+            }
+        );
+    }
+}
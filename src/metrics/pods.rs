@@ -1,15 +1,20 @@
 use anyhow::Result;
 use chrono::{DateTime, Duration, Utc};
-use k8s_openapi::api::core::v1::{Container, Pod};
+use k8s_openapi::api::core::v1::{Container, Event, Pod, ResourceRequirements};
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+use std::collections::BTreeMap;
 use kube::{api::ListParams, Api, Client};
 
 use crate::types::{
     Config, PodRequestTotals, HeavyUsagePod, RestartEventInfo, PendingPodInfo,
-    FailedPodInfo, UnreadyPodInfo, OomKilledInfo
+    FailedPodInfo, UnreadyPodInfo, OomKilledInfo, StaleMetricsInfo,
+    ResourceHygieneInfo, ResourceHygieneIssue, MissingConfigRefInfo, ReadinessFlapInfo
 };
 use crate::parsing::{parse_cpu_to_millicores, parse_memory_to_bytes, compute_utilization_percentages, any_exceeds};
-use super::base::{list_pod_metrics_http, build_usage_map_from_http, pod_status_time};
+use super::base::{
+    list_pod_metrics_http, build_usage_map_from_http, event_occurrences_since, pod_status_time, pod_workload, PodUsageMap
+};
 
 /// Analyze pods with heavy resource usage
 pub async fn analyze_heavy_usage(
@@ -30,7 +35,16 @@ pub async fn analyze_heavy_usage_with_pods(
 ) -> Result<Vec<HeavyUsagePod>> {
     let metrics_items = list_pod_metrics_http(client, namespace).await?;
     let usage_by_pod = build_usage_map_from_http(metrics_items);
-    
+    Ok(analyze_heavy_usage_with_usage(namespace, cfg, pods, &usage_by_pod))
+}
+
+/// Analyze pods with heavy resource usage using pre-listed pods and pre-fetched usage
+pub fn analyze_heavy_usage_with_usage(
+    namespace: &str,
+    cfg: &Config,
+    pods: &[Pod],
+    usage_by_pod: &PodUsageMap,
+) -> Vec<HeavyUsagePod> {
     let mut heavy_usage = Vec::new();
     
     for pod in pods.iter() {
@@ -39,23 +53,55 @@ pub async fn analyze_heavy_usage_with_pods(
             None => continue,
         };
         
-        if let Some(usage) = usage_by_pod.get(&pod_name) {
+        if let Some(usage) = usage_by_pod.get(&(namespace.to_string(), pod_name.clone())) {
             let requests = sum_requests(&pod);
             let (cpu_pct, mem_pct) = compute_utilization_percentages(usage, &requests);
             if let Some(exceeds) = any_exceeds(cpu_pct, mem_pct, cfg.threshold_percent) {
                 if exceeds {
                     heavy_usage.push(HeavyUsagePod {
+                        labels: issue_labels(pod, &cfg.issue_labels),
                         namespace: namespace.to_string(),
                         pod: pod_name,
                         cpu_pct,
                         mem_pct,
+                        cpu_millicores: usage.cpu_millicores,
+                        memory_bytes: usage.memory_bytes,
                     });
                 }
             }
         }
     }
     
-    Ok(heavy_usage)
+    heavy_usage
+}
+
+/// Report pods whose latest metrics sample is older than the configured staleness window
+pub fn analyze_stale_metrics_with_usage(
+    namespace: &str,
+    cfg: &Config,
+    usage_by_pod: &PodUsageMap,
+) -> Vec<StaleMetricsInfo> {
+    let now = Utc::now();
+    let mut stale: Vec<StaleMetricsInfo> = usage_by_pod
+        .iter()
+        .filter(|((ns, _), _)| ns == namespace)
+        .filter_map(|((ns, pod), usage)| {
+            let sampled_at = usage.timestamp?;
+            let age = now - sampled_at;
+            if age <= Duration::minutes(cfg.metrics_staleness_minutes) {
+                return None;
+            }
+            Some(StaleMetricsInfo {
+                namespace: ns.clone(),
+                pod: pod.clone(),
+                sampled_at,
+                age_minutes: age.num_minutes(),
+                window: usage.window.clone(),
+            })
+        })
+        .collect();
+    stale.sort_by(|a, b| a.pod.cmp(&b.pod));
+    stale
 }
 
 /// Analyze container restarts beyond grace period
@@ -101,12 +147,18 @@ pub fn analyze_restarts_with_pods(
                     if include {
                         restarts.push(RestartEventInfo {
                             namespace: namespace.to_string(),
+                            node_name: pod_node(pod),
+                            labels: issue_labels(pod, &cfg.issue_labels),
+                            zone: None,
+                            blast_radius: None,
                             pod: pod_name.clone(),
                             container: cs.name.clone(),
                             last_restart_time,
                             reason,
                             message,
                             exit_code,
+                            image: container_image(pod, &cs.name),
+                            log_tail: None,
                         });
                     }
                 }
@@ -147,8 +199,13 @@ pub fn analyze_pending_pods_with_pods(
             pendings.push(PendingPodInfo {
                 namespace: namespace.to_string(),
                 pod: pod_name,
+                node_name: pod_node(pod),
+                labels: issue_labels(pod, &cfg.issue_labels),
+                zone: None,
+                blast_radius: None,
                 since,
                 duration_minutes,
+                image_pull_failures: image_pull_failures(pod),
             });
         }
     }
@@ -187,6 +244,14 @@ pub fn analyze_failed_pods_with_pods(
             failed_pods.push(FailedPodInfo {
                 namespace: namespace.to_string(),
                 pod: pod_name,
+
+                node_name: pod_node(pod),
+
+                labels: issue_labels(pod, &cfg.issue_labels),
+
+                zone: None,
+
+                blast_radius: None,
                 since,
                 duration_minutes,
                 reason,
@@ -229,6 +294,14 @@ pub fn analyze_unready_pods_with_pods(
             unready_pods.push(UnreadyPodInfo {
                 namespace: namespace.to_string(),
                 pod: pod_name,
+
+                node_name: pod_node(pod),
+
+                labels: issue_labels(pod, &cfg.issue_labels),
+
+                zone: None,
+
+                blast_radius: None,
                 since,
                 duration_minutes,
                 failed_conditions,
@@ -271,10 +344,16 @@ pub fn analyze_oom_killed_with_pods(
                 if let Some(oom_info) = extract_oom_info(cs, &startup_grace_cutoff) {
                     oom_killed.push(OomKilledInfo {
                         namespace: namespace.to_string(),
+                        node_name: pod_node(pod),
+                        labels: issue_labels(pod, &cfg.issue_labels),
+                        zone: None,
+                        blast_radius: None,
                         pod: pod_name.clone(),
                         container: cs.name.clone(),
                         last_oom_time: oom_info.0,
                         restart_count: cs.restart_count,
+                        image: container_image(pod, &cs.name),
+                        log_tail: None,
                     });
                 }
             }
@@ -283,6 +362,233 @@ pub fn analyze_oom_killed_with_pods(
     oom_killed
 }
 
+/// Audit container requests/limits for missing memory limits and pathological ratios.
+/// Findings are reported once per workload and container.
+pub fn analyze_resource_hygiene_with_pods(
+    namespace: &str,
+    cfg: &Config,
+    pods: &[Pod],
+) -> Vec<ResourceHygieneInfo> {
+    let mut findings: Vec<ResourceHygieneInfo> = Vec::new();
+
+    for pod in pods.iter() {
+        let pod_name = match pod.metadata.name.as_ref() {
+            Some(n) => n.clone(),
+            None => continue,
+        };
+        let workload = pod_workload(pod)
+            .map(|(kind, name)| format!("{}/{}", kind, name))
+            .unwrap_or_else(|| format!("Pod/{}", pod_name));
+
+        let containers = pod.spec.as_ref().map(|s| s.containers.as_slice()).unwrap_or_default();
+        for c in containers {
+            for issue in container_hygiene_issues(c, cfg.limit_request_ratio_threshold) {
+                let duplicate = findings.iter().any(|f| {
+                    f.workload == workload && f.container == c.name && f.issue == issue
+                });
+                if !duplicate {
+                    findings.push(ResourceHygieneInfo {
+                        namespace: namespace.to_string(),
+                        workload: workload.clone(),
+                        container: c.name.clone(),
+                        issue,
+                    });
+                }
+            }
+        }
+    }
+    findings
+}
+
+fn container_hygiene_issues(container: &Container, ratio_threshold: f64) -> Vec<ResourceHygieneIssue> {
+    let resources = container.resources.as_ref();
+    let quantity = |select: fn(&ResourceRequirements) -> Option<&BTreeMap<String, Quantity>>, key: &str| {
+        resources
+            .and_then(select)
+            .and_then(|m| m.get(key))
+            .map(|q| q.0.clone())
+    };
+    let mut issues = Vec::new();
+
+    let mem_limit = quantity(|r| r.limits.as_ref(), "memory").and_then(|q| parse_memory_to_bytes(&q));
+    if mem_limit.is_none() {
+        issues.push(ResourceHygieneIssue::MemoryLimitUnset);
+    }
+
+    let cpu_request = quantity(|r| r.requests.as_ref(), "cpu").and_then(|q| parse_cpu_to_millicores(&q));
+    let cpu_limit = quantity(|r| r.limits.as_ref(), "cpu").and_then(|q| parse_cpu_to_millicores(&q));
+    let mem_request = quantity(|r| r.requests.as_ref(), "memory").and_then(|q| parse_memory_to_bytes(&q));
+
+    for (resource, request, limit) in [("cpu", cpu_request, cpu_limit), ("memory", mem_request, mem_limit)] {
+        if let (Some(req), Some(lim)) = (request, limit) {
+            if req > 0 {
+                let ratio = lim as f64 / req as f64;
+                if ratio >= ratio_threshold {
+                    issues.push(ResourceHygieneIssue::HighLimitRatio {
+                        resource: resource.to_string(),
+                        ratio,
+                    });
+                }
+            }
+        }
+    }
+    issues
+}
+
+/// Report pods blocked on ConfigMaps/Secrets that do not exist (or lack a key),
+/// from CreateContainerConfigError states and FailedMount events
+pub fn analyze_missing_config_refs_with_pods(
+    namespace: &str,
+    pods: &[Pod],
+    events: &[Event],
+) -> Vec<MissingConfigRefInfo> {
+    let mut missing: Vec<MissingConfigRefInfo> = Vec::new();
+    let mut push = |info: MissingConfigRefInfo| {
+        let duplicate = missing.iter().any(|m| {
+            m.pod == info.pod && m.kind == info.kind && m.name == info.name && m.key == info.key
+        });
+        if !duplicate {
+            missing.push(info);
+        }
+    };
+
+    for pod in pods.iter() {
+        let pod_name = match pod.metadata.name.as_ref() {
+            Some(n) => n.clone(),
+            None => continue,
+        };
+        let statuses = pod.status.as_ref().into_iter().flat_map(|s| {
+            s.init_container_statuses.iter().flatten().chain(s.container_statuses.iter().flatten())
+        });
+        for cs in statuses {
+            let waiting = match cs.state.as_ref().and_then(|s| s.waiting.as_ref()) {
+                Some(w) if w.reason.as_deref() == Some("CreateContainerConfigError") => w,
+                _ => continue,
+            };
+            if let Some((kind, name, key)) = waiting.message.as_deref().and_then(parse_missing_config_ref) {
+                push(MissingConfigRefInfo {
+                    namespace: namespace.to_string(),
+                    pod: pod_name.clone(),
+                    container: Some(cs.name.clone()),
+                    kind,
+                    name,
+                    key,
+                });
+            }
+        }
+    }
+
+    for event in events {
+        if event.reason.as_deref() != Some("FailedMount")
+            || event.involved_object.kind.as_deref() != Some("Pod")
+        {
+            continue;
+        }
+        let pod_name = match event.involved_object.name.as_ref() {
+            Some(n) => n.clone(),
+            None => continue,
+        };
+        // Events outlive their pods; only report pods that still exist
+        if !pods.iter().any(|p| p.metadata.name.as_ref() == Some(&pod_name)) {
+            continue;
+        }
+        if let Some((kind, name, key)) = event.message.as_deref().and_then(parse_missing_config_ref) {
+            push(MissingConfigRefInfo {
+                namespace: namespace.to_string(),
+                pod: pod_name,
+                container: None,
+                kind,
+                name,
+                key,
+            });
+        }
+    }
+    missing
+}
+
+/// Detect pods whose readiness flaps: repeated readiness probe failures within the
+/// window while the pod is Ready again (or became Ready/unready within the window).
+/// Pods that stay unready are left to the unready check.
+pub fn analyze_readiness_flapping_with_pods(
+    namespace: &str,
+    cfg: &Config,
+    pods: &[Pod],
+    events: &[Event],
+    now: DateTime<Utc>,
+) -> Vec<ReadinessFlapInfo> {
+    let cutoff = now - Duration::minutes(cfg.readiness_flap_window_minutes);
+    let mut flapping = Vec::new();
+
+    for pod in pods.iter() {
+        let pod_name = match pod.metadata.name.as_ref() {
+            Some(n) => n.clone(),
+            None => continue,
+        };
+        let probe_failures: i64 = events
+            .iter()
+            .filter(|e| {
+                e.reason.as_deref() == Some("Unhealthy")
+                    && e.involved_object.kind.as_deref() == Some("Pod")
+                    && e.involved_object.name.as_deref() == Some(pod_name.as_str())
+                    && e.message.as_deref().is_some_and(|m| m.starts_with("Readiness probe failed"))
+            })
+            .map(|e| event_occurrences_since(e, cutoff))
+            .sum();
+        if probe_failures < cfg.readiness_flap_threshold {
+            continue;
+        }
+
+        let ready_condition = pod
+            .status
+            .as_ref()
+            .and_then(|s| s.conditions.as_ref())
+            .and_then(|conditions| conditions.iter().find(|c| c.type_ == "Ready"));
+        let currently_ready = ready_condition.is_some_and(|c| c.status == "True");
+        let last_transition = ready_condition
+            .and_then(|c| c.last_transition_time.as_ref())
+            .map(|t| t.0);
+        if !currently_ready && last_transition.is_none_or(|t| t <= cutoff) {
+            continue;
+        }
+
+        flapping.push(ReadinessFlapInfo {
+            namespace: namespace.to_string(),
+            pod: pod_name,
+            probe_failures,
+            currently_ready,
+            last_transition,
+            window_minutes: cfg.readiness_flap_window_minutes,
+        });
+    }
+    flapping
+}
+
+/// Extract (kind, name, key) from kubelet messages such as
+/// `configmap "app-config" not found` or `couldn't find key DB_HOST in Secret ns/db`
+fn parse_missing_config_ref(message: &str) -> Option<(String, String, Option<String>)> {
+    for (needle, kind) in [("configmap \"", "ConfigMap"), ("secret \"", "Secret")] {
+        if let Some(start) = message.find(needle) {
+            let rest = &message[start + needle.len()..];
+            if let Some((name, tail)) = rest.split_once('"') {
+                if tail.trim_start().starts_with("not found") {
+                    return Some((kind.to_string(), name.to_string(), None));
+                }
+            }
+        }
+    }
+
+    let rest = message.split_once("couldn't find key ")?.1;
+    let (key, rest) = rest.split_once(" in ")?;
+    let (kind, object) = rest.split_once(' ')?;
+    let kind = match kind {
+        "ConfigMap" | "Secret" => kind.to_string(),
+        _ => return None,
+    };
+    let object = object.split_whitespace().next()?;
+    let name = object.rsplit('/').next()?;
+    Some((kind, name.to_string(), Some(key.to_string())))
+}
+
 // Shared helper to list pods once per namespace
 async fn list_namespace_pods(client: &Client, namespace: &str) -> Result<Vec<Pod>> {
     let pod_api: Api<Pod> = Api::namespaced(client.clone(), namespace);
@@ -291,7 +597,7 @@ async fn list_namespace_pods(client: &Client, namespace: &str) -> Result<Vec<Pod
 }
 
 // Helper functions
-fn is_pending_over_grace(pod: &Pod, grace_minutes: i64) -> bool {
+pub(crate) fn is_pending_over_grace(pod: &Pod, grace_minutes: i64) -> bool {
     let phase = pod
         .status
         .as_ref()
@@ -353,7 +659,18 @@ fn is_unready_over_grace(pod: &Pod, grace_minutes: i64) -> bool {
     (Utc::now() - since) > Duration::minutes(grace_minutes)
 }
 
-fn sum_requests(pod: &Pod) -> PodRequestTotals {
+pub(crate) fn sum_requests(pod: &Pod) -> PodRequestTotals {
+    sum_resources(pod, |r| r.requests.as_ref())
+}
+
+pub(crate) fn sum_limits(pod: &Pod) -> PodRequestTotals {
+    sum_resources(pod, |r| r.limits.as_ref())
+}
+
+fn sum_resources(
+    pod: &Pod,
+    select: impl Fn(&ResourceRequirements) -> Option<&BTreeMap<String, Quantity>>,
+) -> PodRequestTotals {
     let mut cpu_sum: i64 = 0;
     let mut mem_sum: i64 = 0;
     let mut have_cpu = false;
@@ -363,7 +680,7 @@ fn sum_requests(pod: &Pod) -> PodRequestTotals {
         let containers: &Vec<Container> = &spec.containers;
         for c in containers {
             if let Some(resources) = c.resources.as_ref() {
-                if let Some(req) = resources.requests.as_ref() {
+                if let Some(req) = select(resources) {
                     if let Some(cpu) = req.get("cpu").map(|q| q.0.as_str()) {
                         if let Some(mc) = parse_cpu_to_millicores(cpu) {
                             have_cpu = true;
@@ -435,6 +752,62 @@ fn extract_failed_conditions(pod: &Pod) -> Vec<String> {
         .unwrap_or_default()
 }
 
+/// The subset of a pod's labels named by `ISSUE_LABELS`
+fn issue_labels(pod: &Pod, keys: &[String]) -> BTreeMap<String, String> {
+    let labels = match pod.metadata.labels.as_ref() {
+        Some(l) => l,
+        None => return BTreeMap::new(),
+    };
+    keys.iter()
+        .filter_map(|k| labels.get(k).map(|v| (k.clone(), v.clone())))
+        .collect()
+}
+
+/// Node a pod is scheduled to
+fn pod_node(pod: &Pod) -> Option<String> {
+    pod.spec.as_ref().and_then(|s| s.node_name.clone())
+}
+
+/// Image of a (init) container as written in the pod spec, falling back to the status
+fn container_image(pod: &Pod, container: &str) -> Option<String> {
+    let spec = pod.spec.as_ref();
+    spec.into_iter()
+        .flat_map(|s| s.containers.iter().chain(s.init_containers.iter().flatten()))
+        .find(|c| c.name == container)
+        .and_then(|c| c.image.clone())
+        .or_else(|| {
+            let status = pod.status.as_ref()?;
+            status
+                .container_statuses
+                .iter()
+                .chain(status.init_container_statuses.iter())
+                .flatten()
+                .find(|cs| cs.name == container)
+                .map(|cs| cs.image.clone())
+        })
+}
+
+/// Images of (init) containers waiting because their image cannot be pulled
+fn image_pull_failures(pod: &Pod) -> Vec<String> {
+    let status = match pod.status.as_ref() {
+        Some(s) => s,
+        None => return Vec::new(),
+    };
+    let mut images: Vec<String> = status
+        .init_container_statuses
+        .iter()
+        .chain(status.container_statuses.iter())
+        .flatten()
+        .filter(|cs| {
+            let reason = cs.state.as_ref().and_then(|s| s.waiting.as_ref()).and_then(|w| w.reason.as_deref());
+            matches!(reason, Some("ErrImagePull" | "ImagePullBackOff" | "InvalidImageName"))
+        })
+        .filter_map(|cs| container_image(pod, &cs.name))
+        .collect();
+    images.dedup();
+    images
+}
+
 fn extract_oom_info(
     cs: &k8s_openapi::api::core::v1::ContainerStatus,
     grace_cutoff: &DateTime<Utc>,
@@ -461,6 +834,7 @@ fn extract_oom_info(
 mod tests {
     use super::*;
     use chrono::Utc;
+    use crate::types::PodUsageTotals;
     use k8s_openapi::api::core::v1::{PodStatus, PodCondition, ContainerStatus, ContainerState, ContainerStateTerminated};
     use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ObjectMeta, Time};
 
@@ -474,6 +848,7 @@ mod tests {
             cluster_name: None,
             datacenter_name: None,
             fail_if_no_metrics: false,
+            ..Default::default()
         }
     }
 
@@ -599,6 +974,269 @@ mod tests {
         assert!(!failed_conditions.iter().any(|c| c.contains("PodScheduled")));
     }
 
+    #[test]
+    fn test_analyze_stale_metrics_with_usage() {
+        let config = create_test_config();
+        let mut usage_by_pod = PodUsageMap::new();
+        usage_by_pod.insert(("default".to_string(), "fresh".to_string()), PodUsageTotals {
+            timestamp: Some(Utc::now() - Duration::minutes(1)),
+            ..Default::default()
+        });
+        usage_by_pod.insert(("default".to_string(), "stale".to_string()), PodUsageTotals {
+            timestamp: Some(Utc::now() - Duration::minutes(20)),
+            window: Some("30s".to_string()),
+            ..Default::default()
+        });
+        usage_by_pod.insert(("other".to_string(), "stale".to_string()), PodUsageTotals {
+            timestamp: Some(Utc::now() - Duration::minutes(20)),
+            ..Default::default()
+        });
+
+        let stale = analyze_stale_metrics_with_usage("default", &config, &usage_by_pod);
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].pod, "stale");
+        assert!(stale[0].age_minutes >= 19);
+        assert_eq!(stale[0].window.as_deref(), Some("30s"));
+    }
+
+    fn container_with(requests: &[(&str, &str)], limits: &[(&str, &str)]) -> Container {
+        let to_map = |pairs: &[(&str, &str)]| {
+            pairs.iter().map(|(k, v)| (k.to_string(), Quantity(v.to_string()))).collect::<BTreeMap<_, _>>()
+        };
+        Container {
+            name: "main".to_string(),
+            resources: Some(ResourceRequirements {
+                requests: Some(to_map(requests)),
+                limits: Some(to_map(limits)),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_container_hygiene_issues() {
+        // Balanced container
+        let c = container_with(&[("cpu", "500m"), ("memory", "512Mi")], &[("cpu", "1"), ("memory", "1Gi")]);
+        assert!(container_hygiene_issues(&c, 10.0).is_empty());
+
+        // Memory limit unset
+        let c = container_with(&[("cpu", "500m"), ("memory", "512Mi")], &[("cpu", "1")]);
+        assert_eq!(container_hygiene_issues(&c, 10.0), vec![ResourceHygieneIssue::MemoryLimitUnset]);
+
+        // CPU limit 20x the request
+        let c = container_with(&[("cpu", "100m"), ("memory", "512Mi")], &[("cpu", "2"), ("memory", "512Mi")]);
+        let issues = container_hygiene_issues(&c, 10.0);
+        assert_eq!(issues.len(), 1);
+        match &issues[0] {
+            ResourceHygieneIssue::HighLimitRatio { resource, ratio } => {
+                assert_eq!(resource, "cpu");
+                assert!((ratio - 20.0).abs() < 0.01);
+            }
+            other => panic!("unexpected issue {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_analyze_resource_hygiene_dedupes_replicas() {
+        let config = create_test_config();
+        let mut pods = Vec::new();
+        for i in 0..3 {
+            let mut pod = create_test_pod(&format!("api-abc12-{}", i), "Running", Utc::now());
+            pod.metadata.owner_references = Some(vec![k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference {
+                kind: "ReplicaSet".to_string(),
+                name: "api-abc12".to_string(),
+                controller: Some(true),
+                ..Default::default()
+            }]);
+            pod.spec = Some(k8s_openapi::api::core::v1::PodSpec {
+                containers: vec![container_with(&[("memory", "128Mi")], &[])],
+                ..Default::default()
+            });
+            pods.push(pod);
+        }
+
+        let findings = analyze_resource_hygiene_with_pods("default", &config, &pods);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].workload, "Deployment/api");
+        assert_eq!(findings[0].issue, ResourceHygieneIssue::MemoryLimitUnset);
+    }
+
+    #[test]
+    fn test_parse_missing_config_ref() {
+        assert_eq!(
+            parse_missing_config_ref("configmap \"app-config\" not found"),
+            Some(("ConfigMap".to_string(), "app-config".to_string(), None))
+        );
+        assert_eq!(
+            parse_missing_config_ref("MountVolume.SetUp failed for volume \"creds\" : secret \"db-creds\" not found"),
+            Some(("Secret".to_string(), "db-creds".to_string(), None))
+        );
+        assert_eq!(
+            parse_missing_config_ref("couldn't find key DB_HOST in ConfigMap default/app-config"),
+            Some(("ConfigMap".to_string(), "app-config".to_string(), Some("DB_HOST".to_string())))
+        );
+        assert_eq!(parse_missing_config_ref("container has runAsNonRoot and image will run as root"), None);
+    }
+
+    #[test]
+    fn test_analyze_missing_config_refs() {
+        use k8s_openapi::api::core::v1::{ContainerState, ContainerStateWaiting, ContainerStatus, ObjectReference};
+
+        let mut pod = create_test_pod("api-0", "Pending", Utc::now());
+        pod.status.as_mut().unwrap().container_statuses = Some(vec![ContainerStatus {
+            name: "api".to_string(),
+            state: Some(ContainerState {
+                waiting: Some(ContainerStateWaiting {
+                    reason: Some("CreateContainerConfigError".to_string()),
+                    message: Some("secret \"api-token\" not found".to_string()),
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }]);
+        let mount_event = |pod: &str| Event {
+            reason: Some("FailedMount".to_string()),
+            message: Some("MountVolume.SetUp failed for volume \"cfg\" : configmap \"api-config\" not found".to_string()),
+            involved_object: ObjectReference {
+                kind: Some("Pod".to_string()),
+                name: Some(pod.to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let events = vec![mount_event("api-0"), mount_event("api-0"), mount_event("deleted-pod")];
+
+        let missing = analyze_missing_config_refs_with_pods("default", &[pod], &events);
+        assert_eq!(missing.len(), 2);
+        assert_eq!(missing[0].kind, "Secret");
+        assert_eq!(missing[0].name, "api-token");
+        assert_eq!(missing[0].container.as_deref(), Some("api"));
+        assert_eq!(missing[1].kind, "ConfigMap");
+        assert_eq!(missing[1].name, "api-config");
+        assert!(missing[1].container.is_none());
+    }
+
+    #[test]
+    fn test_analyze_readiness_flapping() {
+        use k8s_openapi::api::core::v1::{ObjectReference, PodCondition};
+
+        let now = Utc::now();
+        let config = Config {
+            readiness_flap_window_minutes: 60,
+            readiness_flap_threshold: 5,
+            ..create_test_config()
+        };
+        let with_ready = |name: &str, status: &str, since: DateTime<Utc>| {
+            let mut pod = create_test_pod(name, "Running", now - Duration::hours(5));
+            pod.status.as_mut().unwrap().conditions = Some(vec![PodCondition {
+                type_: "Ready".to_string(),
+                status: status.to_string(),
+                last_transition_time: Some(Time(since)),
+                ..Default::default()
+            }]);
+            pod
+        };
+        let probe_failures = |pod: &str, count: i32| Event {
+            reason: Some("Unhealthy".to_string()),
+            message: Some("Readiness probe failed: HTTP probe failed with statuscode: 503".to_string()),
+            involved_object: ObjectReference {
+                kind: Some("Pod".to_string()),
+                name: Some(pod.to_string()),
+                ..Default::default()
+            },
+            count: Some(count),
+            first_timestamp: Some(Time(now - Duration::minutes(30))),
+            last_timestamp: Some(Time(now - Duration::minutes(1))),
+            ..Default::default()
+        };
+
+        let pods = vec![
+            // Ready now, but the probe failed repeatedly in the window
+            with_ready("flappy", "True", now - Duration::minutes(2)),
+            // Unready for hours: reported by the unready check instead
+            with_ready("down", "False", now - Duration::hours(3)),
+            // A couple of failures only
+            with_ready("steady", "True", now - Duration::hours(4)),
+        ];
+        let events = vec![probe_failures("flappy", 12), probe_failures("down", 50), probe_failures("steady", 2)];
+
+        let flapping = analyze_readiness_flapping_with_pods("default", &config, &pods, &events, now);
+        assert_eq!(flapping.len(), 1);
+        assert_eq!(flapping[0].pod, "flappy");
+        assert_eq!(flapping[0].probe_failures, 12);
+        assert!(flapping[0].currently_ready);
+    }
+
+    #[test]
+    fn test_issue_labels() {
+        let pod = Pod {
+            metadata: ObjectMeta {
+                labels: Some(
+                    [("team", "payments"), ("app", "api"), ("pod-template-hash", "7d9f8b6c5")]
+                        .iter()
+                        .map(|(k, v)| (k.to_string(), v.to_string()))
+                        .collect(),
+                ),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let keys = vec!["team".to_string(), "app".to_string(), "owner".to_string()];
+        let labels = issue_labels(&pod, &keys);
+        assert_eq!(labels.len(), 2);
+        assert_eq!(labels["team"], "payments");
+        assert_eq!(labels["app"], "api");
+        assert!(issue_labels(&Pod::default(), &keys).is_empty());
+    }
+
+    #[test]
+    fn test_container_images() {
+        use k8s_openapi::api::core::v1::{ContainerStateWaiting, PodSpec};
+
+        let waiting = |name: &str, image: &str, reason: &str| ContainerStatus {
+            name: name.to_string(),
+            image: image.to_string(),
+            state: Some(ContainerState {
+                waiting: Some(ContainerStateWaiting {
+                    reason: Some(reason.to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let pod = Pod {
+            spec: Some(PodSpec {
+                containers: vec![
+                    Container {
+                        name: "app".to_string(),
+                        image: Some("registry.example.com/app:1.4.2".to_string()),
+                        ..Default::default()
+                    },
+                    Container {
+                        name: "sidecar".to_string(),
+                        image: Some("envoy:v1.29".to_string()),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }),
+            status: Some(PodStatus {
+                container_statuses: Some(vec![
+                    waiting("app", "registry.example.com/app:1.4.2", "ImagePullBackOff"),
+                    waiting("sidecar", "docker.io/library/envoy:v1.29", "ContainerCreating"),
+                ]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(container_image(&pod, "sidecar").as_deref(), Some("envoy:v1.29"));
+        assert_eq!(container_image(&pod, "missing"), None);
+        assert_eq!(image_pull_failures(&pod), vec!["registry.example.com/app:1.4.2".to_string()]);
+    }
+
     #[test]
     fn test_extract_oom_info() {
         let grace_cutoff = Utc::now() - Duration::minutes(2);
@@ -0,0 +1,256 @@
+use anyhow::Result;
+use k8s_openapi::api::core::v1::{Container, LimitRange, Pod, ResourceQuota};
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use kube::{api::ListParams, Api, Client};
+use std::collections::BTreeMap;
+
+use crate::parsing::{parse_cpu_to_millicores, parse_memory_to_bytes};
+use crate::types::{Config, LimitRangeConflictInfo, NamespaceQuotaInfo};
+use super::base::pod_workload;
+
+/// List ResourceQuotas in a namespace
+pub async fn list_namespace_resource_quotas(client: &Client, namespace: &str) -> Result<Vec<ResourceQuota>> {
+    let quota_api: Api<ResourceQuota> = Api::namespaced(client.clone(), namespace);
+    Ok(quota_api.list(&ListParams::default()).await?.items)
+}
+
+/// List LimitRanges in a namespace
+pub async fn list_namespace_limit_ranges(client: &Client, namespace: &str) -> Result<Vec<LimitRange>> {
+    let limit_range_api: Api<LimitRange> = Api::namespaced(client.clone(), namespace);
+    Ok(limit_range_api.list(&ListParams::default()).await?.items)
+}
+
+/// Report a namespace lacking a ResourceQuota or a LimitRange; without them nothing
+/// stops one team's workloads from starving the others on a shared cluster
+pub fn analyze_namespace_quotas(
+    namespace: &str,
+    quotas: &[ResourceQuota],
+    limit_ranges: &[LimitRange],
+) -> Option<NamespaceQuotaInfo> {
+    let has_resource_quota = !quotas.is_empty();
+    let has_limit_range = !limit_ranges.is_empty();
+    if has_resource_quota && has_limit_range {
+        return None;
+    }
+    Some(NamespaceQuotaInfo {
+        namespace: namespace.to_string(),
+        has_resource_quota,
+        has_limit_range,
+    })
+}
+
+/// Check container requests and limits against the `Container` constraints of the
+/// namespace's LimitRanges. Values outside min/max/maxLimitRequestRatio are violations:
+/// the pods were admitted before the LimitRange changed and their replacements will be
+/// rejected. Values within `limit_range_edge_percent` of max or the ratio are at the
+/// edge, where a small bump fails admission. Reported once per workload and container.
+pub fn analyze_limit_range_conflicts_with_pods(
+    namespace: &str,
+    cfg: &Config,
+    pods: &[Pod],
+    limit_ranges: &[LimitRange],
+) -> Vec<LimitRangeConflictInfo> {
+    let mut conflicts: Vec<LimitRangeConflictInfo> = Vec::new();
+
+    for pod in pods {
+        let (Some(pod_name), Some(spec)) = (pod.metadata.name.as_ref(), pod.spec.as_ref()) else {
+            continue;
+        };
+        let workload = pod_workload(pod)
+            .map(|(kind, name)| format!("{}/{}", kind, name))
+            .unwrap_or_else(|| format!("Pod/{}", pod_name));
+
+        for container in spec.init_containers.iter().flatten().chain(&spec.containers) {
+            for limit_range in limit_ranges {
+                for mut conflict in container_conflicts(container, limit_range, cfg.limit_range_edge_percent) {
+                    let duplicate = conflicts.iter().any(|c| {
+                        c.workload == workload
+                            && c.container == conflict.container
+                            && c.limit_range == conflict.limit_range
+                            && c.resource == conflict.resource
+                            && c.constraint == conflict.constraint
+                    });
+                    if !duplicate {
+                        conflict.namespace = namespace.to_string();
+                        conflict.workload = workload.clone();
+                        conflicts.push(conflict);
+                    }
+                }
+            }
+        }
+    }
+    conflicts
+}
+
+/// Conflicts of one container with one LimitRange; namespace and workload are left empty
+fn container_conflicts(container: &Container, limit_range: &LimitRange, edge_percent: f64) -> Vec<LimitRangeConflictInfo> {
+    let empty = BTreeMap::new();
+    let resources = container.resources.as_ref();
+    let requests = resources.and_then(|r| r.requests.as_ref()).unwrap_or(&empty);
+    let limits = resources.and_then(|r| r.limits.as_ref()).unwrap_or(&empty);
+    let items = limit_range.spec.as_ref().map(|s| s.limits.as_slice()).unwrap_or_default();
+    let mut conflicts = Vec::new();
+    let mut push = |resource: &str, constraint: &str, value: String, bound: String, violated: bool| {
+        conflicts.push(LimitRangeConflictInfo {
+            namespace: String::new(),
+            workload: String::new(),
+            container: container.name.clone(),
+            limit_range: limit_range.metadata.name.clone().unwrap_or_default(),
+            resource: resource.to_string(),
+            constraint: constraint.to_string(),
+            value,
+            bound,
+            violated,
+        });
+    };
+
+    for item in items.iter().filter(|i| i.type_ == "Container") {
+        for (resource, max) in item.max.iter().flatten() {
+            // Both must stay below max; the limit is the larger one when set
+            let (field, quantity) = match (limits.get(resource), requests.get(resource)) {
+                (Some(limit), _) => ("limit", limit),
+                (None, Some(request)) => ("request", request),
+                (None, None) => continue,
+            };
+            if let (Some(value), Some(bound)) = (quantity_value(resource, quantity), quantity_value(resource, max)) {
+                if value > bound || value >= bound * edge_percent / 100.0 {
+                    push(resource, &format!("max {}", field), quantity.0.clone(), max.0.clone(), value > bound);
+                }
+            }
+        }
+        for (resource, min) in item.min.iter().flatten() {
+            // An unset request defaults to the limit
+            let Some(quantity) = requests.get(resource).or_else(|| limits.get(resource)) else {
+                continue;
+            };
+            if let (Some(value), Some(bound)) = (quantity_value(resource, quantity), quantity_value(resource, min)) {
+                if value < bound {
+                    push(resource, "min request", quantity.0.clone(), min.0.clone(), true);
+                }
+            }
+        }
+        for (resource, ratio) in item.max_limit_request_ratio.iter().flatten() {
+            let (Some(limit), Some(request)) = (limits.get(resource), requests.get(resource)) else {
+                continue;
+            };
+            let value = quantity_value(resource, limit).zip(quantity_value(resource, request))
+                .filter(|(_, request)| *request > 0.0)
+                .map(|(limit, request)| limit / request);
+            if let (Some(value), Ok(bound)) = (value, ratio.0.parse::<f64>()) {
+                if value > bound || value >= bound * edge_percent / 100.0 {
+                    push(resource, "maxLimitRequestRatio", format!("{:.1}", value), ratio.0.clone(), value > bound);
+                }
+            }
+        }
+    }
+    conflicts
+}
+
+/// Comparable value of a cpu (millicores) or memory/storage (bytes) quantity
+fn quantity_value(resource: &str, quantity: &Quantity) -> Option<f64> {
+    match resource {
+        "cpu" => parse_cpu_to_millicores(&quantity.0).map(|v| v as f64),
+        "memory" | "ephemeral-storage" => parse_memory_to_bytes(&quantity.0).map(|v| v as f64),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::{LimitRangeItem, LimitRangeSpec, PodSpec, ResourceRequirements};
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+
+    fn quantities(values: &[(&str, &str)]) -> Option<BTreeMap<String, Quantity>> {
+        Some(values.iter().map(|(k, v)| (k.to_string(), Quantity(v.to_string()))).collect())
+    }
+
+    fn container_limit_range(max: &[(&str, &str)], min: &[(&str, &str)], ratio: &[(&str, &str)]) -> LimitRange {
+        LimitRange {
+            metadata: ObjectMeta { name: Some("limits".to_string()), ..Default::default() },
+            spec: Some(LimitRangeSpec {
+                limits: vec![LimitRangeItem {
+                    type_: "Container".to_string(),
+                    max: quantities(max),
+                    min: quantities(min),
+                    max_limit_request_ratio: quantities(ratio),
+                    ..Default::default()
+                }],
+            }),
+        }
+    }
+
+    fn pod_with(name: &str, requests: &[(&str, &str)], limits: &[(&str, &str)]) -> Pod {
+        Pod {
+            metadata: ObjectMeta { name: Some(name.to_string()), ..Default::default() },
+            spec: Some(PodSpec {
+                containers: vec![Container {
+                    name: "app".to_string(),
+                    resources: Some(ResourceRequirements {
+                        requests: quantities(requests),
+                        limits: quantities(limits),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_analyze_limit_range_conflicts() {
+        let config = Config { limit_range_edge_percent: 90.0, ..Default::default() };
+        let limit_ranges = vec![container_limit_range(
+            &[("cpu", "2"), ("memory", "1Gi")],
+            &[("memory", "64Mi")],
+            &[("cpu", "4")],
+        )];
+        let pods = vec![
+            // Limit above max: admitted before the LimitRange changed
+            pod_with("big", &[("cpu", "500m"), ("memory", "512Mi")], &[("cpu", "1"), ("memory", "2Gi")]),
+            // cpu limit at 95% of max, ratio 3.8 of 4, memory request below min
+            pod_with("edgy", &[("cpu", "500m"), ("memory", "32Mi")], &[("cpu", "1900m")]),
+            pod_with("fine", &[("cpu", "500m"), ("memory", "128Mi")], &[("cpu", "1"), ("memory", "256Mi")]),
+        ];
+
+        let conflicts = analyze_limit_range_conflicts_with_pods("default", &config, &pods, &limit_ranges);
+        let summary: Vec<(&str, &str, &str, &str, &str, bool)> = conflicts
+            .iter()
+            .map(|c| (c.workload.as_str(), c.resource.as_str(), c.constraint.as_str(), c.value.as_str(), c.bound.as_str(), c.violated))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Pod/big", "memory", "max limit", "2Gi", "1Gi", true),
+                ("Pod/edgy", "cpu", "max limit", "1900m", "2", false),
+                ("Pod/edgy", "memory", "min request", "32Mi", "64Mi", true),
+                ("Pod/edgy", "cpu", "maxLimitRequestRatio", "3.8", "4", false),
+            ]
+        );
+        assert!(conflicts.iter().all(|c| c.namespace == "default" && c.limit_range == "limits" && c.container == "app"));
+
+        // Pod-level items are not checked
+        let mut pod_level = limit_ranges[0].clone();
+        pod_level.spec.as_mut().unwrap().limits[0].type_ = "Pod".to_string();
+        assert!(analyze_limit_range_conflicts_with_pods("default", &config, &pods, &[pod_level]).is_empty());
+    }
+
+    #[test]
+    fn test_analyze_namespace_quotas() {
+        let quota = ResourceQuota::default();
+        let limit_range = LimitRange::default();
+
+        assert!(analyze_namespace_quotas("team-a", std::slice::from_ref(&quota), std::slice::from_ref(&limit_range)).is_none());
+
+        let info = analyze_namespace_quotas("team-a", &[], &[limit_range]).unwrap();
+        assert_eq!(info.namespace, "team-a");
+        assert!(!info.has_resource_quota);
+        assert!(info.has_limit_range);
+
+        let info = analyze_namespace_quotas("team-b", &[quota], &[]).unwrap();
+        assert!(info.has_resource_quota && !info.has_limit_range);
+        assert!(analyze_namespace_quotas("team-c", &[], &[]).is_some());
+    }
+}
@@ -0,0 +1,250 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use k8s_openapi::api::core::v1::Pod;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::types::{RightsizingRecommendation, WorkloadUsage};
+use super::base::{pod_workload, PodUsageMap};
+use super::pods::{sum_limits, sum_requests};
+
+const MAX_SAMPLES_PER_WORKLOAD: usize = 2000;
+const MAX_SAMPLE_AGE_DAYS: i64 = 14;
+const MIN_SAMPLES: usize = 12;
+const REQUEST_HEADROOM: f64 = 1.15;
+const MEMORY_LIMIT_HEADROOM: f64 = 1.25;
+/// Only recommend a change when the suggestion differs from the current value by more than this
+const MIN_RELATIVE_DEVIATION: f64 = 0.3;
+
+/// Per-workload usage samples persisted between runs
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UsageHistory {
+    pub workloads: BTreeMap<String, Vec<UsageSample>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageSample {
+    pub timestamp: DateTime<Utc>,
+    pub cpu_millicores: i64,
+    pub memory_bytes: i64,
+}
+
+impl UsageHistory {
+    /// Load history from `path`, starting empty when the file does not exist yet
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let data = std::fs::read(path)
+            .with_context(|| format!("Failed to read usage history {}", path.display()))?;
+        serde_json::from_slice(&data)
+            .with_context(|| format!("Invalid usage history {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec(self)?)
+            .with_context(|| format!("Failed to write usage history {}", tmp.display()))?;
+        std::fs::rename(&tmp, path)
+            .with_context(|| format!("Failed to replace usage history {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Append one sample per pod and drop samples beyond the retention limits
+    pub fn record(&mut self, usage: &[WorkloadUsage], now: DateTime<Utc>) {
+        for u in usage {
+            self.workloads
+                .entry(workload_key(u))
+                .or_default()
+                .push(UsageSample {
+                    timestamp: now,
+                    cpu_millicores: u.cpu_millicores,
+                    memory_bytes: u.memory_bytes,
+                });
+        }
+
+        let cutoff = now - Duration::days(MAX_SAMPLE_AGE_DAYS);
+        for samples in self.workloads.values_mut() {
+            samples.retain(|s| s.timestamp > cutoff);
+            if samples.len() > MAX_SAMPLES_PER_WORKLOAD {
+                let excess = samples.len() - MAX_SAMPLES_PER_WORKLOAD;
+                samples.drain(..excess);
+            }
+        }
+        self.workloads.retain(|_, samples| !samples.is_empty());
+    }
+}
+
+fn workload_key(u: &WorkloadUsage) -> String {
+    format!("{}/{}/{}", u.namespace, u.kind, u.workload)
+}
+
+/// Attribute current pod usage to the owning workloads; bare pods are skipped
+pub fn workload_usage_with_usage(
+    namespace: &str,
+    pods: &[Pod],
+    usage_by_pod: &PodUsageMap,
+) -> Vec<WorkloadUsage> {
+    let mut result = Vec::new();
+    for pod in pods {
+        let pod_name = match pod.metadata.name.as_ref() {
+            Some(n) => n.clone(),
+            None => continue,
+        };
+        let (kind, workload) = match pod_workload(pod) {
+            Some(w) => w,
+            None => continue,
+        };
+        if let Some(usage) = usage_by_pod.get(&(namespace.to_string(), pod_name)) {
+            result.push(WorkloadUsage {
+                namespace: namespace.to_string(),
+                kind,
+                workload,
+                cpu_millicores: usage.cpu_millicores,
+                memory_bytes: usage.memory_bytes,
+                requests: sum_requests(pod),
+                limits: sum_limits(pod),
+            });
+        }
+    }
+    result
+}
+
+/// Compute p95-based recommendations for workloads that are running now
+pub fn recommend(history: &UsageHistory, current: &[WorkloadUsage]) -> Vec<RightsizingRecommendation> {
+    let mut seen = std::collections::HashSet::new();
+    let mut recommendations = Vec::new();
+
+    for u in current {
+        let key = workload_key(u);
+        if !seen.insert(key.clone()) {
+            continue;
+        }
+        let samples = match history.workloads.get(&key) {
+            Some(s) if s.len() >= MIN_SAMPLES => s,
+            _ => continue,
+        };
+
+        let mut cpu: Vec<i64> = samples.iter().map(|s| s.cpu_millicores).collect();
+        let mut mem: Vec<i64> = samples.iter().map(|s| s.memory_bytes).collect();
+        let suggested_cpu_request = scale(percentile(&mut cpu, 0.95), REQUEST_HEADROOM);
+        let suggested_memory_request = scale(percentile(&mut mem, 0.95), REQUEST_HEADROOM);
+        let suggested_memory_limit = scale(mem.iter().copied().max().unwrap_or(0), MEMORY_LIMIT_HEADROOM);
+
+        let needs_change = deviates(u.requests.cpu_millicores, suggested_cpu_request)
+            || deviates(u.requests.memory_bytes, suggested_memory_request)
+            || deviates(u.limits.memory_bytes, suggested_memory_limit);
+        if !needs_change {
+            continue;
+        }
+
+        recommendations.push(RightsizingRecommendation {
+            namespace: u.namespace.clone(),
+            kind: u.kind.clone(),
+            workload: u.workload.clone(),
+            samples: samples.len(),
+            current_cpu_request: u.requests.cpu_millicores,
+            suggested_cpu_request,
+            current_memory_request: u.requests.memory_bytes,
+            suggested_memory_request,
+            current_memory_limit: u.limits.memory_bytes,
+            suggested_memory_limit,
+        });
+    }
+    recommendations
+}
+
+/// Record this run's usage into the history file and return recommendations
+pub fn update_recommendations(path: &Path, usage: &[WorkloadUsage]) -> Result<Vec<RightsizingRecommendation>> {
+    let mut history = UsageHistory::load(path)?;
+    history.record(usage, Utc::now());
+    history.save(path)?;
+    Ok(recommend(&history, usage))
+}
+
+fn percentile(values: &mut [i64], p: f64) -> i64 {
+    if values.is_empty() {
+        return 0;
+    }
+    values.sort_unstable();
+    let rank = ((p * values.len() as f64).ceil() as usize).clamp(1, values.len());
+    values[rank - 1]
+}
+
+fn scale(value: i64, factor: f64) -> i64 {
+    (value as f64 * factor).round() as i64
+}
+
+fn deviates(current: Option<i64>, suggested: i64) -> bool {
+    match current {
+        Some(c) if c > 0 => ((suggested - c).abs() as f64 / c as f64) > MIN_RELATIVE_DEVIATION,
+        _ => suggested > 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PodRequestTotals;
+
+    fn usage(cpu: i64, mem: i64, cpu_req: Option<i64>, mem_req: Option<i64>) -> WorkloadUsage {
+        WorkloadUsage {
+            namespace: "default".to_string(),
+            kind: "Deployment".to_string(),
+            workload: "api".to_string(),
+            cpu_millicores: cpu,
+            memory_bytes: mem,
+            requests: PodRequestTotals { cpu_millicores: cpu_req, memory_bytes: mem_req },
+            limits: PodRequestTotals { cpu_millicores: None, memory_bytes: mem_req.map(|m| m * 2) },
+        }
+    }
+
+    #[test]
+    fn test_percentile() {
+        let mut values: Vec<i64> = (1..=100).collect();
+        assert_eq!(percentile(&mut values, 0.95), 95);
+        assert_eq!(percentile(&mut values, 0.5), 50);
+        assert_eq!(percentile(&mut [], 0.95), 0);
+        assert_eq!(percentile(&mut [7], 0.95), 7);
+    }
+
+    #[test]
+    fn test_recommend_requires_min_samples() {
+        let mut history = UsageHistory::default();
+        let current = vec![usage(100, 100 * 1024 * 1024, Some(1000), Some(1024 * 1024 * 1024))];
+        for _ in 0..MIN_SAMPLES - 1 {
+            history.record(&current, Utc::now());
+        }
+        assert!(recommend(&history, &current).is_empty());
+
+        history.record(&current, Utc::now());
+        let recs = recommend(&history, &current);
+        assert_eq!(recs.len(), 1);
+        assert_eq!(recs[0].samples, MIN_SAMPLES);
+        assert_eq!(recs[0].suggested_cpu_request, 115);
+        assert_eq!(recs[0].current_cpu_request, Some(1000));
+    }
+
+    #[test]
+    fn test_recommend_skips_well_sized_workloads() {
+        let mut history = UsageHistory::default();
+        // Requests already match p95 usage plus headroom
+        let current = vec![usage(1000, 1000, Some(1150), Some(1150))];
+        for _ in 0..MIN_SAMPLES {
+            history.record(&current, Utc::now());
+        }
+        let mut well_sized = current.clone();
+        well_sized[0].limits.memory_bytes = Some(1250);
+        assert!(recommend(&history, &well_sized).is_empty());
+    }
+
+    #[test]
+    fn test_record_prunes_old_samples() {
+        let mut history = UsageHistory::default();
+        let current = vec![usage(100, 100, None, None)];
+        history.record(&current, Utc::now() - Duration::days(MAX_SAMPLE_AGE_DAYS + 1));
+        history.record(&current, Utc::now());
+        assert_eq!(history.workloads["default/Deployment/api"].len(), 1);
+    }
+}
@@ -0,0 +1,313 @@
+use k8s_openapi::api::core::v1::{Container, Pod, PodSecurityContext, PodSpec};
+
+use crate::types::{Config, PodSecurityLevel, PodSecurityViolationInfo};
+use super::base::pod_workload;
+
+/// Capabilities the baseline profile lets containers add
+const BASELINE_CAPABILITIES: &[&str] = &[
+    "AUDIT_WRITE", "CHOWN", "DAC_OVERRIDE", "FOWNER", "FSETID", "KILL", "MKNOD",
+    "NET_BIND_SERVICE", "SETFCAP", "SETGID", "SETPCAP", "SETUID", "SYS_CHROOT",
+];
+
+/// Sysctls the baseline profile considers namespaced and safe
+const SAFE_SYSCTLS: &[&str] = &[
+    "kernel.shm_rmid_forced", "net.ipv4.ip_local_port_range", "net.ipv4.ip_unprivileged_port_start",
+    "net.ipv4.tcp_syncookies", "net.ipv4.ping_group_range", "net.ipv4.ip_local_reserved_ports",
+    "net.ipv4.tcp_keepalive_time", "net.ipv4.tcp_fin_timeout", "net.ipv4.tcp_keepalive_intvl",
+    "net.ipv4.tcp_keepalive_probes",
+];
+
+/// Volume types (pod spec field names) the restricted profile allows
+const RESTRICTED_VOLUME_TYPES: &[&str] = &[
+    "configMap", "csi", "downwardAPI", "emptyDir", "ephemeral", "persistentVolumeClaim", "projected", "secret",
+];
+
+/// Profile checked in `namespace`: its `POD_SECURITY_NAMESPACE_LEVELS` entry, else `POD_SECURITY_LEVEL`
+pub fn namespace_level(cfg: &Config, namespace: &str) -> PodSecurityLevel {
+    cfg.pod_security_namespace_levels
+        .get(namespace)
+        .copied()
+        .unwrap_or(cfg.pod_security_level)
+}
+
+/// Check pods against the Pod Security Standards profile of the namespace. Replicas
+/// share a spec, so findings are reported once per workload.
+pub fn analyze_pod_security_with_pods(
+    namespace: &str,
+    cfg: &Config,
+    pods: &[Pod],
+) -> Vec<PodSecurityViolationInfo> {
+    let level = namespace_level(cfg, namespace);
+    if level == PodSecurityLevel::Privileged {
+        return Vec::new();
+    }
+    let mut findings: Vec<PodSecurityViolationInfo> = Vec::new();
+
+    for pod in pods {
+        let (Some(pod_name), Some(spec)) = (pod.metadata.name.as_ref(), pod.spec.as_ref()) else {
+            continue;
+        };
+        let workload = pod_workload(pod)
+            .map(|(kind, name)| format!("{}/{}", kind, name))
+            .unwrap_or_else(|| format!("Pod/{}", pod_name));
+        if findings.iter().any(|f| f.workload == workload) {
+            continue;
+        }
+        let violations = pod_security_violations(spec, level);
+        if !violations.is_empty() {
+            findings.push(PodSecurityViolationInfo {
+                namespace: namespace.to_string(),
+                workload,
+                level,
+                violations,
+            });
+        }
+    }
+    findings
+}
+
+/// Checks of the `level` profile the pod spec fails, e.g. `hostNetwork` or `privileged (app)`
+pub fn pod_security_violations(spec: &PodSpec, level: PodSecurityLevel) -> Vec<String> {
+    if level == PodSecurityLevel::Privileged {
+        return Vec::new();
+    }
+    let pod_sc = spec.security_context.as_ref();
+    let mut violations = Vec::new();
+
+    for (enabled, field) in [(spec.host_network, "hostNetwork"), (spec.host_pid, "hostPID"), (spec.host_ipc, "hostIPC")] {
+        if enabled == Some(true) {
+            violations.push(field.to_string());
+        }
+    }
+    let volumes = spec.volumes.as_deref().unwrap_or_default();
+    let host_paths: Vec<&str> = volumes.iter().filter(|v| v.host_path.is_some()).map(|v| v.name.as_str()).collect();
+    if !host_paths.is_empty() {
+        violations.push(format!("hostPath volumes ({})", host_paths.join(", ")));
+    }
+    for sysctl in pod_sc.and_then(|sc| sc.sysctls.as_ref()).into_iter().flatten() {
+        if !SAFE_SYSCTLS.contains(&sysctl.name.as_str()) {
+            violations.push(format!("unsafe sysctl {}", sysctl.name));
+        }
+    }
+    if level == PodSecurityLevel::Restricted {
+        // Any other volume source (hostPath is already reported above)
+        for volume in volumes.iter().filter(|v| v.host_path.is_none()) {
+            let source = serde_json::to_value(volume).ok().and_then(|v| {
+                v.as_object()?.keys().find(|k| *k != "name").cloned()
+            });
+            if let Some(source) = source.filter(|s| !RESTRICTED_VOLUME_TYPES.contains(&s.as_str())) {
+                violations.push(format!("{} volume {}", source, volume.name));
+            }
+        }
+    }
+
+    let containers = spec.init_containers.iter().flatten().chain(&spec.containers);
+    for container in containers {
+        for violation in container_violations(container, pod_sc, level) {
+            violations.push(format!("{} ({})", violation, container.name));
+        }
+    }
+    violations
+}
+
+fn container_violations(container: &Container, pod_sc: Option<&PodSecurityContext>, level: PodSecurityLevel) -> Vec<String> {
+    let sc = container.security_context.as_ref();
+    let mut violations = Vec::new();
+
+    if sc.and_then(|sc| sc.privileged) == Some(true) {
+        violations.push("privileged".to_string());
+    }
+    let allowed_capabilities = match level {
+        PodSecurityLevel::Restricted => &["NET_BIND_SERVICE"][..],
+        _ => BASELINE_CAPABILITIES,
+    };
+    let capabilities = sc.and_then(|sc| sc.capabilities.as_ref());
+    for capability in capabilities.and_then(|c| c.add.as_ref()).into_iter().flatten() {
+        if !allowed_capabilities.contains(&capability.as_str()) {
+            violations.push(format!("adds capability {}", capability));
+        }
+    }
+    for port in container.ports.iter().flatten() {
+        if let Some(host_port) = port.host_port.filter(|p| *p != 0) {
+            violations.push(format!("hostPort {}", host_port));
+        }
+    }
+    if let Some(proc_mount) = sc.and_then(|sc| sc.proc_mount.as_deref()).filter(|m| *m != "Default") {
+        violations.push(format!("procMount {}", proc_mount));
+    }
+    // Container settings override the pod-level ones
+    let seccomp = sc
+        .and_then(|sc| sc.seccomp_profile.as_ref())
+        .or_else(|| pod_sc.and_then(|sc| sc.seccomp_profile.as_ref()))
+        .map(|p| p.type_.as_str());
+    if seccomp == Some("Unconfined") {
+        violations.push("seccomp Unconfined".to_string());
+    }
+    if level < PodSecurityLevel::Restricted {
+        return violations;
+    }
+
+    if sc.and_then(|sc| sc.allow_privilege_escalation) != Some(false) {
+        violations.push("allowPrivilegeEscalation not false".to_string());
+    }
+    let run_as_non_root = sc.and_then(|sc| sc.run_as_non_root).or_else(|| pod_sc.and_then(|sc| sc.run_as_non_root));
+    if run_as_non_root != Some(true) {
+        violations.push("runAsNonRoot not true".to_string());
+    }
+    let run_as_user = sc.and_then(|sc| sc.run_as_user).or_else(|| pod_sc.and_then(|sc| sc.run_as_user));
+    if run_as_user == Some(0) {
+        violations.push("runAsUser 0".to_string());
+    }
+    if seccomp.is_none() {
+        violations.push("seccomp profile unset".to_string());
+    }
+    let drops_all = capabilities
+        .and_then(|c| c.drop.as_ref())
+        .is_some_and(|drop| drop.iter().any(|d| d == "ALL"));
+    if !drops_all {
+        violations.push("does not drop ALL capabilities".to_string());
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::{
+        Capabilities, ContainerPort, HostPathVolumeSource, NFSVolumeSource, SeccompProfile, SecurityContext, Volume,
+    };
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ObjectMeta, OwnerReference};
+
+    /// Container passing the restricted profile
+    fn hardened_container(name: &str) -> Container {
+        Container {
+            name: name.to_string(),
+            security_context: Some(SecurityContext {
+                allow_privilege_escalation: Some(false),
+                run_as_non_root: Some(true),
+                seccomp_profile: Some(SeccompProfile { type_: "RuntimeDefault".to_string(), ..Default::default() }),
+                capabilities: Some(Capabilities { drop: Some(vec!["ALL".to_string()]), ..Default::default() }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_baseline_violations() {
+        let mut privileged = Container { name: "agent".to_string(), ..Default::default() };
+        privileged.security_context = Some(SecurityContext {
+            privileged: Some(true),
+            capabilities: Some(Capabilities {
+                add: Some(vec!["NET_BIND_SERVICE".to_string(), "SYS_ADMIN".to_string()]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        privileged.ports = Some(vec![ContainerPort { container_port: 80, host_port: Some(8080), ..Default::default() }]);
+        let spec = PodSpec {
+            host_network: Some(true),
+            containers: vec![privileged, Container { name: "app".to_string(), ..Default::default() }],
+            volumes: Some(vec![Volume {
+                name: "docker-sock".to_string(),
+                host_path: Some(HostPathVolumeSource { path: "/var/run/docker.sock".to_string(), ..Default::default() }),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            pod_security_violations(&spec, PodSecurityLevel::Baseline),
+            vec![
+                "hostNetwork",
+                "hostPath volumes (docker-sock)",
+                "privileged (agent)",
+                "adds capability SYS_ADMIN (agent)",
+                "hostPort 8080 (agent)",
+            ]
+        );
+        assert!(pod_security_violations(&spec, PodSecurityLevel::Privileged).is_empty());
+        // A plain container is fine under baseline
+        let plain = PodSpec { containers: vec![Container { name: "app".to_string(), ..Default::default() }], ..Default::default() };
+        assert!(pod_security_violations(&plain, PodSecurityLevel::Baseline).is_empty());
+    }
+
+    #[test]
+    fn test_restricted_violations() {
+        let spec = PodSpec { containers: vec![hardened_container("app")], ..Default::default() };
+        assert!(pod_security_violations(&spec, PodSecurityLevel::Restricted).is_empty());
+
+        let plain = PodSpec {
+            containers: vec![Container { name: "app".to_string(), ..Default::default() }],
+            volumes: Some(vec![Volume {
+                name: "shared".to_string(),
+                nfs: Some(NFSVolumeSource { path: "/export".to_string(), server: "nfs".to_string(), ..Default::default() }),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+        assert_eq!(
+            pod_security_violations(&plain, PodSecurityLevel::Restricted),
+            vec![
+                "nfs volume shared",
+                "allowPrivilegeEscalation not false (app)",
+                "runAsNonRoot not true (app)",
+                "seccomp profile unset (app)",
+                "does not drop ALL capabilities (app)",
+            ]
+        );
+
+        // Pod-level runAsNonRoot and seccomp cover the containers
+        let mut container = hardened_container("app");
+        let sc = container.security_context.as_mut().unwrap();
+        sc.run_as_non_root = None;
+        sc.seccomp_profile = None;
+        let spec = PodSpec {
+            containers: vec![container],
+            security_context: Some(PodSecurityContext {
+                run_as_non_root: Some(true),
+                seccomp_profile: Some(SeccompProfile { type_: "RuntimeDefault".to_string(), ..Default::default() }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(pod_security_violations(&spec, PodSecurityLevel::Restricted).is_empty());
+    }
+
+    #[test]
+    fn test_analyze_pod_security_per_namespace_level() {
+        let pods: Vec<Pod> = (0..2)
+            .map(|i| Pod {
+                metadata: ObjectMeta {
+                    name: Some(format!("api-abc12-{}", i)),
+                    owner_references: Some(vec![OwnerReference {
+                        kind: "ReplicaSet".to_string(),
+                        name: "api-abc12".to_string(),
+                        controller: Some(true),
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                },
+                spec: Some(PodSpec {
+                    containers: vec![Container { name: "app".to_string(), ..Default::default() }],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+            .collect();
+        let config = Config {
+            pod_security_level: PodSecurityLevel::Baseline,
+            pod_security_namespace_levels: [("payments".to_string(), PodSecurityLevel::Restricted)].into(),
+            ..Default::default()
+        };
+
+        assert!(analyze_pod_security_with_pods("default", &config, &pods).is_empty());
+        let findings = analyze_pod_security_with_pods("payments", &config, &pods);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].workload, "Deployment/api");
+        assert_eq!(findings[0].level, PodSecurityLevel::Restricted);
+        assert_eq!(findings[0].violations.len(), 4);
+
+        assert!(analyze_pod_security_with_pods("payments", &Config::default(), &pods).is_empty());
+    }
+}
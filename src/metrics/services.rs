@@ -0,0 +1,447 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use k8s_openapi::api::core::v1::{Pod, Service};
+use k8s_openapi::api::networking::v1::Ingress;
+use kube::{api::ListParams, Api, Client};
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+
+use crate::types::{BlastRadius, Config, DualStackIssueInfo, ReadinessFlapInfo, ServiceEndpointChurnInfo};
+use super::base::pod_workload;
+
+/// List Services in a namespace
+pub async fn list_namespace_services(client: &Client, namespace: &str) -> Result<Vec<Service>> {
+    let service_api: Api<Service> = Api::namespaced(client.clone(), namespace);
+    Ok(service_api.list(&ListParams::default()).await?.items)
+}
+
+/// List Ingresses in a namespace
+pub async fn list_namespace_ingresses(client: &Client, namespace: &str) -> Result<Vec<Ingress>> {
+    let ingress_api: Api<Ingress> = Api::namespaced(client.clone(), namespace);
+    Ok(ingress_api.list(&ListParams::default()).await?.items)
+}
+
+/// Whether a Service's selector matches a pod; Services without a selector have
+/// manually managed endpoints and match nothing
+fn selects(service: &Service, pod: &Pod) -> bool {
+    match service.spec.as_ref().and_then(|s| s.selector.as_ref()) {
+        Some(selector) if !selector.is_empty() => {
+            let labels = pod.metadata.labels.as_ref();
+            selector.iter().all(|(k, v)| labels.and_then(|l| l.get(k)) == Some(v))
+        }
+        _ => false,
+    }
+}
+
+/// Estimate endpoint membership changes per Service within the window and report
+/// Services above the threshold.
+///
+/// A backing pod adds or removes an endpoint when it is created, when its Ready
+/// condition changes, and on every readiness probe failure streak; the latter are
+/// taken from the readiness flapping findings, so both reports line up.
+pub fn analyze_endpoint_churn_with_pods(
+    namespace: &str,
+    cfg: &Config,
+    services: &[Service],
+    pods: &[Pod],
+    readiness_flapping: &[ReadinessFlapInfo],
+    now: DateTime<Utc>,
+) -> Vec<ServiceEndpointChurnInfo> {
+    let cutoff = now - Duration::minutes(cfg.endpoint_churn_window_minutes);
+    let mut churning = Vec::new();
+
+    for service in services {
+        let service_name = match service.metadata.name.as_ref() {
+            Some(n) => n.clone(),
+            None => continue,
+        };
+        let backing: Vec<&Pod> = pods.iter().filter(|p| selects(service, p)).collect();
+        if backing.is_empty() {
+            continue;
+        }
+
+        let mut changes = 0i64;
+        let mut flapping_pods = Vec::new();
+        for pod in &backing {
+            let pod_name = pod.metadata.name.as_deref().unwrap_or_default();
+            let created_in_window = pod
+                .metadata
+                .creation_timestamp
+                .as_ref()
+                .is_some_and(|t| t.0 > cutoff);
+            let ready_changed_in_window = pod
+                .status
+                .as_ref()
+                .and_then(|s| s.conditions.as_ref())
+                .and_then(|conditions| conditions.iter().find(|c| c.type_ == "Ready"))
+                .and_then(|c| c.last_transition_time.as_ref())
+                .is_some_and(|t| t.0 > cutoff);
+            if created_in_window || ready_changed_in_window {
+                changes += 1;
+            }
+            if let Some(flap) = readiness_flapping.iter().find(|f| f.pod == pod_name) {
+                changes += flap.probe_failures;
+                flapping_pods.push(pod_name.to_string());
+            }
+        }
+
+        if changes >= cfg.endpoint_churn_threshold {
+            churning.push(ServiceEndpointChurnInfo {
+                namespace: namespace.to_string(),
+                service: service_name,
+                backing_pods: backing.len(),
+                changes,
+                flapping_pods,
+                window_minutes: cfg.endpoint_churn_window_minutes,
+            });
+        }
+    }
+    churning
+}
+
+/// Services selecting each pod and the Ingresses with a backend on one of them, by pod
+/// name; pods nothing routes to are left out. An Ingress counts as public when its load
+/// balancer has a routable IP, or a hostname other than the `internal-` names cloud
+/// providers give private load balancers.
+pub fn analyze_blast_radius(services: &[Service], ingresses: &[Ingress], pods: &[Pod]) -> BTreeMap<String, BlastRadius> {
+    let mut radius = BTreeMap::new();
+    for pod in pods {
+        let Some(pod_name) = pod.metadata.name.as_ref() else {
+            continue;
+        };
+        let mut selecting: Vec<String> = services
+            .iter()
+            .filter(|s| selects(s, pod))
+            .filter_map(|s| s.metadata.name.clone())
+            .collect();
+        selecting.sort();
+        if selecting.is_empty() {
+            continue;
+        }
+        let mut pod_radius = BlastRadius { services: selecting, ..Default::default() };
+        for ingress in ingresses {
+            let Some(name) = ingress.metadata.name.as_ref() else {
+                continue;
+            };
+            if !ingress_services(ingress).any(|s| pod_radius.services.iter().any(|selecting| selecting == s)) {
+                continue;
+            }
+            if is_public_ingress(ingress) {
+                pod_radius.public_ingresses.push(name.clone());
+            } else {
+                pod_radius.internal_ingresses.push(name.clone());
+            }
+        }
+        pod_radius.public_ingresses.sort();
+        pod_radius.internal_ingresses.sort();
+        radius.insert(pod_name.clone(), pod_radius);
+    }
+    radius
+}
+
+/// Services an Ingress sends traffic to, from its default backend and its rules
+fn ingress_services(ingress: &Ingress) -> impl Iterator<Item = &str> {
+    let spec = ingress.spec.as_ref();
+    let default_backend = spec.and_then(|s| s.default_backend.as_ref());
+    let rule_backends = spec
+        .and_then(|s| s.rules.as_ref())
+        .into_iter()
+        .flatten()
+        .filter_map(|r| r.http.as_ref())
+        .flat_map(|http| http.paths.iter().map(|p| &p.backend));
+    default_backend
+        .into_iter()
+        .chain(rule_backends)
+        .filter_map(|b| b.service.as_ref().map(|s| s.name.as_str()))
+}
+
+fn is_public_ingress(ingress: &Ingress) -> bool {
+    ingress
+        .status
+        .as_ref()
+        .and_then(|s| s.load_balancer.as_ref())
+        .and_then(|lb| lb.ingress.as_ref())
+        .into_iter()
+        .flatten()
+        .any(|address| match (address.ip.as_deref(), address.hostname.as_deref()) {
+            (Some(ip), _) => ip.parse::<IpAddr>().is_ok_and(is_public_ip),
+            (None, Some(hostname)) => !hostname.starts_with("internal-"),
+            (None, None) => false,
+        })
+}
+
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            // 100.64.0.0/10 is carrier-grade NAT space
+            let shared = v4.octets()[0] == 100 && v4.octets()[1] & 0xc0 == 64;
+            !(v4.is_private() || v4.is_loopback() || v4.is_link_local() || v4.is_unspecified() || shared)
+        }
+        IpAddr::V6(v6) => {
+            // fc00::/7 unique local, fe80::/10 link-local
+            let first = v6.segments()[0];
+            !(v6.is_loopback() || v6.is_unspecified() || first & 0xfe00 == 0xfc00 || first & 0xffc0 == 0xfe80)
+        }
+    }
+}
+
+/// `IPv4`/`IPv6` families missing from a set of addresses; unparsable addresses are ignored
+fn missing_families<'a>(addresses: impl IntoIterator<Item = &'a str>) -> Vec<&'static str> {
+    let (mut v4, mut v6) = (false, false);
+    for address in addresses {
+        match address.parse::<IpAddr>() {
+            Ok(IpAddr::V4(_)) => v4 = true,
+            Ok(IpAddr::V6(_)) => v6 = true,
+            Err(_) => {}
+        }
+    }
+    let mut missing = Vec::new();
+    if !v4 {
+        missing.push("IPv4");
+    }
+    if !v6 {
+        missing.push("IPv6");
+    }
+    missing
+}
+
+/// Find partial dual-stack setups in a cluster configured for both families: Services
+/// asking for PreferDualStack/RequireDualStack that got cluster IPs of one family only
+/// (typically a single-stack service CIDR on the API server), and running pods with a
+/// single pod IP (a node without a podCIDR of the other family, or a CNI not assigning
+/// it). Pods are reported once per workload and family. Headless and ExternalName
+/// Services, SingleStack Services and hostNetwork pods are skipped.
+pub fn analyze_dual_stack_with_pods(namespace: &str, services: &[Service], pods: &[Pod]) -> Vec<DualStackIssueInfo> {
+    let mut issues: Vec<DualStackIssueInfo> = Vec::new();
+
+    for service in services {
+        let (Some(name), Some(spec)) = (service.metadata.name.as_ref(), service.spec.as_ref()) else {
+            continue;
+        };
+        if !matches!(spec.ip_family_policy.as_deref(), Some("PreferDualStack" | "RequireDualStack")) {
+            continue;
+        }
+        let cluster_ips = spec.cluster_ips.clone().unwrap_or_default();
+        if cluster_ips.is_empty() || cluster_ips.iter().any(|ip| ip == "None") {
+            continue;
+        }
+        for family in missing_families(cluster_ips.iter().map(String::as_str)) {
+            issues.push(DualStackIssueInfo {
+                namespace: namespace.to_string(),
+                object: format!("Service/{}", name),
+                missing_family: family.to_string(),
+                addresses: cluster_ips.clone(),
+                pods: 0,
+            });
+        }
+    }
+
+    for pod in pods {
+        let Some(pod_name) = pod.metadata.name.as_ref() else {
+            continue;
+        };
+        if pod.spec.as_ref().and_then(|s| s.host_network).unwrap_or(false) {
+            continue;
+        }
+        let Some(status) = pod.status.as_ref().filter(|s| s.phase.as_deref() == Some("Running")) else {
+            continue;
+        };
+        let pod_ips: Vec<String> = status.pod_ips.iter().flatten().filter_map(|p| p.ip.clone()).collect();
+        if pod_ips.is_empty() {
+            continue;
+        }
+        let object = pod_workload(pod)
+            .map(|(kind, name)| format!("{}/{}", kind, name))
+            .unwrap_or_else(|| format!("Pod/{}", pod_name));
+
+        for family in missing_families(pod_ips.iter().map(String::as_str)) {
+            match issues.iter_mut().find(|i| i.object == object && i.missing_family == family) {
+                Some(existing) => existing.pods += 1,
+                None => issues.push(DualStackIssueInfo {
+                    namespace: namespace.to_string(),
+                    object: object.clone(),
+                    missing_family: family.to_string(),
+                    addresses: pod_ips.clone(),
+                    pods: 1,
+                }),
+            }
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::{PodCondition, PodIP, PodStatus, ServiceSpec};
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ObjectMeta, Time};
+    use std::collections::BTreeMap;
+
+    fn labels(app: &str) -> BTreeMap<String, String> {
+        [("app".to_string(), app.to_string())].into()
+    }
+
+    fn service(name: &str, app: &str) -> Service {
+        Service {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
+            spec: Some(ServiceSpec {
+                selector: Some(labels(app)),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn pod(name: &str, app: &str, created: DateTime<Utc>, ready_since: DateTime<Utc>) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                labels: Some(labels(app)),
+                creation_timestamp: Some(Time(created)),
+                ..Default::default()
+            },
+            status: Some(PodStatus {
+                conditions: Some(vec![PodCondition {
+                    type_: "Ready".to_string(),
+                    status: "True".to_string(),
+                    last_transition_time: Some(Time(ready_since)),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_analyze_endpoint_churn() {
+        let now = Utc::now();
+        let cfg = Config {
+            endpoint_churn_window_minutes: 60,
+            endpoint_churn_threshold: 5,
+            ..Default::default()
+        };
+        let old = now - Duration::hours(6);
+        let services = vec![service("api", "api"), service("web", "web"), service("orphan", "none")];
+        let pods = vec![
+            pod("api-0", "api", old, now - Duration::minutes(3)),
+            pod("api-1", "api", now - Duration::minutes(20), now - Duration::minutes(19)),
+            pod("web-0", "web", old, old),
+        ];
+        let flapping = vec![ReadinessFlapInfo {
+            namespace: "default".to_string(),
+            pod: "api-0".to_string(),
+            probe_failures: 6,
+            currently_ready: true,
+            last_transition: None,
+            window_minutes: 60,
+        }];
+
+        let churning = analyze_endpoint_churn_with_pods("default", &cfg, &services, &pods, &flapping, now);
+        assert_eq!(churning.len(), 1);
+        assert_eq!(churning[0].service, "api");
+        assert_eq!(churning[0].backing_pods, 2);
+        // api-0 Ready transition + 6 probe failures, api-1 created in the window
+        assert_eq!(churning[0].changes, 8);
+        assert_eq!(churning[0].flapping_pods, vec!["api-0".to_string()]);
+    }
+
+    #[test]
+    fn test_analyze_blast_radius() {
+        use k8s_openapi::api::networking::v1::{
+            HTTPIngressPath, HTTPIngressRuleValue, IngressBackend, IngressLoadBalancerIngress, IngressLoadBalancerStatus,
+            IngressRule, IngressServiceBackend, IngressSpec, IngressStatus,
+        };
+
+        let backend = |service: &str| IngressBackend {
+            service: Some(IngressServiceBackend { name: service.to_string(), port: None }),
+            ..Default::default()
+        };
+        let ingress = |name: &str, service: &str, ip: Option<&str>, hostname: Option<&str>| Ingress {
+            metadata: ObjectMeta { name: Some(name.to_string()), ..Default::default() },
+            spec: Some(IngressSpec {
+                rules: Some(vec![IngressRule {
+                    http: Some(HTTPIngressRuleValue {
+                        paths: vec![HTTPIngressPath {
+                            backend: backend(service),
+                            path: Some("/".to_string()),
+                            path_type: "Prefix".to_string(),
+                        }],
+                    }),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            status: Some(IngressStatus {
+                load_balancer: Some(IngressLoadBalancerStatus {
+                    ingress: Some(vec![IngressLoadBalancerIngress {
+                        ip: ip.map(str::to_string),
+                        hostname: hostname.map(str::to_string),
+                        ..Default::default()
+                    }]),
+                }),
+            }),
+        };
+        let now = Utc::now();
+        let services = vec![service("api", "api"), service("api-metrics", "api"), service("web", "web")];
+        let ingresses = vec![
+            ingress("public", "api", Some("203.0.113.10"), None),
+            ingress("elb", "api", None, Some("a1b2.eu-west-1.elb.amazonaws.com")),
+            ingress("private", "api-metrics", Some("10.0.0.5"), None),
+            ingress("internal-elb", "api", None, Some("internal-a1b2.eu-west-1.elb.amazonaws.com")),
+            ingress("other", "web", Some("203.0.113.11"), None),
+        ];
+        let pods = vec![pod("api-0", "api", now, now), pod("batch-0", "batch", now, now)];
+
+        let radius = analyze_blast_radius(&services, &ingresses, &pods);
+        assert_eq!(radius.len(), 1);
+        assert_eq!(
+            radius["api-0"],
+            BlastRadius {
+                services: vec!["api".to_string(), "api-metrics".to_string()],
+                public_ingresses: vec!["elb".to_string(), "public".to_string()],
+                internal_ingresses: vec!["internal-elb".to_string(), "private".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_analyze_dual_stack() {
+        let now = Utc::now();
+        let dual_stack = |name: &str, policy: &str, ips: &[&str]| {
+            let mut svc = service(name, name);
+            let spec = svc.spec.as_mut().unwrap();
+            spec.ip_family_policy = Some(policy.to_string());
+            spec.cluster_ips = Some(ips.iter().map(|ip| ip.to_string()).collect());
+            svc
+        };
+        let services = vec![
+            dual_stack("api", "RequireDualStack", &["10.96.0.10", "fd00:10:96::a"]),
+            dual_stack("web", "PreferDualStack", &["10.96.0.11"]),
+            dual_stack("legacy", "SingleStack", &["10.96.0.12"]),
+            dual_stack("headless", "PreferDualStack", &["None"]),
+        ];
+        let running = |name: &str, ips: &[&str]| {
+            let mut pod = pod(name, "web", now, now);
+            let status = pod.status.as_mut().unwrap();
+            status.phase = Some("Running".to_string());
+            status.pod_ips = Some(ips.iter().map(|ip| PodIP { ip: Some(ip.to_string()) }).collect());
+            pod
+        };
+        let pods = vec![
+            running("web-0", &["10.244.1.5", "fd00:10:244:1::5"]),
+            running("web-1", &["10.244.2.7"]),
+            running("web-2", &["10.244.2.8"]),
+        ];
+
+        let issues = analyze_dual_stack_with_pods("default", &services, &pods);
+        let summary: Vec<(&str, &str, usize)> =
+            issues.iter().map(|i| (i.object.as_str(), i.missing_family.as_str(), i.pods)).collect();
+        assert_eq!(summary, vec![("Service/web", "IPv6", 0), ("Pod/web-1", "IPv6", 1), ("Pod/web-2", "IPv6", 1)]);
+        assert_eq!(issues[0].addresses, vec!["10.96.0.11".to_string()]);
+        assert_eq!(issues[1].addresses, vec!["10.244.2.7".to_string()]);
+    }
+}
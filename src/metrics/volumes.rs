@@ -1,8 +1,14 @@
 use anyhow::Result;
-use k8s_openapi::api::core::v1::Pod;
+use chrono::Utc;
+use k8s_openapi::api::core::v1::{Event, PersistentVolumeClaim, Pod};
 use kube::{api::ListParams, Api, Client};
 
-use crate::types::{VolumeIssueInfo, VolumeIssueType};
+use crate::types::{Config, VolumeIssueInfo, VolumeIssueType, VolumeSchedulingInfo, VolumeSchedulingReason};
+use super::base::pod_status_time;
+use super::pods::is_pending_over_grace;
+
+/// Annotation set by the scheduler once it picks a node for a WaitForFirstConsumer claim
+const SELECTED_NODE_ANNOTATION: &str = "volume.kubernetes.io/selected-node";
 
 /// Analyze volume issues (high usage and mount failures)
 pub async fn analyze_volume_issues(
@@ -40,6 +46,106 @@ pub async fn analyze_volume_issues(
     Ok(volume_issues)
 }
 
+/// List PersistentVolumeClaims in a namespace
+pub async fn list_namespace_pvcs(client: &Client, namespace: &str) -> Result<Vec<PersistentVolumeClaim>> {
+    let pvc_api: Api<PersistentVolumeClaim> = Api::namespaced(client.clone(), namespace);
+    Ok(pvc_api.list(&ListParams::default()).await?.items)
+}
+
+/// Detect pending pods blocked on their volumes: node affinity conflicts of bound
+/// volumes and WaitForFirstConsumer claims that cannot be bound or provisioned
+pub fn analyze_volume_scheduling_with_pods(
+    namespace: &str,
+    cfg: &Config,
+    pods: &[Pod],
+    events: &[Event],
+    pvcs: &[PersistentVolumeClaim],
+) -> Vec<VolumeSchedulingInfo> {
+    let mut blocked = Vec::new();
+
+    for pod in pods {
+        let pod_name = match pod.metadata.name.as_ref() {
+            Some(n) => n.clone(),
+            None => continue,
+        };
+        if !is_pending_over_grace(pod, cfg.pending_grace_minutes) {
+            continue;
+        }
+        let claims = pod_claims(pod);
+        if claims.is_empty() {
+            continue;
+        }
+
+        let message = latest_failed_scheduling_message(events, &pod_name);
+        let reason = match message.as_deref().and_then(classify_scheduling_message) {
+            Some(reason) => reason,
+            None => {
+                let provisioning_stuck = pvcs.iter().any(|pvc| {
+                    pvc.metadata.name.as_ref().is_some_and(|n| claims.contains(n))
+                        && pvc.status.as_ref().and_then(|s| s.phase.as_deref()) == Some("Pending")
+                        && pvc
+                            .metadata
+                            .annotations
+                            .as_ref()
+                            .is_some_and(|a| a.contains_key(SELECTED_NODE_ANNOTATION))
+                });
+                if !provisioning_stuck {
+                    continue;
+                }
+                VolumeSchedulingReason::ProvisioningStuck
+            }
+        };
+
+        blocked.push(VolumeSchedulingInfo {
+            namespace: namespace.to_string(),
+            pod: pod_name,
+            claims,
+            reason,
+            since: pod_status_time(pod).unwrap_or_else(Utc::now),
+            message,
+        });
+    }
+    blocked
+}
+
+fn pod_claims(pod: &Pod) -> Vec<String> {
+    pod.spec
+        .as_ref()
+        .and_then(|s| s.volumes.as_ref())
+        .map(|volumes| {
+            volumes
+                .iter()
+                .filter_map(|v| v.persistent_volume_claim.as_ref().map(|c| c.claim_name.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn latest_failed_scheduling_message(events: &[Event], pod_name: &str) -> Option<String> {
+    events
+        .iter()
+        .filter(|e| {
+            e.reason.as_deref() == Some("FailedScheduling")
+                && e.involved_object.kind.as_deref() == Some("Pod")
+                && e.involved_object.name.as_deref() == Some(pod_name)
+        })
+        .max_by_key(|e| e.last_timestamp.as_ref().map(|t| t.0))
+        .and_then(|e| e.message.clone())
+}
+
+/// Map scheduler messages to volume-related reasons; other failures are not ours to report
+fn classify_scheduling_message(message: &str) -> Option<VolumeSchedulingReason> {
+    if message.contains("volume node affinity conflict") {
+        Some(VolumeSchedulingReason::NodeAffinityConflict)
+    } else if message.contains("didn't find available persistent volumes to bind")
+        || message.contains("unbound immediate PersistentVolumeClaims")
+    {
+        Some(VolumeSchedulingReason::NoMatchingVolume)
+    } else {
+        None
+    }
+}
+
 fn extract_mount_failures(pod: &Pod) -> Option<Vec<(String, String)>> {
     let mut mount_failures = Vec::new();
     
@@ -134,6 +240,93 @@ mod tests {
         assert!(mount_failures.is_none());
     }
 
+    fn pending_pod_with_claim(name: &str, claim: &str) -> Pod {
+        use k8s_openapi::api::core::v1::{PersistentVolumeClaimVolumeSource, PodSpec, Volume};
+        use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+
+        let mut pod = create_test_pod(name);
+        pod.metadata.creation_timestamp = Some(Time(Utc::now() - chrono::Duration::minutes(30)));
+        pod.spec = Some(PodSpec {
+            volumes: Some(vec![Volume {
+                name: "data".to_string(),
+                persistent_volume_claim: Some(PersistentVolumeClaimVolumeSource {
+                    claim_name: claim.to_string(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        });
+        pod.status = Some(PodStatus {
+            phase: Some("Pending".to_string()),
+            ..Default::default()
+        });
+        pod
+    }
+
+    fn failed_scheduling_event(pod: &str, message: &str) -> Event {
+        Event {
+            reason: Some("FailedScheduling".to_string()),
+            message: Some(message.to_string()),
+            involved_object: k8s_openapi::api::core::v1::ObjectReference {
+                kind: Some("Pod".to_string()),
+                name: Some(pod.to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_analyze_volume_scheduling_from_events() {
+        let cfg = Config::default();
+        let pods = vec![
+            pending_pod_with_claim("db-0", "data-db-0"),
+            pending_pod_with_claim("cache-0", "data-cache-0"),
+            pending_pod_with_claim("web-0", "data-web-0"),
+        ];
+        let events = vec![
+            failed_scheduling_event("db-0", "0/3 nodes are available: 3 node(s) had volume node affinity conflict."),
+            failed_scheduling_event("cache-0", "0/3 nodes are available: 3 node(s) didn't find available persistent volumes to bind."),
+            failed_scheduling_event("web-0", "0/3 nodes are available: 3 Insufficient cpu."),
+        ];
+
+        let blocked = analyze_volume_scheduling_with_pods("default", &cfg, &pods, &events, &[]);
+        assert_eq!(blocked.len(), 2);
+        assert_eq!(blocked[0].pod, "db-0");
+        assert_eq!(blocked[0].reason, VolumeSchedulingReason::NodeAffinityConflict);
+        assert_eq!(blocked[0].claims, vec!["data-db-0".to_string()]);
+        assert_eq!(blocked[1].reason, VolumeSchedulingReason::NoMatchingVolume);
+    }
+
+    #[test]
+    fn test_analyze_volume_scheduling_provisioning_stuck() {
+        use k8s_openapi::api::core::v1::PersistentVolumeClaimStatus;
+
+        let cfg = Config::default();
+        let pods = vec![pending_pod_with_claim("db-0", "data-db-0")];
+        let pvc = PersistentVolumeClaim {
+            metadata: ObjectMeta {
+                name: Some("data-db-0".to_string()),
+                annotations: Some([(SELECTED_NODE_ANNOTATION.to_string(), "node-1".to_string())].into()),
+                ..Default::default()
+            },
+            status: Some(PersistentVolumeClaimStatus {
+                phase: Some("Pending".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let blocked = analyze_volume_scheduling_with_pods("default", &cfg, &pods, &[], &[pvc]);
+        assert_eq!(blocked.len(), 1);
+        assert_eq!(blocked[0].reason, VolumeSchedulingReason::ProvisioningStuck);
+        assert!(blocked[0].message.is_none());
+
+        // No events and no PVC information: nothing to attribute the pending state to
+        assert!(analyze_volume_scheduling_with_pods("default", &cfg, &pods, &[], &[]).is_empty());
+    }
+
     #[test]
     fn test_extract_mount_failures_multiple_containers() {
         let mut pod = create_test_pod("test-pod");
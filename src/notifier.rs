@@ -0,0 +1,475 @@
+use anyhow::{anyhow, Result};
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinSet;
+use tracing::{info, warn};
+
+use crate::csv_export::{render_csv, write_csv};
+use crate::delivery::PayloadSigner;
+use crate::email::{render_report_email, send_email};
+use crate::exporter::render_gauges;
+use crate::openmetrics::{render_openmetrics, write_textfile};
+use crate::output::{render_output, write_stdout};
+use crate::pagerduty::{pagerduty_events, send_pagerduty_events, PAGERDUTY_EVENTS_URL};
+use crate::pipeline::{deliver, outgoing_report, report_payload, should_send, Sink};
+use crate::pushgateway::{push_gauges, pushgateway_url};
+use crate::report::html::{render_html, write_html};
+use crate::report::HealthReport;
+use crate::slack_api::{post_threaded, SLACK_API_URL};
+use crate::telegram::{render_report_telegram, send_telegram_messages, TELEGRAM_API_URL};
+use crate::types::{EmailSettings, OutputFormat, PushgatewaySettings, SlackBotSettings, TelegramSettings};
+use crate::webhook::{report_document, send_report_document};
+
+/// Future returned by `Notifier::notify`; boxed so notifiers can be registered as trait objects
+pub type NotifyFuture<'a> = Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>>;
+
+/// A backend reports are sent through. Implementations decide themselves whether a report
+/// is worth sending (e.g. Slack skips reports without issues) and fail only when the run
+/// should fail.
+pub trait Notifier: Send + Sync {
+    /// Name used in logs, e.g. `slack`
+    fn name(&self) -> &str;
+
+    fn notify<'a>(&'a self, report: &'a HealthReport) -> NotifyFuture<'a>;
+}
+
+/// Slack webhooks: the configured channel plus the teams with their own webhook, each a
+/// sink with its own timeout and circuit breaker
+pub struct SlackNotifier {
+    sinks: Vec<Sink>,
+}
+
+impl SlackNotifier {
+    /// `sinks` must have their secret references resolved (see `pipeline::resolve_sinks`)
+    pub fn new(sinks: Vec<Sink>) -> Self {
+        Self { sinks }
+    }
+}
+
+impl Notifier for SlackNotifier {
+    fn name(&self) -> &str {
+        "slack"
+    }
+
+    fn notify<'a>(&'a self, report: &'a HealthReport) -> NotifyFuture<'a> {
+        Box::pin(deliver(report, &self.sinks))
+    }
+}
+
+/// The main report through the Slack Web API with a bot token: the summary as a message,
+/// the sections as replies in its thread
+pub struct SlackBotNotifier {
+    client: reqwest::Client,
+    channel: String,
+    /// `settings.token` with its secret reference resolved
+    token: String,
+    api_url: String,
+}
+
+impl SlackBotNotifier {
+    pub fn new(client: reqwest::Client, settings: &SlackBotSettings, token: String) -> Self {
+        Self { client, channel: settings.channel.clone(), token, api_url: SLACK_API_URL.to_string() }
+    }
+}
+
+impl Notifier for SlackBotNotifier {
+    fn name(&self) -> &str {
+        "slack-bot"
+    }
+
+    fn notify<'a>(&'a self, report: &'a HealthReport) -> NotifyFuture<'a> {
+        Box::pin(async move {
+            let Some(payload) = report_payload(report, None) else {
+                info!("No issues detected, skipping Slack notification");
+                return Ok(());
+            };
+            let deadline = Duration::from_secs(report.config.sink_timeout_seconds);
+            post_threaded(&self.client, &self.api_url, &self.token, &self.channel, payload, deadline).await?;
+            Ok(())
+        })
+    }
+}
+
+/// HTML email through an SMTP relay, for clusters that cannot reach Slack. It goes out
+/// on the same runs as the main Slack message (see `should_send`).
+pub struct EmailNotifier {
+    settings: EmailSettings,
+    /// `settings.smtp_password` with its secret reference resolved
+    password: Option<String>,
+}
+
+impl EmailNotifier {
+    pub fn new(settings: EmailSettings, password: Option<String>) -> Self {
+        Self { settings, password }
+    }
+}
+
+impl Notifier for EmailNotifier {
+    fn name(&self) -> &str {
+        "email"
+    }
+
+    fn notify<'a>(&'a self, report: &'a HealthReport) -> NotifyFuture<'a> {
+        Box::pin(async move {
+            let summary = report.summary();
+            if !should_send(report, &summary) {
+                info!("No issues detected, skipping email");
+                return Ok(());
+            }
+            let message = render_report_email(&outgoing_report(report, &summary), &summary);
+            let deadline = Duration::from_secs(report.config.sink_timeout_seconds);
+            send_email(&self.settings, self.password.as_deref(), &message, deadline).await?;
+            info!("Sent report email to {}", self.settings.to.join(", "));
+            Ok(())
+        })
+    }
+}
+
+/// MarkdownV2 messages from a Telegram bot; like email it goes out on the same runs as
+/// the main Slack message
+pub struct TelegramNotifier {
+    client: reqwest::Client,
+    chat_id: String,
+    /// `settings.bot_token` with its secret reference resolved
+    bot_token: String,
+    api_url: String,
+}
+
+impl TelegramNotifier {
+    pub fn new(client: reqwest::Client, settings: &TelegramSettings, bot_token: String) -> Self {
+        Self { client, chat_id: settings.chat_id.clone(), bot_token, api_url: TELEGRAM_API_URL.to_string() }
+    }
+}
+
+impl Notifier for TelegramNotifier {
+    fn name(&self) -> &str {
+        "telegram"
+    }
+
+    fn notify<'a>(&'a self, report: &'a HealthReport) -> NotifyFuture<'a> {
+        Box::pin(async move {
+            let summary = report.summary();
+            if !should_send(report, &summary) {
+                info!("No issues detected, skipping Telegram");
+                return Ok(());
+            }
+            let messages = render_report_telegram(&outgoing_report(report, &summary), &summary);
+            let deadline = Duration::from_secs(report.config.sink_timeout_seconds);
+            send_telegram_messages(&self.client, &self.api_url, &self.bot_token, &self.chat_id, &messages, deadline).await
+        })
+    }
+}
+
+/// PagerDuty incidents (Events API v2) for the findings of the paging-worthy sections;
+/// repeated runs update the open incidents through their dedup keys
+pub struct PagerDutyNotifier {
+    client: reqwest::Client,
+    /// Routing key with its secret reference resolved
+    routing_key: String,
+    sections: Vec<String>,
+    events_url: String,
+}
+
+impl PagerDutyNotifier {
+    pub fn new(client: reqwest::Client, routing_key: String, sections: Vec<String>) -> Self {
+        Self { client, routing_key, sections, events_url: PAGERDUTY_EVENTS_URL.to_string() }
+    }
+}
+
+impl Notifier for PagerDutyNotifier {
+    fn name(&self) -> &str {
+        "pagerduty"
+    }
+
+    fn notify<'a>(&'a self, report: &'a HealthReport) -> NotifyFuture<'a> {
+        Box::pin(async move {
+            let events = pagerduty_events(report, &self.routing_key, &self.sections);
+            if events.is_empty() {
+                info!("No paging-worthy findings, skipping PagerDuty");
+                return Ok(());
+            }
+            let deadline = Duration::from_secs(report.config.sink_timeout_seconds);
+            send_pagerduty_events(&self.client, &self.events_url, &events, deadline).await
+        })
+    }
+}
+
+/// The whole report as JSON to an arbitrary endpoint, for pipelines that do not speak
+/// Slack blocks. Unlike Slack it is sent every run, so receivers also learn about
+/// clean runs.
+pub struct GenericWebhookNotifier {
+    client: reqwest::Client,
+    /// URL with its secret reference resolved
+    url: String,
+    headers: Vec<(String, String)>,
+    signer: Option<PayloadSigner>,
+}
+
+impl GenericWebhookNotifier {
+    pub fn new(client: reqwest::Client, url: String, headers: Vec<(String, String)>, signer: Option<PayloadSigner>) -> Self {
+        Self { client, url, headers, signer }
+    }
+}
+
+impl Notifier for GenericWebhookNotifier {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    fn notify<'a>(&'a self, report: &'a HealthReport) -> NotifyFuture<'a> {
+        Box::pin(async move {
+            let document = report_document(report);
+            let deadline = Duration::from_secs(report.config.sink_timeout_seconds);
+            send_report_document(&self.client, &self.url, &self.headers, self.signer.as_ref(), &document, deadline).await?;
+            info!("Sent report document to generic webhook");
+            Ok(())
+        })
+    }
+}
+
+/// The report's gauges pushed to a Pushgateway every run, clean runs included, so
+/// resolved findings drop to zero there too
+pub struct PushgatewayNotifier {
+    client: reqwest::Client,
+    /// Base URL with its secret reference resolved
+    url: String,
+    job: String,
+    instance: Option<String>,
+}
+
+impl PushgatewayNotifier {
+    pub fn new(client: reqwest::Client, settings: &PushgatewaySettings, url: String) -> Self {
+        Self { client, url, job: settings.job.clone(), instance: settings.instance.clone() }
+    }
+}
+
+impl Notifier for PushgatewayNotifier {
+    fn name(&self) -> &str {
+        "pushgateway"
+    }
+
+    fn notify<'a>(&'a self, report: &'a HealthReport) -> NotifyFuture<'a> {
+        Box::pin(async move {
+            // Tenants' runs push their own group rather than replacing each other's
+            let instance = self.instance.as_deref().or(report.config.cluster_name.as_deref());
+            let labels: Vec<(&str, &str)> = [("instance", instance), ("tenant", report.config.tenant.as_deref())]
+                .into_iter()
+                .filter_map(|(name, value)| Some((name, value?)))
+                .collect();
+            let url = pushgateway_url(&self.url, &self.job, &labels)?;
+            let deadline = Duration::from_secs(report.config.sink_timeout_seconds);
+            push_gauges(&self.client, url, render_gauges(report), deadline).await?;
+            info!("Pushed the report's gauges to the Pushgateway (job {})", self.job);
+            Ok(())
+        })
+    }
+}
+
+/// The findings as OpenMetrics in a textfile, rewritten every run (clean runs included)
+/// so a textfile collector always exposes the latest state
+pub struct TextfileNotifier {
+    path: PathBuf,
+}
+
+impl TextfileNotifier {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl Notifier for TextfileNotifier {
+    fn name(&self) -> &str {
+        "textfile"
+    }
+
+    fn notify<'a>(&'a self, report: &'a HealthReport) -> NotifyFuture<'a> {
+        Box::pin(async move {
+            write_textfile(&self.path, &render_openmetrics(report, chrono::Utc::now()))?;
+            info!("Wrote OpenMetrics findings to {}", self.path.display());
+            Ok(())
+        })
+    }
+}
+
+/// The findings as CSV, one row per issue, written to a file or stdout every run
+pub struct CsvNotifier {
+    target: String,
+}
+
+impl CsvNotifier {
+    pub fn new(target: impl Into<String>) -> Self {
+        Self { target: target.into() }
+    }
+}
+
+impl Notifier for CsvNotifier {
+    fn name(&self) -> &str {
+        "csv"
+    }
+
+    fn notify<'a>(&'a self, report: &'a HealthReport) -> NotifyFuture<'a> {
+        Box::pin(async move {
+            write_csv(&self.target, &render_csv(report))?;
+            info!("Wrote CSV findings to {}", self.target);
+            Ok(())
+        })
+    }
+}
+
+/// The report as an HTML page, rewritten every run (clean runs included) so the published
+/// page always shows the latest state
+pub struct HtmlNotifier {
+    path: PathBuf,
+}
+
+impl HtmlNotifier {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl Notifier for HtmlNotifier {
+    fn name(&self) -> &str {
+        "html"
+    }
+
+    fn notify<'a>(&'a self, report: &'a HealthReport) -> NotifyFuture<'a> {
+        Box::pin(async move {
+            write_html(&self.path, &render_html(report))?;
+            info!("Wrote the HTML report to {}", self.path.display());
+            Ok(())
+        })
+    }
+}
+
+/// The whole report printed to stdout every run (`OUTPUT_FORMAT`)
+pub struct StdoutNotifier {
+    format: OutputFormat,
+}
+
+impl StdoutNotifier {
+    pub fn new(format: OutputFormat) -> Self {
+        Self { format }
+    }
+}
+
+impl Notifier for StdoutNotifier {
+    fn name(&self) -> &str {
+        "stdout"
+    }
+
+    fn notify<'a>(&'a self, report: &'a HealthReport) -> NotifyFuture<'a> {
+        Box::pin(async move { write_stdout(&render_output(report, self.format)?) })
+    }
+}
+
+/// The notifiers every report of a run goes to
+#[derive(Default, Clone)]
+pub struct NotifierRegistry {
+    notifiers: Vec<Arc<dyn Notifier>>,
+}
+
+impl NotifierRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, notifier: impl Notifier + 'static) -> Self {
+        self.register(notifier);
+        self
+    }
+
+    pub fn register(&mut self, notifier: impl Notifier + 'static) {
+        self.notifiers.push(Arc::new(notifier));
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.notifiers.iter().map(|n| n.name()).collect()
+    }
+
+    /// Send the report through every notifier concurrently. A failing notifier does not
+    /// hold back the others; the run fails afterwards naming the ones that failed.
+    pub async fn notify_all(&self, report: &HealthReport) -> Result<()> {
+        let report = Arc::new(report.clone());
+        let mut tasks = JoinSet::new();
+        for notifier in &self.notifiers {
+            let (notifier, report) = (notifier.clone(), report.clone());
+            tasks.spawn(async move {
+                let result = notifier.notify(&report).await;
+                (notifier.name().to_string(), result)
+            });
+        }
+
+        let mut failed = Vec::new();
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((name, Ok(()))) => info!("Notifier {} done", name),
+                Ok((name, Err(e))) => {
+                    warn!("Notifier {} failed: {:#}", name, e);
+                    failed.push(name);
+                }
+                Err(e) => {
+                    warn!("Notifier task failed: {}", e);
+                    failed.push("<aborted>".to_string());
+                }
+            }
+        }
+        if !failed.is_empty() {
+            failed.sort();
+            return Err(anyhow!("Notification failed for {}", failed.join(", ")));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Config;
+    use std::sync::Mutex;
+
+    struct Recording {
+        name: &'static str,
+        fail: bool,
+        seen: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Notifier for Recording {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn notify<'a>(&'a self, report: &'a HealthReport) -> NotifyFuture<'a> {
+            Box::pin(async move {
+                self.seen.lock().unwrap().push(format!("{}:{}", self.name, report.run_id));
+                if self.fail {
+                    return Err(anyhow!("endpoint down"));
+                }
+                Ok(())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_notify_all_runs_every_notifier() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let registry = NotifierRegistry::new()
+            .with(Recording { name: "slack", fail: false, seen: seen.clone() })
+            .with(Recording { name: "pagerduty", fail: true, seen: seen.clone() })
+            .with(Recording { name: "teams", fail: false, seen: seen.clone() });
+        assert_eq!(registry.names(), vec!["slack", "pagerduty", "teams"]);
+
+        let report = HealthReport::new(Config::default()).with_run_id("run-1".to_string());
+        let error = registry.notify_all(&report).await.unwrap_err();
+        assert_eq!(error.to_string(), "Notification failed for pagerduty");
+
+        let mut seen = seen.lock().unwrap().clone();
+        seen.sort();
+        assert_eq!(seen, vec!["pagerduty:run-1", "slack:run-1", "teams:run-1"]);
+
+        assert!(NotifierRegistry::new().notify_all(&report).await.is_ok());
+    }
+}
@@ -0,0 +1,113 @@
+//! Who is on call right now, from a PagerDuty or Opsgenie schedule (read-only APIs), so
+//! critical findings mention a person instead of a channel-wide group.
+
+use anyhow::{anyhow, Context, Result};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tokio::time::timeout;
+
+use crate::types::{OnCallProvider, OnCallSettings};
+
+pub const PAGERDUTY_API_URL: &str = "https://api.pagerduty.com";
+pub const OPSGENIE_API_URL: &str = "https://api.opsgenie.com";
+
+/// Person on call for the schedule
+#[derive(Debug, Clone, PartialEq)]
+pub struct OnCallUser {
+    /// Display name; Opsgenie's flat on-call list only gives the email
+    pub name: Option<String>,
+    pub email: String,
+}
+
+/// The schedule's current on-call; `None` when nobody is on call. `token` is the resolved
+/// `api_token`.
+pub async fn current_oncall(
+    client: &reqwest::Client,
+    settings: &OnCallSettings,
+    token: &str,
+    deadline: Duration,
+) -> Result<Option<OnCallUser>> {
+    let request = match settings.provider {
+        OnCallProvider::PagerDuty => {
+            let base = settings.api_url.as_deref().unwrap_or(PAGERDUTY_API_URL);
+            client
+                .get(format!("{}/oncalls", base.trim_end_matches('/')))
+                .query(&[("schedule_ids[]", settings.schedule_id.as_str()), ("include[]", "users"), ("earliest", "true")])
+                .header("Authorization", format!("Token token={}", token))
+                .header("Accept", "application/vnd.pagerduty+json;version=2")
+        }
+        OnCallProvider::Opsgenie => {
+            let base = settings.api_url.as_deref().unwrap_or(OPSGENIE_API_URL);
+            client
+                .get(format!("{}/v2/schedules/{}/on-calls", base.trim_end_matches('/'), settings.schedule_id))
+                .query(&[("scheduleIdentifierType", "id"), ("flat", "true")])
+                .header("Authorization", format!("GenieKey {}", token))
+        }
+    };
+    let response = timeout(deadline, request.send())
+        .await
+        .map_err(|_| anyhow!("On-call lookup timed out after {:?}", deadline))?
+        .context("Failed to reach the on-call API")?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(anyhow!("On-call API answered {} for schedule {}", status, settings.schedule_id));
+    }
+    let body: serde_json::Value = response.json().await.context("Invalid on-call API response")?;
+    Ok(match settings.provider {
+        OnCallProvider::PagerDuty => parse_pagerduty_oncalls(&body),
+        OnCallProvider::Opsgenie => parse_opsgenie_oncalls(&body),
+    })
+}
+
+/// Slack mention of `user`: `<@member>` when their email is mapped (the mention notifies
+/// them), else their name or email in bold
+pub fn slack_mention(user: &OnCallUser, slack_users: &BTreeMap<String, String>) -> String {
+    match slack_users.get(&user.email.to_lowercase()) {
+        Some(member) => format!("<@{}>", member),
+        None => format!("*{}*", user.name.as_deref().unwrap_or(&user.email)),
+    }
+}
+
+/// First responder of `GET /oncalls`: the user at the lowest escalation level
+fn parse_pagerduty_oncalls(body: &serde_json::Value) -> Option<OnCallUser> {
+    let oncall = body["oncalls"]
+        .as_array()?
+        .iter()
+        .filter(|o| o["user"]["email"].is_string())
+        .min_by_key(|o| o["escalation_level"].as_u64().unwrap_or(u64::MAX))?;
+    Some(OnCallUser {
+        name: oncall["user"]["name"].as_str().or(oncall["user"]["summary"].as_str()).map(str::to_string),
+        email: oncall["user"]["email"].as_str()?.to_string(),
+    })
+}
+
+/// First recipient of `GET /v2/schedules/{id}/on-calls?flat=true`
+fn parse_opsgenie_oncalls(body: &serde_json::Value) -> Option<OnCallUser> {
+    let email = body["data"]["onCallRecipients"].as_array()?.first()?.as_str()?;
+    Some(OnCallUser { name: None, email: email.to_string() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_oncalls() {
+        let pagerduty = json!({"oncalls": [
+            {"escalation_level": 2, "user": {"summary": "Joe Bloggs", "email": "joe@example.com"}},
+            {"escalation_level": 1, "user": {"name": "Jane Doe", "summary": "Jane Doe", "email": "Jane@example.com"}}
+        ]});
+        let jane = parse_pagerduty_oncalls(&pagerduty).unwrap();
+        assert_eq!(jane, OnCallUser { name: Some("Jane Doe".to_string()), email: "Jane@example.com".to_string() });
+        assert_eq!(parse_pagerduty_oncalls(&json!({"oncalls": []})), None);
+
+        let opsgenie = json!({"data": {"_parent": {"id": "sre"}, "onCallRecipients": ["joe@example.com"]}});
+        let joe = parse_opsgenie_oncalls(&opsgenie).unwrap();
+        assert_eq!(joe, OnCallUser { name: None, email: "joe@example.com".to_string() });
+
+        let slack_users = BTreeMap::from([("jane@example.com".to_string(), "U0123ABC".to_string())]);
+        assert_eq!(slack_mention(&jane, &slack_users), "<@U0123ABC>");
+        assert_eq!(slack_mention(&joe, &slack_users), "*joe@example.com*");
+    }
+}
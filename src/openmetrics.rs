@@ -0,0 +1,165 @@
+//! Findings as OpenMetrics series in a textfile, for clusters without a push path to
+//! Slack or a webhook: node_exporter's textfile collector (or any scraper reading the
+//! file) picks them up and alerting happens on the Prometheus side.
+
+use anyhow::{Context, Result};
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::report::HealthReport;
+use crate::types::REPORT_SECTIONS;
+use crate::webhook::report_document;
+
+/// Advisory sections of the report document that are not findings
+const ADVISORY_SECTIONS: &[&str] = &["recommendations", "previously_snoozed"];
+
+/// The report as an OpenMetrics exposition:
+/// - `kube_health_finding` (info): one series per finding, labelled with its section,
+///   namespace and name (node for cluster-wide findings)
+/// - `kube_health_section_state` (stateset): `ok` or `issues` per section
+/// - `kube_health_section_findings` (gauge): findings per section
+/// - `kube_health_last_run_timestamp_seconds` (gauge): when the report was collected
+///
+/// Every series carries the `cluster` label; sections are the ones of the webhook document.
+pub fn render_openmetrics(report: &HealthReport, now: chrono::DateTime<chrono::Utc>) -> String {
+    let cluster = escape_label(report.config.cluster_name.as_deref().unwrap_or_default());
+    let document = report_document(report);
+    let sections: Vec<(&str, usize)> = document["sections"]
+        .as_object()
+        .map(|sections| {
+            sections
+                .iter()
+                .filter(|(key, _)| REPORT_SECTIONS.contains(&key.as_str()) && !ADVISORY_SECTIONS.contains(&key.as_str()))
+                .map(|(key, findings)| {
+                    let count = match findings {
+                        serde_json::Value::Array(items) => items.len(),
+                        serde_json::Value::Null => 0,
+                        _ => 1,
+                    };
+                    (key.as_str(), count)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut findings: Vec<(String, String, String)> = report
+        .fingerprints()
+        .iter()
+        .map(|fingerprint| {
+            let mut parts = fingerprint.splitn(3, '/');
+            let mut next = || parts.next().unwrap_or_default().to_string();
+            (next(), next(), next())
+        })
+        .collect();
+    let nodes = &report.cluster_metrics;
+    let node_findings = [
+        ("nodes", nodes.problematic_nodes.iter().map(|n| n.name.clone()).collect::<Vec<_>>()),
+        ("node_flapping", nodes.flapping_nodes.iter().map(|n| n.name.clone()).collect()),
+        ("node_utilization", nodes.high_utilization_nodes.iter().map(|n| n.name.clone()).collect()),
+        ("node_disk", nodes.node_disk_forecast.iter().map(|n| n.name.clone()).collect()),
+    ];
+    for (section, names) in node_findings {
+        findings.extend(names.into_iter().map(|name| (section.to_string(), String::new(), name)));
+    }
+    findings.sort();
+    findings.dedup();
+
+    let mut out = String::new();
+    out.push_str("# TYPE kube_health_finding info\n");
+    out.push_str("# HELP kube_health_finding Finding of the last kube-health-reporter run.\n");
+    for (section, namespace, name) in &findings {
+        let _ = writeln!(
+            out,
+            "kube_health_finding_info{{cluster=\"{}\",section=\"{}\",namespace=\"{}\",name=\"{}\"}} 1",
+            cluster,
+            escape_label(section),
+            escape_label(namespace),
+            escape_label(name),
+        );
+    }
+    out.push_str("# TYPE kube_health_section_state stateset\n");
+    out.push_str("# HELP kube_health_section_state Whether a report section has findings.\n");
+    for (section, count) in &sections {
+        for state in ["ok", "issues"] {
+            let active = (state == "issues") == (*count > 0);
+            let _ = writeln!(
+                out,
+                "kube_health_section_state{{cluster=\"{}\",section=\"{}\",kube_health_section_state=\"{}\"}} {}",
+                cluster,
+                section,
+                state,
+                u8::from(active),
+            );
+        }
+    }
+    out.push_str("# TYPE kube_health_section_findings gauge\n");
+    out.push_str("# HELP kube_health_section_findings Findings in a report section.\n");
+    for (section, count) in &sections {
+        let _ = writeln!(out, "kube_health_section_findings{{cluster=\"{}\",section=\"{}\"}} {}", cluster, section, count);
+    }
+    out.push_str("# TYPE kube_health_last_run_timestamp_seconds gauge\n");
+    out.push_str("# UNIT kube_health_last_run_timestamp_seconds seconds\n");
+    out.push_str("# HELP kube_health_last_run_timestamp_seconds When the last report was collected.\n");
+    let _ = writeln!(out, "kube_health_last_run_timestamp_seconds{{cluster=\"{}\"}} {}", cluster, now.timestamp());
+    out.push_str("# EOF\n");
+    out
+}
+
+/// Replace `path` with `contents` through a temporary file in the same directory, so a
+/// scrape never reads a half-written file
+pub fn write_textfile(path: &Path, contents: &str) -> Result<()> {
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, contents).with_context(|| format!("Failed to write {}", tmp.display()))?;
+    std::fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))?;
+    Ok(())
+}
+
+pub(crate) fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Config, OomKilledInfo, ProblematicNodeInfo};
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_render_openmetrics() {
+        let config = Config { cluster_name: Some("prod".to_string()), ..Default::default() };
+        let mut report = HealthReport::new(config);
+        report.pod_metrics.oom_killed.push(OomKilledInfo {
+            namespace: "default".to_string(),
+            pod: "api-\"0\"".to_string(),
+            labels: Default::default(),
+            node_name: None,
+            zone: None,
+            blast_radius: None,
+            container: "main".to_string(),
+            last_oom_time: None,
+            restart_count: 3,
+            image: None,
+            log_tail: None,
+        });
+        report.cluster_metrics.problematic_nodes.push(ProblematicNodeInfo {
+            name: "node-a".to_string(),
+            os: "linux".to_string(),
+            conditions: vec!["MemoryPressure".to_string()],
+            since: chrono::Utc::now(),
+        });
+
+        let now = chrono::Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let text = render_openmetrics(&report, now);
+        assert!(text.contains(
+            "kube_health_finding_info{cluster=\"prod\",section=\"nodes\",namespace=\"\",name=\"node-a\"} 1\n"
+        ));
+        assert!(text.contains(
+            "kube_health_finding_info{cluster=\"prod\",section=\"oom\",namespace=\"default\",name=\"api-\\\"0\\\"\"} 1\n"
+        ));
+        assert!(text.contains("kube_health_section_state{cluster=\"prod\",section=\"oom\",kube_health_section_state=\"issues\"} 1\n"));
+        assert!(text.contains("kube_health_section_state{cluster=\"prod\",section=\"restarts\",kube_health_section_state=\"ok\"} 1\n"));
+        assert!(text.contains("kube_health_section_findings{cluster=\"prod\",section=\"oom\"} 1\n"));
+        assert!(!text.contains("section=\"recommendations\""));
+        assert!(text.ends_with("kube_health_last_run_timestamp_seconds{cluster=\"prod\"} 1767225600\n# EOF\n"));
+    }
+}
@@ -0,0 +1,54 @@
+//! The whole report printed to stdout in every run (`OUTPUT_FORMAT`), for piping into
+//! jq, archival and other tooling. The structure is that of the generic webhook's report
+//! document.
+
+use anyhow::{Context, Result};
+use std::io::Write;
+
+use crate::report::HealthReport;
+use crate::types::OutputFormat;
+
+/// The report in `format`, ending with a newline
+pub fn render_output(report: &HealthReport, format: OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Json => Ok(serde_json::to_string_pretty(report)? + "\n"),
+        OutputFormat::Yaml => Ok(serde_yaml::to_string(report)?),
+    }
+}
+
+pub fn write_stdout(contents: &str) -> Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    stdout.write_all(contents.as_bytes()).context("Failed to write the report to stdout")?;
+    stdout.flush().context("Failed to write the report to stdout")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Config, FailedJobInfo};
+
+    #[test]
+    fn test_render_output() {
+        let mut report = HealthReport::new(Config { cluster_name: Some("prod".to_string()), ..Default::default() });
+        report.job_metrics.failed_jobs.push(FailedJobInfo {
+            namespace: "batch".to_string(),
+            job: "nightly".to_string(),
+            failed_pods: 1,
+            last_failure_time: None,
+            reason: None,
+        });
+
+        let output = render_output(&report, OutputFormat::Json).unwrap();
+        assert!(output.ends_with("}\n"));
+        let document: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(document["cluster"], "prod");
+        assert_eq!(document["total_issues"], 1);
+        assert_eq!(document["sections"]["failed_jobs"][0]["job"], "nightly");
+
+        let output = render_output(&report, OutputFormat::Yaml).unwrap();
+        assert!(output.contains("cluster: prod\n"));
+        assert!(output.ends_with('\n'));
+        let parsed: serde_json::Value = serde_yaml::from_str(&output).unwrap();
+        assert_eq!(parsed["sections"], document["sections"]);
+    }
+}
@@ -0,0 +1,130 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::types::Scoped;
+
+/// Namespace/label → team mapping loaded from `OWNERSHIP_FILE` (JSON, typically a
+/// mounted ConfigMap). Rules are evaluated in order and the first match wins.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OwnershipMap {
+    #[serde(default)]
+    pub teams: BTreeMap<String, TeamContact>,
+    #[serde(default)]
+    pub rules: Vec<OwnershipRule>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TeamContact {
+    /// Free-form contact shown next to the team (Slack handle, email, ...)
+    pub contact: Option<String>,
+    /// Incoming webhook that receives a report with only this team's findings
+    pub slack_webhook_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OwnershipRule {
+    /// Exact namespace, or a prefix ending in `*`; unset matches every namespace
+    pub namespace: Option<String>,
+    /// Pod labels that must all match; keys are added to `ISSUE_LABELS` automatically
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    pub team: String,
+}
+
+impl OwnershipMap {
+    pub fn load(path: &Path) -> Result<Self> {
+        let data = std::fs::read(path)
+            .with_context(|| format!("Failed to read ownership file {}", path.display()))?;
+        serde_json::from_slice(&data)
+            .with_context(|| format!("Invalid ownership file {}", path.display()))
+    }
+
+    /// Team owning a finding, if any rule matches
+    pub fn team_for(&self, finding: &dyn Scoped) -> Option<&str> {
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(finding))
+            .map(|rule| rule.team.as_str())
+    }
+
+    /// Label keys the rules match on; findings must carry them for label rules to apply
+    pub fn label_keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.rules.iter().flat_map(|r| r.labels.keys().cloned()).collect();
+        keys.sort();
+        keys.dedup();
+        keys
+    }
+}
+
+impl OwnershipRule {
+    fn matches(&self, finding: &dyn Scoped) -> bool {
+        let namespace_matches = match self.namespace.as_deref() {
+            None => true,
+            Some(pattern) => match pattern.strip_suffix('*') {
+                Some(prefix) => finding.namespace().starts_with(prefix),
+                None => finding.namespace() == pattern,
+            },
+        };
+        let labels_match = self.labels.is_empty()
+            || finding
+                .labels()
+                .is_some_and(|labels| self.labels.iter().all(|(k, v)| labels.get(k) == Some(v)));
+        namespace_matches && labels_match
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{FailedJobInfo, PendingPodInfo};
+    use chrono::Utc;
+
+    const MAPPING: &str = r#"{
+        "teams": {
+            "payments": {"contact": "@payments-oncall", "slack_webhook_url": "https://hooks.slack.com/payments"},
+            "platform": {"contact": "platform@example.com"}
+        },
+        "rules": [
+            {"labels": {"team": "payments"}, "team": "payments"},
+            {"namespace": "payments-*", "team": "payments"},
+            {"namespace": "kube-system", "team": "platform"}
+        ]
+    }"#;
+
+    fn pending(namespace: &str, labels: &[(&str, &str)]) -> PendingPodInfo {
+        PendingPodInfo {
+            namespace: namespace.to_string(),
+            pod: "pod-0".to_string(),
+            labels: labels.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+            node_name: None,
+            zone: None,
+            blast_radius: None,
+            since: Utc::now(),
+            duration_minutes: 10,
+            image_pull_failures: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_team_for() {
+        let map: OwnershipMap = serde_json::from_str(MAPPING).unwrap();
+        assert_eq!(map.label_keys(), vec!["team".to_string()]);
+        assert_eq!(map.teams["payments"].contact.as_deref(), Some("@payments-oncall"));
+
+        assert_eq!(map.team_for(&pending("shared", &[("team", "payments")])), Some("payments"));
+        assert_eq!(map.team_for(&pending("payments-eu", &[])), Some("payments"));
+        assert_eq!(map.team_for(&pending("kube-system", &[("team", "other")])), Some("platform"));
+        assert_eq!(map.team_for(&pending("default", &[])), None);
+
+        let job = FailedJobInfo {
+            namespace: "payments-us".to_string(),
+            job: "settle".to_string(),
+            failed_pods: 1,
+            last_failure_time: None,
+            reason: None,
+        };
+        assert_eq!(map.team_for(&job), Some("payments"));
+    }
+}
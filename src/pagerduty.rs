@@ -0,0 +1,158 @@
+use anyhow::{anyhow, Context, Result};
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::timeout;
+use tracing::info;
+
+use crate::report::HealthReport;
+
+/// Events API v2 endpoint (EU service regions use events.eu.pagerduty.com)
+pub const PAGERDUTY_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+
+/// Sections of the namespaced findings, the ones with a fingerprint (see
+/// `HealthReport::fingerprints`) and so a stable dedup key
+pub const PAGEABLE_SECTIONS: &[&str] = &[
+    "usage", "restarts", "pending", "failed", "unready", "readiness_flapping", "endpoint_churn", "dual_stack",
+    "oom", "volume_scheduling", "missing_config", "pod_churn", "event_flood", "stuck_terminating",
+    "custom_resources", "resource_hygiene", "pod_security", "namespace_quotas", "limit_range_conflicts",
+    "stale_metrics", "failed_jobs", "job_backoff", "missed_cronjobs", "cronjob_concurrency", "finished_objects",
+    "volumes",
+];
+
+/// Events for the report's findings in the paging sections, plus those escalated by a
+/// change freeze: a trigger per finding and, with `NOTIFY_RESOLVED`, a resolve per
+/// finding that cleared since the last run. The dedup key is the finding's fingerprint
+/// (`section/namespace/name`) prefixed with the cluster, so every run updates the same
+/// incident instead of opening a new one.
+pub fn pagerduty_events(report: &HealthReport, routing_key: &str, sections: &[String]) -> Vec<serde_json::Value> {
+    let cfg = &report.config;
+    let cluster = cfg.cluster_name.as_deref().unwrap_or("default");
+    let pages = |fingerprint: &str| {
+        let section = fingerprint.split('/').next().unwrap_or_default();
+        sections.iter().any(|s| s == section) || report.is_escalated(fingerprint)
+    };
+    let dedup_key = |fingerprint: &str| {
+        let mut key = format!("kube-health-reporter/{}/{}", cluster, fingerprint);
+        // The Events API rejects longer keys
+        if key.len() > 255 {
+            let mut end = 255;
+            while !key.is_char_boundary(end) {
+                end -= 1;
+            }
+            key.truncate(end);
+        }
+        key
+    };
+
+    let mut events = Vec::new();
+    for fingerprint in report.fingerprints().iter().filter(|f| pages(f)) {
+        let mut parts = fingerprint.splitn(3, '/');
+        let (section, namespace, name) = (
+            parts.next().unwrap_or_default(),
+            parts.next().unwrap_or_default(),
+            parts.next().unwrap_or_default(),
+        );
+        events.push(json!({
+            "routing_key": routing_key,
+            "event_action": "trigger",
+            "dedup_key": dedup_key(fingerprint),
+            "payload": {
+                "summary": format!("[{}] {}: {}/{}", cluster, section, namespace, name),
+                "source": cluster,
+                "severity": "critical",
+                "component": format!("{}/{}", namespace, name),
+                "group": namespace,
+                "class": section,
+                "custom_details": { "fingerprint": fingerprint, "run_id": report.run_id },
+            },
+        }));
+    }
+    for fingerprint in report.resolved.keys().filter(|f| pages(f)) {
+        events.push(json!({
+            "routing_key": routing_key,
+            "event_action": "resolve",
+            "dedup_key": dedup_key(fingerprint),
+        }));
+    }
+    events
+}
+
+/// Send the events one by one (the API takes a single event per request); each must be
+/// accepted within `deadline`
+pub async fn send_pagerduty_events(
+    client: &reqwest::Client,
+    url: &str,
+    events: &[serde_json::Value],
+    deadline: Duration,
+) -> Result<()> {
+    for event in events {
+        let request = client.post(url).json(event).send();
+        let response = timeout(deadline, request)
+            .await
+            .map_err(|_| anyhow!("PagerDuty timed out after {:?}", deadline))?
+            .context("Failed to reach PagerDuty")?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("PagerDuty rejected {} event {}: {} {}", event["event_action"], event["dedup_key"], status, body));
+        }
+    }
+    info!("Sent {} PagerDuty events", events.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ChangeFreezeInfo, Config, FailedJobInfo, IssueHistory, OomKilledInfo};
+    use chrono::Utc;
+
+    #[test]
+    fn test_pagerduty_events() {
+        let config = Config { cluster_name: Some("prod".to_string()), ..Default::default() };
+        let mut report = HealthReport::new(config).with_run_id("run-1".to_string());
+        report.pod_metrics.oom_killed.push(OomKilledInfo {
+            namespace: "payments".to_string(),
+            pod: "api-0".to_string(),
+            labels: Default::default(),
+            node_name: None,
+            zone: None,
+            blast_radius: None,
+            container: "app".to_string(),
+            last_oom_time: None,
+            restart_count: 3,
+            image: None,
+            log_tail: None,
+        });
+        report.job_metrics.failed_jobs.push(FailedJobInfo {
+            namespace: "batch".to_string(),
+            job: "nightly".to_string(),
+            failed_pods: 1,
+            last_failure_time: None,
+            reason: None,
+        });
+        let history = IssueHistory { first_seen: Utc::now(), last_seen: Utc::now(), occurrences: 2 };
+        report.resolved.insert("oom/payments/api-1".to_string(), history.clone());
+        report.resolved.insert("failed_jobs/batch/weekly".to_string(), history);
+
+        // Failed jobs are not paging-worthy here
+        let events = pagerduty_events(&report, "R0UT1NG", &["oom".to_string()]);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0]["event_action"], "trigger");
+        assert_eq!(events[0]["routing_key"], "R0UT1NG");
+        assert_eq!(events[0]["dedup_key"], "kube-health-reporter/prod/oom/payments/api-0");
+        assert_eq!(events[0]["payload"]["summary"], "[prod] oom: payments/api-0");
+        assert_eq!(events[0]["payload"]["severity"], "critical");
+        assert_eq!(events[1]["event_action"], "resolve");
+        assert_eq!(events[1]["dedup_key"], "kube-health-reporter/prod/oom/payments/api-1");
+
+        // Unless the job failed during a change freeze
+        report.change_freeze = Some(ChangeFreezeInfo {
+            escalated: ["failed_jobs/batch/nightly".to_string()].into(),
+            ..Default::default()
+        });
+        let events = pagerduty_events(&report, "R0UT1NG", &["oom".to_string()]);
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0]["dedup_key"], "kube-health-reporter/prod/failed_jobs/batch/nightly");
+    }
+}
@@ -1,75 +1,196 @@
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
+use tracing::warn;
+
 use crate::types::{PodUsageTotals, PodRequestTotals};
 
-pub fn parse_cpu_to_millicores(q: &str) -> Option<i64> {
-    let q = q.trim();
-    if q.is_empty() {
-        return None;
+/// Kubernetes resource quantity (`resource.Quantity`): a signed decimal number with an
+/// optional binary-SI (`Ki`..`Ei`), decimal-SI (`n`..`E`) or exponent (`e3`, `E-2`)
+/// suffix, kept exact as `mantissa * 10^exp10 * 2^exp2`
+#[derive(Debug, Clone, Copy)]
+pub struct Quantity {
+    mantissa: i128,
+    exp10: i32,
+    exp2: u32,
+}
+
+impl Quantity {
+    /// Value in base units (cores, bytes), truncated towards zero; `None` when it does
+    /// not fit an i64
+    pub fn value(&self) -> Option<i64> {
+        self.scaled(0)
     }
-    if let Some(stripped) = q.strip_suffix('n') {
-        if let Ok(nanos) = stripped.parse::<i128>() {
-            return Some((nanos / 1_000_000) as i64);
-        }
-    } else if let Some(stripped) = q.strip_suffix('u') {
-        if let Ok(micros) = stripped.parse::<i128>() {
-            return Some((micros / 1_000) as i64);
-        }
-    } else if let Some(stripped) = q.strip_suffix('m') {
-        if let Ok(mc) = stripped.parse::<i64>() {
-            return Some(mc);
-        }
-    } else {
-        // treat as cores; can be integer or float
-        if let Ok(cores) = q.parse::<f64>() {
-            return Some((cores * 1000.0).round() as i64);
+
+    /// Value in thousandths of the base unit (millicores), truncated towards zero
+    pub fn millis(&self) -> Option<i64> {
+        self.scaled(3)
+    }
+
+    fn scaled(&self, shift: i32) -> Option<i64> {
+        let exp10 = self.exp10.checked_add(shift)?;
+        let mut n = self.mantissa.checked_mul(2i128.checked_pow(self.exp2)?)?;
+        if exp10 >= 0 {
+            n = n.checked_mul(10i128.checked_pow(exp10.unsigned_abs())?)?;
+        } else {
+            // Anything below 10^-38 of the unit truncates to zero anyway
+            n = 10i128.checked_pow(exp10.unsigned_abs()).map_or(0, |d| n / d);
         }
+        i64::try_from(n).ok()
     }
-    None
 }
 
-pub fn parse_memory_to_bytes(q: &str) -> Option<i64> {
+/// Parse a quantity such as `250m`, `1.5Gi`, `129e6` or `+1k`; `None` when malformed
+pub fn parse_quantity(q: &str) -> Option<Quantity> {
     let q = q.trim();
-    if q.is_empty() {
+    let (negative, rest) = match q.as_bytes().first()? {
+        b'+' => (false, &q[1..]),
+        b'-' => (true, &q[1..]),
+        _ => (false, q),
+    };
+    let number_end = rest.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(rest.len());
+    let (number, suffix) = rest.split_at(number_end);
+    let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if (integer.is_empty() && fraction.is_empty()) || fraction.contains('.') {
         return None;
     }
 
-    // Order matters: check binary suffixes first (Ki, Mi, ...), then decimal (K, M, ...)
-    const BINARY_UNITS: &[(&str, i64)] = &[
-        ("Ki", 1024),
-        ("Mi", 1024 * 1024),
-        ("Gi", 1024 * 1024 * 1024),
-        ("Ti", 1024_i64.pow(4)),
-        ("Pi", 1024_i64.pow(5)),
-        ("Ei", 1024_i64.pow(6)),
-    ];
-    const DECIMAL_UNITS: &[(&str, i64)] = &[
-        ("K", 1000),
-        ("M", 1000 * 1000),
-        ("G", 1000 * 1000 * 1000),
-        ("T", 1000_i64.pow(4)),
-        ("P", 1000_i64.pow(5)),
-        ("E", 1000_i64.pow(6)),
-        ("k", 1000),
-    ];
-
-    for (suf, mul) in BINARY_UNITS {
-        if let Some(stripped) = q.strip_suffix(suf) {
-            if let Ok(v) = stripped.parse::<f64>() {
-                return Some((v * (*mul as f64)).round() as i64);
+    let mut mantissa: i128 = 0;
+    for digit in integer.bytes().chain(fraction.bytes()) {
+        mantissa = mantissa.checked_mul(10)?.checked_add((digit - b'0') as i128)?;
+    }
+    let mut exp10 = -(fraction.len() as i32);
+    let mut exp2 = 0;
+    match suffix {
+        "" => {}
+        "n" => exp10 -= 9,
+        "u" => exp10 -= 6,
+        "m" => exp10 -= 3,
+        // Kubernetes only knows lowercase k; K shows up in hand-written specs
+        "k" | "K" => exp10 += 3,
+        "M" => exp10 += 6,
+        "G" => exp10 += 9,
+        "T" => exp10 += 12,
+        "P" => exp10 += 15,
+        "E" => exp10 += 18,
+        "Ki" => exp2 = 10,
+        "Mi" => exp2 = 20,
+        "Gi" => exp2 = 30,
+        "Ti" => exp2 = 40,
+        "Pi" => exp2 = 50,
+        "Ei" => exp2 = 60,
+        // Decimal exponent, e.g. e3 or E-2 (a lone E is exa, above)
+        exponent => {
+            let exponent = exponent.strip_prefix(['e', 'E'])?;
+            if !exponent.trim_start_matches(['+', '-']).bytes().all(|b| b.is_ascii_digit()) {
+                return None;
             }
+            exp10 = exp10.checked_add(exponent.parse::<i32>().ok()?)?;
         }
     }
-    for (suf, mul) in DECIMAL_UNITS {
-        if let Some(stripped) = q.strip_suffix(suf) {
-            if let Ok(v) = stripped.parse::<f64>() {
-                return Some((v * (*mul as f64)).round() as i64);
-            }
+    Some(Quantity { mantissa: if negative { -mantissa } else { mantissa }, exp10, exp2 })
+}
+
+pub fn parse_cpu_to_millicores(q: &str) -> Option<i64> {
+    let millicores = parse_quantity(q).and_then(|q| q.millis());
+    if millicores.is_none() && !q.trim().is_empty() {
+        warn!("Ignoring unparseable CPU quantity '{}'", q);
+    }
+    millicores
+}
+
+pub fn parse_memory_to_bytes(q: &str) -> Option<i64> {
+    let bytes = parse_quantity(q).and_then(|q| q.value());
+    if bytes.is_none() && !q.trim().is_empty() {
+        warn!("Ignoring unparseable memory quantity '{}'", q);
+    }
+    bytes
+}
+
+/// Millicores as an exact Kubernetes quantity, so `parse_cpu_to_millicores` gives them
+/// back: `250m` below a core or for odd values, whole or decimal cores otherwise (`2`,
+/// `1.25`)
+pub fn format_cpu(millicores: i64) -> String {
+    let sign = if millicores < 0 { "-" } else { "" };
+    let abs = millicores.unsigned_abs();
+    if abs < 1000 {
+        return format!("{}m", millicores);
+    }
+    let fraction = format!("{:03}", abs % 1000);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        format!("{}{}", sign, abs / 1000)
+    } else {
+        format!("{}{}.{}", sign, abs / 1000, fraction)
+    }
+}
+
+/// Bytes as an exact Kubernetes quantity, so `parse_memory_to_bytes` gives them back:
+/// the shortest of the largest binary suffix with at most three exact decimals (`1.5Gi`,
+/// `512Mi`) and a whole decimal suffix (`129M`); plain bytes when neither fits
+pub fn format_memory(bytes: i64) -> String {
+    const BINARY: &[(&str, u32)] = &[("Ei", 60), ("Pi", 50), ("Ti", 40), ("Gi", 30), ("Mi", 20), ("Ki", 10)];
+    const DECIMAL: &[(&str, u32)] = &[("E", 18), ("P", 15), ("T", 12), ("G", 9), ("M", 6), ("k", 3)];
+    let sign = if bytes < 0 { "-" } else { "" };
+    let abs = bytes.unsigned_abs();
+
+    let binary = BINARY.iter().find_map(|(suffix, shift)| {
+        let unit = 1u64 << shift;
+        let remainder = (abs % unit) as u128;
+        (abs >= unit && (remainder * 1000).is_multiple_of(unit as u128)).then(|| {
+            let fraction = format!("{:03}", remainder * 1000 / unit as u128);
+            let fraction = fraction.trim_end_matches('0');
+            let dot = if fraction.is_empty() { "" } else { "." };
+            format!("{}{}{}{}{}", sign, abs / unit, dot, fraction, suffix)
+        })
+    });
+    let decimal = DECIMAL.iter().find_map(|(suffix, exp)| {
+        let unit = 10u64.pow(*exp);
+        (abs >= unit && abs.is_multiple_of(unit)).then(|| format!("{}{}{}", sign, abs / unit, suffix))
+    });
+    match (binary, decimal) {
+        (Some(b), Some(d)) if d.len() < b.len() => d,
+        (Some(b), _) => b,
+        (None, Some(d)) => d,
+        (None, None) => bytes.to_string(),
+    }
+}
+
+/// Bytes with the largest binary suffix that keeps the value at or above 1, e.g. `1.5Gi`
+/// or `512Mi` (at most one decimal); plain bytes below 1Ki
+pub fn humanize_bytes(bytes: i64) -> String {
+    const UNITS: &[&str] = &["Ki", "Mi", "Gi", "Ti", "Pi", "Ei"];
+    let mut value = bytes as f64;
+    let mut unit = "";
+    for next in UNITS {
+        if value.abs() < 1024.0 {
+            break;
         }
+        value /= 1024.0;
+        unit = next;
+    }
+    format!("{}{}", trim_decimals(value, 1), unit)
+}
+
+/// Millicores as Kubernetes writes them below a core (`250m`), as cores above
+/// (`1.25 cores`, at most two decimals)
+pub fn humanize_millicores(millicores: i64) -> String {
+    if millicores.abs() < 1000 {
+        return format!("{}m", millicores);
+    }
+    let cores = trim_decimals(millicores as f64 / 1000.0, 2);
+    if cores == "1" {
+        "1 core".to_string()
+    } else {
+        format!("{} cores", cores)
     }
-    // bytes without suffix
-    if let Ok(v) = q.parse::<i64>() {
-        return Some(v);
+}
+
+fn trim_decimals(value: f64, decimals: usize) -> String {
+    let text = format!("{:.*}", decimals, value);
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        text
     }
-    None
 }
 
 pub fn compute_utilization_percentages(usage: &PodUsageTotals, req: &PodRequestTotals) -> (Option<f64>, Option<f64>) {
@@ -91,9 +212,143 @@ pub fn any_exceeds(cpu_pct: Option<f64>, mem_pct: Option<f64>, threshold: f64) -
     }
 }
 
+/// Standard five-field cron schedule (minute hour day-of-month month day-of-week),
+/// as accepted by CronJob `spec.schedule`; evaluated in UTC
+#[derive(Debug, Clone)]
+pub struct CronSchedule {
+    minutes: Vec<bool>,
+    hours: Vec<bool>,
+    days_of_month: Vec<bool>,
+    months: Vec<bool>,
+    days_of_week: Vec<bool>,
+    dom_restricted: bool,
+    dow_restricted: bool,
+}
+
+pub fn parse_cron_schedule(expr: &str) -> Option<CronSchedule> {
+    let expr = match expr.trim() {
+        "@yearly" | "@annually" => "0 0 1 1 *",
+        "@monthly" => "0 0 1 * *",
+        "@weekly" => "0 0 * * 0",
+        "@daily" | "@midnight" => "0 0 * * *",
+        "@hourly" => "0 * * * *",
+        other => other,
+    };
+    let fields: Vec<&str> = expr.split_whitespace().collect();
+    if fields.len() != 5 {
+        return None;
+    }
+    let mut days_of_week = parse_cron_field(fields[4], 0, 7)?;
+    // Both 0 and 7 mean Sunday
+    if days_of_week[7] {
+        days_of_week[0] = true;
+    }
+    Some(CronSchedule {
+        minutes: parse_cron_field(fields[0], 0, 59)?,
+        hours: parse_cron_field(fields[1], 0, 23)?,
+        days_of_month: parse_cron_field(fields[2], 1, 31)?,
+        months: parse_cron_field(fields[3], 1, 12)?,
+        days_of_week,
+        dom_restricted: !fields[2].starts_with('*') && fields[2] != "?",
+        dow_restricted: !fields[4].starts_with('*') && fields[4] != "?",
+    })
+}
+
+fn parse_cron_field(field: &str, min: u32, max: u32) -> Option<Vec<bool>> {
+    let mut allowed = vec![false; max as usize + 1];
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((r, s)) => (r, s.parse::<u32>().ok().filter(|s| *s > 0)?),
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" || range == "?" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            (a.parse().ok()?, b.parse().ok()?)
+        } else {
+            let v: u32 = range.parse().ok()?;
+            // "5/15" means every 15 starting at 5
+            (v, if part.contains('/') { max } else { v })
+        };
+        if start < min || end > max || start > end {
+            return None;
+        }
+        for v in (start..=end).step_by(step as usize) {
+            allowed[v as usize] = true;
+        }
+    }
+    Some(allowed)
+}
+
+impl CronSchedule {
+    /// First scheduled time strictly after `after`, searching up to five years ahead
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let mut t = start;
+        let limit = start + Duration::days(366 * 5);
+        while t < limit {
+            if !self.months[t.month() as usize] {
+                let (y, m) = if t.month() == 12 { (t.year() + 1, 1) } else { (t.year(), t.month() + 1) };
+                t = Utc.with_ymd_and_hms(y, m, 1, 0, 0, 0).single()?;
+                continue;
+            }
+            if !self.day_matches(t) {
+                t = (t + Duration::days(1)).with_hour(0)?.with_minute(0)?;
+                continue;
+            }
+            if !self.hours[t.hour() as usize] {
+                t = (t + Duration::hours(1)).with_minute(0)?;
+                continue;
+            }
+            if !self.minutes[t.minute() as usize] {
+                t += Duration::minutes(1);
+                continue;
+            }
+            return Some(t);
+        }
+        None
+    }
+
+    fn day_matches(&self, t: DateTime<Utc>) -> bool {
+        let dom = self.days_of_month[t.day() as usize];
+        let dow = self.days_of_week[t.weekday().num_days_from_sunday() as usize];
+        // Like cron, a restricted day-of-month and day-of-week match either
+        if self.dom_restricted && self.dow_restricted {
+            dom || dow
+        } else {
+            dom && dow
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_cron_schedule_next_after() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+
+        let every_15 = parse_cron_schedule("*/15 * * * *").unwrap();
+        assert_eq!(every_15.next_after(at("2024-05-01T10:07:30Z")), Some(at("2024-05-01T10:15:00Z")));
+        assert_eq!(every_15.next_after(at("2024-05-01T10:15:00Z")), Some(at("2024-05-01T10:30:00Z")));
+
+        let nightly = parse_cron_schedule("@daily").unwrap();
+        assert_eq!(nightly.next_after(at("2024-12-31T23:59:00Z")), Some(at("2025-01-01T00:00:00Z")));
+
+        // Weekdays at 09:30
+        let weekdays = parse_cron_schedule("30 9 * * 1-5").unwrap();
+        assert_eq!(weekdays.next_after(at("2024-05-03T10:00:00Z")), Some(at("2024-05-06T09:30:00Z")));
+
+        // Day-of-month and day-of-week are OR-ed when both are restricted
+        let either = parse_cron_schedule("0 0 15 * 1").unwrap();
+        assert_eq!(either.next_after(at("2024-05-01T00:00:00Z")), Some(at("2024-05-06T00:00:00Z")));
+
+        assert!(parse_cron_schedule("* * *").is_none());
+        assert!(parse_cron_schedule("61 * * * *").is_none());
+        assert!(parse_cron_schedule("*/0 * * * *").is_none());
+    }
 
     #[test]
     fn test_parse_cpu_to_millicores() {
@@ -144,11 +399,129 @@ mod tests {
         assert_eq!(parse_memory_to_bytes("100X"), None);
     }
 
+    #[test]
+    fn test_parse_quantity_exponents_and_signs() {
+        // Exponent notation, as metrics-server occasionally reports
+        assert_eq!(parse_memory_to_bytes("129e6"), Some(129_000_000));
+        assert_eq!(parse_memory_to_bytes("1E3"), Some(1000));
+        assert_eq!(parse_memory_to_bytes("1E"), Some(1_000_000_000_000_000_000)); // exa, not an exponent
+        assert_eq!(parse_memory_to_bytes("5e+2"), Some(500));
+        assert_eq!(parse_cpu_to_millicores("1e3"), Some(1_000_000));
+        assert_eq!(parse_cpu_to_millicores("25e-2"), Some(250));
+        assert_eq!(parse_cpu_to_millicores("1e-9"), Some(0));
+
+        // Signs and a missing integer part
+        assert_eq!(parse_cpu_to_millicores("+250m"), Some(250));
+        assert_eq!(parse_cpu_to_millicores("-500m"), Some(-500));
+        assert_eq!(parse_cpu_to_millicores(".5"), Some(500));
+        assert_eq!(parse_memory_to_bytes("+1Gi"), Some(1024 * 1024 * 1024));
+
+        // Malformed or out of range
+        assert_eq!(parse_memory_to_bytes("0x10"), None);
+        assert_eq!(parse_memory_to_bytes("1e"), None);
+        assert_eq!(parse_memory_to_bytes("1.2.3"), None);
+        assert_eq!(parse_memory_to_bytes("+"), None);
+        assert_eq!(parse_memory_to_bytes("1e30"), None);
+        assert_eq!(parse_quantity("16Ei").and_then(|q| q.value()), None);
+        assert_eq!(parse_quantity("1.5Gi").and_then(|q| q.millis()), Some(1536 * 1024 * 1024 * 1000));
+    }
+
+    #[test]
+    fn test_humanize_resources() {
+        assert_eq!(humanize_bytes(512), "512");
+        assert_eq!(humanize_bytes(100 * 1024 * 1024), "100Mi");
+        assert_eq!(humanize_bytes(1536 * 1024 * 1024), "1.5Gi");
+        assert_eq!(humanize_bytes(40 * 1024 * 1024 * 1024 + 1), "40Gi");
+        assert_eq!(humanize_bytes(1023 * 1024), "1023Ki");
+
+        assert_eq!(humanize_millicores(250), "250m");
+        assert_eq!(humanize_millicores(1000), "1 core");
+        assert_eq!(humanize_millicores(1250), "1.25 cores");
+        assert_eq!(humanize_millicores(2004), "2 cores");
+    }
+
+    #[test]
+    fn test_format_quantities() {
+        assert_eq!(format_cpu(250), "250m");
+        assert_eq!(format_cpu(2000), "2");
+        assert_eq!(format_cpu(1250), "1.25");
+        assert_eq!(format_cpu(1001), "1.001");
+        assert_eq!(format_cpu(-1500), "-1.5");
+        assert_eq!(format_cpu(0), "0m");
+
+        assert_eq!(format_memory(512), "512");
+        assert_eq!(format_memory(1536 * 1024 * 1024), "1.5Gi");
+        assert_eq!(format_memory(1023 * 1024), "1023Ki");
+        assert_eq!(format_memory(1152), "1.125Ki");
+        assert_eq!(format_memory(129_000_000), "129M");
+        assert_eq!(format_memory(1_000_000_000), "1G");
+        assert_eq!(format_memory(1025), "1025");
+        assert_eq!(format_memory(i64::MIN), "-8Ei");
+    }
+
+    /// Any quantity in the grammar: sign, digits with an optional fraction, and a binary,
+    /// decimal or exponent suffix
+    fn quantity_strategy() -> impl Strategy<Value = String> {
+        let suffix = prop_oneof![
+            prop::sample::select(vec!["", "n", "u", "m", "k", "K", "M", "G", "T", "P", "E", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei"])
+                .prop_map(str::to_string),
+            (prop::sample::select(vec!["e", "E"]), prop::sample::select(vec!["", "+", "-"]), 0u32..20)
+                .prop_map(|(e, sign, exp)| format!("{}{}{}", e, sign, exp)),
+        ];
+        (prop::sample::select(vec!["", "+", "-"]), 0u64..1_000_000, prop::option::of(0u32..1000), suffix).prop_map(
+            |(sign, integer, fraction, suffix)| match fraction {
+                Some(f) => format!("{}{}.{:03}{}", sign, integer, f, suffix),
+                None => format!("{}{}{}", sign, integer, suffix),
+            },
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn prop_format_cpu_round_trips(millicores in -1_000_000_000_000i64..1_000_000_000_000) {
+            prop_assert_eq!(parse_cpu_to_millicores(&format_cpu(millicores)), Some(millicores));
+        }
+
+        #[test]
+        fn prop_format_memory_round_trips(bytes in any::<i64>()) {
+            prop_assert_eq!(parse_memory_to_bytes(&format_memory(bytes)), Some(bytes));
+        }
+
+        #[test]
+        fn prop_parsed_quantities_format_stably(q in quantity_strategy()) {
+            let quantity = parse_quantity(&q);
+            prop_assert!(quantity.is_some(), "{} should parse", q);
+            if let Some(bytes) = quantity.and_then(|q| q.value()) {
+                let formatted = format_memory(bytes);
+                prop_assert_eq!(parse_memory_to_bytes(&formatted), Some(bytes));
+                prop_assert_eq!(format_memory(parse_memory_to_bytes(&formatted).unwrap()), formatted);
+            }
+            if let Some(millicores) = quantity.and_then(|q| q.millis()) {
+                prop_assert_eq!(parse_cpu_to_millicores(&format_cpu(millicores)), Some(millicores));
+            }
+        }
+
+        #[test]
+        fn prop_exponent_matches_decimal_suffix(mantissa in 0i64..1_000_000, exp in 0u32..4) {
+            let suffix = ["", "k", "M", "G"][exp as usize];
+            prop_assert_eq!(
+                parse_memory_to_bytes(&format!("{}e{}", mantissa, exp * 3)),
+                parse_memory_to_bytes(&format!("{}{}", mantissa, suffix))
+            );
+        }
+
+        #[test]
+        fn prop_parse_quantity_never_panics(q in "\\PC{0,24}") {
+            let _ = parse_quantity(&q);
+        }
+    }
+
     #[test]
     fn test_compute_utilization_percentages() {
         let usage = PodUsageTotals {
             cpu_millicores: 500,
             memory_bytes: 1024 * 1024 * 512, // 512 MiB
+            ..Default::default()
         };
         
         // Test with valid requests
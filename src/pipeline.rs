@@ -0,0 +1,702 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use kube::Client;
+use std::borrow::Cow;
+use std::path::Path;
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::{info, info_span, warn, Instrument};
+
+use crate::api_health::{instrumented_client, ApiStats};
+use crate::audit::{append_audit_log, ApiAudit};
+use crate::collector::{until, MetricsCollector};
+use crate::delivery::{check_outcomes, deliver_all, webhook_client, Delivery, DeliveryOutcome, PayloadSigner};
+use crate::freeze::{check_change_freeze, new_findings};
+use crate::hooks::HookRegistry;
+use crate::kubernetes::{
+    detect_cluster_name, ensure_metrics_available, find_missing_namespaces, probe_metrics_availability,
+    probe_namespace_metrics_availability, resolve_rbac_scope,
+};
+use crate::metrics::custom::resolve_custom_resource_checks;
+use crate::metrics::objects::analyze_object_counts;
+use crate::metrics::recommendations::update_recommendations;
+use crate::notifier::{
+    CsvNotifier, EmailNotifier, GenericWebhookNotifier, HtmlNotifier, NotifierRegistry, PagerDutyNotifier, PushgatewayNotifier, SlackBotNotifier,
+    SlackNotifier, StdoutNotifier, TelegramNotifier, TextfileNotifier,
+};
+use crate::oncall::{current_oncall, slack_mention};
+use crate::ownership::OwnershipMap;
+use crate::parsing::humanize_bytes;
+use crate::redaction::redact_payload;
+use crate::report::priority::critical_count;
+use crate::report::{new_run_id, HealthReport, ReportSummary};
+use crate::secrets::SecretResolver;
+use crate::self_usage::{memory_limit_bytes, peak_rss_bytes};
+use crate::slack::{build_report_payload, build_team_context};
+use crate::state::{issue_fingerprint, Snooze, StateStore};
+use crate::suppression::{load_suppressions, Suppression};
+use crate::types::{Config, PreviouslySnoozedInfo, RbacScope, SlackPayload};
+
+/// Where a report is delivered
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sink {
+    /// Name used in logs, e.g. `slack` or `team:payments`
+    pub name: String,
+    pub webhook_url: String,
+    /// Team whose findings alone are sent; `None` sends the whole report
+    pub team: Option<String>,
+    /// A failed required delivery makes `deliver` fail
+    pub required: bool,
+}
+
+/// Collect and assemble a health report: cluster name detection, metrics collection,
+/// recommendations, lifecycle and snooze state, HealthSuppressions. Nothing is sent.
+pub async fn run(client: &Client, config: Config) -> Result<HealthReport> {
+    // Every log line of the run carries the ID that also ends up in the Slack footer
+    let run_id = new_run_id();
+    collect_report(client, config, run_id.clone())
+        .instrument(info_span!("run", run_id = %run_id))
+        .await
+}
+
+/// Client of the runs, tallying how the API server answered and which API verbs were
+/// used; read-only unless `ALLOW_API_WRITES` is set. A daemon keeps one for all its runs.
+pub struct RunClient {
+    client: Client,
+    stats: ApiStats,
+    audit: ApiAudit,
+}
+
+impl RunClient {
+    pub fn new(kube_config: kube::Config, config: &Config) -> Result<Self> {
+        let (client, stats, audit) = instrumented_client(kube_config, config.allow_api_writes, config.api_recording.as_ref())?;
+        Ok(Self { client, stats, audit })
+    }
+}
+
+/// [`run`] with a client built from `kube_config`, see [`run_with_client`]
+pub async fn run_instrumented(kube_config: kube::Config, config: Config) -> Result<HealthReport> {
+    run_with_client(&RunClient::new(kube_config, &config)?, config).await
+}
+
+/// [`run`] on `client`, noting in the report how the API server held up during
+/// collection and which API verbs were used. Both tallies start afresh every run.
+pub async fn run_with_client(client: &RunClient, config: Config) -> Result<HealthReport> {
+    let collected = run(&client.client, config).await;
+    let (api_health, api_audit) = (client.stats.take(), client.audit.take());
+    let mut report = collected?;
+    report.api_health = Some(api_health);
+    report.api_audit = api_audit;
+    for entry in &report.api_audit {
+        info!(
+            verb = %entry.verb,
+            resource = %entry.resource,
+            namespace = entry.namespace.as_deref().unwrap_or(""),
+            requests = entry.requests,
+            denied = entry.denied,
+            "API audit"
+        );
+    }
+    if let Some(path) = report.config.audit_log_file.as_deref() {
+        let cluster = report.config.cluster_name.as_deref();
+        if let Err(e) = append_audit_log(Path::new(path), &report.run_id, cluster, &report.api_audit) {
+            warn!("Failed to write the API audit log: {:#}", e);
+        }
+    }
+    Ok(report)
+}
+
+/// [`run_with_client`] between the hooks' `before_collection` and `after_report`
+pub async fn run_hooked(hooks: &HookRegistry, client: &RunClient, config: Config) -> Result<HealthReport> {
+    hooks.before_collection(&config).await?;
+    let mut report = run_with_client(client, config).await?;
+    hooks.after_report(&mut report).await;
+    Ok(report)
+}
+
+/// Send the report through every notifier unless a hook vetoes it
+pub async fn notify_unless_vetoed(hooks: &HookRegistry, notifiers: &NotifierRegistry, report: &HealthReport) -> Result<()> {
+    if let Some(veto) = hooks.before_send(report).await {
+        info!("Notifications vetoed by hook {}", veto);
+        return Ok(());
+    }
+    notifiers.notify_all(report).await
+}
+
+async fn collect_report(client: &Client, mut cfg: Config, run_id: String) -> Result<HealthReport> {
+    info!("namespaces = {:?}", cfg.namespaces);
+
+    let ownership = match cfg.ownership_file.as_deref() {
+        Some(path) => Some(OwnershipMap::load(Path::new(path))?),
+        None => None,
+    };
+    // Label-based ownership rules need those labels on the findings
+    if let Some(ownership) = &ownership {
+        for key in ownership.label_keys() {
+            if !cfg.issue_labels.contains(&key) {
+                cfg.issue_labels.push(key);
+            }
+        }
+    }
+
+    cfg.rbac_scope = resolve_rbac_scope(client, cfg.rbac_scope).await;
+    // Typo'd or deleted namespaces would otherwise just come back without findings
+    let missing_namespaces = find_missing_namespaces(client, &cfg.namespaces).await;
+    if !missing_namespaces.is_empty() {
+        warn!("Configured namespaces not found, skipping them: {}", missing_namespaces.join(", "));
+        cfg.namespaces.retain(|ns| !missing_namespaces.contains(ns));
+    }
+    let cluster_scoped = cfg.rbac_scope != RbacScope::Namespace;
+
+    if cfg.cluster_name.is_none() {
+        cfg.cluster_name = detect_cluster_name(client).await;
+        if let Some(name) = &cfg.cluster_name {
+            info!("CLUSTER_NAME unset, detected cluster name: {}", name);
+        }
+    }
+
+    // Check metrics API availability early (fail fast if requested)
+    let availability = if cfg.fail_if_no_metrics {
+        ensure_metrics_available(client, &cfg.namespaces, cluster_scoped).await?
+    } else if cluster_scoped {
+        probe_metrics_availability(client, &cfg.namespaces).await
+    } else {
+        probe_namespace_metrics_availability(client, &cfg.namespaces).await
+    };
+    if !availability.is_fully_available() {
+        warn!("Metrics API partially unavailable, affected usage checks will be skipped");
+    }
+
+    // Custom resource kinds are resolved once; unserved ones are skipped with a warning
+    let custom_resource_checks = resolve_custom_resource_checks(client, &cfg.custom_resource_checks).await;
+    let collector = MetricsCollector::new(client, &cfg)
+        .with_metrics_availability(availability)
+        .with_custom_resource_checks(custom_resource_checks);
+    let mut report = HealthReport::new(cfg.clone()).with_run_id(run_id);
+    report.ownership = ownership;
+    report.missing_namespaces = missing_namespaces;
+
+    // Collect metrics for each namespace, within the namespace and run time budgets
+    let budget = |seconds: u64| (seconds > 0).then(|| Instant::now() + Duration::from_secs(seconds));
+    let run_deadline = budget(cfg.run_timeout_seconds);
+    for ns in &cfg.namespaces {
+        if run_deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            warn!("Run budget of {}s used up, skipping namespace {}", cfg.run_timeout_seconds, ns);
+            report.collection_warnings.push(format!(
+                "Namespace `{}` skipped: the run exceeded its {}s budget",
+                ns, cfg.run_timeout_seconds
+            ));
+            continue;
+        }
+        info!("Collecting metrics for namespace: {}", ns);
+
+        // Pod, job and volume analyzers run concurrently
+        let deadline = match (budget(cfg.namespace_timeout_seconds), run_deadline) {
+            (Some(ns_deadline), Some(run_deadline)) => Some(ns_deadline.min(run_deadline)),
+            (ns_deadline, run_deadline) => ns_deadline.or(run_deadline),
+        };
+        let ns_metrics = collector.collect_namespace_metrics_until(ns, deadline).await?;
+        if ns_metrics.terminating {
+            info!("Namespace {} is terminating, skipping its checks", ns);
+            report.collection_warnings.push(format!("Namespace `{}` is terminating, its checks were skipped", ns));
+        }
+        if !ns_metrics.timed_out.is_empty() {
+            let groups = ns_metrics.timed_out.join(", ");
+            warn!("Namespace {} exceeded its time budget, skipped {} checks", ns, groups);
+            report.collection_warnings.push(format!(
+                "Namespace `{}`: {} checks exceeded the time budget and were skipped",
+                ns, groups
+            ));
+        }
+        report.add_pod_metrics(ns_metrics.pods);
+        report.add_job_metrics(ns_metrics.jobs);
+        report.add_volume_metrics(ns_metrics.volumes);
+    }
+
+    // Collect cluster-wide metrics with what is left of the run budget
+    info!("Collecting cluster-wide metrics");
+    match until(run_deadline, collector.collect_cluster_metrics()).await {
+        Some(cluster_metrics) => report.set_cluster_metrics(cluster_metrics?),
+        None => {
+            warn!("Run budget of {}s used up, skipping cluster-wide checks", cfg.run_timeout_seconds);
+            report.collection_warnings.push(format!(
+                "Node and other cluster-wide checks skipped: the run exceeded its {}s budget",
+                cfg.run_timeout_seconds
+            ));
+        }
+    }
+
+    // Right-sizing recommendations need usage history persisted across runs
+    if let Some(path) = cfg.recommendations_history_path.as_deref() {
+        match update_recommendations(Path::new(path), &report.pod_metrics.workload_usage) {
+            Ok(recommendations) => report.recommendations = recommendations,
+            Err(e) => warn!("Skipping right-sizing recommendations: {:#}", e),
+        }
+    }
+
+    // Findings come in API list order; sort them so runs are comparable
+    report.sort_findings();
+
+    // Silence snoozed findings and those matched by HealthSuppression resources
+    let suppressions = if cfg.suppressions_enabled {
+        let namespaces = (!cluster_scoped).then_some(cfg.namespaces.as_slice());
+        load_suppressions(client, namespaces, Utc::now()).await.unwrap_or_else(|e| {
+            warn!("Skipping HealthSuppressions: {:#}", e);
+            Vec::new()
+        })
+    } else {
+        Vec::new()
+    };
+    let mut state = match cfg.state_file.as_deref().map(|path| StateStore::load(Path::new(path))) {
+        Some(Ok(state)) => Some(state),
+        Some(Err(e)) => {
+            warn!("Skipping snoozes: {:#}", e);
+            None
+        }
+        None => None,
+    };
+    // Object count growth is judged against the previous run's counts
+    let previous_counts = state
+        .as_mut()
+        .map(|s| s.record_object_counts(&report.cluster_metrics.object_counts))
+        .unwrap_or_default();
+    report.cluster_metrics.object_count_warnings =
+        analyze_object_counts(&report.cluster_metrics.object_counts, &previous_counts, &cfg);
+    // Lifecycle covers silenced findings too, so they are not seen as new when unsilenced
+    if let Some(state) = state.as_mut() {
+        let fingerprints = report.fingerprints();
+        state.record_run(&fingerprints, Utc::now());
+        let cleared = state.record_issues(&fingerprints, Utc::now());
+        report.issue_history = state.issues.clone();
+        if cfg.notify_resolved {
+            report.resolved = cleared;
+        }
+    }
+    silence_findings(&mut report, state.as_mut(), &suppressions, Utc::now());
+    if let (Some(state), Some(path)) = (&state, cfg.state_file.as_deref()) {
+        if let Err(e) = state.save(Path::new(path)) {
+            warn!("Failed to persist snoozes: {:#}", e);
+        }
+    }
+
+    // New findings during a change freeze are escalated to critical
+    if let Some(source) = cfg.change_freeze_source.as_ref() {
+        let deadline = Duration::from_secs(cfg.sink_timeout_seconds);
+        let checked = async {
+            let http = webhook_client(&cfg)?;
+            check_change_freeze(client, &http, source, deadline, Utc::now()).await
+        };
+        match checked.await {
+            Ok(Some(mut freeze)) => {
+                freeze.escalated = new_findings(&report);
+                info!("Change freeze in effect ({}), escalating {} new findings", freeze.source, freeze.escalated.len());
+                report.change_freeze = Some(freeze);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                warn!("Could not read the change-freeze flag: {:#}", e);
+                report.collection_warnings.push(format!("Change-freeze flag unreadable from {}; findings were not escalated", source));
+            }
+        }
+    }
+
+    // The reporter's own footprint, so operators can size its CronJob
+    report.run_usage.peak_rss_bytes = peak_rss_bytes();
+    report.run_usage.memory_limit_bytes = memory_limit_bytes();
+    let usage = &report.run_usage;
+    info!(
+        peak_rss_bytes = ?usage.peak_rss_bytes,
+        memory_limit_bytes = ?usage.memory_limit_bytes,
+        listed_objects = usage.listed_objects.values().sum::<usize>(),
+        "Reporter resource usage"
+    );
+    if let (Some(pct), Some(peak), Some(limit)) = (usage.memory_limit_pct(), usage.peak_rss_bytes, usage.memory_limit_bytes) {
+        if cfg.memory_warn_percent > 0.0 && pct >= cfg.memory_warn_percent {
+            let (peak, limit) = (humanize_bytes(peak as i64), humanize_bytes(limit as i64));
+            warn!("Reporter peak memory {} is {:.0}% of its {} limit", peak, pct, limit);
+            report.collection_warnings.push(format!(
+                "Reporter peak memory {} is {:.0}% of its {} limit; raise its memory limit",
+                peak, pct, limit
+            ));
+        }
+    }
+
+    // Log summary
+    let summary = report.summary();
+    info!("Health report summary: {} total issues found", summary.total_issues());
+    for (cause, count) in &summary.restart_causes {
+        info!("Restarts caused by {}: {}", cause, count);
+    }
+    Ok(report)
+}
+
+/// The configured Slack webhook (if any) plus each owning team with its own webhook
+pub fn default_sinks(report: &HealthReport) -> Vec<Sink> {
+    let mut sinks = Vec::new();
+    if !report.config.slack_webhook_url.is_empty() {
+        sinks.push(Sink {
+            name: "slack".to_string(),
+            webhook_url: report.config.slack_webhook_url.clone(),
+            team: None,
+            required: true,
+        });
+    }
+    for (team, contact) in report.ownership.iter().flat_map(|o| &o.teams) {
+        if let Some(url) = contact.slack_webhook_url.as_deref() {
+            sinks.push(Sink {
+                name: format!("team:{}", team),
+                webhook_url: url.to_string(),
+                team: Some(team.clone()),
+                required: false,
+            });
+        }
+    }
+    sinks
+}
+
+/// Message for one sink; `None` when it has nothing to report (see [`report_payload`])
+pub fn render(report: &HealthReport, sink: &Sink) -> Option<Delivery> {
+    Some(Delivery {
+        sink: sink.name.clone(),
+        webhook_url: sink.webhook_url.clone(),
+        payload: report_payload(report, sink.team.as_deref())?,
+        required: sink.required,
+        timeout: std::time::Duration::from_secs(report.config.sink_timeout_seconds),
+    })
+}
+
+/// Whether the whole report is worth a message: there are issues, findings that just
+/// cleared, or a clean run has something else to say. The main Slack message, email and
+/// Telegram all go out on exactly these runs.
+pub fn should_send(report: &HealthReport, summary: &ReportSummary) -> bool {
+    needs_full_report(report, summary) || !report.resolved.is_empty()
+}
+
+/// The report the main Slack message, email and Telegram render; a run with nothing but
+/// findings that just cleared leaves out the empty sections
+pub fn outgoing_report<'a>(report: &'a HealthReport, summary: &ReportSummary) -> Cow<'a, HealthReport> {
+    if needs_full_report(report, summary) {
+        return Cow::Borrowed(report);
+    }
+    let mut resolved_only = report.clone();
+    resolved_only.config.omit_empty_sections = true;
+    Cow::Owned(resolved_only)
+}
+
+fn needs_full_report(report: &HealthReport, summary: &ReportSummary) -> bool {
+    // A misconfigured namespace list or stale metrics (usage checks that cannot be
+    // trusted) are worth a message even on a clean run
+    summary.has_issues() || !report.missing_namespaces.is_empty() || !report.pod_metrics.stale_metrics.is_empty()
+}
+
+/// The redacted Slack message of the whole report, or of `team`'s findings; `None` when
+/// there is nothing to report. The whole report goes out as decided by `should_send`,
+/// team reports when the team has issues.
+pub fn report_payload(report: &HealthReport, team: Option<&str>) -> Option<SlackPayload> {
+    let mut payload = match team {
+        None => {
+            let summary = report.summary();
+            if !should_send(report, &summary) {
+                return None;
+            }
+            build_report_payload(&outgoing_report(report, &summary), &summary)
+        }
+        Some(team) => {
+            let ownership = report.ownership.as_ref()?;
+            let team_report = report.scoped_to(|f| ownership.team_for(f) == Some(team));
+            let team_summary = team_report.summary();
+            if !team_summary.has_issues() {
+                return None;
+            }
+            let contact = ownership.teams.get(team).and_then(|t| t.contact.as_deref());
+            let mut payload = build_report_payload(&team_report, &team_summary);
+            payload.blocks.insert(1, build_team_context(team, contact));
+            payload
+        }
+    };
+    redact_payload(&mut payload, &report.config.redaction_patterns);
+    Some(payload)
+}
+
+/// Resolve secret references (`vault:`, `aws-sm:`) in the sink webhook URLs. Optional
+/// sinks whose secret cannot be read are dropped; a required one is an error.
+pub async fn resolve_sinks(resolver: &SecretResolver, sinks: Vec<Sink>) -> Result<Vec<Sink>> {
+    let mut resolved = Vec::with_capacity(sinks.len());
+    for mut sink in sinks {
+        match resolver.resolve(&sink.webhook_url).await {
+            Ok(url) => {
+                sink.webhook_url = url;
+                resolved.push(sink);
+            }
+            Err(e) if !sink.required => warn!("Skipping sink {}: {:#}", sink.name, e),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(resolved)
+}
+
+/// Who critical findings are brought to in Slack: the schedule's current on-call when
+/// `ONCALL_PROVIDER` is set and answers, else `SLACK_MENTION`. The schedule is only
+/// looked up when there are critical findings.
+pub async fn resolve_oncall_mention(resolver: &SecretResolver, report: &mut HealthReport) {
+    if critical_count(report) == 0 {
+        return;
+    }
+    let cfg = &report.config;
+    let mut mention = None;
+    if let Some(oncall) = cfg.oncall.as_ref() {
+        let lookup = async {
+            let token = resolver.resolve(&oncall.api_token).await?;
+            let client = webhook_client(cfg)?;
+            current_oncall(&client, oncall, &token, Duration::from_secs(cfg.sink_timeout_seconds)).await
+        };
+        match lookup.await {
+            Ok(Some(user)) => {
+                info!("On call for schedule {}: {}", oncall.schedule_id, user.email);
+                mention = Some(slack_mention(&user, &oncall.slack_users));
+            }
+            Ok(None) => warn!("Nobody is on call for schedule {}", oncall.schedule_id),
+            Err(e) => warn!("Could not look up the on-call: {:#}", e),
+        }
+    }
+    report.oncall_mention = mention.or_else(|| cfg.slack_mention.clone());
+}
+
+/// Notifiers for a report: Slack, through the configured webhook and the teams' own,
+/// email when an SMTP relay is configured, PagerDuty when a routing key is and the
+/// generic JSON webhook when its URL is
+pub async fn default_notifiers(resolver: &SecretResolver, report: &HealthReport) -> Result<NotifierRegistry> {
+    let mut notifiers = NotifierRegistry::new();
+    let sinks = resolve_sinks(resolver, default_sinks(report)).await?;
+    if !sinks.is_empty() {
+        notifiers.register(SlackNotifier::new(sinks));
+    }
+    if let Some(bot) = &report.config.slack_bot {
+        let token = resolver.resolve(&bot.token).await?;
+        notifiers.register(SlackBotNotifier::new(webhook_client(&report.config)?, bot, token));
+    }
+    if let Some(email) = &report.config.email {
+        let password = match email.smtp_password.as_deref() {
+            Some(password) => Some(resolver.resolve(password).await?),
+            None => None,
+        };
+        notifiers.register(EmailNotifier::new(email.clone(), password));
+    }
+    if let Some(telegram) = &report.config.telegram {
+        let bot_token = resolver.resolve(&telegram.bot_token).await?;
+        notifiers.register(TelegramNotifier::new(webhook_client(&report.config)?, telegram, bot_token));
+    }
+    if let Some(routing_key) = report.config.pagerduty_routing_key.as_deref() {
+        let routing_key = resolver.resolve(routing_key).await?;
+        let client = webhook_client(&report.config)?;
+        notifiers.register(PagerDutyNotifier::new(client, routing_key, report.config.pagerduty_sections.clone()));
+    }
+    if let Some(url) = report.config.generic_webhook_url.as_deref() {
+        let url = resolver.resolve(url).await?;
+        let client = webhook_client(&report.config)?;
+        let headers = report.config.generic_webhook_headers.clone();
+        notifiers.register(GenericWebhookNotifier::new(client, url, headers, PayloadSigner::from_config(&report.config)));
+    }
+    if let Some(pushgateway) = &report.config.pushgateway {
+        let url = resolver.resolve(&pushgateway.url).await?;
+        notifiers.register(PushgatewayNotifier::new(webhook_client(&report.config)?, pushgateway, url));
+    }
+    if let Some(path) = report.config.metrics_textfile.as_deref() {
+        notifiers.register(TextfileNotifier::new(path));
+    }
+    if let Some(target) = report.config.csv_export.as_deref() {
+        notifiers.register(CsvNotifier::new(target));
+    }
+    if let Some(path) = report.config.html_report_file.as_deref() {
+        notifiers.register(HtmlNotifier::new(path));
+    }
+    if let Some(format) = report.config.output_format {
+        notifiers.register(StdoutNotifier::new(format));
+    }
+    Ok(notifiers)
+}
+
+/// Render the report for every sink with something to report and deliver concurrently;
+/// fails when a required sink could not be reached
+pub async fn deliver(report: &HealthReport, sinks: &[Sink]) -> Result<()> {
+    let deliveries: Vec<Delivery> = sinks.iter().filter_map(|sink| render(report, sink)).collect();
+    if deliveries.is_empty() {
+        info!("No issues detected, skipping Slack notification");
+        return Ok(());
+    }
+
+    // Circuit breaker: sinks that kept failing in recent runs are skipped for a while
+    let cfg = &report.config;
+    let state_path = cfg.state_file.as_deref().map(Path::new);
+    let mut state = match state_path.map(StateStore::load) {
+        Some(Ok(state)) => Some(state),
+        Some(Err(e)) => {
+            warn!("Skipping sink circuit breaker: {:#}", e);
+            None
+        }
+        None => None,
+    };
+    let now = Utc::now();
+    let mut skipped = Vec::new();
+    let deliveries: Vec<Delivery> = deliveries
+        .into_iter()
+        .filter_map(|d| match state.as_ref().and_then(|s| s.sink_open_until(&d.sink, now)) {
+            Some(until) => {
+                skipped.push(DeliveryOutcome {
+                    sink: d.sink,
+                    required: d.required,
+                    elapsed: std::time::Duration::ZERO,
+                    result: Err(anyhow!("skipped after repeated failures until {}", until.to_rfc3339())),
+                });
+                None
+            }
+            None => Some(d),
+        })
+        .collect();
+
+    let client = webhook_client(cfg)?;
+    let signer = PayloadSigner::from_config(cfg);
+    let mut outcomes = deliver_all(&client, signer.as_ref(), deliveries).await;
+    if let (Some(state), Some(path)) = (state.as_mut(), state_path) {
+        let cooldown = chrono::Duration::minutes(cfg.sink_cooldown_minutes);
+        for outcome in &outcomes {
+            state.record_delivery(&outcome.sink, outcome.result.is_ok(), now, cfg.sink_failure_threshold, cooldown);
+        }
+        if let Err(e) = state.save(path) {
+            warn!("Failed to persist sink health: {:#}", e);
+        }
+    }
+    outcomes.extend(skipped);
+    check_outcomes(&outcomes)
+}
+
+/// Drop findings that are snoozed or match a HealthSuppression, and flag the ones whose
+/// snooze ran out since the last run. Suppressions with an expiry are remembered in the
+/// state so their findings are flagged the same way once it passes.
+fn silence_findings(
+    report: &mut HealthReport,
+    mut state: Option<&mut StateStore>,
+    suppressions: &[Suppression],
+    now: DateTime<Utc>,
+) {
+    let sources: Vec<String> = suppressions.iter().map(|s| s.source.clone()).collect();
+    let expired = state.as_mut().map(|s| s.expire_snoozes(now, &sources)).unwrap_or_default();
+    let mut previously_snoozed = Vec::new();
+
+    report.suppressed_count = report.retain_findings(|category, finding| {
+        let fingerprint = issue_fingerprint(category, finding);
+        if state.as_ref().is_some_and(|s| s.is_snoozed(&fingerprint)) {
+            info!("Snoozed {}", fingerprint);
+            return false;
+        }
+        if let Some(s) = suppressions.iter().find(|s| s.matches(category, finding)) {
+            info!("Suppressed {} via {}", fingerprint, s.source);
+            if let (Some(state), Some(until)) = (state.as_mut(), s.expires) {
+                let snooze = Snooze { until, reason: s.reason.clone(), suppression: Some(s.source.clone()) };
+                state.snoozes.insert(fingerprint, snooze);
+            }
+            return false;
+        }
+        if let Some(snooze) = expired.get(&fingerprint) {
+            previously_snoozed.push(PreviouslySnoozedInfo {
+                fingerprint,
+                snoozed_until: snooze.until,
+                reason: snooze.reason.clone(),
+            });
+        }
+        true
+    });
+    report.previously_snoozed = previously_snoozed;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DualStackIssueInfo, FailedJobInfo, StaleMetricsInfo};
+
+    #[test]
+    fn test_default_sinks_and_render() {
+        let config = Config {
+            slack_webhook_url: "https://hooks.slack.com/main".to_string(),
+            ..Default::default()
+        };
+        let mut report = HealthReport::new(config);
+        report.ownership = Some(
+            serde_json::from_str(
+                r#"{
+                    "teams": {
+                        "batch": {"slack_webhook_url": "https://hooks.slack.com/batch"},
+                        "web": {"slack_webhook_url": "https://hooks.slack.com/web"},
+                        "platform": {}
+                    },
+                    "rules": [
+                        {"namespace": "batch", "team": "batch"},
+                        {"namespace": "web", "team": "web"}
+                    ]
+                }"#,
+            )
+            .unwrap(),
+        );
+
+        let sinks = default_sinks(&report);
+        assert_eq!(
+            sinks.iter().map(|s| (s.name.as_str(), s.required)).collect::<Vec<_>>(),
+            vec![("slack", true), ("team:batch", false), ("team:web", false)]
+        );
+        // Nothing to report
+        assert!(sinks.iter().all(|s| render(&report, s).is_none()));
+
+        report.job_metrics.failed_jobs.push(FailedJobInfo {
+            namespace: "batch".to_string(),
+            job: "nightly".to_string(),
+            failed_pods: 1,
+            last_failure_time: None,
+            reason: None,
+        });
+        let rendered: Vec<Delivery> = sinks.iter().filter_map(|s| render(&report, s)).collect();
+        assert_eq!(rendered.iter().map(|d| d.sink.as_str()).collect::<Vec<_>>(), vec!["slack", "team:batch"]);
+        assert_eq!(rendered[1].webhook_url, "https://hooks.slack.com/batch");
+        assert_eq!(rendered[1].payload.blocks[1]["elements"][0]["text"], "Findings owned by *batch*");
+
+        // Email-only deployments have no main Slack sink
+        report.config.slack_webhook_url = String::new();
+        let sinks = default_sinks(&report);
+        assert_eq!(sinks.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(), vec!["team:batch", "team:web"]);
+    }
+
+    #[test]
+    fn test_stale_metrics_are_reported_on_a_clean_run() {
+        let mut report = HealthReport::new(Config::default());
+        assert!(!should_send(&report, &report.summary()));
+        assert!(report_payload(&report, None).is_none());
+
+        report.pod_metrics.stale_metrics.push(StaleMetricsInfo {
+            namespace: "default".to_string(),
+            pod: "api-1".to_string(),
+            sampled_at: Utc::now(),
+            age_minutes: 12,
+            window: Some("30s".to_string()),
+        });
+        // Email and Telegram go out on the same runs
+        assert!(should_send(&report, &report.summary()));
+        let payload = report_payload(&report, None).expect("stale metrics are worth a message");
+        assert!(payload.blocks.iter().any(|block| block["block_id"] == "stale_metrics"));
+    }
+
+    #[test]
+    fn test_dual_stack_gaps_are_reported() {
+        // Dual-stack findings can page, so a run with only those must send the report too
+        let mut report = HealthReport::new(Config::default());
+        report.pod_metrics.dual_stack.push(DualStackIssueInfo {
+            namespace: "default".to_string(),
+            object: "Service/api".to_string(),
+            missing_family: "IPv6".to_string(),
+            addresses: vec!["10.96.0.12".to_string()],
+            pods: 0,
+        });
+        let summary = report.summary();
+        assert_eq!(summary.total_issues(), 1);
+        assert!(should_send(&report, &summary));
+        assert!(report_payload(&report, None).is_some());
+    }
+}
@@ -0,0 +1,84 @@
+//! The report's gauges pushed to a Prometheus Pushgateway at the end of every run, for
+//! run-to-completion CronJobs that do not live long enough to be scraped.
+
+use anyhow::{anyhow, Context, Result};
+use std::time::Duration;
+use tokio::time::timeout;
+
+/// Group of the gauges: `<base>/metrics/job/<job>` followed by a `/<name>/<value>` per
+/// grouping label. Each push replaces the whole group, so findings resolved since the
+/// last run disappear.
+pub fn pushgateway_url(base: &str, job: &str, labels: &[(&str, &str)]) -> Result<reqwest::Url> {
+    let mut url = reqwest::Url::parse(base).context("Invalid PUSHGATEWAY_URL")?;
+    {
+        let mut segments = url.path_segments_mut().map_err(|_| anyhow!("Invalid PUSHGATEWAY_URL"))?;
+        segments.pop_if_empty().extend(["metrics", "job", job]);
+        for (name, value) in labels {
+            segments.extend([*name, *value]);
+        }
+    }
+    Ok(url)
+}
+
+/// PUT `exposition` (Prometheus text format) to the group at `url`; fails unless the
+/// Pushgateway accepts it within `deadline`
+pub async fn push_gauges(client: &reqwest::Client, url: reqwest::Url, exposition: String, deadline: Duration) -> Result<()> {
+    let request = client
+        .put(url)
+        .header("content-type", "text/plain; version=0.0.4")
+        .body(exposition)
+        .send();
+    let response = timeout(deadline, request)
+        .await
+        .map_err(|_| anyhow!("Pushgateway timed out after {:?}", deadline))?
+        .map_err(|e| anyhow!("Failed to reach the Pushgateway: {}", e.without_url()))?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!("Pushgateway returned {}: {}", status, body.trim()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_pushgateway_url() {
+        let url = pushgateway_url("http://pushgateway.monitoring:9091", "kube-health-reporter", &[("instance", "prod eu")]).unwrap();
+        assert_eq!(url.as_str(), "http://pushgateway.monitoring:9091/metrics/job/kube-health-reporter/instance/prod%20eu");
+        let url = pushgateway_url("https://push.example.com/prefix/", "khr", &[]).unwrap();
+        assert_eq!(url.as_str(), "https://push.example.com/prefix/metrics/job/khr");
+        assert!(pushgateway_url("pushgateway:9091", "khr", &[]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_push_gauges() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let received = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 8192];
+            // Headers and body may arrive separately
+            while !request.ends_with(b"khr_issues 2\n") {
+                let read = stream.read(&mut buf).unwrap();
+                if read == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..read]);
+            }
+            let _ = write!(stream, "HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n");
+            String::from_utf8_lossy(&request).into_owned()
+        });
+
+        let url = pushgateway_url(&base, "khr", &[("instance", "prod"), ("tenant", "payments")]).unwrap();
+        push_gauges(&reqwest::Client::new(), url, "khr_issues 2\n".to_string(), Duration::from_secs(5)).await.unwrap();
+        let request = received.join().unwrap();
+        assert!(request.starts_with("PUT /metrics/job/khr/instance/prod/tenant/payments HTTP/1.1\r\n"));
+        assert!(request.ends_with("\r\n\r\nkhr_issues 2\n"));
+    }
+}
@@ -0,0 +1,229 @@
+//! Record every API server response of a run to a directory, and replay a recording
+//! later without cluster access, so a user-reported false positive can be reproduced
+//! by running the analyzers against what the user's cluster answered.
+
+use anyhow::{anyhow, Context as _, Result};
+use bytes::Bytes;
+use http::{Request, Response};
+use http_body_util::{BodyExt, Full};
+use kube::client::{Body, ClientBuilder, DynBody};
+use serde::{Deserialize, Serialize};
+use std::future::{ready, Future, Ready};
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tower::util::BoxService;
+use tower::{BoxError, Layer, Service};
+use tracing::{info, warn};
+
+use crate::types::ApiRecording;
+
+/// The client's service stack as `ClientBuilder::try_from` builds it
+pub type ApiService = BoxService<Request<Body>, Response<Box<DynBody>>, BoxError>;
+
+/// API server URL of a replayed run; never contacted
+const REPLAY_CLUSTER_URL: &str = "http://replay.invalid";
+
+/// One recorded request and its answer; JSON bodies are kept as JSON so a recording can
+/// be read and trimmed by hand, anything else (e.g. container logs) as a string
+#[derive(Debug, Serialize, Deserialize)]
+struct RecordedResponse {
+    method: String,
+    /// Path and query of the request
+    uri: String,
+    status: u16,
+    body: serde_json::Value,
+}
+
+/// Cluster configuration of the run: inferred as usual, except when replaying
+pub async fn kube_config(recording: Option<&ApiRecording>) -> Result<kube::Config> {
+    match recording {
+        Some(ApiRecording::Replay(_)) => Ok(kube::Config::new(REPLAY_CLUSTER_URL.parse()?)),
+        _ => Ok(kube::Config::infer().await?),
+    }
+}
+
+/// Client stack for `config`; when recording, every response is also saved to the
+/// directory, and when replaying, requests are answered from it instead of `config`'s
+/// cluster. A request missing from the recording is answered 404.
+pub fn client_builder(config: kube::Config, recording: Option<&ApiRecording>) -> Result<ClientBuilder<ApiService>> {
+    match recording {
+        None => Ok(ClientBuilder::try_from(config)?),
+        Some(ApiRecording::Record(dir)) => {
+            std::fs::create_dir_all(dir).with_context(|| format!("Failed to create API_RECORD_DIR {}", dir))?;
+            info!("Recording API responses to {}", dir);
+            Ok(ClientBuilder::try_from(config)?.with_layer(&RecordLayer(PathBuf::from(dir))))
+        }
+        Some(ApiRecording::Replay(dir)) => {
+            if !Path::new(dir).is_dir() {
+                return Err(anyhow!("API_REPLAY_DIR {} is not a directory", dir));
+            }
+            info!("Replaying API responses from {}", dir);
+            let service = BoxService::new(ReplayService(PathBuf::from(dir)));
+            Ok(ClientBuilder::new(service, config.default_namespace))
+        }
+    }
+}
+
+struct RecordLayer(PathBuf);
+
+impl Layer<ApiService> for RecordLayer {
+    type Service = ApiService;
+
+    fn layer(&self, inner: ApiService) -> ApiService {
+        BoxService::new(RecordService { inner, dir: self.0.clone() })
+    }
+}
+
+/// Service buffering every response body and saving it under `dir` before passing it on
+struct RecordService {
+    inner: ApiService,
+    dir: PathBuf,
+}
+
+impl Service<Request<Body>> for RecordService {
+    type Response = Response<Box<DynBody>>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let method = request.method().to_string();
+        let uri = path_and_query(&request);
+        let dir = self.dir.clone();
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let (parts, body) = response.await?.into_parts();
+            let bytes = body.collect().await?.to_bytes();
+            let recorded = RecordedResponse { method, uri, status: parts.status.as_u16(), body: decode_body(&bytes) };
+            let path = dir.join(recording_file_name(&recorded.method, &recorded.uri));
+            let saved = serde_json::to_vec_pretty(&recorded).map_err(anyhow::Error::from).and_then(|json| {
+                std::fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))
+            });
+            if let Err(e) = saved {
+                warn!("Failed to record {} {}: {:#}", recorded.method, recorded.uri, e);
+            }
+            Ok(Response::from_parts(parts, full_body(bytes)))
+        })
+    }
+}
+
+/// Service answering requests from the recording in its directory
+struct ReplayService(PathBuf);
+
+impl Service<Request<Body>> for ReplayService {
+    type Response = Response<Box<DynBody>>;
+    type Error = BoxError;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let method = request.method().to_string();
+        let uri = path_and_query(&request);
+        ready(replay(&self.0, &method, &uri).map_err(BoxError::from))
+    }
+}
+
+fn replay(dir: &Path, method: &str, uri: &str) -> Result<Response<Box<DynBody>>> {
+    let path = dir.join(recording_file_name(method, uri));
+    if !path.exists() {
+        warn!("No recorded response for {} {}", method, uri);
+        let status = serde_json::json!({
+            "kind": "Status",
+            "apiVersion": "v1",
+            "status": "Failure",
+            "message": format!("{} {} is not in the recording", method, uri),
+            "reason": "NotFound",
+            "code": 404,
+        });
+        return Ok(Response::builder().status(404).body(full_body(Bytes::from(status.to_string())))?);
+    }
+    let content = std::fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let recorded: RecordedResponse =
+        serde_json::from_slice(&content).with_context(|| format!("Invalid recording {}", path.display()))?;
+    let body = match recorded.body {
+        serde_json::Value::String(text) => text,
+        json => json.to_string(),
+    };
+    Ok(Response::builder().status(recorded.status).body(full_body(Bytes::from(body)))?)
+}
+
+fn path_and_query<B>(request: &Request<B>) -> String {
+    request.uri().path_and_query().map_or_else(|| request.uri().path().to_string(), |p| p.to_string())
+}
+
+fn decode_body(bytes: &Bytes) -> serde_json::Value {
+    serde_json::from_slice(bytes)
+        .unwrap_or_else(|_| serde_json::Value::String(String::from_utf8_lossy(bytes).into_owned()))
+}
+
+fn full_body(bytes: Bytes) -> Box<DynBody> {
+    Box::new(Full::new(bytes).map_err(|never| match never {}))
+}
+
+/// File of a request in the recording: the readable path plus a digest of the method,
+/// path and query, e.g. `api_v1_namespaces_default_pods-1f2e3d4c5b6a7980.json`
+fn recording_file_name(method: &str, uri: &str) -> String {
+    let path = uri.split('?').next().unwrap_or_default();
+    let readable: String = path
+        .trim_matches('/')
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '.' { c } else { '_' })
+        .take(120)
+        .collect();
+    let digest = ring::digest::digest(&ring::digest::SHA256, format!("{} {}", method, uri).as_bytes());
+    let hex: String = digest.as_ref()[..8].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}-{}.json", readable, hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::Pod;
+    use kube::api::ListParams;
+    use kube::Api;
+
+    #[test]
+    fn test_recording_file_name() {
+        let list = recording_file_name("GET", "/api/v1/namespaces/default/pods?limit=500");
+        assert!(list.starts_with("api_v1_namespaces_default_pods-"));
+        assert!(list.ends_with(".json"));
+        assert_ne!(list, recording_file_name("GET", "/api/v1/namespaces/default/pods?limit=500&continue=abc"));
+        assert_ne!(list, recording_file_name("DELETE", "/api/v1/namespaces/default/pods?limit=500"));
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let pods = serde_json::json!({
+            "apiVersion": "v1",
+            "kind": "PodList",
+            "metadata": {},
+            "items": [{"metadata": {"name": "api-0", "namespace": "default"}}]
+        });
+        let cluster = tower::service_fn(move |_: Request<Body>| {
+            let body = full_body(Bytes::from(pods.to_string()));
+            async move { Ok::<_, BoxError>(Response::new(body)) }
+        });
+        let recording = ClientBuilder::new(BoxService::new(cluster), "default")
+            .with_layer(&RecordLayer(dir.path().to_path_buf()))
+            .build();
+        let recorded = Api::<Pod>::namespaced(recording, "default").list(&ListParams::default()).await.unwrap();
+        assert_eq!(recorded.items.len(), 1);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        let replay = ClientBuilder::new(BoxService::new(ReplayService(dir.path().to_path_buf())), "default").build();
+        let replayed = Api::<Pod>::namespaced(replay.clone(), "default").list(&ListParams::default()).await.unwrap();
+        assert_eq!(replayed.items[0].metadata.name.as_deref(), Some("api-0"));
+        match Api::<Pod>::namespaced(replay, "payments").list(&ListParams::default()).await {
+            Err(kube::Error::Api(ae)) => assert_eq!(ae.code, 404),
+            other => panic!("expected a 404, got {:?}", other.map(|l| l.items.len())),
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! The report as a self-contained HTML page (inline styles, no scripts or external
+//! assets), rewritten every run so a web server can publish the latest one.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use super::severity::{section_severities, SeverityCounts};
+use super::HealthReport;
+use crate::email::escape_html;
+use crate::types::{Severity, REPORT_SECTIONS};
+use crate::webhook::report_document;
+
+const STYLE: &str = "\
+body{font-family:-apple-system,'Segoe UI',Helvetica,Arial,sans-serif;margin:2em;color:#1d1c1d}\
+h2{margin-top:2em;padding-left:.5em;border-left:6px solid #8d8d8d}\
+h2.critical{border-color:#d93025}h2.warning{border-color:#f9ab00}h2.info{border-color:#1a73e8}\
+table{border-collapse:collapse;width:100%;font-size:14px}\
+th,td{border:1px solid #ddd;padding:4px 8px;text-align:left;vertical-align:top}\
+th{background:#f4f4f4}pre{margin:0;white-space:pre-wrap}\
+.badge{display:inline-block;border-radius:4px;padding:2px 8px;margin-right:4px;color:#fff}\
+.badge.critical{background:#d93025}.badge.warning{background:#f9ab00}.badge.info{background:#1a73e8}\
+.meta{color:#616061}";
+
+/// The page: the run's metadata and severity counts, then a table per section with
+/// findings, in `SECTION_ORDER` when set, else most severe sections first. Findings are
+/// those of the report document, so redaction applies.
+pub fn render_html(report: &HealthReport) -> String {
+    let document = report_document(report);
+    let severities = section_severities(report);
+    let title = match (&report.config.cluster_name, &report.config.datacenter_name) {
+        (Some(c), Some(d)) => format!("Kubernetes Health Report - {} ({})", c, d),
+        (Some(c), None) => format!("Kubernetes Health Report - {}", c),
+        (None, Some(d)) => format!("Kubernetes Health Report - {}", d),
+        (None, None) => "Kubernetes Health Report".to_string(),
+    };
+
+    let mut body = format!(
+        "<h1>{}</h1>\n<p class=\"meta\">Generated {} · Run <code>{}</code> · {} issue(s)</p>\n",
+        escape_html(&title),
+        escape_html(document["generated_at"].as_str().unwrap_or_default()),
+        escape_html(&report.run_id),
+        document["total_issues"],
+    );
+    let totals = report.summary().severity_counts;
+    body.push_str(&format!("<p>{}</p>\n", badges(&totals)));
+
+    let mut sections: Vec<(&str, &Vec<serde_json::Value>)> = REPORT_SECTIONS
+        .iter()
+        .filter_map(|section| Some((*section, document["sections"][section].as_array().filter(|f| !f.is_empty())?)))
+        .collect();
+    let highest = |section: &str| severities.get(section).and_then(SeverityCounts::highest);
+    let order = &report.config.section_order;
+    if order.is_empty() {
+        sections.sort_by_key(|(section, _)| std::cmp::Reverse(highest(section)));
+    } else {
+        // Like the Slack message: only the chosen sections, in the chosen order
+        sections.retain(|(section, _)| order.iter().any(|key| key == section));
+        sections.sort_by_key(|(section, _)| order.iter().position(|key| key == section));
+    }
+    if sections.is_empty() {
+        body.push_str("<p>No findings.</p>\n");
+    }
+    for (section, findings) in sections {
+        let class = highest(section).map_or("", |s| s.name());
+        let counts = severities.get(section).map(badges).unwrap_or_default();
+        body.push_str(&format!("<h2 class=\"{}\" id=\"{}\">{} {}</h2>\n", class, section, section, counts));
+        body.push_str(&render_table(findings));
+    }
+
+    html_page(&title, &body)
+}
+
+/// A standalone page with the report styles around `body`
+pub(super) fn html_page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title),
+        STYLE,
+        body
+    )
+}
+
+/// Write the page to `path`, replacing it atomically so a web server never serves half of it
+pub fn write_html(path: &Path, contents: &str) -> Result<()> {
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, contents).with_context(|| format!("Failed to write {}", tmp.display()))?;
+    std::fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))
+}
+
+/// A row per finding and a column per field, in the order fields first appear
+fn render_table(findings: &[serde_json::Value]) -> String {
+    let mut columns: Vec<&str> = Vec::new();
+    for finding in findings {
+        for (key, value) in finding.as_object().into_iter().flatten() {
+            if !value.is_null() && !columns.contains(&key.as_str()) {
+                columns.push(key.as_str());
+            }
+        }
+    }
+    let header: String = columns.iter().map(|c| format!("<th>{}</th>", escape_html(c))).collect();
+    let mut table = format!("<table>\n<tr>{}</tr>\n", header);
+    for finding in findings {
+        let row: String = columns.iter().map(|c| format!("<td>{}</td>", cell(c, &finding[*c]))).collect();
+        table.push_str(&format!("<tr>{}</tr>\n", row));
+    }
+    table.push_str("</table>\n");
+    table
+}
+
+fn cell(column: &str, value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(s) if column == "log_tail" => format!("<pre>{}</pre>", escape_html(s)),
+        serde_json::Value::String(s) => escape_html(s),
+        serde_json::Value::Array(items) => items.iter().map(|i| cell(column, i)).collect::<Vec<_>>().join(", "),
+        serde_json::Value::Object(fields) => fields
+            .iter()
+            .map(|(k, v)| format!("{}={}", escape_html(k), cell(column, v)))
+            .collect::<Vec<_>>()
+            .join(", "),
+        other => other.to_string(),
+    }
+}
+
+/// A colored badge per severity with findings
+fn badges(counts: &SeverityCounts) -> String {
+    [(Severity::Critical, counts.critical), (Severity::Warning, counts.warning), (Severity::Info, counts.info)]
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(severity, count)| format!("<span class=\"badge {}\">{} {}</span>", severity.name(), count, severity.name()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Config, OomKilledInfo, ResourceHygieneInfo, ResourceHygieneIssue};
+
+    #[test]
+    fn test_render_html() {
+        let config = Config { cluster_name: Some("prod".to_string()), ..Default::default() };
+        let mut report = HealthReport::new(config).with_run_id("run-1".to_string());
+        assert!(render_html(&report).contains("<p>No findings.</p>"));
+
+        report.pod_metrics.resource_hygiene.push(ResourceHygieneInfo {
+            namespace: "web".to_string(),
+            workload: "Deployment/site".to_string(),
+            container: "nginx".to_string(),
+            issue: ResourceHygieneIssue::MemoryLimitUnset,
+        });
+        report.pod_metrics.oom_killed.push(OomKilledInfo {
+            namespace: "payments".to_string(),
+            pod: "api-0".to_string(),
+            labels: Default::default(),
+            node_name: None,
+            zone: None,
+            blast_radius: None,
+            container: "app".to_string(),
+            last_oom_time: None,
+            restart_count: 3,
+            image: None,
+            log_tail: Some("panic: <out of memory>".to_string()),
+        });
+
+        let html = render_html(&report);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Kubernetes Health Report - prod</title>"));
+        assert!(html.contains("<span class=\"badge critical\">1 critical</span><span class=\"badge info\">1 info</span>"));
+        // The critical section comes first, with its findings escaped
+        let oom = html.find("<h2 class=\"critical\" id=\"oom\">").unwrap();
+        let hygiene = html.find("<h2 class=\"info\" id=\"resource_hygiene\">").unwrap();
+        assert!(oom < hygiene);
+        assert!(html.contains("<td>api-0</td>"));
+        assert!(html.contains("<pre>panic: &lt;out of memory&gt;</pre>"));
+
+        // SECTION_ORDER picks and orders the sections as in Slack
+        report.config.section_order = vec!["resource_hygiene".to_string(), "failed".to_string()];
+        let html = render_html(&report);
+        assert!(html.contains("<h2 class=\"info\" id=\"resource_hygiene\">"));
+        assert!(!html.contains("id=\"oom\""));
+        report.config.section_order = vec!["resource_hygiene".to_string(), "oom".to_string()];
+        let html = render_html(&report);
+        assert!(html.find("id=\"resource_hygiene\"").unwrap() < html.find("id=\"oom\"").unwrap());
+    }
+}
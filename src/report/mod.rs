@@ -1,32 +1,99 @@
 use crate::types::*;
 use crate::collector::{PodMetrics, JobMetrics, VolumeMetrics, ClusterMetrics};
+use crate::ownership::OwnershipMap;
+use crate::state::issue_fingerprint;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub mod rollup;
+pub mod priority;
+pub mod severity;
+pub mod html;
 
 /// Aggregated health report containing all metrics
+#[derive(Clone)]
 pub struct HealthReport {
     pub config: Config,
+    /// Correlation ID of this run, shown in logs and the Slack footer
+    pub run_id: String,
+    /// Findings silenced by HealthSuppression resources this run
+    pub suppressed_count: usize,
+    /// Findings whose snooze expired since the last run
+    pub previously_snoozed: Vec<PreviouslySnoozedInfo>,
+    /// Lifecycle of the findings by fingerprint, from the state file
+    pub issue_history: BTreeMap<String, IssueHistory>,
+    /// Findings that cleared since the last run, with their final lifecycle
+    pub resolved: BTreeMap<String, IssueHistory>,
+    /// Team mapping for the owners section and per-team sinks
+    pub ownership: Option<OwnershipMap>,
     pub pod_metrics: AllNamespacePodMetrics,
     pub job_metrics: AllNamespaceJobMetrics,
     pub volume_metrics: AllNamespaceVolumeMetrics,
     pub cluster_metrics: ClusterMetrics,
+    /// Right-sizing suggestions (advisory, not counted as issues)
+    pub recommendations: Vec<RightsizingRecommendation>,
+    /// How the API server answered during collection; `None` for uninstrumented clients
+    pub api_health: Option<ApiHealthInfo>,
+    /// API verbs used during collection; empty for uninstrumented clients
+    pub api_audit: Vec<ApiAuditEntry>,
+    /// Namespaces skipped for terminating, namespaces and analyzer groups skipped for
+    /// exceeding their time budget, and the reporter nearing its own memory limit
+    pub collection_warnings: Vec<String>,
+    /// The reporter's peak memory and the objects it listed
+    pub run_usage: RunUsageInfo,
+    /// Configured namespaces that do not exist, left out of collection
+    pub missing_namespaces: Vec<String>,
+    /// Change freeze in effect during the run, with the findings it escalated
+    pub change_freeze: Option<ChangeFreezeInfo>,
+    /// Who critical findings are brought to: the current on-call, else `SLACK_MENTION`
+    pub oncall_mention: Option<String>,
+    /// Context added by lifecycle hooks, e.g. a deployment in progress
+    pub notes: Vec<String>,
 }
 
 /// Pod metrics aggregated across all namespaces
+#[derive(Clone)]
 pub struct AllNamespacePodMetrics {
     pub heavy_usage: Vec<HeavyUsagePod>,
     pub restarts: Vec<RestartEventInfo>,
     pub pending: Vec<PendingPodInfo>,
     pub failed: Vec<FailedPodInfo>,
     pub unready: Vec<UnreadyPodInfo>,
+    pub readiness_flapping: Vec<ReadinessFlapInfo>,
+    pub endpoint_churn: Vec<ServiceEndpointChurnInfo>,
+    pub dual_stack: Vec<DualStackIssueInfo>,
     pub oom_killed: Vec<OomKilledInfo>,
+    pub volume_scheduling: Vec<VolumeSchedulingInfo>,
+    pub missing_config_refs: Vec<MissingConfigRefInfo>,
+    pub churn: Vec<PodChurnInfo>,
+    pub event_flood: Vec<EventFloodInfo>,
+    pub stuck_terminating: Vec<StuckTerminatingInfo>,
+    pub custom_resource_conditions: Vec<CustomResourceConditionInfo>,
+    pub resource_hygiene: Vec<ResourceHygieneInfo>,
+    /// Pod Security Standards violations (advisory, not counted as issues)
+    pub pod_security: Vec<PodSecurityViolationInfo>,
+    /// Namespaces without a ResourceQuota or LimitRange (advisory, not counted as issues)
+    pub namespace_quotas: Vec<NamespaceQuotaInfo>,
+    /// Containers breaking or at the edge of LimitRange constraints (advisory, not counted as issues)
+    pub limit_range_conflicts: Vec<LimitRangeConflictInfo>,
+    pub stale_metrics: Vec<StaleMetricsInfo>,
+    pub workload_usage: Vec<WorkloadUsage>,
+    /// Usage checks were skipped in at least one namespace
+    pub usage_skipped: bool,
 }
 
 /// Job metrics aggregated across all namespaces
+#[derive(Clone)]
 pub struct AllNamespaceJobMetrics {
     pub failed_jobs: Vec<FailedJobInfo>,
+    pub backoff_risk: Vec<JobBackoffRiskInfo>,
     pub missed_cronjobs: Vec<MissedCronJobInfo>,
+    pub cronjob_concurrency: Vec<CronJobConcurrencyInfo>,
+    /// Namespaces accumulating finished objects (advisory, not counted as issues)
+    pub finished_objects: Vec<FinishedObjectsInfo>,
 }
 
 /// Volume metrics aggregated across all namespaces
+#[derive(Clone)]
 pub struct AllNamespaceVolumeMetrics {
     pub volume_issues: Vec<VolumeIssueInfo>,
 }
@@ -35,48 +102,324 @@ impl HealthReport {
     pub fn new(config: Config) -> Self {
         Self {
             config,
+            run_id: new_run_id(),
+            suppressed_count: 0,
+            previously_snoozed: Vec::new(),
+            issue_history: BTreeMap::new(),
+            resolved: BTreeMap::new(),
+            ownership: None,
             pod_metrics: AllNamespacePodMetrics {
                 heavy_usage: Vec::new(),
                 restarts: Vec::new(),
                 pending: Vec::new(),
                 failed: Vec::new(),
                 unready: Vec::new(),
+                readiness_flapping: Vec::new(),
+                endpoint_churn: Vec::new(),
+                dual_stack: Vec::new(),
                 oom_killed: Vec::new(),
+                volume_scheduling: Vec::new(),
+                missing_config_refs: Vec::new(),
+                churn: Vec::new(),
+                event_flood: Vec::new(),
+                stuck_terminating: Vec::new(),
+                custom_resource_conditions: Vec::new(),
+                resource_hygiene: Vec::new(),
+                pod_security: Vec::new(),
+                namespace_quotas: Vec::new(),
+                limit_range_conflicts: Vec::new(),
+                stale_metrics: Vec::new(),
+                workload_usage: Vec::new(),
+                usage_skipped: false,
             },
             job_metrics: AllNamespaceJobMetrics {
                 failed_jobs: Vec::new(),
+                backoff_risk: Vec::new(),
                 missed_cronjobs: Vec::new(),
+                cronjob_concurrency: Vec::new(),
+                finished_objects: Vec::new(),
             },
             volume_metrics: AllNamespaceVolumeMetrics {
                 volume_issues: Vec::new(),
             },
             cluster_metrics: ClusterMetrics {
                 problematic_nodes: Vec::new(),
+                flapping_nodes: Vec::new(),
                 high_utilization_nodes: Vec::new(),
+                network_agent_issues: Vec::new(),
+                operator_issues: Vec::new(),
+                node_disk_forecast: Vec::new(),
+                object_counts: BTreeMap::new(),
+                object_count_warnings: Vec::new(),
+                node_zones: Default::default(),
+                usage_skipped: false,
+                cluster_checks_skipped: false,
             },
+            recommendations: Vec::new(),
+            api_health: None,
+            api_audit: Vec::new(),
+            collection_warnings: Vec::new(),
+            run_usage: RunUsageInfo::default(),
+            missing_namespaces: Vec::new(),
+            change_freeze: None,
+            oncall_mention: None,
+            notes: Vec::new(),
         }
     }
 
+    /// Whether the finding was escalated to critical for appearing during a change freeze
+    pub fn is_escalated(&self, fingerprint: &str) -> bool {
+        self.change_freeze.as_ref().is_some_and(|freeze| freeze.escalated.contains(fingerprint))
+    }
+
+    /// Use a run ID generated earlier, e.g. one already attached to the log span
+    pub fn with_run_id(mut self, run_id: String) -> Self {
+        self.run_id = run_id;
+        self
+    }
+
     pub fn add_pod_metrics(&mut self, metrics: PodMetrics) {
+        self.count_listed_objects(metrics.listed_objects);
         self.pod_metrics.heavy_usage.extend(metrics.heavy_usage);
         self.pod_metrics.restarts.extend(metrics.restarts);
         self.pod_metrics.pending.extend(metrics.pending);
         self.pod_metrics.failed.extend(metrics.failed);
         self.pod_metrics.unready.extend(metrics.unready);
+        self.pod_metrics.readiness_flapping.extend(metrics.readiness_flapping);
+        self.pod_metrics.endpoint_churn.extend(metrics.endpoint_churn);
+        self.pod_metrics.dual_stack.extend(metrics.dual_stack);
         self.pod_metrics.oom_killed.extend(metrics.oom_killed);
+        self.pod_metrics.volume_scheduling.extend(metrics.volume_scheduling);
+        self.pod_metrics.missing_config_refs.extend(metrics.missing_config_refs);
+        self.pod_metrics.churn.extend(metrics.churn);
+        self.pod_metrics.event_flood.extend(metrics.event_flood);
+        self.pod_metrics.stuck_terminating.extend(metrics.stuck_terminating);
+        self.pod_metrics.custom_resource_conditions.extend(metrics.custom_resource_conditions);
+        self.pod_metrics.resource_hygiene.extend(metrics.resource_hygiene);
+        self.pod_metrics.pod_security.extend(metrics.pod_security);
+        self.pod_metrics.namespace_quotas.extend(metrics.namespace_quotas);
+        self.pod_metrics.limit_range_conflicts.extend(metrics.limit_range_conflicts);
+        self.pod_metrics.stale_metrics.extend(metrics.stale_metrics);
+        self.pod_metrics.workload_usage.extend(metrics.workload_usage);
+        self.pod_metrics.usage_skipped |= metrics.usage_skipped;
     }
 
     pub fn add_job_metrics(&mut self, metrics: JobMetrics) {
+        self.count_listed_objects(metrics.listed_objects);
         self.job_metrics.failed_jobs.extend(metrics.failed_jobs);
+        self.job_metrics.backoff_risk.extend(metrics.backoff_risk);
         self.job_metrics.missed_cronjobs.extend(metrics.missed_cronjobs);
+        self.job_metrics.cronjob_concurrency.extend(metrics.cronjob_concurrency);
+        self.job_metrics.finished_objects.extend(metrics.finished_objects);
+    }
+
+    fn count_listed_objects(&mut self, listed: BTreeMap<String, usize>) {
+        for (resource, count) in listed {
+            *self.run_usage.listed_objects.entry(resource).or_default() += count;
+        }
     }
 
     pub fn add_volume_metrics(&mut self, metrics: VolumeMetrics) {
         self.volume_metrics.volume_issues.extend(metrics.volume_issues);
     }
 
+    /// Store cluster-wide findings and annotate the pod findings with their node's zone
     pub fn set_cluster_metrics(&mut self, metrics: ClusterMetrics) {
         self.cluster_metrics = metrics;
+        let zones = &self.cluster_metrics.node_zones;
+        let zone_of = |node: &Option<String>| node.as_ref().and_then(|n| zones.get(n)).cloned();
+        for r in &mut self.pod_metrics.restarts {
+            r.zone = zone_of(&r.node_name);
+        }
+        for p in &mut self.pod_metrics.pending {
+            p.zone = zone_of(&p.node_name);
+        }
+        for f in &mut self.pod_metrics.failed {
+            f.zone = zone_of(&f.node_name);
+        }
+        for u in &mut self.pod_metrics.unready {
+            u.zone = zone_of(&u.node_name);
+        }
+        for o in &mut self.pod_metrics.oom_killed {
+            o.zone = zone_of(&o.node_name);
+        }
+    }
+
+    /// Copy of the report with only the namespaced findings `keep` accepts; cluster-wide
+    /// findings (nodes, networking agents, operators, object counts, API server health) are left out
+    pub fn scoped_to(&self, keep: impl Fn(&dyn Scoped) -> bool) -> HealthReport {
+        let mut scoped = self.clone();
+        scoped.retain_findings(|_, finding| keep(finding));
+        scoped.cluster_metrics.problematic_nodes.clear();
+        scoped.cluster_metrics.flapping_nodes.clear();
+        scoped.cluster_metrics.high_utilization_nodes.clear();
+        scoped.cluster_metrics.network_agent_issues.clear();
+        scoped.cluster_metrics.operator_issues.clear();
+        scoped.cluster_metrics.node_disk_forecast.clear();
+        scoped.cluster_metrics.object_count_warnings.clear();
+        scoped.api_health = None;
+        scoped.api_audit.clear();
+        scoped.missing_namespaces.clear();
+        scoped.oncall_mention = None;
+        scoped.resolved.clear();
+        scoped.ownership = None;
+        scoped
+    }
+
+    /// Keep the namespaced findings for which `keep(section, finding)` holds, where
+    /// `section` is the finding's key from `REPORT_SECTIONS`; returns how many were dropped
+    pub fn retain_findings(&mut self, mut keep: impl FnMut(&str, &dyn Scoped) -> bool) -> usize {
+        fn retain<T: Scoped>(items: &mut Vec<T>, section: &str, keep: &mut impl FnMut(&str, &dyn Scoped) -> bool) -> usize {
+            let before = items.len();
+            items.retain(|item| keep(section, item));
+            before - items.len()
+        }
+        let pods = &mut self.pod_metrics;
+        let jobs = &mut self.job_metrics;
+        retain(&mut pods.heavy_usage, "usage", &mut keep)
+            + retain(&mut pods.restarts, "restarts", &mut keep)
+            + retain(&mut pods.pending, "pending", &mut keep)
+            + retain(&mut pods.failed, "failed", &mut keep)
+            + retain(&mut pods.unready, "unready", &mut keep)
+            + retain(&mut pods.readiness_flapping, "readiness_flapping", &mut keep)
+            + retain(&mut pods.endpoint_churn, "endpoint_churn", &mut keep)
+            + retain(&mut pods.dual_stack, "dual_stack", &mut keep)
+            + retain(&mut pods.oom_killed, "oom", &mut keep)
+            + retain(&mut pods.volume_scheduling, "volume_scheduling", &mut keep)
+            + retain(&mut pods.missing_config_refs, "missing_config", &mut keep)
+            + retain(&mut pods.churn, "pod_churn", &mut keep)
+            + retain(&mut pods.event_flood, "event_flood", &mut keep)
+            + retain(&mut pods.stuck_terminating, "stuck_terminating", &mut keep)
+            + retain(&mut pods.custom_resource_conditions, "custom_resources", &mut keep)
+            + retain(&mut pods.resource_hygiene, "resource_hygiene", &mut keep)
+            + retain(&mut pods.pod_security, "pod_security", &mut keep)
+            + retain(&mut pods.namespace_quotas, "namespace_quotas", &mut keep)
+            + retain(&mut pods.limit_range_conflicts, "limit_range_conflicts", &mut keep)
+            + retain(&mut pods.stale_metrics, "stale_metrics", &mut keep)
+            + retain(&mut jobs.failed_jobs, "failed_jobs", &mut keep)
+            + retain(&mut jobs.backoff_risk, "job_backoff", &mut keep)
+            + retain(&mut jobs.missed_cronjobs, "missed_cronjobs", &mut keep)
+            + retain(&mut jobs.cronjob_concurrency, "cronjob_concurrency", &mut keep)
+            + retain(&mut jobs.finished_objects, "finished_objects", &mut keep)
+            + retain(&mut self.volume_metrics.volume_issues, "volumes", &mut keep)
+            + retain(&mut self.recommendations, "recommendations", &mut keep)
+    }
+
+    /// Sort every finding list by namespace and object name, then by the finding itself
+    /// (container, category, ...), so reports, payloads and diffs between runs do not
+    /// depend on the order the API server listed objects in
+    pub fn sort_findings(&mut self) {
+        fn sort<T: Scoped + serde::Serialize>(items: &mut [T]) {
+            items.sort_by_cached_key(|item| {
+                (item.namespace().to_string(), item.object_name().to_string(), finding_key(item))
+            });
+        }
+        fn sort_by_name<T: serde::Serialize>(items: &mut [T], name: impl Fn(&T) -> &str) {
+            items.sort_by_cached_key(|item| (name(item).to_string(), finding_key(item)));
+        }
+        let pods = &mut self.pod_metrics;
+        let jobs = &mut self.job_metrics;
+        sort(&mut pods.heavy_usage);
+        sort(&mut pods.restarts);
+        sort(&mut pods.pending);
+        sort(&mut pods.failed);
+        sort(&mut pods.unready);
+        sort(&mut pods.readiness_flapping);
+        sort(&mut pods.endpoint_churn);
+        sort(&mut pods.dual_stack);
+        sort(&mut pods.oom_killed);
+        sort(&mut pods.volume_scheduling);
+        sort(&mut pods.missing_config_refs);
+        sort(&mut pods.churn);
+        sort(&mut pods.event_flood);
+        sort(&mut pods.stuck_terminating);
+        sort(&mut pods.custom_resource_conditions);
+        sort(&mut pods.resource_hygiene);
+        sort(&mut pods.pod_security);
+        sort(&mut pods.namespace_quotas);
+        sort(&mut pods.limit_range_conflicts);
+        sort(&mut pods.stale_metrics);
+        sort(&mut jobs.failed_jobs);
+        sort(&mut jobs.backoff_risk);
+        sort(&mut jobs.missed_cronjobs);
+        sort(&mut jobs.cronjob_concurrency);
+        sort(&mut jobs.finished_objects);
+        sort(&mut self.volume_metrics.volume_issues);
+        sort(&mut self.recommendations);
+        let nodes = &mut self.cluster_metrics;
+        sort_by_name(&mut nodes.problematic_nodes, |n| &n.name);
+        sort_by_name(&mut nodes.flapping_nodes, |n| &n.name);
+        sort_by_name(&mut nodes.high_utilization_nodes, |n| &n.name);
+        sort_by_name(&mut nodes.network_agent_issues, |n| &n.node);
+        sort_by_name(&mut nodes.operator_issues, |n| &n.namespace);
+        sort_by_name(&mut nodes.node_disk_forecast, |n| &n.name);
+        sort_by_name(&mut nodes.object_count_warnings, |n| &n.resource);
+    }
+
+    /// Issue count per owning team (`None` for unowned findings) with the team contact,
+    /// teams without issues left out; empty without an ownership mapping
+    pub fn team_issue_counts(&self) -> Vec<(Option<String>, Option<String>, usize)> {
+        let ownership = match &self.ownership {
+            Some(o) => o,
+            None => return Vec::new(),
+        };
+        let mut teams: Vec<&String> = ownership.rules.iter().map(|r| &r.team).collect();
+        teams.sort();
+        teams.dedup();
+        let mut counts: Vec<(Option<String>, Option<String>, usize)> = teams
+            .into_iter()
+            .map(|team| {
+                let count = self.scoped_to(|f| ownership.team_for(f) == Some(team.as_str())).summary().total_issues();
+                let contact = ownership.teams.get(team).and_then(|t| t.contact.clone());
+                (Some(team.clone()), contact, count)
+            })
+            .collect();
+        let unowned = self.scoped_to(|f| ownership.team_for(f).is_none()).summary().total_issues();
+        counts.push((None, None, unowned));
+        counts.retain(|(_, _, count)| *count > 0);
+        counts
+    }
+
+    /// Fingerprints (`section/namespace/name`) of the namespaced findings, advisories
+    /// (hygiene, security, quotas, stale metrics and the like) included
+    pub fn fingerprints(&self) -> BTreeSet<String> {
+        fn add<T: Scoped>(fingerprints: &mut BTreeSet<String>, items: &[T], section: &str) {
+            fingerprints.extend(items.iter().map(|item| issue_fingerprint(section, item)));
+        }
+        let mut fingerprints = BTreeSet::new();
+        let (pods, jobs) = (&self.pod_metrics, &self.job_metrics);
+        add(&mut fingerprints, &pods.heavy_usage, "usage");
+        add(&mut fingerprints, &pods.restarts, "restarts");
+        add(&mut fingerprints, &pods.pending, "pending");
+        add(&mut fingerprints, &pods.failed, "failed");
+        add(&mut fingerprints, &pods.unready, "unready");
+        add(&mut fingerprints, &pods.readiness_flapping, "readiness_flapping");
+        add(&mut fingerprints, &pods.endpoint_churn, "endpoint_churn");
+        add(&mut fingerprints, &pods.dual_stack, "dual_stack");
+        add(&mut fingerprints, &pods.oom_killed, "oom");
+        add(&mut fingerprints, &pods.volume_scheduling, "volume_scheduling");
+        add(&mut fingerprints, &pods.missing_config_refs, "missing_config");
+        add(&mut fingerprints, &pods.churn, "pod_churn");
+        add(&mut fingerprints, &pods.event_flood, "event_flood");
+        add(&mut fingerprints, &pods.stuck_terminating, "stuck_terminating");
+        add(&mut fingerprints, &pods.custom_resource_conditions, "custom_resources");
+        add(&mut fingerprints, &pods.resource_hygiene, "resource_hygiene");
+        add(&mut fingerprints, &pods.pod_security, "pod_security");
+        add(&mut fingerprints, &pods.namespace_quotas, "namespace_quotas");
+        add(&mut fingerprints, &pods.limit_range_conflicts, "limit_range_conflicts");
+        add(&mut fingerprints, &pods.stale_metrics, "stale_metrics");
+        add(&mut fingerprints, &jobs.failed_jobs, "failed_jobs");
+        add(&mut fingerprints, &jobs.backoff_risk, "job_backoff");
+        add(&mut fingerprints, &jobs.missed_cronjobs, "missed_cronjobs");
+        add(&mut fingerprints, &jobs.cronjob_concurrency, "cronjob_concurrency");
+        add(&mut fingerprints, &jobs.finished_objects, "finished_objects");
+        add(&mut fingerprints, &self.volume_metrics.volume_issues, "volumes");
+        fingerprints
+    }
+
+    /// Whether any usage checks were skipped because metrics.k8s.io was unavailable
+    pub fn usage_checks_skipped(&self) -> bool {
+        self.pod_metrics.usage_skipped || self.cluster_metrics.usage_skipped
     }
 
     /// Check if the report has any issues to report
@@ -86,12 +429,27 @@ impl HealthReport {
         !self.pod_metrics.pending.is_empty() ||
         !self.pod_metrics.failed.is_empty() ||
         !self.pod_metrics.unready.is_empty() ||
+        !self.pod_metrics.readiness_flapping.is_empty() ||
+        !self.pod_metrics.endpoint_churn.is_empty() ||
+        !self.pod_metrics.dual_stack.is_empty() ||
         !self.pod_metrics.oom_killed.is_empty() ||
+        !self.pod_metrics.volume_scheduling.is_empty() ||
+        !self.pod_metrics.missing_config_refs.is_empty() ||
+        !self.pod_metrics.churn.is_empty() ||
+        !self.pod_metrics.event_flood.is_empty() ||
+        !self.pod_metrics.stuck_terminating.is_empty() ||
+        !self.pod_metrics.custom_resource_conditions.is_empty() ||
         !self.job_metrics.failed_jobs.is_empty() ||
+        !self.job_metrics.backoff_risk.is_empty() ||
         !self.job_metrics.missed_cronjobs.is_empty() ||
+        !self.job_metrics.cronjob_concurrency.is_empty() ||
         !self.volume_metrics.volume_issues.is_empty() ||
         !self.cluster_metrics.problematic_nodes.is_empty() ||
-        !self.cluster_metrics.high_utilization_nodes.is_empty()
+        !self.cluster_metrics.flapping_nodes.is_empty() ||
+        !self.cluster_metrics.high_utilization_nodes.is_empty() ||
+        !self.cluster_metrics.network_agent_issues.is_empty() ||
+        !self.cluster_metrics.operator_issues.is_empty() ||
+        !self.cluster_metrics.node_disk_forecast.is_empty()
     }
 
     /// Get a summary of the number of issues found
@@ -99,31 +457,87 @@ impl HealthReport {
         ReportSummary {
             heavy_usage_count: self.pod_metrics.heavy_usage.len(),
             restart_count: self.pod_metrics.restarts.len(),
+            restart_causes: RestartCause::tally(&self.pod_metrics.restarts),
             pending_count: self.pod_metrics.pending.len(),
             failed_pod_count: self.pod_metrics.failed.len(),
             unready_count: self.pod_metrics.unready.len(),
+            readiness_flap_count: self.pod_metrics.readiness_flapping.len(),
+            endpoint_churn_count: self.pod_metrics.endpoint_churn.len(),
+            dual_stack_count: self.pod_metrics.dual_stack.len(),
             oom_killed_count: self.pod_metrics.oom_killed.len(),
+            volume_scheduling_count: self.pod_metrics.volume_scheduling.len(),
+            missing_config_ref_count: self.pod_metrics.missing_config_refs.len(),
+            churn_count: self.pod_metrics.churn.len(),
+            event_flood_count: self.pod_metrics.event_flood.len(),
+            stuck_terminating_count: self.pod_metrics.stuck_terminating.len(),
+            custom_resource_condition_count: self.pod_metrics.custom_resource_conditions.len(),
             failed_job_count: self.job_metrics.failed_jobs.len(),
+            job_backoff_risk_count: self.job_metrics.backoff_risk.len(),
             missed_cronjob_count: self.job_metrics.missed_cronjobs.len(),
+            cronjob_concurrency_count: self.job_metrics.cronjob_concurrency.len(),
             volume_issue_count: self.volume_metrics.volume_issues.len(),
             problematic_node_count: self.cluster_metrics.problematic_nodes.len(),
+            flapping_node_count: self.cluster_metrics.flapping_nodes.len(),
             high_util_node_count: self.cluster_metrics.high_utilization_nodes.len(),
+            network_agent_issue_count: self.cluster_metrics.network_agent_issues.len(),
+            operator_issue_count: self.cluster_metrics.operator_issues.len(),
+            node_disk_forecast_count: self.cluster_metrics.node_disk_forecast.len(),
+            severity_counts: severity::severity_totals(self),
         }
     }
 }
 
+/// Serialized as the generic webhook's report document (see `webhook::report_document`):
+/// every section's findings, their lifecycle and the run's metadata, redacted
+impl serde::Serialize for HealthReport {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&crate::webhook::report_document(self), serializer)
+    }
+}
+
+/// Tiebreaker between findings about the same object: their serialized fields, which
+/// hold the container, category and the like
+fn finding_key(finding: &impl serde::Serialize) -> String {
+    serde_json::to_string(finding).unwrap_or_default()
+}
+
+/// Random (v4) UUID identifying one reporter run
+pub fn new_run_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+#[derive(Debug, Default)]
 pub struct ReportSummary {
     pub heavy_usage_count: usize,
     pub restart_count: usize,
+    /// Restarts per classified exit-code cause, most frequent first
+    pub restart_causes: Vec<(RestartCause, usize)>,
     pub pending_count: usize,
     pub failed_pod_count: usize,
     pub unready_count: usize,
+    pub readiness_flap_count: usize,
+    pub endpoint_churn_count: usize,
+    pub dual_stack_count: usize,
     pub oom_killed_count: usize,
+    pub volume_scheduling_count: usize,
+    pub missing_config_ref_count: usize,
+    pub churn_count: usize,
+    pub event_flood_count: usize,
+    pub stuck_terminating_count: usize,
+    pub custom_resource_condition_count: usize,
     pub failed_job_count: usize,
+    pub job_backoff_risk_count: usize,
     pub missed_cronjob_count: usize,
+    pub cronjob_concurrency_count: usize,
     pub volume_issue_count: usize,
     pub problematic_node_count: usize,
+    pub flapping_node_count: usize,
     pub high_util_node_count: usize,
+    pub network_agent_issue_count: usize,
+    pub operator_issue_count: usize,
+    pub node_disk_forecast_count: usize,
+    /// Every finding by severity, advisories included
+    pub severity_counts: severity::SeverityCounts,
 }
 
 impl ReportSummary {
@@ -133,12 +547,27 @@ impl ReportSummary {
         self.pending_count +
         self.failed_pod_count +
         self.unready_count +
+        self.readiness_flap_count +
+        self.endpoint_churn_count +
+        self.dual_stack_count +
         self.oom_killed_count +
+        self.volume_scheduling_count +
+        self.missing_config_ref_count +
+        self.churn_count +
+        self.event_flood_count +
+        self.stuck_terminating_count +
+        self.custom_resource_condition_count +
         self.failed_job_count +
+        self.job_backoff_risk_count +
         self.missed_cronjob_count +
+        self.cronjob_concurrency_count +
         self.volume_issue_count +
         self.problematic_node_count +
-        self.high_util_node_count
+        self.flapping_node_count +
+        self.high_util_node_count +
+        self.network_agent_issue_count +
+        self.operator_issue_count +
+        self.node_disk_forecast_count
     }
 
     pub fn has_issues(&self) -> bool {
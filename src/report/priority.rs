@@ -0,0 +1,293 @@
+use chrono::{DateTime, Duration, Utc};
+use std::collections::BTreeMap;
+
+use super::severity::section_severities;
+use super::HealthReport;
+use crate::state::issue_fingerprint;
+use crate::types::Scoped;
+
+/// Weight of a finding by section; sections not listed (advisories such as hygiene or
+/// quotas) are not prioritized
+const SECTION_SEVERITY: &[(&str, f64)] = &[
+    ("oom", 5.0),
+    ("failed", 5.0),
+    ("volumes", 4.0),
+    ("missing_config", 4.0),
+    ("volume_scheduling", 4.0),
+    ("restarts", 3.0),
+    ("pending", 3.0),
+    ("unready", 3.0),
+    ("failed_jobs", 3.0),
+    ("stuck_terminating", 2.0),
+    ("readiness_flapping", 2.0),
+    ("endpoint_churn", 2.0),
+    ("custom_resources", 2.0),
+    ("job_backoff", 2.0),
+    ("missed_cronjobs", 2.0),
+    ("usage", 1.0),
+    ("pod_churn", 1.0),
+    ("event_flood", 1.0),
+    ("cronjob_concurrency", 1.0),
+    ("dual_stack", 1.0),
+];
+
+/// Highest weight, given to findings escalated during a change freeze
+pub(crate) const CRITICAL_SEVERITY: f64 = 5.0;
+
+/// Node conditions take down everything scheduled there
+pub(crate) const NODE_SEVERITY: f64 = CRITICAL_SEVERITY;
+
+/// Sections keyed by pod, whose findings are grouped by workload
+const POD_SECTIONS: &[&str] = &[
+    "oom", "failed", "volumes", "missing_config", "volume_scheduling", "restarts", "pending", "unready",
+    "readiness_flapping", "usage",
+];
+
+/// Characters of the random suffixes controllers append to pod names
+const GENERATED_CHARS: &str = "bcdfghjklmnpqrstvwxz2456789";
+
+/// Findings, oldest start and whether one of them was escalated by a change freeze
+type GroupStats = (usize, Option<DateTime<Utc>>, bool);
+
+/// Section, namespace (`None` for nodes) and workload or object name
+type GroupKey = (String, Option<String>, String);
+
+/// A group of findings ranked by `prioritize`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriorityIssue {
+    pub section: String,
+    /// `None` for nodes
+    pub namespace: Option<String>,
+    /// Workload for pod findings (the pod name without generated suffixes), otherwise
+    /// the object the finding is about
+    pub name: String,
+    /// Findings in the group, e.g. the replicas of a Deployment or, for a node, the node
+    /// plus the pods on it with findings
+    pub affected: usize,
+    /// Since the oldest finding in the group was first seen; `None` when unknown
+    pub open_for: Option<Duration>,
+    pub score: f64,
+}
+
+/// Rank the report's findings by severity × duration × blast radius, highest first.
+/// Pod findings of one workload are merged so a crashlooping Deployment counts once,
+/// weighted by its replicas. The duration comes from the issue lifecycle when state is
+/// kept, else from the finding itself; it weighs logarithmically so that a week-old
+/// restart does not outrank a fresh OOM kill. Findings escalated by a change freeze
+/// weigh as critical whatever their section.
+pub fn prioritize(report: &HealthReport, now: DateTime<Utc>) -> Vec<PriorityIssue> {
+    let started = finding_start_times(report);
+    let mut groups: BTreeMap<GroupKey, GroupStats> = BTreeMap::new();
+    for fingerprint in report.fingerprints() {
+        let mut parts = fingerprint.splitn(3, '/');
+        let (Some(section), Some(namespace), Some(name)) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+        if severity(section).is_none() && !report.is_escalated(&fingerprint) {
+            continue;
+        }
+        let name = if POD_SECTIONS.contains(&section) { workload_name(name) } else { name };
+        let since = report.issue_history.get(&fingerprint).map(|h| h.first_seen).or_else(|| started.get(&fingerprint).copied());
+        let group = groups.entry((section.to_string(), Some(namespace.to_string()), name.to_string())).or_default();
+        group.0 += 1;
+        group.1 = match (group.1, since) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        group.2 |= report.is_escalated(&fingerprint);
+    }
+
+    let pods = &report.pod_metrics;
+    let pod_nodes: Vec<&Option<String>> = pods
+        .restarts
+        .iter()
+        .map(|r| &r.node_name)
+        .chain(pods.failed.iter().map(|f| &f.node_name))
+        .chain(pods.unready.iter().map(|u| &u.node_name))
+        .chain(pods.oom_killed.iter().map(|o| &o.node_name))
+        .collect();
+    for node in &report.cluster_metrics.problematic_nodes {
+        let pods_on_node = pod_nodes.iter().filter(|n| n.as_deref() == Some(node.name.as_str())).count();
+        groups.insert(("nodes".to_string(), None, node.name.clone()), (1 + pods_on_node, Some(node.since), false));
+    }
+
+    let mut issues: Vec<PriorityIssue> = groups
+        .into_iter()
+        .map(|((section, namespace, name), (affected, since, escalated))| {
+            let open_for = since.map(|s| (now - s).max(Duration::zero()));
+            let hours = open_for.map_or(0.0, |d| d.num_minutes() as f64 / 60.0);
+            let severity = match (namespace.is_none(), escalated) {
+                (true, _) => NODE_SEVERITY,
+                (false, true) => CRITICAL_SEVERITY,
+                (false, false) => severity(&section).unwrap_or_default(),
+            };
+            let score = severity * (1.0 + (1.0 + hours).ln()) * affected as f64;
+            PriorityIssue { section, namespace, name, affected, open_for, score }
+        })
+        .collect();
+    issues.sort_by(|a, b| b.score.total_cmp(&a.score));
+    issues
+}
+
+/// Findings at the highest severity: node conditions, missing networking agents and
+/// failing operators, the top-weighted sections, whatever a change freeze escalated, and
+/// whatever `SEVERITY_RULES` make critical
+pub fn critical_count(report: &HealthReport) -> usize {
+    critical_sections(report).values().sum()
+}
+
+/// Number of critical findings (see `critical_count`) by section, for the sections that
+/// have any
+pub fn critical_sections(report: &HealthReport) -> BTreeMap<String, usize> {
+    section_severities(report)
+        .into_iter()
+        .filter(|(_, counts)| counts.critical > 0)
+        .map(|(section, counts)| (section, counts.critical))
+        .collect()
+}
+
+pub(crate) fn severity(section: &str) -> Option<f64> {
+    SECTION_SEVERITY.iter().find(|(s, _)| *s == section).map(|(_, weight)| *weight)
+}
+
+/// When the findings that know it started, by fingerprint
+fn finding_start_times(report: &HealthReport) -> BTreeMap<String, DateTime<Utc>> {
+    fn add<T: Scoped>(starts: &mut BTreeMap<String, DateTime<Utc>>, items: &[T], section: &str, since: impl Fn(&T) -> Option<DateTime<Utc>>) {
+        for item in items {
+            if let Some(since) = since(item) {
+                starts.insert(issue_fingerprint(section, item), since);
+            }
+        }
+    }
+    let mut starts = BTreeMap::new();
+    let (pods, jobs) = (&report.pod_metrics, &report.job_metrics);
+    add(&mut starts, &pods.pending, "pending", |p| Some(p.since));
+    add(&mut starts, &pods.failed, "failed", |f| Some(f.since));
+    add(&mut starts, &pods.unready, "unready", |u| Some(u.since));
+    add(&mut starts, &pods.volume_scheduling, "volume_scheduling", |v| Some(v.since));
+    add(&mut starts, &pods.stuck_terminating, "stuck_terminating", |s| Some(s.since));
+    add(&mut starts, &pods.restarts, "restarts", |r| r.last_restart_time);
+    add(&mut starts, &pods.oom_killed, "oom", |o| o.last_oom_time);
+    add(&mut starts, &jobs.failed_jobs, "failed_jobs", |j| j.last_failure_time);
+    add(&mut starts, &jobs.missed_cronjobs, "missed_cronjobs", |c| Some(c.last_schedule_time));
+    starts
+}
+
+/// Pod name without the suffixes controllers generate: `-<hash>-<id>` for Deployments,
+/// `-<id>` for DaemonSets and Jobs, `-<ordinal>` for StatefulSets
+fn workload_name(pod: &str) -> &str {
+    let generated = |s: &str, lengths: std::ops::RangeInclusive<usize>| {
+        lengths.contains(&s.len()) && s.chars().all(|c| GENERATED_CHARS.contains(c))
+    };
+    let Some((rest, suffix)) = pod.rsplit_once('-') else {
+        return pod;
+    };
+    if generated(suffix, 5..=5) {
+        return match rest.rsplit_once('-') {
+            Some((workload, hash)) if generated(hash, 6..=10) => workload,
+            _ => rest,
+        };
+    }
+    if !suffix.is_empty() && suffix.bytes().all(|b| b.is_ascii_digit()) {
+        return rest;
+    }
+    pod
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ChangeFreezeInfo, Config, HeavyUsagePod, IssueHistory, OomKilledInfo, ProblematicNodeInfo, RestartEventInfo};
+
+    fn oom(pod: &str) -> OomKilledInfo {
+        OomKilledInfo {
+            namespace: "payments".to_string(),
+            pod: pod.to_string(),
+            labels: Default::default(),
+            node_name: Some("node-a".to_string()),
+            zone: None,
+            blast_radius: None,
+            container: "app".to_string(),
+            last_oom_time: None,
+            restart_count: 1,
+            image: None,
+            log_tail: None,
+        }
+    }
+
+    #[test]
+    fn test_workload_name() {
+        assert_eq!(workload_name("api-5d8f7c9b6-x2x4q"), "api");
+        assert_eq!(workload_name("fluent-bit-7xk2p"), "fluent-bit");
+        assert_eq!(workload_name("db-12"), "db");
+        assert_eq!(workload_name("standalone"), "standalone");
+        assert_eq!(workload_name("my-app"), "my-app");
+    }
+
+    #[test]
+    fn test_prioritize() {
+        let now = Utc::now();
+        let mut report = HealthReport::new(Config::default());
+        report.pod_metrics.oom_killed.extend([oom("api-5d8f7c9b6-x2x4q"), oom("api-5d8f7c9b6-b8v6d"), oom("cache-0")]);
+        report.pod_metrics.restarts.push(RestartEventInfo {
+            namespace: "batch".to_string(),
+            pod: "worker-0".to_string(),
+            labels: Default::default(),
+            node_name: None,
+            zone: None,
+            blast_radius: None,
+            container: "worker".to_string(),
+            last_restart_time: None,
+            reason: None,
+            message: None,
+            exit_code: Some(1),
+            image: None,
+            log_tail: None,
+        });
+        report.pod_metrics.heavy_usage.push(HeavyUsagePod {
+            namespace: "batch".to_string(),
+            pod: "scraper-0".to_string(),
+            labels: Default::default(),
+            cpu_pct: Some(99.0),
+            mem_pct: None,
+            cpu_millicores: 990,
+            memory_bytes: 0,
+        });
+        // Restarting for a week
+        report.issue_history.insert(
+            "restarts/batch/worker-0".to_string(),
+            IssueHistory { first_seen: now - Duration::days(7), last_seen: now, occurrences: 300 },
+        );
+        report.cluster_metrics.problematic_nodes.push(ProblematicNodeInfo {
+            name: "node-a".to_string(),
+            os: "linux".to_string(),
+            conditions: vec!["MemoryPressure".to_string()],
+            since: now - Duration::hours(2),
+        });
+
+        let issues = prioritize(&report, now);
+        let ranked: Vec<(&str, &str, usize)> = issues.iter().map(|i| (i.section.as_str(), i.name.as_str(), i.affected)).collect();
+        assert_eq!(
+            ranked,
+            vec![
+                // The node with the three OOM-killed pods on it
+                ("nodes", "node-a", 4),
+                ("restarts", "worker", 1),
+                ("oom", "api", 2),
+                ("oom", "cache", 1),
+                ("usage", "scraper", 1),
+            ]
+        );
+        assert_eq!(issues[1].open_for, Some(Duration::days(7)));
+        assert_eq!(issues[2].open_for, None);
+
+        // New findings during a change freeze weigh as critical
+        report.change_freeze = Some(ChangeFreezeInfo {
+            escalated: ["usage/batch/scraper-0".to_string()].into(),
+            ..Default::default()
+        });
+        let issues = prioritize(&report, now);
+        let scraper = issues.iter().find(|i| i.name == "scraper").unwrap();
+        assert_eq!(scraper.score, CRITICAL_SEVERITY);
+    }
+}
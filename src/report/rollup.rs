@@ -0,0 +1,224 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::collections::BTreeMap;
+
+use super::html::html_page;
+use crate::email::escape_html;
+use crate::state::RunRecord;
+
+/// Entries listed per table in the rollup
+const ROLLUP_TOP: usize = 10;
+
+/// Findings over a period, summarized from the run records in the state file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rollup {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    pub runs: usize,
+    /// Fingerprints by the number of runs they were present in, most frequent first
+    pub top_recurring: Vec<(String, usize)>,
+    /// Namespaces by findings summed over all runs, noisiest first
+    pub noisiest_namespaces: Vec<(String, usize)>,
+    /// Average findings per run for each day with runs, oldest first
+    pub daily_issues: Vec<(NaiveDate, f64)>,
+}
+
+/// Summarize the runs of the `days` days before `now`
+pub fn build_rollup(runs: &[RunRecord], now: DateTime<Utc>, days: i64) -> Rollup {
+    let since = now - Duration::days(days);
+    let runs: Vec<&RunRecord> = runs.iter().filter(|r| r.timestamp > since && r.timestamp <= now).collect();
+
+    let mut recurring: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut namespaces: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut days_seen: BTreeMap<NaiveDate, (usize, usize)> = BTreeMap::new();
+    for run in &runs {
+        for fingerprint in &run.fingerprints {
+            *recurring.entry(fingerprint).or_default() += 1;
+            if let Some(namespace) = fingerprint.split('/').nth(1) {
+                *namespaces.entry(namespace).or_default() += 1;
+            }
+        }
+        let day = days_seen.entry(run.timestamp.date_naive()).or_default();
+        day.0 += 1;
+        day.1 += run.fingerprints.len();
+    }
+
+    Rollup {
+        since,
+        until: now,
+        runs: runs.len(),
+        top_recurring: top(recurring),
+        noisiest_namespaces: top(namespaces),
+        daily_issues: days_seen
+            .into_iter()
+            .map(|(day, (runs, findings))| (day, findings as f64 / runs as f64))
+            .collect(),
+    }
+}
+
+fn top(counts: BTreeMap<&str, usize>) -> Vec<(String, usize)> {
+    let mut sorted: Vec<(String, usize)> = counts.into_iter().map(|(k, n)| (k.to_string(), n)).collect();
+    // Stable sort keeps ties in name order
+    sorted.sort_by_key(|(_, n)| std::cmp::Reverse(*n));
+    sorted.truncate(ROLLUP_TOP);
+    sorted
+}
+
+/// Markdown document for review meetings
+pub fn render_rollup_markdown(rollup: &Rollup, cluster: Option<&str>) -> String {
+    let mut out = String::new();
+    let title = match cluster {
+        Some(c) => format!("# Kubernetes Health Rollup - {}\n\n", c),
+        None => "# Kubernetes Health Rollup\n\n".to_string(),
+    };
+    out.push_str(&title);
+    out.push_str(&format!(
+        "{} to {}, {} runs\n",
+        rollup.since.format("%Y-%m-%d %H:%M UTC"),
+        rollup.until.format("%Y-%m-%d %H:%M UTC"),
+        rollup.runs
+    ));
+    if rollup.runs == 0 {
+        out.push_str("\nNo runs recorded in this period.\n");
+        return out;
+    }
+
+    out.push_str("\n## Top recurring issues\n\n");
+    if rollup.top_recurring.is_empty() {
+        out.push_str("No issues.\n");
+    } else {
+        out.push_str("| Issue | Runs |\n|---|---|\n");
+        for (fingerprint, runs) in &rollup.top_recurring {
+            out.push_str(&format!("| `{}` | {} of {} |\n", fingerprint, runs, rollup.runs));
+        }
+    }
+
+    out.push_str("\n## Noisiest namespaces\n\n");
+    if rollup.noisiest_namespaces.is_empty() {
+        out.push_str("No issues.\n");
+    } else {
+        out.push_str("| Namespace | Findings |\n|---|---|\n");
+        for (namespace, findings) in &rollup.noisiest_namespaces {
+            out.push_str(&format!("| {} | {} |\n", namespace, findings));
+        }
+    }
+
+    out.push_str("\n## Issues per run\n\n| Day | Average |\n|---|---|\n");
+    for (day, average) in &rollup.daily_issues {
+        out.push_str(&format!("| {} | {:.1} |\n", day, average));
+    }
+    out
+}
+
+/// The same document as a standalone HTML page, styled like the HTML report
+pub fn render_rollup_html(rollup: &Rollup, cluster: Option<&str>) -> String {
+    let title = match cluster {
+        Some(c) => format!("Kubernetes Health Rollup - {}", c),
+        None => "Kubernetes Health Rollup".to_string(),
+    };
+    let mut body = format!(
+        "<h1>{}</h1>\n<p class=\"meta\">{} to {}, {} runs</p>\n",
+        escape_html(&title),
+        rollup.since.format("%Y-%m-%d %H:%M UTC"),
+        rollup.until.format("%Y-%m-%d %H:%M UTC"),
+        rollup.runs
+    );
+    if rollup.runs == 0 {
+        body.push_str("<p>No runs recorded in this period.</p>\n");
+        return html_page(&title, &body);
+    }
+
+    body.push_str("<h2>Top recurring issues</h2>\n");
+    body.push_str(&html_table(
+        ["Issue", "Runs"],
+        rollup
+            .top_recurring
+            .iter()
+            .map(|(fingerprint, runs)| {
+                [format!("<code>{}</code>", escape_html(fingerprint)), format!("{} of {}", runs, rollup.runs)]
+            })
+            .collect(),
+    ));
+    body.push_str("<h2>Noisiest namespaces</h2>\n");
+    body.push_str(&html_table(
+        ["Namespace", "Findings"],
+        rollup.noisiest_namespaces.iter().map(|(namespace, findings)| [escape_html(namespace), findings.to_string()]).collect(),
+    ));
+    body.push_str("<h2>Issues per run</h2>\n");
+    body.push_str(&html_table(
+        ["Day", "Average"],
+        rollup.daily_issues.iter().map(|(day, average)| [day.to_string(), format!("{:.1}", average)]).collect(),
+    ));
+    html_page(&title, &body)
+}
+
+/// Two-column table of already escaped cells
+fn html_table(header: [&str; 2], rows: Vec<[String; 2]>) -> String {
+    if rows.is_empty() {
+        return "<p>No issues.</p>\n".to_string();
+    }
+    let mut table = format!("<table>\n<tr><th>{}</th><th>{}</th></tr>\n", header[0], header[1]);
+    for [left, right] in rows {
+        table.push_str(&format!("<tr><td>{}</td><td>{}</td></tr>\n", left, right));
+    }
+    table.push_str("</table>\n");
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(timestamp: DateTime<Utc>, fingerprints: &[&str]) -> RunRecord {
+        RunRecord {
+            timestamp,
+            fingerprints: fingerprints.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_build_and_render_rollup() {
+        let now = DateTime::parse_from_rfc3339("2026-03-08T12:00:00Z").unwrap().with_timezone(&Utc);
+        let runs = vec![
+            run(now - Duration::days(9), &["oom/old/gone-0"]),
+            run(now - Duration::days(2), &["oom/default/db-0", "restarts/payments/api-0"]),
+            run(now - Duration::days(2) + Duration::hours(1), &["oom/default/db-0"]),
+            run(now - Duration::hours(1), &["oom/default/db-0", "pending/payments/web-0", "restarts/payments/api-0"]),
+        ];
+
+        let rollup = build_rollup(&runs, now, 7);
+        assert_eq!(rollup.runs, 3);
+        assert_eq!(
+            rollup.top_recurring,
+            vec![
+                ("oom/default/db-0".to_string(), 3),
+                ("restarts/payments/api-0".to_string(), 2),
+                ("pending/payments/web-0".to_string(), 1),
+            ]
+        );
+        // Ties keep name order
+        assert_eq!(rollup.noisiest_namespaces, vec![("default".to_string(), 3), ("payments".to_string(), 3)]);
+        assert_eq!(
+            rollup.daily_issues,
+            vec![(NaiveDate::from_ymd_opt(2026, 3, 6).unwrap(), 1.5), (NaiveDate::from_ymd_opt(2026, 3, 8).unwrap(), 3.0)]
+        );
+
+        let markdown = render_rollup_markdown(&rollup, Some("prod"));
+        assert!(markdown.starts_with("# Kubernetes Health Rollup - prod\n\n2026-03-01 12:00 UTC to 2026-03-08 12:00 UTC, 3 runs\n"));
+        assert!(markdown.contains("| `oom/default/db-0` | 3 of 3 |\n"));
+        assert!(markdown.contains("| payments | 3 |\n"));
+        assert!(markdown.contains("| 2026-03-06 | 1.5 |\n"));
+
+        let html = render_rollup_html(&rollup, Some("prod"));
+        assert!(html.contains("<title>Kubernetes Health Rollup - prod</title>"));
+        assert!(html.contains("<tr><td><code>oom/default/db-0</code></td><td>3 of 3</td></tr>\n"));
+        assert!(html.contains("<tr><td>2026-03-06</td><td>1.5</td></tr>\n"));
+    }
+
+    #[test]
+    fn test_render_empty_rollup() {
+        let now = Utc::now();
+        let markdown = render_rollup_markdown(&build_rollup(&[], now, 7), None);
+        assert!(markdown.ends_with("0 runs\n\nNo runs recorded in this period.\n"));
+        assert!(render_rollup_html(&build_rollup(&[], now, 7), None).contains("<p>No runs recorded in this period.</p>"));
+    }
+}
@@ -0,0 +1,177 @@
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::priority::{severity as section_weight, CRITICAL_SEVERITY};
+use super::HealthReport;
+use crate::state::issue_fingerprint;
+use crate::types::{Scoped, Severity, SeverityRule};
+
+/// Cluster-wide sections, whose findings have no fingerprint, and their default severity
+const CLUSTER_SECTIONS: &[(&str, Severity)] = &[
+    ("nodes", Severity::Critical),
+    ("network_agents", Severity::Critical),
+    ("operators", Severity::Critical),
+    ("node_flapping", Severity::Warning),
+    ("node_utilization", Severity::Warning),
+    ("node_disk", Severity::Warning),
+];
+
+/// Findings per severity
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+pub struct SeverityCounts {
+    pub info: usize,
+    pub warning: usize,
+    pub critical: usize,
+}
+
+impl SeverityCounts {
+    pub fn add(&mut self, severity: Severity, count: usize) {
+        match severity {
+            Severity::Info => self.info += count,
+            Severity::Warning => self.warning += count,
+            Severity::Critical => self.critical += count,
+        }
+    }
+
+    /// Most urgent severity with findings
+    pub fn highest(&self) -> Option<Severity> {
+        [(Severity::Critical, self.critical), (Severity::Warning, self.warning), (Severity::Info, self.info)]
+            .into_iter()
+            .find(|(_, count)| *count > 0)
+            .map(|(severity, _)| severity)
+    }
+}
+
+/// Severity of a section's findings without rules: critical for the top-weighted
+/// sections and node conditions, warning for the other prioritized ones, info for
+/// advisories
+pub fn default_severity(section: &str) -> Severity {
+    if let Some((_, severity)) = CLUSTER_SECTIONS.iter().find(|(s, _)| *s == section) {
+        return *severity;
+    }
+    match section_weight(section) {
+        Some(weight) if weight >= CRITICAL_SEVERITY => Severity::Critical,
+        Some(_) => Severity::Warning,
+        None => Severity::Info,
+    }
+}
+
+/// Severity of a finding in `section` with `measure` (see `MEASURED_SECTIONS`): that of
+/// the last rule matching it, else the section default
+pub fn finding_severity(rules: &[SeverityRule], section: &str, measure: Option<f64>) -> Severity {
+    rules
+        .iter()
+        .rev()
+        .find(|rule| {
+            rule.section == section
+                && match rule.threshold {
+                    None => true,
+                    Some(threshold) => measure.is_some_and(|m| m > threshold),
+                }
+        })
+        .map_or_else(|| default_severity(section), |rule| rule.severity)
+}
+
+/// Findings per severity by section, for the sections that have any. Findings escalated
+/// by a change freeze are critical whatever the rules say.
+pub fn section_severities(report: &HealthReport) -> BTreeMap<String, SeverityCounts> {
+    fn measure<T: Scoped>(measures: &mut BTreeMap<String, f64>, items: &[T], section: &str, value: impl Fn(&T) -> Option<f64>) {
+        for item in items {
+            if let Some(value) = value(item) {
+                let entry = measures.entry(issue_fingerprint(section, item)).or_insert(value);
+                *entry = entry.max(value);
+            }
+        }
+    }
+    let pods = &report.pod_metrics;
+    let now = Utc::now();
+    let mut measures = BTreeMap::new();
+    measure(&mut measures, &pods.heavy_usage, "usage", |h| h.cpu_pct.into_iter().chain(h.mem_pct).reduce(f64::max));
+    measure(&mut measures, &pods.pending, "pending", |p| Some(p.duration_minutes as f64));
+    measure(&mut measures, &pods.failed, "failed", |f| Some(f.duration_minutes as f64));
+    measure(&mut measures, &pods.unready, "unready", |u| Some(u.duration_minutes as f64));
+    measure(&mut measures, &pods.stuck_terminating, "stuck_terminating", |s| Some((now - s.since).num_minutes() as f64));
+
+    let rules = &report.config.severity_rules;
+    let mut counts: BTreeMap<String, SeverityCounts> = BTreeMap::new();
+    for fingerprint in report.fingerprints() {
+        let section = fingerprint.split('/').next().unwrap_or_default();
+        let severity = if report.is_escalated(&fingerprint) {
+            Severity::Critical
+        } else {
+            finding_severity(rules, section, measures.get(&fingerprint).copied())
+        };
+        counts.entry(section.to_string()).or_default().add(severity, 1);
+    }
+    let nodes = &report.cluster_metrics;
+    for (section, count) in [
+        ("nodes", nodes.problematic_nodes.len()),
+        ("network_agents", nodes.network_agent_issues.len()),
+        ("operators", nodes.operator_issues.len()),
+        ("node_flapping", nodes.flapping_nodes.len()),
+        ("node_utilization", nodes.high_utilization_nodes.len()),
+        ("node_disk", nodes.node_disk_forecast.len()),
+    ] {
+        if count > 0 {
+            counts.entry(section.to_string()).or_default().add(finding_severity(rules, section, None), count);
+        }
+    }
+    counts
+}
+
+/// Findings per severity over all sections
+pub fn severity_totals(report: &HealthReport) -> SeverityCounts {
+    section_severities(report).values().fold(SeverityCounts::default(), |mut total, counts| {
+        total.info += counts.info;
+        total.warning += counts.warning;
+        total.critical += counts.critical;
+        total
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Config, HeavyUsagePod};
+
+    #[test]
+    fn test_finding_severity() {
+        assert_eq!(default_severity("oom"), Severity::Critical);
+        assert_eq!(default_severity("pending"), Severity::Warning);
+        assert_eq!(default_severity("nodes"), Severity::Critical);
+        assert_eq!(default_severity("resource_hygiene"), Severity::Info);
+
+        let rules = vec![
+            SeverityRule { section: "pending".to_string(), threshold: Some(30.0), severity: Severity::Critical },
+            SeverityRule { section: "nodes".to_string(), threshold: None, severity: Severity::Warning },
+        ];
+        assert_eq!(finding_severity(&rules, "pending", Some(45.0)), Severity::Critical);
+        assert_eq!(finding_severity(&rules, "pending", Some(30.0)), Severity::Warning);
+        assert_eq!(finding_severity(&rules, "nodes", None), Severity::Warning);
+        assert_eq!(finding_severity(&rules, "oom", None), Severity::Critical);
+    }
+
+    #[test]
+    fn test_section_severities() {
+        let rules = vec![SeverityRule { section: "usage".to_string(), threshold: Some(95.0), severity: Severity::Critical }];
+        let mut report = HealthReport::new(Config { severity_rules: rules, ..Default::default() });
+        for (pod, cpu_pct) in [("api-0", 97.0), ("api-1", 90.0), ("web-0", 86.0)] {
+            report.pod_metrics.heavy_usage.push(HeavyUsagePod {
+                labels: Default::default(),
+                namespace: "default".to_string(),
+                pod: pod.to_string(),
+                cpu_pct: Some(cpu_pct),
+                mem_pct: None,
+                cpu_millicores: 900,
+                memory_bytes: 0,
+            });
+        }
+
+        let severities = section_severities(&report);
+        assert_eq!(severities.len(), 1);
+        assert_eq!(severities["usage"], SeverityCounts { info: 0, warning: 2, critical: 1 });
+        assert_eq!(severities["usage"].highest(), Some(Severity::Critical));
+        assert_eq!(SeverityCounts::default().highest(), None);
+    }
+}
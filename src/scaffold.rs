@@ -0,0 +1,193 @@
+//! `generate analyzer <name>`: scaffolds a pod analyzer the way the built-in ones are
+//! laid out (module under `metrics/`, finding struct, Slack section, test fixture), so
+//! new checks start consistent. Run from the repository root.
+
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const ANALYZER_TEMPLATE: &str = r#"use k8s_openapi::api::core::v1::Pod;
+
+use crate::types::{Config, __TYPE__};
+
+/// __TITLE__ findings among the namespace's pods
+pub fn analyze___NAME___with_pods(namespace: &str, _cfg: &Config, pods: &[Pod]) -> Vec<__TYPE__> {
+    pods.iter()
+        .filter_map(|pod| {
+            let name = pod.metadata.name.as_ref()?;
+            let reason = check_pod(pod)?;
+            Some(__TYPE__ {
+                namespace: namespace.to_string(),
+                pod: name.clone(),
+                reason,
+            })
+        })
+        .collect()
+}
+
+/// Why the pod is a finding; `None` when it is fine. Replace with the actual check.
+fn check_pod(pod: &Pod) -> Option<String> {
+    let phase = pod.status.as_ref()?.phase.as_deref()?;
+    (phase == "Unknown").then(|| format!("phase {}", phase))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::List;
+
+    #[test]
+    fn test_analyze___NAME__() {
+        let pods: List<Pod> = serde_json::from_str(include_str!("../../tests/fixtures/__NAME__/pods.json")).unwrap();
+        let findings = analyze___NAME___with_pods("default", &Config::default(), &pods.items);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].pod, "lost-0");
+    }
+}
+"#;
+
+const FINDING_TEMPLATE: &str = r#"
+/// __TITLE__ finding of a pod
+#[derive(Debug, Clone, Serialize)]
+pub struct __TYPE__ {
+    pub namespace: String,
+    pub pod: String,
+    pub reason: String,
+}
+
+impl_scoped!(__TYPE__ => pod);
+"#;
+
+const SECTION_TEMPLATE: &str = r#"/// __TITLE__ section; `None` when there are no findings
+pub fn build___NAME___section(findings: &[crate::types::__TYPE__]) -> Option<serde_json::Value> {
+    if findings.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = findings.iter().map(|f| format!("• `{}/{}`: {}", f.namespace, f.pod, f.reason)).collect();
+    Some(Block::section(format!("*__TITLE__*\n{}", lines.join("\n"))).with_block_id("__NAME__").into())
+}
+
+"#;
+
+const FIXTURE_TEMPLATE: &str = r#"{
+  "apiVersion": "v1",
+  "kind": "PodList",
+  "metadata": {},
+  "items": [
+    {
+      "metadata": {"name": "web-0", "namespace": "default"},
+      "spec": {"containers": [{"name": "app", "image": "example/web:1.0"}]},
+      "status": {"phase": "Running"}
+    },
+    {
+      "metadata": {"name": "lost-0", "namespace": "default"},
+      "spec": {"containers": [{"name": "app", "image": "example/web:1.0"}]},
+      "status": {"phase": "Unknown"}
+    }
+  ]
+}
+"#;
+
+/// Wiring left to do by hand, printed after scaffolding
+const NEXT_STEPS: &str = "\
+Next steps:
+  1. Add a `__NAME__` field to PodMetrics (collector) and AllNamespacePodMetrics (report), and call
+     metrics::__NAME__::analyze___NAME___with_pods in collect_namespace_metrics_until
+  2. Add \"__NAME__\" to REPORT_SECTIONS, report_document, HealthReport::fingerprints and retain_findings
+  3. Extend build_report_payload with build___NAME___section and the summary with a count
+  4. Replace check_pod and the fixture with the actual check";
+
+/// Scaffold analyzer `name` (snake_case) under `root`; returns the files created or
+/// changed. Nothing is written when the analyzer already exists.
+pub fn scaffold_analyzer(root: &Path, name: &str) -> Result<Vec<PathBuf>> {
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !valid {
+        return Err(anyhow!("Invalid analyzer name '{}' (expected snake_case, e.g. image_pull)", name));
+    }
+    let module = root.join("src/metrics").join(format!("{}.rs", name));
+    if module.exists() {
+        return Err(anyhow!("{} already exists", module.display()));
+    }
+    let render = |template: &str| {
+        template
+            .replace("__NAME__", name)
+            .replace("__TYPE__", &format!("{}Info", camel_case(name)))
+            .replace("__TITLE__", &title_case(name))
+    };
+
+    let fixture = root.join("tests/fixtures").join(name).join("pods.json");
+    fs::create_dir_all(fixture.parent().unwrap_or(root)).with_context(|| format!("Failed to create {}", fixture.display()))?;
+    write(&fixture, FIXTURE_TEMPLATE.to_string())?;
+    write(&module, render(ANALYZER_TEMPLATE))?;
+
+    let mod_rs = root.join("src/metrics/mod.rs");
+    let content = read(&mod_rs)?;
+    let last_mod = content.lines().rev().find(|l| l.starts_with("pub mod ")).ok_or_else(|| anyhow!("No modules in {}", mod_rs.display()))?;
+    write(&mod_rs, content.replacen(last_mod, &format!("{}\npub mod {};", last_mod, name), 1))?;
+
+    let types_rs = root.join("src/types.rs");
+    write(&types_rs, read(&types_rs)? + &render(FINDING_TEMPLATE))?;
+
+    let slack_rs = root.join("src/slack.rs");
+    let mut content = read(&slack_rs)?;
+    let tests = content.find("#[cfg(test)]\nmod tests").ok_or_else(|| anyhow!("No tests module in {}", slack_rs.display()))?;
+    content.insert_str(tests, &render(SECTION_TEMPLATE));
+    write(&slack_rs, content)?;
+
+    println!("{}", render(NEXT_STEPS));
+    Ok(vec![module, fixture, mod_rs, types_rs, slack_rs])
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("Failed to read {} (run from the repository root)", path.display()))
+}
+
+fn write(path: &Path, content: String) -> Result<()> {
+    fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// `image_pull` → `ImagePull`
+fn camel_case(name: &str) -> String {
+    name.split('_').filter(|w| !w.is_empty()).map(capitalize).collect()
+}
+
+/// `image_pull` → `Image pull`
+fn title_case(name: &str) -> String {
+    capitalize(&name.replace('_', " "))
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map(|c| c.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scaffold_analyzer() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/metrics")).unwrap();
+        fs::write(root.join("src/metrics/mod.rs"), "// Metrics collection modules\npub mod pods;\npub mod custom;\n\npub use pods::analyze_restarts;\n").unwrap();
+        fs::write(root.join("src/types.rs"), "pub struct Config;\n").unwrap();
+        fs::write(root.join("src/slack.rs"), "pub fn build() {}\n\n#[cfg(test)]\nmod tests {}\n").unwrap();
+
+        let files = scaffold_analyzer(root, "image_pull").unwrap();
+        assert_eq!(files.len(), 5);
+        let module = fs::read_to_string(root.join("src/metrics/image_pull.rs")).unwrap();
+        assert!(module.contains("pub fn analyze_image_pull_with_pods(namespace: &str, _cfg: &Config, pods: &[Pod]) -> Vec<ImagePullInfo>"));
+        assert!(module.contains("include_str!(\"../../tests/fixtures/image_pull/pods.json\")"));
+        assert!(fs::read_to_string(root.join("src/metrics/mod.rs")).unwrap().contains("pub mod custom;\npub mod image_pull;\n\npub use"));
+        assert!(fs::read_to_string(root.join("src/types.rs")).unwrap().contains("pub struct ImagePullInfo {"));
+        let slack = fs::read_to_string(root.join("src/slack.rs")).unwrap();
+        assert!(slack.contains("*Image pull*"));
+        assert!(slack.find("pub fn build_image_pull_section").unwrap() < slack.find("#[cfg(test)]").unwrap());
+        assert!(root.join("tests/fixtures/image_pull/pods.json").exists());
+
+        assert!(scaffold_analyzer(root, "image_pull").unwrap_err().to_string().contains("already exists"));
+        assert!(scaffold_analyzer(root, "ImagePull").unwrap_err().to_string().contains("Invalid analyzer name"));
+    }
+}
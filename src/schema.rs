@@ -0,0 +1,346 @@
+use anyhow::{anyhow, Context, Result};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::config::{load_config_with_env, MockEnvironment};
+use SettingType::{Boolean, Integer, Number, OneOf, Text};
+
+/// Value format of a setting; every setting is an environment variable, so a string
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SettingType {
+    Text,
+    Boolean,
+    Integer,
+    Number,
+    OneOf(&'static [&'static str]),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Setting {
+    pub name: &'static str,
+    pub kind: SettingType,
+    /// Also accepted as `<name>_FILE` holding the path of a mounted Secret
+    pub secret: bool,
+    pub description: &'static str,
+}
+
+const fn setting(name: &'static str, kind: SettingType, description: &'static str) -> Setting {
+    Setting { name, kind, secret: false, description }
+}
+
+const fn secret(name: &'static str, description: &'static str) -> Setting {
+    Setting { name, kind: Text, secret: true, description }
+}
+
+const BOOLEAN_VALUES: &[&str] = &["1", "0", "true", "false", "TRUE", "FALSE", "True", "False"];
+
+/// Every setting read by `load_config` and `SecretResolver::from_env`
+pub const SETTINGS: &[Setting] = &[
+    setting("NAMESPACES", Text, "Comma-separated namespaces to report on; required unless TENANT is set"),
+    setting("TENANT", Text, "Tenant of TENANTS_FILE to report on"),
+    setting("TENANTS_FILE", Text, "Path of the tenants JSON file"),
+    secret("SLACK_WEBHOOK_URL", "Slack incoming webhook (or secret reference); optional with SLACK_BOT_TOKEN, SMTP_HOST, TELEGRAM_BOT_TOKEN, PUSHGATEWAY_URL, GENERIC_WEBHOOK_URL, METRICS_TEXTFILE, CSV_EXPORT, HTML_REPORT_FILE, METRICS_LISTEN_ADDR or OUTPUT_FORMAT"),
+    setting("THRESHOLD_PERCENT", Number, "CPU/memory utilization reported as heavy usage"),
+    setting("RESTART_GRACE_MINUTES", Integer, "Minutes a restart is ignored for"),
+    setting("PENDING_GRACE_MINUTES", Integer, "Minutes a pod may stay Pending before it is reported"),
+    setting("TERMINATING_GRACE_MINUTES", Integer, "Minutes an object may stay Terminating before it is reported"),
+    setting("CLUSTER_NAME", Text, "Cluster name shown in the report header"),
+    setting("DATACENTER_NAME", Text, "Datacenter name shown in the report header"),
+    setting("FAIL_IF_NO_METRICS", Boolean, "Fail the run when metrics-server is unavailable"),
+    setting("RBAC_SCOPE", OneOf(&["", "auto", "cluster", "namespace"]), "Run the cluster-scoped analyzers, or detect whether they are allowed"),
+    setting("NAMESPACE_TIMEOUT_SECONDS", Integer, "Time budget of one namespace's analyzers (0 disables)"),
+    setting("RUN_TIMEOUT_SECONDS", Integer, "Time budget of the whole collection (0 disables)"),
+    setting("RUN_MODE", OneOf(&["", "once", "daemon"]), "once reports and exits; daemon keeps reporting every INTERVAL_SECONDS"),
+    setting("INTERVAL_SECONDS", Integer, "Seconds between the runs of a daemon"),
+    setting("MEMORY_WARN_PERCENT", Number, "Share of its memory limit at which the reporter warns about its own usage (0 disables)"),
+    setting("METRICS_STALENESS_MINUTES", Integer, "Age at which pod metrics count as stale"),
+    setting("NODE_POD_COUNT_ALL_NAMESPACES", Boolean, "Count pods of all namespaces in node figures"),
+    setting("NODE_DISK_FORECAST", Boolean, "Forecast node disk usage against eviction thresholds"),
+    setting("NODE_DISK_MARGIN_PERCENT", Number, "Margin below the eviction threshold at which node disk is reported"),
+    setting("STATE_FILE", Text, "Path of the state file keeping snoozes and run history"),
+    setting("NOTIFY_RESOLVED", Boolean, "Report findings resolved since the previous run"),
+    setting("ALLOW_API_WRITES", Boolean, "Let the API client send requests other than get/list/watch"),
+    setting("AUDIT_LOG_FILE", Text, "File each run appends the API verbs it used to (JSON lines)"),
+    setting("API_RECORD_DIR", Text, "Directory the run's API responses are recorded to"),
+    setting("API_REPLAY_DIR", Text, "Directory of recorded API responses to run against instead of a cluster"),
+    setting("SINK_TIMEOUT_SECONDS", Integer, "Timeout of a webhook delivery"),
+    setting("SINK_FAILURE_THRESHOLD", Integer, "Consecutive failures that open a sink's circuit breaker"),
+    setting("SINK_COOLDOWN_MINUTES", Integer, "Minutes an open circuit breaker skips its sink"),
+    setting("WEBHOOK_CA_BUNDLE", Text, "Path of a PEM bundle trusted for webhooks"),
+    secret("WEBHOOK_SIGNING_SECRET", "Secret signing webhook payloads"),
+    setting("WEBHOOK_SIGNING_ALGORITHM", OneOf(&["sha256", "sha512"]), "HMAC algorithm of the payload signature"),
+    setting("SMTP_HOST", Text, "SMTP relay the report email is sent through"),
+    setting("SMTP_PORT", Integer, "Port of the relay; 587, 465 or 25 by SMTP_TLS"),
+    setting("SMTP_TLS", OneOf(&["", "starttls", "tls", "none"]), "How the relay connection is secured"),
+    setting("SMTP_USERNAME", Text, "User for AUTH PLAIN"),
+    secret("SMTP_PASSWORD", "Password for AUTH PLAIN (or secret reference)"),
+    setting("SMTP_CA_BUNDLE", Text, "Path of a PEM bundle trusted for the relay"),
+    setting("EMAIL_FROM", Text, "Sender of the report email"),
+    setting("EMAIL_TO", Text, "Comma-separated recipients of the report email"),
+    secret("GENERIC_WEBHOOK_URL", "Endpoint receiving the whole report as JSON every run (or secret reference)"),
+    secret("GENERIC_WEBHOOK_HEADERS", "Extra headers for GENERIC_WEBHOOK_URL, one 'Name: value' per line"),
+    secret("SLACK_BOT_TOKEN", "Slack bot token (or secret reference) posting the report through the Web API instead of a webhook"),
+    setting("SLACK_CHANNEL", Text, "Channel ID the bot posts the report to, with its sections as thread replies"),
+    secret("TELEGRAM_BOT_TOKEN", "Telegram bot token (or secret reference) the report is sent with"),
+    setting("TELEGRAM_CHAT_ID", Text, "Telegram chat the report is sent to, e.g. -1001234567890 or @channel"),
+    setting("HOOK_PRE_COLLECTION", Text, "Shell command run before collection; a failure aborts the run"),
+    setting("HOOK_POST_REPORT", Text, "Shell command given the report document on stdin; may print JSON notes or a change freeze to attach"),
+    setting("HOOK_PRE_SEND", Text, "Shell command given the report document on stdin; a non-zero exit vetoes the notifications"),
+    setting("HOOK_TIMEOUT_SECONDS", Integer, "Time a hook command may take before it is killed"),
+    secret("PUSHGATEWAY_URL", "Prometheus Pushgateway (or secret reference) the report's gauges are pushed to every run"),
+    setting("PUSHGATEWAY_JOB", Text, "job label of the pushed gauges (default kube-health-reporter)"),
+    setting("PUSHGATEWAY_INSTANCE", Text, "instance label of the pushed gauges (default: the cluster name)"),
+    secret("PAGERDUTY_ROUTING_KEY", "PagerDuty Events API v2 routing key (or secret reference)"),
+    setting("METRICS_TEXTFILE", Text, "File the findings are written to as OpenMetrics every run, for a textfile collector"),
+    secret("CHANGE_FREEZE_SOURCE", "Change-freeze flag: configmap:<namespace>/<name>, namespace:<name> or an http(s) URL"),
+    setting("CSV_EXPORT", Text, "File the findings are written to as CSV every run; '-' for stdout"),
+    setting("HTML_REPORT_FILE", Text, "File the report is written to as a self-contained HTML page every run"),
+    setting("METRICS_LISTEN_ADDR", Text, "Address (e.g. 0.0.0.0:9090) to serve Prometheus gauges on /metrics from; implies RUN_MODE=daemon"),
+    setting("OUTPUT_FORMAT", OneOf(&["", "json", "yaml"]), "Format the whole report is printed to stdout in every run"),
+    setting("PAGERDUTY_SECTIONS", Text, "Comma-separated sections whose findings trigger incidents"),
+    setting("ONCALL_PROVIDER", OneOf(&["", "pagerduty", "opsgenie"]), "Schedule provider of the on-call mentioned for critical findings"),
+    setting("ONCALL_SCHEDULE_ID", Text, "On-call schedule ID"),
+    secret("ONCALL_API_TOKEN", "Read-only PagerDuty or Opsgenie API token (or secret reference)"),
+    setting("ONCALL_API_URL", Text, "API base URL, e.g. https://api.eu.opsgenie.com; defaults to the US region"),
+    setting("ONCALL_SLACK_USERS", Text, "Comma-separated email=Slack member ID pairs, so mentions notify"),
+    setting("SLACK_MENTION", Text, "Mention for critical findings when nobody on call is resolved, e.g. <!subteam^S0123>"),
+    setting("SLACK_SECTION_MENTIONS", Text, "Comma-separated section=IDs pairs mentioned when the section has critical findings, e.g. nodes=S0123ABC"),
+    setting("REDACTION_PATTERNS", Text, "Regexes, one per line, redacted from messages and logs"),
+    setting("CLUSTERS", Text, "Comma-separated kubeconfig contexts to report on in one run"),
+    secret("FEDERATION_WEBHOOK_URL", "Webhook receiving the cross-cluster summary"),
+    setting("POD_SECURITY_LEVEL", OneOf(&["", "privileged", "baseline", "restricted"]), "Pod Security Standard pods are checked against"),
+    setting("POD_SECURITY_NAMESPACE_LEVELS", Text, "Per-namespace levels, e.g. payments=restricted,legacy=privileged"),
+    setting("LIMIT_RANGE_EDGE_PERCENT", Number, "Share of a LimitRange bound at which a container is at the edge"),
+    setting("CUSTOM_RESOURCE_CHECKS", Text, "group[/version]/Kind: Type=Status entries separated by ';'"),
+    setting("NAMESPACE_QUOTA_CHECK", Boolean, "Report namespaces without ResourceQuota or LimitRange"),
+    setting("HEALTH_SUPPRESSIONS", Boolean, "Honour health-reporter suppression annotations"),
+    setting("OMIT_EMPTY_SECTIONS", Boolean, "Leave sections without findings out of the message"),
+    setting("RECOMMENDATIONS_HISTORY_PATH", Text, "Path of the usage history for rightsizing recommendations"),
+    setting("LIMIT_REQUEST_RATIO_THRESHOLD", Number, "Limit/request ratio reported as resource hygiene"),
+    setting("POD_CHURN_WINDOW_MINUTES", Integer, "Lookback window for pod churn"),
+    setting("POD_CHURN_THRESHOLD", Integer, "Pod replacements within the window at which a workload is reported"),
+    setting("EVENT_FLOOD_WINDOW_MINUTES", Integer, "Lookback window for Warning events"),
+    setting("EVENT_FLOOD_THRESHOLD", Integer, "Warning events within the window at which a namespace is reported"),
+    setting("JOB_BACKOFF_WARNING_PERCENT", Number, "Share of backoffLimit at which a Job is reported"),
+    setting("CRONJOB_ACTIVE_JOBS_THRESHOLD", Integer, "Active Jobs of a CronJob at which it is reported"),
+    setting("FINISHED_OBJECTS_THRESHOLD", Integer, "Finished pods/Jobs in a namespace at which it is reported"),
+    setting("READINESS_FLAP_WINDOW_MINUTES", Integer, "Lookback window for readiness probe failures"),
+    setting("READINESS_FLAP_THRESHOLD", Integer, "Readiness probe failures within the window at which a pod is reported"),
+    setting("OBJECT_COUNT_CHECK", Boolean, "Report resources with many stored objects"),
+    setting("OBJECT_COUNT_THRESHOLD", Integer, "Stored objects of a resource at which it is reported"),
+    setting("OBJECT_COUNT_GROWTH_PERCENT", Number, "Growth since the previous run at which a resource is reported"),
+    setting("NODE_FLAP_WINDOW_MINUTES", Integer, "Lookback window for node NotReady transitions"),
+    setting("NODE_FLAP_THRESHOLD", Integer, "NotReady transitions within the window at which a node is reported"),
+    setting("ENDPOINT_CHURN_WINDOW_MINUTES", Integer, "Lookback window for Service endpoint churn"),
+    setting("ENDPOINT_CHURN_THRESHOLD", Integer, "Endpoint changes within the window at which a Service is reported"),
+    setting("DUAL_STACK_CHECK", Boolean, "Report Services and pods lacking an IPv4 or IPv6 address"),
+    setting("NETWORK_AGENT_DAEMONSETS", Text, "Comma-separated namespace/name of DaemonSets required on every node"),
+    setting("OPERATOR_CHECK", Boolean, "Check OLM Subscriptions and operator Deployments"),
+    setting("CRASH_LOG_LINES", Integer, "Log lines attached to crashlooping containers; 0 disables"),
+    setting("OWNERSHIP_FILE", Text, "Path of the team ownership file"),
+    setting("ISSUE_LABELS", Text, "Comma-separated pod labels shown with findings"),
+    setting("SECTION_ORDER", Text, "Comma-separated report sections to show, in order"),
+    setting("PRIORITY_TOP_N", Integer, "Findings listed under \"Act on these first\"; 0 hides the block"),
+    setting("SEVERITY_RULES", Text, "Comma-separated section[>threshold]=severity overrides, e.g. usage>95%=critical,pending>30m=critical"),
+    setting("SECRETS_CACHE_SECONDS", Integer, "Seconds resolved secret references are cached"),
+    setting("VAULT_ADDR", Text, "Vault address for vault:// references"),
+    setting("VAULT_NAMESPACE", Text, "Vault Enterprise namespace"),
+    secret("VAULT_TOKEN", "Vault token"),
+    setting("VAULT_K8S_ROLE", Text, "Vault role for Kubernetes auth, instead of a token"),
+    setting("VAULT_K8S_MOUNT", Text, "Mount of the Vault Kubernetes auth method"),
+];
+
+/// JSON Schema (draft 2020-12) of the settings, as a JSON object of environment variables:
+/// the `data` of the ConfigMap or the `env` of the container. The usual AWS credential
+/// chain variables (`AWS_*`) are allowed as well.
+pub fn settings_schema() -> Value {
+    let mut properties = Map::new();
+    for setting in SETTINGS {
+        let mut property = json!({ "type": "string", "description": setting.description });
+        match setting.kind {
+            Text => {}
+            Boolean => property["enum"] = json!(BOOLEAN_VALUES),
+            Integer => property["pattern"] = json!(r"^\s*-?[0-9]+\s*$"),
+            Number => property["pattern"] = json!(r"^\s*-?[0-9]+(\.[0-9]+)?\s*$"),
+            OneOf(values) => property["enum"] = json!(values),
+        }
+        properties.insert(setting.name.to_string(), property);
+        if setting.secret {
+            properties.insert(
+                format!("{}_FILE", setting.name),
+                json!({ "type": "string", "description": format!("Path of a file holding {}", setting.name) }),
+            );
+        }
+    }
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "kube-health-reporter settings",
+        "type": "object",
+        "properties": properties,
+        "patternProperties": { "^AWS_": { "type": "string" } },
+        "additionalProperties": false,
+    })
+}
+
+/// Read settings from a JSON object (e.g. `kubectl get configmap -o jsonpath='{.data}'`)
+/// or an env file of `NAME=value` lines; numbers and booleans in JSON are taken as written
+pub fn load_settings_file(path: &Path) -> Result<BTreeMap<String, String>> {
+    let data = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read settings file {}", path.display()))?;
+    if data.trim_start().starts_with('{') {
+        let object: Map<String, Value> = serde_json::from_str(&data)
+            .with_context(|| format!("Invalid settings file {}", path.display()))?;
+        return object
+            .into_iter()
+            .map(|(name, value)| match value {
+                Value::String(s) => Ok((name, s)),
+                Value::Number(_) | Value::Bool(_) => Ok((name, value.to_string())),
+                _ => Err(anyhow!("Invalid settings file {}: {} must be a string", path.display(), name)),
+            })
+            .collect();
+    }
+    data.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (name, value) = line
+                .split_once('=')
+                .ok_or_else(|| anyhow!("Invalid settings file {}: expected NAME=value, got '{}'", path.display(), line))?;
+            let value = value.trim();
+            let unquoted = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+                .unwrap_or(value);
+            Ok((name.trim().to_string(), unquoted.to_string()))
+        })
+        .collect()
+}
+
+/// Problems with the settings, empty when they are valid: unknown names, values not
+/// matching their type (which `load_config` would otherwise silently replace by the
+/// default for some settings), then whatever `load_config` rejects. Secrets are never
+/// part of the checked files, so a placeholder stands in for a missing webhook.
+pub fn validate_settings(settings: &BTreeMap<String, String>) -> Vec<String> {
+    let mut problems = Vec::new();
+    for (name, value) in settings {
+        let known = SETTINGS.iter().find(|s| {
+            s.name == name || (s.secret && name.strip_suffix("_FILE") == Some(s.name))
+        });
+        let Some(setting) = known else {
+            if !name.starts_with("AWS_") {
+                problems.push(format!("{}: unknown setting", name));
+            }
+            continue;
+        };
+        if setting.name != name {
+            continue;
+        }
+        let expected = match setting.kind {
+            Text => None,
+            Boolean => (!BOOLEAN_VALUES.contains(&value.as_str())).then(|| "a boolean (true or false)".to_string()),
+            Integer => value.trim().parse::<i64>().is_err().then(|| "an integer".to_string()),
+            Number => value.trim().parse::<f64>().is_err().then(|| "a number".to_string()),
+            OneOf(values) => (!values.contains(&value.trim())).then(|| {
+                let values: Vec<&str> = values.iter().copied().filter(|v| !v.is_empty()).collect();
+                format!("one of {}", values.join(", "))
+            }),
+        };
+        if let Some(expected) = expected {
+            problems.push(format!("{}: '{}' is not {}", name, value, expected));
+        }
+    }
+    if !problems.is_empty() {
+        return problems;
+    }
+
+    let mut env = MockEnvironment::new();
+    for (name, value) in settings {
+        // Secret files are mounted in the cluster only
+        let secret_file = SETTINGS.iter().any(|s| s.secret && name.strip_suffix("_FILE") == Some(s.name));
+        if !secret_file {
+            env.set_var(name.as_str(), value.as_str());
+        }
+    }
+    if !settings.contains_key("SLACK_WEBHOOK_URL") {
+        env.set_var("SLACK_WEBHOOK_URL", "https://hooks.slack.com/services/placeholder");
+    }
+    if let Err(e) = load_config_with_env(&env) {
+        problems.push(format!("{:#}", e));
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(values: &[(&str, &str)]) -> BTreeMap<String, String> {
+        values.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_settings_schema() {
+        let schema = settings_schema();
+        assert_eq!(schema["additionalProperties"], false);
+        assert_eq!(schema["properties"]["NAMESPACES"]["type"], "string");
+        assert!(schema["properties"]["OPERATOR_CHECK"]["enum"].as_array().unwrap().contains(&json!("true")));
+        assert_eq!(schema["properties"]["WEBHOOK_SIGNING_ALGORITHM"]["enum"], json!(["sha256", "sha512"]));
+        assert!(schema["properties"]["SLACK_WEBHOOK_URL_FILE"].is_object());
+        assert!(schema["properties"]["THRESHOLD_PERCENT"]["pattern"].is_string());
+    }
+
+    #[test]
+    fn test_validate_settings() {
+        let valid = settings(&[
+            ("NAMESPACES", "default,payments"),
+            ("THRESHOLD_PERCENT", "90"),
+            ("OPERATOR_CHECK", "true"),
+            ("SLACK_WEBHOOK_URL_FILE", "/var/run/secrets/slack/url"),
+            ("AWS_REGION", "eu-west-1"),
+        ]);
+        assert!(validate_settings(&valid).is_empty());
+
+        let invalid = settings(&[
+            ("NAMESPACES", "default"),
+            ("OPERATOR_CHEK", "true"),
+            ("POD_CHURN_THRESHOLD", "ten"),
+            ("DUAL_STACK_CHECK", "yes"),
+            ("POD_SECURITY_LEVEL", "strict"),
+        ]);
+        assert_eq!(
+            validate_settings(&invalid),
+            vec![
+                "DUAL_STACK_CHECK: 'yes' is not a boolean (true or false)",
+                "OPERATOR_CHEK: unknown setting",
+                "POD_CHURN_THRESHOLD: 'ten' is not an integer",
+                "POD_SECURITY_LEVEL: 'strict' is not one of privileged, baseline, restricted",
+            ]
+        );
+
+        // Passes the type checks, rejected by load_config
+        let problems = validate_settings(&settings(&[("NAMESPACES", "default"), ("SECTION_ORDER", "nodes,bogus")]));
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].starts_with("Invalid SECTION_ORDER: unknown section 'bogus'"));
+        assert_eq!(validate_settings(&settings(&[])), vec!["NAMESPACES env var must be set (comma-separated)"]);
+    }
+
+    #[test]
+    fn test_load_settings_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let env_file = dir.path().join("reporter.env");
+        std::fs::write(&env_file, "# reporter\nNAMESPACES=default\nexport CLUSTER_NAME=\"prod eu\"\n").unwrap();
+        let loaded = load_settings_file(&env_file).unwrap();
+        assert_eq!(loaded, settings(&[("NAMESPACES", "default"), ("CLUSTER_NAME", "prod eu")]));
+
+        let json_file = dir.path().join("reporter.json");
+        std::fs::write(&json_file, r#"{"NAMESPACES": "default", "CRASH_LOG_LINES": 20, "OPERATOR_CHECK": true}"#).unwrap();
+        let loaded = load_settings_file(&json_file).unwrap();
+        assert_eq!(
+            loaded,
+            settings(&[("NAMESPACES", "default"), ("CRASH_LOG_LINES", "20"), ("OPERATOR_CHECK", "true")])
+        );
+    }
+}
@@ -0,0 +1,522 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use ring::{digest, hmac};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+use crate::config::EnvironmentProvider;
+
+const SERVICE_ACCOUNT_TOKEN: &str = "/var/run/secrets/kubernetes.io/serviceaccount/token";
+/// Temporary credentials (STS, Pod Identity, Vault logins) are renewed this long before
+/// they expire, so a long-running daemon never sends a request with expired ones
+const CREDENTIAL_REFRESH_MARGIN: Duration = Duration::from_secs(300);
+
+/// A webhook URL or token kept in a secret manager instead of the environment:
+/// `vault:<path>#<field>` or `aws-sm:<secret-id>[#<json-key>]`
+#[derive(Debug, Clone, PartialEq)]
+pub enum SecretRef {
+    /// Field of a Vault secret; KV v2 paths include `data/`, e.g. `secret/data/reporter`
+    Vault { path: String, field: String },
+    /// AWS Secrets Manager secret; with a key, the secret string is a JSON object
+    AwsSecretsManager { secret_id: String, key: Option<String> },
+}
+
+impl SecretRef {
+    /// `None` for plain values, which are used as they are
+    pub fn parse(value: &str) -> Option<Result<Self>> {
+        if let Some(rest) = value.strip_prefix("vault:") {
+            return Some(match rest.split_once('#') {
+                Some((path, field)) if !path.is_empty() && !field.is_empty() => Ok(SecretRef::Vault {
+                    path: path.trim_matches('/').to_string(),
+                    field: field.to_string(),
+                }),
+                _ => Err(anyhow!("Invalid Vault reference {} (expected vault:<path>#<field>)", value)),
+            });
+        }
+        if let Some(rest) = value.strip_prefix("aws-sm:") {
+            let (secret_id, key) = match rest.split_once('#') {
+                Some((id, key)) => (id, Some(key.to_string())),
+                None => (rest, None),
+            };
+            if secret_id.is_empty() {
+                return Some(Err(anyhow!("Invalid AWS Secrets Manager reference {}", value)));
+            }
+            return Some(Ok(SecretRef::AwsSecretsManager { secret_id: secret_id.to_string(), key }));
+        }
+        None
+    }
+}
+
+#[derive(Debug, Clone)]
+struct VaultSettings {
+    addr: String,
+    namespace: Option<String>,
+    token: Option<String>,
+    /// Role for the Kubernetes auth method, used when no token is given
+    kubernetes_role: Option<String>,
+    kubernetes_mount: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AwsCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct AwsSettings {
+    region: Option<String>,
+    static_credentials: Option<AwsCredentials>,
+    /// IRSA: `AWS_WEB_IDENTITY_TOKEN_FILE` and `AWS_ROLE_ARN`
+    web_identity: Option<(String, String)>,
+    role_session_name: String,
+    /// EKS Pod Identity: `AWS_CONTAINER_CREDENTIALS_FULL_URI` and its authorization token
+    container_credentials: Option<(String, Option<String>)>,
+}
+
+/// A backend credential and when it expires; `None` for ones that do not
+#[derive(Debug, Clone)]
+struct Expiring<T> {
+    value: T,
+    expires: Option<Instant>,
+}
+
+impl<T: Clone> Expiring<T> {
+    fn new(value: T, expires_at: Option<DateTime<Utc>>) -> Self {
+        // An expiration in the past (or a skewed clock) makes the credential stale at once
+        let expires = expires_at.map(|at| Instant::now() + (at - Utc::now()).to_std().unwrap_or_default());
+        Self { value, expires }
+    }
+
+    /// The value, unless it expires within `CREDENTIAL_REFRESH_MARGIN`
+    fn fresh(&self) -> Option<T> {
+        match self.expires {
+            Some(expires) if expires.saturating_duration_since(Instant::now()) < CREDENTIAL_REFRESH_MARGIN => None,
+            _ => Some(self.value.clone()),
+        }
+    }
+}
+
+/// Resolves secret references at startup. Values are cached for `SECRETS_CACHE_SECONDS`
+/// and temporary backend credentials until shortly before they expire; `invalidate` drops
+/// both so a reload (SIGHUP in daemon mode) picks up rotated secrets.
+pub struct SecretResolver {
+    client: reqwest::Client,
+    vault: Option<VaultSettings>,
+    aws: AwsSettings,
+    ttl: Duration,
+    cache: Mutex<HashMap<String, (String, Instant)>>,
+    vault_token: Mutex<Option<Expiring<String>>>,
+    aws_credentials: Mutex<Option<Expiring<AwsCredentials>>>,
+}
+
+impl SecretResolver {
+    /// Backends are configured with their usual variables: `VAULT_ADDR`, `VAULT_TOKEN`
+    /// (or `VAULT_K8S_ROLE` for Kubernetes auth), `AWS_REGION` and the AWS credential chain
+    pub fn from_env<E: EnvironmentProvider>(env: &E, client: reqwest::Client) -> Result<Self> {
+        let vault = env.get_var("VAULT_ADDR").filter(|a| !a.is_empty()).map(|addr| -> Result<VaultSettings> {
+            Ok(VaultSettings {
+                addr: addr.trim_end_matches('/').to_string(),
+                namespace: env.get_var("VAULT_NAMESPACE").filter(|n| !n.is_empty()),
+                token: env.get_secret("VAULT_TOKEN")?.filter(|t| !t.is_empty()),
+                kubernetes_role: env.get_var("VAULT_K8S_ROLE").filter(|r| !r.is_empty()),
+                kubernetes_mount: env.get_var("VAULT_K8S_MOUNT").unwrap_or_else(|| "kubernetes".to_string()),
+            })
+        });
+        let static_credentials = match (env.get_var("AWS_ACCESS_KEY_ID"), env.get_secret("AWS_SECRET_ACCESS_KEY")?) {
+            (Some(access_key_id), Some(secret_access_key)) => Some(AwsCredentials {
+                access_key_id,
+                secret_access_key,
+                session_token: env.get_var("AWS_SESSION_TOKEN"),
+            }),
+            _ => None,
+        };
+        let container_token = match env.get_var("AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE") {
+            Some(path) => Some(
+                std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE {}", path))?
+                    .trim()
+                    .to_string(),
+            ),
+            None => env.get_var("AWS_CONTAINER_AUTHORIZATION_TOKEN"),
+        };
+        let aws = AwsSettings {
+            region: env.get_var("AWS_REGION").or_else(|| env.get_var("AWS_DEFAULT_REGION")),
+            static_credentials,
+            web_identity: env.get_var("AWS_WEB_IDENTITY_TOKEN_FILE").zip(env.get_var("AWS_ROLE_ARN")),
+            role_session_name: env.get_var("AWS_ROLE_SESSION_NAME").unwrap_or_else(|| "kube-health-reporter".to_string()),
+            container_credentials: env.get_var("AWS_CONTAINER_CREDENTIALS_FULL_URI").map(|uri| (uri, container_token)),
+        };
+        let ttl = env.get_var("SECRETS_CACHE_SECONDS")
+            .unwrap_or_else(|| "300".to_string())
+            .parse()
+            .context("Invalid SECRETS_CACHE_SECONDS")?;
+        Ok(Self {
+            client,
+            vault: vault.transpose()?,
+            aws,
+            ttl: Duration::from_secs(ttl),
+            cache: Mutex::new(HashMap::new()),
+            vault_token: Mutex::new(None),
+            aws_credentials: Mutex::new(None),
+        })
+    }
+
+    /// The secret behind `value` when it is a reference, otherwise `value` itself
+    pub async fn resolve(&self, value: &str) -> Result<String> {
+        let reference = match SecretRef::parse(value) {
+            None => return Ok(value.to_string()),
+            Some(reference) => reference?,
+        };
+        if let Some((secret, fetched)) = self.cache.lock().unwrap().get(value) {
+            if fetched.elapsed() < self.ttl {
+                return Ok(secret.clone());
+            }
+        }
+        let secret = match &reference {
+            SecretRef::Vault { path, field } => self.read_vault(path, field).await,
+            SecretRef::AwsSecretsManager { secret_id, key } => self.read_aws_secret(secret_id, key.as_deref()).await,
+        }
+        .with_context(|| format!("Failed to resolve {}", value))?;
+        debug!("Resolved secret reference {}", value);
+        self.cache.lock().unwrap().insert(value.to_string(), (secret.clone(), Instant::now()));
+        Ok(secret)
+    }
+
+    /// Forget cached secrets and backend credentials
+    pub fn invalidate(&self) {
+        self.cache.lock().unwrap().clear();
+        *self.vault_token.lock().unwrap() = None;
+        *self.aws_credentials.lock().unwrap() = None;
+    }
+
+    async fn read_vault(&self, path: &str, field: &str) -> Result<String> {
+        let vault = self.vault.as_ref().ok_or_else(|| anyhow!("VAULT_ADDR is not set"))?;
+        let token = self.vault_token(vault).await?;
+        let mut request = self.client.get(format!("{}/v1/{}", vault.addr, path)).header("X-Vault-Token", token);
+        if let Some(namespace) = &vault.namespace {
+            request = request.header("X-Vault-Namespace", namespace);
+        }
+        let body: serde_json::Value = request.send().await?.error_for_status()?.json().await?;
+        // KV v2 nests the secret under data.data, KV v1 and other engines under data
+        let data = match body["data"].get("data") {
+            Some(inner) if inner.is_object() => inner,
+            _ => &body["data"],
+        };
+        data.get(field)
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("Vault secret {} has no field {}", path, field))
+    }
+
+    async fn vault_token(&self, vault: &VaultSettings) -> Result<String> {
+        if let Some(token) = &vault.token {
+            return Ok(token.clone());
+        }
+        if let Some(token) = self.vault_token.lock().unwrap().as_ref().and_then(Expiring::fresh) {
+            return Ok(token);
+        }
+        let role = vault.kubernetes_role.as_deref()
+            .ok_or_else(|| anyhow!("Set VAULT_TOKEN or VAULT_K8S_ROLE to authenticate to Vault"))?;
+        let jwt = std::fs::read_to_string(SERVICE_ACCOUNT_TOKEN).context("Failed to read the service account token")?;
+        let mut request = self.client
+            .post(format!("{}/v1/auth/{}/login", vault.addr, vault.kubernetes_mount))
+            .json(&serde_json::json!({"role": role, "jwt": jwt.trim()}));
+        if let Some(namespace) = &vault.namespace {
+            request = request.header("X-Vault-Namespace", namespace);
+        }
+        let body: serde_json::Value = request.send().await?.error_for_status()?.json().await?;
+        let token = body["auth"]["client_token"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Vault login returned no client token"))?
+            .to_string();
+        // A lease duration of 0 means the token does not expire
+        let expires_at = body["auth"]["lease_duration"]
+            .as_i64()
+            .filter(|seconds| *seconds > 0)
+            .map(|seconds| Utc::now() + chrono::Duration::seconds(seconds));
+        info!("Logged in to Vault with Kubernetes role {}", role);
+        *self.vault_token.lock().unwrap() = Some(Expiring::new(token.clone(), expires_at));
+        Ok(token)
+    }
+
+    async fn read_aws_secret(&self, secret_id: &str, key: Option<&str>) -> Result<String> {
+        let region = self.aws.region.as_deref().ok_or_else(|| anyhow!("AWS_REGION is not set"))?;
+        let credentials = self.aws_credentials(region).await?;
+        let host = format!("secretsmanager.{}.amazonaws.com", region);
+        let body = serde_json::to_vec(&serde_json::json!({"SecretId": secret_id}))?;
+        let amz_date = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let mut headers = vec![
+            ("content-type".to_string(), "application/x-amz-json-1.1".to_string()),
+            ("host".to_string(), host.clone()),
+            ("x-amz-date".to_string(), amz_date),
+            ("x-amz-target".to_string(), "secretsmanager.GetSecretValue".to_string()),
+        ];
+        if let Some(token) = &credentials.session_token {
+            headers.push(("x-amz-security-token".to_string(), token.clone()));
+        }
+        let authorization = sign_v4(&credentials, region, "secretsmanager", "POST", "/", &headers, &body);
+
+        let mut request = self.client.post(format!("https://{}/", host)).body(body);
+        for (name, value) in headers.iter().filter(|(name, _)| name != "host") {
+            request = request.header(name, value);
+        }
+        let response: serde_json::Value = request
+            .header("authorization", authorization)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let secret = response["SecretString"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Secret {} has no SecretString", secret_id))?;
+        match key {
+            None => Ok(secret.to_string()),
+            Some(key) => serde_json::from_str::<serde_json::Value>(secret)
+                .ok()
+                .and_then(|v| v.get(key).and_then(|v| v.as_str()).map(str::to_string))
+                .ok_or_else(|| anyhow!("Secret {} has no key {}", secret_id, key)),
+        }
+    }
+
+    async fn aws_credentials(&self, region: &str) -> Result<AwsCredentials> {
+        if let Some(credentials) = &self.aws.static_credentials {
+            return Ok(credentials.clone());
+        }
+        if let Some(credentials) = self.aws_credentials.lock().unwrap().as_ref().and_then(Expiring::fresh) {
+            return Ok(credentials);
+        }
+        let credentials = if let Some((token_file, role_arn)) = &self.aws.web_identity {
+            self.assume_role_with_web_identity(region, token_file, role_arn).await?
+        } else if let Some((uri, token)) = &self.aws.container_credentials {
+            let mut request = self.client.get(uri);
+            if let Some(token) = token {
+                request = request.header("authorization", token);
+            }
+            let body: ContainerCredentials = request.send().await?.error_for_status()?.json().await?;
+            let credentials = AwsCredentials {
+                access_key_id: body.access_key_id,
+                secret_access_key: body.secret_access_key,
+                session_token: body.token,
+            };
+            Expiring::new(credentials, body.expiration)
+        } else {
+            return Err(anyhow!("No AWS credentials (static, web identity or container credentials) configured"));
+        };
+        *self.aws_credentials.lock().unwrap() = Some(credentials.clone());
+        Ok(credentials.value)
+    }
+
+    async fn assume_role_with_web_identity(
+        &self,
+        region: &str,
+        token_file: &str,
+        role_arn: &str,
+    ) -> Result<Expiring<AwsCredentials>> {
+        let token = std::fs::read_to_string(token_file)
+            .with_context(|| format!("Failed to read AWS_WEB_IDENTITY_TOKEN_FILE {}", token_file))?;
+        let body: serde_json::Value = self.client
+            .get(format!("https://sts.{}.amazonaws.com/", region))
+            .query(&[
+                ("Action", "AssumeRoleWithWebIdentity"),
+                ("Version", "2011-06-15"),
+                ("RoleArn", role_arn),
+                ("RoleSessionName", self.aws.role_session_name.as_str()),
+                ("WebIdentityToken", token.trim()),
+            ])
+            .header("accept", "application/json")
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let credentials = &body["AssumeRoleWithWebIdentityResponse"]["AssumeRoleWithWebIdentityResult"]["Credentials"];
+        let field = |name: &str| {
+            credentials[name].as_str().map(str::to_string).ok_or_else(|| anyhow!("STS response has no {}", name))
+        };
+        // Epoch seconds in the JSON protocol
+        let expires_at = credentials["Expiration"].as_f64().and_then(|seconds| DateTime::from_timestamp(seconds as i64, 0));
+        info!("Assumed AWS role {}", role_arn);
+        let credentials = AwsCredentials {
+            access_key_id: field("AccessKeyId")?,
+            secret_access_key: field("SecretAccessKey")?,
+            session_token: Some(field("SessionToken")?),
+        };
+        Ok(Expiring::new(credentials, expires_at))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerCredentials {
+    access_key_id: String,
+    secret_access_key: String,
+    token: Option<String>,
+    expiration: Option<DateTime<Utc>>,
+}
+
+/// `Authorization` header value of an AWS Signature Version 4 request without query
+/// string. `headers` are all signed and must include `host` and `x-amz-date`.
+pub fn sign_v4(
+    credentials: &AwsCredentials,
+    region: &str,
+    service: &str,
+    method: &str,
+    path: &str,
+    headers: &[(String, String)],
+    body: &[u8],
+) -> String {
+    let mut headers: Vec<(String, String)> = headers.iter().map(|(k, v)| (k.to_lowercase(), v.trim().to_string())).collect();
+    headers.sort();
+    let amz_date = headers.iter().find(|(k, _)| k == "x-amz-date").map(|(_, v)| v.clone()).unwrap_or_default();
+    let date = &amz_date[..amz_date.len().min(8)];
+    let signed_headers = headers.iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>().join(";");
+    let canonical_headers: String = headers.iter().map(|(k, v)| format!("{}:{}\n", k, v)).collect();
+    let canonical_request = format!(
+        "{}\n{}\n\n{}\n{}\n{}",
+        method, path, canonical_headers, signed_headers, hex(digest::digest(&digest::SHA256, body).as_ref())
+    );
+    let scope = format!("{}/{}/{}/aws4_request", date, region, service);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex(digest::digest(&digest::SHA256, canonical_request.as_bytes()).as_ref())
+    );
+    let key = signing_key(&credentials.secret_access_key, date, region, service);
+    let signature = hex(hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, &key), string_to_sign.as_bytes()).as_ref());
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        credentials.access_key_id, scope, signed_headers, signature
+    )
+}
+
+fn signing_key(secret: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let sign = |key: &[u8], data: &str| hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, key), data.as_bytes()).as_ref().to_vec();
+    let k_date = sign(format!("AWS4{}", secret).as_bytes(), date);
+    let k_region = sign(&k_date, region);
+    let k_service = sign(&k_region, service);
+    sign(&k_service, "aws4_request")
+}
+
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MockEnvironment;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_parse_secret_ref() {
+        assert!(SecretRef::parse("https://hooks.slack.com/services/x").is_none());
+        assert_eq!(
+            SecretRef::parse("vault:/secret/data/reporter#webhook").unwrap().unwrap(),
+            SecretRef::Vault { path: "secret/data/reporter".to_string(), field: "webhook".to_string() }
+        );
+        assert!(SecretRef::parse("vault:secret/data/reporter").unwrap().is_err());
+        assert_eq!(
+            SecretRef::parse("aws-sm:prod/reporter#slack").unwrap().unwrap(),
+            SecretRef::AwsSecretsManager { secret_id: "prod/reporter".to_string(), key: Some("slack".to_string()) }
+        );
+        assert_eq!(
+            SecretRef::parse("aws-sm:prod/reporter").unwrap().unwrap(),
+            SecretRef::AwsSecretsManager { secret_id: "prod/reporter".to_string(), key: None }
+        );
+    }
+
+    #[test]
+    fn test_sign_v4() {
+        // "get-vanilla" from the AWS SigV4 test suite
+        let credentials = AwsCredentials {
+            access_key_id: "AKIDEXAMPLE".to_string(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+            session_token: None,
+        };
+        let headers = vec![
+            ("Host".to_string(), "example.amazonaws.com".to_string()),
+            ("X-Amz-Date".to_string(), "20150830T123600Z".to_string()),
+        ];
+        assert_eq!(
+            sign_v4(&credentials, "us-east-1", "service", "GET", "/", &headers, b""),
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+             SignedHeaders=host;x-amz-date, \
+             Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+        );
+    }
+
+    /// Minimal HTTP endpoint answering every request with `body`, counting requests
+    fn serve_json(body: &'static str) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = format!("http://{}", listener.local_addr().unwrap());
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut buf = [0u8; 8192];
+                let _ = stream.read(&mut buf);
+                counter.fetch_add(1, Ordering::SeqCst);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+            }
+        });
+        (addr, hits)
+    }
+
+    #[tokio::test]
+    async fn test_resolve_vault_kv2_with_cache() {
+        let (addr, hits) = serve_json(r#"{"data": {"data": {"webhook": "https://hooks.slack.com/services/T0/B0/x"}}}"#);
+        let env = MockEnvironment::new().with_var("VAULT_ADDR", addr).with_var("VAULT_TOKEN", "s.test");
+        let resolver = SecretResolver::from_env(&env, reqwest::Client::new()).unwrap();
+
+        assert_eq!(resolver.resolve("https://plain.example/hook").await.unwrap(), "https://plain.example/hook");
+        let reference = "vault:secret/data/reporter#webhook";
+        assert_eq!(resolver.resolve(reference).await.unwrap(), "https://hooks.slack.com/services/T0/B0/x");
+        resolver.resolve(reference).await.unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        resolver.invalidate();
+        resolver.resolve(reference).await.unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        assert!(resolver.resolve("vault:secret/data/reporter#missing").await.is_err());
+        // No AWS region configured
+        assert!(resolver.resolve("aws-sm:prod/reporter").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_container_credentials_refreshed_before_expiry() {
+        let (addr, hits) = serve_json(
+            r#"{"AccessKeyId": "AKID", "SecretAccessKey": "secret", "Token": "t", "Expiration": "2099-01-01T00:00:00Z"}"#,
+        );
+        let env = MockEnvironment::new().with_var("AWS_CONTAINER_CREDENTIALS_FULL_URI", addr);
+        let resolver = SecretResolver::from_env(&env, reqwest::Client::new()).unwrap();
+        assert_eq!(resolver.aws_credentials("eu-west-1").await.unwrap().access_key_id, "AKID");
+        resolver.aws_credentials("eu-west-1").await.unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        // Credentials about to expire are fetched again on every use
+        let (addr, hits) = serve_json(
+            r#"{"AccessKeyId": "AKID", "SecretAccessKey": "secret", "Token": "t", "Expiration": "2020-01-01T00:00:00Z"}"#,
+        );
+        let env = MockEnvironment::new().with_var("AWS_CONTAINER_CREDENTIALS_FULL_URI", addr);
+        let resolver = SecretResolver::from_env(&env, reqwest::Client::new()).unwrap();
+        resolver.aws_credentials("eu-west-1").await.unwrap();
+        resolver.aws_credentials("eu-west-1").await.unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,49 @@
+//! The reporter's own memory footprint, read from procfs and the cgroup, so operators
+//! can size its CronJob from the run metadata instead of from OOM kills.
+
+use std::fs;
+
+/// cgroup v1 reports "no limit" as a page-aligned `i64::MAX`; anything this large is unlimited
+const UNLIMITED_BYTES: u64 = 1 << 60;
+
+/// Peak resident memory of this process (`VmHWM`); `None` where procfs is unavailable
+pub fn peak_rss_bytes() -> Option<u64> {
+    parse_vm_hwm(&fs::read_to_string("/proc/self/status").ok()?)
+}
+
+/// Memory limit of the container, from cgroup v2 (`memory.max`) or v1
+/// (`memory.limit_in_bytes`); `None` when unlimited or not in a cgroup
+pub fn memory_limit_bytes() -> Option<u64> {
+    ["/sys/fs/cgroup/memory.max", "/sys/fs/cgroup/memory/memory.limit_in_bytes"]
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+        .and_then(|content| parse_memory_limit(&content))
+}
+
+/// `VmHWM` of a `/proc/<pid>/status` file, in bytes
+fn parse_vm_hwm(status: &str) -> Option<u64> {
+    let line = status.lines().find_map(|line| line.strip_prefix("VmHWM:"))?;
+    let kib: u64 = line.trim().trim_end_matches("kB").trim().parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// A cgroup memory limit file's value; `max` (v2) and huge values (v1) mean unlimited
+fn parse_memory_limit(content: &str) -> Option<u64> {
+    content.trim().parse().ok().filter(|&bytes| bytes < UNLIMITED_BYTES)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_self_usage() {
+        let status = "Name:\tkube-health-rep\nVmPeak:\t  812344 kB\nVmHWM:\t   95236 kB\nVmRSS:\t   90112 kB\n";
+        assert_eq!(parse_vm_hwm(status), Some(95236 * 1024));
+        assert_eq!(parse_vm_hwm("Name:\tinit\n"), None);
+
+        assert_eq!(parse_memory_limit("268435456\n"), Some(268_435_456));
+        assert_eq!(parse_memory_limit("max\n"), None);
+        assert_eq!(parse_memory_limit("9223372036854771712\n"), None);
+    }
+}
@@ -1,66 +1,97 @@
 use anyhow::{anyhow, Context, Result};
-use tracing::error;
+use regex::Regex;
+use std::sync::OnceLock;
+use tracing::{error, info};
+use crate::blocks::{Block, SlackMessage};
+use crate::delivery::{PayloadSigner, SIGNATURE_HEADER, SIGNATURE_TIMESTAMP_HEADER};
+use crate::parsing::{format_cpu, format_memory, humanize_bytes, humanize_millicores};
+use crate::report::priority::{critical_count, critical_sections, prioritize, PriorityIssue};
+use crate::report::severity::{section_severities, SeverityCounts};
+use crate::report::{HealthReport, ReportSummary};
 use crate::types::{
-    Config, SlackPayload, HeavyUsagePod, RestartEventInfo, PendingPodInfo,
-    FailedPodInfo, UnreadyPodInfo, OomKilledInfo, ProblematicNodeInfo, 
-    NodeUtilizationInfo, VolumeIssueInfo, VolumeIssueType, FailedJobInfo, MissedCronJobInfo
+    Config, Severity, SlackPayload, REPORT_SECTIONS, RestartCause, VolumeIssueType, StaleMetricsInfo,
+    RightsizingRecommendation, ResourceHygieneInfo, ResourceHygieneIssue, PodChurnInfo, VolumeSchedulingInfo,
+    VolumeSchedulingReason, JobBackoffRiskInfo, CronJobConcurrencyInfo,
+    FinishedObjectsInfo, MissingConfigRefInfo, ReadinessFlapInfo,
+    ServiceEndpointChurnInfo, DualStackIssueInfo, NetworkAgentIssueInfo, NetworkAgentIssue, OperatorIssueInfo, OperatorIssue, PreviouslySnoozedInfo,
+    IssueHistory, PodSecurityViolationInfo, NamespaceQuotaInfo, BlastRadius,
+    LimitRangeConflictInfo, EventFloodInfo, StuckTerminatingInfo, CustomResourceConditionInfo, NodeDiskForecastInfo, NodeFlapInfo, ApiHealthInfo, ObjectCountInfo
 };
 
-pub fn build_slack_payload(
-    cfg: &Config,
-    heavy: &[HeavyUsagePod],
-    restarts: &[RestartEventInfo],
-    pendings: &[PendingPodInfo],
-    failed: &[FailedPodInfo],
-    unready: &[UnreadyPodInfo],
-    oom_killed: &[OomKilledInfo],
-    problematic_nodes: &[ProblematicNodeInfo],
-    high_util_nodes: &[NodeUtilizationInfo],
-    volume_issues: &[VolumeIssueInfo],
-    failed_jobs: &[FailedJobInfo],
-    missed_cronjobs: &[MissedCronJobInfo],
-) -> SlackPayload {
-    let mut blocks: Vec<serde_json::Value> = Vec::new();
-    let title = match (&cfg.cluster_name, &cfg.datacenter_name) {
+/// Build the core report (header, run settings and the issue sections) from the
+/// findings in `report`
+pub fn build_slack_payload(report: &HealthReport) -> SlackPayload {
+    let cfg = &report.config;
+    let pods = &report.pod_metrics;
+    let nodes = &report.cluster_metrics;
+    let jobs = &report.job_metrics;
+    let usage_checks_skipped = report.usage_checks_skipped();
+    let mut title = match (&cfg.cluster_name, &cfg.datacenter_name) {
         (Some(c), Some(d)) => format!("Kubernetes Health Report - {} ({})", c, d),
         (Some(c), None) => format!("Kubernetes Health Report - {}", c),
         (None, Some(d)) => format!("Kubernetes Health Report - {}", d),
         (None, None) => "Kubernetes Health Report".to_string(),
     };
-    blocks.push(serde_json::json!({
-        "type": "header",
-        "text": {"type": "plain_text", "text": title}
-    }));
+    if let Some(tenant) = &cfg.tenant {
+        title.push_str(&format!(" · {}", tenant));
+    }
+    let mut message = SlackMessage::new().block(Block::header(title));
+    if let Some(mentions) = build_section_mentions(&cfg.slack_section_mentions, &critical_sections(report)) {
+        message.push(mentions);
+    }
 
-    let ns_text = format!("Namespaces: {}\nThreshold: {}%\nGrace: restarts {}m, pending {}m",
+    let mut ns_text = format!("Namespaces: {}\nThreshold: {}%\nGrace: restarts {}m, pending {}m",
         cfg.namespaces.join(", "),
         cfg.threshold_percent,
         cfg.restart_grace_minutes,
         cfg.pending_grace_minutes,
     );
-    blocks.push(serde_json::json!({
-        "type": "section",
-        "text": {"type": "mrkdwn", "text": ns_text}
-    }));
+    if !report.missing_namespaces.is_empty() {
+        let missing: Vec<String> = report.missing_namespaces.iter().map(|ns| format!("`{}`", ns)).collect();
+        ns_text.push_str(&format!(
+            "\n:warning: *Namespaces not found:* {} — check NAMESPACES for typos",
+            missing.join(", ")
+        ));
+    }
+    if let Some(freeze) = &report.change_freeze {
+        let escalated = report.fingerprints().iter().filter(|f| freeze.escalated.contains(*f)).count();
+        let until = freeze.until.map(|t| format!(" until {}", t.format("%Y-%m-%d %H:%M UTC"))).unwrap_or_default();
+        ns_text.push_str(&format!(
+            "\n:snowflake: *Change freeze in effect*{} — {} new finding(s) escalated to critical",
+            until, escalated
+        ));
+    }
+    if usage_checks_skipped {
+        ns_text.push_str("\n:warning: Metrics API unavailable — usage checks skipped");
+    }
+    if nodes.cluster_checks_skipped {
+        ns_text.push_str("\nNamespace-scoped RBAC — node and other cluster-wide checks skipped");
+    }
+    for warning in &report.collection_warnings {
+        ns_text.push_str(&format!("\n:warning: {}", warning));
+    }
+    for note in &report.notes {
+        ns_text.push_str(&format!("\n:memo: {}", note));
+    }
+    message.push(Block::section(ns_text));
 
     // Heavy usage section
     let mut heavy_lines: Vec<String> = Vec::new();
-    for h in heavy {
-        let cpu = h.cpu_pct.map(|v| format!("{:.0}%", v)).unwrap_or("-".to_string());
-        let mem = h.mem_pct.map(|v| format!("{:.0}%", v)).unwrap_or("-".to_string());
-        heavy_lines.push(format!("• `{}/{}:` CPU {} | MEM {}", h.namespace, h.pod, cpu, mem));
-    }
-    if heavy_lines.is_empty() {
-        heavy_lines.push("No pods exceeding threshold.".to_string());
+    for h in &pods.heavy_usage {
+        let cpu = format!("{} ({})", percent(h.cpu_pct), humanize_millicores(h.cpu_millicores));
+        let mem = format!("{} ({})", percent(h.mem_pct), humanize_bytes(h.memory_bytes));
+        heavy_lines.push(format!("• `{}/{}:` CPU {} | MEM {}{}", h.namespace, h.pod, cpu, mem, labels_suffix(&h.labels)));
     }
-    blocks.push(serde_json::json!({
-        "type": "section",
-        "text": {"type": "mrkdwn", "text": format!("*High resource usage*\n{}", heavy_lines.join("\n"))}
-    }));
+    let heavy_empty = if usage_checks_skipped {
+        "Metrics unavailable — usage checks skipped."
+    } else {
+        "No pods exceeding threshold."
+    };
+    push_section(&mut message, cfg, "usage", "High resource usage", heavy_lines, heavy_empty);
 
     // Restarts section
     let mut restart_lines: Vec<String> = Vec::new();
-    for r in restarts {
+    for r in &pods.restarts {
         let t = r
             .last_restart_time
             .map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
@@ -69,154 +100,163 @@ pub fn build_slack_payload(
         let msg = r.message.clone().unwrap_or_default();
         let code = r
             .exit_code
-            .map(|c| format!(" (exit {})", c))
+            .map(|c| format!(" (exit {}: {})", c, r.cause()))
             .unwrap_or_default();
         restart_lines.push(format!(
-            "• `{}/{}` [{}] {}{} - {}",
-            r.namespace, r.pod, r.container, reason, code, msg
+            "• `{}/{}`{} [{}]{} {}{} - {}{}",
+            r.namespace, r.pod, node_suffix(&r.node_name, &r.zone), r.container, image_suffix(r.image.as_deref()), reason, code, msg,
+            labels_suffix(&r.labels)
         ));
         restart_lines.push(format!("  last: {}", t));
+        restart_lines.extend(blast_radius_line(&r.blast_radius));
+        if let Some(tail) = &r.log_tail {
+            restart_lines.push(format!("```{}```", tail));
+        }
     }
-    if restart_lines.is_empty() {
-        restart_lines.push("No container restarts beyond grace.".to_string());
+    if !restart_lines.is_empty() {
+        let causes: Vec<String> = RestartCause::tally(&pods.restarts)
+            .iter()
+            .map(|(cause, count)| format!("{} {}", count, cause))
+            .collect();
+        restart_lines.insert(0, format!("Causes: {}", causes.join(", ")));
     }
-    blocks.push(serde_json::json!({
-        "type": "section",
-        "text": {"type": "mrkdwn", "text": format!("*Container restarts*\n{}", restart_lines.join("\n"))}
-    }));
+    push_section(&mut message, cfg, "restarts", "Container restarts", restart_lines, "No container restarts beyond grace.");
 
     // Pending section
     let mut pending_lines: Vec<String> = Vec::new();
-    for p in pendings {
+    for p in &pods.pending {
         pending_lines.push(format!(
-            "• `{}/{}` pending for {}m (since {})",
+            "• `{}/{}`{} pending for {}m (since {}){}",
             p.namespace,
             p.pod,
+            node_suffix(&p.node_name, &p.zone),
             p.duration_minutes,
-            p.since.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+            p.since.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            labels_suffix(&p.labels)
         ));
+        pending_lines.extend(blast_radius_line(&p.blast_radius));
+        if !p.image_pull_failures.is_empty() {
+            let images: Vec<String> = p.image_pull_failures.iter().map(|i| format!("`{}`", i)).collect();
+            pending_lines.push(format!("  image pull failing: {}", images.join(", ")));
+        }
     }
-    if pending_lines.is_empty() {
-        pending_lines.push("No pending pods beyond grace.".to_string());
-    }
-    blocks.push(serde_json::json!({
-        "type": "section",
-        "text": {"type": "mrkdwn", "text": format!("*Pending pods*\n{}", pending_lines.join("\n"))}
-    }));
+    push_section(&mut message, cfg, "pending", "Pending pods", pending_lines, "No pending pods beyond grace.");
 
     // Failed pods section
     let mut failed_lines: Vec<String> = Vec::new();
-    for f in failed {
+    for f in &pods.failed {
         let reason = f.reason.as_ref().map(|s| s.as_str()).unwrap_or("Unknown");
         let message = f.message.as_ref().map(|m| format!(" - {}", m)).unwrap_or_default();
         failed_lines.push(format!(
-            "• `{}/{}` failed for {}m ({}{})",
+            "• `{}/{}`{} failed for {}m ({}{}){}",
             f.namespace,
             f.pod,
+            node_suffix(&f.node_name, &f.zone),
             f.duration_minutes,
             reason,
-            message
+            message,
+            labels_suffix(&f.labels)
         ));
+        failed_lines.extend(blast_radius_line(&f.blast_radius));
     }
-    if failed_lines.is_empty() {
-        failed_lines.push("No failed pods beyond grace.".to_string());
-    }
-    blocks.push(serde_json::json!({
-        "type": "section",
-        "text": {"type": "mrkdwn", "text": format!("*Failed pods*\n{}", failed_lines.join("\n"))}
-    }));
+    push_section(&mut message, cfg, "failed", "Failed pods", failed_lines, "No failed pods beyond grace.");
 
     // Unready pods section
     let mut unready_lines: Vec<String> = Vec::new();
-    for u in unready {
+    for u in &pods.unready {
         let conditions = if u.failed_conditions.is_empty() {
             "Unknown conditions".to_string()
         } else {
             u.failed_conditions.join(", ")
         };
         unready_lines.push(format!(
-            "• `{}/{}` unready for {}m ({})",
+            "• `{}/{}`{} unready for {}m ({}){}",
             u.namespace,
             u.pod,
+            node_suffix(&u.node_name, &u.zone),
             u.duration_minutes,
-            conditions
+            conditions,
+            labels_suffix(&u.labels)
         ));
+        unready_lines.extend(blast_radius_line(&u.blast_radius));
     }
-    if unready_lines.is_empty() {
-        unready_lines.push("No unready pods beyond grace.".to_string());
-    }
-    blocks.push(serde_json::json!({
-        "type": "section",
-        "text": {"type": "mrkdwn", "text": format!("*Unready pods*\n{}", unready_lines.join("\n"))}
-    }));
+    push_section(&mut message, cfg, "unready", "Unready pods", unready_lines, "No unready pods beyond grace.");
 
     // OOMKilled containers section
     let mut oom_lines: Vec<String> = Vec::new();
-    for o in oom_killed {
+    for o in &pods.oom_killed {
         let time_str = o.last_oom_time
             .map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
             .unwrap_or_else(|| "recent".to_string());
         oom_lines.push(format!(
-            "• `{}/{}` [{}] OOMKilled (restarts: {}, last: {})",
+            "• `{}/{}`{} [{}]{} OOMKilled (restarts: {}, last: {}){}",
             o.namespace,
             o.pod,
+            node_suffix(&o.node_name, &o.zone),
             o.container,
+            image_suffix(o.image.as_deref()),
             o.restart_count,
-            time_str
+            time_str,
+            labels_suffix(&o.labels)
         ));
+        oom_lines.extend(blast_radius_line(&o.blast_radius));
+        if let Some(tail) = &o.log_tail {
+            oom_lines.push(format!("```{}```", tail));
+        }
     }
-    if oom_lines.is_empty() {
-        oom_lines.push("No OOMKilled containers beyond grace.".to_string());
-    }
-    blocks.push(serde_json::json!({
-        "type": "section",
-        "text": {"type": "mrkdwn", "text": format!("*OOMKilled containers*\n{}", oom_lines.join("\n"))}
-    }));
+    push_section(&mut message, cfg, "oom", "OOMKilled containers", oom_lines, "No OOMKilled containers beyond grace.");
 
     // Problematic nodes section
     let mut node_problem_lines: Vec<String> = Vec::new();
-    for n in problematic_nodes {
+    for n in &nodes.problematic_nodes {
         node_problem_lines.push(format!(
-            "• `{}` {} (since {})",
+            "• `{}`{} {} (since {})",
             n.name,
+            os_suffix(&n.os),
             n.conditions.join(", "),
             n.since.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
         ));
     }
-    if node_problem_lines.is_empty() {
-        node_problem_lines.push("No problematic nodes.".to_string());
+    if let Some(breakdown) = os_breakdown(nodes.problematic_nodes.iter().map(|n| n.os.as_str())) {
+        node_problem_lines.insert(0, breakdown);
     }
-    blocks.push(serde_json::json!({
-        "type": "section",
-        "text": {"type": "mrkdwn", "text": format!("*Problematic nodes*\n{}", node_problem_lines.join("\n"))}
-    }));
+    let node_problem_empty = if nodes.cluster_checks_skipped {
+        "Node checks skipped (namespace-scoped RBAC)."
+    } else {
+        "No problematic nodes."
+    };
+    push_section(&mut message, cfg, "nodes", "Problematic nodes", node_problem_lines, node_problem_empty);
 
     // High utilization nodes section
     let mut node_util_lines: Vec<String> = Vec::new();
-    for n in high_util_nodes {
-        let cpu = n.cpu_pct.map(|v| format!("{:.0}%", v)).unwrap_or("-".to_string());
-        let mem = n.memory_pct.map(|v| format!("{:.0}%", v)).unwrap_or("-".to_string());
+    for n in &nodes.high_utilization_nodes {
+        let cpu = percent(n.cpu_pct) + &n.cpu_millicores.map(|m| format!(" ({})", humanize_millicores(m))).unwrap_or_default();
+        let mem = percent(n.memory_pct) + &n.memory_bytes.map(|b| format!(" ({})", humanize_bytes(b))).unwrap_or_default();
         let pod_util = if n.pods_capacity > 0 {
             format!("{:.0}%", (n.pods_count as f64 / n.pods_capacity as f64) * 100.0)
         } else {
             "-".to_string()
         };
         node_util_lines.push(format!(
-            "• `{}` CPU {} | MEM {} | Pods {}/{} ({})",
-            n.name, cpu, mem, n.pods_count, n.pods_capacity, pod_util
+            "• `{}`{} CPU {} | MEM {} | Pods {}/{} ({})",
+            n.name, os_suffix(&n.os), cpu, mem, n.pods_count, n.pods_capacity, pod_util
         ));
     }
-    if node_util_lines.is_empty() {
-        node_util_lines.push("No high utilization nodes.".to_string());
+    if let Some(breakdown) = os_breakdown(nodes.high_utilization_nodes.iter().map(|n| n.os.as_str())) {
+        node_util_lines.insert(0, breakdown);
     }
-    blocks.push(serde_json::json!({
-        "type": "section",
-        "text": {"type": "mrkdwn", "text": format!("*High utilization nodes*\n{}", node_util_lines.join("\n"))}
-    }));
+    let node_util_empty = if nodes.cluster_checks_skipped {
+        "Node checks skipped (namespace-scoped RBAC)."
+    } else if usage_checks_skipped {
+        "Metrics unavailable — utilization checks skipped."
+    } else {
+        "No high utilization nodes."
+    };
+    push_section(&mut message, cfg, "node_utilization", "High utilization nodes", node_util_lines, node_util_empty);
 
     // Volume issues section
     let mut volume_lines: Vec<String> = Vec::new();
-    for v in volume_issues {
+    for v in &report.volume_metrics.volume_issues {
         let issue_desc = match &v.issue_type {
             VolumeIssueType::HighUsage(pct) => format!("High usage ({:.1}%)", pct),
             VolumeIssueType::MountFailure => "Mount failure".to_string(),
@@ -230,17 +270,11 @@ pub fn build_slack_payload(
             v.message
         ));
     }
-    if volume_lines.is_empty() {
-        volume_lines.push("No volume issues.".to_string());
-    }
-    blocks.push(serde_json::json!({
-        "type": "section",
-        "text": {"type": "mrkdwn", "text": format!("*Volume issues*\n{}", volume_lines.join("\n"))}
-    }));
+    push_section(&mut message, cfg, "volumes", "Volume issues", volume_lines, "No volume issues.");
 
     // Failed jobs section
     let mut job_lines: Vec<String> = Vec::new();
-    for j in failed_jobs {
+    for j in &jobs.failed_jobs {
         let time_str = j.last_failure_time
             .map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
             .unwrap_or_else(|| "unknown".to_string());
@@ -254,17 +288,11 @@ pub fn build_slack_payload(
             time_str
         ));
     }
-    if job_lines.is_empty() {
-        job_lines.push("No failed jobs.".to_string());
-    }
-    blocks.push(serde_json::json!({
-        "type": "section",
-        "text": {"type": "mrkdwn", "text": format!("*Failed jobs*\n{}", job_lines.join("\n"))}
-    }));
+    push_section(&mut message, cfg, "failed_jobs", "Failed jobs", job_lines, "No failed jobs.");
 
     // Missed CronJobs section
     let mut cronjob_lines: Vec<String> = Vec::new();
-    for c in missed_cronjobs {
+    for c in &jobs.missed_cronjobs {
         cronjob_lines.push(format!(
             "• `{}/{}` missed {} runs (last scheduled: {})",
             c.namespace,
@@ -273,22 +301,1060 @@ pub fn build_slack_payload(
             c.last_schedule_time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
         ));
     }
-    if cronjob_lines.is_empty() {
-        cronjob_lines.push("No missed CronJobs.".to_string());
+    push_section(&mut message, cfg, "missed_cronjobs", "Missed CronJobs", cronjob_lines, "No missed CronJobs.");
+
+    message.build()
+}
+
+/// ` on node (zone)` annotation after a pod name, empty for unscheduled pods
+fn node_suffix(node: &Option<String>, zone: &Option<String>) -> String {
+    match (node, zone) {
+        (Some(n), Some(z)) => format!(" on `{}` ({})", n, z),
+        (Some(n), None) => format!(" on `{}`", n),
+        (None, _) => String::new(),
+    }
+}
+
+/// Utilization rounded to a whole percent, `-` when unknown (e.g. no request set)
+fn percent(pct: Option<f64>) -> String {
+    pct.map(|v| format!("{:.0}%", v)).unwrap_or_else(|| "-".to_string())
+}
+
+/// ` (windows)` annotation after a node name, empty for Linux nodes
+fn os_suffix(os: &str) -> String {
+    if os == "linux" {
+        String::new()
+    } else {
+        format!(" ({})", os)
+    }
+}
+
+/// `By OS: 2 linux, 1 windows` line heading node findings that span several operating
+/// systems; `None` when they are all on one
+fn os_breakdown<'a>(oses: impl Iterator<Item = &'a str>) -> Option<String> {
+    let mut counts: std::collections::BTreeMap<&str, usize> = Default::default();
+    for os in oses {
+        *counts.entry(os).or_default() += 1;
+    }
+    if counts.len() < 2 {
+        return None;
+    }
+    let parts: Vec<String> = counts.iter().map(|(os, count)| format!("{} {}", count, os)).collect();
+    Some(format!("By OS: {}", parts.join(", ")))
+}
+
+/// `  affects 2 public Ingresses, 1 Service` line under a pod finding; `None` when
+/// nothing routes to the pod
+fn blast_radius_line(radius: &Option<BlastRadius>) -> Option<String> {
+    let radius = radius.as_ref()?;
+    let count = |n: usize, noun: &str, plural: &str| format!("{} {}", n, if n == 1 { noun } else { plural });
+    let mut parts = Vec::new();
+    if !radius.public_ingresses.is_empty() {
+        parts.push(count(radius.public_ingresses.len(), "public Ingress", "public Ingresses"));
+    }
+    if !radius.internal_ingresses.is_empty() {
+        parts.push(count(radius.internal_ingresses.len(), "internal Ingress", "internal Ingresses"));
+    }
+    if !radius.services.is_empty() {
+        parts.push(count(radius.services.len(), "Service", "Services"));
+    }
+    if parts.is_empty() {
+        return None;
+    }
+    Some(format!("  affects {}", parts.join(", ")))
+}
+
+/// ` _team=x, app=y_` annotation with the `ISSUE_LABELS` of a finding, empty without any
+fn labels_suffix(labels: &std::collections::BTreeMap<String, String>) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+    let pairs: Vec<String> = labels.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+    format!(" _{}_", pairs.join(", "))
+}
+
+/// ` image` annotation after a container name, empty when the image is unknown
+fn image_suffix(image: Option<&str>) -> String {
+    image.map(|i| format!(" `{}`", i)).unwrap_or_default()
+}
+
+/// Push a titled list section; an empty list shows `empty_text`, or is left out
+/// entirely when `OMIT_EMPTY_SECTIONS` is set
+fn push_section(
+    message: &mut SlackMessage,
+    cfg: &Config,
+    key: &str,
+    title: &str,
+    lines: Vec<String>,
+    empty_text: &str,
+) {
+    let body = if lines.is_empty() {
+        if cfg.omit_empty_sections {
+            return;
+        }
+        empty_text.to_string()
+    } else {
+        lines.join("\n")
+    };
+    message.push(Block::section(format!("*{}*\n{}", title, body)).with_block_id(key));
+}
+
+/// Section with issue counts per owning team (`None` = unowned) and their contacts
+pub fn build_owners_section(counts: &[(Option<String>, Option<String>, usize)]) -> Option<serde_json::Value> {
+    if counts.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = counts
+        .iter()
+        .map(|(team, contact, count)| match (team, contact) {
+            (Some(team), Some(contact)) => format!("• *{}* ({}): {}", team, contact, count),
+            (Some(team), None) => format!("• *{}*: {}", team, count),
+            (None, _) => format!("• _unowned_: {}", count),
+        })
+        .collect();
+    Some(Block::section(format!("*Issues by owner*\n{}", lines.join("\n"))).with_block_id("owners").into())
+}
+
+/// Context block marking a report routed to a single team
+pub fn build_team_context(team: &str, contact: Option<&str>) -> serde_json::Value {
+    let text = match contact {
+        Some(contact) => format!("Findings owned by *{}* ({})", team, contact),
+        None => format!("Findings owned by *{}*", team),
+    };
+    Block::context(text).into()
+}
+
+/// Section bringing the critical findings to the on-call (or `SLACK_MENTION`); `None`
+/// without a mention or critical findings
+pub fn build_oncall_section(mention: Option<&str>, critical: usize) -> Option<serde_json::Value> {
+    let mention = mention.filter(|_| critical > 0)?;
+    Some(Block::section(format!(":rotating_light: {} — {} critical finding(s) need attention", mention, critical)).into())
+}
+
+/// Section under the header mentioning, for each section in `mentions` with critical
+/// findings, whoever is configured for it; `None` when nobody is to be mentioned
+pub fn build_section_mentions(
+    mentions: &std::collections::BTreeMap<String, String>,
+    critical: &std::collections::BTreeMap<String, usize>,
+) -> Option<Block> {
+    let lines: Vec<String> = critical
+        .iter()
+        .filter_map(|(section, count)| {
+            let mention = mentions.get(section)?;
+            Some(format!("{} `{}`: {} critical finding(s)", mention, section, count))
+        })
+        .collect();
+    if lines.is_empty() {
+        return None;
+    }
+    Some(Block::section(format!(":rotating_light: *Critical findings*\n{}", lines.join("\n"))))
+}
+
+/// Prefix each report section with findings with the emoji of its most urgent severity
+pub fn mark_section_severities(payload: &mut SlackPayload, severities: &std::collections::BTreeMap<String, SeverityCounts>) {
+    for block in &mut payload.blocks {
+        let Some(highest) = block["block_id"].as_str().and_then(|id| severities.get(id)).and_then(SeverityCounts::highest) else {
+            continue;
+        };
+        let marked = block["text"]["text"].as_str().map(|text| format!("{} {}", severity_emoji(highest), text));
+        if let Some(marked) = marked {
+            block["text"]["text"] = marked.into();
+        }
+    }
+}
+
+fn severity_emoji(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical => ":red_circle:",
+        Severity::Warning => ":large_yellow_circle:",
+        Severity::Info => ":large_blue_circle:",
+    }
+}
+
+/// Footer context block with the run ID, to trace a message back to the run's logs,
+/// and the number of findings silenced by HealthSuppressions
+pub fn build_run_footer(run_id: &str, suppressed: usize) -> serde_json::Value {
+    let mut text = format!("Run ID: `{}`", run_id);
+    if suppressed > 0 {
+        text.push_str(&format!(" • {} suppressed", suppressed));
+    }
+    Block::context(text).into()
+}
+
+/// Reorder and filter the report sections (blocks tagged with a `block_id` from
+/// `REPORT_SECTIONS`) to follow `order`. Untagged blocks (header, summary, config info)
+/// stay on top; sections not named in `order` are dropped. An empty order is a no-op.
+pub fn apply_section_order(payload: &mut SlackPayload, order: &[String]) {
+    if order.is_empty() {
+        return;
+    }
+    let (mut sections, pinned): (Vec<serde_json::Value>, Vec<serde_json::Value>) = payload
+        .blocks
+        .drain(..)
+        .partition(|b| b.get("block_id").and_then(|id| id.as_str()).is_some_and(|id| REPORT_SECTIONS.contains(&id)));
+    sections.retain(|b| order.iter().any(|key| b["block_id"] == key.as_str()));
+    sections.sort_by_key(|b| order.iter().position(|key| b["block_id"] == key.as_str()));
+    payload.blocks = pinned;
+    payload.blocks.extend(sections);
+}
+
+/// Section listing pods whose metrics samples are stale; `None` when there are none
+pub fn build_stale_metrics_section(stale: &[StaleMetricsInfo]) -> Option<serde_json::Value> {
+    if stale.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = stale
+        .iter()
+        .map(|s| {
+            let window = s.window.as_ref().map(|w| format!(", window {}", w)).unwrap_or_default();
+            format!(
+                "• `{}/{}` metrics sample is {}m old (sampled {}{})",
+                s.namespace,
+                s.pod,
+                s.age_minutes,
+                s.sampled_at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                window
+            )
+        })
+        .collect();
+    Some(Block::section(format!("*Stale pod metrics*\n{}", lines.join("\n"))).with_block_id("stale_metrics").into())
+}
+
+/// Right-sizing section showing current vs. suggested values; `None` when there are none
+pub fn build_recommendations_section(recommendations: &[RightsizingRecommendation]) -> Option<serde_json::Value> {
+    if recommendations.is_empty() {
+        return None;
+    }
+    // Exact quantities, so suggestions can be pasted into a manifest
+    let cpu = |v: Option<i64>| v.map(format_cpu).unwrap_or_else(|| "unset".to_string());
+    let mem = |v: Option<i64>| v.map(format_memory).unwrap_or_else(|| "unset".to_string());
+    let lines: Vec<String> = recommendations
+        .iter()
+        .map(|r| {
+            format!(
+                "• `{}/{}` ({}, {} samples): CPU req {} → {} | MEM req {} → {} | MEM limit {} → {}",
+                r.namespace,
+                r.workload,
+                r.kind,
+                r.samples,
+                cpu(r.current_cpu_request),
+                cpu(Some(r.suggested_cpu_request)),
+                mem(r.current_memory_request),
+                mem(Some(r.suggested_memory_request)),
+                mem(r.current_memory_limit),
+                mem(Some(r.suggested_memory_limit)),
+            )
+        })
+        .collect();
+    Some(Block::section(format!("*Right-sizing recommendations*\n{}", lines.join("\n"))).with_block_id("recommendations").into())
+}
+
+/// Resource hygiene section (request/limit audit); `None` when there are no findings
+pub fn build_resource_hygiene_section(findings: &[ResourceHygieneInfo]) -> Option<serde_json::Value> {
+    if findings.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = findings
+        .iter()
+        .map(|f| {
+            let issue = match &f.issue {
+                ResourceHygieneIssue::MemoryLimitUnset => "memory limit unset".to_string(),
+                ResourceHygieneIssue::HighLimitRatio { resource, ratio } => {
+                    format!("{} limit is {:.0}× the request", resource, ratio)
+                }
+            };
+            format!("• `{}/{}` [{}] {}", f.namespace, f.workload, f.container, issue)
+        })
+        .collect();
+    Some(Block::section(format!("*Resource hygiene*\n{}", lines.join("\n"))).with_block_id("resource_hygiene").into())
+}
+
+/// Pod Security Standards section, grouped by namespace with the profile each is checked
+/// against; `None` when there are no violations
+pub fn build_pod_security_section(findings: &[PodSecurityViolationInfo]) -> Option<serde_json::Value> {
+    if findings.is_empty() {
+        return None;
+    }
+    let mut lines: Vec<String> = Vec::new();
+    let mut namespace: Option<&str> = None;
+    for f in findings {
+        if namespace != Some(f.namespace.as_str()) {
+            namespace = Some(f.namespace.as_str());
+            let count = findings.iter().filter(|o| o.namespace == f.namespace).count();
+            lines.push(format!("`{}` ({}): {} workload(s)", f.namespace, f.level.name(), count));
+        }
+        lines.push(format!("• `{}`: {}", f.workload, f.violations.join(", ")));
+    }
+    Some(Block::section(format!("*Pod Security Standards*\n{}", lines.join("\n"))).with_block_id("pod_security").into())
+}
+
+/// Section listing pending pods blocked on their volumes; `None` when there are none
+pub fn build_volume_scheduling_section(blocked: &[VolumeSchedulingInfo]) -> Option<serde_json::Value> {
+    if blocked.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = blocked
+        .iter()
+        .map(|b| {
+            let reason = match b.reason {
+                VolumeSchedulingReason::NodeAffinityConflict => "volume node affinity conflict",
+                VolumeSchedulingReason::NoMatchingVolume => "no volume can be bound on any node",
+                VolumeSchedulingReason::ProvisioningStuck => "volume provisioning stuck on selected node",
+            };
+            let message = b.message.as_ref().map(|m| format!(" - {}", m)).unwrap_or_default();
+            format!(
+                "• `{}/{}` claims {}: {} (pending {}m){}",
+                b.namespace,
+                b.pod,
+                b.claims.join(", "),
+                reason,
+                (chrono::Utc::now() - b.since).num_minutes(),
+                message
+            )
+        })
+        .collect();
+    Some(Block::section(format!("*Pods blocked on volumes*\n{}", lines.join("\n"))).with_block_id("volume_scheduling").into())
+}
+
+/// Section listing unfinished jobs close to their backoffLimit; `None` when there are none
+pub fn build_job_backoff_section(at_risk: &[JobBackoffRiskInfo]) -> Option<serde_json::Value> {
+    if at_risk.is_empty() {
+        return None;
     }
-    blocks.push(serde_json::json!({
-        "type": "section",
-        "text": {"type": "mrkdwn", "text": format!("*Missed CronJobs*\n{}", cronjob_lines.join("\n"))}
-    }));
+    let lines: Vec<String> = at_risk
+        .iter()
+        .map(|j| {
+            format!(
+                "• `{}/{}` {}/{} failures before backoffLimit ({} active)",
+                j.namespace, j.job, j.failed, j.backoff_limit, j.active
+            )
+        })
+        .collect();
+    Some(Block::section(format!("*Jobs approaching backoffLimit*\n{}", lines.join("\n"))).with_block_id("job_backoff").into())
+}
 
-    SlackPayload { text: None, blocks }
+/// Section listing CronJobs with overlapping or piled-up runs; `None` when there are none
+pub fn build_cronjob_concurrency_section(overlapping: &[CronJobConcurrencyInfo]) -> Option<serde_json::Value> {
+    if overlapping.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = overlapping
+        .iter()
+        .map(|c| {
+            let oldest = c.oldest_active_since
+                .map(|t| format!(", oldest running {}m", (chrono::Utc::now() - t).num_minutes()))
+                .unwrap_or_default();
+            let detail = match c.next_run_due {
+                Some(due) => format!(
+                    ", run due at {} blocked by the active one",
+                    due.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+                ),
+                None => String::new(),
+            };
+            format!(
+                "• `{}/{}` ({}): {} active job(s){}{}",
+                c.namespace, c.cronjob, c.concurrency_policy, c.active_jobs, oldest, detail
+            )
+        })
+        .collect();
+    Some(Block::section(format!("*CronJob concurrency*\n{}", lines.join("\n"))).with_block_id("cronjob_concurrency").into())
 }
 
-pub async fn send_to_slack(webhook_url: &str, payload: &SlackPayload) -> Result<()> {
-    let client = reqwest::Client::new();
-    let res = client
-        .post(webhook_url)
-        .json(payload)
+/// Section listing namespaces accumulating finished Jobs and pods; `None` when there are none
+pub fn build_finished_objects_section(finished: &[FinishedObjectsInfo]) -> Option<serde_json::Value> {
+    if finished.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = finished
+        .iter()
+        .map(|f| {
+            format!(
+                "• `{}`: {} completed and {} failed Jobs without ttlSecondsAfterFinished, {} Succeeded pods",
+                f.namespace, f.completed_jobs, f.failed_jobs, f.succeeded_pods
+            )
+        })
+        .collect();
+    Some(Block::section(format!("*Finished object accumulation*\n{}", lines.join("\n"))).with_block_id("finished_objects").into())
+}
+
+/// Section listing namespaces without a ResourceQuota or LimitRange; `None` when there are none
+pub fn build_namespace_quotas_section(namespaces: &[NamespaceQuotaInfo]) -> Option<serde_json::Value> {
+    if namespaces.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = namespaces
+        .iter()
+        .map(|n| {
+            let missing: Vec<&str> = [(n.has_resource_quota, "ResourceQuota"), (n.has_limit_range, "LimitRange")]
+                .iter()
+                .filter(|(present, _)| !present)
+                .map(|(_, kind)| *kind)
+                .collect();
+            format!("• `{}`: no {}", n.namespace, missing.join(" or "))
+        })
+        .collect();
+    Some(Block::section(format!("*Namespaces without quotas*\n{}", lines.join("\n"))).with_block_id("namespace_quotas").into())
+}
+
+/// Section listing containers breaking or close to breaking LimitRange constraints, which
+/// is what makes new pods of a workload fail admission; `None` when there are none
+pub fn build_limit_range_conflicts_section(conflicts: &[LimitRangeConflictInfo]) -> Option<serde_json::Value> {
+    if conflicts.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = conflicts
+        .iter()
+        .map(|c| {
+            let state = if c.violated { "new pods will be rejected" } else { "at the edge" };
+            format!(
+                "• `{}/{}` [{}] {} {} {} vs {} of LimitRange `{}`: {}",
+                c.namespace, c.workload, c.container, c.resource, c.constraint, c.value, c.bound, c.limit_range, state
+            )
+        })
+        .collect();
+    Some(Block::section(format!("*LimitRange conflicts*\n{}", lines.join("\n"))).with_block_id("limit_range_conflicts").into())
+}
+
+/// Section naming the missing ConfigMaps/Secrets pods are blocked on; `None` when there are none
+pub fn build_missing_config_refs_section(missing: &[MissingConfigRefInfo]) -> Option<serde_json::Value> {
+    if missing.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = missing
+        .iter()
+        .map(|m| {
+            let container = m.container.as_ref().map(|c| format!(" [{}]", c)).unwrap_or_default();
+            let what = match &m.key {
+                Some(key) => format!("key `{}` missing from {} `{}`", key, m.kind, m.name),
+                None => format!("{} `{}` not found", m.kind, m.name),
+            };
+            format!("• `{}/{}`{}: {}", m.namespace, m.pod, container, what)
+        })
+        .collect();
+    Some(Block::section(format!("*Missing ConfigMaps/Secrets*\n{}", lines.join("\n"))).with_block_id("missing_config").into())
+}
+
+/// Section listing pods with flapping readiness; `None` when there are none
+pub fn build_readiness_flapping_section(flapping: &[ReadinessFlapInfo]) -> Option<serde_json::Value> {
+    if flapping.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = flapping
+        .iter()
+        .map(|f| {
+            let state = if f.currently_ready { "Ready now" } else { "not Ready now" };
+            let changed = f.last_transition
+                .map(|t| format!(", last change {}m ago", (chrono::Utc::now() - t).num_minutes()))
+                .unwrap_or_default();
+            format!(
+                "• `{}/{}` {} readiness probe failures in the last {}m ({}{})",
+                f.namespace, f.pod, f.probe_failures, f.window_minutes, state, changed
+            )
+        })
+        .collect();
+    Some(Block::section(format!("*Readiness flapping*\n{}", lines.join("\n"))).with_block_id("readiness_flapping").into())
+}
+
+/// Section listing Services with rapidly changing endpoints; `None` when there are none
+pub fn build_endpoint_churn_section(churning: &[ServiceEndpointChurnInfo]) -> Option<serde_json::Value> {
+    if churning.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = churning
+        .iter()
+        .map(|s| {
+            let flapping = if s.flapping_pods.is_empty() {
+                String::new()
+            } else {
+                format!(", flapping: {}", s.flapping_pods.join(", "))
+            };
+            format!(
+                "• `{}/{}` ~{} endpoint changes across {} pods in the last {}m{}",
+                s.namespace, s.service, s.changes, s.backing_pods, s.window_minutes, flapping
+            )
+        })
+        .collect();
+    Some(Block::section(format!("*Service endpoint churn (availability risk)*\n{}", lines.join("\n"))).with_block_id("endpoint_churn").into())
+}
+
+/// Section listing dual-stack Services and pods missing an address family; `None` when there are none
+pub fn build_dual_stack_section(issues: &[DualStackIssueInfo]) -> Option<serde_json::Value> {
+    if issues.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = issues
+        .iter()
+        .map(|i| {
+            let pods = if i.pods > 0 { format!(" on {} pods", i.pods) } else { String::new() };
+            format!(
+                "• `{}/{}` no {} address{} (has {})",
+                i.namespace, i.object, i.missing_family, pods, i.addresses.join(", ")
+            )
+        })
+        .collect();
+    Some(Block::section(format!("*Dual-stack gaps*\n{}", lines.join("\n"))).with_block_id("dual_stack").into())
+}
+
+/// Context block with the summary counts (e.g. "3 OOM • 2 pending • 1 node hot"), meant to
+/// sit right under the header; `None` when nothing was found
+pub fn build_summary_context(summary: &ReportSummary) -> Option<serde_json::Value> {
+    let severities = &summary.severity_counts;
+    let by_severity = [
+        (severities.critical, Severity::Critical),
+        (severities.warning, Severity::Warning),
+        (severities.info, Severity::Info),
+    ];
+    let counts = [
+        (summary.network_agent_issue_count, "node network down"),
+        (summary.operator_issue_count, "operator failing"),
+        (summary.problematic_node_count, "node unhealthy"),
+        (summary.flapping_node_count, "node flapping"),
+        (summary.high_util_node_count, "node hot"),
+        (summary.node_disk_forecast_count, "node disk filling"),
+        (summary.oom_killed_count, "OOM"),
+        (summary.restart_count, "restarting"),
+        (summary.failed_pod_count, "failed"),
+        (summary.pending_count, "pending"),
+        (summary.unready_count, "unready"),
+        (summary.readiness_flap_count, "readiness flapping"),
+        (summary.heavy_usage_count, "heavy usage"),
+        (summary.missing_config_ref_count, "missing config"),
+        (summary.volume_scheduling_count, "volume blocked"),
+        (summary.volume_issue_count, "volume"),
+        (summary.failed_job_count, "job failed"),
+        (summary.job_backoff_risk_count, "job near backoff"),
+        (summary.missed_cronjob_count, "cronjob missed"),
+        (summary.cronjob_concurrency_count, "cronjob overlapping"),
+        (summary.endpoint_churn_count, "endpoint churn"),
+        (summary.dual_stack_count, "dual-stack gap"),
+        (summary.churn_count, "pod churn"),
+        (summary.event_flood_count, "event flood"),
+        (summary.stuck_terminating_count, "stuck terminating"),
+        (summary.custom_resource_condition_count, "custom resource condition"),
+    ];
+    let parts: Vec<String> = by_severity
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, severity)| format!("{} {} {}", severity_emoji(*severity), count, severity.name()))
+        .chain(counts.iter().filter(|(count, _)| *count > 0).map(|(count, label)| format!("{} {}", count, label)))
+        .collect();
+    if parts.is_empty() {
+        return None;
+    }
+    Some(Block::context(parts.join(" • ")).into())
+}
+
+/// Critical section listing nodes without a healthy networking agent; `None` when there are none
+pub fn build_network_agents_section(issues: &[NetworkAgentIssueInfo]) -> Option<serde_json::Value> {
+    if issues.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = issues
+        .iter()
+        .map(|i| {
+            let what = match &i.issue {
+                NetworkAgentIssue::Missing => "no pod scheduled".to_string(),
+                NetworkAgentIssue::Unhealthy { pod, phase } => format!("pod `{}` {} and not Ready", pod, phase),
+            };
+            format!("• node `{}`: `{}` {}", i.node, i.daemonset, what)
+        })
+        .collect();
+    Some(Block::section(format!(":rotating_light: *CRITICAL: node networking agents*\n{}", lines.join("\n"))).with_block_id("network_agents").into())
+}
+
+/// Section listing OLM operators whose Subscription reports a failure or whose Deployment
+/// lacks available replicas; `None` when there are none
+pub fn build_operators_section(issues: &[OperatorIssueInfo]) -> Option<serde_json::Value> {
+    if issues.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = issues
+        .iter()
+        .map(|i| {
+            let what = match &i.issue {
+                OperatorIssue::Subscription { condition, message: Some(message) } => format!("{}: {}", condition, message),
+                OperatorIssue::Subscription { condition, message: None } => condition.clone(),
+                OperatorIssue::DeploymentUnavailable { deployment, available, desired } => {
+                    format!("deployment `{}` {}/{} available", deployment, available, desired)
+                }
+            };
+            format!("• `{}/{}` {}", i.namespace, i.operator, what)
+        })
+        .collect();
+    Some(Block::section(format!(":rotating_light: *Operators*\n{}", lines.join("\n"))).with_block_id("operators").into())
+}
+
+/// Section listing nodes that went NotReady repeatedly, Ready now or not; `None` when
+/// there are none
+pub fn build_node_flapping_section(nodes: &[NodeFlapInfo]) -> Option<serde_json::Value> {
+    if nodes.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = nodes
+        .iter()
+        .map(|n| {
+            let state = if n.currently_ready { "Ready now" } else { "NotReady now" };
+            format!("• node `{}`: NotReady {} times in {}m ({})", n.name, n.not_ready_transitions, n.window_minutes, state)
+        })
+        .collect();
+    Some(Block::section(format!("*Flapping nodes*\n{}", lines.join("\n"))).with_block_id("node_flapping").into())
+}
+
+/// Section listing node filesystems nearing the kubelet image GC/eviction thresholds;
+/// `None` when there are none
+pub fn build_node_disk_section(forecasts: &[NodeDiskForecastInfo]) -> Option<serde_json::Value> {
+    if forecasts.is_empty() {
+        return None;
+    }
+    const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+    let lines: Vec<String> = forecasts
+        .iter()
+        .map(|f| {
+            let thresholds: Vec<String> = [("image GC", f.image_gc_pct), ("eviction", f.eviction_pct)]
+                .iter()
+                .filter_map(|(what, pct)| pct.map(|p| format!("{} at {:.0}%", what, p)))
+                .collect();
+            format!(
+                "• node `{}` {}: {:.0}% used ({:.1}/{:.1} GiB), {}",
+                f.name, f.filesystem, f.used_pct, f.used_bytes as f64 / GIB, f.capacity_bytes as f64 / GIB, thresholds.join(", ")
+            )
+        })
+        .collect();
+    Some(Block::section(format!("*Node disk filling up*\n{}", lines.join("\n"))).with_block_id("node_disk").into())
+}
+
+/// Section listing resources with large or fast-growing object counts, which slow down
+/// etcd and the API server; `None` when there are none
+pub fn build_object_counts_section(warnings: &[ObjectCountInfo]) -> Option<serde_json::Value> {
+    if warnings.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = warnings
+        .iter()
+        .map(|w| {
+            let growth = match w.previous {
+                Some(p) if p > 0 && w.count > p => format!(" (+{:.0}% since last run)", (w.count - p) as f64 / p as f64 * 100.0),
+                _ => String::new(),
+            };
+            format!("• `{}`: {} objects{}", w.resource, w.count, growth)
+        })
+        .collect();
+    Some(Block::section(format!("*Stored object counts*\n{}", lines.join("\n"))).with_block_id("object_counts").into())
+}
+
+/// Context note on how the API server answered during collection: calls, error rate,
+/// throttling and the slowest calls; `None` when no calls were recorded
+pub fn build_api_health_context(health: &ApiHealthInfo) -> Option<serde_json::Value> {
+    if health.requests == 0 {
+        return None;
+    }
+    let mut text = format!("API server: {} calls, {:.1}% errors", health.requests, health.error_pct());
+    if health.throttled > 0 {
+        text.push_str(&format!(", {} throttled", health.throttled));
+    }
+    if health.errors > 0 || health.throttled > 0 {
+        text.insert_str(0, ":warning: ");
+    }
+    if !health.slowest.is_empty() {
+        let slowest: Vec<String> = health
+            .slowest
+            .iter()
+            .map(|c| format!("`{} {}` {:.1}s", c.method, c.path, c.duration_ms as f64 / 1000.0))
+            .collect();
+        text.push_str(&format!(" • slowest: {}", slowest.join(", ")));
+    }
+    Some(Block::context(text).with_block_id("api_health").into())
+}
+
+/// Most chronic issues listed in the chronic section
+const MAX_CHRONIC_ISSUES: usize = 10;
+
+/// Section listing findings reported in more than one consecutive run, longest-running
+/// first, as `(fingerprint, history)`; `None` when there are none
+pub fn build_chronic_issues_section(issues: &[(String, IssueHistory)], now: chrono::DateTime<chrono::Utc>) -> Option<serde_json::Value> {
+    let mut chronic: Vec<&(String, IssueHistory)> = issues.iter().filter(|(_, h)| h.occurrences > 1).collect();
+    if chronic.is_empty() {
+        return None;
+    }
+    chronic.sort_by_key(|(fingerprint, h)| (h.first_seen, fingerprint));
+    let mut lines: Vec<String> = chronic
+        .iter()
+        .take(MAX_CHRONIC_ISSUES)
+        .map(|(fingerprint, h)| {
+            format!(
+                "• `{}`: ongoing for {}, seen in {} runs",
+                fingerprint,
+                format_age(now - h.first_seen),
+                h.occurrences
+            )
+        })
+        .collect();
+    if chronic.len() > MAX_CHRONIC_ISSUES {
+        lines.push(format!("…and {} more", chronic.len() - MAX_CHRONIC_ISSUES));
+    }
+    Some(Block::section(format!("*Chronic issues*\n{}", lines.join("\n"))).with_block_id("chronic").into())
+}
+
+/// Most cleared findings listed in the resolved section
+const MAX_RESOLVED_ISSUES: usize = 20;
+
+/// Section listing findings that cleared since the last run, as `(fingerprint, history)`;
+/// `None` when there are none
+pub fn build_resolved_section(resolved: &[(String, IssueHistory)]) -> Option<serde_json::Value> {
+    if resolved.is_empty() {
+        return None;
+    }
+    let mut lines: Vec<String> = resolved
+        .iter()
+        .take(MAX_RESOLVED_ISSUES)
+        .map(|(fingerprint, h)| match h.occurrences {
+            1 => format!("• `{}`", fingerprint),
+            n => format!("• `{}` (ongoing for {}, seen in {} runs)", fingerprint, format_age(h.last_seen - h.first_seen), n),
+        })
+        .collect();
+    if resolved.len() > MAX_RESOLVED_ISSUES {
+        lines.push(format!("…and {} more", resolved.len() - MAX_RESOLVED_ISSUES));
+    }
+    Some(Block::section(format!(":white_check_mark: *Resolved since last run*\n{}", lines.join("\n"))).with_block_id("resolved").into())
+}
+
+/// "Act on these first": the `top` highest-ranked findings, each with how many objects
+/// it covers and how long it has been open; `None` when nothing ranks
+pub fn build_priorities_section(priorities: &[PriorityIssue], top: usize) -> Option<serde_json::Value> {
+    if priorities.is_empty() || top == 0 {
+        return None;
+    }
+    let lines: Vec<String> = priorities
+        .iter()
+        .take(top)
+        .enumerate()
+        .map(|(i, p)| {
+            let object = match &p.namespace {
+                Some(namespace) => format!("{}/{}", namespace, p.name),
+                None => p.name.clone(),
+            };
+            let affected = if p.affected > 1 { format!(" ×{}", p.affected) } else { String::new() };
+            let open = p.open_for.map(|d| format!(", open {}", format_age(d))).unwrap_or_default();
+            format!("{}. `{}` — {}{}{}", i + 1, object, p.section, affected, open)
+        })
+        .collect();
+    Some(Block::section(format!("*:dart: Act on these first*\n{}", lines.join("\n"))).with_block_id("priorities").into())
+}
+
+/// `3 days`, `5 hours`, `20 minutes`
+fn format_age(age: chrono::Duration) -> String {
+    let (amount, unit) = if age.num_days() >= 1 {
+        (age.num_days(), "day")
+    } else if age.num_hours() >= 1 {
+        (age.num_hours(), "hour")
+    } else {
+        (age.num_minutes().max(1), "minute")
+    };
+    format!("{} {}{}", amount, unit, if amount == 1 { "" } else { "s" })
+}
+
+/// Section flagging findings that came back after their snooze expired; `None` when there are none
+pub fn build_previously_snoozed_section(issues: &[PreviouslySnoozedInfo]) -> Option<serde_json::Value> {
+    if issues.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = issues
+        .iter()
+        .map(|i| {
+            let reason = i.reason.as_deref().map(|r| format!(" ({})", r)).unwrap_or_default();
+            format!(
+                "• `{}`: previously snoozed until {}{}",
+                i.fingerprint,
+                i.snoozed_until.format("%Y-%m-%d %H:%M UTC"),
+                reason
+            )
+        })
+        .collect();
+    Some(Block::section(format!("*Snooze expired, still failing*\n{}", lines.join("\n"))).with_block_id("previously_snoozed").into())
+}
+
+/// Section listing workloads with abnormal pod churn; `None` when there are none
+pub fn build_pod_churn_section(churn: &[PodChurnInfo]) -> Option<serde_json::Value> {
+    if churn.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = churn
+        .iter()
+        .map(|c| {
+            format!(
+                "• `{}/{}` ({}): {} pods created, {} deleted in the last {}m",
+                c.namespace, c.workload, c.kind, c.created, c.deleted, c.window_minutes
+            )
+        })
+        .collect();
+    Some(Block::section(format!("*Pod churn*\n{}", lines.join("\n"))).with_block_id("pod_churn").into())
+}
+
+/// Section listing namespaces flooded with Warning events and their top reasons; `None`
+/// when there are none
+pub fn build_event_flood_section(floods: &[EventFloodInfo]) -> Option<serde_json::Value> {
+    if floods.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = floods
+        .iter()
+        .map(|f| {
+            let reasons: Vec<String> = f.top_reasons.iter().map(|(reason, count)| format!("{} ×{}", reason, count)).collect();
+            format!(
+                "• `{}`: {} Warning events in the last {}m ({})",
+                f.namespace, f.warnings, f.window_minutes, reasons.join(", ")
+            )
+        })
+        .collect();
+    Some(Block::section(format!("*Warning event floods*\n{}", lines.join("\n"))).with_block_id("event_flood").into())
+}
+
+/// Section listing objects and namespaces stuck in Terminating with the finalizers
+/// holding them; `None` when there are none
+pub fn build_stuck_terminating_section(stuck: &[StuckTerminatingInfo]) -> Option<serde_json::Value> {
+    if stuck.is_empty() {
+        return None;
+    }
+    let now = chrono::Utc::now();
+    let lines: Vec<String> = stuck
+        .iter()
+        .map(|s| {
+            let finalizers = if s.finalizers.is_empty() {
+                "no finalizers left".to_string()
+            } else {
+                format!("finalizers `{}`", s.finalizers.join("`, `"))
+            };
+            // A namespace is its own scope
+            let target = if s.object.starts_with("Namespace/") {
+                s.object.clone()
+            } else {
+                format!("{}/{}", s.namespace, s.object)
+            };
+            let mut line = format!("• `{}` terminating for {}, {}", target, format_age(now - s.since), finalizers);
+            for detail in &s.details {
+                line.push_str(&format!("\n    ↳ {}", detail));
+            }
+            line
+        })
+        .collect();
+    Some(Block::section(format!("*Stuck in Terminating*\n{}", lines.join("\n"))).with_block_id("stuck_terminating").into())
+}
+
+/// Section listing custom resources whose status conditions differ from the configured
+/// expectation, with the condition's reason and message; `None` when there are none
+pub fn build_custom_resources_section(violations: &[CustomResourceConditionInfo]) -> Option<serde_json::Value> {
+    if violations.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = violations
+        .iter()
+        .map(|v| {
+            let actual = match &v.actual {
+                Some(actual) => format!("{}={} (expected {})", v.condition, actual, v.expected),
+                None => format!("{} not reported (expected {})", v.condition, v.expected),
+            };
+            let mut line = format!("• `{}/{}` {}", v.namespace, v.resource, actual);
+            match (&v.reason, &v.message) {
+                (Some(reason), Some(message)) => line.push_str(&format!("\n    ↳ {}: {}", reason, message)),
+                (Some(detail), None) | (None, Some(detail)) => line.push_str(&format!("\n    ↳ {}", detail)),
+                (None, None) => {}
+            }
+            line
+        })
+        .collect();
+    Some(Block::section(format!("*Custom resource conditions*\n{}", lines.join("\n"))).with_block_id("custom_resources").into())
+}
+
+/// Slack message for a report: the fixed sections, the optional ones, ordering and footer
+pub fn build_report_payload(report: &HealthReport, summary: &ReportSummary) -> SlackPayload {
+    let mut payload = build_slack_payload(report);
+    // Counts and the top priorities go right under the header so readers get the gist
+    // before the sections
+    let mut top: Vec<serde_json::Value> = build_oncall_section(report.oncall_mention.as_deref(), critical_count(report))
+        .into_iter()
+        .chain(build_summary_context(summary))
+        .collect();
+    if report.config.priority_top_n > 0 {
+        top.extend(build_priorities_section(&prioritize(report, chrono::Utc::now()), report.config.priority_top_n));
+    }
+    payload.blocks.splice(1..1, top);
+    payload.blocks.extend(build_readiness_flapping_section(&report.pod_metrics.readiness_flapping));
+    payload.blocks.extend(build_endpoint_churn_section(&report.pod_metrics.endpoint_churn));
+    payload.blocks.extend(build_dual_stack_section(&report.pod_metrics.dual_stack));
+    payload.blocks.extend(build_missing_config_refs_section(&report.pod_metrics.missing_config_refs));
+    payload.blocks.extend(build_volume_scheduling_section(&report.pod_metrics.volume_scheduling));
+    payload.blocks.extend(build_network_agents_section(&report.cluster_metrics.network_agent_issues));
+    payload.blocks.extend(build_operators_section(&report.cluster_metrics.operator_issues));
+    payload.blocks.extend(build_node_flapping_section(&report.cluster_metrics.flapping_nodes));
+    payload.blocks.extend(build_node_disk_section(&report.cluster_metrics.node_disk_forecast));
+    payload.blocks.extend(build_object_counts_section(&report.cluster_metrics.object_count_warnings));
+    payload.blocks.extend(build_job_backoff_section(&report.job_metrics.backoff_risk));
+    payload.blocks.extend(build_cronjob_concurrency_section(&report.job_metrics.cronjob_concurrency));
+    payload.blocks.extend(build_pod_churn_section(&report.pod_metrics.churn));
+    payload.blocks.extend(build_event_flood_section(&report.pod_metrics.event_flood));
+    payload.blocks.extend(build_stuck_terminating_section(&report.pod_metrics.stuck_terminating));
+    payload.blocks.extend(build_custom_resources_section(&report.pod_metrics.custom_resource_conditions));
+    payload.blocks.extend(build_stale_metrics_section(&report.pod_metrics.stale_metrics));
+    payload.blocks.extend(build_finished_objects_section(&report.job_metrics.finished_objects));
+    payload.blocks.extend(build_resource_hygiene_section(&report.pod_metrics.resource_hygiene));
+    payload.blocks.extend(build_pod_security_section(&report.pod_metrics.pod_security));
+    payload.blocks.extend(build_namespace_quotas_section(&report.pod_metrics.namespace_quotas));
+    payload.blocks.extend(build_limit_range_conflicts_section(&report.pod_metrics.limit_range_conflicts));
+    payload.blocks.extend(build_recommendations_section(&report.recommendations));
+    payload.blocks.extend(build_previously_snoozed_section(&report.previously_snoozed));
+    let history: Vec<(String, IssueHistory)> = report
+        .fingerprints()
+        .into_iter()
+        .filter_map(|f| report.issue_history.get(&f).map(|h| (f, h.clone())))
+        .collect();
+    payload.blocks.extend(build_chronic_issues_section(&history, chrono::Utc::now()));
+    let resolved: Vec<(String, IssueHistory)> = report.resolved.iter().map(|(f, h)| (f.clone(), h.clone())).collect();
+    payload.blocks.extend(build_resolved_section(&resolved));
+    payload.blocks.extend(build_owners_section(&report.team_issue_counts()));
+    payload.blocks.extend(report.api_health.as_ref().and_then(build_api_health_context));
+    mark_section_severities(&mut payload, &section_severities(report));
+    apply_section_order(&mut payload, &report.config.section_order);
+    payload.blocks.push(build_run_footer(&report.run_id, report.suppressed_count));
+    payload
+}
+
+/// Most blocks Slack accepts in one message
+const MAX_BLOCKS_PER_MESSAGE: usize = 50;
+
+/// Longest section text Slack accepts, in characters
+const MAX_SECTION_TEXT: usize = 3000;
+
+/// `payload` as messages Slack accepts. A section longer than `MAX_SECTION_TEXT` keeps its
+/// title and first findings and counts the rest in an "…and N more" line; the blocks are
+/// spread over messages of at most `MAX_BLOCKS_PER_MESSAGE`, each after the first opening
+/// with a continuation note.
+pub fn split_payload(payload: &SlackPayload) -> Vec<SlackPayload> {
+    let mut blocks = payload.blocks.clone();
+    for block in &mut blocks {
+        let fitted = block["text"]["text"]
+            .as_str()
+            .filter(|text| block["type"] == "section" && text.chars().count() > MAX_SECTION_TEXT)
+            .map(|text| fit_section_text(text, MAX_SECTION_TEXT));
+        if let Some(fitted) = fitted {
+            block["text"]["text"] = fitted.into();
+        }
+    }
+    if blocks.len() <= MAX_BLOCKS_PER_MESSAGE {
+        return vec![SlackPayload { text: payload.text.clone(), blocks }];
+    }
+
+    let total = 1 + (blocks.len() - MAX_BLOCKS_PER_MESSAGE).div_ceil(MAX_BLOCKS_PER_MESSAGE - 1);
+    let mut blocks = blocks.into_iter();
+    let mut messages = vec![SlackPayload { text: payload.text.clone(), blocks: blocks.by_ref().take(MAX_BLOCKS_PER_MESSAGE).collect() }];
+    loop {
+        let chunk: Vec<serde_json::Value> = blocks.by_ref().take(MAX_BLOCKS_PER_MESSAGE - 1).collect();
+        if chunk.is_empty() {
+            break;
+        }
+        let note: serde_json::Value = Block::context(format!("_Continued ({}/{})_", messages.len() + 1, total)).into();
+        messages.push(SlackPayload { text: payload.text.clone(), blocks: std::iter::once(note).chain(chunk).collect() });
+    }
+    messages
+}
+
+/// `text` within `limit` characters: its title lines and as many whole findings (a `•`
+/// line and the lines under it) as fit, then "…and N more" counting the findings left out
+/// plus those the section had already left out
+fn fit_section_text(text: &str, limit: usize) -> String {
+    static MORE: OnceLock<Regex> = OnceLock::new();
+    let more = MORE.get_or_init(|| Regex::new(r"^…and (\d+) more$").unwrap());
+
+    let mut lines: Vec<&str> = text.lines().collect();
+    let mut omitted = lines.last().and_then(|l| more.captures(l)).and_then(|c| c[1].parse::<usize>().ok()).unwrap_or(0);
+    if omitted > 0 {
+        lines.pop();
+    }
+    let mut title = Vec::new();
+    let mut findings: Vec<Vec<&str>> = Vec::new();
+    for line in lines {
+        if line.starts_with('•') {
+            findings.push(vec![line]);
+        } else if let Some(finding) = findings.last_mut() {
+            finding.push(line);
+        } else {
+            title.push(line);
+        }
+    }
+
+    // Leave room for the "…and N more" line
+    let budget = limit.saturating_sub(32);
+    let mut fitted = cut_text(&title.join("\n"), budget);
+    let mut shown = 0;
+    for finding in &findings {
+        let finding = finding.join("\n");
+        if fitted.chars().count() + 1 + finding.chars().count() > budget {
+            break;
+        }
+        fitted.push('\n');
+        fitted.push_str(&finding);
+        shown += 1;
+    }
+    // A single finding too long for the section (e.g. with a log tail) is cut instead
+    if shown == 0 && !findings.is_empty() {
+        fitted.push('\n');
+        let room = budget.saturating_sub(fitted.chars().count());
+        fitted.push_str(&cut_text(&findings[0].join("\n"), room));
+        shown = 1;
+    }
+    omitted += findings.len() - shown;
+    if omitted > 0 {
+        fitted.push_str(&format!("\n…and {} more", omitted));
+    }
+    fitted
+}
+
+/// `text` cut to at most `limit` characters, closing a code block the cut leaves open
+fn cut_text(text: &str, limit: usize) -> String {
+    if text.chars().count() <= limit {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(limit.saturating_sub(5)).collect();
+    cut.push('…');
+    if cut.matches("```").count() % 2 == 1 {
+        cut.push_str("\n```");
+    }
+    cut
+}
+
+/// Post `payload` to the webhook, as several messages when it exceeds Slack's limits
+/// (see `split_payload`)
+pub async fn send_to_slack(
+    client: &reqwest::Client,
+    webhook_url: &str,
+    payload: &SlackPayload,
+    signer: Option<&PayloadSigner>,
+) -> Result<()> {
+    let messages = split_payload(payload);
+    if messages.len() > 1 {
+        info!("Report exceeds Slack's {} block limit, sending it as {} messages", MAX_BLOCKS_PER_MESSAGE, messages.len());
+    }
+    for message in &messages {
+        post_webhook_message(client, webhook_url, message, signer).await?;
+    }
+    Ok(())
+}
+
+async fn post_webhook_message(
+    client: &reqwest::Client,
+    webhook_url: &str,
+    payload: &SlackPayload,
+    signer: Option<&PayloadSigner>,
+) -> Result<()> {
+    let body = serde_json::to_vec(payload)?;
+    let mut request = client.post(webhook_url).header("content-type", "application/json");
+    if let Some(signer) = signer {
+        let timestamp = chrono::Utc::now().timestamp();
+        request = request
+            .header(SIGNATURE_TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, signer.sign(&body, timestamp));
+    }
+    let res = request
+        .body(body)
         .send()
         .await
         .context("Failed to send Slack request")?;
@@ -304,7 +1370,8 @@ pub async fn send_to_slack(webhook_url: &str, payload: &SlackPayload) -> Result<
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Utc;
+    use crate::types::{ApiCallInfo, ChangeFreezeInfo, FailedJobInfo, HeavyUsagePod, PendingPodInfo, ProblematicNodeInfo, RestartEventInfo};
+    use chrono::{TimeZone, Utc};
 
     #[test]
     fn test_build_slack_payload_basic() {
@@ -317,19 +1384,27 @@ mod tests {
             cluster_name: Some("test-cluster".to_string()),
             datacenter_name: Some("us-east-1".to_string()),
             fail_if_no_metrics: true,
+            ..Default::default()
         };
         
         let heavy_usage = vec![
             HeavyUsagePod {
+                labels: Default::default(),
                 namespace: "default".to_string(),
                 pod: "heavy-pod".to_string(),
                 cpu_pct: Some(90.0),
                 mem_pct: Some(95.0),
+                cpu_millicores: 900,
+                memory_bytes: 512 * 1024 * 1024,
             }
         ];
         
         let restarts = vec![
             RestartEventInfo {
+                labels: Default::default(),
+                node_name: None,
+                zone: None,
+                blast_radius: None,
                 namespace: "default".to_string(),
                 pod: "restart-pod".to_string(),
                 container: "main".to_string(),
@@ -337,19 +1412,34 @@ mod tests {
                 reason: Some("Error".to_string()),
                 message: Some("Container crashed".to_string()),
                 exit_code: Some(1),
+                image: None,
+                log_tail: None,
             }
         ];
         
         let pendings = vec![
             PendingPodInfo {
+                labels: Default::default(),
+                node_name: None,
+                zone: None,
+                blast_radius: None,
                 namespace: "default".to_string(),
                 pod: "pending-pod".to_string(),
                 since: Utc::now(),
                 duration_minutes: 10,
+                image_pull_failures: Vec::new(),
             }
         ];
         
-        let payload = build_slack_payload(&config, &heavy_usage, &restarts, &pendings, &[], &[], &[], &[], &[], &[], &[], &[]);
+        let mut report = HealthReport::new(config);
+        
+        report.pod_metrics.heavy_usage = heavy_usage;
+        
+        report.pod_metrics.restarts = restarts;
+        
+        report.pod_metrics.pending = pendings;
+        
+        let payload = build_slack_payload(&report);
         
         // Check that payload has blocks
         assert!(!payload.blocks.is_empty());
@@ -363,6 +1453,10 @@ mod tests {
         let header_text = header.get("text").unwrap().get("text").unwrap().as_str().unwrap();
         assert!(header_text.contains("test-cluster"));
         assert!(header_text.contains("us-east-1"));
+
+        // Absolute usage next to the percentages
+        let heavy_text = payload.blocks[2]["text"]["text"].as_str().unwrap();
+        assert!(heavy_text.contains("• `default/heavy-pod:` CPU 90% (900m) | MEM 95% (512Mi)"));
     }
 
     #[test]
@@ -376,9 +1470,12 @@ mod tests {
             cluster_name: None,
             datacenter_name: None,
             fail_if_no_metrics: true,
+            ..Default::default()
         };
         
-        let payload = build_slack_payload(&config, &[], &[], &[], &[], &[], &[], &[], &[], &[], &[], &[]);
+        let report = HealthReport::new(config);
+        
+        let payload = build_slack_payload(&report);
         
         // Should have 13 blocks: header, config info, and 11 metric sections
         assert_eq!(payload.blocks.len(), 13);
@@ -396,4 +1493,1003 @@ mod tests {
         let pending_text = pending_section.get("text").unwrap().get("text").unwrap().as_str().unwrap();
         assert!(pending_text.contains("No pending pods beyond grace"));
     }
+
+    #[test]
+    fn test_build_slack_payload_omit_empty_sections() {
+        let config = Config {
+            namespaces: vec!["default".to_string()],
+            omit_empty_sections: true,
+            ..Default::default()
+        };
+        let pendings = vec![PendingPodInfo {
+            labels: Default::default(),
+            node_name: None,
+            zone: None,
+            blast_radius: None,
+            namespace: "default".to_string(),
+            pod: "pending-pod".to_string(),
+            since: Utc::now(),
+            duration_minutes: 10,
+            image_pull_failures: Vec::new(),
+        }];
+
+        let mut report = HealthReport::new(config);
+
+        report.pod_metrics.pending = pendings;
+
+        let payload = build_slack_payload(&report);
+
+        // Header, config info and the only section with findings
+        assert_eq!(payload.blocks.len(), 3);
+        let text = payload.blocks[2]["text"]["text"].as_str().unwrap();
+        assert!(text.starts_with("*Pending pods*"));
+        assert!(text.contains("pending-pod"));
+    }
+
+    #[test]
+    fn test_build_slack_payload_node_os() {
+        let config = Config { omit_empty_sections: true, ..Default::default() };
+        let mut report = HealthReport::new(config);
+        for (name, os) in [("linux-1", "linux"), ("linux-2", "linux"), ("win-1", "windows")] {
+            report.cluster_metrics.problematic_nodes.push(ProblematicNodeInfo {
+                name: name.to_string(),
+                os: os.to_string(),
+                conditions: vec!["NotReady".to_string()],
+                since: Utc::now(),
+            });
+        }
+
+        let payload = build_slack_payload(&report);
+        let text = payload.blocks[2]["text"]["text"].as_str().unwrap();
+        assert!(text.starts_with("*Problematic nodes*\nBy OS: 2 linux, 1 windows\n• `linux-1` NotReady"));
+        assert!(text.contains("• `win-1` (windows) NotReady"));
+
+        // One OS, no breakdown
+        report.cluster_metrics.problematic_nodes.truncate(2);
+        let payload = build_slack_payload(&report);
+        assert!(!payload.blocks[2]["text"]["text"].as_str().unwrap().contains("By OS"));
+    }
+
+    #[test]
+    fn test_build_slack_payload_section_mentions() {
+        let mentions = [("nodes", "<!subteam^S0123ABC>"), ("oom", "<@U0456DEF>"), ("restarts", "<@U0789GHI>")];
+        let config = Config {
+            slack_section_mentions: mentions.iter().map(|(s, m)| (s.to_string(), m.to_string())).collect(),
+            ..Default::default()
+        };
+        let mut report = HealthReport::new(config);
+        assert!(build_slack_payload(&report).blocks[1]["text"]["text"].as_str().unwrap().starts_with("Namespaces:"));
+
+        report.cluster_metrics.problematic_nodes.push(ProblematicNodeInfo {
+            name: "node-1".to_string(),
+            os: "linux".to_string(),
+            conditions: vec!["NotReady".to_string()],
+            since: Utc::now(),
+        });
+        report.pod_metrics.restarts.push(RestartEventInfo {
+            labels: Default::default(),
+            node_name: None,
+            zone: None,
+            blast_radius: None,
+            namespace: "default".to_string(),
+            pod: "api-0".to_string(),
+            container: "main".to_string(),
+            last_restart_time: None,
+            reason: None,
+            message: None,
+            exit_code: None,
+            image: None,
+            log_tail: None,
+        });
+
+        // Restarts are not critical and there are no OOM kills: only the nodes are mentioned
+        let payload = build_slack_payload(&report);
+        assert_eq!(
+            payload.blocks[1]["text"]["text"],
+            ":rotating_light: *Critical findings*\n<!subteam^S0123ABC> `nodes`: 1 critical finding(s)"
+        );
+    }
+
+    #[test]
+    fn test_build_slack_payload_log_tail() {
+        let restarts = vec![RestartEventInfo {
+            labels: [("team".to_string(), "payments".to_string())].into(),
+            node_name: Some("node-1".to_string()),
+            zone: Some("eu-west-1a".to_string()),
+            blast_radius: None,
+            namespace: "default".to_string(),
+            pod: "api-0".to_string(),
+            container: "main".to_string(),
+            last_restart_time: None,
+            reason: Some("Error".to_string()),
+            message: None,
+            exit_code: Some(1),
+            image: Some("api:2.3.1".to_string()),
+            log_tail: Some("panic: connection refused".to_string()),
+        }];
+
+        let mut report = HealthReport::new(Config::default());
+
+        report.pod_metrics.restarts = restarts;
+
+        let payload = build_slack_payload(&report);
+        let text = payload.blocks[3]["text"]["text"].as_str().unwrap();
+        assert!(text.contains("• `default/api-0` on `node-1` (eu-west-1a) [main] `api:2.3.1` Error (exit 1: application error) -  _team=payments_"));
+        assert!(text.contains("  last: -\n```panic: connection refused```"));
+    }
+
+    #[test]
+    fn test_blast_radius_line() {
+        assert_eq!(blast_radius_line(&None), None);
+        let radius = BlastRadius {
+            services: vec!["api".to_string()],
+            public_ingresses: vec!["api".to_string(), "api-v2".to_string()],
+            internal_ingresses: Vec::new(),
+        };
+        assert_eq!(blast_radius_line(&Some(radius)).unwrap(), "  affects 2 public Ingresses, 1 Service");
+    }
+
+    #[test]
+    fn test_build_owners_section() {
+        assert!(build_owners_section(&[]).is_none());
+
+        let counts = vec![
+            (Some("payments".to_string()), Some("@payments-oncall".to_string()), 3),
+            (Some("platform".to_string()), None, 1),
+            (None, None, 2),
+        ];
+        let section = build_owners_section(&counts).unwrap();
+        assert_eq!(section["block_id"], "owners");
+        let text = section["text"]["text"].as_str().unwrap();
+        assert!(text.contains("• *payments* (@payments-oncall): 3"));
+        assert!(text.contains("• *platform*: 1"));
+        assert!(text.contains("• _unowned_: 2"));
+
+        let context = build_team_context("payments", Some("@payments-oncall"));
+        assert_eq!(context["elements"][0]["text"], "Findings owned by *payments* (@payments-oncall)");
+    }
+
+    #[test]
+    fn test_build_run_footer() {
+        let footer = build_run_footer("3f2a9c1e-0b7d-4e55-9a11-2c6d8e4f0a12", 0);
+        assert_eq!(footer["type"], "context");
+        assert_eq!(footer["elements"][0]["text"], "Run ID: `3f2a9c1e-0b7d-4e55-9a11-2c6d8e4f0a12`");
+
+        let footer = build_run_footer("3f2a9c1e-0b7d-4e55-9a11-2c6d8e4f0a12", 4);
+        assert_eq!(footer["elements"][0]["text"], "Run ID: `3f2a9c1e-0b7d-4e55-9a11-2c6d8e4f0a12` • 4 suppressed");
+    }
+
+    #[test]
+    fn test_apply_section_order() {
+        let config = Config::default();
+        let report = HealthReport::new(config);
+        let mut payload = build_slack_payload(&report);
+        payload.blocks.extend(build_pod_churn_section(&[PodChurnInfo {
+            namespace: "default".to_string(),
+            kind: "Deployment".to_string(),
+            workload: "api".to_string(),
+            created: 12,
+            deleted: 11,
+            window_minutes: 60,
+        }]));
+
+        apply_section_order(&mut payload, &["pod_churn".to_string(), "nodes".to_string(), "oom".to_string()]);
+
+        // Header and config info stay on top, then only the selected sections in order
+        assert_eq!(payload.blocks.len(), 5);
+        assert_eq!(payload.blocks[0]["type"], "header");
+        assert!(payload.blocks[1].get("block_id").is_none());
+        let ids: Vec<&str> = payload.blocks[2..].iter().map(|b| b["block_id"].as_str().unwrap()).collect();
+        assert_eq!(ids, vec!["pod_churn", "nodes", "oom"]);
+    }
+
+    #[test]
+    fn test_build_stale_metrics_section() {
+        assert!(build_stale_metrics_section(&[]).is_none());
+
+        let stale = vec![StaleMetricsInfo {
+            namespace: "default".to_string(),
+            pod: "api-1".to_string(),
+            sampled_at: Utc::now(),
+            age_minutes: 12,
+            window: Some("30s".to_string()),
+        }];
+        let section = build_stale_metrics_section(&stale).unwrap();
+        let text = section["text"]["text"].as_str().unwrap();
+        assert!(text.contains("*Stale pod metrics*"));
+        assert!(text.contains("default/api-1"));
+        assert!(text.contains("12m old"));
+        assert!(text.contains("window 30s"));
+    }
+
+    #[test]
+    fn test_build_recommendations_section() {
+        assert!(build_recommendations_section(&[]).is_none());
+
+        let recs = vec![RightsizingRecommendation {
+            namespace: "default".to_string(),
+            kind: "Deployment".to_string(),
+            workload: "api".to_string(),
+            samples: 48,
+            current_cpu_request: Some(1000),
+            suggested_cpu_request: 250,
+            current_memory_request: None,
+            suggested_memory_request: 512 * 1024 * 1024,
+            current_memory_limit: Some(2048 * 1024 * 1024),
+            suggested_memory_limit: 640 * 1024 * 1024,
+        }];
+        let section = build_recommendations_section(&recs).unwrap();
+        let text = section["text"]["text"].as_str().unwrap();
+        assert!(text.contains("default/api"));
+        assert!(text.contains("CPU req 1 → 250m"));
+        assert!(text.contains("MEM req unset → 512Mi"));
+        assert!(text.contains("MEM limit 2Gi → 640Mi"));
+    }
+
+    #[test]
+    fn test_build_volume_scheduling_section() {
+        assert!(build_volume_scheduling_section(&[]).is_none());
+
+        let blocked = vec![VolumeSchedulingInfo {
+            namespace: "default".to_string(),
+            pod: "db-0".to_string(),
+            claims: vec!["data-db-0".to_string()],
+            reason: VolumeSchedulingReason::NodeAffinityConflict,
+            since: chrono::Utc::now() - chrono::Duration::minutes(20),
+            message: None,
+        }];
+        let section = build_volume_scheduling_section(&blocked).unwrap();
+        let text = section["text"]["text"].as_str().unwrap();
+        assert!(text.contains("`default/db-0` claims data-db-0: volume node affinity conflict (pending 20m)"));
+    }
+
+    #[test]
+    fn test_build_job_backoff_section() {
+        assert!(build_job_backoff_section(&[]).is_none());
+
+        let at_risk = vec![JobBackoffRiskInfo {
+            namespace: "batch".to_string(),
+            job: "import".to_string(),
+            failed: 4,
+            backoff_limit: 6,
+            active: 1,
+        }];
+        let section = build_job_backoff_section(&at_risk).unwrap();
+        let text = section["text"]["text"].as_str().unwrap();
+        assert!(text.contains("`batch/import` 4/6 failures before backoffLimit (1 active)"));
+    }
+
+    #[test]
+    fn test_build_cronjob_concurrency_section() {
+        assert!(build_cronjob_concurrency_section(&[]).is_none());
+
+        let due = chrono::DateTime::parse_from_rfc3339("2024-05-01T12:00:00Z").unwrap().with_timezone(&Utc);
+        let overlapping = vec![
+            CronJobConcurrencyInfo {
+                namespace: "batch".to_string(),
+                cronjob: "report".to_string(),
+                concurrency_policy: "Forbid".to_string(),
+                active_jobs: 1,
+                oldest_active_since: None,
+                next_run_due: Some(due),
+            },
+            CronJobConcurrencyInfo {
+                namespace: "batch".to_string(),
+                cronjob: "sync".to_string(),
+                concurrency_policy: "Allow".to_string(),
+                active_jobs: 4,
+                oldest_active_since: None,
+                next_run_due: None,
+            },
+        ];
+        let section = build_cronjob_concurrency_section(&overlapping).unwrap();
+        let text = section["text"]["text"].as_str().unwrap();
+        assert!(text.contains("`batch/report` (Forbid): 1 active job(s), run due at 2024-05-01T12:00:00Z blocked by the active one"));
+        assert!(text.ends_with("`batch/sync` (Allow): 4 active job(s)"));
+    }
+
+    #[test]
+    fn test_build_finished_objects_section() {
+        assert!(build_finished_objects_section(&[]).is_none());
+
+        let finished = vec![FinishedObjectsInfo {
+            namespace: "batch".to_string(),
+            completed_jobs: 340,
+            failed_jobs: 12,
+            succeeded_pods: 352,
+        }];
+        let section = build_finished_objects_section(&finished).unwrap();
+        let text = section["text"]["text"].as_str().unwrap();
+        assert!(text.contains("`batch`: 340 completed and 12 failed Jobs without ttlSecondsAfterFinished, 352 Succeeded pods"));
+    }
+
+    #[test]
+    fn test_build_namespace_quotas_section() {
+        assert!(build_namespace_quotas_section(&[]).is_none());
+
+        let namespaces = vec![
+            NamespaceQuotaInfo { namespace: "team-a".to_string(), has_resource_quota: false, has_limit_range: true },
+            NamespaceQuotaInfo { namespace: "team-b".to_string(), has_resource_quota: false, has_limit_range: false },
+        ];
+        let section = build_namespace_quotas_section(&namespaces).unwrap();
+        assert_eq!(section["block_id"], "namespace_quotas");
+        let text = section["text"]["text"].as_str().unwrap();
+        assert!(text.contains("• `team-a`: no ResourceQuota\n"));
+        assert!(text.ends_with("• `team-b`: no ResourceQuota or LimitRange"));
+    }
+
+    #[test]
+    fn test_build_limit_range_conflicts_section() {
+        assert!(build_limit_range_conflicts_section(&[]).is_none());
+
+        let conflict = |constraint: &str, value: &str, bound: &str, violated| LimitRangeConflictInfo {
+            namespace: "payments".to_string(),
+            workload: "Deployment/api".to_string(),
+            container: "app".to_string(),
+            limit_range: "limits".to_string(),
+            resource: "memory".to_string(),
+            constraint: constraint.to_string(),
+            value: value.to_string(),
+            bound: bound.to_string(),
+            violated,
+        };
+        let section = build_limit_range_conflicts_section(&[
+            conflict("max limit", "2Gi", "1Gi", true),
+            conflict("min request", "64Mi", "64Mi", false),
+        ])
+        .unwrap();
+        let text = section["text"]["text"].as_str().unwrap();
+        assert!(text.contains("`payments/Deployment/api` [app] memory max limit 2Gi vs 1Gi of LimitRange `limits`: new pods will be rejected"));
+        assert!(text.ends_with("min request 64Mi vs 64Mi of LimitRange `limits`: at the edge"));
+    }
+
+    #[test]
+    fn test_build_missing_config_refs_section() {
+        assert!(build_missing_config_refs_section(&[]).is_none());
+
+        let missing = vec![
+            MissingConfigRefInfo {
+                namespace: "default".to_string(),
+                pod: "api-0".to_string(),
+                container: Some("api".to_string()),
+                kind: "Secret".to_string(),
+                name: "api-token".to_string(),
+                key: None,
+            },
+            MissingConfigRefInfo {
+                namespace: "default".to_string(),
+                pod: "web-0".to_string(),
+                container: None,
+                kind: "ConfigMap".to_string(),
+                name: "web-config".to_string(),
+                key: Some("LISTEN_ADDR".to_string()),
+            },
+        ];
+        let section = build_missing_config_refs_section(&missing).unwrap();
+        let text = section["text"]["text"].as_str().unwrap();
+        assert!(text.contains("• `default/api-0` [api]: Secret `api-token` not found"));
+        assert!(text.contains("• `default/web-0`: key `LISTEN_ADDR` missing from ConfigMap `web-config`"));
+    }
+
+    #[test]
+    fn test_build_readiness_flapping_section() {
+        assert!(build_readiness_flapping_section(&[]).is_none());
+
+        let flapping = vec![ReadinessFlapInfo {
+            namespace: "default".to_string(),
+            pod: "api-0".to_string(),
+            probe_failures: 12,
+            currently_ready: true,
+            last_transition: None,
+            window_minutes: 60,
+        }];
+        let section = build_readiness_flapping_section(&flapping).unwrap();
+        let text = section["text"]["text"].as_str().unwrap();
+        assert!(text.contains("`default/api-0` 12 readiness probe failures in the last 60m (Ready now)"));
+    }
+
+    #[test]
+    fn test_build_endpoint_churn_section() {
+        assert!(build_endpoint_churn_section(&[]).is_none());
+
+        let churning = vec![ServiceEndpointChurnInfo {
+            namespace: "default".to_string(),
+            service: "api".to_string(),
+            backing_pods: 3,
+            changes: 14,
+            flapping_pods: vec!["api-0".to_string()],
+            window_minutes: 60,
+        }];
+        let section = build_endpoint_churn_section(&churning).unwrap();
+        let text = section["text"]["text"].as_str().unwrap();
+        assert!(text.contains("`default/api` ~14 endpoint changes across 3 pods in the last 60m, flapping: api-0"));
+    }
+
+    #[test]
+    fn test_build_dual_stack_section() {
+        assert!(build_dual_stack_section(&[]).is_none());
+
+        let issues = vec![
+            DualStackIssueInfo {
+                namespace: "default".to_string(),
+                object: "Service/web".to_string(),
+                missing_family: "IPv6".to_string(),
+                addresses: vec!["10.96.0.11".to_string()],
+                pods: 0,
+            },
+            DualStackIssueInfo {
+                namespace: "default".to_string(),
+                object: "Deployment/web".to_string(),
+                missing_family: "IPv6".to_string(),
+                addresses: vec!["10.244.2.7".to_string()],
+                pods: 2,
+            },
+        ];
+        let section = build_dual_stack_section(&issues).unwrap();
+        let text = section["text"]["text"].as_str().unwrap();
+        assert!(text.contains("`default/Service/web` no IPv6 address (has 10.96.0.11)"));
+        assert!(text.contains("`default/Deployment/web` no IPv6 address on 2 pods (has 10.244.2.7)"));
+    }
+
+    #[test]
+    fn test_build_summary_context() {
+        assert!(build_summary_context(&ReportSummary::default()).is_none());
+
+        let summary = ReportSummary {
+            oom_killed_count: 3,
+            pending_count: 2,
+            high_util_node_count: 1,
+            ..Default::default()
+        };
+        let context = build_summary_context(&summary).unwrap();
+        assert_eq!(context["type"], "context");
+        assert_eq!(context["elements"][0]["text"], "1 node hot • 3 OOM • 2 pending");
+
+        let summary = ReportSummary {
+            oom_killed_count: 3,
+            pending_count: 2,
+            severity_counts: SeverityCounts { info: 0, warning: 2, critical: 3 },
+            ..Default::default()
+        };
+        let context = build_summary_context(&summary).unwrap();
+        assert_eq!(context["elements"][0]["text"], ":red_circle: 3 critical • :large_yellow_circle: 2 warning • 3 OOM • 2 pending");
+    }
+
+    #[test]
+    fn test_mark_section_severities() {
+        let mut payload = SlackPayload {
+            text: None,
+            blocks: vec![
+                Block::header("Cluster health").into(),
+                Block::section("*OOMKilled*\n• `default/api-0`").with_block_id("oom").into(),
+                Block::section("*Pending pods*\nNo pending pods.").with_block_id("pending").into(),
+            ],
+        };
+        let severities = [("oom".to_string(), SeverityCounts { info: 0, warning: 1, critical: 1 })].into();
+
+        mark_section_severities(&mut payload, &severities);
+        assert_eq!(payload.blocks[0]["text"]["text"], "Cluster health");
+        assert_eq!(payload.blocks[1]["text"]["text"], ":red_circle: *OOMKilled*\n• `default/api-0`");
+        assert_eq!(payload.blocks[2]["text"]["text"], "*Pending pods*\nNo pending pods.");
+    }
+
+    #[test]
+    fn test_build_oncall_section() {
+        assert!(build_oncall_section(None, 2).is_none());
+        assert!(build_oncall_section(Some("<@U0123ABC>"), 0).is_none());
+
+        let section = build_oncall_section(Some("<@U0123ABC>"), 2).unwrap();
+        assert_eq!(section["text"]["text"], ":rotating_light: <@U0123ABC> — 2 critical finding(s) need attention");
+    }
+
+    #[test]
+    fn test_build_stuck_terminating_section() {
+        assert!(build_stuck_terminating_section(&[]).is_none());
+
+        let stuck = vec![
+            StuckTerminatingInfo {
+                namespace: "old-team".to_string(),
+                object: "Namespace/old-team".to_string(),
+                since: Utc::now() - chrono::Duration::days(3),
+                finalizers: vec!["kubernetes".to_string()],
+                details: vec!["Some content in the namespace has finalizers remaining".to_string()],
+            },
+            StuckTerminatingInfo {
+                namespace: "default".to_string(),
+                object: "PersistentVolumeClaim/data-0".to_string(),
+                since: Utc::now() - chrono::Duration::hours(2),
+                finalizers: vec!["kubernetes.io/pvc-protection".to_string()],
+                details: Vec::new(),
+            },
+        ];
+        let section = build_stuck_terminating_section(&stuck).unwrap();
+        assert_eq!(section["block_id"], "stuck_terminating");
+        assert_eq!(
+            section["text"]["text"],
+            "*Stuck in Terminating*\n\
+             • `Namespace/old-team` terminating for 3 days, finalizers `kubernetes`\n    ↳ Some content in the namespace has finalizers remaining\n\
+             • `default/PersistentVolumeClaim/data-0` terminating for 2 hours, finalizers `kubernetes.io/pvc-protection`"
+        );
+    }
+
+    #[test]
+    fn test_build_custom_resources_section() {
+        assert!(build_custom_resources_section(&[]).is_none());
+
+        let violations = vec![
+            CustomResourceConditionInfo {
+                namespace: "streaming".to_string(),
+                resource: "Kafka/events".to_string(),
+                condition: "Ready".to_string(),
+                expected: "True".to_string(),
+                actual: Some("False".to_string()),
+                reason: Some("ZooKeeperNotReady".to_string()),
+                message: Some("Waiting for ZooKeeper".to_string()),
+            },
+            CustomResourceConditionInfo {
+                namespace: "streaming".to_string(),
+                resource: "Kafka/audit".to_string(),
+                condition: "Ready".to_string(),
+                expected: "True".to_string(),
+                actual: None,
+                reason: None,
+                message: None,
+            },
+        ];
+        let section = build_custom_resources_section(&violations).unwrap();
+        assert_eq!(section["block_id"], "custom_resources");
+        assert_eq!(
+            section["text"]["text"],
+            "*Custom resource conditions*\n\
+             • `streaming/Kafka/events` Ready=False (expected True)\n    ↳ ZooKeeperNotReady: Waiting for ZooKeeper\n\
+             • `streaming/Kafka/audit` Ready not reported (expected True)"
+        );
+    }
+
+    #[test]
+    fn test_build_event_flood_section() {
+        assert!(build_event_flood_section(&[]).is_none());
+
+        let floods = vec![EventFloodInfo {
+            namespace: "payments".to_string(),
+            warnings: 812,
+            top_reasons: vec![("BackOff".to_string(), 700), ("FailedMount".to_string(), 100)],
+            window_minutes: 60,
+        }];
+        let section = build_event_flood_section(&floods).unwrap();
+        assert_eq!(section["block_id"], "event_flood");
+        assert_eq!(
+            section["text"]["text"],
+            "*Warning event floods*\n• `payments`: 812 Warning events in the last 60m (BackOff ×700, FailedMount ×100)"
+        );
+    }
+
+    #[test]
+    fn test_build_object_counts_section() {
+        assert!(build_object_counts_section(&[]).is_none());
+
+        let warnings = vec![
+            ObjectCountInfo { resource: "events".to_string(), count: 1_500_000, previous: None },
+            ObjectCountInfo { resource: "configmaps".to_string(), count: 30_000, previous: Some(15_000) },
+        ];
+        let section = build_object_counts_section(&warnings).unwrap();
+        assert_eq!(section["block_id"], "object_counts");
+        let text = section["text"]["text"].as_str().unwrap();
+        assert!(text.contains("• `events`: 1500000 objects\n"));
+        assert!(text.ends_with("• `configmaps`: 30000 objects (+100% since last run)"));
+    }
+
+    #[test]
+    fn test_build_api_health_context() {
+        assert!(build_api_health_context(&ApiHealthInfo::default()).is_none());
+
+        let call = |path: &str, duration_ms| ApiCallInfo { method: "GET".to_string(), path: path.to_string(), status: Some(200), duration_ms };
+        let mut health = ApiHealthInfo {
+            requests: 200,
+            errors: 0,
+            throttled: 0,
+            slowest: vec![call("/api/v1/nodes", 2400), call("/api/v1/namespaces/default/pods", 800)],
+        };
+        let context = build_api_health_context(&health).unwrap();
+        assert_eq!(context["block_id"], "api_health");
+        assert_eq!(
+            context["elements"][0]["text"],
+            "API server: 200 calls, 0.0% errors • slowest: `GET /api/v1/nodes` 2.4s, `GET /api/v1/namespaces/default/pods` 0.8s"
+        );
+
+        health.errors = 3;
+        health.throttled = 5;
+        health.slowest.clear();
+        let context = build_api_health_context(&health).unwrap();
+        assert_eq!(context["elements"][0]["text"], ":warning: API server: 200 calls, 1.5% errors, 5 throttled");
+    }
+
+    #[test]
+    fn test_build_node_flapping_section() {
+        assert!(build_node_flapping_section(&[]).is_none());
+
+        let nodes = vec![
+            NodeFlapInfo { name: "node-a".to_string(), not_ready_transitions: 4, currently_ready: true, window_minutes: 60 },
+            NodeFlapInfo { name: "node-b".to_string(), not_ready_transitions: 3, currently_ready: false, window_minutes: 60 },
+        ];
+        let section = build_node_flapping_section(&nodes).unwrap();
+        assert_eq!(section["block_id"], "node_flapping");
+        let text = section["text"]["text"].as_str().unwrap();
+        assert!(text.contains("• node `node-a`: NotReady 4 times in 60m (Ready now)"));
+        assert!(text.contains("• node `node-b`: NotReady 3 times in 60m (NotReady now)"));
+    }
+
+    #[test]
+    fn test_build_node_disk_section() {
+        assert!(build_node_disk_section(&[]).is_none());
+
+        let gib = 1024 * 1024 * 1024;
+        let forecasts = vec![
+            NodeDiskForecastInfo {
+                name: "node-a".to_string(),
+                filesystem: "imagefs".to_string(),
+                used_pct: 80.4,
+                used_bytes: 80 * gib,
+                capacity_bytes: 100 * gib,
+                image_gc_pct: Some(85.0),
+                eviction_pct: Some(85.0),
+            },
+            NodeDiskForecastInfo {
+                name: "node-b".to_string(),
+                filesystem: "nodefs".to_string(),
+                used_pct: 82.0,
+                used_bytes: 41 * gib,
+                capacity_bytes: 50 * gib,
+                image_gc_pct: None,
+                eviction_pct: Some(90.0),
+            },
+        ];
+        let section = build_node_disk_section(&forecasts).unwrap();
+        assert_eq!(section["block_id"], "node_disk");
+        let text = section["text"]["text"].as_str().unwrap();
+        assert!(text.contains("• node `node-a` imagefs: 80% used (80.0/100.0 GiB), image GC at 85%, eviction at 85%"));
+        assert!(text.contains("• node `node-b` nodefs: 82% used (41.0/50.0 GiB), eviction at 90%"));
+    }
+
+    #[test]
+    fn test_build_network_agents_section() {
+        assert!(build_network_agents_section(&[]).is_none());
+
+        let issues = vec![
+            NetworkAgentIssueInfo {
+                node: "n1".to_string(),
+                daemonset: "kube-system/kube-proxy".to_string(),
+                issue: NetworkAgentIssue::Missing,
+            },
+            NetworkAgentIssueInfo {
+                node: "n2".to_string(),
+                daemonset: "kube-system/cilium".to_string(),
+                issue: NetworkAgentIssue::Unhealthy { pod: "cilium-x".to_string(), phase: "Pending".to_string() },
+            },
+        ];
+        let section = build_network_agents_section(&issues).unwrap();
+        let text = section["text"]["text"].as_str().unwrap();
+        assert!(text.contains("CRITICAL"));
+        assert!(text.contains("• node `n1`: `kube-system/kube-proxy` no pod scheduled"));
+        assert!(text.contains("• node `n2`: `kube-system/cilium` pod `cilium-x` Pending and not Ready"));
+    }
+
+    #[test]
+    fn test_build_operators_section() {
+        assert!(build_operators_section(&[]).is_none());
+
+        let issues = vec![
+            OperatorIssueInfo {
+                namespace: "openshift-operators".to_string(),
+                operator: "strimzi-kafka-operator".to_string(),
+                issue: OperatorIssue::Subscription {
+                    condition: "InstallPlanFailed".to_string(),
+                    message: Some("CRD conflict".to_string()),
+                },
+            },
+            OperatorIssueInfo {
+                namespace: "openshift-operators".to_string(),
+                operator: "etcd".to_string(),
+                issue: OperatorIssue::DeploymentUnavailable {
+                    deployment: "etcd-operator".to_string(),
+                    available: 0,
+                    desired: 1,
+                },
+            },
+        ];
+        let section = build_operators_section(&issues).unwrap();
+        assert_eq!(section["block_id"], "operators");
+        let text = section["text"]["text"].as_str().unwrap();
+        assert!(text.contains("• `openshift-operators/strimzi-kafka-operator` InstallPlanFailed: CRD conflict"));
+        assert!(text.contains("• `openshift-operators/etcd` deployment `etcd-operator` 0/1 available"));
+    }
+
+    #[test]
+    fn test_build_chronic_issues_section() {
+        let now = Utc::now();
+        let history = |age: chrono::Duration, occurrences| IssueHistory {
+            first_seen: now - age,
+            last_seen: now,
+            occurrences,
+        };
+        assert!(build_chronic_issues_section(&[("oom/default/db-0".to_string(), history(chrono::Duration::zero(), 1))], now).is_none());
+
+        let issues = vec![
+            ("restarts/default/api-0".to_string(), history(chrono::Duration::hours(5), 6)),
+            ("oom/default/db-0".to_string(), history(chrono::Duration::days(3), 12)),
+            ("pending/default/web-0".to_string(), history(chrono::Duration::zero(), 1)),
+        ];
+        let section = build_chronic_issues_section(&issues, now).unwrap();
+        assert_eq!(section["block_id"], "chronic");
+        assert_eq!(
+            section["text"]["text"],
+            "*Chronic issues*\n• `oom/default/db-0`: ongoing for 3 days, seen in 12 runs\n\
+             • `restarts/default/api-0`: ongoing for 5 hours, seen in 6 runs"
+        );
+    }
+
+    #[test]
+    fn test_build_resolved_section() {
+        assert!(build_resolved_section(&[]).is_none());
+
+        let now = Utc::now();
+        let resolved = vec![
+            ("oom/default/db-0".to_string(), IssueHistory { first_seen: now - chrono::Duration::days(2), last_seen: now, occurrences: 9 }),
+            ("pending/default/web-0".to_string(), IssueHistory { first_seen: now, last_seen: now, occurrences: 1 }),
+        ];
+        let section = build_resolved_section(&resolved).unwrap();
+        assert_eq!(section["block_id"], "resolved");
+        assert_eq!(
+            section["text"]["text"],
+            ":white_check_mark: *Resolved since last run*\n• `oom/default/db-0` (ongoing for 2 days, seen in 9 runs)\n\
+             • `pending/default/web-0`"
+        );
+    }
+
+    #[test]
+    fn test_build_previously_snoozed_section() {
+        assert!(build_previously_snoozed_section(&[]).is_none());
+
+        let until = chrono::DateTime::parse_from_rfc3339("2026-03-01T12:00:00Z").unwrap().with_timezone(&Utc);
+        let issues = vec![
+            PreviouslySnoozedInfo {
+                fingerprint: "restarts/default/api-0".to_string(),
+                snoozed_until: until,
+                reason: Some("vendor fix pending".to_string()),
+            },
+            PreviouslySnoozedInfo {
+                fingerprint: "failed_jobs/batch/nightly".to_string(),
+                snoozed_until: until,
+                reason: None,
+            },
+        ];
+        let section = build_previously_snoozed_section(&issues).unwrap();
+        assert_eq!(section["block_id"], "previously_snoozed");
+        let text = section["text"]["text"].as_str().unwrap();
+        assert!(text.contains("• `restarts/default/api-0`: previously snoozed until 2026-03-01 12:00 UTC (vendor fix pending)"));
+        assert!(text.ends_with("• `failed_jobs/batch/nightly`: previously snoozed until 2026-03-01 12:00 UTC"));
+    }
+
+    #[test]
+    fn test_build_pod_churn_section() {
+        assert!(build_pod_churn_section(&[]).is_none());
+
+        let churn = vec![PodChurnInfo {
+            namespace: "default".to_string(),
+            kind: "Deployment".to_string(),
+            workload: "api".to_string(),
+            created: 42,
+            deleted: 41,
+            window_minutes: 60,
+        }];
+        let section = build_pod_churn_section(&churn).unwrap();
+        let text = section["text"]["text"].as_str().unwrap();
+        assert!(text.contains("`default/api` (Deployment): 42 pods created, 41 deleted in the last 60m"));
+    }
+
+    #[test]
+    fn test_build_priorities_section() {
+        assert!(build_priorities_section(&[], 5).is_none());
+
+        let issue = |section: &str, namespace: Option<&str>, name: &str, affected, open_for| PriorityIssue {
+            section: section.to_string(),
+            namespace: namespace.map(str::to_string),
+            name: name.to_string(),
+            affected,
+            open_for,
+            score: 0.0,
+        };
+        let priorities = vec![
+            issue("nodes", None, "node-a", 4, Some(chrono::Duration::hours(2))),
+            issue("oom", Some("payments"), "api", 2, None),
+            issue("usage", Some("batch"), "scraper", 1, None),
+        ];
+        let section = build_priorities_section(&priorities, 2).unwrap();
+        assert_eq!(section["block_id"], "priorities");
+        assert_eq!(
+            section["text"]["text"],
+            "*:dart: Act on these first*\n1. `node-a` — nodes ×4, open 2 hours\n2. `payments/api` — oom ×2"
+        );
+    }
+
+    #[test]
+    fn test_build_resource_hygiene_section() {
+        assert!(build_resource_hygiene_section(&[]).is_none());
+
+        let findings = vec![
+            ResourceHygieneInfo {
+                namespace: "default".to_string(),
+                workload: "Deployment/api".to_string(),
+                container: "main".to_string(),
+                issue: ResourceHygieneIssue::MemoryLimitUnset,
+            },
+            ResourceHygieneInfo {
+                namespace: "default".to_string(),
+                workload: "Pod/debug".to_string(),
+                container: "shell".to_string(),
+                issue: ResourceHygieneIssue::HighLimitRatio { resource: "cpu".to_string(), ratio: 20.0 },
+            },
+        ];
+        let section = build_resource_hygiene_section(&findings).unwrap();
+        let text = section["text"]["text"].as_str().unwrap();
+        assert!(text.contains("`default/Deployment/api` [main] memory limit unset"));
+        assert!(text.contains("cpu limit is 20× the request"));
+    }
+
+    #[test]
+    fn test_build_pod_security_section() {
+        use crate::types::PodSecurityLevel;
+        assert!(build_pod_security_section(&[]).is_none());
+
+        let violation = |namespace: &str, workload: &str, level, violations: &[&str]| PodSecurityViolationInfo {
+            namespace: namespace.to_string(),
+            workload: workload.to_string(),
+            level,
+            violations: violations.iter().map(|v| v.to_string()).collect(),
+        };
+        let findings = vec![
+            violation("payments", "Deployment/api", PodSecurityLevel::Restricted, &["runAsNonRoot not true (app)"]),
+            violation("payments", "Pod/debug", PodSecurityLevel::Restricted, &["hostPID", "privileged (shell)"]),
+            violation("infra", "DaemonSet/agent", PodSecurityLevel::Baseline, &["hostNetwork"]),
+        ];
+        let section = build_pod_security_section(&findings).unwrap();
+        assert_eq!(section["block_id"], "pod_security");
+        assert_eq!(
+            section["text"]["text"].as_str().unwrap(),
+            "*Pod Security Standards*\n\
+             `payments` (restricted): 2 workload(s)\n\
+             • `Deployment/api`: runAsNonRoot not true (app)\n\
+             • `Pod/debug`: hostPID, privileged (shell)\n\
+             `infra` (baseline): 1 workload(s)\n\
+             • `DaemonSet/agent`: hostNetwork"
+        );
+    }
+
+    #[test]
+    fn test_build_slack_payload_usage_checks_skipped() {
+        let config = Config {
+            namespaces: vec!["default".to_string()],
+            threshold_percent: 85.0,
+            slack_webhook_url: "https://hooks.slack.com/test".to_string(),
+            restart_grace_minutes: 5,
+            pending_grace_minutes: 5,
+            cluster_name: None,
+            datacenter_name: None,
+            fail_if_no_metrics: false,
+            ..Default::default()
+        };
+
+        let mut report = HealthReport::new(config);
+
+        report.pod_metrics.usage_skipped = true;
+
+        let payload = build_slack_payload(&report);
+        assert_eq!(payload.blocks.len(), 13);
+
+        let config_text = payload.blocks[1]["text"]["text"].as_str().unwrap();
+        assert!(config_text.contains("usage checks skipped"));
+
+        let heavy_text = payload.blocks[2]["text"]["text"].as_str().unwrap();
+        assert!(heavy_text.contains("Metrics unavailable"));
+        assert!(!heavy_text.contains("No pods exceeding threshold"));
+
+        let node_util_text = payload.blocks[9]["text"]["text"].as_str().unwrap();
+        assert!(node_util_text.contains("Metrics unavailable"));
+    }
+
+    #[test]
+    fn test_build_slack_payload_cluster_checks_skipped() {
+        let mut report = HealthReport::new(Config::default());
+        report.cluster_metrics.cluster_checks_skipped = true;
+
+        let payload = build_slack_payload(&report);
+        let config_text = payload.blocks[1]["text"]["text"].as_str().unwrap();
+        assert!(config_text.contains("Namespace-scoped RBAC — node and other cluster-wide checks skipped"));
+        for key in ["nodes", "node_utilization"] {
+            let section = payload.blocks.iter().find(|b| b["block_id"] == key).unwrap();
+            assert!(section["text"]["text"].as_str().unwrap().ends_with("Node checks skipped (namespace-scoped RBAC)."));
+        }
+    }
+
+    #[test]
+    fn test_build_slack_payload_missing_namespaces() {
+        let mut report = HealthReport::new(Config::default());
+        report.missing_namespaces = vec!["paymnets".to_string()];
+
+        let payload = build_slack_payload(&report);
+        let config_text = payload.blocks[1]["text"]["text"].as_str().unwrap();
+        assert!(config_text.contains("\n:warning: *Namespaces not found:* `paymnets` — check NAMESPACES for typos"));
+    }
+
+    #[test]
+    fn test_build_slack_payload_change_freeze() {
+        let mut report = HealthReport::new(Config::default());
+        report.job_metrics.failed_jobs.push(FailedJobInfo {
+            namespace: "batch".to_string(),
+            job: "nightly".to_string(),
+            failed_pods: 1,
+            last_failure_time: None,
+            reason: None,
+        });
+        report.change_freeze = Some(ChangeFreezeInfo {
+            source: "ConfigMap ops/change-freeze".to_string(),
+            until: Some(Utc.with_ymd_and_hms(2027, 1, 4, 0, 0, 0).unwrap()),
+            escalated: ["failed_jobs/batch/nightly".to_string()].into(),
+        });
+
+        let payload = build_slack_payload(&report);
+        let config_text = payload.blocks[1]["text"]["text"].as_str().unwrap();
+        assert!(config_text.contains(
+            ":snowflake: *Change freeze in effect* until 2027-01-04 00:00 UTC — 1 new finding(s) escalated to critical"
+        ));
+    }
+
+    #[test]
+    fn test_build_slack_payload_collection_warnings() {
+        let mut report = HealthReport::new(Config::default());
+        report.collection_warnings.push("Namespace `batch`: pod checks exceeded the 120s budget and were skipped".to_string());
+
+        let payload = build_slack_payload(&report);
+        let config_text = payload.blocks[1]["text"]["text"].as_str().unwrap();
+        assert!(config_text.ends_with("\n:warning: Namespace `batch`: pod checks exceeded the 120s budget and were skipped"));
+    }
+
+    #[test]
+    fn test_split_payload() {
+        let findings: Vec<String> = (0..200).map(|i| format!("• `payments/api-{}`: restarted 5 times", i)).collect();
+        let long_section = format!("*Restarts*\n{}\n…and 40 more", findings.join("\n"));
+        let mut blocks: Vec<serde_json::Value> = vec![Block::header("Cluster health").into(), Block::section(long_section).with_block_id("restarts").into()];
+        blocks.extend((0..118).map(|i| serde_json::Value::from(Block::section(format!("*Section {}*", i)))));
+        let payload = SlackPayload { text: Some("Cluster health".to_string()), blocks };
+
+        let messages = split_payload(&payload);
+        let sizes: Vec<usize> = messages.iter().map(|m| m.blocks.len()).collect();
+        assert_eq!(sizes, vec![50, 50, 22]);
+        assert_eq!(messages[0].blocks[0]["type"], "header");
+        assert_eq!(messages[2].blocks[0]["elements"][0]["text"], "_Continued (3/3)_");
+        assert_eq!(messages[2].blocks[21]["text"]["text"], "*Section 117*");
+        assert!(messages.iter().all(|m| m.text.as_deref() == Some("Cluster health")));
+
+        // Whole findings are kept and the rest counted, including those left out already
+        let fitted = messages[0].blocks[1]["text"]["text"].as_str().unwrap();
+        assert!(fitted.chars().count() <= MAX_SECTION_TEXT);
+        let shown = fitted.lines().filter(|l| l.starts_with('•')).count();
+        assert!(fitted.starts_with("*Restarts*\n• `payments/api-0`"));
+        assert!(fitted.ends_with(&format!("…and {} more", 200 - shown + 40)));
+
+        let small = SlackPayload { text: None, blocks: vec![Block::section("*Restarts*\n• `payments/api-0`").into()] };
+        let unchanged = split_payload(&small);
+        assert_eq!(unchanged.len(), 1);
+        assert_eq!(serde_json::to_value(&unchanged[0]).unwrap(), serde_json::to_value(&small).unwrap());
+    }
+
+    #[test]
+    fn test_fit_section_text_cuts_a_single_long_finding() {
+        let log = (0..100).map(|i| format!("panic at line {}", i)).collect::<Vec<_>>().join("\n");
+        let text = format!("*Restarts*\n• `payments/api-0`: crashed\n```{}```\n• `payments/api-1`: crashed", log);
+        let fitted = fit_section_text(&text, 300);
+        assert!(fitted.chars().count() <= 300);
+        assert!(fitted.starts_with("*Restarts*\n• `payments/api-0`: crashed\n```panic at line 0"));
+        assert!(fitted.ends_with("…\n```\n…and 1 more"));
+    }
 }
@@ -0,0 +1,127 @@
+//! Slack Web API transport (`chat.postMessage` with a bot token): the report's summary is
+//! posted as a message and each section as a reply in its thread, which keeps channels
+//! readable when reports are long. Unlike webhooks, the API returns the message's `ts`,
+//! so it can be updated later.
+
+use anyhow::{anyhow, Context, Result};
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::{sleep, timeout};
+use tracing::{info, warn};
+
+use crate::slack::split_payload;
+use crate::types::{SlackPayload, REPORT_SECTIONS};
+
+pub const SLACK_API_URL: &str = "https://slack.com/api";
+
+/// Longest rate-limit pause honored before giving up on a message
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// Split the report message into the thread's parent, everything but the report sections
+/// (header, counts, priorities, footer), and one reply per section
+pub fn thread_payloads(payload: SlackPayload) -> (SlackPayload, Vec<SlackPayload>) {
+    let (sections, summary): (Vec<serde_json::Value>, Vec<serde_json::Value>) = payload
+        .blocks
+        .into_iter()
+        .partition(|b| b.get("block_id").and_then(|id| id.as_str()).is_some_and(|id| REPORT_SECTIONS.contains(&id)));
+    let replies = sections.into_iter().map(|block| SlackPayload { text: None, blocks: vec![block] }).collect();
+    (SlackPayload { text: payload.text, blocks: summary }, replies)
+}
+
+/// Post `payload` to `channel` as a thread: the summary first, then its sections as
+/// replies, each kept within Slack's limits by `split_payload` (a summary too large for
+/// one message continues in the thread). Returns the `ts` of the summary message. Each
+/// call must be answered within `deadline`.
+pub async fn post_threaded(
+    client: &reqwest::Client,
+    api_url: &str,
+    token: &str,
+    channel: &str,
+    payload: SlackPayload,
+    deadline: Duration,
+) -> Result<String> {
+    let fallback = payload.text.clone().unwrap_or_else(|| "Kubernetes health report".to_string());
+    let (summary, replies) = thread_payloads(payload);
+    let mut summary = split_payload(&summary).into_iter();
+    let parent = summary.next().map(|p| p.blocks).unwrap_or_default();
+    let parent = json!({ "channel": channel, "text": fallback, "blocks": parent });
+    let ts = post_message(client, api_url, token, &parent, deadline).await?;
+    let replies: Vec<SlackPayload> = summary.chain(replies.iter().flat_map(split_payload)).collect();
+    for reply in &replies {
+        let message = json!({ "channel": channel, "thread_ts": ts, "text": fallback, "blocks": reply.blocks });
+        post_message(client, api_url, token, &message, deadline).await?;
+    }
+    info!("Posted the report to {} (ts {}) with {} thread replies", channel, ts, replies.len());
+    Ok(ts)
+}
+
+/// `chat.postMessage`; returns the message's `ts`. A rate-limited call is retried once
+/// after the `Retry-After` Slack asks for.
+async fn post_message(
+    client: &reqwest::Client,
+    api_url: &str,
+    token: &str,
+    message: &serde_json::Value,
+    deadline: Duration,
+) -> Result<String> {
+    let url = format!("{}/chat.postMessage", api_url.trim_end_matches('/'));
+    for attempt in 0..2 {
+        let request = client.post(&url).bearer_auth(token).json(message).send();
+        let response = timeout(deadline, request)
+            .await
+            .map_err(|_| anyhow!("Slack API timed out after {:?}", deadline))?
+            .context("Failed to reach the Slack API")?;
+        let status = response.status();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS && attempt == 0 {
+            let retry_after = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse().ok())
+                .map_or(Duration::from_secs(1), Duration::from_secs);
+            if retry_after <= MAX_RETRY_AFTER {
+                warn!("Slack API rate limited, retrying in {:?}", retry_after);
+                sleep(retry_after).await;
+                continue;
+            }
+        }
+        if !status.is_success() {
+            return Err(anyhow!("Slack API answered {}", status));
+        }
+        // Failures are reported with a 200 and `ok: false`
+        let body: serde_json::Value = response.json().await.context("Invalid Slack API response")?;
+        if body["ok"] != true {
+            return Err(anyhow!("chat.postMessage failed: {}", body["error"].as_str().unwrap_or("unknown error")));
+        }
+        return body["ts"].as_str().map(str::to_string).ok_or_else(|| anyhow!("chat.postMessage returned no ts"));
+    }
+    Err(anyhow!("Slack API kept rate limiting"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::Block;
+
+    #[test]
+    fn test_thread_payloads() {
+        let payload = SlackPayload {
+            text: Some("Cluster health".to_string()),
+            blocks: vec![
+                Block::header("Cluster health").into(),
+                Block::context("3 issues").into(),
+                Block::section("*OOMKilled*\n• `payments/api-0`").with_block_id("oom").into(),
+                Block::section("*Act on these first*").with_block_id("priorities").into(),
+                Block::section("*Restarts*\n• `payments/api-1`").with_block_id("restarts").into(),
+                Block::context("run abc").into(),
+            ],
+        };
+
+        let (summary, replies) = thread_payloads(payload);
+        assert_eq!(summary.text.as_deref(), Some("Cluster health"));
+        let summary_ids: Vec<&str> = summary.blocks.iter().map(|b| b["block_id"].as_str().unwrap_or("-")).collect();
+        assert_eq!(summary_ids, vec!["-", "-", "priorities", "-"]);
+        let reply_ids: Vec<&str> = replies.iter().map(|r| r.blocks[0]["block_id"].as_str().unwrap()).collect();
+        assert_eq!(reply_ids, vec!["oom", "restarts"]);
+    }
+}
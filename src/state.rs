@@ -0,0 +1,300 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crate::types::{IssueHistory, Scoped};
+
+/// Stable identifier of a finding across runs: `section/namespace/name`
+pub fn issue_fingerprint(section: &str, finding: &dyn Scoped) -> String {
+    format!("{}/{}/{}", section, finding.namespace(), finding.object_name())
+}
+
+/// Cross-run state persisted at `STATE_FILE`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct StateStore {
+    /// Snoozed findings by fingerprint
+    #[serde(default)]
+    pub snoozes: BTreeMap<String, Snooze>,
+    /// Lifecycle of the findings present in the last run, by fingerprint
+    #[serde(default)]
+    pub issues: BTreeMap<String, IssueHistory>,
+    /// Findings of each recent run, oldest first, for the rollup report
+    #[serde(default)]
+    pub runs: Vec<RunRecord>,
+    /// Delivery health of the sinks that failed recently, by sink name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sinks: BTreeMap<String, SinkHealth>,
+    /// Stored objects per resource at the last run, for the object count growth check
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub object_counts: BTreeMap<String, u64>,
+}
+
+/// Days of run records kept for the rollup report
+pub const RUN_HISTORY_DAYS: i64 = 14;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub timestamp: DateTime<Utc>,
+    /// Fingerprints of the findings present in the run
+    pub fingerprints: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snooze {
+    pub until: DateTime<Utc>,
+    pub reason: Option<String>,
+    /// HealthSuppression (`namespace/name`) that silenced the finding; `None` for snoozes
+    /// created with the `snooze` command. Only those silence findings by themselves, the
+    /// others just remember the expiry so the finding can be flagged when it comes back.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suppression: Option<String>,
+}
+
+/// Circuit breaker state of a sink
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SinkHealth {
+    /// Failed deliveries since the last successful one
+    pub consecutive_failures: u32,
+    /// Deliveries to the sink are skipped until then
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_until: Option<DateTime<Utc>>,
+}
+
+impl StateStore {
+    /// Load state from `path`, starting empty when the file does not exist yet
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let data = std::fs::read(path)
+            .with_context(|| format!("Failed to read state file {}", path.display()))?;
+        serde_json::from_slice(&data)
+            .with_context(|| format!("Invalid state file {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)
+            .with_context(|| format!("Failed to write state file {}", tmp.display()))?;
+        std::fs::rename(&tmp, path)
+            .with_context(|| format!("Failed to replace state file {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Remove and return the snoozes that ran out at `now`. Suppression-backed ones
+    /// whose HealthSuppression is gone (not in `active_suppressions`) are dropped too,
+    /// but not returned: the finding was unsilenced on purpose.
+    pub fn expire_snoozes(&mut self, now: DateTime<Utc>, active_suppressions: &[String]) -> BTreeMap<String, Snooze> {
+        let mut expired = BTreeMap::new();
+        self.snoozes.retain(|fingerprint, snooze| {
+            if snooze.until <= now {
+                expired.insert(fingerprint.clone(), snooze.clone());
+                return false;
+            }
+            snooze.suppression.as_ref().is_none_or(|s| active_suppressions.contains(s))
+        });
+        expired
+    }
+
+    /// Update the lifecycle of the findings present in this run and forget the rest;
+    /// returns the history of the findings that cleared since the last run
+    pub fn record_issues(&mut self, current: &BTreeSet<String>, now: DateTime<Utc>) -> BTreeMap<String, IssueHistory> {
+        let (ongoing, cleared) = std::mem::take(&mut self.issues)
+            .into_iter()
+            .partition(|(fingerprint, _)| current.contains(fingerprint));
+        self.issues = ongoing;
+        for fingerprint in current {
+            let history = self.issues.entry(fingerprint.clone()).or_insert(IssueHistory {
+                first_seen: now,
+                last_seen: now,
+                occurrences: 0,
+            });
+            history.last_seen = now;
+            history.occurrences += 1;
+        }
+        cleared
+    }
+
+    /// Append this run's findings and drop records older than `RUN_HISTORY_DAYS`
+    pub fn record_run(&mut self, fingerprints: &BTreeSet<String>, now: DateTime<Utc>) {
+        self.runs.push(RunRecord {
+            timestamp: now,
+            fingerprints: fingerprints.iter().cloned().collect(),
+        });
+        let cutoff = now - Duration::days(RUN_HISTORY_DAYS);
+        self.runs.retain(|r| r.timestamp > cutoff);
+    }
+
+    /// Store this run's object counts and return the previous run's; empty counts (the
+    /// check is off or failed) leave the stored ones in place
+    pub fn record_object_counts(&mut self, counts: &BTreeMap<String, u64>) -> BTreeMap<String, u64> {
+        if counts.is_empty() {
+            return self.object_counts.clone();
+        }
+        std::mem::replace(&mut self.object_counts, counts.clone())
+    }
+
+    /// When deliveries to `sink` are skipped at `now`, the time they resume
+    pub fn sink_open_until(&self, sink: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.sinks.get(sink).and_then(|h| h.open_until).filter(|until| *until > now)
+    }
+
+    /// Track a delivery to `sink`. After `threshold` consecutive failures it is skipped
+    /// for `cooldown`; the first delivery after that reopens it right away if it fails
+    /// again. A threshold of 0 never skips.
+    pub fn record_delivery(&mut self, sink: &str, succeeded: bool, now: DateTime<Utc>, threshold: u32, cooldown: Duration) {
+        if succeeded {
+            self.sinks.remove(sink);
+            return;
+        }
+        let health = self.sinks.entry(sink.to_string()).or_default();
+        health.consecutive_failures += 1;
+        if threshold > 0 && health.consecutive_failures >= threshold {
+            health.open_until = Some(now + cooldown);
+        }
+    }
+
+    /// Whether a `snooze` command currently silences the fingerprint
+    pub fn is_snoozed(&self, fingerprint: &str) -> bool {
+        self.snoozes.get(fingerprint).is_some_and(|s| s.suppression.is_none())
+    }
+}
+
+/// Parse a snooze duration such as `90m`, `12h` or `7d`
+pub fn parse_snooze_duration(value: &str) -> Result<Duration> {
+    let value = value.trim();
+    let invalid = || anyhow!("Invalid snooze duration: {} (expected e.g. 90m, 12h, 7d)", value);
+    let (split, unit) = value.char_indices().last().ok_or_else(invalid)?;
+    let amount: i64 = value[..split].parse().map_err(|_| invalid())?;
+    if amount <= 0 {
+        return Err(invalid());
+    }
+    match unit {
+        'm' => Duration::try_minutes(amount),
+        'h' => Duration::try_hours(amount),
+        'd' => Duration::try_days(amount),
+        _ => None,
+    }
+    .ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snooze(until: DateTime<Utc>, suppression: Option<&str>) -> Snooze {
+        Snooze {
+            until,
+            reason: None,
+            suppression: suppression.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_expire_snoozes() {
+        let now = Utc::now();
+        let mut state = StateStore::default();
+        state.snoozes.insert("restarts/default/api-0".to_string(), snooze(now - Duration::minutes(1), None));
+        state.snoozes.insert("oom/default/db-0".to_string(), snooze(now + Duration::hours(1), None));
+        state.snoozes.insert("failed_jobs/batch/nightly".to_string(), snooze(now + Duration::hours(1), Some("ops/nightly")));
+        state.snoozes.insert("failed_jobs/batch/hourly".to_string(), snooze(now + Duration::hours(1), Some("ops/deleted")));
+
+        let expired = state.expire_snoozes(now, &["ops/nightly".to_string()]);
+        assert_eq!(expired.keys().collect::<Vec<_>>(), vec!["restarts/default/api-0"]);
+        assert_eq!(
+            state.snoozes.keys().collect::<Vec<_>>(),
+            vec!["failed_jobs/batch/nightly", "oom/default/db-0"]
+        );
+        assert!(state.is_snoozed("oom/default/db-0"));
+        // Silenced by the HealthSuppression itself, not by the stored record
+        assert!(!state.is_snoozed("failed_jobs/batch/nightly"));
+    }
+
+    #[test]
+    fn test_record_issues() {
+        let start = Utc::now();
+        let mut state = StateStore::default();
+        let run = |fingerprints: &[&str]| fingerprints.iter().map(|f| f.to_string()).collect::<BTreeSet<_>>();
+
+        assert!(state.record_issues(&run(&["restarts/default/api-0", "oom/default/db-0"]), start).is_empty());
+        let later = start + Duration::hours(1);
+        let cleared = state.record_issues(&run(&["restarts/default/api-0"]), later);
+
+        assert_eq!(cleared.keys().collect::<Vec<_>>(), vec!["oom/default/db-0"]);
+        assert_eq!(
+            state.issues["restarts/default/api-0"],
+            IssueHistory { first_seen: start, last_seen: later, occurrences: 2 }
+        );
+        assert_eq!(state.issues.len(), 1);
+
+        // A finding that comes back starts a new lifecycle
+        state.record_issues(&run(&["restarts/default/api-0", "oom/default/db-0"]), later + Duration::hours(1));
+        assert_eq!(state.issues["oom/default/db-0"].occurrences, 1);
+    }
+
+    #[test]
+    fn test_record_run_drops_old_runs() {
+        let now = Utc::now();
+        let mut state = StateStore::default();
+        let fingerprints: BTreeSet<String> = ["oom/default/db-0".to_string()].into();
+        state.record_run(&fingerprints, now - Duration::days(RUN_HISTORY_DAYS + 1));
+        state.record_run(&fingerprints, now);
+
+        assert_eq!(state.runs.len(), 1);
+        assert_eq!(state.runs[0].timestamp, now);
+        assert_eq!(state.runs[0].fingerprints, vec!["oom/default/db-0".to_string()]);
+    }
+
+    #[test]
+    fn test_record_object_counts() {
+        let mut state = StateStore::default();
+        let first = BTreeMap::from([("events".to_string(), 1000)]);
+        assert!(state.record_object_counts(&first).is_empty());
+
+        let second = BTreeMap::from([("events".to_string(), 5000)]);
+        assert_eq!(state.record_object_counts(&second), first);
+        // A run without counts keeps the last known ones
+        assert_eq!(state.record_object_counts(&BTreeMap::new()), second);
+        assert_eq!(state.object_counts, second);
+    }
+
+    #[test]
+    fn test_sink_circuit_breaker() {
+        let now = Utc::now();
+        let cooldown = Duration::minutes(30);
+        let mut state = StateStore::default();
+
+        state.record_delivery("team:payments", false, now, 2, cooldown);
+        assert_eq!(state.sink_open_until("team:payments", now), None);
+        state.record_delivery("team:payments", false, now, 2, cooldown);
+        assert_eq!(state.sink_open_until("team:payments", now), Some(now + cooldown));
+        assert_eq!(state.sink_open_until("team:payments", now + cooldown), None);
+
+        // Still failing after the cooldown: skipped again straight away
+        let later = now + cooldown;
+        state.record_delivery("team:payments", false, later, 2, cooldown);
+        assert_eq!(state.sink_open_until("team:payments", later), Some(later + cooldown));
+
+        state.record_delivery("team:payments", true, later, 2, cooldown);
+        assert!(state.sinks.is_empty());
+
+        state.record_delivery("slack", false, now, 0, cooldown);
+        state.record_delivery("slack", false, now, 0, cooldown);
+        assert_eq!(state.sink_open_until("slack", now), None);
+    }
+
+    #[test]
+    fn test_parse_snooze_duration() {
+        assert_eq!(parse_snooze_duration("90m").unwrap(), Duration::minutes(90));
+        assert_eq!(parse_snooze_duration("12h").unwrap(), Duration::hours(12));
+        assert_eq!(parse_snooze_duration(" 7d ").unwrap(), Duration::days(7));
+        assert!(parse_snooze_duration("").is_err());
+        assert!(parse_snooze_duration("0d").is_err());
+        assert!(parse_snooze_duration("1w").is_err());
+        assert!(parse_snooze_duration("d").is_err());
+        assert!(parse_snooze_duration("7д").is_err());
+        assert!(parse_snooze_duration("9223372036854775807d").is_err());
+    }
+}
@@ -0,0 +1,187 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use kube::{
+    api::{ApiResource, DynamicObject, GroupVersionKind, ListParams},
+    Api, Client,
+};
+use regex::Regex;
+use serde::Deserialize;
+use tracing::{debug, info, warn};
+
+use crate::types::Scoped;
+
+pub const SUPPRESSION_GROUP: &str = "kube-health-reporter.io";
+pub const SUPPRESSION_VERSION: &str = "v1alpha1";
+pub const SUPPRESSION_KIND: &str = "HealthSuppression";
+const SUPPRESSION_PLURAL: &str = "healthsuppressions";
+
+/// `spec` of a HealthSuppression resource; unset matchers match everything
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuppressionSpec {
+    /// Exact namespace, or a prefix ending in `*`
+    pub namespace: Option<String>,
+    /// Regex matched against the pod/workload/job name of the finding
+    pub name: Option<String>,
+    /// Report section key (see `SECTION_ORDER`), e.g. `restarts`
+    pub category: Option<String>,
+    /// The suppression stops applying after this time
+    pub expires: Option<DateTime<Utc>>,
+    /// Why the finding is silenced; for the audit trail only
+    pub reason: Option<String>,
+}
+
+/// An active suppression with its name regex compiled
+#[derive(Debug, Clone)]
+pub struct Suppression {
+    /// `namespace/name` of the HealthSuppression resource
+    pub source: String,
+    pub namespace: Option<String>,
+    pub name: Option<Regex>,
+    pub category: Option<String>,
+    pub expires: Option<DateTime<Utc>>,
+    pub reason: Option<String>,
+}
+
+impl Suppression {
+    /// Build from a spec; `None` when it has expired or its name regex is invalid
+    pub fn from_spec(source: &str, spec: SuppressionSpec, now: DateTime<Utc>) -> Option<Self> {
+        if spec.expires.is_some_and(|t| t <= now) {
+            debug!("HealthSuppression {} expired, ignoring", source);
+            return None;
+        }
+        let name = match spec.name.as_deref().map(|pattern| Regex::new(&format!("^(?:{})$", pattern))) {
+            None => None,
+            Some(Ok(re)) => Some(re),
+            Some(Err(e)) => {
+                warn!("HealthSuppression {} has an invalid name regex, ignoring: {}", source, e);
+                return None;
+            }
+        };
+        Some(Self {
+            source: source.to_string(),
+            namespace: spec.namespace,
+            name,
+            category: spec.category,
+            expires: spec.expires,
+            reason: spec.reason,
+        })
+    }
+
+    pub fn matches(&self, category: &str, finding: &dyn Scoped) -> bool {
+        let namespace_matches = match self.namespace.as_deref() {
+            None => true,
+            Some(pattern) => match pattern.strip_suffix('*') {
+                Some(prefix) => finding.namespace().starts_with(prefix),
+                None => finding.namespace() == pattern,
+            },
+        };
+        namespace_matches
+            && self.category.as_deref().is_none_or(|c| c == category)
+            && self.name.as_ref().is_none_or(|re| re.is_match(finding.object_name()))
+    }
+}
+
+/// Read the active HealthSuppression resources from every namespace, or only from
+/// `namespaces` when given (namespace-scoped RBAC). A missing CRD means no
+/// suppressions; other API errors are returned.
+pub async fn load_suppressions(client: &Client, namespaces: Option<&[String]>, now: DateTime<Utc>) -> Result<Vec<Suppression>> {
+    let gvk = GroupVersionKind::gvk(SUPPRESSION_GROUP, SUPPRESSION_VERSION, SUPPRESSION_KIND);
+    let resource = ApiResource::from_gvk_with_plural(&gvk, SUPPRESSION_PLURAL);
+    let apis: Vec<Api<DynamicObject>> = match namespaces {
+        None => vec![Api::all_with(client.clone(), &resource)],
+        Some(namespaces) => namespaces.iter().map(|ns| Api::namespaced_with(client.clone(), ns, &resource)).collect(),
+    };
+
+    let mut objects = Vec::new();
+    for api in apis {
+        match api.list(&ListParams::default()).await {
+            Ok(list) => objects.extend(list.items),
+            Err(kube::Error::Api(ae)) if ae.code == 404 => {
+                debug!("HealthSuppression CRD not installed");
+                return Ok(Vec::new());
+            }
+            Err(e) => return Err(e).context("Failed to list HealthSuppressions"),
+        }
+    }
+
+    let mut suppressions = Vec::new();
+    for object in objects {
+        let source = format!(
+            "{}/{}",
+            object.metadata.namespace.as_deref().unwrap_or_default(),
+            object.metadata.name.as_deref().unwrap_or_default()
+        );
+        let spec: SuppressionSpec = match object.data.get("spec").cloned().map(serde_json::from_value) {
+            Some(Ok(spec)) => spec,
+            Some(Err(e)) => {
+                warn!("HealthSuppression {} has an invalid spec, ignoring: {}", source, e);
+                continue;
+            }
+            None => SuppressionSpec::default(),
+        };
+        if let Some(reason) = spec.reason.as_deref() {
+            info!("HealthSuppression {} active: {}", source, reason);
+        }
+        suppressions.extend(Suppression::from_spec(&source, spec, now));
+    }
+    Ok(suppressions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FailedJobInfo;
+    use chrono::Duration;
+
+    fn failed_job(namespace: &str, job: &str) -> FailedJobInfo {
+        FailedJobInfo {
+            namespace: namespace.to_string(),
+            job: job.to_string(),
+            failed_pods: 1,
+            last_failure_time: None,
+            reason: None,
+        }
+    }
+
+    #[test]
+    fn test_suppression_matching() {
+        let now = Utc::now();
+        let spec: SuppressionSpec = serde_json::from_value(serde_json::json!({
+            "namespace": "batch-*",
+            "name": "nightly-.*",
+            "category": "failed_jobs",
+            "expires": (now + Duration::days(1)).to_rfc3339(),
+            "reason": "Known flaky upstream, TICKET-123"
+        }))
+        .unwrap();
+        let suppression = Suppression::from_spec("ops/nightly", spec, now).unwrap();
+
+        assert!(suppression.matches("failed_jobs", &failed_job("batch-eu", "nightly-export")));
+        assert!(!suppression.matches("failed_jobs", &failed_job("batch-eu", "hourly-export")));
+        // The regex is anchored to the whole name
+        assert!(!suppression.matches("failed_jobs", &failed_job("batch-eu", "pre-nightly-export")));
+        assert!(!suppression.matches("failed_jobs", &failed_job("default", "nightly-export")));
+        assert!(!suppression.matches("job_backoff", &failed_job("batch-eu", "nightly-export")));
+    }
+
+    #[test]
+    fn test_expired_and_invalid_suppressions_are_ignored() {
+        let now = Utc::now();
+        let expired = SuppressionSpec {
+            expires: Some(now - Duration::minutes(1)),
+            ..Default::default()
+        };
+        assert!(Suppression::from_spec("ops/old", expired, now).is_none());
+
+        let invalid = SuppressionSpec {
+            name: Some("(unclosed".to_string()),
+            ..Default::default()
+        };
+        assert!(Suppression::from_spec("ops/bad", invalid, now).is_none());
+
+        // No matchers: everything is silenced
+        let catch_all = Suppression::from_spec("ops/all", SuppressionSpec::default(), now).unwrap();
+        assert!(catch_all.matches("restarts", &failed_job("default", "anything")));
+    }
+}
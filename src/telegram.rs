@@ -0,0 +1,263 @@
+//! The report as Telegram messages sent by a bot, for teams that live in Telegram rather
+//! than Slack.
+
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use serde_json::json;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::time::timeout;
+use tracing::info;
+
+use crate::email::EMOJI;
+use crate::redaction::redact_payload;
+use crate::report::{HealthReport, ReportSummary};
+use crate::slack::build_report_payload;
+
+pub const TELEGRAM_API_URL: &str = "https://api.telegram.org";
+
+/// Longest text `sendMessage` accepts, in characters
+pub const TELEGRAM_MESSAGE_LIMIT: usize = 4096;
+
+/// Characters MarkdownV2 reserves outside code
+const RESERVED: &[char] = &['_', '*', '[', ']', '(', ')', '~', '`', '>', '#', '+', '-', '=', '|', '{', '}', '.', '!', '\\'];
+
+/// The report as MarkdownV2 messages: the blocks of the Slack message (sections, order
+/// and redaction included) converted block by block, split so none exceeds
+/// `TELEGRAM_MESSAGE_LIMIT`
+pub fn render_report_telegram(report: &HealthReport, summary: &ReportSummary) -> Vec<String> {
+    let mut payload = build_report_payload(report, summary);
+    redact_payload(&mut payload, &report.config.redaction_patterns);
+
+    let mut parts = Vec::new();
+    for block in &payload.blocks {
+        match block["type"].as_str() {
+            Some("header") => parts.push(format!("*{}*", escape(block["text"]["text"].as_str().unwrap_or_default()))),
+            Some("section") => parts.push(mrkdwn_to_markdown_v2(block["text"]["text"].as_str().unwrap_or_default())),
+            Some("context") => {
+                let texts: Vec<&str> = block["elements"].as_array().into_iter().flatten().filter_map(|e| e["text"].as_str()).collect();
+                parts.push(mrkdwn_to_markdown_v2(&texts.join(" ")));
+            }
+            _ => {}
+        }
+    }
+    split_messages(&parts, TELEGRAM_MESSAGE_LIMIT)
+}
+
+/// Send the messages in order to `chat_id`; each must be accepted within `deadline`.
+/// `bot_token` has its secret reference resolved and is kept out of errors.
+pub async fn send_telegram_messages(
+    client: &reqwest::Client,
+    api_url: &str,
+    bot_token: &str,
+    chat_id: &str,
+    messages: &[String],
+    deadline: Duration,
+) -> Result<()> {
+    let url = format!("{}/bot{}/sendMessage", api_url.trim_end_matches('/'), bot_token);
+    for (i, text) in messages.iter().enumerate() {
+        let request = client
+            .post(&url)
+            .json(&json!({
+                "chat_id": chat_id,
+                "text": text,
+                "parse_mode": "MarkdownV2",
+                "disable_web_page_preview": true,
+            }))
+            .send();
+        let response = timeout(deadline, request)
+            .await
+            .map_err(|_| anyhow!("Telegram timed out after {:?}", deadline))?
+            .map_err(|e| anyhow!("Failed to reach Telegram: {}", e.without_url()))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            let description = body["description"].as_str().unwrap_or_default();
+            return Err(anyhow!("Telegram rejected message {} of {}: {} {}", i + 1, messages.len(), status, description));
+        }
+    }
+    info!("Sent {} Telegram messages to {}", messages.len(), chat_id);
+    Ok(())
+}
+
+/// Escape `text` for MarkdownV2 outside code
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if RESERVED.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Escape `text` for MarkdownV2 inside code, where only `` ` `` and `\` are reserved
+fn escape_code(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('`', "\\`")
+}
+
+/// Slack mrkdwn as used by the report (`*bold*`, `` `code` ``, ```` ``` ```` blocks, emoji
+/// shortcodes, `<url|text>` links) to MarkdownV2. Slack mentions mean nothing in Telegram
+/// and are dropped.
+fn mrkdwn_to_markdown_v2(text: &str) -> String {
+    static TOKEN: OnceLock<Regex> = OnceLock::new();
+    let token = TOKEN.get_or_init(|| {
+        Regex::new(r"(?s)```(?P<block>.*?)```|`(?P<code>[^`\n]+)`|\*(?P<bold>[^*\n]+)\*|<(?P<target>[^<>|\n]+)(?:\|(?P<label>[^<>\n]+))?>")
+            .unwrap()
+    });
+
+    let mut text = text.replace("&lt;", "\u{1}").replace("&gt;", "\u{2}").replace("&amp;", "&");
+    for (shortcode, emoji) in EMOJI {
+        text = text.replace(shortcode, emoji);
+    }
+    let unescape = |s: &str| s.replace('\u{1}', "<").replace('\u{2}', ">");
+    let plain = |s: &str| escape(&unescape(s));
+
+    let mut out = String::new();
+    let mut last = 0;
+    for captures in token.captures_iter(&text) {
+        let whole = captures.get(0).unwrap();
+        out.push_str(&plain(&text[last..whole.start()]));
+        last = whole.end();
+        if let Some(block) = captures.name("block") {
+            out.push_str(&format!("```\n{}\n```", escape_code(&unescape(block.as_str().trim_matches('\n')))));
+        } else if let Some(code) = captures.name("code") {
+            out.push_str(&format!("`{}`", escape_code(&unescape(code.as_str()))));
+        } else if let Some(bold) = captures.name("bold") {
+            out.push_str(&format!("*{}*", plain(bold.as_str())));
+        } else if let Some(target) = captures.name("target") {
+            let target = target.as_str();
+            if target.starts_with("http://") || target.starts_with("https://") {
+                let label = captures.name("label").map_or(target, |l| l.as_str());
+                out.push_str(&format!("[{}]({})", plain(label), target.replace('\\', "\\\\").replace(')', "\\)")));
+            } else if let Some(label) = captures.name("label") {
+                out.push_str(&plain(label.as_str()));
+            }
+        }
+    }
+    out.push_str(&plain(&text[last..]));
+    out
+}
+
+/// Join `parts` with blank lines into as few messages of at most `limit` characters as
+/// possible, breaking between parts, else within a part (see `split_part`)
+fn split_messages(parts: &[String], limit: usize) -> Vec<String> {
+    let len = |s: &str| s.chars().count();
+    let mut messages = Vec::new();
+    let mut current = String::new();
+    for part in parts {
+        let pieces = if len(part) <= limit { vec![part.clone()] } else { split_part(part, limit) };
+        for (i, piece) in pieces.iter().enumerate() {
+            let separator = if i == 0 { "\n\n" } else { "\n" };
+            if !current.is_empty() && len(&current) + separator.len() + len(piece) > limit {
+                messages.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push_str(separator);
+            }
+            current.push_str(piece);
+        }
+    }
+    if !current.is_empty() {
+        messages.push(current);
+    }
+    messages
+}
+
+/// A part too long for one message as pieces of at most `limit` characters: its lines,
+/// except that a code block stays one piece (keeping its last lines when too long, as
+/// the end of a log matters most) and a longer line is cut
+fn split_part(part: &str, limit: usize) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut block: Vec<&str> = Vec::new();
+    let mut in_block = false;
+    for line in part.lines() {
+        // Backticks are escaped everywhere else, so only fences contain three in a row
+        let fence = line.matches("```").count() % 2 == 1;
+        if in_block || fence {
+            block.push(line);
+            if in_block && fence {
+                pieces.push(fit_code_block(&block, limit));
+                block.clear();
+            }
+            in_block ^= fence;
+            continue;
+        }
+        pieces.extend(cut_line(line, limit));
+    }
+    if !block.is_empty() {
+        pieces.push(fit_code_block(&block, limit));
+    }
+    pieces
+}
+
+/// The code block's fence lines around as many of its last lines as fit in `limit`
+fn fit_code_block(lines: &[&str], limit: usize) -> String {
+    let joined = lines.join("\n");
+    if joined.chars().count() <= limit || lines.len() < 2 {
+        return joined;
+    }
+    let (first, last) = (lines[0], lines[lines.len() - 1]);
+    let mut used = first.chars().count() + "\n…\n".chars().count() + 1 + last.chars().count();
+    let mut kept = Vec::new();
+    for line in lines[1..lines.len() - 1].iter().rev() {
+        used += line.chars().count() + 1;
+        if used > limit {
+            break;
+        }
+        kept.push(*line);
+    }
+    kept.reverse();
+    format!("{}\n…\n{}\n{}", first, kept.join("\n"), last)
+}
+
+/// `line` in pieces of at most `limit` characters, never separating an escape from the
+/// character it escapes
+fn cut_line(line: &str, limit: usize) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut rest = line;
+    while rest.chars().count() > limit {
+        let mut end = rest.char_indices().nth(limit).map_or(rest.len(), |(i, _)| i);
+        if rest[..end].ends_with('\\') && !rest[..end].ends_with("\\\\") {
+            end -= 1;
+        }
+        pieces.push(rest[..end].to_string());
+        rest = &rest[end..];
+    }
+    pieces.push(rest.to_string());
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mrkdwn_to_markdown_v2() {
+        assert_eq!(
+            mrkdwn_to_markdown_v2(":warning: *Restarts* (3)\n• `payments/api-0`: 5 restarts, see <https://runbooks.example.com/restarts|runbook>"),
+            "\u{26a0}\u{fe0f} *Restarts* \\(3\\)\n• `payments/api-0`: 5 restarts, see [runbook](https://runbooks.example.com/restarts)"
+        );
+        assert_eq!(mrkdwn_to_markdown_v2("```panic: a_b\n```"), "```\npanic: a_b\n```");
+        assert_eq!(mrkdwn_to_markdown_v2("cpu &gt; 90% on node-1.eu"), "cpu \\> 90% on node\\-1\\.eu");
+        assert_eq!(mrkdwn_to_markdown_v2("on call: <@U0123ABC>"), "on call: ");
+    }
+
+    #[test]
+    fn test_split_messages() {
+        let parts = vec!["a".repeat(6), "b".repeat(3), format!("{}\n{}", "c".repeat(4), "d".repeat(12))];
+        let messages = split_messages(&parts, 10);
+        assert_eq!(messages, vec!["aaaaaa".to_string(), "bbb\n\ncccc".to_string(), "dddddddddd".to_string(), "dd".to_string()]);
+        assert!(messages.iter().all(|m| m.chars().count() <= 10));
+
+        // An escape is never separated from the character it escapes
+        let messages = split_messages(&[format!("{}\\.", "x".repeat(9))], 10);
+        assert_eq!(messages, vec!["x".repeat(9), "\\.".to_string()]);
+
+        // Code blocks stay whole, keeping their last lines
+        let log = format!("*Crash log*\n```\n{}\n```", (1..=9).map(|i| format!("line {}", i)).collect::<Vec<_>>().join("\n"));
+        let messages = split_messages(&[log], 30);
+        assert_eq!(messages, vec!["*Crash log*".to_string(), "```\n…\nline 8\nline 9\n```".to_string()]);
+    }
+}
@@ -0,0 +1,116 @@
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::config::validate_section_order;
+use crate::types::Config;
+
+/// Tenants loaded from `TENANTS_FILE` (JSON, typically a mounted ConfigMap). Each tenant
+/// is a group of namespaces with its own thresholds and sink; a run reports on the one
+/// named by `TENANT`, so one deployment serves many teams with isolated reports.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TenantsFile {
+    #[serde(default)]
+    pub tenants: BTreeMap<String, TenantSpec>,
+}
+
+/// Settings of a tenant; unset ones keep the deployment-wide value
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TenantSpec {
+    pub namespaces: Vec<String>,
+    /// Incoming webhook (or secret reference) receiving the tenant's report
+    pub slack_webhook_url: Option<String>,
+    pub threshold_percent: Option<f64>,
+    pub restart_grace_minutes: Option<i64>,
+    pub pending_grace_minutes: Option<i64>,
+    pub omit_empty_sections: Option<bool>,
+    pub section_order: Option<Vec<String>>,
+    /// Cron schedule of the tenant's CronJob; only read by the Helm chart
+    pub schedule: Option<String>,
+}
+
+impl TenantsFile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let data = std::fs::read(path)
+            .with_context(|| format!("Failed to read tenants file {}", path.display()))?;
+        serde_json::from_slice(&data)
+            .with_context(|| format!("Invalid tenants file {}", path.display()))
+    }
+}
+
+/// Narrow `cfg` down to tenant `name`: its namespaces, overrides and sink
+pub fn apply_tenant(cfg: &mut Config, name: &str, spec: &TenantSpec) -> Result<()> {
+    if spec.namespaces.is_empty() {
+        return Err(anyhow!("Tenant {} has no namespaces", name));
+    }
+    cfg.namespaces = spec.namespaces.clone();
+    if let Some(url) = &spec.slack_webhook_url {
+        cfg.slack_webhook_url = url.clone();
+    }
+    if let Some(threshold) = spec.threshold_percent {
+        cfg.threshold_percent = threshold;
+    }
+    if let Some(minutes) = spec.restart_grace_minutes {
+        cfg.restart_grace_minutes = minutes;
+    }
+    if let Some(minutes) = spec.pending_grace_minutes {
+        cfg.pending_grace_minutes = minutes;
+    }
+    if let Some(omit) = spec.omit_empty_sections {
+        cfg.omit_empty_sections = omit;
+    }
+    if let Some(order) = &spec.section_order {
+        validate_section_order(order).with_context(|| format!("Tenant {}", name))?;
+        cfg.section_order = order.clone();
+    }
+    cfg.tenant = Some(name.to_string());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_tenant() {
+        let tenants: TenantsFile = serde_json::from_value(serde_json::json!({
+            "tenants": {
+                "payments": {
+                    "namespaces": ["payments", "payments-batch"],
+                    "slack_webhook_url": "vault:secret/data/payments#webhook",
+                    "threshold_percent": 70.0,
+                    "section_order": ["failed", "restarts"],
+                    "schedule": "*/10 * * * *"
+                },
+                "empty": {"namespaces": []}
+            }
+        }))
+        .unwrap();
+        let mut cfg = Config {
+            namespaces: vec!["default".to_string()],
+            slack_webhook_url: "https://hooks.slack.com/platform".to_string(),
+            restart_grace_minutes: 5,
+            ..Default::default()
+        };
+
+        apply_tenant(&mut cfg, "payments", &tenants.tenants["payments"]).unwrap();
+        assert_eq!(cfg.namespaces, vec!["payments", "payments-batch"]);
+        assert_eq!(cfg.slack_webhook_url, "vault:secret/data/payments#webhook");
+        assert_eq!(cfg.threshold_percent, 70.0);
+        assert_eq!(cfg.restart_grace_minutes, 5);
+        assert_eq!(cfg.section_order, vec!["failed", "restarts"]);
+        assert_eq!(cfg.tenant.as_deref(), Some("payments"));
+
+        assert!(apply_tenant(&mut cfg, "empty", &tenants.tenants["empty"]).is_err());
+        let bad_order = TenantSpec {
+            namespaces: vec!["a".to_string()],
+            section_order: Some(vec!["bogus".to_string()]),
+            ..Default::default()
+        };
+        assert!(apply_tenant(&mut cfg, "bad", &bad_order).is_err());
+        // Typos in tenant settings are rejected rather than silently ignored
+        assert!(serde_json::from_value::<TenantSpec>(serde_json::json!({"namespaces": ["a"], "treshold_percent": 1})).is_err());
+    }
+}
@@ -1,22 +1,528 @@
 use chrono::{DateTime, Utc};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 
 #[derive(Debug, Clone)]
 pub struct Config {
     pub namespaces: Vec<String>,
     pub threshold_percent: f64,
+    /// Incoming webhook (or secret reference); empty when only email is configured
     pub slack_webhook_url: String,
     pub restart_grace_minutes: i64,
     pub pending_grace_minutes: i64,
+    /// Objects (and namespaces) deleted longer ago than this but held by finalizers are reported
+    pub terminating_grace_minutes: i64,
     pub cluster_name: Option<String>,
     pub datacenter_name: Option<String>,
     pub fail_if_no_metrics: bool,
+    /// Whether cluster-scoped analyzers run, or only the namespaced ones
+    pub rbac_scope: RbacScope,
+    /// Analyzers of a namespace still running after this long are skipped; 0 disables
+    pub namespace_timeout_seconds: u64,
+    /// Namespaces and cluster-wide checks not done after this long are skipped; 0 disables
+    pub run_timeout_seconds: u64,
+    /// Warn when the reporter's peak memory reaches this share of its container limit; 0 disables
+    pub memory_warn_percent: f64,
+    /// Pod metrics samples older than this are reported as stale
+    pub metrics_staleness_minutes: i64,
+    /// Count pods from every namespace (kube-system, control plane, ...) in node pod figures
+    pub node_pod_count_all_namespaces: bool,
+    /// Forecast node disk pressure from kubelet filesystem stats and thresholds
+    pub node_disk_forecast: bool,
+    /// Percentage points below a kubelet image GC/eviction threshold at which a node
+    /// filesystem is reported
+    pub node_disk_margin_percent: f64,
+    /// File used to persist usage samples between runs; recommendations are off when unset
+    pub recommendations_history_path: Option<String>,
+    /// Limit:request ratio above which a container is flagged in the hygiene audit
+    pub limit_request_ratio_threshold: f64,
+    /// Lookback window for the pod churn analyzer
+    pub pod_churn_window_minutes: i64,
+    /// Pods replaced by one workload within the churn window before it is flagged
+    pub pod_churn_threshold: i64,
+    /// Lookback window for the Warning event flood check
+    pub event_flood_window_minutes: i64,
+    /// Warning events a namespace may record within the window before it is flagged
+    pub event_flood_threshold: i64,
+    /// Share of `backoffLimit` an unfinished job may burn through before it is reported
+    pub job_backoff_warning_percent: f64,
+    /// Active jobs of a single CronJob at which runs are considered piling up
+    pub cronjob_active_jobs_threshold: usize,
+    /// Finished Jobs without TTL, or Succeeded pods, a namespace may hold before it is reported
+    pub finished_objects_threshold: usize,
+    /// Lookback window for readiness probe failures
+    pub readiness_flap_window_minutes: i64,
+    /// Readiness probe failures within the window before a pod counts as flapping
+    pub readiness_flap_threshold: i64,
+    /// Read stored object counts per resource from the API server's `/metrics`
+    pub object_count_check: bool,
+    /// Stored objects of one resource at which it is reported
+    pub object_count_threshold: u64,
+    /// Growth of a resource's object count since the previous run at which it is reported
+    pub object_count_growth_percent: f64,
+    /// Lookback window for node NotReady transitions
+    pub node_flap_window_minutes: i64,
+    /// NotReady transitions of a node within the window before it counts as flapping
+    pub node_flap_threshold: i64,
+    /// Lookback window for Service endpoint churn
+    pub endpoint_churn_window_minutes: i64,
+    /// Estimated endpoint membership changes within the window before a Service is reported
+    pub endpoint_churn_threshold: i64,
+    /// Report Services and pods of dual-stack clusters lacking an IPv4 or IPv6 address
+    pub dual_stack_check: bool,
+    /// Networking DaemonSets (`namespace/name`) that must be healthy on every node
+    pub network_agent_daemonsets: Vec<String>,
+    /// Check OLM Subscriptions and operator Deployments (`operators.coreos.com/*` labels)
+    pub operator_check: bool,
+    /// Leave sections without findings out of the Slack message instead of printing "No X"
+    pub omit_empty_sections: bool,
+    /// Report sections (keys from `REPORT_SECTIONS`) to show, in order; empty shows all
+    pub section_order: Vec<String>,
+    /// Highest-priority findings shown in "Act on these first" at the top; 0 hides it
+    pub priority_top_n: usize,
+    /// Rules overriding the default severity of findings; the last matching one applies
+    pub severity_rules: Vec<SeverityRule>,
+    /// Lines of `logs --previous` attached to crashlooping/OOMKilled containers; 0 disables
+    pub crash_log_lines: usize,
+    /// Pod label keys copied onto every pod finding for downstream routing
+    pub issue_labels: Vec<String>,
+    /// JSON namespace/label → team mapping used for owner annotation and per-team routing
+    pub ownership_file: Option<String>,
+    /// Read HealthSuppression resources and silence the findings they match
+    pub suppressions_enabled: bool,
+    /// File persisting snoozes between runs; snoozing is off when unset
+    pub state_file: Option<String>,
+    /// List findings that cleared since the last run (needs `state_file`), also
+    /// sending a report when nothing else is wrong
+    pub notify_resolved: bool,
+    /// Let the API client send requests other than get/list/watch; off keeps it read-only
+    pub allow_api_writes: bool,
+    /// File each run appends the API verbs it used to, as a JSON line
+    pub audit_log_file: Option<String>,
+    /// Record the run's API responses to a directory, or replay them from one
+    pub api_recording: Option<ApiRecording>,
+    /// Give up on a single sink delivery after this long
+    pub sink_timeout_seconds: u64,
+    /// Consecutive failed runs after which a sink is skipped (needs `state_file`); 0 disables
+    pub sink_failure_threshold: u32,
+    /// How long a sink is skipped once it hit `sink_failure_threshold`
+    pub sink_cooldown_minutes: i64,
+    /// PEM bundle of extra CAs trusted for webhook deliveries, e.g. of a TLS-intercepting proxy
+    pub webhook_ca_bundle: Option<String>,
+    /// HMAC key signing every webhook payload; unsigned when unset
+    pub webhook_signing_secret: Option<String>,
+    pub webhook_signing_algorithm: SigningAlgorithm,
+    /// Email delivery through an SMTP relay, when `SMTP_HOST` is set
+    pub email: Option<EmailSettings>,
+    /// Events API v2 routing key (or secret reference); PagerDuty is off when unset
+    pub pagerduty_routing_key: Option<String>,
+    /// Sections (keys from `pagerduty::PAGEABLE_SECTIONS`) whose findings trigger PagerDuty incidents
+    pub pagerduty_sections: Vec<String>,
+    /// Slack Web API delivery with a bot token instead of `slack_webhook_url`, when
+    /// `SLACK_BOT_TOKEN` is set
+    pub slack_bot: Option<SlackBotSettings>,
+    /// Telegram bot delivery, when `TELEGRAM_BOT_TOKEN` is set
+    pub telegram: Option<TelegramSettings>,
+    /// Prometheus Pushgateway delivery, when `PUSHGATEWAY_URL` is set
+    pub pushgateway: Option<PushgatewaySettings>,
+    /// Shell commands run before collection, after report assembly and before sending
+    pub hook_commands: HookCommands,
+    /// Time a hook command may take before it is killed
+    pub hook_timeout_seconds: u64,
+    /// Schedule whose current on-call is mentioned in Slack for critical findings
+    pub oncall: Option<OnCallSettings>,
+    /// Mention used for critical findings when nobody on call could be resolved, e.g.
+    /// `<!subteam^S0123>`
+    pub slack_mention: Option<String>,
+    /// Mentions by report section, e.g. `nodes` → `<!subteam^S0123>`, added under the
+    /// header when the section has critical findings
+    pub slack_section_mentions: BTreeMap<String, String>,
+    /// Endpoint (or secret reference) receiving the whole report as JSON every run
+    pub generic_webhook_url: Option<String>,
+    /// Extra headers sent to `generic_webhook_url`, e.g. for auth
+    pub generic_webhook_headers: Vec<(String, String)>,
+    /// File the findings are written to every run as OpenMetrics, for a textfile collector
+    pub metrics_textfile: Option<String>,
+    /// File the findings are written to every run as CSV; `-` writes them to stdout
+    pub csv_export: Option<String>,
+    /// Format the report document is printed to stdout in every run, for jq, archival
+    /// and other tooling
+    pub output_format: Option<OutputFormat>,
+    /// File the report is written to every run as a self-contained HTML page
+    pub html_report_file: Option<String>,
+    /// Address the Prometheus exporter serves `/metrics` on, in daemon mode
+    pub metrics_listen_addr: Option<std::net::SocketAddr>,
+    /// Whether the process reports once and exits, or keeps reporting every `interval_seconds`
+    pub run_mode: RunMode,
+    pub interval_seconds: u64,
+    /// Where the change-freeze flag is read; findings new during a freeze are escalated
+    pub change_freeze_source: Option<ChangeFreezeSource>,
+    /// Text matching these is replaced with `[REDACTED]` in every outgoing message
+    pub redaction_patterns: Vec<regex::Regex>,
+    /// Tenant (from `TENANTS_FILE`) this run reports on; `None` for the whole deployment
+    pub tenant: Option<String>,
+    /// Kubeconfig contexts to report on in one run; empty reports on the current cluster
+    pub clusters: Vec<String>,
+    /// Central webhook receiving the cross-cluster summary when `clusters` is set
+    pub federation_webhook_url: Option<String>,
+    /// Pod Security Standards profile pods are checked against; `Privileged` skips the check
+    pub pod_security_level: PodSecurityLevel,
+    /// Per-namespace overrides of `pod_security_level`
+    pub pod_security_namespace_levels: BTreeMap<String, PodSecurityLevel>,
+    /// Report namespaces without a ResourceQuota or LimitRange
+    pub namespace_quota_check: bool,
+    /// Share of a LimitRange max (or maxLimitRequestRatio) at which a container counts as at the edge
+    pub limit_range_edge_percent: f64,
+    /// Custom resource kinds whose status conditions must match the configured values
+    pub custom_resource_checks: Vec<CustomResourceCheck>,
+}
+
+/// Expected status conditions for one custom resource kind, e.g. `kafka.strimzi.io/Kafka: Ready=True`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomResourceCheck {
+    pub group: String,
+    /// Pinned API version; the group's preferred version when unset
+    pub version: Option<String>,
+    pub kind: String,
+    /// `(condition type, expected status)` pairs
+    pub conditions: Vec<(String, String)>,
+}
+
+/// SMTP relay and addresses the report email goes through
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailSettings {
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    pub smtp_tls: SmtpTls,
+    /// Authenticates with AUTH PLAIN when set
+    pub smtp_username: Option<String>,
+    /// Password or secret reference (`vault:`, `aws-sm:`)
+    pub smtp_password: Option<String>,
+    /// PEM bundle of extra CAs trusted for the relay, e.g. an internal CA
+    pub ca_bundle: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+}
+
+/// Slack channel the report is posted to through `chat.postMessage`: the summary as a
+/// message, the sections as replies in its thread
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlackBotSettings {
+    /// Bot token (`xoxb-...`, needs `chat:write`) or secret reference
+    pub token: String,
+    /// Channel ID the bot is a member of, e.g. `C0123ABC`
+    pub channel: String,
+}
+
+/// Telegram chat the report is sent to by a bot
+#[derive(Debug, Clone, PartialEq)]
+pub struct TelegramSettings {
+    /// Bot API token or secret reference (`vault:`, `aws-sm:`)
+    pub bot_token: String,
+    /// Numeric chat ID (groups are negative) or `@channelusername`
+    pub chat_id: String,
+}
+
+/// Shell commands run at the lifecycle hook points (see `hooks::CommandHook`)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HookCommands {
+    /// Before collection; a failure aborts the run
+    pub pre_collection: Option<String>,
+    /// After the report is assembled, with the report document on stdin; may print
+    /// enrichment as JSON
+    pub post_report: Option<String>,
+    /// Before notifiers run, with the report document on stdin; a non-zero exit vetoes
+    /// the notifications
+    pub pre_send: Option<String>,
+}
+
+/// Prometheus Pushgateway the report's gauges are pushed to every run
+#[derive(Debug, Clone, PartialEq)]
+pub struct PushgatewaySettings {
+    /// Base URL or secret reference (`vault:`, `aws-sm:`), e.g. `http://pushgateway:9091`
+    pub url: String,
+    /// `job` label of the pushed group
+    pub job: String,
+    /// `instance` label of the pushed group; the cluster name when unset
+    pub instance: Option<String>,
+}
+
+/// On-call schedule read (never written) to mention whoever is on call
+#[derive(Debug, Clone, PartialEq)]
+pub struct OnCallSettings {
+    pub provider: OnCallProvider,
+    pub schedule_id: String,
+    /// Read-only API token or secret reference (`vault:`, `aws-sm:`)
+    pub api_token: String,
+    /// API base URL; `None` uses the provider's default (US) region
+    pub api_url: Option<String>,
+    /// On-call email → Slack member ID, so the mention notifies them
+    pub slack_users: BTreeMap<String, String>,
+}
+
+/// Where on-call schedules live (`ONCALL_PROVIDER`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnCallProvider {
+    PagerDuty,
+    Opsgenie,
+}
+
+/// How the SMTP connection is secured
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SmtpTls {
+    /// Upgrade a plain connection with STARTTLS (submission, port 587)
+    #[default]
+    StartTls,
+    /// TLS from the start (SMTPS, port 465)
+    Tls,
+    /// Plain SMTP, for relays inside a trusted network (port 25)
+    None,
+}
+
+impl SmtpTls {
+    pub fn default_port(&self) -> u16 {
+        match self {
+            SmtpTls::StartTls => 587,
+            SmtpTls::Tls => 465,
+            SmtpTls::None => 25,
+        }
+    }
+}
+
+/// What the reporter's RBAC lets it read (`RBAC_SCOPE`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RbacScope {
+    /// Decided by the preflight check: `Namespace` when listing nodes is forbidden
+    #[default]
+    Auto,
+    Cluster,
+    /// Namespaced Roles only: the cluster-scoped analyzers (nodes, node metrics and
+    /// disk, networking agents, operators, object counts) are skipped and
+    /// HealthSuppressions are read from the reported namespaces alone
+    Namespace,
+}
+
+/// How urgent a finding is, from least to most
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+        }
+    }
+}
+
+/// Severity given to a section's findings, or only to those whose measure (see
+/// `MEASURED_SECTIONS`) is over `threshold`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeverityRule {
+    pub section: String,
+    pub threshold: Option<f64>,
+    pub severity: Severity,
+}
+
+/// Sections whose findings have a measure severity rules can set a threshold on: the
+/// higher of CPU and memory percent for `usage`, minutes in the state for the others
+pub const MEASURED_SECTIONS: &[&str] = &["usage", "pending", "failed", "unready", "stuck_terminating"];
+
+/// Pod Security Standards profile, from least to most restrictive
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PodSecurityLevel {
+    #[default]
+    Privileged,
+    Baseline,
+    Restricted,
+}
+
+impl PodSecurityLevel {
+    /// Name as used by the `pod-security.kubernetes.io/*` namespace labels
+    pub fn name(&self) -> &'static str {
+        match self {
+            PodSecurityLevel::Privileged => "privileged",
+            PodSecurityLevel::Baseline => "baseline",
+            PodSecurityLevel::Restricted => "restricted",
+        }
+    }
+}
+
+/// Directory API responses are recorded to (`API_RECORD_DIR`) or replayed from
+/// (`API_REPLAY_DIR`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiRecording {
+    Record(String),
+    /// Answer every request from the recording; no cluster is contacted
+    Replay(String),
+}
+
+/// How long the process lives (`RUN_MODE`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunMode {
+    /// One report, then exit; scheduled by a CronJob
+    Once,
+    /// Collect, report and sleep in a loop with a single API client
+    Daemon,
+}
+
+/// Format the whole report is printed to stdout in every run (`OUTPUT_FORMAT`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Json,
+    /// Readable in diffs, e.g. for health snapshots committed to Git
+    Yaml,
+}
+
+/// Where the change-freeze flag is read (`CHANGE_FREEZE_SOURCE`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeFreezeSource {
+    /// `configmap:<namespace>/<name>`: the ConfigMap's `change-freeze` key
+    ConfigMap { namespace: String, name: String },
+    /// `namespace:<name>`: the namespace's `kube-health-reporter.io/change-freeze` annotation
+    Namespace(String),
+    /// `http://` or `https://` URL: the response body
+    Http(String),
+}
+
+impl std::fmt::Display for ChangeFreezeSource {
+    /// The URL is left out as it may carry a token
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChangeFreezeSource::ConfigMap { namespace, name } => write!(f, "ConfigMap {}/{}", namespace, name),
+            ChangeFreezeSource::Namespace(name) => write!(f, "namespace {}", name),
+            ChangeFreezeSource::Http(_) => write!(f, "HTTP endpoint"),
+        }
+    }
+}
+
+/// HMAC algorithm of the webhook payload signature
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SigningAlgorithm {
+    #[default]
+    Sha256,
+    Sha512,
+}
+
+impl SigningAlgorithm {
+    /// Prefix of the signature header value, e.g. `sha256`
+    pub fn name(&self) -> &'static str {
+        match self {
+            SigningAlgorithm::Sha256 => "sha256",
+            SigningAlgorithm::Sha512 => "sha512",
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            namespaces: Vec::new(),
+            threshold_percent: 85.0,
+            slack_webhook_url: String::new(),
+            restart_grace_minutes: 5,
+            pending_grace_minutes: 5,
+            terminating_grace_minutes: 60,
+            cluster_name: None,
+            datacenter_name: None,
+            fail_if_no_metrics: true,
+            rbac_scope: RbacScope::Auto,
+            namespace_timeout_seconds: 120,
+            run_timeout_seconds: 0,
+            memory_warn_percent: 80.0,
+            metrics_staleness_minutes: 5,
+            node_pod_count_all_namespaces: false,
+            node_disk_forecast: false,
+            node_disk_margin_percent: 10.0,
+            recommendations_history_path: None,
+            limit_request_ratio_threshold: 10.0,
+            pod_churn_window_minutes: 60,
+            pod_churn_threshold: 10,
+            event_flood_window_minutes: 60,
+            event_flood_threshold: 500,
+            job_backoff_warning_percent: 60.0,
+            cronjob_active_jobs_threshold: 3,
+            finished_objects_threshold: 100,
+            readiness_flap_window_minutes: 60,
+            readiness_flap_threshold: 5,
+            object_count_check: false,
+            object_count_threshold: 100_000,
+            object_count_growth_percent: 50.0,
+            node_flap_window_minutes: 60,
+            node_flap_threshold: 3,
+            endpoint_churn_window_minutes: 60,
+            endpoint_churn_threshold: 10,
+            dual_stack_check: false,
+            network_agent_daemonsets: Vec::new(),
+            operator_check: false,
+            omit_empty_sections: false,
+            section_order: Vec::new(),
+            priority_top_n: 5,
+            severity_rules: Vec::new(),
+            crash_log_lines: 0,
+            issue_labels: Vec::new(),
+            ownership_file: None,
+            suppressions_enabled: true,
+            state_file: None,
+            notify_resolved: false,
+            allow_api_writes: false,
+            audit_log_file: None,
+            api_recording: None,
+            sink_timeout_seconds: 10,
+            sink_failure_threshold: 3,
+            sink_cooldown_minutes: 60,
+            webhook_ca_bundle: None,
+            webhook_signing_secret: None,
+            webhook_signing_algorithm: SigningAlgorithm::Sha256,
+            email: None,
+            pagerduty_routing_key: None,
+            pagerduty_sections: Vec::new(),
+            slack_bot: None,
+            telegram: None,
+            pushgateway: None,
+            hook_commands: HookCommands::default(),
+            hook_timeout_seconds: 30,
+            oncall: None,
+            slack_mention: None,
+            slack_section_mentions: BTreeMap::new(),
+            generic_webhook_url: None,
+            generic_webhook_headers: Vec::new(),
+            metrics_textfile: None,
+            csv_export: None,
+            output_format: None,
+            html_report_file: None,
+            metrics_listen_addr: None,
+            run_mode: RunMode::Once,
+            interval_seconds: 300,
+            change_freeze_source: None,
+            redaction_patterns: Vec::new(),
+            tenant: None,
+            clusters: Vec::new(),
+            federation_webhook_url: None,
+            pod_security_level: PodSecurityLevel::Privileged,
+            pod_security_namespace_levels: BTreeMap::new(),
+            namespace_quota_check: false,
+            limit_range_edge_percent: 90.0,
+            custom_resource_checks: Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct PodUsageTotals {
     pub cpu_millicores: i64,
     pub memory_bytes: i64,
+    /// When metrics-server sampled the usage
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Sampling window reported by metrics-server (e.g. "30s")
+    pub window: Option<String>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -25,78 +531,550 @@ pub struct PodRequestTotals {
     pub memory_bytes: Option<i64>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct HeavyUsagePod {
     pub namespace: String,
     pub pod: String,
+    /// Pod labels selected by `ISSUE_LABELS` (e.g. team, app) for routing
+    pub labels: BTreeMap<String, String>,
     pub cpu_pct: Option<f64>,
     pub mem_pct: Option<f64>,
+    /// Current usage, shown next to the percentages
+    pub cpu_millicores: i64,
+    pub memory_bytes: i64,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct StaleMetricsInfo {
+    pub namespace: String,
+    pub pod: String,
+    pub sampled_at: DateTime<Utc>,
+    pub age_minutes: i64,
+    pub window: Option<String>,
+}
+
+/// Current usage and resource settings of one pod, attributed to its workload
 #[derive(Debug, Clone)]
+pub struct WorkloadUsage {
+    pub namespace: String,
+    pub kind: String,
+    pub workload: String,
+    pub cpu_millicores: i64,
+    pub memory_bytes: i64,
+    pub requests: PodRequestTotals,
+    pub limits: PodRequestTotals,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RightsizingRecommendation {
+    pub namespace: String,
+    pub kind: String,
+    pub workload: String,
+    pub samples: usize,
+    pub current_cpu_request: Option<i64>,
+    pub suggested_cpu_request: i64,
+    pub current_memory_request: Option<i64>,
+    pub suggested_memory_request: i64,
+    pub current_memory_limit: Option<i64>,
+    pub suggested_memory_limit: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceHygieneInfo {
+    pub namespace: String,
+    /// Owning workload as `Kind/name`, or `Pod/name` for bare pods
+    pub workload: String,
+    pub container: String,
+    pub issue: ResourceHygieneIssue,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ResourceHygieneIssue {
+    MemoryLimitUnset,
+    HighLimitRatio { resource: String, ratio: f64 },
+}
+
+/// Workload whose pod spec fails the Pod Security Standards profile of its namespace
+#[derive(Debug, Clone, Serialize)]
+pub struct PodSecurityViolationInfo {
+    pub namespace: String,
+    /// Owning workload as `Kind/name`, or `Pod/name` for bare pods
+    pub workload: String,
+    pub level: PodSecurityLevel,
+    /// Failed checks, e.g. `hostNetwork` or `privileged (app)`
+    pub violations: Vec<String>,
+}
+
+/// Object or namespace stuck in Terminating, held by finalizers past the grace period
+#[derive(Debug, Clone, Serialize)]
+pub struct StuckTerminatingInfo {
+    pub namespace: String,
+    /// `Kind/name`, e.g. `PersistentVolumeClaim/data-0` or `Namespace/old-team`
+    pub object: String,
+    /// When deletion was requested
+    pub since: DateTime<Utc>,
+    pub finalizers: Vec<String>,
+    /// What the namespace controller reports as left (namespaces only)
+    pub details: Vec<String>,
+}
+
+/// Custom resource whose status condition does not have the expected value
+#[derive(Debug, Clone, Serialize)]
+pub struct CustomResourceConditionInfo {
+    pub namespace: String,
+    /// `Kind/name`, e.g. `Kafka/events`
+    pub resource: String,
+    pub condition: String,
+    pub expected: String,
+    /// Reported status; `None` when the condition is missing
+    pub actual: Option<String>,
+    pub reason: Option<String>,
+    pub message: Option<String>,
+}
+
+/// Namespace recording an abnormal number of Warning events
+#[derive(Debug, Clone, Serialize)]
+pub struct EventFloodInfo {
+    pub namespace: String,
+    /// Warning events within the window
+    pub warnings: i64,
+    /// Most frequent reasons with their counts, most frequent first
+    pub top_reasons: Vec<(String, i64)>,
+    pub window_minutes: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PodChurnInfo {
+    pub namespace: String,
+    pub kind: String,
+    pub workload: String,
+    /// Pods created within the churn window
+    pub created: i64,
+    /// Pods deleted by the controller within the churn window
+    pub deleted: i64,
+    pub window_minutes: i64,
+}
+
+/// Pod blocked on a ConfigMap/Secret (or key) that does not exist
+#[derive(Debug, Clone, Serialize)]
+pub struct MissingConfigRefInfo {
+    pub namespace: String,
+    pub pod: String,
+    /// Container reporting CreateContainerConfigError; `None` for volume mount failures
+    pub container: Option<String>,
+    /// `ConfigMap` or `Secret`
+    pub kind: String,
+    pub name: String,
+    /// Missing key when the object exists but lacks it
+    pub key: Option<String>,
+}
+
+/// Pod whose Ready condition keeps flipping
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessFlapInfo {
+    pub namespace: String,
+    pub pod: String,
+    /// Readiness probe failures within the window
+    pub probe_failures: i64,
+    pub currently_ready: bool,
+    pub last_transition: Option<DateTime<Utc>>,
+    pub window_minutes: i64,
+}
+
+/// Service whose endpoint membership changes rapidly (availability risk)
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceEndpointChurnInfo {
+    pub namespace: String,
+    pub service: String,
+    pub backing_pods: usize,
+    /// Estimated endpoint additions/removals within the window
+    pub changes: i64,
+    /// Backing pods also reported as readiness flapping
+    pub flapping_pods: Vec<String>,
+    pub window_minutes: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DualStackIssueInfo {
+    pub namespace: String,
+    /// `Service/<name>`, or the owning workload (`Kind/name`) of the pods
+    pub object: String,
+    /// `IPv4` or `IPv6`
+    pub missing_family: String,
+    /// Addresses assigned (cluster IPs, or pod IPs of the first affected pod)
+    pub addresses: Vec<String>,
+    /// Affected pods; 0 for Services
+    pub pods: usize,
+}
+
+/// What routes traffic to a pod: the Services selecting it and the Ingresses with a
+/// backend on one of them, split by whether their load balancer address is public
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BlastRadius {
+    pub services: Vec<String>,
+    pub public_ingresses: Vec<String>,
+    pub internal_ingresses: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct RestartEventInfo {
     pub namespace: String,
     pub pod: String,
+    /// Pod labels selected by `ISSUE_LABELS` (e.g. team, app) for routing
+    pub labels: BTreeMap<String, String>,
+    /// Node the pod runs on (unset for unscheduled pods)
+    pub node_name: Option<String>,
+    /// Zone of that node (`topology.kubernetes.io/zone`)
+    pub zone: Option<String>,
+    /// Services and Ingresses routing to the pod; `None` when nothing does
+    pub blast_radius: Option<BlastRadius>,
     pub container: String,
     pub last_restart_time: Option<DateTime<Utc>>,
     pub reason: Option<String>,
     pub message: Option<String>,
     pub exit_code: Option<i32>,
+    /// Container image as written in the pod spec (name:tag)
+    pub image: Option<String>,
+    /// Sanitized tail of the previous container instance's log (`CRASH_LOG_LINES`)
+    pub log_tail: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+impl RestartEventInfo {
+    pub fn cause(&self) -> RestartCause {
+        RestartCause::classify(self.exit_code, self.reason.as_deref())
+    }
+}
+
+/// Human-readable cause of a container restart, derived from its exit code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RestartCause {
+    OomKilled,
+    /// 137 without OOMKilled: SIGKILL from eviction or a failed liveness probe
+    Killed,
+    /// 143: SIGTERM
+    Terminated,
+    /// 139: SIGSEGV
+    Segfault,
+    /// 1: generic application error
+    AppError,
+    /// 0: the process exited successfully but was restarted
+    Completed,
+    Other(i32),
+    Unknown,
+}
+
+impl RestartCause {
+    pub fn classify(exit_code: Option<i32>, reason: Option<&str>) -> Self {
+        if reason == Some("OOMKilled") {
+            return RestartCause::OomKilled;
+        }
+        match exit_code {
+            Some(137) => RestartCause::Killed,
+            Some(143) => RestartCause::Terminated,
+            Some(139) => RestartCause::Segfault,
+            Some(1) => RestartCause::AppError,
+            Some(0) => RestartCause::Completed,
+            // Windows containers exit with NTSTATUS codes, reported as negative int32
+            Some(code) => match code as u32 {
+                // STATUS_ACCESS_VIOLATION, STATUS_STACK_BUFFER_OVERRUN
+                0xC000_0005 | 0xC000_0409 => RestartCause::Segfault,
+                // STATUS_CONTROL_C_EXIT: stopped by the container runtime
+                0xC000_013A => RestartCause::Terminated,
+                _ => RestartCause::Other(code),
+            },
+            None => RestartCause::Unknown,
+        }
+    }
+
+    /// Number of restarts per cause, most frequent first
+    pub fn tally(restarts: &[RestartEventInfo]) -> Vec<(RestartCause, usize)> {
+        let mut counts: std::collections::BTreeMap<RestartCause, usize> = Default::default();
+        for r in restarts {
+            *counts.entry(r.cause()).or_default() += 1;
+        }
+        let mut counts: Vec<(RestartCause, usize)> = counts.into_iter().collect();
+        counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        counts
+    }
+}
+
+impl std::fmt::Display for RestartCause {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RestartCause::OomKilled => write!(f, "OOM killed"),
+            RestartCause::Killed => write!(f, "killed (SIGKILL, eviction or liveness probe)"),
+            RestartCause::Terminated => write!(f, "terminated (SIGTERM)"),
+            RestartCause::Segfault => write!(f, "segmentation fault"),
+            RestartCause::AppError => write!(f, "application error"),
+            RestartCause::Completed => write!(f, "exited successfully"),
+            RestartCause::Other(code) => write!(f, "exit code {}", code),
+            RestartCause::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct PendingPodInfo {
     pub namespace: String,
     pub pod: String,
+    /// Pod labels selected by `ISSUE_LABELS` (e.g. team, app) for routing
+    pub labels: BTreeMap<String, String>,
+    /// Node the pod runs on (unset for unscheduled pods)
+    pub node_name: Option<String>,
+    /// Zone of that node (`topology.kubernetes.io/zone`)
+    pub zone: Option<String>,
+    /// Services and Ingresses routing to the pod; `None` when nothing does
+    pub blast_radius: Option<BlastRadius>,
     pub since: DateTime<Utc>,
     pub duration_minutes: i64,
+    /// Images of containers stuck in ErrImagePull/ImagePullBackOff/InvalidImageName
+    pub image_pull_failures: Vec<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FailedPodInfo {
     pub namespace: String,
     pub pod: String,
+    /// Pod labels selected by `ISSUE_LABELS` (e.g. team, app) for routing
+    pub labels: BTreeMap<String, String>,
+    /// Node the pod runs on (unset for unscheduled pods)
+    pub node_name: Option<String>,
+    /// Zone of that node (`topology.kubernetes.io/zone`)
+    pub zone: Option<String>,
+    /// Services and Ingresses routing to the pod; `None` when nothing does
+    pub blast_radius: Option<BlastRadius>,
     pub since: DateTime<Utc>,
     pub duration_minutes: i64,
     pub reason: Option<String>,
     pub message: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct UnreadyPodInfo {
     pub namespace: String,
     pub pod: String,
+    /// Pod labels selected by `ISSUE_LABELS` (e.g. team, app) for routing
+    pub labels: BTreeMap<String, String>,
+    /// Node the pod runs on (unset for unscheduled pods)
+    pub node_name: Option<String>,
+    /// Zone of that node (`topology.kubernetes.io/zone`)
+    pub zone: Option<String>,
+    /// Services and Ingresses routing to the pod; `None` when nothing does
+    pub blast_radius: Option<BlastRadius>,
     pub since: DateTime<Utc>,
     pub duration_minutes: i64,
     pub failed_conditions: Vec<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct OomKilledInfo {
     pub namespace: String,
     pub pod: String,
+    /// Pod labels selected by `ISSUE_LABELS` (e.g. team, app) for routing
+    pub labels: BTreeMap<String, String>,
+    /// Node the pod runs on (unset for unscheduled pods)
+    pub node_name: Option<String>,
+    /// Zone of that node (`topology.kubernetes.io/zone`)
+    pub zone: Option<String>,
+    /// Services and Ingresses routing to the pod; `None` when nothing does
+    pub blast_radius: Option<BlastRadius>,
     pub container: String,
     pub last_oom_time: Option<DateTime<Utc>>,
     pub restart_count: i32,
+    /// Container image as written in the pod spec (name:tag)
+    pub image: Option<String>,
+    /// Sanitized tail of the previous container instance's log (`CRASH_LOG_LINES`)
+    pub log_tail: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ProblematicNodeInfo {
     pub name: String,
+    /// Operating system (`kubernetes.io/os`), e.g. `linux` or `windows`
+    pub os: String,
     pub conditions: Vec<String>,
     pub since: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone)]
+/// How the API server answered the reporter's own calls during one run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ApiHealthInfo {
+    pub requests: usize,
+    /// 5xx responses and requests that got no response at all
+    pub errors: usize,
+    /// 429 Too Many Requests responses
+    pub throttled: usize,
+    /// Slowest calls, slowest first
+    pub slowest: Vec<ApiCallInfo>,
+}
+
+impl ApiHealthInfo {
+    pub fn error_pct(&self) -> f64 {
+        if self.requests == 0 {
+            return 0.0;
+        }
+        self.errors as f64 / self.requests as f64 * 100.0
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiCallInfo {
+    pub method: String,
+    pub path: String,
+    /// `None` when no response arrived (connection error, timeout)
+    pub status: Option<u16>,
+    pub duration_ms: u64,
+}
+
+/// Requests the reporter made with one verb to one resource (and namespace) in a run
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ApiAuditEntry {
+    /// Kubernetes verb (`get`, `list`, `watch`, `create`, ...)
+    pub verb: String,
+    /// `pods`, `batch/jobs`, `nodes/proxy/stats/summary`, or the path of non-resource URLs
+    pub resource: String,
+    pub namespace: Option<String>,
+    pub requests: usize,
+    /// Refused by the read-only guard without reaching the API server
+    pub denied: usize,
+}
+
+/// Change freeze in effect during the run
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ChangeFreezeInfo {
+    /// Where the flag was read, e.g. `ConfigMap ops/change-freeze`
+    pub source: String,
+    /// End of the freeze window; `None` when the flag is open-ended
+    pub until: Option<DateTime<Utc>>,
+    /// Fingerprints of the findings first seen during the freeze, escalated to critical
+    pub escalated: BTreeSet<String>,
+}
+
+/// The reporter's own footprint in a run, for sizing its CronJob
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RunUsageInfo {
+    /// Peak resident memory of the process; `None` where procfs is unavailable
+    pub peak_rss_bytes: Option<u64>,
+    /// Container memory limit; `None` when unlimited or unknown
+    pub memory_limit_bytes: Option<u64>,
+    /// Objects the namespace analyzers listed, by resource (`pods`, `events`, ...)
+    pub listed_objects: BTreeMap<String, usize>,
+}
+
+impl RunUsageInfo {
+    /// Peak resident memory as a percentage of the container's memory limit
+    pub fn memory_limit_pct(&self) -> Option<f64> {
+        match (self.peak_rss_bytes, self.memory_limit_bytes) {
+            (Some(peak), Some(limit)) if limit > 0 => Some(peak as f64 / limit as f64 * 100.0),
+            _ => None,
+        }
+    }
+}
+
+/// How long a finding (by fingerprint) has been reported without interruption
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IssueHistory {
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    /// Runs the finding was present in
+    pub occurrences: u32,
+}
+
+/// Finding that is back in the report because its snooze ran out
+#[derive(Debug, Clone, Serialize)]
+pub struct PreviouslySnoozedInfo {
+    /// `section/namespace/name`, as used by the `snooze` command
+    pub fingerprint: String,
+    pub snoozed_until: DateTime<Utc>,
+    pub reason: Option<String>,
+}
+
+/// Node missing a healthy kube-proxy/CNI agent pod (critical)
+#[derive(Debug, Clone, Serialize)]
+pub struct NetworkAgentIssueInfo {
+    pub node: String,
+    /// DaemonSet as `namespace/name`
+    pub daemonset: String,
+    pub issue: NetworkAgentIssue,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum NetworkAgentIssue {
+    Missing,
+    Unhealthy { pod: String, phase: String },
+}
+
+/// OLM-managed operator failing to install, upgrade or run (critical)
+#[derive(Debug, Clone, Serialize)]
+pub struct OperatorIssueInfo {
+    pub namespace: String,
+    /// Subscription name, or the package from the Deployment's `operators.coreos.com/*` label
+    pub operator: String,
+    pub issue: OperatorIssue,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OperatorIssue {
+    /// Subscription condition such as `InstallPlanFailed` or `ResolutionFailed` is True
+    Subscription { condition: String, message: Option<String> },
+    /// Operator Deployment with fewer available replicas than desired
+    DeploymentUnavailable { deployment: String, available: i32, desired: i32 },
+}
+
+/// Resource with a large or fast-growing number of objects stored in etcd
+#[derive(Debug, Clone, Serialize)]
+pub struct ObjectCountInfo {
+    /// Resource as named by the API server metrics, e.g. `events` or `leases.coordination.k8s.io`
+    pub resource: String,
+    pub count: u64,
+    /// Count at the previous run, when known from the state file
+    pub previous: Option<u64>,
+}
+
+/// Node that went NotReady repeatedly within the flap window
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeFlapInfo {
+    pub name: String,
+    /// NotReady transitions within the window
+    pub not_ready_transitions: i64,
+    pub currently_ready: bool,
+    pub window_minutes: i64,
+}
+
+/// Node filesystem approaching the kubelet's image GC or eviction threshold
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeDiskForecastInfo {
+    pub name: String,
+    /// `imagefs` (container images) or `nodefs` (kubelet root, logs, emptyDirs; also
+    /// images when the runtime has no separate filesystem)
+    pub filesystem: String,
+    pub used_pct: f64,
+    pub used_bytes: u64,
+    pub capacity_bytes: u64,
+    /// Usage at which the kubelet starts garbage-collecting unused images
+    pub image_gc_pct: Option<f64>,
+    /// Usage at which the kubelet sets DiskPressure and starts evicting pods
+    pub eviction_pct: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct NodeUtilizationInfo {
     pub name: String,
+    /// Operating system (`kubernetes.io/os`), e.g. `linux` or `windows`
+    pub os: String,
     pub cpu_pct: Option<f64>,
     pub memory_pct: Option<f64>,
+    /// Current usage; `None` when the node has no metrics sample
+    pub cpu_millicores: Option<i64>,
+    pub memory_bytes: Option<i64>,
     pub pods_count: i32,
     pub pods_capacity: i32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct VolumeIssueInfo {
     pub namespace: String,
     pub pod: String,
@@ -105,13 +1083,36 @@ pub struct VolumeIssueInfo {
     pub message: String,
 }
 
-#[derive(Debug, Clone)]
+/// Pending pod that cannot be scheduled because of its PersistentVolumeClaims
+#[derive(Debug, Clone, Serialize)]
+pub struct VolumeSchedulingInfo {
+    pub namespace: String,
+    pub pod: String,
+    pub claims: Vec<String>,
+    pub reason: VolumeSchedulingReason,
+    pub since: DateTime<Utc>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VolumeSchedulingReason {
+    /// A bound volume's node affinity excludes every node the pod could run on
+    NodeAffinityConflict,
+    /// WaitForFirstConsumer claims could not be bound on any candidate node
+    NoMatchingVolume,
+    /// A node was selected for a WaitForFirstConsumer claim but provisioning never completed
+    ProvisioningStuck,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum VolumeIssueType {
     HighUsage(f64), // percentage
     MountFailure,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FailedJobInfo {
     pub namespace: String,
     pub job: String,
@@ -120,7 +1121,65 @@ pub struct FailedJobInfo {
     pub reason: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+pub struct JobBackoffRiskInfo {
+    pub namespace: String,
+    pub job: String,
+    pub failed: i32,
+    pub backoff_limit: i32,
+    pub active: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CronJobConcurrencyInfo {
+    pub namespace: String,
+    pub cronjob: String,
+    pub concurrency_policy: String,
+    pub active_jobs: usize,
+    pub oldest_active_since: Option<DateTime<Utc>>,
+    /// Schedule slot that came due while a Forbid/Replace run was still active
+    pub next_run_due: Option<DateTime<Utc>>,
+}
+
+/// Finished objects piling up in a namespace
+#[derive(Debug, Clone, Serialize)]
+pub struct FinishedObjectsInfo {
+    pub namespace: String,
+    /// Completed Jobs without `ttlSecondsAfterFinished`
+    pub completed_jobs: usize,
+    /// Failed Jobs without `ttlSecondsAfterFinished`
+    pub failed_jobs: usize,
+    pub succeeded_pods: usize,
+}
+
+/// Namespace without a ResourceQuota or LimitRange
+#[derive(Debug, Clone, Serialize)]
+pub struct NamespaceQuotaInfo {
+    pub namespace: String,
+    pub has_resource_quota: bool,
+    pub has_limit_range: bool,
+}
+
+/// Container whose requests/limits break or nearly break a LimitRange constraint
+#[derive(Debug, Clone, Serialize)]
+pub struct LimitRangeConflictInfo {
+    pub namespace: String,
+    /// Owning workload as `Kind/name`, or `Pod/name` for bare pods
+    pub workload: String,
+    pub container: String,
+    pub limit_range: String,
+    pub resource: String,
+    /// `max limit`, `max request`, `min request` or `maxLimitRequestRatio`
+    pub constraint: String,
+    /// The container's value as written in the pod spec (the ratio for `maxLimitRequestRatio`)
+    pub value: String,
+    /// The LimitRange's value
+    pub bound: String,
+    /// Outside the constraint (new pods are rejected) rather than at its edge
+    pub violated: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct MissedCronJobInfo {
     pub namespace: String,
     pub cronjob: String,
@@ -128,6 +1187,94 @@ pub struct MissedCronJobInfo {
     pub missed_runs: i32,
 }
 
+/// Namespace, object name (and selected pod labels) a finding belongs to, for
+/// ownership routing and suppression matching
+pub trait Scoped {
+    fn namespace(&self) -> &str;
+    /// Name of the pod, workload, job, ... the finding is about
+    fn object_name(&self) -> &str;
+    fn labels(&self) -> Option<&BTreeMap<String, String>> {
+        None
+    }
+}
+
+macro_rules! impl_scoped {
+    ($($ty:ty => $name:ident),* $(,)?) => {
+        $(impl Scoped for $ty {
+            fn namespace(&self) -> &str {
+                &self.namespace
+            }
+            fn object_name(&self) -> &str {
+                &self.$name
+            }
+        })*
+    };
+    (labels: $($ty:ty => $name:ident),* $(,)?) => {
+        $(impl Scoped for $ty {
+            fn namespace(&self) -> &str {
+                &self.namespace
+            }
+            fn object_name(&self) -> &str {
+                &self.$name
+            }
+            fn labels(&self) -> Option<&BTreeMap<String, String>> {
+                Some(&self.labels)
+            }
+        })*
+    };
+}
+
+impl_scoped!(labels:
+    HeavyUsagePod => pod, RestartEventInfo => pod, PendingPodInfo => pod, FailedPodInfo => pod,
+    UnreadyPodInfo => pod, OomKilledInfo => pod,
+);
+impl_scoped!(
+    StaleMetricsInfo => pod, RightsizingRecommendation => workload,
+    ResourceHygieneInfo => workload, PodSecurityViolationInfo => workload,
+    LimitRangeConflictInfo => workload, StuckTerminatingInfo => object,
+    CustomResourceConditionInfo => resource, PodChurnInfo => workload, MissingConfigRefInfo => pod,
+    ReadinessFlapInfo => pod, ServiceEndpointChurnInfo => service, DualStackIssueInfo => object,
+    VolumeIssueInfo => pod,
+    VolumeSchedulingInfo => pod, FailedJobInfo => job, JobBackoffRiskInfo => job,
+    CronJobConcurrencyInfo => cronjob, MissedCronJobInfo => cronjob,
+);
+
+// Namespace-level findings: the namespace is the object
+impl Scoped for FinishedObjectsInfo {
+    fn namespace(&self) -> &str {
+        &self.namespace
+    }
+    fn object_name(&self) -> &str {
+        &self.namespace
+    }
+}
+
+impl Scoped for EventFloodInfo {
+    fn namespace(&self) -> &str {
+        &self.namespace
+    }
+    fn object_name(&self) -> &str {
+        &self.namespace
+    }
+}
+
+impl Scoped for NamespaceQuotaInfo {
+    fn namespace(&self) -> &str {
+        &self.namespace
+    }
+    fn object_name(&self) -> &str {
+        &self.namespace
+    }
+}
+
+/// Keys of the report sections, as accepted by `SECTION_ORDER`
+pub const REPORT_SECTIONS: &[&str] = &[
+    "usage", "restarts", "pending", "failed", "unready", "oom", "nodes", "node_flapping", "node_utilization",
+    "volumes", "failed_jobs", "missed_cronjobs", "readiness_flapping", "endpoint_churn", "dual_stack",
+    "missing_config", "volume_scheduling", "network_agents", "operators", "node_disk", "object_counts", "job_backoff", "cronjob_concurrency",
+    "pod_churn", "event_flood", "stuck_terminating", "custom_resources", "stale_metrics", "finished_objects", "resource_hygiene", "pod_security", "namespace_quotas", "limit_range_conflicts", "recommendations", "owners", "previously_snoozed", "chronic", "resolved", "api_health",
+];
+
 #[derive(Serialize)]
 pub struct SlackPayload {
     #[serde(skip_serializing_if = "Option::is_none")]
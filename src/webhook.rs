@@ -0,0 +1,178 @@
+use anyhow::{anyhow, Context, Result};
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::timeout;
+
+use crate::delivery::{PayloadSigner, SIGNATURE_HEADER, SIGNATURE_TIMESTAMP_HEADER};
+use crate::redaction::redact_json;
+use crate::report::HealthReport;
+
+/// Bumped when fields are removed or change meaning; new fields do not bump it
+pub const REPORT_DOCUMENT_VERSION: u32 = 1;
+
+/// The whole report as JSON for the generic webhook: every section as a list of
+/// findings under its `SECTION_ORDER` key (empty lists included, so receivers see a
+/// stable shape), the lifecycle of the current findings and what cleared since the last
+/// run. Redaction patterns apply to every string.
+pub fn report_document(report: &HealthReport) -> serde_json::Value {
+    let pods = &report.pod_metrics;
+    let jobs = &report.job_metrics;
+    let nodes = &report.cluster_metrics;
+    let summary = report.summary();
+    let fingerprints = report.fingerprints();
+    let issue_history: serde_json::Map<String, serde_json::Value> = fingerprints
+        .iter()
+        .filter_map(|f| report.issue_history.get(f).map(|h| (f.clone(), json!(h))))
+        .collect();
+    let sections: serde_json::Map<String, serde_json::Value> = [
+        ("usage", json!(pods.heavy_usage)),
+        ("restarts", json!(pods.restarts)),
+        ("pending", json!(pods.pending)),
+        ("failed", json!(pods.failed)),
+        ("unready", json!(pods.unready)),
+        ("oom", json!(pods.oom_killed)),
+        ("nodes", json!(nodes.problematic_nodes)),
+        ("node_flapping", json!(nodes.flapping_nodes)),
+        ("node_utilization", json!(nodes.high_utilization_nodes)),
+        ("volumes", json!(report.volume_metrics.volume_issues)),
+        ("failed_jobs", json!(jobs.failed_jobs)),
+        ("missed_cronjobs", json!(jobs.missed_cronjobs)),
+        ("readiness_flapping", json!(pods.readiness_flapping)),
+        ("endpoint_churn", json!(pods.endpoint_churn)),
+        ("dual_stack", json!(pods.dual_stack)),
+        ("missing_config", json!(pods.missing_config_refs)),
+        ("volume_scheduling", json!(pods.volume_scheduling)),
+        ("network_agents", json!(nodes.network_agent_issues)),
+        ("operators", json!(nodes.operator_issues)),
+        ("node_disk", json!(nodes.node_disk_forecast)),
+        ("object_counts", json!(nodes.object_count_warnings)),
+        ("job_backoff", json!(jobs.backoff_risk)),
+        ("cronjob_concurrency", json!(jobs.cronjob_concurrency)),
+        ("pod_churn", json!(pods.churn)),
+        ("event_flood", json!(pods.event_flood)),
+        ("stuck_terminating", json!(pods.stuck_terminating)),
+        ("custom_resources", json!(pods.custom_resource_conditions)),
+        ("stale_metrics", json!(pods.stale_metrics)),
+        ("finished_objects", json!(jobs.finished_objects)),
+        ("resource_hygiene", json!(pods.resource_hygiene)),
+        ("pod_security", json!(pods.pod_security)),
+        ("namespace_quotas", json!(pods.namespace_quotas)),
+        ("limit_range_conflicts", json!(pods.limit_range_conflicts)),
+        ("recommendations", json!(report.recommendations)),
+        ("previously_snoozed", json!(report.previously_snoozed)),
+    ]
+    .into_iter()
+    .map(|(key, findings)| (key.to_string(), findings))
+    .collect();
+
+    let mut document = json!({
+        "version": REPORT_DOCUMENT_VERSION,
+        "cluster": report.config.cluster_name,
+        "datacenter": report.config.datacenter_name,
+        "run_id": report.run_id,
+        "generated_at": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        "has_issues": summary.has_issues(),
+        "total_issues": summary.total_issues(),
+        "severity_counts": summary.severity_counts,
+        "suppressed_count": report.suppressed_count,
+        "sections": sections,
+        "fingerprints": fingerprints,
+        "issue_history": issue_history,
+        "resolved": report.resolved,
+        "api_health": report.api_health,
+        "api_audit": report.api_audit,
+        "collection_warnings": report.collection_warnings,
+        "notes": report.notes,
+        "missing_namespaces": report.missing_namespaces,
+        "change_freeze": report.change_freeze,
+        "run_usage": report.run_usage,
+    });
+    redact_json(&mut document, &report.config.redaction_patterns);
+    document
+}
+
+/// POST the document with the configured extra headers (and the signature headers when
+/// signing is on); fails unless the endpoint accepts it within `deadline`
+pub async fn send_report_document(
+    client: &reqwest::Client,
+    url: &str,
+    headers: &[(String, String)],
+    signer: Option<&PayloadSigner>,
+    document: &serde_json::Value,
+    deadline: Duration,
+) -> Result<()> {
+    let body = serde_json::to_vec(document)?;
+    let mut request = client.post(url).header("content-type", "application/json");
+    for (name, value) in headers {
+        request = request.header(name.as_str(), value.as_str());
+    }
+    if let Some(signer) = signer {
+        let timestamp = chrono::Utc::now().timestamp();
+        request = request
+            .header(SIGNATURE_TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, signer.sign(&body, timestamp));
+    }
+    let response = timeout(deadline, request.body(body).send())
+        .await
+        .map_err(|_| anyhow!("Generic webhook timed out after {:?}", deadline))?
+        .context("Failed to send generic webhook request")?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!("Generic webhook returned {}: {}", status, body));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Config, OomKilledInfo, VolumeIssueInfo, VolumeIssueType};
+    use regex::Regex;
+
+    #[test]
+    fn test_report_document() {
+        let config = Config {
+            cluster_name: Some("prod".to_string()),
+            redaction_patterns: vec![Regex::new(r"token=\S+").unwrap()],
+            ..Default::default()
+        };
+        let mut report = HealthReport::new(config).with_run_id("run-1".to_string());
+        report.pod_metrics.oom_killed.push(OomKilledInfo {
+            namespace: "payments".to_string(),
+            pod: "api-0".to_string(),
+            labels: [("team".to_string(), "payments".to_string())].into(),
+            node_name: Some("node-a".to_string()),
+            zone: None,
+            blast_radius: None,
+            container: "app".to_string(),
+            last_oom_time: None,
+            restart_count: 3,
+            image: None,
+            log_tail: Some("fatal: token=abc123 rejected".to_string()),
+        });
+        report.volume_metrics.volume_issues.push(VolumeIssueInfo {
+            namespace: "payments".to_string(),
+            pod: "db-0".to_string(),
+            volume_name: "data".to_string(),
+            issue_type: VolumeIssueType::HighUsage(92.5),
+            message: "Volume usage at 92.5%".to_string(),
+        });
+
+        let document = report_document(&report);
+        assert_eq!(document["version"], 1);
+        assert_eq!(document["cluster"], "prod");
+        assert_eq!(document["run_id"], "run-1");
+        assert_eq!(document["total_issues"], 2);
+        assert_eq!(document["severity_counts"], json!({ "info": 0, "warning": 1, "critical": 1 }));
+        let oom = &document["sections"]["oom"][0];
+        assert_eq!(oom["pod"], "api-0");
+        assert_eq!(oom["labels"]["team"], "payments");
+        assert_eq!(oom["restart_count"], 3);
+        assert_eq!(oom["log_tail"], "fatal: [REDACTED] rejected");
+        assert_eq!(document["sections"]["volumes"][0]["issue_type"], json!({ "high_usage": 92.5 }));
+        // Empty sections are present too
+        assert_eq!(document["sections"]["failed_jobs"], json!([]));
+        assert_eq!(document["fingerprints"], json!(["oom/payments/api-0", "volumes/payments/db-0"]));
+    }
+}
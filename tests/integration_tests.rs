@@ -1,9 +1,10 @@
 use kube_health_reporter::{
     parse_cpu_to_millicores, parse_memory_to_bytes, compute_utilization_percentages,
     any_exceeds, build_slack_payload, load_config_with_env, MockEnvironment, PodUsageTotals, PodRequestTotals,
-    HeavyUsagePod, RestartEventInfo, PendingPodInfo, Config
+    HeavyUsagePod, RestartEventInfo, RestartCause, PendingPodInfo, Config
 };
-use kube_health_reporter::report::{HealthReport, ReportSummary};
+use kube_health_reporter::report::{new_run_id, HealthReport, ReportSummary};
+use kube_health_reporter::report::severity::SeverityCounts;
 
 #[test]
 fn test_cpu_parsing_edge_cases() {
@@ -46,6 +47,7 @@ fn test_utilization_calculations_edge_cases() {
     let zero_usage = PodUsageTotals {
         cpu_millicores: 0,
         memory_bytes: 0,
+        ..Default::default()
     };
     
     let requests = PodRequestTotals {
@@ -61,6 +63,7 @@ fn test_utilization_calculations_edge_cases() {
     let high_usage = PodUsageTotals {
         cpu_millicores: 2000, // 200% of request
         memory_bytes: 2 * 1024 * 1024 * 1024, // 200% of request
+        ..Default::default()
     };
     
     let (cpu_pct, mem_pct) = compute_utilization_percentages(&high_usage, &requests);
@@ -130,26 +133,37 @@ fn test_slack_payload_formatting() {
         cluster_name: Some("production-cluster".to_string()),
         datacenter_name: Some("eu-west-1".to_string()),
         fail_if_no_metrics: false,
+        ..Default::default()
     };
     
     // Test with multiple items of each type
     let heavy_usage = vec![
         HeavyUsagePod {
+            labels: Default::default(),
             namespace: "prod".to_string(),
             pod: "api-server-1".to_string(),
             cpu_pct: Some(95.5),
             mem_pct: Some(87.2),
+            cpu_millicores: 900,
+            memory_bytes: 512 * 1024 * 1024,
         },
         HeavyUsagePod {
+            labels: Default::default(),
             namespace: "staging".to_string(),
             pod: "worker-2".to_string(),
             cpu_pct: None, // Only memory exceeds
             mem_pct: Some(92.8),
+            cpu_millicores: 900,
+            memory_bytes: 512 * 1024 * 1024,
         },
     ];
     
     let restarts = vec![
         RestartEventInfo {
+            labels: Default::default(),
+            node_name: None,
+            zone: None,
+            blast_radius: None,
             namespace: "prod".to_string(),
             pod: "database-1".to_string(),
             container: "postgres".to_string(),
@@ -157,19 +171,34 @@ fn test_slack_payload_formatting() {
             reason: Some("OOMKilled".to_string()),
             message: Some("Container exceeded memory limit".to_string()),
             exit_code: Some(137),
+            image: None,
+            log_tail: None,
         },
     ];
     
     let pendings = vec![
         PendingPodInfo {
+            labels: Default::default(),
+            node_name: None,
+            zone: None,
+            blast_radius: None,
             namespace: "staging".to_string(),
             pod: "new-deployment".to_string(),
             since: chrono::Utc::now() - chrono::Duration::minutes(15),
             duration_minutes: 15,
+            image_pull_failures: Vec::new(),
         },
     ];
     
-    let payload = build_slack_payload(&config, &heavy_usage, &restarts, &pendings, &[], &[], &[], &[], &[], &[], &[], &[]);
+    let mut report = HealthReport::new(config);
+    
+    report.pod_metrics.heavy_usage = heavy_usage;
+    
+    report.pod_metrics.restarts = restarts;
+    
+    report.pod_metrics.pending = pendings;
+    
+    let payload = build_slack_payload(&report);
     
     // Verify structure - now has 13 blocks (header + config + 11 metric sections)
     assert_eq!(payload.blocks.len(), 13);
@@ -201,7 +230,8 @@ fn test_slack_payload_formatting() {
     assert!(restart_text.contains("prod/database-1"));
     assert!(restart_text.contains("[postgres]"));
     assert!(restart_text.contains("OOMKilled"));
-    assert!(restart_text.contains("(exit 137)"));
+    assert!(restart_text.contains("(exit 137: OOM killed)"));
+    assert!(restart_text.contains("Causes: 1 OOM killed"));
     assert!(restart_text.contains("Container exceeded memory limit"));
     
     // Check pending section
@@ -249,15 +279,32 @@ fn test_report_summary_has_issues() {
     let empty_summary = ReportSummary {
         heavy_usage_count: 0,
         restart_count: 0,
+        restart_causes: Vec::new(),
         pending_count: 0,
         failed_pod_count: 0,
         unready_count: 0,
+        readiness_flap_count: 0,
+        endpoint_churn_count: 0,
+        dual_stack_count: 0,
         oom_killed_count: 0,
+        volume_scheduling_count: 0,
+        missing_config_ref_count: 0,
+        churn_count: 0,
+        event_flood_count: 0,
+        stuck_terminating_count: 0,
+        custom_resource_condition_count: 0,
         failed_job_count: 0,
+        job_backoff_risk_count: 0,
         missed_cronjob_count: 0,
+        cronjob_concurrency_count: 0,
         volume_issue_count: 0,
         problematic_node_count: 0,
+        flapping_node_count: 0,
         high_util_node_count: 0,
+        network_agent_issue_count: 0,
+        operator_issue_count: 0,
+        node_disk_forecast_count: 0,
+        severity_counts: SeverityCounts::default(),
     };
     
     assert_eq!(empty_summary.total_issues(), 0);
@@ -267,15 +314,32 @@ fn test_report_summary_has_issues() {
     let summary_with_issues = ReportSummary {
         heavy_usage_count: 2,
         restart_count: 1,
+        restart_causes: Vec::new(),
         pending_count: 0,
         failed_pod_count: 1,
         unready_count: 0,
+        readiness_flap_count: 0,
+        endpoint_churn_count: 0,
+        dual_stack_count: 0,
         oom_killed_count: 1,
+        volume_scheduling_count: 0,
+        missing_config_ref_count: 0,
+        churn_count: 0,
+        event_flood_count: 0,
+        stuck_terminating_count: 0,
+        custom_resource_condition_count: 0,
         failed_job_count: 0,
+        job_backoff_risk_count: 0,
         missed_cronjob_count: 0,
+        cronjob_concurrency_count: 0,
         volume_issue_count: 0,
         problematic_node_count: 1,
+        flapping_node_count: 0,
         high_util_node_count: 0,
+        network_agent_issue_count: 0,
+        operator_issue_count: 0,
+        node_disk_forecast_count: 0,
+        severity_counts: SeverityCounts::default(),
     };
     
     assert_eq!(summary_with_issues.total_issues(), 6);
@@ -285,15 +349,32 @@ fn test_report_summary_has_issues() {
     let single_issue_summary = ReportSummary {
         heavy_usage_count: 0,
         restart_count: 0,
+        restart_causes: Vec::new(),
         pending_count: 0,
         failed_pod_count: 0,
         unready_count: 0,
+        readiness_flap_count: 0,
+        endpoint_churn_count: 0,
+        dual_stack_count: 0,
         oom_killed_count: 0,
+        volume_scheduling_count: 0,
+        missing_config_ref_count: 0,
+        churn_count: 0,
+        event_flood_count: 0,
+        stuck_terminating_count: 0,
+        custom_resource_condition_count: 0,
         failed_job_count: 0,
+        job_backoff_risk_count: 0,
         missed_cronjob_count: 0,
+        cronjob_concurrency_count: 0,
         volume_issue_count: 1,
         problematic_node_count: 0,
+        flapping_node_count: 0,
         high_util_node_count: 0,
+        network_agent_issue_count: 0,
+        operator_issue_count: 0,
+        node_disk_forecast_count: 0,
+        severity_counts: SeverityCounts::default(),
     };
     
     assert_eq!(single_issue_summary.total_issues(), 1);
@@ -311,6 +392,7 @@ fn test_health_report_has_issues() {
         cluster_name: None,
         datacenter_name: None,
         fail_if_no_metrics: true,
+        ..Default::default()
     };
     
     // Test empty report
@@ -320,10 +402,13 @@ fn test_health_report_has_issues() {
     // Test report with heavy usage pod
     let mut report_with_issues = HealthReport::new(config.clone());
     report_with_issues.pod_metrics.heavy_usage.push(HeavyUsagePod {
+        labels: Default::default(),
         namespace: "test".to_string(),
         pod: "heavy-pod".to_string(),
         cpu_pct: Some(90.0),
         mem_pct: Some(95.0),
+        cpu_millicores: 900,
+        memory_bytes: 512 * 1024 * 1024,
     });
     
     assert!(report_with_issues.has_issues());
@@ -331,3 +416,123 @@ fn test_health_report_has_issues() {
     assert_eq!(summary.total_issues(), 1);
     assert!(summary.has_issues());
 }
+
+#[test]
+fn test_run_id_is_uuid_v4() {
+    let run_id = new_run_id();
+    let uuid = uuid::Uuid::parse_str(&run_id).unwrap();
+    assert_eq!(uuid.get_version(), Some(uuid::Version::Random));
+    assert_eq!(uuid.get_variant(), uuid::Variant::RFC4122);
+    assert_ne!(run_id, new_run_id());
+
+    let report = HealthReport::new(Config::default()).with_run_id(run_id.clone());
+    assert_eq!(report.run_id, run_id);
+}
+
+#[test]
+fn test_cluster_metrics_annotate_pod_zones() {
+    let mut report = HealthReport::new(Config::default());
+    report.pod_metrics.pending.push(PendingPodInfo {
+        labels: Default::default(),
+        node_name: Some("node-a".to_string()),
+        zone: None,
+        blast_radius: None,
+        namespace: "prod".to_string(),
+        pod: "api-0".to_string(),
+        since: chrono::Utc::now(),
+        duration_minutes: 12,
+        image_pull_failures: Vec::new(),
+    });
+
+    let mut cluster = HealthReport::new(Config::default()).cluster_metrics;
+    cluster.node_zones.insert("node-a".to_string(), "eu-west-1b".to_string());
+    report.set_cluster_metrics(cluster);
+
+    assert_eq!(report.pod_metrics.pending[0].zone.as_deref(), Some("eu-west-1b"));
+}
+
+#[test]
+fn test_report_scoped_to_namespace() {
+    let pending = |namespace: &str| PendingPodInfo {
+        labels: Default::default(),
+        node_name: None,
+        zone: None,
+        blast_radius: None,
+        namespace: namespace.to_string(),
+        pod: "pod-0".to_string(),
+        since: chrono::Utc::now(),
+        duration_minutes: 12,
+        image_pull_failures: Vec::new(),
+    };
+    let mut report = HealthReport::new(Config::default());
+    report.pod_metrics.pending.push(pending("payments"));
+    report.pod_metrics.pending.push(pending("search"));
+
+    let scoped = report.scoped_to(|f| f.namespace() == "payments");
+    assert_eq!(scoped.pod_metrics.pending.len(), 1);
+    assert_eq!(scoped.pod_metrics.pending[0].namespace, "payments");
+    assert_eq!(report.pod_metrics.pending.len(), 2);
+}
+
+#[test]
+fn test_report_sort_findings() {
+    let pending = |namespace: &str, pod: &str| PendingPodInfo {
+        labels: Default::default(),
+        node_name: None,
+        zone: None,
+        blast_radius: None,
+        namespace: namespace.to_string(),
+        pod: pod.to_string(),
+        since: chrono::Utc::now(),
+        duration_minutes: 12,
+        image_pull_failures: Vec::new(),
+    };
+    let mut report = HealthReport::new(Config::default());
+    report.pod_metrics.pending.push(pending("search", "api-0"));
+    report.pod_metrics.pending.push(pending("payments", "worker-1"));
+    report.pod_metrics.pending.push(pending("payments", "api-0"));
+
+    report.sort_findings();
+    let order: Vec<(&str, &str)> =
+        report.pod_metrics.pending.iter().map(|p| (p.namespace.as_str(), p.pod.as_str())).collect();
+    assert_eq!(order, vec![("payments", "api-0"), ("payments", "worker-1"), ("search", "api-0")]);
+}
+
+#[test]
+fn test_restart_cause_classification() {
+    assert_eq!(RestartCause::classify(Some(137), Some("OOMKilled")), RestartCause::OomKilled);
+    assert_eq!(RestartCause::classify(Some(137), Some("Error")), RestartCause::Killed);
+    assert_eq!(RestartCause::classify(Some(143), Some("Error")), RestartCause::Terminated);
+    assert_eq!(RestartCause::classify(Some(139), None), RestartCause::Segfault);
+    assert_eq!(RestartCause::classify(Some(1), Some("Error")), RestartCause::AppError);
+    assert_eq!(RestartCause::classify(Some(2), None), RestartCause::Other(2));
+    // Windows NTSTATUS codes
+    assert_eq!(RestartCause::classify(Some(0xC000_0005_u32 as i32), Some("Error")), RestartCause::Segfault);
+    assert_eq!(RestartCause::classify(Some(0xC000_013A_u32 as i32), None), RestartCause::Terminated);
+    assert_eq!(RestartCause::classify(None, Some("CrashLoopBackOff")), RestartCause::Unknown);
+
+    let restart = |exit_code: i32| RestartEventInfo {
+        labels: Default::default(),
+        node_name: None,
+        zone: None,
+        blast_radius: None,
+        namespace: "prod".to_string(),
+        pod: "api".to_string(),
+        container: "main".to_string(),
+        last_restart_time: None,
+        reason: Some("Error".to_string()),
+        message: None,
+        exit_code: Some(exit_code),
+        image: None,
+        log_tail: None,
+    };
+    let config = Config {
+        namespaces: vec!["prod".to_string()],
+        ..Default::default()
+    };
+    let mut report = HealthReport::new(config);
+    report.pod_metrics.restarts = vec![restart(1), restart(137), restart(1)];
+
+    let summary = report.summary();
+    assert_eq!(summary.restart_causes, vec![(RestartCause::AppError, 2), (RestartCause::Killed, 1)]);
+}
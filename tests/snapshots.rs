@@ -0,0 +1,432 @@
+//! Golden-file tests for everything the reporter renders. The fixture report has a
+//! finding of every kind; the outputs live in `tests/snapshots/` and are reviewed with
+//! `cargo insta review` when a formatting change is intended.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use std::collections::BTreeMap;
+
+use kube_health_reporter::email::render_report_email;
+use kube_health_reporter::pagerduty::pagerduty_events;
+use kube_health_reporter::report::html::render_html;
+use kube_health_reporter::report::rollup::{build_rollup, render_rollup_html, render_rollup_markdown};
+use kube_health_reporter::state::RunRecord;
+use kube_health_reporter::webhook::report_document;
+use kube_health_reporter::*;
+
+/// Timestamps in the outputs depend on when the test runs; durations ("20m ago") do
+/// not, as the fixture is built relative to now
+macro_rules! assert_golden {
+    ($macro:ident!($name:expr, $value:expr)) => {
+        insta::with_settings!({
+            filters => vec![
+                (r"\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(\.\d+)?Z", "[timestamp]"),
+                (r"\d{4}-\d{2}-\d{2} \d{2}:\d{2} UTC", "[timestamp]"),
+            ],
+        }, {
+            insta::$macro!($name, $value);
+        })
+    };
+}
+
+fn labels(team: &str) -> BTreeMap<String, String> {
+    [("team".to_string(), team.to_string())].into()
+}
+
+/// Report with one finding per section, all in a handful of namespaces
+fn fixture_report(now: DateTime<Utc>) -> HealthReport {
+    let config = Config {
+        cluster_name: Some("prod-eu".to_string()),
+        namespaces: vec!["payments".to_string(), "batch".to_string()],
+        ..Default::default()
+    };
+    let mut report = HealthReport::new(config).with_run_id("0b7f3a5e-golden".to_string());
+    report.suppressed_count = 2;
+
+    let pods = &mut report.pod_metrics;
+    pods.heavy_usage.push(HeavyUsagePod {
+        namespace: "payments".to_string(),
+        pod: "api-7d9f-x2x4q".to_string(),
+        labels: labels("payments"),
+        cpu_pct: Some(96.5),
+        mem_pct: Some(71.0),
+        cpu_millicores: 1930,
+        memory_bytes: 1454 * 1024 * 1024,
+    });
+    pods.restarts.push(RestartEventInfo {
+        namespace: "payments".to_string(),
+        pod: "worker-0".to_string(),
+        labels: labels("payments"),
+        node_name: Some("node-a".to_string()),
+        zone: Some("eu-west-1a".to_string()),
+        blast_radius: None,
+        container: "worker".to_string(),
+        last_restart_time: Some(now - Duration::minutes(4)),
+        reason: Some("Error".to_string()),
+        message: None,
+        exit_code: Some(1),
+        image: Some("registry.example.com/worker:1.4.2".to_string()),
+        log_tail: Some("panic: connection refused".to_string()),
+    });
+    pods.pending.push(PendingPodInfo {
+        namespace: "payments".to_string(),
+        pod: "ledger-1".to_string(),
+        labels: labels("ledger"),
+        node_name: None,
+        zone: None,
+        blast_radius: None,
+        since: now - Duration::minutes(25),
+        duration_minutes: 25,
+        image_pull_failures: vec!["registry.example.com/ledger:missing".to_string()],
+    });
+    pods.failed.push(FailedPodInfo {
+        namespace: "batch".to_string(),
+        pod: "export-28471".to_string(),
+        labels: labels("data"),
+        node_name: Some("node-b".to_string()),
+        zone: Some("eu-west-1b".to_string()),
+        blast_radius: None,
+        since: now - Duration::minutes(40),
+        duration_minutes: 40,
+        reason: Some("Evicted".to_string()),
+        message: Some("The node was low on resource: ephemeral-storage.".to_string()),
+    });
+    pods.unready.push(UnreadyPodInfo {
+        namespace: "payments".to_string(),
+        pod: "gateway-5c6b-ttl2m".to_string(),
+        labels: labels("edge"),
+        node_name: Some("node-a".to_string()),
+        zone: Some("eu-west-1a".to_string()),
+        blast_radius: None,
+        since: now - Duration::minutes(12),
+        duration_minutes: 12,
+        failed_conditions: vec!["Ready".to_string(), "ContainersReady".to_string()],
+    });
+    pods.readiness_flapping.push(ReadinessFlapInfo {
+        namespace: "payments".to_string(),
+        pod: "gateway-5c6b-9kq7z".to_string(),
+        probe_failures: 14,
+        currently_ready: true,
+        last_transition: Some(now - Duration::minutes(3)),
+        window_minutes: 30,
+    });
+    pods.endpoint_churn.push(ServiceEndpointChurnInfo {
+        namespace: "payments".to_string(),
+        service: "gateway".to_string(),
+        backing_pods: 3,
+        changes: 22,
+        flapping_pods: vec!["gateway-5c6b-9kq7z".to_string()],
+        window_minutes: 30,
+    });
+    pods.dual_stack.push(DualStackIssueInfo {
+        namespace: "payments".to_string(),
+        object: "Service/gateway".to_string(),
+        missing_family: "IPv6".to_string(),
+        addresses: vec!["10.96.14.2".to_string()],
+        pods: 0,
+    });
+    pods.oom_killed.push(OomKilledInfo {
+        namespace: "payments".to_string(),
+        pod: "cache-0".to_string(),
+        labels: labels("payments"),
+        node_name: Some("node-c".to_string()),
+        zone: Some("eu-west-1c".to_string()),
+        blast_radius: None,
+        container: "redis".to_string(),
+        last_oom_time: Some(now - Duration::minutes(9)),
+        restart_count: 6,
+        image: Some("redis:7.2".to_string()),
+        log_tail: None,
+    });
+    pods.volume_scheduling.push(VolumeSchedulingInfo {
+        namespace: "batch".to_string(),
+        pod: "warehouse-0".to_string(),
+        claims: vec!["data-warehouse-0".to_string()],
+        reason: VolumeSchedulingReason::NodeAffinityConflict,
+        since: now - Duration::minutes(50),
+        message: Some("1 node(s) had volume node affinity conflict".to_string()),
+    });
+    pods.missing_config_refs.push(MissingConfigRefInfo {
+        namespace: "payments".to_string(),
+        pod: "api-7d9f-b8v6d".to_string(),
+        container: Some("api".to_string()),
+        kind: "Secret".to_string(),
+        name: "api-credentials".to_string(),
+        key: Some("token".to_string()),
+    });
+    pods.churn.push(PodChurnInfo {
+        namespace: "batch".to_string(),
+        kind: "Deployment".to_string(),
+        workload: "scraper".to_string(),
+        created: 48,
+        deleted: 45,
+        window_minutes: 60,
+    });
+    pods.event_flood.push(EventFloodInfo {
+        namespace: "batch".to_string(),
+        warnings: 612,
+        top_reasons: vec![("BackOff".to_string(), 401), ("FailedMount".to_string(), 150)],
+        window_minutes: 60,
+    });
+    pods.stuck_terminating.push(StuckTerminatingInfo {
+        namespace: "batch".to_string(),
+        object: "PersistentVolumeClaim/scratch-3".to_string(),
+        since: now - Duration::hours(3),
+        finalizers: vec!["kubernetes.io/pvc-protection".to_string()],
+        details: Vec::new(),
+    });
+    pods.custom_resource_conditions.push(CustomResourceConditionInfo {
+        namespace: "payments".to_string(),
+        resource: "Certificate/gateway-tls".to_string(),
+        condition: "Ready".to_string(),
+        expected: "True".to_string(),
+        actual: Some("False".to_string()),
+        reason: Some("Failed".to_string()),
+        message: Some("ACME challenge failed".to_string()),
+    });
+    pods.resource_hygiene.push(ResourceHygieneInfo {
+        namespace: "batch".to_string(),
+        workload: "Deployment/scraper".to_string(),
+        container: "scraper".to_string(),
+        issue: ResourceHygieneIssue::MemoryLimitUnset,
+    });
+    pods.resource_hygiene.push(ResourceHygieneInfo {
+        namespace: "payments".to_string(),
+        workload: "StatefulSet/cache".to_string(),
+        container: "redis".to_string(),
+        issue: ResourceHygieneIssue::HighLimitRatio { resource: "cpu".to_string(), ratio: 8.0 },
+    });
+    pods.pod_security.push(PodSecurityViolationInfo {
+        namespace: "batch".to_string(),
+        workload: "DaemonSet/log-shipper".to_string(),
+        level: PodSecurityLevel::Baseline,
+        violations: vec!["hostPath volumes".to_string(), "privileged (shipper)".to_string()],
+    });
+    pods.namespace_quotas.push(NamespaceQuotaInfo {
+        namespace: "batch".to_string(),
+        has_resource_quota: false,
+        has_limit_range: true,
+    });
+    pods.limit_range_conflicts.push(LimitRangeConflictInfo {
+        namespace: "payments".to_string(),
+        workload: "Deployment/api".to_string(),
+        container: "api".to_string(),
+        limit_range: "defaults".to_string(),
+        resource: "memory".to_string(),
+        constraint: "max limit".to_string(),
+        value: "2Gi".to_string(),
+        bound: "2Gi".to_string(),
+        violated: false,
+    });
+    pods.stale_metrics.push(StaleMetricsInfo {
+        namespace: "batch".to_string(),
+        pod: "scraper-6f7d-2mz8c".to_string(),
+        sampled_at: now - Duration::minutes(18),
+        age_minutes: 18,
+        window: Some("30s".to_string()),
+    });
+
+    let jobs = &mut report.job_metrics;
+    jobs.failed_jobs.push(FailedJobInfo {
+        namespace: "batch".to_string(),
+        job: "export-28471".to_string(),
+        failed_pods: 3,
+        last_failure_time: Some(now - Duration::minutes(35)),
+        reason: Some("BackoffLimitExceeded".to_string()),
+    });
+    jobs.backoff_risk.push(JobBackoffRiskInfo {
+        namespace: "batch".to_string(),
+        job: "reindex-28472".to_string(),
+        failed: 5,
+        backoff_limit: 6,
+        active: 1,
+    });
+    jobs.missed_cronjobs.push(MissedCronJobInfo {
+        namespace: "batch".to_string(),
+        cronjob: "nightly-report".to_string(),
+        last_schedule_time: now - Duration::hours(26),
+        missed_runs: 1,
+    });
+    jobs.cronjob_concurrency.push(CronJobConcurrencyInfo {
+        namespace: "batch".to_string(),
+        cronjob: "reindex".to_string(),
+        concurrency_policy: "Forbid".to_string(),
+        active_jobs: 1,
+        oldest_active_since: Some(now - Duration::minutes(95)),
+        next_run_due: Some(now - Duration::minutes(35)),
+    });
+    jobs.finished_objects.push(FinishedObjectsInfo {
+        namespace: "batch".to_string(),
+        completed_jobs: 310,
+        failed_jobs: 12,
+        succeeded_pods: 322,
+    });
+
+    report.volume_metrics.volume_issues.push(VolumeIssueInfo {
+        namespace: "payments".to_string(),
+        pod: "cache-0".to_string(),
+        volume_name: "data".to_string(),
+        issue_type: VolumeIssueType::HighUsage(92.5),
+        message: "Volume usage at 92.5%".to_string(),
+    });
+    report.volume_metrics.volume_issues.push(VolumeIssueInfo {
+        namespace: "batch".to_string(),
+        pod: "warehouse-0".to_string(),
+        volume_name: "data-warehouse-0".to_string(),
+        issue_type: VolumeIssueType::MountFailure,
+        message: "MountVolume.SetUp failed: timed out waiting for the condition".to_string(),
+    });
+
+    let cluster = &mut report.cluster_metrics;
+    cluster.problematic_nodes.push(ProblematicNodeInfo {
+        name: "node-d".to_string(),
+        os: "linux".to_string(),
+        conditions: vec!["MemoryPressure".to_string()],
+        since: now - Duration::minutes(15),
+    });
+    cluster.problematic_nodes.push(ProblematicNodeInfo {
+        name: "win-node-1".to_string(),
+        os: "windows".to_string(),
+        conditions: vec!["NotReady".to_string()],
+        since: now - Duration::minutes(7),
+    });
+    cluster.flapping_nodes.push(NodeFlapInfo {
+        name: "node-e".to_string(),
+        not_ready_transitions: 4,
+        currently_ready: true,
+        window_minutes: 60,
+    });
+    cluster.high_utilization_nodes.push(NodeUtilizationInfo {
+        name: "node-a".to_string(),
+        os: "linux".to_string(),
+        cpu_pct: Some(91.0),
+        memory_pct: Some(88.5),
+        cpu_millicores: Some(14_560),
+        memory_bytes: Some(56 * 1024 * 1024 * 1024),
+        pods_count: 104,
+        pods_capacity: 110,
+    });
+    cluster.network_agent_issues.push(NetworkAgentIssueInfo {
+        node: "node-f".to_string(),
+        daemonset: "kube-system/kube-proxy".to_string(),
+        issue: NetworkAgentIssue::Missing,
+    });
+    cluster.network_agent_issues.push(NetworkAgentIssueInfo {
+        node: "node-d".to_string(),
+        daemonset: "kube-system/cilium".to_string(),
+        issue: NetworkAgentIssue::Unhealthy { pod: "cilium-x8k2p".to_string(), phase: "Pending".to_string() },
+    });
+    cluster.operator_issues.push(OperatorIssueInfo {
+        namespace: "operators".to_string(),
+        operator: "strimzi-kafka-operator".to_string(),
+        issue: OperatorIssue::Subscription {
+            condition: "InstallPlanFailed".to_string(),
+            message: Some("api-server resource not found".to_string()),
+        },
+    });
+    cluster.operator_issues.push(OperatorIssueInfo {
+        namespace: "operators".to_string(),
+        operator: "etcd".to_string(),
+        issue: OperatorIssue::DeploymentUnavailable { deployment: "etcd-operator".to_string(), available: 0, desired: 1 },
+    });
+    cluster.node_disk_forecast.push(NodeDiskForecastInfo {
+        name: "node-b".to_string(),
+        filesystem: "imagefs".to_string(),
+        used_pct: 83.0,
+        used_bytes: 83 * 1024 * 1024 * 1024,
+        capacity_bytes: 100 * 1024 * 1024 * 1024,
+        image_gc_pct: Some(85.0),
+        eviction_pct: Some(90.0),
+    });
+    cluster.object_count_warnings.push(ObjectCountInfo {
+        resource: "events".to_string(),
+        count: 182_000,
+        previous: Some(120_000),
+    });
+
+    report.recommendations.push(RightsizingRecommendation {
+        namespace: "payments".to_string(),
+        kind: "Deployment".to_string(),
+        workload: "api".to_string(),
+        samples: 96,
+        current_cpu_request: Some(2000),
+        suggested_cpu_request: 450,
+        current_memory_request: Some(4 * 1024 * 1024 * 1024),
+        suggested_memory_request: 1536 * 1024 * 1024,
+        current_memory_limit: None,
+        suggested_memory_limit: 2 * 1024 * 1024 * 1024,
+    });
+    report.previously_snoozed.push(PreviouslySnoozedInfo {
+        fingerprint: "restarts/payments/worker-0".to_string(),
+        snoozed_until: now - Duration::hours(1),
+        reason: Some("waiting for upstream fix".to_string()),
+    });
+    report.issue_history.insert(
+        "oom/payments/cache-0".to_string(),
+        IssueHistory { first_seen: now - Duration::days(3), last_seen: now, occurrences: 72 },
+    );
+    report.resolved.insert(
+        "failed/batch/export-28390".to_string(),
+        IssueHistory { first_seen: now - Duration::hours(5), last_seen: now - Duration::hours(1), occurrences: 4 },
+    );
+    report.api_health = Some(ApiHealthInfo {
+        requests: 240,
+        errors: 12,
+        throttled: 3,
+        slowest: vec![ApiCallInfo {
+            method: "GET".to_string(),
+            path: "/api/v1/pods".to_string(),
+            status: Some(200),
+            duration_ms: 4200,
+        }],
+    });
+    report
+}
+
+#[test]
+fn test_slack_payload_snapshot() {
+    let report = fixture_report(Utc::now());
+    let payload = build_report_payload(&report, &report.summary());
+    assert_golden!(assert_json_snapshot!("slack_payload", payload));
+}
+
+#[test]
+fn test_email_snapshot() {
+    let report = fixture_report(Utc::now());
+    let message = render_report_email(&report, &report.summary());
+    assert_golden!(assert_snapshot!("email_subject", message.subject));
+    assert_golden!(assert_snapshot!("email_html", message.html));
+}
+
+#[test]
+fn test_report_document_snapshot() {
+    let report = fixture_report(Utc::now());
+    assert_golden!(assert_json_snapshot!("report_document", report_document(&report)));
+}
+
+#[test]
+fn test_html_report_snapshot() {
+    let report = fixture_report(Utc::now());
+    assert_golden!(assert_snapshot!("html_report", render_html(&report)));
+}
+
+#[test]
+fn test_pagerduty_events_snapshot() {
+    let report = fixture_report(Utc::now());
+    let sections = ["oom", "failed", "failed_jobs"].map(String::from);
+    let events = pagerduty_events(&report, "R0UT1NG", &sections);
+    assert_golden!(assert_json_snapshot!("pagerduty_events", events));
+}
+
+#[test]
+fn test_rollup_markdown_snapshot() {
+    let now = Utc.with_ymd_and_hms(2026, 3, 8, 12, 0, 0).unwrap();
+    let runs: Vec<RunRecord> = (0..4)
+        .map(|day| RunRecord {
+            timestamp: now - Duration::days(day),
+            fingerprints: fixture_report(now).fingerprints().into_iter().skip(day as usize).collect(),
+        })
+        .collect();
+    let rollup = build_rollup(&runs, now, 7);
+    insta::assert_snapshot!("rollup_markdown", render_rollup_markdown(&rollup, Some("prod-eu")));
+    insta::assert_snapshot!("rollup_html", render_rollup_html(&rollup, Some("prod-eu")));
+}